@@ -0,0 +1,271 @@
+//! Compatibility with tokio's I/O traits.
+//!
+//! Libraries built on tokio, such as `hyper`, `tonic`, or `tokio-tungstenite`, read and write
+//! through [`tokio::io::AsyncRead`] and [`tokio::io::AsyncWrite`] instead of the traits in
+//! [`async_std::io`]. The [`Compat`] adapter bridges the two in both directions:
+//!
+//! * Wrapping an async-std reader or writer, such as a [`TcpStream`], implements tokio's traits,
+//!   so it can be handed to a tokio-based library.
+//! * Wrapping a tokio reader or writer implements [`Read`], [`Write`], and [`BufRead`], so it can
+//!   be used with the rest of async-std.
+//!
+//! The extension traits add a `.compat()` method for readers and a `.compat_write()` method for
+//! writers that only implement the writing half.
+//!
+//! [`tokio::io::AsyncRead`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncRead.html
+//! [`tokio::io::AsyncWrite`]: https://docs.rs/tokio/1/tokio/io/trait.AsyncWrite.html
+//! [`async_std::io`]: ../io/index.html
+//! [`Compat`]: struct.Compat.html
+//! [`TcpStream`]: ../net/struct.TcpStream.html
+//! [`Read`]: ../io/trait.Read.html
+//! [`Write`]: ../io/trait.Write.html
+//! [`BufRead`]: ../io/trait.BufRead.html
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::compat::{ReadCompatExt, TokioReadCompatExt};
+//! use async_std::io::Cursor;
+//! use async_std::prelude::*;
+//! use tokio::io::AsyncReadExt;
+//!
+//! // An async-std reader used through tokio's traits.
+//! let mut reader = Cursor::new(b"hello".to_vec()).compat();
+//! let mut text = String::new();
+//! AsyncReadExt::read_to_string(&mut reader, &mut text).await?;
+//! assert_eq!(text, "hello");
+//!
+//! // A tokio reader used through async-std's traits.
+//! let (mut writer, reader) = tokio::io::duplex(64);
+//! tokio::io::AsyncWriteExt::write_all(&mut writer, b"world").await?;
+//! drop(writer);
+//!
+//! let mut text = String::new();
+//! reader.compat().read_to_string(&mut text).await?;
+//! assert_eq!(text, "world");
+//! #
+//! # Ok(()) }) }
+//! ```
+
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+use tokio::io::{AsyncBufRead as TokioBufRead, AsyncRead as TokioRead};
+use tokio::io::{AsyncWrite as TokioWrite, ReadBuf};
+
+use crate::io::{self, BufRead, IoSlice, Read, Write};
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// An adapter between async-std's and tokio's I/O traits.
+    ///
+    /// A `Compat` implements tokio's traits for whichever of async-std's traits the wrapped value
+    /// implements, and the other way around. Shutting down a wrapped async-std writer closes it,
+    /// and closing a wrapped tokio writer shuts it down.
+    ///
+    /// This type is created by the `compat` and `compat_write` methods of the extension traits in
+    /// this module. See the [module-level documentation] for more.
+    ///
+    /// [module-level documentation]: index.html
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "tokio-compat")))]
+    #[derive(Debug)]
+    pub struct Compat<T> {
+        #[pin]
+        inner: T,
+    }
+}
+
+impl<T> Compat<T> {
+    /// Wraps a reader or writer.
+    pub fn new(inner: T) -> Compat<T> {
+        Compat { inner }
+    }
+
+    /// Gets a reference to the underlying reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader or writer.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps the adapter, returning the underlying reader or writer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+/// Extension methods turning an async-std reader into a tokio reader.
+#[cfg_attr(feature = "docs", doc(cfg(feature = "tokio-compat")))]
+pub trait ReadCompatExt: Read {
+    /// Wraps the reader so that it implements tokio's `AsyncRead`.
+    ///
+    /// If the reader also implements [`Write`] or [`BufRead`], the adapter implements tokio's
+    /// `AsyncWrite` or `AsyncBufRead` as well.
+    ///
+    /// [`Write`]: ../io/trait.Write.html
+    /// [`BufRead`]: ../io/trait.BufRead.html
+    fn compat(self) -> Compat<Self>
+    where
+        Self: Sized,
+    {
+        Compat::new(self)
+    }
+}
+
+impl<T: Read + ?Sized> ReadCompatExt for T {}
+
+/// Extension methods turning an async-std writer into a tokio writer.
+#[cfg_attr(feature = "docs", doc(cfg(feature = "tokio-compat")))]
+pub trait WriteCompatExt: Write {
+    /// Wraps the writer so that it implements tokio's `AsyncWrite`.
+    fn compat_write(self) -> Compat<Self>
+    where
+        Self: Sized,
+    {
+        Compat::new(self)
+    }
+}
+
+impl<T: Write + ?Sized> WriteCompatExt for T {}
+
+/// Extension methods turning a tokio reader into an async-std reader.
+#[cfg_attr(feature = "docs", doc(cfg(feature = "tokio-compat")))]
+pub trait TokioReadCompatExt: TokioRead {
+    /// Wraps the reader so that it implements [`Read`].
+    ///
+    /// If the reader also implements tokio's `AsyncWrite` or `AsyncBufRead`, the adapter
+    /// implements [`Write`] or [`BufRead`] as well.
+    ///
+    /// [`Read`]: ../io/trait.Read.html
+    /// [`Write`]: ../io/trait.Write.html
+    /// [`BufRead`]: ../io/trait.BufRead.html
+    fn compat(self) -> Compat<Self>
+    where
+        Self: Sized,
+    {
+        Compat::new(self)
+    }
+}
+
+impl<T: TokioRead + ?Sized> TokioReadCompatExt for T {}
+
+/// Extension methods turning a tokio writer into an async-std writer.
+#[cfg_attr(feature = "docs", doc(cfg(feature = "tokio-compat")))]
+pub trait TokioWriteCompatExt: TokioWrite {
+    /// Wraps the writer so that it implements [`Write`].
+    ///
+    /// [`Write`]: ../io/trait.Write.html
+    fn compat_write(self) -> Compat<Self>
+    where
+        Self: Sized,
+    {
+        Compat::new(self)
+    }
+}
+
+impl<T: TokioWrite + ?Sized> TokioWriteCompatExt for T {}
+
+impl<T: Read> TokioRead for Compat<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let n = futures_core::ready!(
+            self.project()
+                .inner
+                .poll_read(cx, buf.initialize_unfilled())
+        )?;
+        buf.advance(n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T: BufRead> TokioBufRead for Compat<T> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.project().inner.poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.project().inner.consume(amt)
+    }
+}
+
+impl<T: Write> TokioWrite for Compat<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.project().inner.poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.project().inner.poll_write_vectored(cx, bufs)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_close(cx)
+    }
+}
+
+impl<T: TokioRead> Read for Compat<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut buf = ReadBuf::new(buf);
+        futures_core::ready!(self.project().inner.poll_read(cx, &mut buf))?;
+        Poll::Ready(Ok(buf.filled().len()))
+    }
+}
+
+impl<T: TokioBufRead> BufRead for Compat<T> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        self.project().inner.poll_fill_buf(cx)
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        self.project().inner.consume(amt)
+    }
+}
+
+impl<T: TokioWrite> Write for Compat<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.project().inner.poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        self.project().inner.poll_write_vectored(cx, bufs)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_shutdown(cx)
+    }
+}
@@ -15,12 +15,39 @@ use crate::task::spawn_blocking;
 ///
 /// [`read_dir`]: fn.read_dir.html
 /// [`std::fs::DirEntry`]: https://doc.rust-lang.org/std/fs/struct.DirEntry.html
-pub struct DirEntry(Arc<std::fs::DirEntry>);
+pub struct DirEntry {
+    /// The synchronous entry.
+    inner: Arc<std::fs::DirEntry>,
+
+    /// Metadata read ahead of time by the stream the entry came from.
+    metadata: Option<Metadata>,
+
+    /// File type read ahead of time by the stream the entry came from.
+    file_type: Option<FileType>,
+}
 
 impl DirEntry {
-    /// Creates an asynchronous `DirEntry` from a synchronous one.
-    pub(crate) fn new(inner: std::fs::DirEntry) -> DirEntry {
-        DirEntry(Arc::new(inner))
+    /// Creates an asynchronous `DirEntry` from a synchronous one, optionally reading its metadata
+    /// and file type right away.
+    ///
+    /// Reading them blocks, so this must then be called on the blocking pool. Failures aren't
+    /// recorded, so that they are reported when the information is requested.
+    pub(crate) fn new(inner: std::fs::DirEntry, metadata: bool, file_type: bool) -> DirEntry {
+        let metadata = if metadata {
+            inner.metadata().ok()
+        } else {
+            None
+        };
+        let file_type = if file_type {
+            inner.file_type().ok()
+        } else {
+            None
+        };
+        DirEntry {
+            inner: Arc::new(inner),
+            metadata,
+            file_type,
+        }
     }
 
     /// Returns the full path to this entry.
@@ -48,13 +75,16 @@ impl DirEntry {
     /// # Ok(()) }) }
     /// ```
     pub fn path(&self) -> PathBuf {
-        self.0.path().into()
+        self.inner.path().into()
     }
 
     /// Reads the metadata for this entry.
     ///
     /// This function will traverse symbolic links to read the metadata.
     ///
+    /// If the entry comes from a stream that prefetches metadata, the metadata read at that time
+    /// is returned without touching the filesystem again.
+    ///
     /// If you want to read metadata without following symbolic links, use [`symlink_metadata`]
     /// instead.
     ///
@@ -86,7 +116,10 @@ impl DirEntry {
     /// # Ok(()) }) }
     /// ```
     pub async fn metadata(&self) -> io::Result<Metadata> {
-        let inner = self.0.clone();
+        if let Some(metadata) = &self.metadata {
+            return Ok(metadata.clone());
+        }
+        let inner = self.inner.clone();
         spawn_blocking(move || inner.metadata()).await
     }
 
@@ -96,6 +129,9 @@ impl DirEntry {
     ///
     /// If you want to read metadata with following symbolic links, use [`metadata`] instead.
     ///
+    /// If the entry comes from a stream that prefetches file types, the file type read at that
+    /// time is returned without touching the filesystem again.
+    ///
     /// [`metadata`]: #method.metadata
     ///
     /// # Errors
@@ -124,7 +160,10 @@ impl DirEntry {
     /// # Ok(()) }) }
     /// ```
     pub async fn file_type(&self) -> io::Result<FileType> {
-        let inner = self.0.clone();
+        if let Some(file_type) = self.file_type {
+            return Ok(file_type);
+        }
+        let inner = self.inner.clone();
         spawn_blocking(move || inner.file_type()).await
     }
 
@@ -148,7 +187,7 @@ impl DirEntry {
     /// # Ok(()) }) }
     /// ```
     pub fn file_name(&self) -> OsString {
-        self.0.file_name()
+        self.inner.file_name()
     }
 }
 
@@ -163,7 +202,7 @@ cfg_unix! {
 
     impl DirEntryExt for DirEntry {
         fn ino(&self) -> u64 {
-            self.0.ino()
+            self.inner.ino()
         }
     }
 }
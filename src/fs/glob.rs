@@ -0,0 +1,510 @@
+use std::collections::HashSet;
+use std::future::Future;
+use std::path::{is_separator, Path, PathBuf};
+use std::pin::Pin;
+
+use crate::io;
+use crate::stream::Stream;
+use crate::task::{spawn_blocking, Context, JoinHandle, Poll};
+use crate::utils::Context as _;
+
+/// The number of directories a single blocking task reads before handing back control.
+const DIRS_PER_TASK: usize = 64;
+
+/// Returns a stream of paths matching a glob pattern.
+///
+/// The pattern is a path whose components may contain wildcards:
+///
+/// * `?` matches any single character.
+/// * `*` matches any sequence of characters.
+/// * `[abc]` matches one of the characters inside the brackets, and `[a-z]` one of the
+///   characters in the range. `[!abc]` matches any character not inside the brackets.
+/// * `**` as a whole component matches any number of directories, including none.
+/// * `{a,b}` matches either of the comma-separated alternatives, which may contain wildcards,
+///   separators, and braces of their own.
+///
+/// Wildcards never match a path separator, nor a leading `.` in a file name, so hidden files and
+/// directories are only matched by components starting with a literal `.`. Symbolic links are
+/// followed, except by `**`. A trailing `**` matches directories only.
+///
+/// Directories are read on the blocking pool, and matching paths are yielded in the order of a
+/// depth-first walk with the entries of each directory sorted by name. A path matching several
+/// alternatives of a pattern is only yielded once. Directories that don't exist are skipped, but
+/// other errors reading them are yielded by the stream.
+///
+/// # Errors
+///
+/// An error is returned if the pattern contains an unclosed `[` or an unmatched brace.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs;
+/// use async_std::prelude::*;
+///
+/// let mut paths = fs::glob("logs/**/*.{gz,zst}")?;
+///
+/// while let Some(path) = paths.next().await {
+///     println!("{}", path?.display());
+/// }
+/// #
+/// # Ok(()) }) }
+/// ```
+pub fn glob(pattern: &str) -> io::Result<Glob> {
+    let patterns = expand_braces(pattern)?
+        .iter()
+        .map(|pattern| Pattern::parse(pattern))
+        .collect::<io::Result<Vec<_>>>()?;
+    let stack = patterns
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, pattern)| (i, pattern.base.clone(), 0))
+        .collect();
+    let walker = Walker {
+        patterns,
+        stack,
+        seen: HashSet::new(),
+    };
+    Ok(Glob {
+        state: State::Idle(Some(Box::new(walker))),
+        buffer: Vec::new(),
+    })
+}
+
+/// A stream of paths matching a glob pattern.
+///
+/// This stream is returned by [`glob`] and yields items of type
+/// [`io::Result`]`<`[`PathBuf`]`>`.
+///
+/// [`glob`]: fn.glob.html
+/// [`io::Result`]: ../io/type.Result.html
+/// [`PathBuf`]: ../path/struct.PathBuf.html
+#[derive(Debug)]
+pub struct Glob {
+    state: State,
+
+    /// Results that have been found but not yet yielded, in reverse order.
+    buffer: Vec<io::Result<PathBuf>>,
+}
+
+/// The state of a `Glob`.
+///
+/// The `Glob` can be either idle, busy walking directories on the blocking pool, or done once
+/// there is nothing left to walk.
+#[derive(Debug)]
+enum State {
+    Idle(Option<Box<Walker>>),
+    Busy(JoinHandle<(Box<Walker>, Vec<io::Result<PathBuf>>)>),
+    Done,
+}
+
+impl Stream for Glob {
+    type Item = io::Result<crate::path::PathBuf>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(res) = self.buffer.pop() {
+                return Poll::Ready(Some(res.map(Into::into)));
+            }
+
+            match &mut self.state {
+                State::Idle(opt) => {
+                    let mut walker = opt.take().unwrap();
+
+                    // Start the operation asynchronously.
+                    self.state = State::Busy(spawn_blocking(move || {
+                        let found = walker.walk();
+                        (walker, found)
+                    }));
+                }
+                // Poll the asynchronous operation the walk is currently blocked on.
+                State::Busy(task) => {
+                    let (walker, mut found) = futures_core::ready!(Pin::new(task).poll(cx));
+                    self.state = if walker.stack.is_empty() {
+                        State::Done
+                    } else {
+                        State::Idle(Some(walker))
+                    };
+                    found.reverse();
+                    self.buffer = found;
+                }
+                State::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// A depth-first walk over the directories that may contain matches.
+#[derive(Debug)]
+struct Walker {
+    patterns: Vec<Pattern>,
+
+    /// Paths left to visit, along with the index of their pattern and of the first component they
+    /// still have to match.
+    stack: Vec<(usize, PathBuf, usize)>,
+
+    /// Paths that have been yielded already.
+    seen: HashSet<PathBuf>,
+}
+
+impl Walker {
+    /// Walks until some results are found, a number of directories have been read, or the walk is
+    /// over.
+    fn walk(&mut self) -> Vec<io::Result<PathBuf>> {
+        let mut found = Vec::new();
+        let mut dirs = 0;
+
+        while found.is_empty() && dirs < DIRS_PER_TASK {
+            let (pattern, path, index) = match self.stack.pop() {
+                Some(next) => next,
+                None => break,
+            };
+            let components = &self.patterns[pattern].components;
+
+            let component = match components.get(index) {
+                Some(component) => component,
+                None => {
+                    if std::fs::symlink_metadata(&path).is_ok() && self.seen.insert(path.clone()) {
+                        found.push(Ok(path));
+                    }
+                    continue;
+                }
+            };
+            let last = index + 1 == components.len();
+
+            let mut next = Vec::new();
+            match component {
+                Component::Literal(name) => {
+                    let child = path.join(name);
+                    if last || child.is_dir() {
+                        next.push((child, index + 1));
+                    }
+                }
+                Component::Wildcard(tokens) => {
+                    dirs += 1;
+                    let entries = match read_sorted(&path) {
+                        Ok(entries) => entries,
+                        Err(err) => {
+                            found.push(Err(err));
+                            continue;
+                        }
+                    };
+                    for (name, file_type) in entries {
+                        if !matches(tokens, &name) {
+                            continue;
+                        }
+                        let child = path.join(&name);
+                        if last {
+                            if self.seen.insert(child.clone()) {
+                                found.push(Ok(child));
+                            }
+                        } else if file_type.is_dir() || (file_type.is_symlink() && child.is_dir()) {
+                            next.push((child, index + 1));
+                        }
+                    }
+                }
+                Component::Recursive => {
+                    dirs += 1;
+                    let entries = match read_sorted(&path) {
+                        Ok(entries) => entries,
+                        Err(err) => {
+                            found.push(Err(err));
+                            continue;
+                        }
+                    };
+                    next.push((path.clone(), index + 1));
+                    for (name, file_type) in entries {
+                        if file_type.is_dir() && !name.starts_with('.') {
+                            next.push((path.join(&name), index));
+                        }
+                    }
+                }
+            }
+
+            // Push in reverse so that paths are visited in order.
+            self.stack.extend(
+                next.into_iter()
+                    .rev()
+                    .map(|(path, index)| (pattern, path, index)),
+            );
+        }
+
+        found
+    }
+}
+
+/// Reads the entries of a directory, sorted by name.
+///
+/// A directory that doesn't exist has no entries.
+fn read_sorted(path: &Path) -> io::Result<Vec<(String, std::fs::FileType)>> {
+    let dir = if path.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        path
+    };
+    let read = match std::fs::read_dir(dir) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        res => res.context(|| format!("could not read directory `{}`", dir.display()))?,
+    };
+
+    let mut entries = Vec::new();
+    for entry in read {
+        let entry = entry.context(|| format!("could not read directory `{}`", dir.display()))?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        entries.push((name, entry.file_type()?));
+    }
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(entries)
+}
+
+/// A pattern without braces.
+#[derive(Debug)]
+struct Pattern {
+    /// The leading components without wildcards.
+    base: PathBuf,
+
+    /// The remaining components.
+    components: Vec<Component>,
+}
+
+/// A component of a pattern.
+#[derive(Debug)]
+enum Component {
+    /// A file name without wildcards.
+    Literal(String),
+
+    /// A file name with wildcards.
+    Wildcard(Vec<Token>),
+
+    /// `**`, matching any number of directories.
+    Recursive,
+}
+
+/// A part of a file name with wildcards.
+#[derive(Debug)]
+enum Token {
+    /// A literal character.
+    Char(char),
+
+    /// `?`, matching any character.
+    Any,
+
+    /// `*`, matching any sequence of characters.
+    Star,
+
+    /// A bracket expression matching characters in the ranges, or not in them if negated.
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> io::Result<Pattern> {
+        let mut base_len = pattern.len();
+        let mut components = Vec::new();
+        let mut offset = 0;
+
+        for part in pattern.split(is_separator) {
+            if components.is_empty() && !has_wildcards(part) {
+                // Keep the separator following the component in the base.
+                offset += part.len() + 1;
+                continue;
+            }
+            if components.is_empty() {
+                base_len = offset;
+            }
+            offset += part.len() + 1;
+
+            if part == "**" {
+                components.push(Component::Recursive);
+            } else if has_wildcards(part) {
+                components.push(Component::Wildcard(parse_tokens(part)?));
+            } else if !part.is_empty() {
+                components.push(Component::Literal(part.to_string()));
+            }
+        }
+
+        Ok(Pattern {
+            base: PathBuf::from(&pattern[..base_len]),
+            components,
+        })
+    }
+}
+
+fn has_wildcards(part: &str) -> bool {
+    part.contains(['*', '?', '['])
+}
+
+fn parse_tokens(part: &str) -> io::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = part.chars();
+
+    while let Some(c) = chars.next() {
+        let token = match c {
+            '?' => Token::Any,
+            '*' => Token::Star,
+            '[' => {
+                let mut negated = false;
+                let mut ranges = Vec::new();
+                let mut first = true;
+                loop {
+                    let c = match chars.next() {
+                        Some(']') if !first => break,
+                        Some('!') | Some('^') if first && !negated => {
+                            negated = true;
+                            continue;
+                        }
+                        Some(c) => c,
+                        None => {
+                            return Err(io::Error::new(
+                                io::ErrorKind::InvalidInput,
+                                format!("unclosed `[` in glob pattern `{}`", part),
+                            ));
+                        }
+                    };
+                    first = false;
+
+                    let mut lookahead = chars.clone();
+                    match (lookahead.next(), lookahead.next()) {
+                        (Some('-'), Some(end)) if end != ']' => {
+                            chars = lookahead;
+                            ranges.push((c, end));
+                        }
+                        _ => ranges.push((c, c)),
+                    }
+                }
+                Token::Class { negated, ranges }
+            }
+            c => Token::Char(c),
+        };
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Checks whether a file name matches the tokens of a component.
+fn matches(tokens: &[Token], name: &str) -> bool {
+    // Wildcards don't match a leading dot.
+    if name.starts_with('.') {
+        match tokens.first() {
+            Some(Token::Char('.')) => {}
+            _ => return false,
+        }
+    }
+
+    let name: Vec<char> = name.chars().collect();
+    let (mut t, mut n) = (0, 0);
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match tokens.get(t) {
+            Some(Token::Star) => {
+                backtrack = Some((t, n));
+                t += 1;
+                continue;
+            }
+            Some(token) if token.matches(name[n]) => {
+                t += 1;
+                n += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        // Let the last star match one more character.
+        match backtrack {
+            Some((bt, bn)) => {
+                backtrack = Some((bt, bn + 1));
+                t = bt + 1;
+                n = bn + 1;
+            }
+            None => return false,
+        }
+    }
+
+    tokens[t..].iter().all(|token| matches!(token, Token::Star))
+}
+
+impl Token {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Token::Char(expected) => c == *expected,
+            Token::Any => true,
+            Token::Star => false,
+            Token::Class { negated, ranges } => {
+                ranges.iter().any(|&(start, end)| start <= c && c <= end) != *negated
+            }
+        }
+    }
+}
+
+/// Expands the braces in a pattern into the patterns they stand for.
+fn expand_braces(pattern: &str) -> io::Result<Vec<String>> {
+    let unmatched = || {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unmatched brace in glob pattern `{}`", pattern),
+        )
+    };
+
+    // Find the first opening brace outside of brackets.
+    let mut in_class = false;
+    let mut open = None;
+    for (i, c) in pattern.char_indices() {
+        match c {
+            '[' if !in_class => in_class = true,
+            ']' if in_class => in_class = false,
+            '{' if !in_class => {
+                open = Some(i);
+                break;
+            }
+            '}' if !in_class => return Err(unmatched()),
+            _ => {}
+        }
+    }
+    let open = match open {
+        Some(open) => open,
+        None => return Ok(vec![pattern.to_string()]),
+    };
+
+    // Split the alternatives at the commas of the same depth, up to the matching closing brace.
+    let mut depth = 0;
+    let mut alternatives = Vec::new();
+    let mut start = open + 1;
+    let mut close = None;
+    for (i, c) in pattern[open + 1..].char_indices() {
+        let i = open + 1 + i;
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => {
+                alternatives.push(&pattern[start..i]);
+                close = Some(i);
+                break;
+            }
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                alternatives.push(&pattern[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    let close = close.ok_or_else(unmatched)?;
+
+    let mut expanded = Vec::new();
+    for alternative in alternatives {
+        let pattern = format!(
+            "{}{}{}",
+            &pattern[..open],
+            alternative,
+            &pattern[close + 1..]
+        );
+        expanded.extend(expand_braces(&pattern)?);
+    }
+    Ok(expanded)
+}
@@ -36,6 +36,13 @@ pub use open_options::OpenOptions;
 pub use permissions::Permissions;
 pub use read_dir::ReadDir;
 
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub use glob::{glob, Glob};
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub use read_dir_options::ReadDirOptions;
+
 pub use canonicalize::canonicalize;
 pub use copy::copy;
 pub use create_dir::create_dir;
@@ -62,12 +69,16 @@ mod dir_builder;
 mod dir_entry;
 mod file;
 mod file_type;
+#[cfg(feature = "unstable")]
+mod glob;
 mod hard_link;
 mod metadata;
 mod open_options;
 mod permissions;
 mod read;
 mod read_dir;
+#[cfg(feature = "unstable")]
+mod read_dir_options;
 mod read_link;
 mod read_to_string;
 mod remove_dir;
@@ -1,3 +1,4 @@
+use std::collections::VecDeque;
 use std::future::Future;
 use std::pin::Pin;
 
@@ -54,6 +55,57 @@ pub async fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<ReadDir> {
     .map(ReadDir::new)
 }
 
+/// How a `ReadDir` reads entries on the blocking pool.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Config {
+    /// The number of entries read per blocking task.
+    pub(crate) batch_size: usize,
+
+    /// Set to `true` if the metadata of each entry is read along with it.
+    pub(crate) metadata: bool,
+
+    /// Set to `true` if the file type of each entry is read along with it.
+    pub(crate) file_type: bool,
+
+    /// Set to `true` if the whole directory is read up front and sorted by file name.
+    pub(crate) sorted: bool,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            batch_size: 1,
+            metadata: false,
+            file_type: false,
+            sorted: false,
+        }
+    }
+}
+
+impl Config {
+    /// Reads the next batch of entries.
+    ///
+    /// Returns `true` along with the batch if the end of the directory has been reached.
+    fn read_batch(&self, inner: &mut std::fs::ReadDir) -> (Vec<io::Result<DirEntry>>, bool) {
+        let limit = if self.sorted {
+            usize::MAX
+        } else {
+            self.batch_size
+        };
+        let mut batch: Vec<_> = inner
+            .by_ref()
+            .take(limit)
+            .map(|res| res.map(|entry| DirEntry::new(entry, self.metadata, self.file_type)))
+            .collect();
+        let done = batch.len() < limit;
+        if self.sorted {
+            // Errors have no name and come first.
+            batch.sort_by_cached_key(|res| res.as_ref().ok().map(DirEntry::file_name));
+        }
+        (batch, done)
+    }
+}
+
 /// A stream of entries in a directory.
 ///
 /// This stream is returned by [`read_dir`] and yields items of type
@@ -67,21 +119,40 @@ pub async fn read_dir<P: AsRef<Path>>(path: P) -> io::Result<ReadDir> {
 /// [`DirEntry`]: struct.DirEntry.html
 /// [`std::fs::ReadDir`]: https://doc.rust-lang.org/std/fs/struct.ReadDir.html
 #[derive(Debug)]
-pub struct ReadDir(State);
+pub struct ReadDir {
+    state: State,
+
+    /// Entries that have been read but not yet yielded.
+    buffer: VecDeque<io::Result<DirEntry>>,
+
+    config: Config,
+}
 
 /// The state of an asynchronous `ReadDir`.
 ///
-/// The `ReadDir` can be either idle or busy performing an asynchronous operation.
+/// The `ReadDir` can be either idle, busy performing an asynchronous operation, or done once the
+/// end of the directory has been reached.
 #[derive(Debug)]
 enum State {
     Idle(Option<std::fs::ReadDir>),
-    Busy(JoinHandle<(std::fs::ReadDir, Option<io::Result<std::fs::DirEntry>>)>),
+    Busy(JoinHandle<(std::fs::ReadDir, Vec<io::Result<DirEntry>>, bool)>),
+    Done,
 }
 
 impl ReadDir {
     /// Creates an asynchronous `ReadDir` from a synchronous handle.
     pub(crate) fn new(inner: std::fs::ReadDir) -> ReadDir {
-        ReadDir(State::Idle(Some(inner)))
+        ReadDir::with_config(inner, Config::default())
+    }
+
+    /// Creates an asynchronous `ReadDir` from a synchronous handle, reading entries as
+    /// configured.
+    pub(crate) fn with_config(inner: std::fs::ReadDir, config: Config) -> ReadDir {
+        ReadDir {
+            state: State::Idle(Some(inner)),
+            buffer: VecDeque::new(),
+            config,
+        }
     }
 }
 
@@ -90,22 +161,32 @@ impl Stream for ReadDir {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         loop {
-            match &mut self.0 {
+            if let Some(res) = self.buffer.pop_front() {
+                return Poll::Ready(Some(res));
+            }
+
+            match &mut self.state {
                 State::Idle(opt) => {
                     let mut inner = opt.take().unwrap();
+                    let config = self.config;
 
                     // Start the operation asynchronously.
-                    self.0 = State::Busy(spawn_blocking(move || {
-                        let next = inner.next();
-                        (inner, next)
+                    self.state = State::Busy(spawn_blocking(move || {
+                        let (batch, done) = config.read_batch(&mut inner);
+                        (inner, batch, done)
                     }));
                 }
-                // Poll the asynchronous operation the file is currently blocked on.
+                // Poll the asynchronous operation the directory is currently blocked on.
                 State::Busy(task) => {
-                    let (inner, opt) = futures_core::ready!(Pin::new(task).poll(cx));
-                    self.0 = State::Idle(Some(inner));
-                    return Poll::Ready(opt.map(|res| res.map(DirEntry::new)));
+                    let (inner, batch, done) = futures_core::ready!(Pin::new(task).poll(cx));
+                    self.state = if done {
+                        State::Done
+                    } else {
+                        State::Idle(Some(inner))
+                    };
+                    self.buffer.extend(batch);
                 }
+                State::Done => return Poll::Ready(None),
             }
         }
     }
@@ -0,0 +1,126 @@
+use crate::fs::read_dir::Config;
+use crate::fs::ReadDir;
+use crate::io;
+use crate::path::Path;
+use crate::task::spawn_blocking;
+use crate::utils::Context as _;
+
+/// Options for reading the entries of a directory.
+///
+/// [`read_dir`] reads entries one at a time, each in its own task on the blocking pool, and
+/// reads the metadata of an entry only when asked. Listing a large directory that way costs a
+/// round-trip to the blocking pool per entry, or several if the metadata is needed too. These
+/// options read entries in batches, optionally along with their metadata and file types, and can
+/// sort them by name.
+///
+/// [`read_dir`]: fn.read_dir.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs::ReadDirOptions;
+/// use async_std::prelude::*;
+///
+/// let mut entries = ReadDirOptions::new()
+///     .batch_size(256)
+///     .metadata(true)
+///     .read_dir(".")
+///     .await?;
+///
+/// while let Some(res) = entries.next().await {
+///     let entry = res?;
+///     println!("{:?}: {} bytes", entry.file_name(), entry.metadata().await?.len());
+/// }
+/// #
+/// # Ok(()) }) }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReadDirOptions {
+    config: Config,
+}
+
+impl ReadDirOptions {
+    /// Creates a blank set of options.
+    ///
+    /// Entries are read one at a time, in the order the filesystem returns them, and without
+    /// their metadata, just like with [`read_dir`].
+    ///
+    /// [`read_dir`]: fn.read_dir.html
+    pub fn new() -> ReadDirOptions {
+        ReadDirOptions::default()
+    }
+
+    /// Sets the number of entries read at a time on the blocking pool.
+    ///
+    /// This option is initially set to 1.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `batch_size` is zero.
+    pub fn batch_size(&mut self, batch_size: usize) -> &mut Self {
+        assert!(batch_size > 0, "batches must contain at least one entry");
+        self.config.batch_size = batch_size;
+        self
+    }
+
+    /// Sets the option to read the metadata of each entry along with it.
+    ///
+    /// [`DirEntry::metadata`] then returns the metadata read at that time. Metadata that couldn't
+    /// be read is read again when asked for, so that the error is reported.
+    ///
+    /// This option is initially set to `false`.
+    ///
+    /// [`DirEntry::metadata`]: struct.DirEntry.html#method.metadata
+    pub fn metadata(&mut self, metadata: bool) -> &mut Self {
+        self.config.metadata = metadata;
+        self
+    }
+
+    /// Sets the option to read the file type of each entry along with it.
+    ///
+    /// [`DirEntry::file_type`] then returns the file type read at that time. On most platforms,
+    /// the file type comes with the entry and reading it is free.
+    ///
+    /// This option is initially set to `false`.
+    ///
+    /// [`DirEntry::file_type`]: struct.DirEntry.html#method.file_type
+    pub fn file_type(&mut self, file_type: bool) -> &mut Self {
+        self.config.file_type = file_type;
+        self
+    }
+
+    /// Sets the option to yield entries sorted by file name.
+    ///
+    /// The whole directory is read before the first entry is yielded, regardless of the
+    /// [`batch_size`]. Errors encountered while reading it are yielded before the entries.
+    ///
+    /// This option is initially set to `false`.
+    ///
+    /// [`batch_size`]: #method.batch_size
+    pub fn sorted(&mut self, sorted: bool) -> &mut Self {
+        self.config.sorted = sorted;
+        self
+    }
+
+    /// Returns a stream of entries in a directory, read with the configured options.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned in the following situations:
+    ///
+    /// * `path` does not point to an existing directory.
+    /// * The current process lacks permissions to read the contents of the directory.
+    /// * Some other I/O error occurred.
+    pub async fn read_dir<P: AsRef<Path>>(&self, path: P) -> io::Result<ReadDir> {
+        let path = path.as_ref().to_owned();
+        let config = self.config;
+        spawn_blocking(move || {
+            std::fs::read_dir(&path)
+                .context(|| format!("could not read directory `{}`", path.display()))
+        })
+        .await
+        .map(|inner| ReadDir::with_config(inner, config))
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::iter::FromIterator;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+
+use slab::Slab;
+
+use crate::stream::Stream;
+
+/// A growable set of futures polled concurrently.
+///
+/// The set is a [`Stream`] yielding the outputs of the futures in the order they complete. More
+/// futures can be pushed at any time, including while the stream is being consumed, which makes
+/// it a good fit for fanning out work dynamically on a single task rather than spawning a task
+/// per future.
+///
+/// Each future gets its own waker, so only the futures that have been woken are polled again.
+///
+/// The stream yields `None` while the set is empty, but it can be polled again after more
+/// futures have been pushed.
+///
+/// [`Stream`]: ../stream/trait.Stream.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::future::ConcurrentQueue;
+/// use async_std::prelude::*;
+/// use async_std::task;
+///
+/// async fn crawl(depth: u64) -> u64 {
+///     task::sleep(Duration::from_millis(depth)).await;
+///     depth
+/// }
+///
+/// let mut queue = ConcurrentQueue::new();
+/// queue.push(crawl(0));
+///
+/// let mut visited = Vec::new();
+/// while let Some(depth) = queue.next().await {
+///     visited.push(depth);
+///     if depth < 3 {
+///         queue.push(crawl(depth + 1));
+///         queue.push(crawl(depth + 1));
+///     }
+/// }
+/// assert_eq!(visited.len(), 15);
+/// #
+/// # })
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct ConcurrentQueue<F> {
+    /// The futures in the set along with their wakers.
+    futures: Slab<(Pin<Box<F>>, Arc<Notifier>)>,
+
+    /// The futures that have been woken.
+    ready: Arc<Mutex<Ready>>,
+}
+
+/// The futures that need to be polled, and the task consuming the set.
+struct Ready {
+    queue: VecDeque<usize>,
+    waker: Option<Waker>,
+}
+
+/// The waker of a future in the set.
+struct Notifier {
+    key: usize,
+
+    /// Set while the key of the future is in the ready queue.
+    queued: AtomicBool,
+
+    ready: Arc<Mutex<Ready>>,
+}
+
+impl Wake for Notifier {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.queued.swap(true, Ordering::AcqRel) {
+            let mut ready = self.ready.lock().unwrap();
+            ready.queue.push_back(self.key);
+            if let Some(w) = ready.waker.take() {
+                w.wake();
+            }
+        }
+    }
+}
+
+impl<F> ConcurrentQueue<F> {
+    /// Creates an empty set.
+    pub fn new() -> ConcurrentQueue<F> {
+        ConcurrentQueue {
+            futures: Slab::new(),
+            ready: Arc::new(Mutex::new(Ready {
+                queue: VecDeque::new(),
+                waker: None,
+            })),
+        }
+    }
+
+    /// Returns the number of futures in the set.
+    pub fn len(&self) -> usize {
+        self.futures.len()
+    }
+
+    /// Returns `true` if the set contains no futures.
+    pub fn is_empty(&self) -> bool {
+        self.futures.is_empty()
+    }
+
+    /// Adds a future to the set.
+    ///
+    /// The future is polled the next time the set is polled.
+    pub fn push(&mut self, future: F) {
+        let entry = self.futures.vacant_entry();
+        let notifier = Arc::new(Notifier {
+            key: entry.key(),
+            queued: AtomicBool::new(false),
+            ready: self.ready.clone(),
+        });
+        entry.insert((Box::pin(future), notifier.clone()));
+        notifier.wake();
+    }
+
+    /// Drops all futures in the set.
+    pub fn clear(&mut self) {
+        self.futures.clear();
+        self.ready.lock().unwrap().queue.clear();
+    }
+}
+
+impl<F: Future> ConcurrentQueue<F> {
+    /// Polls the futures that have been woken, returning the first output.
+    ///
+    /// Returns `Poll::Ready(None)` if the set is empty.
+    pub fn poll_next_output(&mut self, cx: &mut Context<'_>) -> Poll<Option<F::Output>> {
+        if self.futures.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        // Poll each future at most once, so that futures waking themselves can't starve the task.
+        let mut budget = self.futures.len();
+        loop {
+            let key = {
+                let mut ready = self.ready.lock().unwrap();
+                match ready.queue.pop_front() {
+                    Some(key) if budget > 0 => key,
+                    Some(key) => {
+                        ready.queue.push_front(key);
+                        drop(ready);
+                        cx.waker().wake_by_ref();
+                        return Poll::Pending;
+                    }
+                    None => {
+                        match &ready.waker {
+                            Some(w) if w.will_wake(cx.waker()) => {}
+                            _ => ready.waker = Some(cx.waker().clone()),
+                        }
+                        return Poll::Pending;
+                    }
+                }
+            };
+            budget -= 1;
+
+            // The future may have completed since it was woken.
+            let (future, notifier) = match self.futures.get_mut(key) {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            notifier.queued.store(false, Ordering::Release);
+            let waker = Waker::from(notifier.clone());
+            if let Poll::Ready(v) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                self.futures.remove(key);
+                return Poll::Ready(Some(v));
+            }
+        }
+    }
+}
+
+impl<F> Default for ConcurrentQueue<F> {
+    fn default() -> ConcurrentQueue<F> {
+        ConcurrentQueue::new()
+    }
+}
+
+impl<F> Unpin for ConcurrentQueue<F> {}
+
+impl<F: Future> Stream for ConcurrentQueue<F> {
+    type Item = F::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_next_output(cx)
+    }
+}
+
+impl<F> FromIterator<F> for ConcurrentQueue<F> {
+    fn from_iter<I: IntoIterator<Item = F>>(iter: I) -> ConcurrentQueue<F> {
+        let mut queue = ConcurrentQueue::new();
+        queue.extend(iter);
+        queue
+    }
+}
+
+impl<F> Extend<F> for ConcurrentQueue<F> {
+    fn extend<I: IntoIterator<Item = F>>(&mut self, iter: I) {
+        for future in iter {
+            self.push(future);
+        }
+    }
+}
+
+impl<F> fmt::Debug for ConcurrentQueue<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrentQueue")
+            .field("len", &self.len())
+            .finish()
+    }
+}
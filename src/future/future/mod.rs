@@ -5,6 +5,7 @@ cfg_unstable! {
     mod try_race;
     mod join;
     mod try_join;
+    mod timeout;
 
     use std::time::Duration;
 
@@ -15,6 +16,7 @@ cfg_unstable! {
     use try_race::TryRace;
     use join::Join;
     use try_join::TryJoin;
+    use timeout::Timeout;
 }
 
 extension_trait! {
@@ -157,6 +159,51 @@ extension_trait! {
             DelayFuture::new(self, dur)
         }
 
+        /// Awaits the future or times out after a duration of time.
+        ///
+        /// Unlike [`future::timeout`], the future isn't lost when it times out: the returned
+        /// [`TimeoutError`] carries it back, so that it can be retried with a new timeout or
+        /// awaited until completion. The future must be [`Unpin`]; other futures can be pinned
+        /// with [`Box::pin`] first.
+        ///
+        /// [`future::timeout`]: ../future/fn.timeout.html
+        /// [`TimeoutError`]: ../future/struct.TimeoutError.html
+        /// [`Unpin`]: https://doc.rust-lang.org/std/marker/trait.Unpin.html
+        /// [`Box::pin`]: https://doc.rust-lang.org/std/boxed/struct.Box.html#method.pin
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// # async_std::task::block_on(async {
+        /// use std::time::Duration;
+        ///
+        /// use async_std::prelude::*;
+        /// use async_std::future;
+        ///
+        /// let res = future::ready(1).timeout(Duration::from_millis(10)).await;
+        /// assert_eq!(res.unwrap(), 1);
+        ///
+        /// let mut never = future::pending::<()>();
+        /// for _ in 0..3 {
+        ///     // Retry the same future until giving up.
+        ///     let err = never.timeout(Duration::from_millis(10)).await.unwrap_err();
+        ///     never = err.into_inner();
+        /// }
+        /// # })
+        /// ```
+        #[cfg(all(feature = "default", feature = "unstable"))]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn timeout(
+            self,
+            dur: Duration,
+        ) -> impl Future<Output = Result<Self::Output, crate::future::TimeoutError<Self>>>
+            [Timeout<Self>]
+        where
+            Self: Sized + Unpin,
+        {
+            Timeout::new(self, dur)
+        }
+
         /// Flatten out the execution of this future when the result itself
         /// can be converted into another future.
         ///
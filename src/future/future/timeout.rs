@@ -0,0 +1,48 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use crate::future::TimeoutError;
+use crate::task::{Context, Poll};
+use crate::time::Delay;
+
+#[doc(hidden)]
+#[allow(missing_debug_implementations)]
+pub struct Timeout<F> {
+    future: Option<F>,
+    delay: Delay,
+}
+
+impl<F> Timeout<F> {
+    pub(super) fn new(future: F, dur: Duration) -> Timeout<F> {
+        Timeout {
+            future: Some(future),
+            delay: Delay::new(dur),
+        }
+    }
+}
+
+impl<F: Future + Unpin> Future for Timeout<F> {
+    type Output = Result<F::Output, TimeoutError<F>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let future = this
+            .future
+            .as_mut()
+            .expect("`Timeout` polled after completion");
+
+        if let Poll::Ready(v) = Pin::new(future).poll(cx) {
+            this.future = None;
+            return Poll::Ready(Ok(v));
+        }
+
+        match Pin::new(&mut this.delay).poll(cx) {
+            Poll::Ready(()) => {
+                let future = this.future.take().unwrap();
+                Poll::Ready(Err(TimeoutError::new(future)))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -15,6 +15,9 @@
 //! | [`Future::join`]   | `(T1, T2)`       | Wait for all to complete
 //! | [`Future::race`]   | `T`              | Return on first value
 //!
+//! To wait on more than two futures at once, the [`join!`] macro takes any number of futures with
+//! different output types.
+//!
 //! ## Fallible Futures Concurrency
 //!
 //! For operating on futures that return `Result` additional `try_` variants of
@@ -41,10 +44,19 @@
 //! | [`Future::race`]       | `Result<T, E>`                 | Return on first value
 //! | [`Future::try_race`]   | `Result<T, E>`                 | Return on first `Ok`, reject on last Err
 //!
+//! Similarly, [`try_join!`] waits on any number of fallible futures, and [`race_ok`] waits for
+//! the first success out of a collection of similarly-typed futures, collecting the errors of
+//! the ones that failed. A [`ConcurrentQueue`] polls a growing set of futures, yielding their
+//! outputs as they complete.
+//!
 //! [`Future::join`]: trait.Future.html#method.join
 //! [`Future::try_join`]: trait.Future.html#method.try_join
 //! [`Future::race`]: trait.Future.html#method.race
 //! [`Future::try_race`]: trait.Future.html#method.try_race
+//! [`join!`]: macro.join.html
+//! [`try_join!`]: macro.try_join.html
+//! [`race_ok`]: fn.race_ok.html
+//! [`ConcurrentQueue`]: struct.ConcurrentQueue.html
 
 pub use future::Future;
 pub use pending::pending;
@@ -58,12 +70,24 @@ mod ready;
 
 cfg_default! {
     pub use timeout::{timeout, TimeoutError};
+    #[cfg(feature = "unstable")]
+    pub use timeout::timeout_at;
     mod timeout;
 }
 
 cfg_unstable! {
+    pub use concurrent_queue::ConcurrentQueue;
     pub use into_future::IntoFuture;
     pub(crate) use maybe_done::MaybeDone;
+    pub use poll_once::poll_once;
+    pub use race_ok::{race_ok, RaceOk};
+    pub use retry::{retry, retry_if, Backoff};
+    #[doc(inline)]
+    pub use crate::{join, try_join};
+    mod concurrent_queue;
     mod into_future;
     mod maybe_done;
+    mod poll_once;
+    mod race_ok;
+    mod retry;
 }
@@ -0,0 +1,59 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::task::{Context, Poll};
+
+/// Polls a future exactly once.
+///
+/// Returns `Some` with the output if the future completed on that poll, and `None` if it is
+/// still pending. The task is woken when the future can make progress, as if it had been
+/// awaited.
+///
+/// Passing a reference such as `&mut future` or `future.as_mut()` keeps the future around, so
+/// that it can be polled again or awaited afterwards.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::future;
+/// use async_std::sync::channel;
+///
+/// let (s, r) = channel(1);
+/// let mut recv = Box::pin(r.recv());
+/// assert_eq!(future::poll_once(&mut recv).await, None);
+///
+/// s.send(1).await;
+/// assert_eq!(future::poll_once(&mut recv).await, Some(Some(1)));
+/// #
+/// # })
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn poll_once<F>(future: F) -> Option<F::Output>
+where
+    F: Future,
+{
+    let fut = PollOnce { future };
+    fut.await
+}
+
+pin_project! {
+    struct PollOnce<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F: Future> Future for PollOnce<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project().future.poll(cx) {
+            Poll::Ready(v) => Poll::Ready(Some(v)),
+            Poll::Pending => Poll::Ready(None),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Waits for the first of many fallible futures to succeed.
+///
+/// All futures are polled concurrently. The output of the first future to complete with `Ok` is
+/// returned and the other futures are dropped. If all futures fail, their errors are returned in
+/// the order the futures were passed in.
+///
+/// An empty set of futures fails immediately with no errors.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::future;
+/// use async_std::task;
+///
+/// async fn connect(addr: u64) -> Result<u64, String> {
+///     task::sleep(Duration::from_millis(addr * 10)).await;
+///     if addr == 1 {
+///         Err("connection refused".to_string())
+///     } else {
+///         Ok(addr)
+///     }
+/// }
+///
+/// let res = future::race_ok((1..=3).map(connect)).await;
+/// assert_eq!(res, Ok(2));
+///
+/// let res = future::race_ok(vec![future::ready(Err::<(), _>(1)), future::ready(Err(2))]).await;
+/// assert_eq!(res, Err(vec![1, 2]));
+/// #
+/// # })
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn race_ok<I, F, T, E>(futures: I) -> RaceOk<F>
+where
+    I: IntoIterator<Item = F>,
+    F: Future<Output = Result<T, E>>,
+{
+    let futures: Box<[_]> = futures.into_iter().map(Some).collect();
+    RaceOk {
+        errors: (0..futures.len()).map(|_| None).collect(),
+        pending: futures.len(),
+        futures,
+    }
+}
+
+/// A future that waits for the first of many fallible futures to succeed.
+///
+/// This future is created by the [`race_ok`] function. See its documentation for more.
+///
+/// [`race_ok`]: fn.race_ok.html
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct RaceOk<F: Future> {
+    /// The futures, which are never moved out of the boxed slice, or `None` once they complete.
+    futures: Box<[Option<F>]>,
+
+    /// The outputs of the futures that failed.
+    errors: Vec<Option<F::Output>>,
+
+    /// The number of futures that haven't completed yet.
+    pending: usize,
+}
+
+// The futures are pinned in their heap allocation, which stays in place when the `RaceOk` moves.
+impl<F: Future> Unpin for RaceOk<F> {}
+
+impl<F, T, E> Future for RaceOk<F>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    type Output = Result<T, Vec<E>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+
+        let mut output = None;
+        for (i, slot) in this.futures.iter_mut().enumerate() {
+            let future = match slot {
+                Some(future) => unsafe { Pin::new_unchecked(future) },
+                None => continue,
+            };
+
+            if let Poll::Ready(res) = future.poll(cx) {
+                *slot = None;
+                this.pending -= 1;
+                match res {
+                    Ok(v) => {
+                        output = Some(v);
+                        break;
+                    }
+                    Err(e) => this.errors[i] = Some(Err(e)),
+                }
+            }
+        }
+
+        if let Some(v) = output {
+            // Drop the remaining futures right away.
+            this.futures = Box::new([]);
+            this.pending = 0;
+            return Poll::Ready(Ok(v));
+        }
+
+        if this.pending > 0 {
+            return Poll::Pending;
+        }
+
+        let errors = this
+            .errors
+            .drain(..)
+            .map(|res| match res {
+                Some(Err(e)) => e,
+                _ => unreachable!(),
+            })
+            .collect();
+        Poll::Ready(Err(errors))
+    }
+}
+
+impl<F: Future> fmt::Debug for RaceOk<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RaceOk")
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
@@ -0,0 +1,228 @@
+use std::convert::TryFrom;
+use std::future::Future;
+use std::time::Duration;
+
+use crate::task;
+use crate::utils::random;
+
+/// A policy for how long to wait between attempts of an operation, and how often to try.
+///
+/// The delay starts at an initial value and is multiplied by a factor after every failed
+/// attempt, up to a maximum delay. A random jitter can be subtracted from each delay so that
+/// many clients failing at once don't all retry at the same time.
+///
+/// By default, the factor is 2, the maximum delay is 60 seconds, there is no jitter, and the
+/// operation is tried until it succeeds.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use async_std::future::Backoff;
+///
+/// let backoff = Backoff::exponential(Duration::from_millis(100))
+///     .max_delay(Duration::from_secs(10))
+///     .jitter(0.5)
+///     .max_attempts(5);
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Backoff {
+    initial: Duration,
+    factor: f64,
+    max_delay: Duration,
+    jitter: f64,
+    max_attempts: Option<usize>,
+}
+
+impl Backoff {
+    /// Creates a policy whose delay starts at `initial` and doubles after every attempt.
+    pub fn exponential(initial: Duration) -> Backoff {
+        Backoff {
+            initial,
+            factor: 2.0,
+            max_delay: Duration::from_secs(60),
+            jitter: 0.0,
+            max_attempts: None,
+        }
+    }
+
+    /// Creates a policy waiting the same delay between all attempts.
+    pub fn fixed(delay: Duration) -> Backoff {
+        Backoff::exponential(delay).factor(1.0).max_delay(delay)
+    }
+
+    /// Sets the factor the delay is multiplied by after every attempt.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the factor is less than 1.
+    pub fn factor(mut self, factor: f64) -> Backoff {
+        assert!(factor >= 1.0, "the backoff factor must be at least 1");
+        self.factor = factor;
+        self
+    }
+
+    /// Sets the longest delay between two attempts.
+    pub fn max_delay(mut self, max_delay: Duration) -> Backoff {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the largest fraction of each delay that is randomly subtracted from it.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the fraction is not between 0 and 1.
+    pub fn jitter(mut self, jitter: f64) -> Backoff {
+        assert!(
+            (0.0..=1.0).contains(&jitter),
+            "the backoff jitter must be between 0 and 1"
+        );
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets how many times the operation is tried in total before giving up.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `max_attempts` is 0.
+    pub fn max_attempts(mut self, max_attempts: usize) -> Backoff {
+        assert!(
+            max_attempts > 0,
+            "the operation must be attempted at least once"
+        );
+        self.max_attempts = Some(max_attempts);
+        self
+    }
+
+    /// Returns the longest delay between two attempts.
+    pub(crate) fn longest_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    /// Returns `true` if another attempt may be made after the given number of failed attempts.
+    pub(crate) fn allows_retry(&self, failures: usize) -> bool {
+        !matches!(self.max_attempts, Some(max) if failures >= max)
+    }
+
+    /// Returns the delay after the given number of failed attempts, with jitter subtracted.
+    pub(crate) fn jittered_delay(&self, failures: usize) -> Duration {
+        let delay = self.delay(failures);
+        let jitter = delay.mul_f64(self.jitter * f64::from(random(u32::MAX)) / f64::from(u32::MAX));
+        delay - jitter
+    }
+
+    /// Returns the delay after the given number of failed attempts, without jitter.
+    fn delay(&self, failures: usize) -> Duration {
+        let exp = i32::try_from(failures - 1).unwrap_or(i32::MAX);
+        let secs = self.initial.as_secs_f64() * self.factor.powi(exp);
+        if secs >= self.max_delay.as_secs_f64() {
+            self.max_delay
+        } else {
+            Duration::from_secs_f64(secs)
+        }
+    }
+}
+
+/// Runs a fallible operation until it succeeds, waiting between attempts according to a policy.
+///
+/// The closure is called to start each attempt. If the last allowed attempt fails, its error is
+/// returned.
+///
+/// See [`retry_if`] for retrying only some errors.
+///
+/// [`retry_if`]: fn.retry_if.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::io;
+/// use std::time::Duration;
+///
+/// use async_std::future::{self, Backoff};
+///
+/// let mut attempts = 0;
+/// let res = future::retry(Backoff::exponential(Duration::from_millis(1)), || {
+///     attempts += 1;
+///     let n = attempts;
+///     async move {
+///         if n < 3 {
+///             Err(io::Error::new(io::ErrorKind::ConnectionRefused, "not yet"))
+///         } else {
+///             Ok(n)
+///         }
+///     }
+/// })
+/// .await;
+/// assert_eq!(res.unwrap(), 3);
+/// #
+/// # })
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn retry<F, Fut, T, E>(backoff: Backoff, f: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_if(backoff, f, |_| true).await
+}
+
+/// Runs a fallible operation until it succeeds or fails with an error that can't be retried.
+///
+/// This is like [`retry`], except that errors for which the predicate returns `false` are
+/// returned right away.
+///
+/// [`retry`]: fn.retry.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::io;
+/// use std::time::Duration;
+///
+/// use async_std::future::{self, Backoff};
+///
+/// let backoff = Backoff::fixed(Duration::from_millis(1)).max_attempts(5);
+/// let mut attempts = 0;
+/// let res: io::Result<()> = future::retry_if(
+///     backoff,
+///     || {
+///         attempts += 1;
+///         async { Err(io::Error::from(io::ErrorKind::PermissionDenied)) }
+///     },
+///     |err| err.kind() != io::ErrorKind::PermissionDenied,
+/// )
+/// .await;
+/// assert!(res.is_err());
+/// assert_eq!(attempts, 1);
+/// #
+/// # })
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn retry_if<F, Fut, P, T, E>(backoff: Backoff, mut f: F, mut retryable: P) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: FnMut(&E) -> bool,
+{
+    let mut failures = 0;
+    loop {
+        let err = match f().await {
+            Ok(v) => return Ok(v),
+            Err(err) => err,
+        };
+
+        failures += 1;
+        if !backoff.allows_retry(failures) || !retryable(&err) {
+            return Err(err);
+        }
+        task::sleep(backoff.jittered_delay(failures)).await;
+    }
+}
@@ -2,12 +2,14 @@ use std::error::Error;
 use std::fmt;
 use std::pin::Pin;
 use std::time::Duration;
+#[cfg(feature = "unstable")]
+use std::time::Instant;
 use std::future::Future;
 
-use futures_timer::Delay;
 use pin_project_lite::pin_project;
 
 use crate::task::{Context, Poll};
+use crate::time::Delay;
 
 /// Awaits a future or times out after a duration of time.
 ///
@@ -40,8 +42,46 @@ where
     f.await
 }
 
+/// Awaits a future or times out at a deadline.
+///
+/// This is the deadline-based counterpart to [`timeout`]. Deadlines are measured on the clock of
+/// the current runtime, which only differs from [`Instant::now`] when it has been paused. See
+/// [`time::now`].
+///
+/// [`timeout`]: fn.timeout.html
+/// [`Instant::now`]: https://doc.rust-lang.org/std/time/struct.Instant.html#method.now
+/// [`time::now`]: ../time/fn.now.html
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use std::time::{Duration, Instant};
+///
+/// use async_std::future;
+///
+/// let deadline = Instant::now() + Duration::from_millis(5);
+/// assert!(future::timeout_at(deadline, future::pending::<()>()).await.is_err());
+/// assert_eq!(future::timeout_at(deadline, future::ready(1)).await, Ok(1));
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn timeout_at<F, T>(deadline: Instant, f: F) -> Result<T, TimeoutError>
+where
+    F: Future<Output = T>,
+{
+    let f = TimeoutFuture {
+        future: f,
+        delay: Delay::at(deadline),
+    };
+    f.await
+}
+
 pin_project! {
-    /// A future that times out after a duration of time.
+    /// A future that times out when its timer fires.
     struct TimeoutFuture<F> {
         #[pin]
         future: F,
@@ -58,7 +98,9 @@ impl<F: Future> Future for TimeoutFuture<F> {
         match this.future.poll(cx) {
             Poll::Ready(v) => Poll::Ready(Ok(v)),
             Poll::Pending => match this.delay.poll(cx) {
-                Poll::Ready(_) => Poll::Ready(Err(TimeoutError { _private: () })),
+                Poll::Ready(_) => {
+                    Poll::Ready(Err(TimeoutError::new(())))
+                }
                 Poll::Pending => Poll::Pending,
             },
         }
@@ -66,14 +108,64 @@ impl<F: Future> Future for TimeoutFuture<F> {
 }
 
 /// An error returned when a future times out.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
-pub struct TimeoutError {
-    _private: (),
+///
+/// The error returned by [`FutureExt::timeout`] carries the future that timed out, which can be
+/// retrieved with [`into_inner`] to keep awaiting it. The error returned by [`timeout`] doesn't
+/// carry anything.
+///
+/// [`FutureExt::timeout`]: ../future/trait.FutureExt.html#method.timeout
+/// [`into_inner`]: #method.into_inner
+/// [`timeout`]: fn.timeout.html
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct TimeoutError<F = ()> {
+    future: F,
 }
 
-impl Error for TimeoutError {}
+impl<F> TimeoutError<F> {
+    pub(crate) fn new(future: F) -> TimeoutError<F> {
+        TimeoutError { future }
+    }
+
+    /// Consumes the error, returning the future that timed out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::prelude::*;
+    /// use async_std::task;
+    ///
+    /// let slow = Box::pin(async {
+    ///     task::sleep(Duration::from_millis(50)).await;
+    ///     1
+    /// });
+    ///
+    /// // Keep awaiting the same operation after the first timeout.
+    /// let err = slow.timeout(Duration::from_millis(10)).await.unwrap_err();
+    /// let slow = err.into_inner();
+    /// assert_eq!(slow.await, 1);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn into_inner(self) -> F {
+        self.future
+    }
+}
+
+impl<F> Error for TimeoutError<F> {}
+
+impl<F> fmt::Debug for TimeoutError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("TimeoutError { .. }")
+    }
+}
 
-impl fmt::Display for TimeoutError {
+impl<F> fmt::Display for TimeoutError<F> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         "future has timed out".fmt(f)
     }
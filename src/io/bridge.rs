@@ -0,0 +1,291 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::io::{self, Read, Seek, SeekFrom, Write};
+use crate::prelude::*;
+use crate::task::{self, spawn_blocking, Context, JoinHandle, Poll};
+
+/// An adapter that turns an asynchronous reader or writer into a blocking one.
+///
+/// Every operation on the adapter blocks the current thread until the corresponding
+/// asynchronous operation completes. This lets synchronous libraries, such as decoders or archive
+/// readers expecting a [`std::io::Read`], consume asynchronous streams.
+///
+/// The adapter must only be used where blocking is fine, such as inside [`spawn_blocking`] or on
+/// a dedicated thread, and never directly inside an asynchronous task.
+///
+/// [`std::io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+/// [`spawn_blocking`]: ../task/fn.spawn_blocking.html
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use std::io::Read as _;
+///
+/// use async_std::io::{AsyncAsSync, Cursor};
+/// use async_std::task;
+///
+/// let reader = Cursor::new(b"hello world".to_vec());
+/// let text = task::spawn_blocking(move || {
+///     let mut text = String::new();
+///     AsyncAsSync::new(reader).read_to_string(&mut text)?;
+///     std::io::Result::Ok(text)
+/// })
+/// .await?;
+/// assert_eq!(text, "hello world");
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct AsyncAsSync<T> {
+    inner: T,
+}
+
+impl<T> AsyncAsSync<T> {
+    /// Wraps an asynchronous reader or writer.
+    pub fn new(inner: T) -> AsyncAsSync<T> {
+        AsyncAsSync { inner }
+    }
+
+    /// Gets a reference to the underlying reader or writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader or writer.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps the adapter, returning the underlying reader or writer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Read + Unpin> std::io::Read for AsyncAsSync<T> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        task::block_on(self.inner.read(buf))
+    }
+}
+
+impl<T: Write + Unpin> std::io::Write for AsyncAsSync<T> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        task::block_on(self.inner.write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        task::block_on(self.inner.flush())
+    }
+}
+
+impl<T: Seek + Unpin> std::io::Seek for AsyncAsSync<T> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        task::block_on(self.inner.seek(pos))
+    }
+}
+
+/// An adapter that turns a blocking reader or writer into an asynchronous one.
+///
+/// Every operation runs on the blocking thread pool, so that synchronous readers and writers,
+/// such as encoders or pipes from other libraries, can be used with asynchronous code without
+/// blocking the executor. Data is copied between the caller's buffer and a buffer owned by the
+/// adapter.
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::io::SyncAsAsync;
+/// use async_std::prelude::*;
+///
+/// let mut reader = SyncAsAsync::new(std::io::Cursor::new(b"hello world".to_vec()));
+/// let mut text = String::new();
+/// reader.read_to_string(&mut text).await?;
+/// assert_eq!(text, "hello world");
+///
+/// let mut writer = SyncAsAsync::new(Vec::new());
+/// writer.write_all(b"hello").await?;
+/// assert_eq!(writer.into_inner().await, b"hello");
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct SyncAsAsync<T> {
+    state: State<T>,
+}
+
+/// The state of a blocking reader or writer.
+enum State<T> {
+    /// The reader or writer is idle.
+    Idle(Option<Inner<T>>),
+
+    /// The reader or writer is blocked on an operation running on the blocking pool.
+    Busy(JoinHandle<State<T>>),
+}
+
+/// Inner representation of the adapter.
+struct Inner<T> {
+    /// The blocking reader or writer.
+    io: T,
+
+    /// The buffer of the operation in progress.
+    buf: Vec<u8>,
+
+    /// The result of the last operation.
+    last_op: Option<Operation>,
+}
+
+/// Possible results of an operation on the blocking reader or writer.
+enum Operation {
+    Read(io::Result<usize>),
+    Write(io::Result<usize>),
+    Flush(io::Result<()>),
+}
+
+impl<T: Send + 'static> SyncAsAsync<T> {
+    /// Wraps a blocking reader or writer.
+    pub fn new(io: T) -> SyncAsAsync<T> {
+        SyncAsAsync {
+            state: State::Idle(Some(Inner {
+                io,
+                buf: Vec::new(),
+                last_op: None,
+            })),
+        }
+    }
+
+    /// Waits for the operation in progress to complete and returns the underlying reader or
+    /// writer.
+    pub async fn into_inner(mut self) -> T {
+        loop {
+            match self.state {
+                State::Idle(opt) => return opt.unwrap().io,
+                State::Busy(task) => self.state = task.await,
+            }
+        }
+    }
+
+    /// Runs an operation on the blocking pool, or returns the result of the last one if it
+    /// matches.
+    ///
+    /// The buffer is prepared for the operation before it is started.
+    fn poll_op<R>(
+        &mut self,
+        cx: &mut Context<'_>,
+        take: fn(&mut Option<Operation>) -> Option<R>,
+        prepare: impl FnOnce(&mut Vec<u8>),
+        op: fn(&mut Inner<T>) -> Operation,
+    ) -> Poll<R> {
+        let mut prepare = Some(prepare);
+        loop {
+            match &mut self.state {
+                State::Idle(opt) => {
+                    // Check if the operation has completed.
+                    if let Some(res) = take(&mut opt.as_mut().unwrap().last_op) {
+                        return Poll::Ready(res);
+                    }
+
+                    // Start the operation asynchronously.
+                    let mut inner = opt.take().unwrap();
+                    (prepare.take().unwrap())(&mut inner.buf);
+                    self.state = State::Busy(spawn_blocking(move || {
+                        inner.last_op = Some(op(&mut inner));
+                        State::Idle(Some(inner))
+                    }));
+                }
+                // Poll the operation the adapter is currently blocked on.
+                State::Busy(task) => self.state = futures_core::ready!(Pin::new(task).poll(cx)),
+            }
+        }
+    }
+}
+
+impl<T: std::io::Read + Send + 'static> Read for SyncAsAsync<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            let res = futures_core::ready!(this.poll_op(
+                cx,
+                |op| match op.take() {
+                    Some(Operation::Read(res)) => Some(res),
+                    _ => None,
+                },
+                |data| data.resize(buf.len(), 0),
+                |inner| Operation::Read(inner.io.read(&mut inner.buf)),
+            ));
+            let n = res?;
+
+            // If more data was read than fits into the buffer, read again.
+            if n <= buf.len() {
+                if let State::Idle(Some(inner)) = &this.state {
+                    buf[..n].copy_from_slice(&inner.buf[..n]);
+                }
+                return Poll::Ready(Ok(n));
+            }
+        }
+    }
+}
+
+impl<T: std::io::Write + Send + 'static> Write for SyncAsAsync<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            let res = futures_core::ready!(this.poll_op(
+                cx,
+                |op| match op.take() {
+                    Some(Operation::Write(res)) => Some(res),
+                    _ => None,
+                },
+                |data| {
+                    data.clear();
+                    data.extend_from_slice(buf);
+                },
+                |inner| Operation::Write(inner.io.write(&inner.buf)),
+            ));
+            let n = res?;
+
+            // If more data was written than was passed in this time, write again.
+            if n <= buf.len() {
+                return Poll::Ready(Ok(n));
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_op(
+            cx,
+            |op| match op.take() {
+                Some(Operation::Flush(res)) => Some(res),
+                _ => None,
+            },
+            |_| {},
+            |inner| Operation::Flush(inner.io.flush()),
+        )
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl<T> Unpin for SyncAsAsync<T> {}
+
+impl<T> fmt::Debug for SyncAsAsync<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("SyncAsAsync { .. }")
+    }
+}
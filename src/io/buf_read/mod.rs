@@ -1,5 +1,7 @@
 mod lines;
 mod read_line;
+#[cfg(feature = "unstable")]
+mod read_line_resumable;
 mod read_until;
 mod split;
 
@@ -7,6 +9,8 @@ pub use lines::Lines;
 pub use split::Split;
 
 use read_line::ReadLineFuture;
+#[cfg(feature = "unstable")]
+use read_line_resumable::ReadLineResumableFuture;
 use read_until::ReadUntilFuture;
 
 use std::mem;
@@ -79,6 +83,12 @@ extension_trait! {
 
             If successful, this function will return the total number of bytes read.
 
+            # Cancel safety
+
+            This method is cancel safe. Bytes are appended to `buf` as soon as they are read, so
+            if the future is dropped before it completes, `buf` holds the part read so far and
+            calling this method again reads the rest.
+
             # Examples
 
             ```no_run
@@ -155,7 +165,14 @@ extension_trait! {
             `buf` may contain some bytes already read in the event that all data read so far
             was valid UTF-8.
 
+            # Cancel safety
+
+            This method is not cancel safe. If the future is dropped before it completes, `buf`
+            keeps its previous contents, but the part of the line read so far is lost. Use
+            [`read_line_resumable`] to resume reading the line after it was interrupted.
+
             [`read_until`]: #method.read_until
+            [`read_line_resumable`]: #method.read_line_resumable
 
             # Examples
 
@@ -183,12 +200,77 @@ extension_trait! {
         {
             ReadLineFuture {
                 reader: self,
+                len: buf.len(),
                 bytes: unsafe { mem::replace(buf.as_mut_vec(), Vec::new()) },
                 buf,
                 read: 0,
             }
         }
 
+        #[doc = r#"
+            Reads all bytes until a newline (the 0xA byte) is reached, and appends them to
+            `buf`, keeping the part of the line read so far in `partial`.
+
+            Bytes are moved into `partial` as soon as they are read. Once the newline or EOF is
+            reached, the whole line is checked to be valid UTF-8 and appended to `buf`, and
+            `partial` is left empty.
+
+            If successful, this function returns the length of the line. If it returns `Ok(0)`,
+            the stream has reached EOF.
+
+            # Errors
+
+            This function has the same error semantics as [`read_until`], and returns an error
+            of the kind [`ErrorKind::InvalidData`] if the line is not valid UTF-8, in which case
+            the line is discarded.
+
+            # Cancel safety
+
+            This method is cancel safe. If the future is dropped before it completes, calling
+            this method again with the same `partial` resumes reading the line where it stopped.
+
+            [`read_until`]: #method.read_until
+            [`ErrorKind::InvalidData`]: enum.ErrorKind.html#variant.InvalidData
+
+            # Examples
+
+            ```no_run
+            # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+            #
+            use std::time::Duration;
+
+            use async_std::future;
+            use async_std::io;
+            use async_std::prelude::*;
+
+            let mut stdin = io::BufReader::new(io::stdin());
+            let mut line = String::new();
+            let mut partial = Vec::new();
+
+            loop {
+                let read = stdin.read_line_resumable(&mut line, &mut partial);
+                match future::timeout(Duration::from_secs(10), read).await {
+                    Ok(res) => break res?,
+                    Err(_) => println!("Still there?"),
+                }
+            };
+            #
+            # Ok(()) }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn read_line_resumable<'a>(
+            &'a mut self,
+            buf: &'a mut String,
+            partial: &'a mut Vec<u8>,
+        ) -> impl Future<Output = io::Result<usize>> + 'a [ReadLineResumableFuture<'a, Self>]
+        where
+            Self: Unpin,
+        {
+            ReadLineResumableFuture { reader: self, buf, partial }
+        }
+
         #[doc = r#"
             Returns a stream over the lines of this byte stream.
 
@@ -13,6 +13,8 @@ pub struct ReadLineFuture<'a, T: Unpin + ?Sized> {
     pub(crate) reader: &'a mut T,
     pub(crate) buf: &'a mut String,
     pub(crate) bytes: Vec<u8>,
+    /// The length of `buf` before the line was read.
+    pub(crate) len: usize,
     pub(crate) read: usize,
 }
 
@@ -25,6 +27,7 @@ impl<T: BufRead + Unpin + ?Sized> Future for ReadLineFuture<'_, T> {
             buf,
             bytes,
             read,
+            ..
         } = &mut *self;
         let reader = Pin::new(reader);
 
@@ -49,3 +52,13 @@ impl<T: BufRead + Unpin + ?Sized> Future for ReadLineFuture<'_, T> {
         }
     }
 }
+
+impl<T: Unpin + ?Sized> Drop for ReadLineFuture<'_, T> {
+    fn drop(&mut self) {
+        // Give back the previous contents of the buffer if the line wasn't read.
+        if self.buf.is_empty() && self.len > 0 {
+            self.bytes.truncate(self.len);
+            mem::swap(unsafe { self.buf.as_mut_vec() }, &mut self.bytes);
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use std::mem;
+use std::pin::Pin;
+use std::future::Future;
+
+use super::read_until_internal;
+use crate::io::{self, BufRead};
+use crate::task::{Context, Poll};
+
+#[doc(hidden)]
+#[allow(missing_debug_implementations)]
+pub struct ReadLineResumableFuture<'a, T: Unpin + ?Sized> {
+    pub(crate) reader: &'a mut T,
+    pub(crate) buf: &'a mut String,
+    pub(crate) partial: &'a mut Vec<u8>,
+}
+
+impl<T: BufRead + Unpin + ?Sized> Future for ReadLineResumableFuture<'_, T> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self {
+            reader,
+            buf,
+            partial,
+        } = &mut *self;
+
+        // Bytes are moved into `partial` as soon as they are read, so nothing is lost if the
+        // future is dropped.
+        let mut read = 0;
+        futures_core::ready!(read_until_internal(
+            Pin::new(&mut **reader),
+            cx,
+            b'\n',
+            partial,
+            &mut read
+        ))?;
+
+        let bytes = mem::take(&mut **partial);
+        match String::from_utf8(bytes) {
+            Ok(line) => {
+                buf.push_str(&line);
+                Poll::Ready(Ok(line.len()))
+            }
+            Err(err) => Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                err.utf8_error(),
+            ))),
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex};
+
+use crate::io::{self, ReadBuf};
+use crate::task::Poll;
+
+/// A pool of reusable read buffers.
+///
+/// Allocating a fresh buffer for every read, and zeroing it before a reader may write into it,
+/// shows up in the profiles of servers reading from many connections. A pool hands out
+/// [`PooledBuf`]s that return to it when dropped, keeping both their allocation and their
+/// initialized memory, so that reading into a reused buffer costs neither.
+///
+/// Cloning the pool gives another handle to the same buffers.
+///
+/// [`PooledBuf`]: struct.PooledBuf.html
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::io::{BufferPool, Cursor};
+/// use async_std::prelude::*;
+///
+/// let pool = BufferPool::new(4096);
+/// let mut reader = Cursor::new(b"hello world".to_vec());
+///
+/// let mut buf = pool.get();
+/// while reader.read_buf(&mut buf).await? > 0 {}
+/// assert_eq!(&buf[..], b"hello world");
+///
+/// // The buffer goes back into the pool.
+/// drop(buf);
+/// assert_eq!(pool.pooled(), 1);
+/// #
+/// # Ok(()) }) }
+/// ```
+#[derive(Clone)]
+pub struct BufferPool {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    buffers: Mutex<Vec<Vec<u8>>>,
+    buffer_size: usize,
+    max_pooled: usize,
+}
+
+impl BufferPool {
+    /// Creates a pool of buffers of `buffer_size` bytes, keeping at most 64 idle buffers.
+    pub fn new(buffer_size: usize) -> BufferPool {
+        BufferPool::with_max_pooled(buffer_size, 64)
+    }
+
+    /// Creates a pool of buffers of `buffer_size` bytes, keeping at most `max_pooled` idle
+    /// buffers.
+    ///
+    /// Buffers returned to a full pool are deallocated.
+    pub fn with_max_pooled(buffer_size: usize, max_pooled: usize) -> BufferPool {
+        BufferPool {
+            inner: Arc::new(Inner {
+                buffers: Mutex::new(Vec::new()),
+                buffer_size,
+                max_pooled,
+            }),
+        }
+    }
+
+    /// Takes an empty buffer from the pool, allocating one if the pool is empty.
+    pub fn get(&self) -> PooledBuf {
+        let buf = self.inner.buffers.lock().unwrap().pop();
+        PooledBuf {
+            buf: buf.unwrap_or_else(|| Vec::with_capacity(self.inner.buffer_size)),
+            filled: 0,
+            pool: Some(self.inner.clone()),
+        }
+    }
+
+    /// Returns the size of the buffers in the pool.
+    pub fn buffer_size(&self) -> usize {
+        self.inner.buffer_size
+    }
+
+    /// Returns the number of idle buffers in the pool.
+    pub fn pooled(&self) -> usize {
+        self.inner.buffers.lock().unwrap().len()
+    }
+}
+
+impl fmt::Debug for BufferPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufferPool")
+            .field("buffer_size", &self.inner.buffer_size)
+            .field("pooled", &self.pooled())
+            .finish()
+    }
+}
+
+/// A buffer taken from a [`BufferPool`].
+///
+/// The buffer dereferences to the bytes read into it. It grows when a read finds it full, and
+/// returns to its pool when dropped.
+///
+/// [`BufferPool`]: struct.BufferPool.html
+pub struct PooledBuf {
+    /// The buffer, whose length is the number of initialized bytes.
+    buf: Vec<u8>,
+
+    /// The number of bytes read into the buffer.
+    filled: usize,
+
+    pool: Option<Arc<Inner>>,
+}
+
+impl PooledBuf {
+    /// Returns the number of bytes read into the buffer.
+    pub fn len(&self) -> usize {
+        self.filled
+    }
+
+    /// Returns `true` if no bytes have been read into the buffer.
+    pub fn is_empty(&self) -> bool {
+        self.filled == 0
+    }
+
+    /// Returns the number of bytes the buffer can hold without growing.
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Shortens the buffer to `len` bytes.
+    ///
+    /// This has no effect if the buffer is already shorter.
+    pub fn truncate(&mut self, len: usize) {
+        self.filled = self.filled.min(len);
+    }
+
+    /// Removes all bytes from the buffer.
+    pub fn clear(&mut self) {
+        self.filled = 0;
+    }
+
+    /// Detaches the buffer from its pool, returning the bytes read into it.
+    pub fn into_vec(mut self) -> Vec<u8> {
+        let mut buf = std::mem::take(&mut self.buf);
+        buf.truncate(self.filled);
+        self.pool = None;
+        buf
+    }
+}
+
+impl ReadBuf for PooledBuf {
+    fn poll_fill<F>(&mut self, read: F) -> Poll<io::Result<usize>>
+    where
+        F: FnOnce(&mut [u8]) -> Poll<io::Result<usize>>,
+    {
+        if self.filled == self.buf.capacity() {
+            self.buf.reserve(self.filled.max(32));
+        }
+
+        // Memory is only initialized the first time it is used.
+        if self.buf.len() < self.buf.capacity() {
+            let capacity = self.buf.capacity();
+            self.buf.resize(capacity, 0);
+        }
+
+        let res = read(&mut self.buf[self.filled..]);
+        if let Poll::Ready(Ok(n)) = res {
+            self.filled += n;
+        }
+        res
+    }
+}
+
+impl Deref for PooledBuf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buf[..self.filled]
+    }
+}
+
+impl DerefMut for PooledBuf {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buf[..self.filled]
+    }
+}
+
+impl AsRef<[u8]> for PooledBuf {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl Drop for PooledBuf {
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.take() {
+            let mut buf = std::mem::take(&mut self.buf);
+
+            // Buffers that have grown are shrunk back to the size of the pool.
+            if buf.capacity() > pool.buffer_size {
+                buf.truncate(pool.buffer_size);
+                buf.shrink_to_fit();
+            }
+
+            let mut buffers = pool.buffers.lock().unwrap();
+            if buffers.len() < pool.max_pooled {
+                buffers.push(buf);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for PooledBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PooledBuf")
+            .field("len", &self.filled)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
@@ -0,0 +1,43 @@
+//! HTTP/1.1 chunked transfer coding.
+//!
+//! A message body sent with `Transfer-Encoding: chunked` is split into chunks, each preceded by
+//! its length in hexadecimal, and ends with an empty chunk followed by optional trailer fields,
+//! as described in [RFC 7230, section 4.1]. This lets a sender stream a body whose length isn't
+//! known up front, while the receiver still knows where the body ends.
+//!
+//! [`ChunkedReader`] decodes such a body from a buffered reader, and [`ChunkedWriter`] encodes
+//! one into a writer. Both only deal with the body: reading and writing the request or response
+//! head is left to the caller.
+//!
+//! [RFC 7230, section 4.1]: https://tools.ietf.org/html/rfc7230#section-4.1
+//! [`ChunkedReader`]: struct.ChunkedReader.html
+//! [`ChunkedWriter`]: struct.ChunkedWriter.html
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::io::chunked::{ChunkedReader, ChunkedWriter};
+//! use async_std::prelude::*;
+//!
+//! let mut writer = ChunkedWriter::new(Vec::new());
+//! writer.write_all(b"hello ").await?;
+//! writer.write_all(b"world").await?;
+//! writer.finish().await?;
+//!
+//! let encoded = writer.into_inner();
+//! assert_eq!(encoded, b"6\r\nhello \r\n5\r\nworld\r\n0\r\n\r\n");
+//!
+//! let mut body = String::new();
+//! ChunkedReader::new(&encoded[..]).read_to_string(&mut body).await?;
+//! assert_eq!(body, "hello world");
+//! #
+//! # Ok(()) }) }
+//! ```
+
+pub use reader::ChunkedReader;
+pub use writer::ChunkedWriter;
+
+mod reader;
+mod writer;
@@ -0,0 +1,229 @@
+use std::fmt;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::io::{self, BufRead, Read};
+use crate::task::{Context, Poll};
+
+/// The longest chunk header or trailer line that is accepted.
+const MAX_LINE_LEN: usize = 4096;
+
+pin_project! {
+    /// A reader of a body in the chunked transfer coding.
+    ///
+    /// Reading yields the data of the chunks, and returns `Ok(0)` once the last chunk and the
+    /// trailer have been read. The trailer fields are skipped. Bytes following the body are left
+    /// in the underlying reader, so that another message can be read from it.
+    ///
+    /// Malformed framing fails with an error of the `InvalidData` kind, and a body that is cut
+    /// short fails with an error of the `UnexpectedEof` kind.
+    ///
+    /// See the [module documentation] for an example.
+    ///
+    /// [module documentation]: index.html
+    pub struct ChunkedReader<R> {
+        #[pin]
+        inner: R,
+        state: State,
+        line: Vec<u8>,
+    }
+}
+
+/// The part of the body a reader is in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum State {
+    /// Reading the line with the size of the next chunk.
+    Size,
+
+    /// Reading the data of a chunk, with the given number of bytes left.
+    Data(u64),
+
+    /// Reading the line break after the data of a chunk.
+    DataEnd,
+
+    /// Reading the trailer after the last chunk.
+    Trailer,
+
+    /// The body has been read.
+    Done,
+}
+
+impl<R> ChunkedReader<R> {
+    /// Creates a reader decoding a chunked body from `inner`.
+    pub fn new(inner: R) -> ChunkedReader<R> {
+        ChunkedReader {
+            inner,
+            state: State::Size,
+            line: Vec::new(),
+        }
+    }
+
+    /// Returns `true` once the whole body has been read.
+    pub fn is_done(&self) -> bool {
+        self.state == State::Done
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading from it directly before the body has been read corrupts the body.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `ChunkedReader`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for ChunkedReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut this = self.project();
+
+        loop {
+            match *this.state {
+                State::Size => {
+                    futures_core::ready!(poll_line(this.inner.as_mut(), this.line, cx))?;
+                    let size = parse_size(this.line)?;
+                    this.line.clear();
+                    *this.state = if size == 0 {
+                        State::Trailer
+                    } else {
+                        State::Data(size)
+                    };
+                }
+                State::Data(remaining) => {
+                    if buf.is_empty() {
+                        return Poll::Ready(Ok(0));
+                    }
+
+                    let available = futures_core::ready!(this.inner.as_mut().poll_fill_buf(cx))?;
+                    if available.is_empty() {
+                        return Poll::Ready(Err(unexpected_eof()));
+                    }
+
+                    let n = available.len().min(buf.len()).min(remaining as usize);
+                    buf[..n].copy_from_slice(&available[..n]);
+                    this.inner.as_mut().consume(n);
+
+                    let remaining = remaining - n as u64;
+                    *this.state = if remaining == 0 {
+                        State::DataEnd
+                    } else {
+                        State::Data(remaining)
+                    };
+                    return Poll::Ready(Ok(n));
+                }
+                State::DataEnd => {
+                    futures_core::ready!(poll_line(this.inner.as_mut(), this.line, cx))?;
+                    if !trim_line_break(this.line).is_empty() {
+                        return Poll::Ready(Err(invalid_data("missing line break after chunk")));
+                    }
+                    this.line.clear();
+                    *this.state = State::Size;
+                }
+                State::Trailer => {
+                    futures_core::ready!(poll_line(this.inner.as_mut(), this.line, cx))?;
+                    // The trailer ends with an empty line.
+                    if trim_line_break(this.line).is_empty() {
+                        *this.state = State::Done;
+                    }
+                    this.line.clear();
+                }
+                State::Done => return Poll::Ready(Ok(0)),
+            }
+        }
+    }
+}
+
+impl<R: fmt::Debug> fmt::Debug for ChunkedReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkedReader")
+            .field("inner", &self.inner)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// Reads from `inner` into `line` until it ends with a line feed.
+fn poll_line<R: BufRead>(
+    mut inner: Pin<&mut R>,
+    line: &mut Vec<u8>,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<()>> {
+    loop {
+        let available = futures_core::ready!(inner.as_mut().poll_fill_buf(cx))?;
+        if available.is_empty() {
+            return Poll::Ready(Err(unexpected_eof()));
+        }
+
+        let (n, found) = match memchr::memchr(b'\n', available) {
+            Some(i) => (i + 1, true),
+            None => (available.len(), false),
+        };
+        if line.len() + n > MAX_LINE_LEN {
+            return Poll::Ready(Err(invalid_data(
+                "chunk header or trailer line is too long",
+            )));
+        }
+        line.extend_from_slice(&available[..n]);
+        inner.as_mut().consume(n);
+
+        if found {
+            return Poll::Ready(Ok(()));
+        }
+    }
+}
+
+/// Parses the size of a chunk from its header line, ignoring chunk extensions.
+///
+/// The size must be one or more hex digits, directly followed by the line break or the `;` of an
+/// extension. Signs and padding are rejected, so that this reader never frames a body differently
+/// from a strict parser in front of it.
+fn parse_size(line: &[u8]) -> io::Result<u64> {
+    let line = trim_line_break(line);
+    let digits = match line.iter().position(|&b| b == b';') {
+        Some(i) => &line[..i],
+        None => line,
+    };
+
+    if digits.is_empty() {
+        return Err(invalid_data("invalid chunk size"));
+    }
+
+    let mut size: u64 = 0;
+    for &b in digits {
+        let digit = (b as char)
+            .to_digit(16)
+            .ok_or_else(|| invalid_data("invalid chunk size"))?;
+        size = size
+            .checked_mul(16)
+            .and_then(|size| size.checked_add(u64::from(digit)))
+            .ok_or_else(|| invalid_data("chunk size is too large"))?;
+    }
+    Ok(size)
+}
+
+/// Strips the CRLF or bare LF from the end of a line.
+fn trim_line_break(line: &[u8]) -> &[u8] {
+    let line = line.strip_suffix(b"\n").unwrap_or(line);
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn unexpected_eof() -> io::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "chunked body ended early")
+}
@@ -0,0 +1,164 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::io::{self, Write};
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// A writer of a body in the chunked transfer coding.
+    ///
+    /// Every write becomes one chunk, so writing through a [`BufWriter`] avoids sending many
+    /// small chunks. The body must be ended with [`finish`], which writes the last chunk and
+    /// flushes the writer. Closing the writer finishes the body too, and then closes the
+    /// underlying writer.
+    ///
+    /// Dropping the writer without finishing the body leaves it incomplete.
+    ///
+    /// See the [module documentation] for an example.
+    ///
+    /// [`BufWriter`]: ../struct.BufWriter.html
+    /// [`finish`]: #method.finish
+    /// [module documentation]: index.html
+    pub struct ChunkedWriter<W> {
+        #[pin]
+        inner: W,
+        buf: Vec<u8>,
+        written: usize,
+        finished: bool,
+    }
+}
+
+impl<W> ChunkedWriter<W> {
+    /// Creates a writer encoding a chunked body into `inner`.
+    pub fn new(inner: W) -> ChunkedWriter<W> {
+        ChunkedWriter {
+            inner,
+            buf: Vec::new(),
+            written: 0,
+            finished: false,
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing to it directly before the body has been finished corrupts the body.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `ChunkedWriter`, returning the underlying writer.
+    ///
+    /// Encoded bytes that haven't been flushed yet are lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Writes the last chunk and flushes the underlying writer.
+    ///
+    /// The underlying writer is left open, so that another message can be written to it.
+    /// Writing after the body has been finished fails.
+    pub fn finish(&mut self) -> impl Future<Output = io::Result<()>> + '_
+    where
+        W: Write + Unpin,
+    {
+        crate::future::poll_fn(move |cx| Pin::new(&mut *self).poll_finish(cx))
+    }
+
+    /// Polls writing the last chunk and flushing the underlying writer.
+    fn poll_finish(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>
+    where
+        W: Write,
+    {
+        let this = self.as_mut().project();
+        if !*this.finished {
+            this.buf.extend_from_slice(b"0\r\n\r\n");
+            *this.finished = true;
+        }
+        futures_core::ready!(self.as_mut().poll_write_buf(cx))?;
+        self.project().inner.poll_flush(cx)
+    }
+
+    /// Writes out the encoded bytes that haven't been written yet.
+    fn poll_write_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>
+    where
+        W: Write,
+    {
+        let mut this = self.project();
+        while *this.written < this.buf.len() {
+            match futures_core::ready!(
+                this.inner
+                    .as_mut()
+                    .poll_write(cx, &this.buf[*this.written..])
+            )? {
+                0 => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write chunk to the writer",
+                    )));
+                }
+                n => *this.written += n,
+            }
+        }
+        this.buf.clear();
+        *this.written = 0;
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: Write> Write for ChunkedWriter<W> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.finished {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "chunked body has already been finished",
+            )));
+        }
+
+        // Only one chunk is buffered at a time.
+        futures_core::ready!(self.as_mut().poll_write_buf(cx))?;
+        let this = self.project();
+
+        // An empty chunk would end the body.
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        this.buf
+            .extend_from_slice(format!("{:x}\r\n", buf.len()).as_bytes());
+        this.buf.extend_from_slice(buf);
+        this.buf.extend_from_slice(b"\r\n");
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        futures_core::ready!(self.as_mut().poll_write_buf(cx))?;
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        futures_core::ready!(self.as_mut().poll_finish(cx))?;
+        self.project().inner.poll_close(cx)
+    }
+}
+
+impl<W: fmt::Debug> fmt::Debug for ChunkedWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkedWriter")
+            .field("inner", &self.inner)
+            .field("buffered", &(self.buf.len() - self.written))
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
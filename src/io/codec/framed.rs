@@ -0,0 +1,443 @@
+use std::fmt;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::io::codec::{Decoder, Encoder};
+use crate::io::{self, Read, Write, DEFAULT_BUF_SIZE};
+use crate::sink::Sink;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// The number of encoded bytes at which a sink stops accepting frames until they are written.
+const BACKPRESSURE_BOUNDARY: usize = DEFAULT_BUF_SIZE;
+
+pin_project! {
+    /// A stream of frames decoded from a reader.
+    ///
+    /// Bytes are read from the reader as needed and split into frames by a [`Decoder`]. The
+    /// stream ends when the reader reaches its end, or after the first error.
+    ///
+    /// [`Decoder`]: trait.Decoder.html
+    pub struct FramedRead<T, D> {
+        #[pin]
+        inner: T,
+        decoder: D,
+        state: ReadState,
+    }
+}
+
+pin_project! {
+    /// A sink of frames encoded into a writer.
+    ///
+    /// Frames are encoded by an [`Encoder`] into a buffer, which is written to the writer when
+    /// the sink is flushed, or once it has grown large enough that frames should stop piling up.
+    ///
+    /// [`Encoder`]: trait.Encoder.html
+    pub struct FramedWrite<T, E> {
+        #[pin]
+        inner: T,
+        encoder: E,
+        buf: Vec<u8>,
+    }
+}
+
+pin_project! {
+    /// A stream and sink of frames over a single reader and writer.
+    ///
+    /// This combines a [`FramedRead`] and a [`FramedWrite`] sharing one codec, which is the
+    /// usual shape of a connection speaking a framed protocol.
+    ///
+    /// [`FramedRead`]: struct.FramedRead.html
+    /// [`FramedWrite`]: struct.FramedWrite.html
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::io::codec::{Framed, LinesCodec};
+    /// use async_std::net::TcpStream;
+    /// use async_std::prelude::*;
+    /// use async_std::sink::SinkExt;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// let mut lines = Framed::new(stream, LinesCodec::new());
+    ///
+    /// while let Some(line) = lines.next().await {
+    ///     SinkExt::send(&mut lines, line?).await?;
+    /// }
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub struct Framed<T, C> {
+        #[pin]
+        inner: T,
+        codec: C,
+        read: ReadState,
+        write_buf: Vec<u8>,
+    }
+}
+
+/// The buffered input of a framed reader.
+#[derive(Default)]
+struct ReadState {
+    buf: Vec<u8>,
+
+    /// Whether bytes have been read since the decoder last returned `Ok(None)`.
+    readable: bool,
+
+    /// Whether the end of the stream has been reached.
+    eof: bool,
+}
+
+impl<T, D> FramedRead<T, D> {
+    /// Creates a stream of frames decoded from `inner` by `decoder`.
+    pub fn new(inner: T, decoder: D) -> FramedRead<T, D> {
+        FramedRead {
+            inner,
+            decoder,
+            state: ReadState::default(),
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading from it directly may corrupt the stream of frames.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying reader.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.project().inner
+    }
+
+    /// Unwraps the stream, returning the underlying reader.
+    ///
+    /// Bytes that have been read but not decoded yet are lost, see [`read_buffer`].
+    ///
+    /// [`read_buffer`]: #method.read_buffer
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns a reference to the decoder.
+    pub fn decoder(&self) -> &D {
+        &self.decoder
+    }
+
+    /// Returns a mutable reference to the decoder.
+    pub fn decoder_mut(&mut self) -> &mut D {
+        &mut self.decoder
+    }
+
+    /// Returns the bytes that have been read but not decoded yet.
+    pub fn read_buffer(&self) -> &[u8] {
+        &self.state.buf
+    }
+}
+
+impl<T: Read, D: Decoder> Stream for FramedRead<T, D> {
+    type Item = io::Result<D::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        poll_decode(this.inner, this.decoder, this.state, cx)
+    }
+}
+
+impl<T: fmt::Debug, D: fmt::Debug> fmt::Debug for FramedRead<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FramedRead")
+            .field("inner", &self.inner)
+            .field("decoder", &self.decoder)
+            .field("buffered", &self.state.buf.len())
+            .finish()
+    }
+}
+
+impl<T, E> FramedWrite<T, E> {
+    /// Creates a sink of frames encoded into `inner` by `encoder`.
+    pub fn new(inner: T, encoder: E) -> FramedWrite<T, E> {
+        FramedWrite {
+            inner,
+            encoder,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing to it directly may corrupt the stream of frames.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying writer.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.project().inner
+    }
+
+    /// Unwraps the sink, returning the underlying writer.
+    ///
+    /// Frames that haven't been flushed yet are lost.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns a reference to the encoder.
+    pub fn encoder(&self) -> &E {
+        &self.encoder
+    }
+
+    /// Returns a mutable reference to the encoder.
+    pub fn encoder_mut(&mut self) -> &mut E {
+        &mut self.encoder
+    }
+}
+
+impl<T: Write, E: Encoder<Item>, Item> Sink<Item> for FramedWrite<T, E> {
+    type Error = io::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.project();
+        poll_ready(this.inner, this.buf, cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Item) -> io::Result<()> {
+        let this = self.project();
+        this.encoder.encode(item, this.buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.project();
+        poll_flush(this.inner, this.buf, cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.project();
+        poll_close(this.inner, this.buf, cx)
+    }
+}
+
+impl<T: fmt::Debug, E: fmt::Debug> fmt::Debug for FramedWrite<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FramedWrite")
+            .field("inner", &self.inner)
+            .field("encoder", &self.encoder)
+            .field("buffered", &self.buf.len())
+            .finish()
+    }
+}
+
+impl<T, C> Framed<T, C> {
+    /// Creates a stream and sink of frames over `inner`, encoded and decoded by `codec`.
+    pub fn new(inner: T, codec: C) -> Framed<T, C> {
+        Framed {
+            inner,
+            codec,
+            read: ReadState::default(),
+            write_buf: Vec::new(),
+        }
+    }
+
+    /// Gets a reference to the underlying I/O object.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying I/O object.
+    ///
+    /// Reading from or writing to it directly may corrupt the streams of frames.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Gets a pinned mutable reference to the underlying I/O object.
+    pub fn get_pin_mut(self: Pin<&mut Self>) -> Pin<&mut T> {
+        self.project().inner
+    }
+
+    /// Unwraps the adapter, returning the underlying I/O object.
+    ///
+    /// Bytes that have been read but not decoded yet and frames that haven't been flushed yet
+    /// are lost.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns a reference to the codec.
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Returns a mutable reference to the codec.
+    pub fn codec_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    /// Returns the bytes that have been read but not decoded yet.
+    pub fn read_buffer(&self) -> &[u8] {
+        &self.read.buf
+    }
+}
+
+impl<T: Read, C: Decoder> Stream for Framed<T, C> {
+    type Item = io::Result<C::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        poll_decode(this.inner, this.codec, this.read, cx)
+    }
+}
+
+impl<T: Write, C: Encoder<Item>, Item> Sink<Item> for Framed<T, C> {
+    type Error = io::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.project();
+        poll_ready(this.inner, this.write_buf, cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Item) -> io::Result<()> {
+        let this = self.project();
+        this.codec.encode(item, this.write_buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.project();
+        poll_flush(this.inner, this.write_buf, cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.project();
+        poll_close(this.inner, this.write_buf, cx)
+    }
+}
+
+impl<T: fmt::Debug, C: fmt::Debug> fmt::Debug for Framed<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Framed")
+            .field("inner", &self.inner)
+            .field("codec", &self.codec)
+            .finish()
+    }
+}
+
+/// Decodes the next frame, reading more bytes as needed.
+fn poll_decode<T: Read, D: Decoder>(
+    mut inner: Pin<&mut T>,
+    decoder: &mut D,
+    state: &mut ReadState,
+    cx: &mut Context<'_>,
+) -> Poll<Option<io::Result<D::Item>>> {
+    loop {
+        if state.eof {
+            return Poll::Ready(match decoder.decode_eof(&mut state.buf) {
+                Ok(frame) => frame.map(Ok),
+                Err(err) => {
+                    state.buf.clear();
+                    Some(Err(err))
+                }
+            });
+        }
+
+        if state.readable {
+            match decoder.decode(&mut state.buf) {
+                Ok(Some(frame)) => return Poll::Ready(Some(Ok(frame))),
+                Ok(None) => state.readable = false,
+                Err(err) => {
+                    // A decoding error ends the stream.
+                    state.buf.clear();
+                    state.eof = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+        }
+
+        let len = state.buf.len();
+        state.buf.resize(len + DEFAULT_BUF_SIZE, 0);
+        let res = inner.as_mut().poll_read(cx, &mut state.buf[len..]);
+        let n = match &res {
+            Poll::Ready(Ok(n)) => *n,
+            _ => 0,
+        };
+        state.buf.truncate(len + n);
+
+        match futures_core::ready!(res) {
+            Ok(0) => state.eof = true,
+            Ok(_) => state.readable = true,
+            Err(err) => return Poll::Ready(Some(Err(err))),
+        }
+    }
+}
+
+/// Writes out the encoded frames if enough of them have piled up.
+fn poll_ready<T: Write>(
+    inner: Pin<&mut T>,
+    buf: &mut Vec<u8>,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<()>> {
+    if buf.len() >= BACKPRESSURE_BOUNDARY {
+        poll_write_buf(inner, buf, cx)
+    } else {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Writes out the encoded frames and flushes the writer.
+fn poll_flush<T: Write>(
+    mut inner: Pin<&mut T>,
+    buf: &mut Vec<u8>,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<()>> {
+    futures_core::ready!(poll_write_buf(inner.as_mut(), buf, cx))?;
+    inner.poll_flush(cx)
+}
+
+/// Writes out the encoded frames and closes the writer.
+fn poll_close<T: Write>(
+    mut inner: Pin<&mut T>,
+    buf: &mut Vec<u8>,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<()>> {
+    futures_core::ready!(poll_write_buf(inner.as_mut(), buf, cx))?;
+    inner.poll_close(cx)
+}
+
+fn poll_write_buf<T: Write>(
+    mut inner: Pin<&mut T>,
+    buf: &mut Vec<u8>,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<()>> {
+    let mut written = 0;
+    let res = loop {
+        if written == buf.len() {
+            break Poll::Ready(Ok(()));
+        }
+        match inner.as_mut().poll_write(cx, &buf[written..]) {
+            Poll::Ready(Ok(0)) => {
+                break Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write frames to the writer",
+                )));
+            }
+            Poll::Ready(Ok(n)) => written += n,
+            Poll::Ready(Err(err)) => break Poll::Ready(Err(err)),
+            Poll::Pending => break Poll::Pending,
+        }
+    };
+    buf.drain(..written);
+    res
+}
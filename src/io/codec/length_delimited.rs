@@ -0,0 +1,139 @@
+use std::convert::TryFrom;
+
+use crate::io;
+use crate::io::codec::{Decoder, Encoder};
+
+/// A codec for frames preceded by their length.
+///
+/// Each frame is sent as a length field followed by that many bytes of payload. By default, the
+/// length field is a big-endian `u32` and frames are limited to 8 MiB, which covers the framing
+/// of most binary protocols. Frames are decoded into `Vec<u8>`s, and anything that can be
+/// viewed as a byte slice can be encoded.
+///
+/// # Examples
+///
+/// ```
+/// use async_std::io::codec::LengthDelimitedCodec;
+///
+/// // Two-byte little-endian length fields, with frames of at most 1 KiB.
+/// let codec = LengthDelimitedCodec::new()
+///     .length_field_length(2)
+///     .little_endian()
+///     .max_frame_length(1024);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LengthDelimitedCodec {
+    field_len: usize,
+    little_endian: bool,
+    max_frame_len: usize,
+}
+
+impl LengthDelimitedCodec {
+    /// Creates a codec with a big-endian four-byte length field and frames of at most 8 MiB.
+    pub fn new() -> LengthDelimitedCodec {
+        LengthDelimitedCodec {
+            field_len: 4,
+            little_endian: false,
+            max_frame_len: 8 * 1024 * 1024,
+        }
+    }
+
+    /// Sets the number of bytes of the length field.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `len` is not between 1 and 8.
+    pub fn length_field_length(mut self, len: usize) -> LengthDelimitedCodec {
+        assert!(
+            (1..=8).contains(&len),
+            "the length field must be between 1 and 8 bytes long"
+        );
+        self.field_len = len;
+        self
+    }
+
+    /// Makes the length field little-endian.
+    pub fn little_endian(mut self) -> LengthDelimitedCodec {
+        self.little_endian = true;
+        self
+    }
+
+    /// Sets the maximum length of a frame's payload.
+    ///
+    /// Decoding or encoding a longer frame is an error of kind [`InvalidData`].
+    ///
+    /// [`InvalidData`]: ../enum.ErrorKind.html#variant.InvalidData
+    pub fn max_frame_length(mut self, len: usize) -> LengthDelimitedCodec {
+        self.max_frame_len = len;
+        self
+    }
+
+    fn check_length(&self, len: u64) -> io::Result<usize> {
+        match usize::try_from(len) {
+            Ok(len) if len <= self.max_frame_len => Ok(len),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "frame exceeds the maximum length",
+            )),
+        }
+    }
+}
+
+impl Default for LengthDelimitedCodec {
+    fn default() -> LengthDelimitedCodec {
+        LengthDelimitedCodec::new()
+    }
+}
+
+impl Decoder for LengthDelimitedCodec {
+    type Item = Vec<u8>;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Vec<u8>>> {
+        if src.len() < self.field_len {
+            return Ok(None);
+        }
+
+        let mut bytes = [0; 8];
+        let field = &src[..self.field_len];
+        let len = if self.little_endian {
+            bytes[..self.field_len].copy_from_slice(field);
+            u64::from_le_bytes(bytes)
+        } else {
+            bytes[8 - self.field_len..].copy_from_slice(field);
+            u64::from_be_bytes(bytes)
+        };
+        let len = self.check_length(len)?;
+
+        if src.len() - self.field_len < len {
+            // Make room for the rest of the frame.
+            src.reserve(self.field_len + len - src.len());
+            return Ok(None);
+        }
+
+        let frame = src[self.field_len..self.field_len + len].to_vec();
+        src.drain(..self.field_len + len);
+        Ok(Some(frame))
+    }
+}
+
+impl<T: AsRef<[u8]>> Encoder<T> for LengthDelimitedCodec {
+    fn encode(&mut self, frame: T, dst: &mut Vec<u8>) -> io::Result<()> {
+        let frame = frame.as_ref();
+        let len = self.check_length(frame.len() as u64)?;
+        if self.field_len < 8 && len as u64 >= 1 << (self.field_len * 8) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frame length doesn't fit into the length field",
+            ));
+        }
+
+        dst.reserve(self.field_len + len);
+        if self.little_endian {
+            dst.extend_from_slice(&(len as u64).to_le_bytes()[..self.field_len]);
+        } else {
+            dst.extend_from_slice(&(len as u64).to_be_bytes()[8 - self.field_len..]);
+        }
+        dst.extend_from_slice(frame);
+        Ok(())
+    }
+}
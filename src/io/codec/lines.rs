@@ -0,0 +1,126 @@
+use std::str;
+
+use crate::io;
+use crate::io::codec::{Decoder, Encoder};
+
+/// A codec for lines of text.
+///
+/// Lines are decoded into strings without their `\n` or `\r\n` terminators, and encoded with a
+/// `\n` appended. Lines that aren't valid UTF-8 are an error of kind [`InvalidData`].
+///
+/// [`InvalidData`]: ../enum.ErrorKind.html#variant.InvalidData
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::io::codec::{FramedRead, LinesCodec};
+/// use async_std::prelude::*;
+///
+/// let mut lines = FramedRead::new(&b"GET /\r\nHost: example.com\n"[..], LinesCodec::new());
+/// assert_eq!(lines.next().await.unwrap()?, "GET /");
+/// assert_eq!(lines.next().await.unwrap()?, "Host: example.com");
+/// assert!(lines.next().await.is_none());
+/// #
+/// # Ok(()) }) }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct LinesCodec {
+    /// The number of bytes at the start of the buffer known not to contain a newline.
+    next_index: usize,
+
+    /// The maximum length of a line, if any.
+    max_length: Option<usize>,
+}
+
+impl LinesCodec {
+    /// Creates a codec for lines of any length.
+    pub fn new() -> LinesCodec {
+        LinesCodec::default()
+    }
+
+    /// Creates a codec for lines of at most `max_length` bytes, not counting the terminator.
+    ///
+    /// Decoding a longer line is an error of kind [`InvalidData`], so that a peer can't make
+    /// the decoder buffer an unbounded amount of data.
+    ///
+    /// [`InvalidData`]: ../enum.ErrorKind.html#variant.InvalidData
+    pub fn with_max_length(max_length: usize) -> LinesCodec {
+        LinesCodec {
+            next_index: 0,
+            max_length: Some(max_length),
+        }
+    }
+
+    /// Returns the maximum length of a line, if any.
+    pub fn max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    /// Removes the first `len` bytes and the terminator from `src` and returns them as a line.
+    fn take_line(&mut self, src: &mut Vec<u8>, len: usize, terminated: bool) -> io::Result<String> {
+        self.next_index = 0;
+        let mut line: Vec<u8> = src.drain(..len + terminated as usize).collect();
+        if terminated {
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+        }
+        self.check_length(line.len())?;
+        String::from_utf8(line)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "line is not valid UTF-8"))
+    }
+
+    fn check_length(&self, len: usize) -> io::Result<()> {
+        match self.max_length {
+            Some(max) if len > max => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "line exceeds the maximum length",
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Decoder for LinesCodec {
+    type Item = String;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<String>> {
+        match memchr::memchr(b'\n', &src[self.next_index..]) {
+            Some(i) => {
+                let len = self.next_index + i;
+                self.take_line(src, len, true).map(Some)
+            }
+            None => {
+                self.next_index = src.len();
+
+                // A `\r` may still be followed by the `\n`.
+                let len = src.len() - (src.last() == Some(&b'\r')) as usize;
+                self.check_length(len)?;
+                Ok(None)
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> io::Result<Option<String>> {
+        match self.decode(src)? {
+            Some(line) => Ok(Some(line)),
+            None if src.is_empty() => Ok(None),
+
+            // The last line doesn't need a terminator.
+            None => self.take_line(src, src.len(), false).map(Some),
+        }
+    }
+}
+
+impl<T: AsRef<str>> Encoder<T> for LinesCodec {
+    fn encode(&mut self, line: T, dst: &mut Vec<u8>) -> io::Result<()> {
+        let line = line.as_ref();
+        dst.reserve(line.len() + 1);
+        dst.extend_from_slice(line.as_bytes());
+        dst.push(b'\n');
+        Ok(())
+    }
+}
@@ -0,0 +1,90 @@
+//! Framing of byte streams into streams and sinks of messages.
+//!
+//! Protocols rarely deal in raw bytes: a server reads requests and writes responses, each of
+//! them a *frame* of bytes on the wire. A [`Decoder`] splits incoming bytes into frames, an
+//! [`Encoder`] turns frames into bytes, and the [`Framed`], [`FramedRead`], and [`FramedWrite`]
+//! adapters apply them to any reader or writer. The result is a [`Stream`] of decoded frames
+//! and a [`Sink`] accepting frames to encode.
+//!
+//! Two codecs are built in: [`LinesCodec`] for newline-delimited text, and
+//! [`LengthDelimitedCodec`] for binary frames preceded by their length.
+//!
+//! [`Decoder`]: trait.Decoder.html
+//! [`Encoder`]: trait.Encoder.html
+//! [`Framed`]: struct.Framed.html
+//! [`FramedRead`]: struct.FramedRead.html
+//! [`FramedWrite`]: struct.FramedWrite.html
+//! [`Stream`]: ../../stream/trait.Stream.html
+//! [`Sink`]: ../../sink/trait.Sink.html
+//! [`LinesCodec`]: struct.LinesCodec.html
+//! [`LengthDelimitedCodec`]: struct.LengthDelimitedCodec.html
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::io::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
+//! use async_std::prelude::*;
+//! use async_std::sink::SinkExt;
+//!
+//! let mut writer = FramedWrite::new(Vec::new(), LengthDelimitedCodec::new());
+//! SinkExt::send(&mut writer, "hello").await?;
+//! SinkExt::send(&mut writer, "world").await?;
+//!
+//! let bytes = writer.into_inner();
+//! assert_eq!(&bytes[..9], b"\0\0\0\x05hello");
+//!
+//! let mut reader = FramedRead::new(&bytes[..], LengthDelimitedCodec::new());
+//! assert_eq!(reader.next().await.unwrap()?, b"hello");
+//! assert_eq!(reader.next().await.unwrap()?, b"world");
+//! assert!(reader.next().await.is_none());
+//! #
+//! # Ok(()) }) }
+//! ```
+
+pub use framed::{Framed, FramedRead, FramedWrite};
+pub use length_delimited::LengthDelimitedCodec;
+pub use lines::LinesCodec;
+
+use crate::io;
+
+mod framed;
+mod length_delimited;
+mod lines;
+
+/// Splits bytes into frames.
+pub trait Decoder {
+    /// The type of decoded frames.
+    type Item;
+
+    /// Attempts to decode a frame from the start of `src`.
+    ///
+    /// If `src` holds a complete frame, it is removed from `src` and returned. Otherwise,
+    /// `Ok(None)` is returned and the decoder is called again once more bytes have been read.
+    /// Returning an error ends the stream of frames.
+    fn decode(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Self::Item>>;
+
+    /// Decodes a frame after the end of the byte stream has been reached.
+    ///
+    /// This is called repeatedly until it returns `Ok(None)`. By default, it calls [`decode`]
+    /// and returns an error if bytes that don't form a complete frame remain.
+    ///
+    /// [`decode`]: #tymethod.decode
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> io::Result<Option<Self::Item>> {
+        match self.decode(src)? {
+            Some(frame) => Ok(Some(frame)),
+            None if src.is_empty() => Ok(None),
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "bytes remaining on stream",
+            )),
+        }
+    }
+}
+
+/// Turns frames into bytes.
+pub trait Encoder<Item> {
+    /// Appends the encoding of a frame to `dst`.
+    fn encode(&mut self, item: Item, dst: &mut Vec<u8>) -> io::Result<()>;
+}
@@ -0,0 +1,71 @@
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use super::Decoder;
+use crate::io::{self, BufRead};
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// Decompresses the data of a buffered reader with a decoder.
+    pub(super) struct Decode<R, D> {
+        #[pin]
+        reader: R,
+        decoder: D,
+        done: bool,
+    }
+}
+
+impl<R, D> Decode<R, D> {
+    pub(super) fn new(reader: R, decoder: D) -> Decode<R, D> {
+        Decode {
+            reader,
+            decoder,
+            done: false,
+        }
+    }
+
+    pub(super) fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    pub(super) fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    pub(super) fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R: BufRead, D: Decoder> Decode<R, D> {
+    pub(super) fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut this = self.project();
+
+        loop {
+            if *this.done || buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            let input = futures_core::ready!(this.reader.as_mut().poll_fill_buf(cx))?;
+            let eof = input.is_empty();
+            let progress = this.decoder.decode(input, buf)?;
+            this.reader.as_mut().consume(progress.consumed);
+            *this.done = progress.done;
+
+            if progress.written > 0 {
+                return Poll::Ready(Ok(progress.written));
+            }
+            if !progress.done && eof {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "compressed stream ended early",
+                )));
+            }
+        }
+    }
+}
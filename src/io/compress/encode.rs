@@ -0,0 +1,150 @@
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use super::{Encoder, Flush};
+use crate::io::{self, DEFAULT_BUF_SIZE, Write};
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// Compresses the data written to a writer with an encoder.
+    pub(super) struct Encode<W, E> {
+        #[pin]
+        writer: W,
+        encoder: E,
+
+        // Compressed bytes that haven't been written out yet are in `buf[pos..]`.
+        buf: Vec<u8>,
+        pos: usize,
+
+        finished: bool,
+    }
+}
+
+impl<W, E> Encode<W, E> {
+    pub(super) fn new(writer: W, encoder: E) -> Encode<W, E> {
+        Encode {
+            writer,
+            encoder,
+            buf: Vec::new(),
+            pos: 0,
+            finished: false,
+        }
+    }
+
+    pub(super) fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    pub(super) fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    pub(super) fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write, E: Encoder> Encode<W, E> {
+    /// Writes out the compressed bytes that haven't been written yet.
+    fn poll_write_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut this = self.project();
+        while *this.pos < this.buf.len() {
+            let n =
+                futures_core::ready!(this.writer.as_mut().poll_write(cx, &this.buf[*this.pos..]))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "failed to write compressed data to the writer",
+                )));
+            }
+            *this.pos += n;
+        }
+        this.buf.clear();
+        *this.pos = 0;
+        Poll::Ready(Ok(()))
+    }
+
+    /// Runs the encoder on `input`, putting the compressed bytes into the empty buffer.
+    fn encode(self: Pin<&mut Self>, input: &[u8], flush: Flush) -> io::Result<(usize, bool)> {
+        let this = self.project();
+        debug_assert!(this.buf.is_empty());
+        this.buf.resize(DEFAULT_BUF_SIZE, 0);
+        let res = this.encoder.encode(input, this.buf, flush);
+        let written = res.as_ref().map_or(0, |p| p.written);
+        this.buf.truncate(written);
+        let progress = res?;
+        if progress.done {
+            *this.finished = true;
+        }
+        // Filling the whole buffer means more output may be pending.
+        Ok((
+            progress.consumed,
+            progress.done || written < DEFAULT_BUF_SIZE,
+        ))
+    }
+
+    pub(super) fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.finished {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "compressed stream has already been finished",
+            )));
+        }
+
+        loop {
+            futures_core::ready!(self.as_mut().poll_write_buf(cx))?;
+            if buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+
+            let (consumed, _) = self.as_mut().encode(buf, Flush::None)?;
+            if consumed > 0 {
+                return Poll::Ready(Ok(consumed));
+            }
+        }
+    }
+
+    pub(super) fn poll_flush(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        if !self.finished {
+            loop {
+                futures_core::ready!(self.as_mut().poll_write_buf(cx))?;
+                let (_, flushed) = self.as_mut().encode(&[], Flush::Sync)?;
+                if flushed {
+                    break;
+                }
+            }
+        }
+        futures_core::ready!(self.as_mut().poll_write_buf(cx))?;
+        self.project().writer.poll_flush(cx)
+    }
+
+    pub(super) fn poll_finish(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        loop {
+            futures_core::ready!(self.as_mut().poll_write_buf(cx))?;
+            if self.finished {
+                break;
+            }
+            self.as_mut().encode(&[], Flush::Finish)?;
+        }
+        self.project().writer.poll_flush(cx)
+    }
+
+    pub(super) fn poll_close(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<()>> {
+        futures_core::ready!(self.as_mut().poll_finish(cx))?;
+        self.project().writer.poll_close(cx)
+    }
+}
@@ -0,0 +1,92 @@
+use miniz_oxide::deflate::core::{CompressorOxide, create_comp_flags_from_zip_params};
+use miniz_oxide::inflate::stream::InflateState;
+use miniz_oxide::{DataFormat, MZError, MZFlush, MZStatus, StreamResult};
+
+use super::{Decoder, Encoder, Flush, Progress};
+use crate::io;
+
+/// The base-2 logarithm of the window size, which is negated to omit the zlib wrapper.
+const WINDOW_BITS: i32 = 15;
+
+/// A DEFLATE decompressor, optionally expecting the zlib wrapper.
+pub(super) struct Inflate {
+    state: Box<InflateState>,
+}
+
+impl Inflate {
+    pub(super) fn raw() -> Inflate {
+        Inflate {
+            state: InflateState::new_boxed(DataFormat::Raw),
+        }
+    }
+
+    pub(super) fn zlib() -> Inflate {
+        Inflate {
+            state: InflateState::new_boxed(DataFormat::Zlib),
+        }
+    }
+}
+
+impl Decoder for Inflate {
+    fn decode(&mut self, input: &[u8], output: &mut [u8]) -> io::Result<Progress> {
+        let res =
+            miniz_oxide::inflate::stream::inflate(&mut self.state, input, output, MZFlush::None);
+        progress(res, "corrupt deflate stream")
+    }
+}
+
+/// A DEFLATE compressor, optionally adding the zlib wrapper.
+pub(super) struct Deflate {
+    compressor: Box<CompressorOxide>,
+}
+
+impl Deflate {
+    pub(super) fn raw(level: u32) -> Deflate {
+        Deflate::with_window_bits(level, -WINDOW_BITS)
+    }
+
+    pub(super) fn zlib(level: u32) -> Deflate {
+        Deflate::with_window_bits(level, WINDOW_BITS)
+    }
+
+    fn with_window_bits(level: u32, window_bits: i32) -> Deflate {
+        let flags = create_comp_flags_from_zip_params(level as i32, window_bits, 0);
+        Deflate {
+            compressor: Box::new(CompressorOxide::new(flags)),
+        }
+    }
+}
+
+impl Encoder for Deflate {
+    fn encode(&mut self, input: &[u8], output: &mut [u8], flush: Flush) -> io::Result<Progress> {
+        let flush = match flush {
+            Flush::None => MZFlush::None,
+            Flush::Sync => MZFlush::Sync,
+            Flush::Finish => MZFlush::Finish,
+        };
+        let res = miniz_oxide::deflate::stream::deflate(&mut self.compressor, input, output, flush);
+        progress(res, "failed to compress data")
+    }
+}
+
+/// Turns the result of a call to miniz into the progress it made.
+fn progress(res: StreamResult, msg: &str) -> io::Result<Progress> {
+    let done = match res.status {
+        Ok(MZStatus::StreamEnd) => true,
+        Ok(_) => false,
+        // No progress could be made, because more input or output space is needed.
+        Err(MZError::Buf) => false,
+        Err(MZError::Data) => return Err(io::Error::new(io::ErrorKind::InvalidData, msg)),
+        // Other errors point at a bug rather than at the data, but are reported the same way.
+        Err(err) => {
+            let msg = format!("{}: {:?}", msg, err);
+            return Err(io::Error::new(io::ErrorKind::InvalidData, msg));
+        }
+    };
+
+    Ok(Progress {
+        consumed: res.bytes_consumed,
+        written: res.bytes_written,
+        done,
+    })
+}
@@ -0,0 +1,250 @@
+use super::flate::{Deflate, Inflate};
+use super::{Decoder, Encoder, Flush, Progress};
+use crate::io;
+use crate::io::hash::{Crc32, Digest};
+
+/// The magic bytes every gzip member starts with.
+const MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// The compression method of DEFLATE.
+const CM_DEFLATE: u8 = 8;
+
+/// The header written by the encoder: no flags, no modification time, unknown OS.
+const HEADER: [u8; 10] = [MAGIC[0], MAGIC[1], CM_DEFLATE, 0, 0, 0, 0, 0, 0, 0xff];
+
+/// Header flags.
+const FHCRC: u8 = 1 << 1;
+const FEXTRA: u8 = 1 << 2;
+const FNAME: u8 = 1 << 3;
+const FCOMMENT: u8 = 1 << 4;
+
+/// A gzip decompressor.
+pub(super) struct GzipDecode {
+    state: DecodeState,
+    inflate: Inflate,
+    crc: Crc32,
+    size: u32,
+
+    /// Bytes of the header or trailer read so far.
+    buf: Vec<u8>,
+}
+
+enum DecodeState {
+    Header,
+    Body,
+    Trailer,
+    Done,
+}
+
+impl GzipDecode {
+    pub(super) fn new() -> GzipDecode {
+        GzipDecode {
+            state: DecodeState::Header,
+            inflate: Inflate::raw(),
+            crc: Crc32::new(),
+            size: 0,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl Decoder for GzipDecode {
+    fn decode(&mut self, input: &[u8], output: &mut [u8]) -> io::Result<Progress> {
+        let mut consumed = 0;
+
+        loop {
+            let input = &input[consumed..];
+            match self.state {
+                DecodeState::Header => {
+                    // The header has a variable length, so it's parsed again as more of it arrives.
+                    self.buf.extend_from_slice(input);
+                    match parse_header(&self.buf)? {
+                        Some(len) => {
+                            consumed += input.len() - (self.buf.len() - len);
+                            self.buf.clear();
+                            self.state = DecodeState::Body;
+                        }
+                        None => {
+                            return Ok(Progress {
+                                consumed: consumed + input.len(),
+                                written: 0,
+                                done: false,
+                            });
+                        }
+                    }
+                }
+                DecodeState::Body => {
+                    let progress = self.inflate.decode(input, output)?;
+                    self.crc.update(&output[..progress.written]);
+                    self.size = self.size.wrapping_add(progress.written as u32);
+                    consumed += progress.consumed;
+                    if !progress.done {
+                        return Ok(Progress {
+                            consumed,
+                            written: progress.written,
+                            done: false,
+                        });
+                    }
+                    self.state = DecodeState::Trailer;
+
+                    // Hand out the end of the data before checking the trailer.
+                    if progress.written > 0 {
+                        return Ok(Progress {
+                            consumed,
+                            written: progress.written,
+                            done: false,
+                        });
+                    }
+                }
+                DecodeState::Trailer => {
+                    let n = input.len().min(8 - self.buf.len());
+                    self.buf.extend_from_slice(&input[..n]);
+                    consumed += n;
+                    if self.buf.len() < 8 {
+                        return Ok(Progress {
+                            consumed,
+                            written: 0,
+                            done: false,
+                        });
+                    }
+
+                    let crc =
+                        u32::from_le_bytes([self.buf[0], self.buf[1], self.buf[2], self.buf[3]]);
+                    let size =
+                        u32::from_le_bytes([self.buf[4], self.buf[5], self.buf[6], self.buf[7]]);
+                    if crc != self.crc.finalize() || size != self.size {
+                        return Err(invalid_data("gzip checksum mismatch"));
+                    }
+                    self.state = DecodeState::Done;
+                }
+                DecodeState::Done => {
+                    return Ok(Progress {
+                        consumed,
+                        written: 0,
+                        done: true,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Returns the length of the gzip header at the start of `buf`, if it is complete.
+fn parse_header(buf: &[u8]) -> io::Result<Option<usize>> {
+    if buf.len() < HEADER.len() {
+        return Ok(None);
+    }
+    if buf[..2] != MAGIC || buf[2] != CM_DEFLATE {
+        return Err(invalid_data("invalid gzip header"));
+    }
+
+    let flags = buf[3];
+    let mut len = HEADER.len();
+
+    if flags & FEXTRA != 0 {
+        if buf.len() < len + 2 {
+            return Ok(None);
+        }
+        len += 2 + u16::from_le_bytes([buf[len], buf[len + 1]]) as usize;
+    }
+    for &flag in &[FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            // A zero-terminated string.
+            match buf.get(len..).and_then(|rest| memchr::memchr(0, rest)) {
+                Some(i) => len += i + 1,
+                None => return Ok(None),
+            }
+        }
+    }
+    if flags & FHCRC != 0 {
+        len += 2;
+    }
+
+    Ok(if buf.len() >= len { Some(len) } else { None })
+}
+
+/// A gzip compressor.
+pub(super) struct GzipEncode {
+    state: EncodeState,
+    deflate: Deflate,
+    crc: Crc32,
+    size: u32,
+
+    /// Bytes of the header or trailer that haven't been output yet.
+    pending: Vec<u8>,
+}
+
+#[derive(PartialEq)]
+enum EncodeState {
+    Header,
+    Body,
+    Trailer,
+}
+
+impl GzipEncode {
+    pub(super) fn new(level: u32) -> GzipEncode {
+        GzipEncode {
+            state: EncodeState::Header,
+            deflate: Deflate::raw(level),
+            crc: Crc32::new(),
+            size: 0,
+            pending: HEADER.to_vec(),
+        }
+    }
+
+    /// Outputs as much of the pending header or trailer as fits.
+    fn write_pending(&mut self, output: &mut [u8]) -> usize {
+        let n = self.pending.len().min(output.len());
+        output[..n].copy_from_slice(&self.pending[..n]);
+        self.pending.drain(..n);
+        n
+    }
+}
+
+impl Encoder for GzipEncode {
+    fn encode(&mut self, input: &[u8], output: &mut [u8], flush: Flush) -> io::Result<Progress> {
+        let mut written = self.write_pending(output);
+        if !self.pending.is_empty() {
+            return Ok(Progress {
+                consumed: 0,
+                written,
+                done: false,
+            });
+        }
+
+        match self.state {
+            EncodeState::Header => self.state = EncodeState::Body,
+            EncodeState::Trailer => {
+                return Ok(Progress {
+                    consumed: 0,
+                    written,
+                    done: true,
+                });
+            }
+            EncodeState::Body => {}
+        }
+
+        let progress = self.deflate.encode(input, &mut output[written..], flush)?;
+        self.crc.update(&input[..progress.consumed]);
+        self.size = self.size.wrapping_add(progress.consumed as u32);
+        written += progress.written;
+
+        if progress.done {
+            self.state = EncodeState::Trailer;
+            self.pending
+                .extend_from_slice(&self.crc.finalize().to_le_bytes());
+            self.pending.extend_from_slice(&self.size.to_le_bytes());
+            written += self.write_pending(&mut output[written..]);
+        }
+
+        Ok(Progress {
+            consumed: progress.consumed,
+            written,
+            done: self.state == EncodeState::Trailer && self.pending.is_empty(),
+        })
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
@@ -0,0 +1,294 @@
+//! Streaming compression and decompression.
+//!
+//! The decoders wrap a buffered reader of compressed bytes and are themselves readers of the
+//! decompressed bytes. The encoders wrap a writer and compress the bytes written to them. Both
+//! work incrementally on bounded buffers, so large payloads never need to be held in memory,
+//! and each call does a bounded amount of work before yielding back to the executor.
+//!
+//! Three formats are supported, all based on the DEFLATE algorithm:
+//!
+//! * [`DeflateDecoder`] and [`DeflateEncoder`] handle raw DEFLATE data ([RFC 1951]).
+//! * [`ZlibDecoder`] and [`ZlibEncoder`] handle the zlib format ([RFC 1950]), which is what HTTP
+//!   calls `deflate`.
+//! * [`GzipDecoder`] and [`GzipEncoder`] handle the gzip format ([RFC 1952]).
+//!
+//! An encoder must be closed, or [finished] when the underlying writer should stay open, to
+//! write the end of the compressed stream.
+//!
+//! [`DeflateDecoder`]: struct.DeflateDecoder.html
+//! [`DeflateEncoder`]: struct.DeflateEncoder.html
+//! [`ZlibDecoder`]: struct.ZlibDecoder.html
+//! [`ZlibEncoder`]: struct.ZlibEncoder.html
+//! [`GzipDecoder`]: struct.GzipDecoder.html
+//! [`GzipEncoder`]: struct.GzipEncoder.html
+//! [finished]: struct.GzipEncoder.html#method.finish
+//! [RFC 1950]: https://tools.ietf.org/html/rfc1950
+//! [RFC 1951]: https://tools.ietf.org/html/rfc1951
+//! [RFC 1952]: https://tools.ietf.org/html/rfc1952
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::io::compress::{GzipDecoder, GzipEncoder};
+//! use async_std::prelude::*;
+//!
+//! let mut encoder = GzipEncoder::new(Vec::new());
+//! encoder.write_all(b"hello hello hello hello").await?;
+//! encoder.finish().await?;
+//! let compressed = encoder.into_inner();
+//!
+//! let mut text = String::new();
+//! GzipDecoder::new(&compressed[..]).read_to_string(&mut text).await?;
+//! assert_eq!(text, "hello hello hello hello");
+//! #
+//! # Ok(()) }) }
+//! ```
+
+use std::fmt;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::io::{self, BufRead, Read, Write};
+use crate::task::{Context, Poll};
+
+use decode::Decode;
+use encode::Encode;
+
+mod decode;
+mod encode;
+mod flate;
+mod gzip;
+
+/// The compression level used by encoders unless another one is given.
+const DEFAULT_LEVEL: u32 = 6;
+
+/// How much of its input an encoder must turn into output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Flush {
+    /// The encoder may hold back input to compress it better.
+    None,
+
+    /// All input so far must be output, but the stream continues.
+    Sync,
+
+    /// All input so far must be output, and the stream ends.
+    Finish,
+}
+
+/// The progress of a single call to a codec.
+#[derive(Clone, Copy, Debug)]
+struct Progress {
+    /// The number of input bytes that have been consumed.
+    consumed: usize,
+
+    /// The number of output bytes that have been produced.
+    written: usize,
+
+    /// Whether the end of the compressed stream has been reached.
+    done: bool,
+}
+
+/// Decompresses a stream of bytes.
+trait Decoder {
+    /// Decompresses as much of `input` into `output` as possible.
+    fn decode(&mut self, input: &[u8], output: &mut [u8]) -> io::Result<Progress>;
+}
+
+/// Compresses a stream of bytes.
+trait Encoder {
+    /// Compresses as much of `input` into `output` as possible.
+    ///
+    /// `output` is never empty.
+    fn encode(&mut self, input: &[u8], output: &mut [u8], flush: Flush) -> io::Result<Progress>;
+}
+
+/// Defines a reader decompressing data in one of the supported formats.
+macro_rules! decoder {
+    ($(#[$attr:meta])* $name:ident, $codec:ty, $new:expr) => {
+        pin_project! {
+            $(#[$attr])*
+            ///
+            /// Decompression ends at the end of the compressed stream, leaving any bytes after it
+            /// in the underlying reader. Corrupt data fails with an error of the `InvalidData`
+            /// kind, and data that is cut short with an error of the `UnexpectedEof` kind.
+            pub struct $name<R> {
+                #[pin]
+                inner: Decode<R, $codec>,
+            }
+        }
+
+        impl<R> $name<R> {
+            /// Creates a decoder reading compressed data from `reader`.
+            pub fn new(reader: R) -> $name<R> {
+                $name {
+                    inner: Decode::new(reader, $new),
+                }
+            }
+
+            /// Gets a reference to the underlying reader.
+            pub fn get_ref(&self) -> &R {
+                self.inner.get_ref()
+            }
+
+            /// Gets a mutable reference to the underlying reader.
+            ///
+            /// Reading from it directly corrupts the decompressed data.
+            pub fn get_mut(&mut self) -> &mut R {
+                self.inner.get_mut()
+            }
+
+            /// Unwraps the decoder, returning the underlying reader.
+            pub fn into_inner(self) -> R {
+                self.inner.into_inner()
+            }
+        }
+
+        impl<R: BufRead> Read for $name<R> {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &mut [u8],
+            ) -> Poll<io::Result<usize>> {
+                self.project().inner.poll_read(cx, buf)
+            }
+        }
+
+        impl<R: fmt::Debug> fmt::Debug for $name<R> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("reader", self.inner.get_ref())
+                    .finish()
+            }
+        }
+    };
+}
+
+/// Defines a writer compressing data into one of the supported formats.
+macro_rules! encoder {
+    ($(#[$attr:meta])* $name:ident, $codec:ty, $new:expr) => {
+        pin_project! {
+            $(#[$attr])*
+            ///
+            /// Flushing the encoder writes out all data written so far, at the cost of a worse
+            /// compression ratio. Closing it writes the end of the compressed stream and closes
+            /// the underlying writer.
+            pub struct $name<W> {
+                #[pin]
+                inner: Encode<W, $codec>,
+            }
+        }
+
+        impl<W> $name<W> {
+            /// Creates an encoder writing compressed data to `writer` at the default
+            /// compression level.
+            pub fn new(writer: W) -> $name<W> {
+                $name::with_level(writer, DEFAULT_LEVEL)
+            }
+
+            /// Creates an encoder writing compressed data to `writer` at the given compression
+            /// level.
+            ///
+            /// The level goes from 0, which doesn't compress at all, to 9, which compresses
+            /// best but is slowest. Higher levels are treated as 9.
+            pub fn with_level(writer: W, level: u32) -> $name<W> {
+                let new: fn(u32) -> $codec = $new;
+                $name {
+                    inner: Encode::new(writer, new(level.min(9))),
+                }
+            }
+
+            /// Gets a reference to the underlying writer.
+            pub fn get_ref(&self) -> &W {
+                self.inner.get_ref()
+            }
+
+            /// Gets a mutable reference to the underlying writer.
+            ///
+            /// Writing to it directly corrupts the compressed data.
+            pub fn get_mut(&mut self) -> &mut W {
+                self.inner.get_mut()
+            }
+
+            /// Unwraps the encoder, returning the underlying writer.
+            ///
+            /// Compressed data that hasn't been written out yet is lost.
+            pub fn into_inner(self) -> W {
+                self.inner.into_inner()
+            }
+
+            /// Writes the end of the compressed stream and flushes the underlying writer.
+            ///
+            /// Unlike closing the encoder, this leaves the underlying writer open. Writing
+            /// after the stream has been finished fails.
+            pub async fn finish(&mut self) -> io::Result<()>
+            where
+                W: Write + Unpin,
+            {
+                let inner = &mut self.inner;
+                crate::future::poll_fn(|cx| Pin::new(&mut *inner).poll_finish(cx)).await
+            }
+        }
+
+        impl<W: Write> Write for $name<W> {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                self.project().inner.poll_write(cx, buf)
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                self.project().inner.poll_flush(cx)
+            }
+
+            fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                self.project().inner.poll_close(cx)
+            }
+        }
+
+        impl<W: fmt::Debug> fmt::Debug for $name<W> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("writer", self.inner.get_ref())
+                    .finish()
+            }
+        }
+    };
+}
+
+decoder! {
+    /// A reader decompressing raw DEFLATE data.
+    DeflateDecoder, flate::Inflate, flate::Inflate::raw()
+}
+
+decoder! {
+    /// A reader decompressing data in the zlib format.
+    ZlibDecoder, flate::Inflate, flate::Inflate::zlib()
+}
+
+decoder! {
+    /// A reader decompressing data in the gzip format.
+    ///
+    /// Only the first member of a gzip file is decompressed. The checksum and length stored at
+    /// its end are verified.
+    GzipDecoder, gzip::GzipDecode, gzip::GzipDecode::new()
+}
+
+encoder! {
+    /// A writer compressing data into raw DEFLATE data.
+    DeflateEncoder, flate::Deflate, flate::Deflate::raw
+}
+
+encoder! {
+    /// A writer compressing data into the zlib format.
+    ZlibEncoder, flate::Deflate, flate::Deflate::zlib
+}
+
+encoder! {
+    /// A writer compressing data into the gzip format.
+    GzipEncoder, gzip::GzipEncode, gzip::GzipEncode::new
+}
@@ -0,0 +1,70 @@
+use super::Digest;
+
+/// The CRC-32 checksum.
+///
+/// This is the checksum used by gzip, zip, and PNG files, with the polynomial `0x04c11db7`.
+///
+/// # Examples
+///
+/// ```
+/// use async_std::io::hash::{Crc32, Digest};
+///
+/// let mut crc = Crc32::new();
+/// crc.update(b"1234");
+/// crc.update(b"56789");
+/// assert_eq!(crc.finalize(), 0xcbf4_3926);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Crc32 {
+    /// Creates a checksum of no bytes.
+    pub fn new() -> Crc32 {
+        Crc32 { crc: !0 }
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Crc32 {
+        Crc32::new()
+    }
+}
+
+impl Digest for Crc32 {
+    type Output = u32;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.crc = TABLE[((self.crc ^ b as u32) & 0xff) as usize] ^ (self.crc >> 8);
+        }
+    }
+
+    fn finalize(&self) -> u32 {
+        !self.crc
+    }
+}
+
+/// The lookup table of the reflected polynomial `0xedb88320`.
+static TABLE: [u32; 256] = table();
+
+const fn table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 != 0 {
+                0xedb8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
@@ -0,0 +1,69 @@
+//! Checksums of data passing through readers and writers.
+//!
+//! A [`HashingReader`] or [`HashingWriter`] wraps another reader or writer and feeds every byte
+//! that passes through it to a [`Digest`]. This way, data can be verified or fingerprinted while
+//! it is transferred, without reading it a second time.
+//!
+//! Two digests are built in: [`Crc32`], the checksum used by gzip and zip files, and
+//! [`Sha256`]. Other algorithms can be plugged in by implementing [`Digest`].
+//!
+//! Hashing is fast, but not free: hashing a large buffer in one go can hold up other tasks on
+//! the same thread. The adapters can hand large buffers off with [`task::block_in_place`], see
+//! [`HashingReader::with_offload_threshold`].
+//!
+//! [`HashingReader`]: struct.HashingReader.html
+//! [`HashingWriter`]: struct.HashingWriter.html
+//! [`Digest`]: trait.Digest.html
+//! [`Crc32`]: struct.Crc32.html
+//! [`Sha256`]: struct.Sha256.html
+//! [`task::block_in_place`]: ../../task/fn.block_in_place.html
+//! [`HashingReader::with_offload_threshold`]: struct.HashingReader.html#method.with_offload_threshold
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::io::hash::{Crc32, Digest, HashingReader};
+//! use async_std::io::{self, sink};
+//!
+//! let mut reader = HashingReader::new(&b"123456789"[..], Crc32::new());
+//! io::copy(&mut reader, &mut sink()).await?;
+//! assert_eq!(reader.digest().finalize(), 0xcbf4_3926);
+//! #
+//! # Ok(()) }) }
+//! ```
+
+pub use crc32::Crc32;
+pub use reader::HashingReader;
+pub use sha256::Sha256;
+pub use writer::HashingWriter;
+
+mod crc32;
+mod reader;
+mod sha256;
+mod writer;
+
+/// An algorithm computing a checksum or hash of a sequence of bytes.
+pub trait Digest {
+    /// The type of the checksum or hash.
+    type Output;
+
+    /// Feeds more bytes into the digest.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the checksum or hash of the bytes fed into the digest so far.
+    ///
+    /// The digest isn't reset, so more bytes can be fed into it afterwards.
+    fn finalize(&self) -> Self::Output;
+}
+
+/// Feeds `bytes` into `digest`, off the executor if there are at least `threshold` of them.
+fn update<D: Digest>(digest: &mut D, bytes: &[u8], threshold: Option<usize>) {
+    match threshold {
+        Some(threshold) if bytes.len() >= threshold => {
+            crate::task::block_in_place(|| digest.update(bytes))
+        }
+        _ => digest.update(bytes),
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use super::Digest;
+use crate::io::{self, Read};
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// A reader feeding the bytes read from it into a digest.
+    ///
+    /// See the [module documentation] for an example.
+    ///
+    /// [module documentation]: index.html
+    pub struct HashingReader<R, D> {
+        #[pin]
+        inner: R,
+        digest: D,
+        offload_threshold: Option<usize>,
+    }
+}
+
+impl<R, D: Digest> HashingReader<R, D> {
+    /// Creates a reader feeding the bytes read from `inner` into `digest`.
+    pub fn new(inner: R, digest: D) -> HashingReader<R, D> {
+        HashingReader {
+            inner,
+            digest,
+            offload_threshold: None,
+        }
+    }
+
+    /// Hashes reads of at least `len` bytes with [`task::block_in_place`].
+    ///
+    /// This keeps hashing large reads from holding up other tasks, at the cost of starting a
+    /// new worker thread for each of them. It is only worth it for slow digests and large
+    /// buffers, of hundreds of kilobytes or more.
+    ///
+    /// [`task::block_in_place`]: ../../task/fn.block_in_place.html
+    pub fn with_offload_threshold(mut self, len: usize) -> HashingReader<R, D> {
+        self.offload_threshold = Some(len);
+        self
+    }
+
+    /// Returns a reference to the digest.
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read from it directly are not hashed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `HashingReader`, returning the underlying reader and the digest.
+    pub fn into_parts(self) -> (R, D) {
+        (self.inner, self.digest)
+    }
+}
+
+impl<R: Read, D: Digest> Read for HashingReader<R, D> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+        let n = futures_core::ready!(this.inner.poll_read(cx, buf))?;
+        super::update(this.digest, &buf[..n], *this.offload_threshold);
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<R: fmt::Debug, D: fmt::Debug> fmt::Debug for HashingReader<R, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashingReader")
+            .field("inner", &self.inner)
+            .field("digest", &self.digest)
+            .finish()
+    }
+}
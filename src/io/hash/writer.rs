@@ -0,0 +1,112 @@
+use std::fmt;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use super::Digest;
+use crate::io::{self, Write};
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// A writer feeding the bytes written to it into a digest.
+    ///
+    /// Only the bytes the underlying writer accepts are hashed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::io::hash::{Digest, HashingWriter, Sha256};
+    /// use async_std::prelude::*;
+    ///
+    /// let mut writer = HashingWriter::new(Vec::new(), Sha256::new());
+    /// writer.write_all(b"abc").await?;
+    ///
+    /// let (bytes, sha) = writer.into_parts();
+    /// assert_eq!(bytes, b"abc");
+    /// assert_eq!(sha.finalize()[..4], [0xba, 0x78, 0x16, 0xbf]);
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub struct HashingWriter<W, D> {
+        #[pin]
+        inner: W,
+        digest: D,
+        offload_threshold: Option<usize>,
+    }
+}
+
+impl<W, D: Digest> HashingWriter<W, D> {
+    /// Creates a writer feeding the bytes written to `inner` into `digest`.
+    pub fn new(inner: W, digest: D) -> HashingWriter<W, D> {
+        HashingWriter {
+            inner,
+            digest,
+            offload_threshold: None,
+        }
+    }
+
+    /// Hashes writes of at least `len` bytes with [`task::block_in_place`].
+    ///
+    /// See [`HashingReader::with_offload_threshold`] for details.
+    ///
+    /// [`task::block_in_place`]: ../../task/fn.block_in_place.html
+    /// [`HashingReader::with_offload_threshold`]: struct.HashingReader.html#method.with_offload_threshold
+    pub fn with_offload_threshold(mut self, len: usize) -> HashingWriter<W, D> {
+        self.offload_threshold = Some(len);
+        self
+    }
+
+    /// Returns a reference to the digest.
+    pub fn digest(&self) -> &D {
+        &self.digest
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written to it directly are not hashed.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `HashingWriter`, returning the underlying writer and the digest.
+    pub fn into_parts(self) -> (W, D) {
+        (self.inner, self.digest)
+    }
+}
+
+impl<W: Write, D: Digest> Write for HashingWriter<W, D> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.project();
+        let n = futures_core::ready!(this.inner.poll_write(cx, buf))?;
+        super::update(this.digest, &buf[..n], *this.offload_threshold);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.project().inner.poll_close(cx)
+    }
+}
+
+impl<W: fmt::Debug, D: fmt::Debug> fmt::Debug for HashingWriter<W, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HashingWriter")
+            .field("inner", &self.inner)
+            .field("digest", &self.digest)
+            .finish()
+    }
+}
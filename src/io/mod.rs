@@ -241,6 +241,22 @@
 //! return errors if they happen. In this case, the only purpose of this function is
 //! to read the line and print it, so we use `()`.
 //!
+//! ## Cancel safety
+//!
+//! A future returned by an I/O method can be dropped before it completes, for example when it
+//! loses a race against a [timeout]. Whether that loses data depends on the method:
+//!
+//! * [`read`], [`write`], [`flush`], and [`fill_buf`] are cancel safe: if the future is dropped,
+//!   nothing has been read or written.
+//! * [`read_until`] and [`read_to_end`] are cancel safe: bytes are appended to the buffer as soon
+//!   as they are read, so calling the method again continues where it stopped.
+//! * [`read_exact`], [`write_all`], [`read_line`], and [`read_to_string`] are not: if the future
+//!   is dropped, some data may have been read or written without a way to tell how much.
+//!
+//! With the `unstable` feature, [`read_exact_resumable`], [`write_all_resumable`], and
+//! [`read_line_resumable`] keep track of their progress in state owned by the caller, so that
+//! they can be resumed after they were interrupted.
+//!
 //! ## Platform-specific behavior
 //!
 //! Many I/O functions throughout the standard library are documented to indicate
@@ -268,6 +284,20 @@
 //! [`Read::read`]: trait.Read.html#tymethod.read
 //! [`Result`]: https://doc.rust-lang.org/std/result/enum.Result.html
 //! [`.unwrap()`]: https://doc.rust-lang.org/std/result/enum.Result.html#method.unwrap
+//! [timeout]: fn.timeout.html
+//! [`read`]: prelude/trait.ReadExt.html#method.read
+//! [`write`]: prelude/trait.WriteExt.html#method.write
+//! [`flush`]: prelude/trait.WriteExt.html#method.flush
+//! [`fill_buf`]: trait.BufRead.html#tymethod.poll_fill_buf
+//! [`read_until`]: prelude/trait.BufReadExt.html#method.read_until
+//! [`read_to_end`]: prelude/trait.ReadExt.html#method.read_to_end
+//! [`read_exact`]: prelude/trait.ReadExt.html#method.read_exact
+//! [`write_all`]: prelude/trait.WriteExt.html#method.write_all
+//! [`read_line`]: prelude/trait.BufReadExt.html#method.read_line
+//! [`read_to_string`]: prelude/trait.ReadExt.html#method.read_to_string
+//! [`read_exact_resumable`]: prelude/trait.ReadExt.html#method.read_exact_resumable
+//! [`write_all_resumable`]: prelude/trait.WriteExt.html#method.write_all_resumable
+//! [`read_line_resumable`]: prelude/trait.BufReadExt.html#method.read_line_resumable
 
 const DEFAULT_BUF_SIZE: usize = 8 * 1024;
 
@@ -322,7 +352,23 @@ cfg_default! {
 }
 
 cfg_unstable! {
-    pub use stderr::StderrLock;
-    pub use stdin::StdinLock;
-    pub use stdout::StdoutLock;
+    pub use bridge::{AsyncAsSync, SyncAsAsync};
+    pub use buffer_pool::{BufferPool, PooledBuf};
+    pub use read_buf::ReadBuf;
+    pub use stderr::{OwnedStderrLock, StderrLock};
+    pub use stdin::{OwnedStdinLock, StdinLines, StdinLock};
+    pub use stdout::{BufferMode, OwnedStdoutLock, StdoutLock};
+
+    pub mod chunked;
+    pub mod codec;
+    #[cfg(feature = "compress")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "compress")))]
+    pub mod compress;
+    pub mod hash;
+    pub mod mux;
+    pub mod readline;
+
+    mod bridge;
+    mod buffer_pool;
+    mod read_buf;
 }
@@ -0,0 +1,176 @@
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use super::WINDOW;
+use super::frame::{FIN, Header, Kind, MAX_DATA_LEN};
+use super::session::State;
+use crate::io::{self, Read, Write};
+use crate::task::{Context, Poll};
+
+/// A bidirectional channel of a [`Session`].
+///
+/// Closing a channel closes it for writing: the other side reads to its end, but can keep
+/// writing. Dropping a channel without closing it resets it instead, which makes reads and
+/// writes on the other side fail once the data received before has been read.
+///
+/// [`Session`]: struct.Session.html
+pub struct Channel {
+    id: u32,
+    shared: Arc<Mutex<State>>,
+
+    /// The number of the last frame queued for this channel.
+    last_frame: u64,
+
+    /// This side has closed the channel for writing.
+    closed: bool,
+}
+
+impl Channel {
+    pub(crate) fn new(id: u32, shared: Arc<Mutex<State>>, last_frame: u64) -> Channel {
+        Channel {
+            id,
+            shared,
+            last_frame,
+            closed: false,
+        }
+    }
+
+    /// Returns the id of the channel.
+    ///
+    /// Ids are unique within a session: channels opened by the client have odd ids, and ones
+    /// opened by the server have even ids.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+impl Read for Channel {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let mut state = self.shared.lock().unwrap();
+        let ch = state
+            .channels
+            .get_mut(&self.id)
+            .expect("channel state is missing");
+
+        if !ch.recv_buf.is_empty() && !buf.is_empty() {
+            let n = buf.len().min(ch.recv_buf.len());
+            for (dst, src) in buf.iter_mut().zip(ch.recv_buf.drain(..n)) {
+                *dst = src;
+            }
+
+            // Extend the window once half of it has been read.
+            let credit = WINDOW - ch.recv_window - ch.recv_buf.len() as u32;
+            if credit >= WINDOW / 2 && !ch.remote_fin && !ch.reset {
+                ch.recv_window += credit;
+                state.send(Header::new(Kind::WindowUpdate, 0, self.id, credit).encode(&[]));
+            }
+            return Poll::Ready(Ok(n));
+        }
+
+        if ch.remote_fin || buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        if ch.reset {
+            return Poll::Ready(Err(reset()));
+        }
+        ch.read_waker = Some(cx.waker().clone());
+        state.check()?;
+        Poll::Pending
+    }
+}
+
+impl Write for Channel {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.closed {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "channel is closed",
+            )));
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+
+        let id = self.id;
+        let mut state = self.shared.lock().unwrap();
+        state.check()?;
+        let ch = state
+            .channels
+            .get_mut(&id)
+            .expect("channel state is missing");
+        if ch.reset {
+            return Poll::Ready(Err(reset()));
+        }
+        if ch.send_window == 0 {
+            ch.write_waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+
+        let n = buf.len().min(ch.send_window as usize).min(MAX_DATA_LEN);
+        ch.send_window -= n as u32;
+        let frame = state.send(Header::new(Kind::Data, 0, id, n as u32).encode(&buf[..n]));
+        drop(state);
+
+        self.last_frame = frame;
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut state = self.shared.lock().unwrap();
+        if state.flushed >= self.last_frame {
+            return Poll::Ready(Ok(()));
+        }
+        state.check()?;
+        state.flush_wakers.push(cx.waker().clone());
+        Poll::Pending
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.closed {
+            let mut state = self.shared.lock().unwrap();
+            state.check()?;
+            let frame = state.send(Header::new(Kind::Data, FIN, self.id, 0).encode(&[]));
+            drop(state);
+
+            self.last_frame = frame;
+            self.closed = true;
+        }
+        self.poll_flush(cx)
+    }
+}
+
+impl Drop for Channel {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock().unwrap();
+        if let Some(ch) = state.channels.remove(&self.id) {
+            if !self.closed && !ch.reset && state.error.is_none() {
+                state.reset(self.id);
+            }
+        }
+        if let Some(w) = state.driver_waker.take() {
+            w.wake();
+        }
+    }
+}
+
+impl fmt::Debug for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Channel")
+            .field("id", &self.id)
+            .field("closed", &self.closed)
+            .finish()
+    }
+}
+
+fn reset() -> io::Error {
+    io::Error::new(io::ErrorKind::ConnectionReset, "channel was reset")
+}
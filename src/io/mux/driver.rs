@@ -0,0 +1,198 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use super::WINDOW;
+use super::frame::{FIN, HEADER_LEN, Header, Kind, RST, SYN, protocol_error};
+use super::session::{ChannelState, State};
+use crate::io::{self, Read, Write};
+use crate::task::{Context, Poll};
+
+/// The task reading and writing the frames of a session.
+pub(crate) struct Driver<T> {
+    io: T,
+    shared: Arc<Mutex<State>>,
+
+    /// The frame being read is in `read_buf[..filled]`.
+    read_buf: Vec<u8>,
+    filled: usize,
+
+    /// The frames being written are in `write_buf[written..]`.
+    write_buf: Vec<u8>,
+    written: usize,
+
+    /// The number of frames written but not flushed.
+    unflushed: u64,
+}
+
+impl<T: Read + Write + Unpin> Driver<T> {
+    pub(crate) fn new(io: T, shared: Arc<Mutex<State>>) -> Driver<T> {
+        Driver {
+            io,
+            shared,
+            read_buf: vec![0; HEADER_LEN],
+            filled: 0,
+            write_buf: Vec::new(),
+            written: 0,
+            unflushed: 0,
+        }
+    }
+
+    fn poll_drive(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if let Poll::Ready(()) = self.poll_read(cx)? {
+            return Poll::Ready(Err(io::Error::new(
+                io::ErrorKind::ConnectionAborted,
+                "connection closed",
+            )));
+        }
+        futures_core::ready!(self.poll_write(cx))?;
+
+        let done = {
+            let state = self.shared.lock().unwrap();
+            state.closing && state.channels.is_empty() && state.outgoing.is_empty()
+        };
+        if !done {
+            return Poll::Pending;
+        }
+        futures_core::ready!(Pin::new(&mut self.io).poll_close(cx))?;
+        Poll::Ready(Ok(()))
+    }
+
+    /// Reads and handles frames until reading would block, or the connection ends.
+    fn poll_read(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            let mut want = HEADER_LEN;
+            if self.filled >= HEADER_LEN {
+                let header = Header::decode(&self.read_buf[..HEADER_LEN])?;
+                if header.kind == Kind::Data {
+                    if header.len > WINDOW {
+                        return Poll::Ready(Err(protocol_error("frame is too long")));
+                    }
+                    want += header.len as usize;
+                }
+
+                if self.filled == want {
+                    self.filled = 0;
+                    let mut state = self.shared.lock().unwrap();
+                    handle(&mut state, header, &self.read_buf[HEADER_LEN..want])?;
+                    continue;
+                }
+            }
+
+            if self.read_buf.len() < want {
+                self.read_buf.resize(want, 0);
+            }
+            let buf = &mut self.read_buf[self.filled..want];
+            let n = futures_core::ready!(Pin::new(&mut self.io).poll_read(cx, buf))?;
+            if n == 0 {
+                if self.filled == 0 {
+                    return Poll::Ready(Ok(()));
+                }
+                return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()));
+            }
+            self.filled += n;
+        }
+    }
+
+    /// Writes and flushes queued frames until there are none left.
+    fn poll_write(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            while self.written < self.write_buf.len() {
+                let buf = &self.write_buf[self.written..];
+                let n = futures_core::ready!(Pin::new(&mut self.io).poll_write(cx, buf))?;
+                if n == 0 {
+                    return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+                }
+                self.written += n;
+            }
+
+            if self.unflushed > 0 {
+                futures_core::ready!(Pin::new(&mut self.io).poll_flush(cx))?;
+                let mut state = self.shared.lock().unwrap();
+                state.flushed += self.unflushed;
+                self.unflushed = 0;
+                for w in state.flush_wakers.drain(..) {
+                    w.wake();
+                }
+            }
+
+            let mut state = self.shared.lock().unwrap();
+            if state.outgoing.is_empty() {
+                state.driver_waker = Some(cx.waker().clone());
+                return Poll::Ready(Ok(()));
+            }
+            self.write_buf.clear();
+            self.written = 0;
+            while let Some(frame) = state.outgoing.pop_front() {
+                self.write_buf.extend_from_slice(&frame);
+                self.unflushed += 1;
+            }
+        }
+    }
+}
+
+impl<T: Read + Write + Unpin> Future for Driver<T> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let res = futures_core::ready!(self.poll_drive(cx));
+        let err = res.err().unwrap_or_else(|| {
+            io::Error::new(io::ErrorKind::ConnectionAborted, "session is closed")
+        });
+        self.shared.lock().unwrap().fail(err);
+        Poll::Ready(())
+    }
+}
+
+/// Applies a frame received from the other side.
+fn handle(state: &mut State, header: Header, data: &[u8]) -> io::Result<()> {
+    if header.kind == Kind::GoAway {
+        state.remote_goaway = true;
+        if let Some(w) = state.accept_waker.take() {
+            w.wake();
+        }
+        return Ok(());
+    }
+
+    if header.flags & SYN != 0 {
+        if header.id % 2 == state.next_id % 2 || header.id <= state.last_remote_id {
+            return Err(protocol_error("invalid channel id"));
+        }
+        state.last_remote_id = header.id;
+
+        if state.closing {
+            state.reset(header.id);
+            return Ok(());
+        }
+        state.channels.insert(header.id, ChannelState::new());
+        state.incoming.push_back(header.id);
+        if let Some(w) = state.accept_waker.take() {
+            w.wake();
+        }
+    }
+
+    // Frames for channels that have been dropped are ignored.
+    let ch = match state.channels.get_mut(&header.id) {
+        Some(ch) => ch,
+        None => return Ok(()),
+    };
+
+    match header.kind {
+        Kind::Data => {
+            if data.len() as u32 > ch.recv_window {
+                return Err(protocol_error("channel window exceeded"));
+            }
+            ch.recv_window -= data.len() as u32;
+            ch.recv_buf.extend(data);
+        }
+        _ => ch.send_window = ch.send_window.saturating_add(header.len),
+    }
+    if header.flags & FIN != 0 {
+        ch.remote_fin = true;
+    }
+    if header.flags & RST != 0 {
+        ch.reset = true;
+    }
+    ch.wake();
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use crate::io;
+
+/// The length of a frame header.
+pub(crate) const HEADER_LEN: usize = 12;
+
+/// The most data sent in a single frame.
+pub(crate) const MAX_DATA_LEN: usize = 16 * 1024;
+
+/// Opens a channel.
+pub(crate) const SYN: u16 = 1;
+
+/// Closes a channel for writing.
+pub(crate) const FIN: u16 = 4;
+
+/// Resets a channel.
+pub(crate) const RST: u16 = 8;
+
+const VERSION: u8 = 0;
+
+/// The type of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Kind {
+    /// Data for a channel.
+    Data,
+
+    /// An increment of a channel's window.
+    WindowUpdate,
+
+    /// The sender won't accept any more channels.
+    GoAway,
+}
+
+/// A frame header.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Header {
+    pub(crate) kind: Kind,
+    pub(crate) flags: u16,
+    pub(crate) id: u32,
+    pub(crate) len: u32,
+}
+
+impl Header {
+    pub(crate) fn new(kind: Kind, flags: u16, id: u32, len: u32) -> Header {
+        Header {
+            kind,
+            flags,
+            id,
+            len,
+        }
+    }
+
+    /// Encodes the header followed by `data`.
+    pub(crate) fn encode(&self, data: &[u8]) -> Vec<u8> {
+        let kind = match self.kind {
+            Kind::Data => 0,
+            Kind::WindowUpdate => 1,
+            Kind::GoAway => 3,
+        };
+
+        let mut frame = Vec::with_capacity(HEADER_LEN + data.len());
+        frame.push(VERSION);
+        frame.push(kind);
+        frame.extend_from_slice(&self.flags.to_be_bytes());
+        frame.extend_from_slice(&self.id.to_be_bytes());
+        frame.extend_from_slice(&self.len.to_be_bytes());
+        frame.extend_from_slice(data);
+        frame
+    }
+
+    /// Decodes a header from its first `HEADER_LEN` bytes.
+    pub(crate) fn decode(bytes: &[u8]) -> io::Result<Header> {
+        if bytes[0] != VERSION {
+            return Err(protocol_error("unsupported version"));
+        }
+        let kind = match bytes[1] {
+            0 => Kind::Data,
+            1 => Kind::WindowUpdate,
+            3 => Kind::GoAway,
+            _ => return Err(protocol_error("unknown frame type")),
+        };
+
+        Ok(Header {
+            kind,
+            flags: u16::from_be_bytes([bytes[2], bytes[3]]),
+            id: u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            len: u32::from_be_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]),
+        })
+    }
+}
+
+pub(crate) fn protocol_error(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
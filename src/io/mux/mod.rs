@@ -0,0 +1,79 @@
+//! Many independent channels over a single connection.
+//!
+//! A [`Session`] wraps a reader and writer, typically a TCP stream, and multiplexes any number
+//! of bidirectional [`Channel`]s over it. Either side of the session can [`open`] channels, which
+//! the other side [`accept`]s. Each channel has its own flow control: a receiver grants its
+//! sender a window of [`WINDOW`] bytes, and only extends it as data is read, so a channel that
+//! isn't being read doesn't hold up the others.
+//!
+//! The framing is modeled after [yamux]. Every frame starts with a 12-byte header:
+//!
+//! | Field     | Size    | Meaning                                                    |
+//! |-----------|---------|------------------------------------------------------------|
+//! | version   | 1 byte  | Always 0.                                                  |
+//! | type      | 1 byte  | 0 for data, 1 for a window update, 3 for going away.       |
+//! | flags     | 2 bytes | 1 opens a channel, 4 closes it for writing, 8 resets it.   |
+//! | channel   | 4 bytes | The channel the frame is about, odd if opened by a client. |
+//! | length    | 4 bytes | The length of the data, or the window increment.           |
+//!
+//! All fields are big-endian. Data frames are followed by their data.
+//!
+//! The session is driven by a task spawned when it is created, which keeps running until the
+//! session and all of its channels are closed, or the connection fails.
+//!
+//! [`Session`]: struct.Session.html
+//! [`Channel`]: struct.Channel.html
+//! [`open`]: struct.Session.html#method.open
+//! [`accept`]: struct.Session.html#method.accept
+//! [`WINDOW`]: constant.WINDOW.html
+//! [yamux]: https://github.com/hashicorp/yamux/blob/master/spec.md
+//!
+//! # Examples
+//!
+//! ```
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::io::mux::Session;
+//! use async_std::net::{TcpListener, TcpStream};
+//! use async_std::prelude::*;
+//! use async_std::task;
+//!
+//! let listener = TcpListener::bind("127.0.0.1:0").await?;
+//! let addr = listener.local_addr()?;
+//!
+//! task::spawn(async move {
+//!     let (stream, _) = listener.accept().await?;
+//!     let session = Session::server(stream);
+//!     while let Ok(mut channel) = session.accept().await {
+//!         task::spawn(async move {
+//!             let mut buf = Vec::new();
+//!             channel.read_to_end(&mut buf).await?;
+//!             channel.write_all(&buf).await?;
+//!             channel.close().await
+//!         });
+//!     }
+//!     std::io::Result::Ok(())
+//! });
+//!
+//! let session = Session::client(TcpStream::connect(addr).await?);
+//! let mut channel = session.open()?;
+//! channel.write_all(b"hello").await?;
+//! channel.close().await?;
+//!
+//! let mut echo = Vec::new();
+//! channel.read_to_end(&mut echo).await?;
+//! assert_eq!(echo, b"hello");
+//! #
+//! # Ok(()) }) }
+//! ```
+
+pub use channel::Channel;
+pub use session::Session;
+
+mod channel;
+mod driver;
+mod frame;
+mod session;
+
+/// The number of bytes a channel may receive before it is read from.
+pub const WINDOW: u32 = 256 * 1024;
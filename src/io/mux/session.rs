@@ -0,0 +1,296 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::task::Waker;
+
+use super::WINDOW;
+use super::channel::Channel;
+use super::driver::Driver;
+use super::frame::{Header, Kind, RST, SYN};
+use crate::future;
+use crate::io::{self, Read, Write};
+use crate::task::{self, Context, Poll};
+
+/// One end of a multiplexed connection.
+///
+/// Dropping the session [closes] it, but the channels opened or accepted on it keep working.
+///
+/// See the [module documentation] for an example.
+///
+/// [closes]: #method.close
+/// [module documentation]: index.html
+pub struct Session {
+    shared: Arc<Mutex<State>>,
+}
+
+impl Session {
+    /// Creates the client end of a session.
+    ///
+    /// Channels opened by the client have odd ids, so that they don't clash with the ones
+    /// opened by the server.
+    pub fn client<T>(io: T) -> Session
+    where
+        T: Read + Write + Unpin + Send + 'static,
+    {
+        Session::new(io, 1)
+    }
+
+    /// Creates the server end of a session.
+    ///
+    /// Channels opened by the server have even ids.
+    pub fn server<T>(io: T) -> Session
+    where
+        T: Read + Write + Unpin + Send + 'static,
+    {
+        Session::new(io, 2)
+    }
+
+    fn new<T>(io: T, first_id: u32) -> Session
+    where
+        T: Read + Write + Unpin + Send + 'static,
+    {
+        let shared = Arc::new(Mutex::new(State::new(first_id)));
+        task::spawn(Driver::new(io, shared.clone()));
+        Session { shared }
+    }
+
+    /// Opens a new channel.
+    ///
+    /// The other side learns about the channel as soon as the driver gets to send a frame for
+    /// it, so data can be written to it right away.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the session is closed, either side has gone away, or the
+    /// connection has failed.
+    pub fn open(&self) -> io::Result<Channel> {
+        let mut state = self.shared.lock().unwrap();
+        state.check()?;
+        if state.closing || state.remote_goaway {
+            return Err(closed());
+        }
+
+        let id = state.next_id;
+        state.next_id = id
+            .checked_add(2)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "out of channel ids"))?;
+        state.channels.insert(id, ChannelState::new());
+        let frame = state.send(Header::new(Kind::WindowUpdate, SYN, id, 0).encode(&[]));
+
+        Ok(Channel::new(id, self.shared.clone(), frame))
+    }
+
+    /// Accepts a channel opened by the other side.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned once the session is closed, the other side has gone away, or the
+    /// connection has failed.
+    pub async fn accept(&self) -> io::Result<Channel> {
+        future::poll_fn(|cx| self.poll_accept(cx)).await
+    }
+
+    fn poll_accept(&self, cx: &mut Context<'_>) -> Poll<io::Result<Channel>> {
+        let mut state = self.shared.lock().unwrap();
+        if let Some(id) = state.incoming.pop_front() {
+            return Poll::Ready(Ok(Channel::new(id, self.shared.clone(), 0)));
+        }
+        state.check()?;
+        if state.closing || state.remote_goaway {
+            return Poll::Ready(Err(closed()));
+        }
+        state.accept_waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+
+    /// Closes the session.
+    ///
+    /// The other side is told not to open any more channels, and channels it opened but that
+    /// haven't been accepted yet are reset. Channels that are already open keep working, and the
+    /// connection is closed once they are all closed too.
+    pub fn close(&self) {
+        self.shared.lock().unwrap().close();
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl fmt::Debug for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.shared.lock().unwrap();
+        f.debug_struct("Session")
+            .field("channels", &state.channels.len())
+            .field("closing", &state.closing)
+            .finish()
+    }
+}
+
+/// The state shared by a session, its channels, and its driver.
+#[derive(Debug)]
+pub(crate) struct State {
+    pub(crate) channels: HashMap<u32, ChannelState>,
+
+    /// The id of the next channel opened on this side.
+    pub(crate) next_id: u32,
+
+    /// The id of the last channel opened by the other side.
+    pub(crate) last_remote_id: u32,
+
+    /// Channels opened by the other side that haven't been accepted yet.
+    pub(crate) incoming: VecDeque<u32>,
+    pub(crate) accept_waker: Option<Waker>,
+
+    /// Encoded frames waiting for the driver.
+    pub(crate) outgoing: VecDeque<Vec<u8>>,
+    pub(crate) driver_waker: Option<Waker>,
+
+    /// The number of frames queued and flushed so far.
+    pub(crate) queued: u64,
+    pub(crate) flushed: u64,
+    pub(crate) flush_wakers: Vec<Waker>,
+
+    /// This side has gone away.
+    pub(crate) closing: bool,
+
+    /// The other side has gone away.
+    pub(crate) remote_goaway: bool,
+
+    /// Why the connection has ended.
+    pub(crate) error: Option<(io::ErrorKind, String)>,
+}
+
+impl State {
+    fn new(first_id: u32) -> State {
+        State {
+            channels: HashMap::new(),
+            next_id: first_id,
+            last_remote_id: 0,
+            incoming: VecDeque::new(),
+            accept_waker: None,
+            outgoing: VecDeque::new(),
+            driver_waker: None,
+            queued: 0,
+            flushed: 0,
+            flush_wakers: Vec::new(),
+            closing: false,
+            remote_goaway: false,
+            error: None,
+        }
+    }
+
+    /// Returns the error the connection ended with, if any.
+    pub(crate) fn check(&self) -> io::Result<()> {
+        match &self.error {
+            Some((kind, msg)) => Err(io::Error::new(*kind, msg.as_str())),
+            None => Ok(()),
+        }
+    }
+
+    /// Queues a frame, returning its number.
+    pub(crate) fn send(&mut self, frame: Vec<u8>) -> u64 {
+        self.outgoing.push_back(frame);
+        self.queued += 1;
+        if let Some(w) = self.driver_waker.take() {
+            w.wake();
+        }
+        self.queued
+    }
+
+    /// Queues a reset of channel `id`.
+    pub(crate) fn reset(&mut self, id: u32) {
+        self.send(Header::new(Kind::WindowUpdate, RST, id, 0).encode(&[]));
+    }
+
+    pub(crate) fn close(&mut self) {
+        if self.closing {
+            return;
+        }
+        self.closing = true;
+        self.send(Header::new(Kind::GoAway, 0, 0, 0).encode(&[]));
+
+        while let Some(id) = self.incoming.pop_front() {
+            self.channels.remove(&id);
+            self.reset(id);
+        }
+        if let Some(w) = self.accept_waker.take() {
+            w.wake();
+        }
+        if let Some(w) = self.driver_waker.take() {
+            w.wake();
+        }
+    }
+
+    /// Ends the connection with an error, waking everything waiting on it.
+    pub(crate) fn fail(&mut self, err: io::Error) {
+        if self.error.is_none() {
+            self.error = Some((err.kind(), err.to_string()));
+        }
+
+        for channel in self.channels.values_mut() {
+            channel.wake();
+        }
+        for w in self
+            .accept_waker
+            .take()
+            .into_iter()
+            .chain(self.flush_wakers.drain(..))
+        {
+            w.wake();
+        }
+    }
+}
+
+/// The state of a channel.
+#[derive(Debug)]
+pub(crate) struct ChannelState {
+    /// Data received but not read yet.
+    pub(crate) recv_buf: VecDeque<u8>,
+
+    /// How much more data the other side may send.
+    pub(crate) recv_window: u32,
+
+    /// How much more data may be sent to the other side.
+    pub(crate) send_window: u32,
+
+    pub(crate) read_waker: Option<Waker>,
+    pub(crate) write_waker: Option<Waker>,
+
+    /// The other side has closed the channel for writing.
+    pub(crate) remote_fin: bool,
+
+    /// The other side has reset the channel.
+    pub(crate) reset: bool,
+}
+
+impl ChannelState {
+    pub(crate) fn new() -> ChannelState {
+        ChannelState {
+            recv_buf: VecDeque::new(),
+            recv_window: WINDOW,
+            send_window: WINDOW,
+            read_waker: None,
+            write_waker: None,
+            remote_fin: false,
+            reset: false,
+        }
+    }
+
+    pub(crate) fn wake(&mut self) {
+        for w in self
+            .read_waker
+            .take()
+            .into_iter()
+            .chain(self.write_waker.take())
+        {
+            w.wake();
+        }
+    }
+}
+
+fn closed() -> io::Error {
+    io::Error::new(io::ErrorKind::ConnectionAborted, "session is closed")
+}
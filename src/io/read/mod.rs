@@ -1,14 +1,22 @@
 mod bytes;
 mod chain;
 mod read;
+#[cfg(feature = "unstable")]
+mod read_buf;
 mod read_exact;
+#[cfg(feature = "unstable")]
+mod read_exact_resumable;
 mod read_to_end;
 mod read_to_string;
 mod read_vectored;
 mod take;
 
 use read::ReadFuture;
+#[cfg(feature = "unstable")]
+use read_buf::ReadBufFuture;
 use read_exact::ReadExactFuture;
+#[cfg(feature = "unstable")]
+use read_exact_resumable::ReadExactResumableFuture;
 use read_to_end::{read_to_end_internal, ReadToEndFuture};
 use read_to_string::ReadToStringFuture;
 use read_vectored::ReadVectoredFuture;
@@ -240,7 +248,14 @@ extension_trait! {
             If this function returns an error, it is unspecified how many bytes it has read,
             but it will never read more than would be necessary to completely fill the buffer.
 
+            # Cancel safety
+
+            This method is not cancel safe. If the future is dropped before it completes, some
+            bytes may have been read into `buf` without a way to tell how many. Use
+            [`read_exact_resumable`] to resume the read after it was interrupted.
+
             [`ErrorKind::UnexpectedEof`]: enum.ErrorKind.html#variant.UnexpectedEof
+            [`read_exact_resumable`]: #method.read_exact_resumable
 
             # Examples
 
@@ -268,6 +283,104 @@ extension_trait! {
             ReadExactFuture { reader: self, buf }
         }
 
+        #[doc = r#"
+            Reads the exact number of bytes required to fill `buf`, keeping track of the progress
+            in `filled`.
+
+            Bytes are read into `buf[*filled..]`, and `filled` is advanced as soon as they are,
+            so that it always tells how much of `buf` has been filled. The read is complete once
+            `filled` reaches the length of `buf`.
+
+            # Cancel safety
+
+            This method is cancel safe. If the future is dropped before it completes, calling
+            this method again with the same `buf` and `filled` resumes the read where it
+            stopped. The same is true after an error of the kind [`ErrorKind::Interrupted`] or
+            [`ErrorKind::TimedOut`].
+
+            [`ErrorKind::Interrupted`]: enum.ErrorKind.html#variant.Interrupted
+            [`ErrorKind::TimedOut`]: enum.ErrorKind.html#variant.TimedOut
+
+            # Examples
+
+            ```no_run
+            # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+            #
+            use std::time::Duration;
+
+            use async_std::future;
+            use async_std::net::TcpStream;
+            use async_std::prelude::*;
+
+            let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+
+            let mut header = [0; 16];
+            let mut filled = 0;
+            loop {
+                let read = stream.read_exact_resumable(&mut header, &mut filled);
+                match future::timeout(Duration::from_secs(1), read).await {
+                    Ok(res) => break res?,
+                    Err(_) => println!("{} bytes of the header so far", filled),
+                }
+            }
+            #
+            # Ok(()) }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn read_exact_resumable<'a>(
+            &'a mut self,
+            buf: &'a mut [u8],
+            filled: &'a mut usize,
+        ) -> impl Future<Output = io::Result<()>> + 'a [ReadExactResumableFuture<'a, Self>]
+        where
+            Self: Unpin,
+        {
+            ReadExactResumableFuture { reader: self, buf, filled }
+        }
+
+        #[doc = r#"
+            Reads some bytes from the byte stream, appending them to a growable buffer.
+
+            Returns the number of bytes read, which is 0 once the end of the stream has been
+            reached. Unlike [`read`], this doesn't need an initialized slice to be prepared
+            up front: the buffer grows when it is full, and a [`PooledBuf`] reuses memory that
+            has already been initialized instead of zeroing it for every read.
+
+            [`read`]: #method.read
+            [`PooledBuf`]: ../struct.PooledBuf.html
+
+            # Examples
+
+            ```no_run
+            # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+            #
+            use async_std::net::TcpStream;
+            use async_std::prelude::*;
+
+            let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+
+            let mut buf = Vec::with_capacity(4096);
+            let n = stream.read_buf(&mut buf).await?;
+            assert_eq!(buf.len(), n);
+            #
+            # Ok(()) }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn read_buf<'a, B>(
+            &'a mut self,
+            buf: &'a mut B,
+        ) -> impl Future<Output = io::Result<usize>> + 'a [ReadBufFuture<'a, Self, B>]
+        where
+            Self: Unpin,
+            B: crate::io::ReadBuf + ?Sized,
+        {
+            ReadBufFuture { reader: self, buf }
+        }
+
         #[doc = r#"
             Creates an adaptor which will read at most `limit` bytes from it.
 
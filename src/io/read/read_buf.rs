@@ -0,0 +1,21 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::io::{self, Read, ReadBuf};
+use crate::task::{Context, Poll};
+
+#[doc(hidden)]
+#[allow(missing_debug_implementations)]
+pub struct ReadBufFuture<'a, T: Unpin + ?Sized, B: ?Sized> {
+    pub(crate) reader: &'a mut T,
+    pub(crate) buf: &'a mut B,
+}
+
+impl<T: Read + Unpin + ?Sized, B: ReadBuf + ?Sized> Future for ReadBufFuture<'_, T, B> {
+    type Output = io::Result<usize>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self { reader, buf } = &mut *self;
+        buf.poll_fill(|unfilled| Pin::new(&mut **reader).poll_read(cx, unfilled))
+    }
+}
@@ -0,0 +1,36 @@
+use std::pin::Pin;
+use std::future::Future;
+
+use crate::io::{self, Read};
+use crate::task::{Context, Poll};
+
+#[doc(hidden)]
+#[allow(missing_debug_implementations)]
+pub struct ReadExactResumableFuture<'a, T: Unpin + ?Sized> {
+    pub(crate) reader: &'a mut T,
+    pub(crate) buf: &'a mut [u8],
+    pub(crate) filled: &'a mut usize,
+}
+
+impl<T: Read + Unpin + ?Sized> Future for ReadExactResumableFuture<'_, T> {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self {
+            reader,
+            buf,
+            filled,
+        } = &mut *self;
+
+        while **filled < buf.len() {
+            let n =
+                futures_core::ready!(Pin::new(&mut **reader).poll_read(cx, &mut buf[**filled..]))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into()));
+            }
+            **filled += n;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
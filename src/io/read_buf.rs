@@ -0,0 +1,73 @@
+use crate::io::{self, DEFAULT_BUF_SIZE};
+use crate::task::Poll;
+
+/// A growable buffer that bytes can be read into.
+///
+/// This is what [`ReadExt::read_buf`] reads into. Each read appends to the end of the buffer,
+/// so that the same buffer can be filled by many reads without copying data around, and
+/// implementations decide how memory is allocated and initialized.
+///
+/// It is implemented for `Vec<u8>`, for [`PooledBuf`], and, with the `bytes` feature, for
+/// `bytes::BytesMut`.
+///
+/// [`ReadExt::read_buf`]: prelude/trait.ReadExt.html#method.read_buf
+/// [`PooledBuf`]: struct.PooledBuf.html
+pub trait ReadBuf {
+    /// Passes the unfilled part of the buffer to `read`, and appends the bytes it reports to
+    /// have read.
+    ///
+    /// The slice is never empty: a full buffer is grown first. `read` must return at most the
+    /// length of the slice.
+    fn poll_fill<F>(&mut self, read: F) -> Poll<io::Result<usize>>
+    where
+        F: FnOnce(&mut [u8]) -> Poll<io::Result<usize>>;
+}
+
+impl ReadBuf for Vec<u8> {
+    fn poll_fill<F>(&mut self, read: F) -> Poll<io::Result<usize>>
+    where
+        F: FnOnce(&mut [u8]) -> Poll<io::Result<usize>>,
+    {
+        let len = self.len();
+        if len == self.capacity() {
+            self.reserve(DEFAULT_BUF_SIZE);
+        }
+
+        // The spare capacity has to be initialized since the reader could look at it.
+        let capacity = self.capacity();
+        self.resize(capacity, 0);
+        let res = read(&mut self[len..]);
+        let n = match &res {
+            Poll::Ready(Ok(n)) => *n,
+            _ => 0,
+        };
+        self.truncate(len + n);
+        res
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl ReadBuf for bytes::BytesMut {
+    fn poll_fill<F>(&mut self, read: F) -> Poll<io::Result<usize>>
+    where
+        F: FnOnce(&mut [u8]) -> Poll<io::Result<usize>>,
+    {
+        use bytes::BufMut;
+
+        if self.remaining_mut() == 0 {
+            self.reserve(DEFAULT_BUF_SIZE);
+        }
+
+        // Safety: the spare capacity is initialized before it is handed out, and only the
+        // bytes the reader reports to have read are marked as filled.
+        unsafe {
+            let spare = self.bytes_mut();
+            std::ptr::write_bytes(spare.as_mut_ptr(), 0, spare.len());
+            let res = read(spare);
+            if let Poll::Ready(Ok(n)) = res {
+                self.advance_mut(n);
+            }
+            res
+        }
+    }
+}
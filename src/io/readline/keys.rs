@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+
+/// A key press decoded from terminal input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Key {
+    Char(char),
+
+    /// A control key, such as `Ctrl('a')`.
+    Ctrl(char),
+
+    /// A key pressed along with Alt, sent as an escape followed by the key.
+    Alt(char),
+
+    /// Enter, sent as `\r` by terminals in raw mode, or `\n` otherwise.
+    Enter {
+        cr: bool,
+    },
+
+    Tab,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+
+    /// Anything else, which is ignored.
+    Unknown,
+}
+
+/// Decodes the next key from the front of `bytes`.
+///
+/// Returns `None` if `bytes` doesn't hold a complete key yet.
+pub(crate) fn decode(bytes: &mut VecDeque<u8>) -> Option<Key> {
+    let buf = bytes.make_contiguous();
+    let (len, key) = match *buf.first()? {
+        0x1b => match *buf.get(1)? {
+            b'[' | b'O' => {
+                // A control sequence ends in a byte from `@` to `~`.
+                let end = 2 + buf[2..].iter().position(|b| (0x40..=0x7e).contains(b))?;
+                let key = match &buf[2..=end] {
+                    b"A" => Key::Up,
+                    b"B" => Key::Down,
+                    b"C" => Key::Right,
+                    b"D" => Key::Left,
+                    b"H" | b"1~" | b"7~" => Key::Home,
+                    b"F" | b"4~" | b"8~" => Key::End,
+                    b"3~" => Key::Delete,
+                    _ => Key::Unknown,
+                };
+                (end + 1, key)
+            }
+            b if b.is_ascii_graphic() => (2, Key::Alt(b as char)),
+            _ => (1, Key::Unknown),
+        },
+        b'\r' => (1, Key::Enter { cr: true }),
+        b'\n' => (1, Key::Enter { cr: false }),
+        b'\t' => (1, Key::Tab),
+        0x7f | 0x08 => (1, Key::Backspace),
+        b @ 0x01..=0x1a => (1, Key::Ctrl((b'a' + b - 1) as char)),
+        b if b < 0x80 => (1, Key::Char(b as char)),
+        b => {
+            let width = match b {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => 1,
+            };
+            if buf.len() < width {
+                return None;
+            }
+            match std::str::from_utf8(&buf[..width]) {
+                Ok(s) => (width, s.chars().next().map_or(Key::Unknown, Key::Char)),
+                Err(_) => (1, Key::Unknown),
+            }
+        }
+    };
+
+    bytes.drain(..len);
+    Some(key)
+}
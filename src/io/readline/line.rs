@@ -0,0 +1,124 @@
+/// The line being edited.
+#[derive(Debug, Default)]
+pub(crate) struct Line {
+    chars: Vec<char>,
+
+    /// The cursor, as an index into `chars`.
+    pos: usize,
+}
+
+impl Line {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.chars.is_empty()
+    }
+
+    /// Returns the number of characters after the cursor.
+    pub(crate) fn after_cursor(&self) -> usize {
+        self.chars.len() - self.pos
+    }
+
+    /// Returns the cursor as a byte index into the line.
+    pub(crate) fn byte_pos(&self) -> usize {
+        self.chars[..self.pos].iter().map(|c| c.len_utf8()).sum()
+    }
+
+    /// Replaces the whole line, putting the cursor at its end.
+    pub(crate) fn set(&mut self, s: &str) {
+        self.chars = s.chars().collect();
+        self.pos = self.chars.len();
+    }
+
+    pub(crate) fn insert(&mut self, c: char) {
+        self.chars.insert(self.pos, c);
+        self.pos += 1;
+    }
+
+    /// Replaces the `len` characters before the cursor with `s`.
+    pub(crate) fn replace_before(&mut self, len: usize, s: &str) {
+        let start = self.pos - len;
+        let after = self.chars.split_off(self.pos);
+        self.chars.truncate(start);
+        self.chars.extend(s.chars());
+        self.pos = self.chars.len();
+        self.chars.extend(after);
+    }
+
+    pub(crate) fn left(&mut self) {
+        self.pos = self.pos.saturating_sub(1);
+    }
+
+    pub(crate) fn right(&mut self) {
+        self.pos = (self.pos + 1).min(self.chars.len());
+    }
+
+    pub(crate) fn home(&mut self) {
+        self.pos = 0;
+    }
+
+    pub(crate) fn end(&mut self) {
+        self.pos = self.chars.len();
+    }
+
+    /// Moves the cursor to the start of the previous word.
+    pub(crate) fn word_left(&mut self) {
+        while self.pos > 0 && !self.chars[self.pos - 1].is_alphanumeric() {
+            self.pos -= 1;
+        }
+        while self.pos > 0 && self.chars[self.pos - 1].is_alphanumeric() {
+            self.pos -= 1;
+        }
+    }
+
+    /// Moves the cursor to the end of the next word.
+    pub(crate) fn word_right(&mut self) {
+        let len = self.chars.len();
+        while self.pos < len && !self.chars[self.pos].is_alphanumeric() {
+            self.pos += 1;
+        }
+        while self.pos < len && self.chars[self.pos].is_alphanumeric() {
+            self.pos += 1;
+        }
+    }
+
+    pub(crate) fn backspace(&mut self) {
+        if self.pos > 0 {
+            self.pos -= 1;
+            self.chars.remove(self.pos);
+        }
+    }
+
+    pub(crate) fn delete(&mut self) {
+        if self.pos < self.chars.len() {
+            self.chars.remove(self.pos);
+        }
+    }
+
+    pub(crate) fn kill_to_end(&mut self) {
+        self.chars.truncate(self.pos);
+    }
+
+    pub(crate) fn kill_to_start(&mut self) {
+        self.chars.drain(..self.pos);
+        self.pos = 0;
+    }
+
+    /// Deletes the word before the cursor.
+    pub(crate) fn kill_word(&mut self) {
+        let end = self.pos;
+        while self.pos > 0 && self.chars[self.pos - 1].is_whitespace() {
+            self.pos -= 1;
+        }
+        while self.pos > 0 && !self.chars[self.pos - 1].is_whitespace() {
+            self.pos -= 1;
+        }
+        self.chars.drain(self.pos..end);
+    }
+}
+
+impl std::fmt::Display for Line {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.chars
+            .iter()
+            .try_for_each(|c| std::fmt::Write::write_char(f, *c))
+    }
+}
@@ -0,0 +1,373 @@
+//! Line editing for interactive programs.
+//!
+//! An [`Editor`] reads lines typed on a terminal, letting the user edit them as they type,
+//! recall earlier lines from the history, and complete words with Tab. It puts the terminal into
+//! raw mode and reads stdin asynchronously, so a REPL can keep other tasks running while it waits
+//! for input.
+//!
+//! The following keys are supported:
+//!
+//! | Keys                        | Action                                                                |
+//! |-----------------------------|-----------------------------------------------------------------------|
+//! | Left, Right, Ctrl-B, Ctrl-F | Move the cursor by a character.                                       |
+//! | Alt-B, Alt-F                | Move the cursor by a word.                                            |
+//! | Home, End, Ctrl-A, Ctrl-E   | Move the cursor to the start or end of the line.                      |
+//! | Up, Down, Ctrl-P, Ctrl-N    | Recall earlier or later lines from the history.                       |
+//! | Backspace, Delete           | Delete the character before or under the cursor.                      |
+//! | Ctrl-D                      | Delete the character under the cursor, or end input on an empty line. |
+//! | Ctrl-K, Ctrl-U              | Delete everything after or before the cursor.                         |
+//! | Ctrl-W                      | Delete the word before the cursor.                                    |
+//! | Ctrl-L                      | Clear the screen.                                                     |
+//! | Ctrl-C                      | Abandon the line.                                                     |
+//! | Tab                         | Complete the word before the cursor.                                  |
+//!
+//! Lines are assumed to fit on a single row of the terminal, with every character one column
+//! wide. If stdin isn't a terminal, or on platforms other than Linux and Android, lines are read
+//! without editing.
+//!
+//! [`Editor`]: struct.Editor.html
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::io::readline::Editor;
+//!
+//! let mut editor = Editor::new();
+//! editor.set_completer(|line, pos| {
+//!     let start = line[..pos].rfind(' ').map_or(0, |i| i + 1);
+//!     let words = ["help", "history", "quit"];
+//!     let candidates = words
+//!         .iter()
+//!         .filter(|w| w.starts_with(&line[start..pos]))
+//!         .map(|w| w.to_string())
+//!         .collect();
+//!     (start, candidates)
+//! });
+//!
+//! while let Some(line) = editor.readline("> ").await? {
+//!     if line == "quit" {
+//!         break;
+//!     }
+//!     println!("you typed: {}", line);
+//! }
+//! #
+//! # Ok(()) }) }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::io::{self, Read, Write};
+use crate::prelude::*;
+
+use keys::Key;
+use line::Line;
+use terminal::RawMode;
+
+mod keys;
+mod line;
+mod terminal;
+
+/// The default maximum length of the history.
+const DEFAULT_MAX_HISTORY: usize = 1000;
+
+/// A completion hook, see [`Editor::set_completer`].
+///
+/// [`Editor::set_completer`]: struct.Editor.html#method.set_completer
+type Completer = Box<dyn Fn(&str, usize) -> (usize, Vec<String>) + Send + Sync>;
+
+/// A line editor with a history.
+///
+/// See the [module documentation] for the supported keys and an example.
+///
+/// [module documentation]: index.html
+pub struct Editor {
+    history: VecDeque<String>,
+    max_history: usize,
+    completer: Option<Completer>,
+
+    /// Input read but not handled yet.
+    pending: VecDeque<u8>,
+
+    /// The last line ended with `\r`, so a `\n` right after it belongs to the same line break.
+    skip_lf: bool,
+}
+
+impl Editor {
+    /// Creates an editor with an empty history.
+    pub fn new() -> Editor {
+        Editor {
+            history: VecDeque::new(),
+            max_history: DEFAULT_MAX_HISTORY,
+            completer: None,
+            pending: VecDeque::new(),
+            skip_lf: false,
+        }
+    }
+
+    /// Sets the hook called to complete words when Tab is pressed.
+    ///
+    /// The hook receives the line and the byte index of the cursor in it, and returns the byte
+    /// index where the word being completed starts, along with the candidates to replace it
+    /// with. A single candidate is filled in right away. Of several candidates, the prefix they
+    /// share is filled in, and if that doesn't add anything, they are listed below the line.
+    pub fn set_completer<F>(&mut self, completer: F)
+    where
+        F: Fn(&str, usize) -> (usize, Vec<String>) + Send + Sync + 'static,
+    {
+        self.completer = Some(Box::new(completer));
+    }
+
+    /// Returns the history, oldest line first.
+    pub fn history(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().map(|s| s.as_str())
+    }
+
+    /// Adds a line to the history.
+    ///
+    /// Lines read by the editor are added automatically, unless they are empty or repeat the
+    /// previous line.
+    pub fn add_history(&mut self, line: impl Into<String>) {
+        let line = line.into();
+        if line.is_empty() || self.history.back() == Some(&line) {
+            return;
+        }
+        self.history.push_back(line);
+        while self.history.len() > self.max_history {
+            self.history.pop_front();
+        }
+    }
+
+    /// Sets the maximum number of lines kept in the history.
+    ///
+    /// The oldest lines are forgotten first. The default is 1000.
+    pub fn set_max_history(&mut self, max: usize) {
+        self.max_history = max;
+        while self.history.len() > max {
+            self.history.pop_front();
+        }
+    }
+
+    /// Reads a line from stdin, after writing `prompt` to stdout.
+    ///
+    /// The returned line doesn't include the line break. `None` is returned at the end of input,
+    /// or when Ctrl-D is pressed on an empty line.
+    ///
+    /// # Errors
+    ///
+    /// An error of kind [`Interrupted`] is returned when Ctrl-C is pressed.
+    ///
+    /// [`Interrupted`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.Interrupted
+    pub async fn readline(&mut self, prompt: &str) -> io::Result<Option<String>> {
+        let raw = RawMode::enable()?;
+        if raw.is_none() {
+            let mut stdout = io::stdout();
+            stdout.write_all(prompt.as_bytes()).await?;
+            stdout.flush().await?;
+
+            let mut line = String::new();
+            if io::stdin().read_line(&mut line).await? == 0 {
+                return Ok(None);
+            }
+            if line.ends_with('\n') {
+                line.pop();
+                if line.ends_with('\r') {
+                    line.pop();
+                }
+            }
+            return Ok(Some(line));
+        }
+
+        self.read_line_with(prompt, io::stdin(), io::stdout()).await
+    }
+
+    /// Reads a line from `input`, echoing it to `output`.
+    ///
+    /// This is what [`readline`] does with stdin and stdout, for terminals that are somewhere
+    /// else, such as at the other end of a network connection. `input` should send key presses as
+    /// a terminal in raw mode would.
+    ///
+    /// [`readline`]: #method.readline
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::io::readline::Editor;
+    ///
+    /// let mut editor = Editor::new();
+    ///
+    /// // Type "hllo", move left three times, and insert the "e".
+    /// let input = &b"hllo\x1b[D\x1b[D\x1b[De\r"[..];
+    /// let line = editor.read_line_with("> ", input, Vec::new()).await?;
+    /// assert_eq!(line.as_deref(), Some("hello"));
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn read_line_with<R, W>(
+        &mut self,
+        prompt: &str,
+        mut input: R,
+        mut output: W,
+    ) -> io::Result<Option<String>>
+    where
+        R: Read + Unpin,
+        W: Write + Unpin,
+    {
+        let mut line = Line::default();
+        let mut hist_pos = self.history.len();
+        let mut unsent = String::new();
+        let mut out = Vec::new();
+        render(&mut out, prompt, &line);
+
+        loop {
+            while let Some(key) = keys::decode(&mut self.pending) {
+                let skip_lf = std::mem::replace(&mut self.skip_lf, false);
+
+                match key {
+                    Key::Enter { cr } => {
+                        if skip_lf && !cr {
+                            continue;
+                        }
+                        self.skip_lf = cr;
+                        out.extend_from_slice(b"\r\n");
+                        output.write_all(&out).await?;
+                        output.flush().await?;
+
+                        let line = line.to_string();
+                        self.add_history(line.clone());
+                        return Ok(Some(line));
+                    }
+                    Key::Ctrl('c') => {
+                        out.extend_from_slice(b"^C\r\n");
+                        output.write_all(&out).await?;
+                        output.flush().await?;
+                        return Err(io::ErrorKind::Interrupted.into());
+                    }
+                    Key::Ctrl('d') if line.is_empty() => {
+                        out.extend_from_slice(b"\r\n");
+                        output.write_all(&out).await?;
+                        output.flush().await?;
+                        return Ok(None);
+                    }
+
+                    Key::Char(c) => line.insert(c),
+                    Key::Left | Key::Ctrl('b') => line.left(),
+                    Key::Right | Key::Ctrl('f') => line.right(),
+                    Key::Alt('b') => line.word_left(),
+                    Key::Alt('f') => line.word_right(),
+                    Key::Home | Key::Ctrl('a') => line.home(),
+                    Key::End | Key::Ctrl('e') => line.end(),
+                    Key::Backspace => line.backspace(),
+                    Key::Delete | Key::Ctrl('d') => line.delete(),
+                    Key::Ctrl('k') => line.kill_to_end(),
+                    Key::Ctrl('u') => line.kill_to_start(),
+                    Key::Ctrl('w') => line.kill_word(),
+                    Key::Ctrl('l') => out.extend_from_slice(b"\x1b[H\x1b[2J"),
+                    Key::Tab => self.complete(&mut line, &mut out),
+
+                    Key::Up | Key::Ctrl('p') if hist_pos > 0 => {
+                        if hist_pos == self.history.len() {
+                            unsent = line.to_string();
+                        }
+                        hist_pos -= 1;
+                        line.set(&self.history[hist_pos]);
+                    }
+                    Key::Down | Key::Ctrl('n') if hist_pos < self.history.len() => {
+                        hist_pos += 1;
+                        match self.history.get(hist_pos) {
+                            Some(s) => line.set(s),
+                            None => line.set(&unsent),
+                        }
+                    }
+
+                    _ => {}
+                }
+                render(&mut out, prompt, &line);
+            }
+
+            output.write_all(&out).await?;
+            output.flush().await?;
+            out.clear();
+
+            let mut buf = [0; 64];
+            let n = input.read(&mut buf).await?;
+            if n == 0 {
+                // The input ended in the middle of a line.
+                output.write_all(b"\r\n").await?;
+                output.flush().await?;
+                if line.is_empty() {
+                    return Ok(None);
+                }
+                let line = line.to_string();
+                self.add_history(line.clone());
+                return Ok(Some(line));
+            }
+            self.pending.extend(&buf[..n]);
+        }
+    }
+
+    /// Completes the word before the cursor.
+    fn complete(&self, line: &mut Line, out: &mut Vec<u8>) {
+        let complete = match &self.completer {
+            Some(complete) => complete,
+            None => return,
+        };
+
+        let text = line.to_string();
+        let pos = line.byte_pos();
+        let (start, candidates) = complete(&text, pos);
+        if start > pos || !text.is_char_boundary(start) || candidates.is_empty() {
+            return;
+        }
+        let word = &text[start..pos];
+
+        let mut prefix = candidates[0].as_str();
+        for c in &candidates[1..] {
+            let len = prefix
+                .char_indices()
+                .zip(c.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(prefix.len().min(c.len()), |((i, _), _)| i);
+            prefix = &prefix[..len];
+        }
+
+        if prefix.len() > word.len() || candidates.len() == 1 {
+            line.replace_before(word.chars().count(), prefix);
+        } else {
+            out.extend_from_slice(b"\r\n");
+            out.extend_from_slice(candidates.join("  ").as_bytes());
+            out.extend_from_slice(b"\r\n");
+        }
+    }
+}
+
+impl Default for Editor {
+    fn default() -> Editor {
+        Editor::new()
+    }
+}
+
+impl fmt::Debug for Editor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Editor")
+            .field("history", &self.history)
+            .field("max_history", &self.max_history)
+            .finish()
+    }
+}
+
+/// Redraws the prompt and the line, and puts the cursor in place.
+fn render(out: &mut Vec<u8>, prompt: &str, line: &Line) {
+    out.extend_from_slice(b"\r");
+    out.extend_from_slice(prompt.as_bytes());
+    out.extend_from_slice(line.to_string().as_bytes());
+    out.extend_from_slice(b"\x1b[K");
+    let back = line.after_cursor();
+    if back > 0 {
+        out.extend_from_slice(format!("\x1b[{}D", back).as_bytes());
+    }
+}
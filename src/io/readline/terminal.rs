@@ -0,0 +1,59 @@
+use crate::io;
+
+/// Keeps the terminal on stdin in raw mode, restoring its settings when dropped.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) struct RawMode {
+    original: libc::termios,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl RawMode {
+    /// Puts the terminal into raw mode, or returns `None` if stdin isn't a terminal.
+    pub(crate) fn enable() -> io::Result<Option<RawMode>> {
+        use std::mem;
+
+        unsafe {
+            if libc::isatty(libc::STDIN_FILENO) != 1 {
+                return Ok(None);
+            }
+
+            let mut original: libc::termios = mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            // Turn off echoing, line buffering, and signals, but keep output processing.
+            let mut raw = original;
+            raw.c_iflag &= !(libc::BRKINT | libc::ICRNL | libc::INPCK | libc::ISTRIP | libc::IXON);
+            raw.c_lflag &= !(libc::ECHO | libc::ICANON | libc::IEXTEN | libc::ISIG);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSADRAIN, &raw) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(Some(RawMode { original }))
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSADRAIN, &self.original);
+        }
+    }
+}
+
+/// Keeps the terminal on stdin in raw mode, restoring its settings when dropped.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) struct RawMode(());
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+impl RawMode {
+    /// Raw mode isn't supported on this platform, so lines are read without editing.
+    pub(crate) fn enable() -> io::Result<Option<RawMode>> {
+        Ok(None)
+    }
+}
@@ -6,8 +6,12 @@ use crate::io::{self, Write};
 use crate::task::{spawn_blocking, Context, JoinHandle, Poll};
 
 cfg_unstable! {
+    use std::sync::Arc;
+
     use once_cell::sync::Lazy;
     use std::io::Write as _;
+
+    use crate::sync::OwnedMutexGuard;
 }
 
 /// Constructs a new handle to the standard error of the current process.
@@ -74,6 +78,21 @@ pub struct StderrLock<'a>(std::io::StderrLock<'a>);
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
 unsafe impl Send for StderrLock<'_> {}
 
+/// An owned lock on the standard error, usable across `.await` points.
+///
+/// This handle implements the [`Write`] trait, and is constructed via the [`Stderr::lock_owned`]
+/// method.
+///
+/// [`Write`]: trait.Write.html
+/// [`Stderr::lock_owned`]: struct.Stderr.html#method.lock_owned
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct OwnedStderrLock {
+    stderr: Stderr,
+    _guard: OwnedMutexGuard<()>,
+}
+
 /// The state of the asynchronous stderr.
 ///
 /// The stderr can be either idle or busy performing an asynchronous operation.
@@ -135,11 +154,44 @@ impl Stderr {
 
         spawn_blocking(move || StderrLock(STDERR.lock())).await
     }
+
+    /// Locks the standard error, returning an owned guard.
+    ///
+    /// Unlike the guard returned by [`lock`], the owned guard can be held across `.await` points
+    /// and moved into spawned tasks. While it is held, other tasks calling this method wait, so
+    /// that their output isn't interleaved. Output written through other handles isn't affected.
+    ///
+    /// [`lock`]: #method.lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::io;
+    /// use async_std::prelude::*;
+    ///
+    /// let mut err = io::stderr().lock_owned().await;
+    /// err.write_all(b"error: ").await?;
+    /// err.write_all(b"something went wrong\n").await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn lock_owned(&self) -> OwnedStderrLock {
+        static LOCK: Lazy<Arc<crate::sync::Mutex<()>>> = Lazy::new(Default::default);
+
+        OwnedStderrLock {
+            _guard: LOCK.clone().lock_owned().await,
+            stderr: stderr(),
+        }
+    }
 }
 
 impl Write for Stderr {
     fn poll_write(
-        mut self: Pin<&mut Self>,
+        self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
@@ -187,7 +239,7 @@ impl Write for Stderr {
         }
     }
 
-    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
         let state = &mut *self.0.lock().unwrap();
 
         loop {
@@ -240,6 +292,26 @@ cfg_windows! {
     }
 }
 
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+impl Write for OwnedStderrLock {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stderr).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stderr).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stderr).poll_close(cx)
+    }
+}
+
 #[cfg(feature = "unstable")]
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
 impl io::Write for StderrLock<'_> {
@@ -1,15 +1,22 @@
 use std::future::Future;
+use std::mem;
 use std::pin::Pin;
 use std::sync::Mutex;
 
+use once_cell::sync::Lazy;
+
 use crate::future;
 use crate::io::{self, Read};
 use crate::task::{spawn_blocking, Context, JoinHandle, Poll};
 use crate::utils::Context as _;
 
 cfg_unstable! {
-    use once_cell::sync::Lazy;
+    use std::sync::Arc;
+
     use std::io::Read as _;
+
+    use crate::stream::Stream;
+    use crate::sync::OwnedMutexGuard;
 }
 
 /// Constructs a new handle to the standard input of the current process.
@@ -38,12 +45,18 @@ cfg_unstable! {
 /// # Ok(()) }) }
 /// ```
 pub fn stdin() -> Stdin {
-    Stdin(Mutex::new(State::Idle(Some(Inner {
-        stdin: std::io::stdin(),
-        line: String::new(),
-        buf: Vec::new(),
-        last_op: None,
-    }))))
+    static STDIN: Lazy<Mutex<State>> = Lazy::new(|| {
+        Mutex::new(State::Idle(Some(Inner {
+            stdin: std::io::stdin(),
+            line: String::new(),
+            buf: Vec::new(),
+            pending: Vec::new(),
+            eof: false,
+            last_op: None,
+        })))
+    });
+
+    Stdin(&STDIN)
 }
 
 /// A handle to the standard input of the current process.
@@ -51,6 +64,10 @@ pub fn stdin() -> Stdin {
 /// This reader is created by the [`stdin`] function. See its documentation for
 /// more.
 ///
+/// All handles share the input read from the standard input. Input read by an operation that is
+/// cancelled, for example because it lost a race against a timeout, isn't lost: it is returned by
+/// the next operation on any handle.
+///
 /// ### Note: Windows Portability Consideration
 ///
 /// When operating in a console, the Windows implementation of this stream does not support
@@ -59,7 +76,7 @@ pub fn stdin() -> Stdin {
 ///
 /// [`stdin`]: fn.stdin.html
 #[derive(Debug)]
-pub struct Stdin(Mutex<State>);
+pub struct Stdin(&'static Mutex<State>);
 
 /// A locked reference to the Stdin handle.
 ///
@@ -76,6 +93,21 @@ pub struct StdinLock<'a>(std::io::StdinLock<'a>);
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
 unsafe impl Send for StdinLock<'_> {}
 
+/// An owned lock on the standard input, usable across `.await` points.
+///
+/// This handle implements the [`Read`] trait, and is constructed via the [`Stdin::lock_owned`]
+/// method.
+///
+/// [`Read`]: trait.Read.html
+/// [`Stdin::lock_owned`]: struct.Stdin.html#method.lock_owned
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct OwnedStdinLock {
+    stdin: Stdin,
+    _guard: OwnedMutexGuard<()>,
+}
+
 /// The state of the asynchronous stdin.
 ///
 /// The stdin can be either idle or busy performing an asynchronous operation.
@@ -99,13 +131,29 @@ struct Inner {
     /// The line buffer.
     line: String,
 
-    /// The write buffer.
+    /// The read buffer.
     buf: Vec<u8>,
 
+    /// Input that has been read but not yet returned.
+    pending: Vec<u8>,
+
+    /// Set when the end of the input has been reached but not yet returned.
+    eof: bool,
+
     /// The result of the last asynchronous operation on the stdin.
     last_op: Option<Operation>,
 }
 
+/// A stream over the lines of the standard input.
+///
+/// This stream is created by the [`Stdin::lines`] method.
+///
+/// [`Stdin::lines`]: struct.Stdin.html#method.lines
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct StdinLines(Stdin);
+
 /// Possible results of an asynchronous operation on the stdin.
 #[derive(Debug)]
 enum Operation {
@@ -130,22 +178,29 @@ impl Stdin {
     /// # Ok(()) }) }
     /// ```
     pub async fn read_line(&self, buf: &mut String) -> io::Result<usize> {
-        future::poll_fn(|cx| {
-            let state = &mut *self.0.lock().unwrap();
-
-            loop {
-                match state {
-                    State::Idle(opt) => {
-                        let inner = opt.as_mut().unwrap();
-
-                        // Check if the operation has completed.
-                        if let Some(Operation::ReadLine(res)) = inner.last_op.take() {
-                            let n = res?;
-
-                            // Copy the read data into the buffer and return.
-                            buf.push_str(&inner.line);
-                            return Poll::Ready(Ok(n));
-                        } else {
+        future::poll_fn(|cx| self.poll_read_line(cx, buf))
+            .await
+            .context(|| String::from("could not read line on stdin"))
+    }
+
+    /// Attempts to read a line of input into the specified buffer.
+    ///
+    /// The buffer is only appended to once a whole line, or the rest of the input, is available,
+    /// so that nothing is lost if the operation is abandoned.
+    fn poll_read_line(&self, cx: &mut Context<'_>, buf: &mut String) -> Poll<io::Result<usize>> {
+        let state = &mut *self.0.lock().unwrap();
+
+        loop {
+            match state {
+                State::Idle(opt) => {
+                    let inner = opt.as_mut().unwrap();
+                    inner.complete_op()?;
+
+                    // Return the first line of pending input, or all of it at the end of the input.
+                    let len = match inner.pending.iter().position(|&b| b == b'\n') {
+                        Some(i) => i + 1,
+                        None if mem::take(&mut inner.eof) => inner.pending.len(),
+                        None => {
                             let mut inner = opt.take().unwrap();
 
                             // Start the operation asynchronously.
@@ -155,15 +210,63 @@ impl Stdin {
                                 inner.last_op = Some(Operation::ReadLine(res));
                                 State::Idle(Some(inner))
                             }));
+                            continue;
                         }
-                    }
-                    // Poll the asynchronous operation the stdin is currently blocked on.
-                    State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
+                    };
+
+                    let line = std::str::from_utf8(&inner.pending[..len]).map_err(|_| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            "stream did not contain valid UTF-8",
+                        )
+                    })?;
+                    buf.push_str(line);
+                    inner.pending.drain(..len);
+                    return Poll::Ready(Ok(len));
                 }
+                // Poll the asynchronous operation the stdin is currently blocked on.
+                State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
             }
-        })
-        .await
-        .context(|| String::from("could not read line on stdin"))
+        }
+    }
+
+    /// Returns a stream over the lines of input.
+    ///
+    /// The stream yields items of type [`io::Result`]`<`[`String`]`>`, without the newline
+    /// characters at the end. Like [`read_line`], it doesn't lose input when the future returned
+    /// by `next` is dropped before it completes.
+    ///
+    /// [`io::Result`]: type.Result.html
+    /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
+    /// [`read_line`]: #method.read_line
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::future;
+    /// use async_std::io;
+    /// use async_std::prelude::*;
+    ///
+    /// let mut lines = io::stdin().lines();
+    ///
+    /// loop {
+    ///     match future::timeout(Duration::from_secs(5), lines.next()).await {
+    ///         Ok(Some(line)) => println!("> {}", line?),
+    ///         Ok(None) => break,
+    ///         Err(_) => println!("still waiting..."),
+    ///     }
+    /// }
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn lines(self) -> StdinLines {
+        StdinLines(self)
     }
 
     /// Locks this handle to the standard input stream, returning a readable guard.
@@ -194,11 +297,78 @@ impl Stdin {
 
         spawn_blocking(move || StdinLock(STDIN.lock())).await
     }
+
+    /// Locks the standard input, returning an owned guard.
+    ///
+    /// Unlike the guard returned by [`lock`], the owned guard can be held across `.await` points
+    /// and moved into spawned tasks. While it is held, other tasks calling this method wait, so
+    /// that consecutive reads by the holder aren't interleaved with theirs. Reads through other
+    /// handles aren't affected.
+    ///
+    /// [`lock`]: #method.lock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::io;
+    ///
+    /// let stdin = io::stdin().lock_owned().await;
+    /// let mut name = String::new();
+    /// let mut email = String::new();
+    ///
+    /// stdin.read_line(&mut name).await?;
+    /// stdin.read_line(&mut email).await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn lock_owned(&self) -> OwnedStdinLock {
+        static LOCK: Lazy<Arc<crate::sync::Mutex<()>>> = Lazy::new(Default::default);
+
+        OwnedStdinLock {
+            _guard: LOCK.clone().lock_owned().await,
+            stdin: stdin(),
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl OwnedStdinLock {
+    /// Reads a line of input into the specified buffer.
+    pub async fn read_line(&self, buf: &mut String) -> io::Result<usize> {
+        self.stdin.read_line(buf).await
+    }
+}
+
+impl Inner {
+    /// Adds the input read by the last asynchronous operation to the pending input.
+    fn complete_op(&mut self) -> io::Result<()> {
+        let n = match self.last_op.take() {
+            Some(Operation::ReadLine(res)) => {
+                let n = res?;
+                self.pending.extend_from_slice(self.line.as_bytes());
+                n
+            }
+            Some(Operation::Read(res)) => {
+                let n = res?;
+                self.pending.extend_from_slice(&self.buf[..n]);
+                n
+            }
+            None => return Ok(()),
+        };
+        if n == 0 {
+            self.eof = true;
+        }
+        Ok(())
+    }
 }
 
 impl Read for Stdin {
     fn poll_read(
-        mut self: Pin<&mut Self>,
+        self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &mut [u8],
     ) -> Poll<io::Result<usize>> {
@@ -208,36 +378,35 @@ impl Read for Stdin {
             match state {
                 State::Idle(opt) => {
                     let inner = opt.as_mut().unwrap();
+                    inner.complete_op()?;
 
-                    // Check if the operation has completed.
-                    if let Some(Operation::Read(res)) = inner.last_op.take() {
-                        let n = res?;
-
-                        // If more data was read than fits into the buffer, let's retry the read
-                        // operation.
-                        if n <= buf.len() {
-                            // Copy the read data into the buffer and return.
-                            buf[..n].copy_from_slice(&inner.buf[..n]);
-                            return Poll::Ready(Ok(n));
-                        }
-                    } else {
-                        let mut inner = opt.take().unwrap();
+                    // Return pending input first.
+                    if !inner.pending.is_empty() {
+                        let n = buf.len().min(inner.pending.len());
+                        buf[..n].copy_from_slice(&inner.pending[..n]);
+                        inner.pending.drain(..n);
+                        return Poll::Ready(Ok(n));
+                    }
+                    if mem::take(&mut inner.eof) || buf.is_empty() {
+                        return Poll::Ready(Ok(0));
+                    }
 
-                        // Set the length of the inner buffer to the length of the provided buffer.
-                        if inner.buf.len() < buf.len() {
-                            inner.buf.reserve(buf.len() - inner.buf.len());
-                        }
-                        unsafe {
-                            inner.buf.set_len(buf.len());
-                        }
+                    let mut inner = opt.take().unwrap();
 
-                        // Start the operation asynchronously.
-                        *state = State::Busy(spawn_blocking(move || {
-                            let res = std::io::Read::read(&mut inner.stdin, &mut inner.buf);
-                            inner.last_op = Some(Operation::Read(res));
-                            State::Idle(Some(inner))
-                        }));
+                    // Set the length of the inner buffer to the length of the provided buffer.
+                    if inner.buf.len() < buf.len() {
+                        inner.buf.reserve(buf.len() - inner.buf.len());
                     }
+                    unsafe {
+                        inner.buf.set_len(buf.len());
+                    }
+
+                    // Start the operation asynchronously.
+                    *state = State::Busy(spawn_blocking(move || {
+                        let res = std::io::Read::read(&mut inner.stdin, &mut inner.buf);
+                        inner.last_op = Some(Operation::Read(res));
+                        State::Idle(Some(inner))
+                    }));
                 }
                 // Poll the asynchronous operation the stdin is currently blocked on.
                 State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
@@ -246,6 +415,28 @@ impl Read for Stdin {
     }
 }
 
+#[cfg(feature = "unstable")]
+impl Stream for StdinLines {
+    type Item = io::Result<String>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut line = String::new();
+        match futures_core::ready!(self.0.poll_read_line(cx, &mut line)) {
+            Ok(0) => Poll::Ready(None),
+            Ok(_) => {
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+                Poll::Ready(Some(Ok(line)))
+            }
+            Err(err) => Poll::Ready(Some(Err(err))),
+        }
+    }
+}
+
 cfg_unix! {
     use crate::os::unix::io::{AsRawFd, RawFd};
 
@@ -266,6 +457,18 @@ cfg_windows! {
     }
 }
 
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+impl Read for OwnedStdinLock {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stdin).poll_read(cx, buf)
+    }
+}
+
 #[cfg(feature = "unstable")]
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
 impl Read for StdinLock<'_> {
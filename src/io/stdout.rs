@@ -1,4 +1,5 @@
 use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use std::future::Future;
 
@@ -6,10 +7,17 @@ use crate::io::{self, Write};
 use crate::task::{spawn_blocking, Context, JoinHandle, Poll};
 
 cfg_unstable! {
+    use std::sync::Arc;
+
     use once_cell::sync::Lazy;
     use std::io::Write as _;
+
+    use crate::sync::OwnedMutexGuard;
 }
 
+/// The amount of data buffered in block-buffered mode before it is written.
+const BLOCK_SIZE: usize = 8 * 1024;
+
 /// Constructs a new handle to the standard output of the current process.
 ///
 /// This function is an async version of [`std::io::stdout`].
@@ -36,11 +44,15 @@ cfg_unstable! {
 /// # Ok(()) }) }
 /// ```
 pub fn stdout() -> Stdout {
-    Stdout(Mutex::new(State::Idle(Some(Inner {
-        stdout: std::io::stdout(),
-        buf: Vec::new(),
-        last_op: None,
-    }))))
+    Stdout {
+        state: Mutex::new(State::Idle(Some(Inner {
+            stdout: std::io::stdout(),
+            buf: Vec::new(),
+            pending: Vec::new(),
+            last_op: None,
+        }))),
+        block_buffered: AtomicBool::new(false),
+    }
 }
 
 /// A handle to the standard output of the current process.
@@ -56,7 +68,12 @@ pub fn stdout() -> Stdout {
 ///
 /// [`stdout`]: fn.stdout.html
 #[derive(Debug)]
-pub struct Stdout(Mutex<State>);
+pub struct Stdout {
+    state: Mutex<State>,
+
+    /// Set to `true` if writes are buffered until a block is full.
+    block_buffered: AtomicBool,
+}
 
 /// A locked reference to the Stderr handle.
 ///
@@ -74,6 +91,40 @@ pub struct StdoutLock<'a>(std::io::StdoutLock<'a>);
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
 unsafe impl Send for StdoutLock<'_> {}
 
+/// An owned lock on the standard output, usable across `.await` points.
+///
+/// This handle implements the [`Write`] trait, and is constructed via the [`Stdout::lock_owned`]
+/// method.
+///
+/// [`Write`]: trait.Write.html
+/// [`Stdout::lock_owned`]: struct.Stdout.html#method.lock_owned
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct OwnedStdoutLock {
+    stdout: Stdout,
+
+    /// Dropped after `stdout`, once buffered data has been written.
+    _guard: OwnedMutexGuard<()>,
+}
+
+/// How [`Stdout`] buffers the data written to it.
+///
+/// [`Stdout`]: struct.Stdout.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BufferMode {
+    /// Data is written at the end of every line.
+    ///
+    /// This is the default, and matches the standard library.
+    Line,
+
+    /// Data is written once 8 KiB have accumulated, when the handle is flushed, or when it is
+    /// dropped.
+    Block,
+}
+
 /// The state of the asynchronous stdout.
 ///
 /// The stdout can be either idle or busy performing an asynchronous operation.
@@ -97,6 +148,9 @@ struct Inner {
     /// The write buffer.
     buf: Vec<u8>,
 
+    /// Data buffered in block-buffered mode, written before anything else.
+    pending: Vec<u8>,
+
     /// The result of the last asynchronous operation on the stdout.
     last_op: Option<Operation>,
 }
@@ -135,15 +189,108 @@ impl Stdout {
 
         spawn_blocking(move || StdoutLock(STDOUT.lock())).await
     }
+
+    /// Locks the standard output, returning an owned guard.
+    ///
+    /// Unlike the guard returned by [`lock`], the owned guard can be held across `.await` points
+    /// and moved into spawned tasks. While it is held, other tasks calling this method wait, so
+    /// that their output isn't interleaved. Output written through other handles isn't affected.
+    ///
+    /// The guard uses the [buffer mode] of this handle. Data buffered by this handle isn't written
+    /// first, so it should be flushed before locking.
+    ///
+    /// [`lock`]: #method.lock
+    /// [buffer mode]: #method.set_buffer_mode
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::io;
+    /// use async_std::prelude::*;
+    /// use async_std::task;
+    ///
+    /// let mut out = io::stdout().lock_owned().await;
+    ///
+    /// task::spawn(async move {
+    ///     out.write_all(b"first line\n").await?;
+    ///     task::yield_now().await;
+    ///     out.write_all(b"second line\n").await
+    /// })
+    /// .await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn lock_owned(&self) -> OwnedStdoutLock {
+        static LOCK: Lazy<Arc<crate::sync::Mutex<()>>> = Lazy::new(Default::default);
+
+        let guard = LOCK.clone().lock_owned().await;
+        let stdout = stdout();
+        stdout.set_buffer_mode(self.buffer_mode());
+        OwnedStdoutLock {
+            stdout,
+            _guard: guard,
+        }
+    }
+
+    /// Sets how this handle buffers the data written to it.
+    ///
+    /// Switching to [`BufferMode::Line`] doesn't write data already buffered right away. It is
+    /// written before the next write, or when the handle is flushed or dropped.
+    ///
+    /// Data buffered in block-buffered mode is lost if the process exits before the handle is
+    /// flushed or dropped.
+    ///
+    /// [`BufferMode::Line`]: enum.BufferMode.html#variant.Line
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::io::{self, BufferMode};
+    /// use async_std::prelude::*;
+    ///
+    /// let mut stdout = io::stdout();
+    /// stdout.set_buffer_mode(BufferMode::Block);
+    ///
+    /// for i in 0..1000 {
+    ///     stdout.write_all(format!("{}\n", i).as_bytes()).await?;
+    /// }
+    /// stdout.flush().await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn set_buffer_mode(&self, mode: BufferMode) {
+        self.block_buffered
+            .store(mode == BufferMode::Block, Ordering::SeqCst);
+    }
+
+    /// Returns how this handle buffers the data written to it.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn buffer_mode(&self) -> BufferMode {
+        if self.block_buffered.load(Ordering::SeqCst) {
+            BufferMode::Block
+        } else {
+            BufferMode::Line
+        }
+    }
 }
 
 impl Write for Stdout {
     fn poll_write(
-        mut self: Pin<&mut Self>,
+        self: Pin<&mut Self>,
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
-        let state = &mut *self.0.lock().unwrap();
+        let block_buffered = self.block_buffered.load(Ordering::SeqCst);
+        let state = &mut *self.state.lock().unwrap();
 
         loop {
             match state {
@@ -159,6 +306,22 @@ impl Write for Stdout {
                         if n <= buf.len() {
                             return Poll::Ready(Ok(n));
                         }
+                    } else if block_buffered && inner.pending.len() + buf.len() < BLOCK_SIZE {
+                        // Keep the data until the block is full.
+                        inner.pending.extend_from_slice(buf);
+                        return Poll::Ready(Ok(buf.len()));
+                    } else if block_buffered {
+                        let mut inner = opt.take().unwrap();
+                        inner.pending.extend_from_slice(buf);
+                        let n = buf.len();
+
+                        // Write the whole block asynchronously.
+                        *state = State::Busy(spawn_blocking(move || {
+                            let res = std::io::Write::write_all(&mut inner.stdout, &inner.pending);
+                            inner.pending.clear();
+                            inner.last_op = Some(Operation::Write(res.map(|()| n)));
+                            State::Idle(Some(inner))
+                        }));
                     } else {
                         let mut inner = opt.take().unwrap();
 
@@ -175,7 +338,9 @@ impl Write for Stdout {
 
                         // Start the operation asynchronously.
                         *state = State::Busy(spawn_blocking(move || {
-                            let res = std::io::Write::write(&mut inner.stdout, &inner.buf);
+                            let res = inner.write_pending().and_then(|()| {
+                                std::io::Write::write(&mut inner.stdout, &inner.buf)
+                            });
                             inner.last_op = Some(Operation::Write(res));
                             State::Idle(Some(inner))
                         }));
@@ -187,8 +352,8 @@ impl Write for Stdout {
         }
     }
 
-    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
-        let state = &mut *self.0.lock().unwrap();
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let state = &mut *self.state.lock().unwrap();
 
         loop {
             match state {
@@ -203,7 +368,9 @@ impl Write for Stdout {
 
                         // Start the operation asynchronously.
                         *state = State::Busy(spawn_blocking(move || {
-                            let res = std::io::Write::flush(&mut inner.stdout);
+                            let res = inner
+                                .write_pending()
+                                .and_then(|()| std::io::Write::flush(&mut inner.stdout));
                             inner.last_op = Some(Operation::Flush(res));
                             State::Idle(Some(inner))
                         }));
@@ -220,6 +387,46 @@ impl Write for Stdout {
     }
 }
 
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+impl Write for OwnedStdoutLock {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stdout).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stdout).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stdout).poll_close(cx)
+    }
+}
+
+impl Inner {
+    /// Writes the data buffered in block-buffered mode.
+    fn write_pending(&mut self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let res = std::io::Write::write_all(&mut self.stdout, &self.pending);
+        self.pending.clear();
+        res
+    }
+}
+
+impl Drop for Stdout {
+    fn drop(&mut self) {
+        if let Ok(State::Idle(Some(inner))) = self.state.get_mut() {
+            let _ = inner.write_pending();
+        }
+    }
+}
+
 cfg_unix! {
     use crate::os::unix::io::{AsRawFd, RawFd};
 
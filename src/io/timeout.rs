@@ -3,10 +3,10 @@ use std::task::{Context, Poll};
 use std::time::Duration;
 use std::future::Future;
 
-use futures_timer::Delay;
 use pin_project_lite::pin_project;
 
 use crate::io;
+use crate::time::Delay;
 
 /// Awaits an I/O future or times out after a duration of time.
 ///
@@ -1,12 +1,20 @@
+#[cfg(feature = "unstable")]
+mod close;
 mod flush;
 mod write;
 mod write_all;
+#[cfg(feature = "unstable")]
+mod write_all_resumable;
 mod write_fmt;
 mod write_vectored;
 
+#[cfg(feature = "unstable")]
+use close::CloseFuture;
 use flush::FlushFuture;
 use write::WriteFuture;
 use write_all::WriteAllFuture;
+#[cfg(feature = "unstable")]
+use write_all_resumable::WriteAllResumableFuture;
 use write_fmt::WriteFmtFuture;
 use write_vectored::WriteVectoredFuture;
 
@@ -143,6 +151,43 @@ extension_trait! {
             FlushFuture { writer: self }
         }
 
+        #[doc = r#"
+            Flushes and closes the stream.
+
+            Once the stream is closed, nothing more can be written to it. For a [`TcpStream`],
+            this shuts down the writing portion of the connection, so that the peer reads the end
+            of the stream while the response can still be read.
+
+            [`TcpStream`]: ../net/struct.TcpStream.html
+
+            # Examples
+
+            ```no_run
+            # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+            #
+            use async_std::net::TcpStream;
+            use async_std::prelude::*;
+
+            let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+
+            stream.write_all(b"request").await?;
+            stream.close().await?;
+
+            let mut response = Vec::new();
+            stream.read_to_end(&mut response).await?;
+            #
+            # Ok(()) }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn close(&mut self) -> impl Future<Output = io::Result<()>> + '_ [CloseFuture<'_, Self>]
+        where
+            Self: Unpin,
+        {
+            CloseFuture { writer: self }
+        }
+
         #[doc = r#"
             Like [`write`], except that it writes from a slice of buffers.
 
@@ -172,7 +217,14 @@ extension_trait! {
             written or an error is returned. This method will not return until the entire
             buffer has been successfully written or such an error occurs.
 
+            # Cancel safety
+
+            This method is not cancel safe. If the future is dropped before it completes, part of
+            `buf` may have been written without a way to tell how much. Use
+            [`write_all_resumable`] to resume the write after it was interrupted.
+
             [`write`]: #tymethod.write
+            [`write_all_resumable`]: #method.write_all_resumable
 
             # Examples
 
@@ -201,6 +253,59 @@ extension_trait! {
             WriteAllFuture { writer: self, buf }
         }
 
+        #[doc = r#"
+            Writes an entire buffer into the byte stream, keeping track of the progress in
+            `written`.
+
+            Bytes are written from `buf[*written..]`, and `written` is advanced as soon as they
+            are, so that it always tells how much of `buf` has been written. The write is complete
+            once `written` reaches the length of `buf`.
+
+            # Cancel safety
+
+            This method is cancel safe. If the future is dropped before it completes, calling
+            this method again with the same `buf` and `written` resumes the write where it
+            stopped, without writing any byte twice.
+
+            # Examples
+
+            ```no_run
+            # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+            #
+            use std::time::Duration;
+
+            use async_std::future;
+            use async_std::net::TcpStream;
+            use async_std::prelude::*;
+
+            let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+
+            let request = b"GET / HTTP/1.1\r\n\r\n";
+            let mut written = 0;
+            loop {
+                let write = stream.write_all_resumable(request, &mut written);
+                match future::timeout(Duration::from_secs(1), write).await {
+                    Ok(res) => break res?,
+                    Err(_) => println!("{} bytes sent so far", written),
+                }
+            }
+            #
+            # Ok(()) }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn write_all_resumable<'a>(
+            &'a mut self,
+            buf: &'a [u8],
+            written: &'a mut usize,
+        ) -> impl Future<Output = io::Result<()>> + 'a [WriteAllResumableFuture<'a, Self>]
+        where
+            Self: Unpin,
+        {
+            WriteAllResumableFuture { writer: self, buf, written }
+        }
+
         #[doc = r#"
             Writes a formatted string into this writer, returning any error encountered.
 
@@ -0,0 +1,36 @@
+use std::pin::Pin;
+use std::future::Future;
+
+use crate::io::{self, Write};
+use crate::task::{Context, Poll};
+
+#[doc(hidden)]
+#[allow(missing_debug_implementations)]
+pub struct WriteAllResumableFuture<'a, T: Unpin + ?Sized> {
+    pub(crate) writer: &'a mut T,
+    pub(crate) buf: &'a [u8],
+    pub(crate) written: &'a mut usize,
+}
+
+impl<T: Write + Unpin + ?Sized> Future for WriteAllResumableFuture<'_, T> {
+    type Output = io::Result<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self {
+            writer,
+            buf,
+            written,
+        } = &mut *self;
+
+        while **written < buf.len() {
+            let n =
+                futures_core::ready!(Pin::new(&mut **writer).poll_write(cx, &buf[**written..]))?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            **written += n;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
@@ -211,6 +211,86 @@
 //! features = ["attributes"]
 //! ```
 //!
+//! Items marked with
+//! <span
+//!   class="module-item stab portability"
+//!   style="display: inline; border-radius: 3px; padding: 2px; font-size: 80%; line-height: 1.2;"
+//! ><code>io-uring</code></span>
+//! are available only when the `io-uring` Cargo feature is enabled, which also enables
+//! `unstable`:
+//!
+//! ```toml
+//! [dependencies.async-std]
+//! version = "1.0.0"
+//! features = ["io-uring"]
+//! ```
+//!
+//! Items marked with
+//! <span
+//!   class="module-item stab portability"
+//!   style="display: inline; border-radius: 3px; padding: 2px; font-size: 80%; line-height: 1.2;"
+//! ><code>compress</code></span>
+//! are available only when the `compress` Cargo feature is enabled, which also enables
+//! `unstable`:
+//!
+//! ```toml
+//! [dependencies.async-std]
+//! version = "1.0.0"
+//! features = ["compress"]
+//! ```
+//!
+//! Items marked with
+//! <span
+//!   class="module-item stab portability"
+//!   style="display: inline; border-radius: 3px; padding: 2px; font-size: 80%; line-height: 1.2;"
+//! ><code>tokio-compat</code></span>
+//! are available only when the `tokio-compat` Cargo feature is enabled, which also enables
+//! `unstable`:
+//!
+//! ```toml
+//! [dependencies.async-std]
+//! version = "1.0.0"
+//! features = ["tokio-compat"]
+//! ```
+//!
+//! Items marked with
+//! <span
+//!   class="module-item stab portability"
+//!   style="display: inline; border-radius: 3px; padding: 2px; font-size: 80%; line-height: 1.2;"
+//! ><code>tls</code></span>
+//! are available only when the `tls` Cargo feature is enabled, which also enables `unstable`:
+//!
+//! ```toml
+//! [dependencies.async-std]
+//! version = "1.0.0"
+//! features = ["tls"]
+//! ```
+//!
+//! The `tokio-runtime` Cargo feature, which also enables `unstable`, lets tasks await futures
+//! that need a tokio reactor or timer, such as the ones of `reqwest`. A tokio runtime is started
+//! on first use, with its drivers running on a background thread, and every task and
+//! [`task::block_on`] call is polled within its context. Tokio tasks spawned from async-std tasks
+//! run on that runtime as well.
+//!
+//! ```toml
+//! [dependencies.async-std]
+//! version = "1.0.0"
+//! features = ["tokio-runtime"]
+//! ```
+//!
+//! The `tracing` Cargo feature makes the runtime report to the [`tracing`] crate, with a span for
+//! every task and its polls, and events when the I/O reactor wakes up or a timer fires. See the
+//! [`task`] module for the spans and events and their fields.
+//!
+//! ```toml
+//! [dependencies.async-std]
+//! version = "1.0.0"
+//! features = ["tracing"]
+//! ```
+//!
+//! [`tracing`]: https://docs.rs/tracing
+//! [`task`]: task/index.html#instrumentation
+//!
 //! Additionally it's possible to only use the core traits and combinators by
 //! only enabling the `std` Cargo feature:
 //!
@@ -220,6 +300,27 @@
 //! default-features = false
 //! features = ["std"]
 //! ```
+//!
+//! # Platform support
+//!
+//! On `wasm32-unknown-unknown`, where there are neither threads nor an I/O reactor, tasks run on
+//! the JavaScript event loop instead: [`task::spawn`] queues them with `wasm-bindgen-futures`,
+//! and timers such as [`task::sleep`] and [`future::timeout`] wait on `setTimeout`. The
+//! [`sync`] primitives and channels work as on other platforms, so libraries written against
+//! async-std can run in the browser.
+//!
+//! The [`net`] module and [`task::LocalSet`] are not available there, and blocking on a future
+//! with [`task::block_on`] isn't possible in the browser. The standard clock isn't available
+//! either, so APIs taking an [`Instant`] panic like `Instant::now` does.
+//!
+//! [`task::spawn`]: task/fn.spawn.html
+//! [`task::sleep`]: task/fn.sleep.html
+//! [`future::timeout`]: future/fn.timeout.html
+//! [`sync`]: sync/index.html
+//! [`net`]: net/index.html
+//! [`task::LocalSet`]: task/struct.LocalSet.html
+//! [`task::block_on`]: task/fn.block_on.html
+//! [`Instant`]: https://doc.rust-lang.org/std/time/struct.Instant.html
 
 #![cfg_attr(feature = "docs", feature(doc_cfg))]
 #![warn(missing_docs, missing_debug_implementations, rust_2018_idioms)]
@@ -253,12 +354,24 @@ cfg_std! {
 cfg_default! {
     pub mod fs;
     pub mod path;
+
+    #[cfg(not(target_arch = "wasm32"))]
     pub mod net;
 }
 
+#[cfg(all(feature = "default", not(feature = "unstable")))]
+mod time;
+
 cfg_unstable! {
     pub mod pin;
     pub mod process;
+    pub mod runtime;
+    pub mod sink;
+    pub mod time;
+
+    #[cfg(feature = "tokio-compat")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "tokio-compat")))]
+    pub mod compat;
 
     mod unit;
     mod vec;
@@ -269,4 +382,7 @@ cfg_unstable! {
 
     #[doc(inline)]
     pub use std::{write, writeln};
+
+    #[doc(hidden)]
+    pub use utils::random as __random;
 }
@@ -217,3 +217,273 @@ macro_rules! task_local {
         $crate::task_local!($($rest)*);
     );
 }
+
+/// Waits on multiple concurrent branches, returning when the first one completes.
+///
+/// Each branch has the form `<pattern> = <future> => <handler>`. All futures are polled
+/// concurrently. When a future completes and its output matches the pattern, the handler runs
+/// with the pattern's bindings and its value becomes the value of the whole `select!`. The other
+/// futures are dropped.
+///
+/// If a future completes but its output doesn't match the pattern, that branch is disabled and
+/// the remaining branches keep being polled. When all branches are disabled, the optional
+/// `else => <handler>` branch runs. Without an `else` branch, `select!` panics in that case.
+///
+/// By default, branches are polled in a random order on every wake-up so that no branch gets
+/// starved. Starting the macro with `biased;` polls branches in the order they are declared.
+///
+/// Channel operations are futures too, so `recv()` and `send()` can be raced like any other
+/// future. Handlers run outside of the polling loop, so they may use `.await`, `?`, `return`,
+/// `break`, and `continue`.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::future;
+/// use async_std::select;
+/// use async_std::sync::channel;
+///
+/// let (s, r) = channel(1);
+/// s.send(1).await;
+///
+/// let n = select! {
+///     Some(n) = r.recv() => n,
+///     _ = future::pending::<()>() => unreachable!(),
+/// };
+/// assert_eq!(n, 1);
+///
+/// // Once the channel is closed, `recv()` returns `None` and its branch gets disabled.
+/// drop(s);
+/// let n = select! {
+///     biased;
+///     Some(n) = r.recv() => n,
+///     else => 0,
+/// };
+/// assert_eq!(n, 0);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[macro_export]
+macro_rules! select {
+    (@one $t:tt) => (1);
+
+    (@else) => (panic!("all branches are disabled and there is no else branch"));
+    (@else $else:expr) => ($else);
+
+    // All branches have been parsed.
+    (@parse $biased:tt;
+        [$([$fut:ident $done:ident $out:ident ($p:pat) ($f:expr) ($body:tt)])*]
+        $(else => $else:expr $(,)?)?
+    ) => {{
+        $(
+            let mut $fut = $f;
+            // The original future is shadowed and can never be moved again.
+            #[allow(unused_mut)]
+            let mut $fut = unsafe { ::core::pin::Pin::new_unchecked(&mut $fut) };
+            let mut $done = false;
+            let mut $out = ::core::option::Option::None;
+        )*
+
+        #[allow(unused_variables, unused_mut)]
+        $crate::future::poll_fn(|cx| {
+            let count: u32 = 0 $(+ $crate::select!(@one $fut))*;
+            let start = if $biased { 0 } else { $crate::__random(count) };
+
+            // Poll branches starting at `start` and wrapping around.
+            for round in 0..2 {
+                let mut idx = 0u32;
+                $(
+                    idx += 1;
+                    if (round == 0) == (idx > start) && !$done {
+                        if let $crate::task::Poll::Ready(v) =
+                            $crate::future::Future::poll($fut.as_mut(), cx)
+                        {
+                            $done = true;
+                            #[allow(unused_variables, unreachable_patterns)]
+                            match &v {
+                                $p => {
+                                    $out = ::core::option::Option::Some(v);
+                                    return $crate::task::Poll::Ready(());
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                )*
+            }
+
+            if true $(&& $done)* {
+                $crate::task::Poll::Ready(())
+            } else {
+                $crate::task::Poll::Pending
+            }
+        })
+        .await;
+
+        $(
+            if let ::core::option::Option::Some($p) = $out {
+                $body
+            } else
+        )* {
+            $crate::select!(@else $($else)?)
+        }
+    }};
+
+    (@parse $biased:tt; [$($branch:tt)*] , $($rest:tt)*) => (
+        $crate::select!(@parse $biased; [$($branch)*] $($rest)*)
+    );
+
+    (@parse $biased:tt; [$($branch:tt)*] $p:pat = $f:expr => $body:block $($rest:tt)*) => (
+        $crate::select!(@parse $biased; [$($branch)* [fut done out ($p) ($f) ($body)]] $($rest)*)
+    );
+
+    (@parse $biased:tt; [$($branch:tt)*] $p:pat = $f:expr => $body:expr $(, $($rest:tt)*)?) => (
+        $crate::select!(@parse $biased; [$($branch)* [fut done out ($p) ($f) ($body)]] $($($rest)*)?)
+    );
+
+    (biased; $($t:tt)*) => ($crate::select!(@parse true; [] $($t)*));
+
+    ($($t:tt)*) => ($crate::select!(@parse false; [] $($t)*));
+}
+
+/// Waits on multiple concurrent futures, returning all of their outputs once they complete.
+///
+/// The futures may have different output types. They are polled concurrently on the current
+/// task, and the outputs are returned as a tuple in the order the futures were passed in.
+///
+/// Use [`try_join!`] to stop early when a fallible future fails.
+///
+/// [`try_join!`]: macro.try_join.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::future;
+///
+/// let a = future::ready(1u8);
+/// let b = async { "two" };
+/// let c = future::ready(3u16);
+///
+/// assert_eq!(future::join!(a, b, c), (1, "two", 3));
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[macro_export]
+macro_rules! join {
+    // All futures have been parsed.
+    (@parse [$([$fut:ident $out:ident ($f:expr)])*]) => {{
+        $(
+            let mut $fut = $f;
+            // The original future is shadowed and can never be moved again.
+            let mut $fut = unsafe { ::core::pin::Pin::new_unchecked(&mut $fut) };
+            let mut $out = ::core::option::Option::None;
+        )*
+
+        $crate::future::poll_fn(|cx| {
+            let mut done = true;
+            $(
+                if $out.is_none() {
+                    match $crate::future::Future::poll($fut.as_mut(), cx) {
+                        $crate::task::Poll::Ready(v) => $out = ::core::option::Option::Some(v),
+                        $crate::task::Poll::Pending => done = false,
+                    }
+                }
+            )*
+
+            if done {
+                $crate::task::Poll::Ready(($($out.take().unwrap(),)*))
+            } else {
+                $crate::task::Poll::Pending
+            }
+        })
+        .await
+    }};
+
+    (@parse [$($fut:tt)*] $f:expr $(, $($rest:tt)*)?) => (
+        $crate::join!(@parse [$($fut)* [fut out ($f)]] $($($rest)*)?)
+    );
+
+    ($($t:tt)*) => ($crate::join!(@parse [] $($t)*));
+}
+
+/// Waits on multiple concurrent fallible futures, returning all of their outputs once they
+/// succeed or the first error.
+///
+/// The futures may have different success types but must share the error type. They are polled
+/// concurrently on the current task. If any future fails, the remaining ones are dropped and its
+/// error is returned. Otherwise, the outputs are returned as a tuple in the order the futures
+/// were passed in.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::io::{Error, ErrorKind};
+///
+/// use async_std::future;
+///
+/// let a = future::ready(Ok::<_, Error>(1u8));
+/// let b = future::ready(Ok("two"));
+/// assert_eq!(future::try_join!(a, b).unwrap(), (1, "two"));
+///
+/// let a = future::pending::<Result<u8, Error>>();
+/// let b = future::ready(Err::<(), _>(Error::from(ErrorKind::Other)));
+/// assert!(future::try_join!(a, b).is_err());
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[macro_export]
+macro_rules! try_join {
+    // All futures have been parsed.
+    (@parse [$([$fut:ident $out:ident ($f:expr)])*]) => {{
+        $(
+            let mut $fut = $f;
+            // The original future is shadowed and can never be moved again.
+            let mut $fut = unsafe { ::core::pin::Pin::new_unchecked(&mut $fut) };
+            let mut $out = ::core::option::Option::None;
+        )*
+
+        $crate::future::poll_fn(|cx| {
+            let mut done = true;
+            $(
+                if $out.is_none() {
+                    match $crate::future::Future::poll($fut.as_mut(), cx) {
+                        $crate::task::Poll::Ready(::core::result::Result::Ok(v)) => {
+                            $out = ::core::option::Option::Some(v);
+                        }
+                        $crate::task::Poll::Ready(::core::result::Result::Err(e)) => {
+                            return $crate::task::Poll::Ready(::core::result::Result::Err(e));
+                        }
+                        $crate::task::Poll::Pending => done = false,
+                    }
+                }
+            )*
+
+            if done {
+                $crate::task::Poll::Ready(::core::result::Result::Ok(
+                    ($($out.take().unwrap(),)*)
+                ))
+            } else {
+                $crate::task::Poll::Pending
+            }
+        })
+        .await
+    }};
+
+    (@parse [$($fut:tt)*] $f:expr $(, $($rest:tt)*)?) => (
+        $crate::try_join!(@parse [$($fut)* [fut out ($f)]] $($($rest)*)?)
+    );
+
+    ($($t:tt)*) => ($crate::try_join!(@parse [] $($t)*));
+}
@@ -1,4 +1,7 @@
+use std::cell::RefCell;
 use std::fmt;
+#[cfg(feature = "unstable")]
+use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 
 use mio::{self, Evented};
@@ -6,9 +9,46 @@ use once_cell::sync::Lazy;
 use slab::Slab;
 
 use crate::io;
-use crate::task::{Context, Poll, Waker};
+#[cfg(feature = "unstable")]
+use crate::runtime::metrics::COUNTERS;
+#[cfg(feature = "unstable")]
+use crate::task::executor::Park;
+use crate::task::{budget, Context, Poll, Waker};
+#[cfg(feature = "unstable")]
+use crate::time::wheel;
+use crate::time::wheel::{Timer, TIMER};
 use crate::utils::abort_on_panic;
 
+use wakeup::{Alarm, Notifier};
+
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub(crate) mod uring;
+mod wakeup;
+
+/// The token of the notifier waking up the polling thread.
+const NOTIFY_TOKEN: mio::Token = mio::Token(usize::MAX - 1);
+
+/// The token of the alarm waking up the polling thread at the next timer deadline.
+const ALARM_TOKEN: mio::Token = mio::Token(usize::MAX - 2);
+
+/// Returns `true` if sockets created on the current thread should use io_uring.
+///
+/// This is the case inside a runtime built with io_uring enabled.
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub(crate) fn uring_selected() -> bool {
+    crate::task::executor::io_uring()
+}
+
+/// Returns `true` if io_uring can be used on this system.
+#[cfg(feature = "io-uring")]
+pub(crate) fn uring_supported() -> bool {
+    #[cfg(target_os = "linux")]
+    return uring::is_supported();
+
+    #[cfg(not(target_os = "linux"))]
+    return false;
+}
+
 /// Data associated with a registered I/O handle.
 #[derive(Debug)]
 struct Entry {
@@ -30,30 +70,30 @@ struct Reactor {
     /// A collection of registered I/O handles.
     entries: Mutex<Slab<Arc<Entry>>>,
 
-    /// Dummy I/O handle that is only used to wake up the polling thread.
-    notify_reg: (mio::Registration, mio::SetReadiness),
+    /// Wakes up the polling thread from other threads.
+    notifier: Arc<Notifier>,
+
+    /// Wakes up the polling thread when the next timer is due.
+    alarm: Alarm,
 
-    /// An identifier for the notification handle.
-    notify_token: mio::Token,
+    /// The timers fired by the polling thread.
+    timer: Arc<Timer>,
 }
 
 impl Reactor {
-    /// Creates a new reactor for polling I/O events.
-    fn new() -> io::Result<Reactor> {
-        let poller = mio::Poll::new()?;
-        let notify_reg = mio::Registration::new2();
-
-        let mut reactor = Reactor {
-            poller,
+    /// Creates a new reactor for polling I/O events and firing the timers of `timer`.
+    ///
+    /// The timer must wake up the polling thread through `notifier`.
+    fn new(notifier: Arc<Notifier>, timer: Arc<Timer>) -> io::Result<Reactor> {
+        let reactor = Reactor {
+            poller: mio::Poll::new()?,
             entries: Mutex::new(Slab::new()),
-            notify_reg,
-            notify_token: mio::Token(0),
+            notifier,
+            alarm: Alarm::new()?,
+            timer,
         };
-
-        // Register a dummy I/O handle for waking up the polling thread.
-        let entry = reactor.register(&reactor.notify_reg.0)?;
-        reactor.notify_token = entry.token;
-
+        reactor.notifier.register(&reactor.poller, NOTIFY_TOKEN)?;
+        reactor.alarm.register(&reactor.poller, ALARM_TOKEN)?;
         Ok(reactor)
     }
 
@@ -92,50 +132,33 @@ impl Reactor {
         Ok(())
     }
 
-    // fn notify(&self) {
-    //     self.notify_reg
-    //         .1
-    //         .set_readiness(mio::Ready::readable())
-    //         .unwrap();
-    // }
-}
+    /// Fires due timers, then waits on the poller for new events until the next timer deadline
+    /// and wakes up tasks blocked on I/O handles.
+    fn turn(&self, events: &mut mio::Events) -> io::Result<()> {
+        // Fire due timers and set the alarm to the next deadline.
+        let timeout = self.alarm.set(self.timer.fire())?;
 
-/// The state of the global networking driver.
-static REACTOR: Lazy<Reactor> = Lazy::new(|| {
-    // Spawn a thread that waits on the poller for new events and wakes up tasks blocked on I/O
-    // handles.
-    std::thread::Builder::new()
-        .name("async-std/net".to_string())
-        .spawn(move || {
-            // If the driver thread panics, there's not much we can do. It is not a
-            // recoverable error and there is no place to propagate it into so we just abort.
-            abort_on_panic(|| {
-                main_loop().expect("async networking thread has panicked");
-            })
-        })
-        .expect("cannot start a thread driving blocking tasks");
-
-    Reactor::new().expect("cannot initialize reactor")
-});
+        // Block on the poller until at least one new event comes in.
+        self.poller.poll(events, timeout)?;
 
-/// Waits on the poller for new events and wakes up tasks blocked on I/O handles.
-fn main_loop() -> io::Result<()> {
-    let reactor = &REACTOR;
-    let mut events = mio::Events::with_capacity(1000);
+        #[cfg(feature = "unstable")]
+        COUNTERS.reactor_wakeups.fetch_add(1, Ordering::Relaxed);
 
-    loop {
-        // Block on the poller until at least one new event comes in.
-        reactor.poller.poll(&mut events, None)?;
+        #[cfg(feature = "tracing")]
+        tracing::trace!(events = events.iter().count(), "reactor wakeup");
 
         // Lock the entire entry table while we're processing new events.
-        let entries = reactor.entries.lock().unwrap();
+        let entries = self.entries.lock().unwrap();
 
         for event in events.iter() {
             let token = event.token();
 
-            if token == reactor.notify_token {
-                // If this is the notification token, we just need the notification state.
-                reactor.notify_reg.1.set_readiness(mio::Ready::empty())?;
+            if token == NOTIFY_TOKEN {
+                // Timers are checked on every iteration, so we just need to reset the notifier.
+                self.notifier.clear()?;
+            } else if token == ALARM_TOKEN {
+                // The alarm went off, so due timers are fired on the next iteration.
+                self.alarm.clear()?;
             } else {
                 // Otherwise, look for the entry associated with this token.
                 if let Some(entry) = entries.get(token.0) {
@@ -158,6 +181,120 @@ fn main_loop() -> io::Result<()> {
                 }
             }
         }
+        Ok(())
+    }
+}
+
+/// Wakes up the polling thread so that it rechecks the timer deadline.
+///
+/// Calls made before the polling thread has woken up are coalesced into a single wakeup.
+pub(crate) fn notify() {
+    REACTOR
+        .notifier
+        .notify()
+        .expect("cannot wake up the networking thread");
+}
+
+/// The state of the global networking driver.
+static REACTOR: Lazy<Arc<Reactor>> = Lazy::new(|| {
+    // Spawn a thread that waits on the poller for new events and wakes up tasks blocked on I/O
+    // handles.
+    std::thread::Builder::new()
+        .name("async-std/net".to_string())
+        .spawn(move || {
+            // If the driver thread panics, there's not much we can do. It is not a
+            // recoverable error and there is no place to propagate it into so we just abort.
+            abort_on_panic(|| {
+                main_loop().expect("async networking thread has panicked");
+            })
+        })
+        .expect("cannot start a thread driving blocking tasks");
+
+    let notifier = Notifier::new().expect("cannot initialize reactor");
+    Arc::new(Reactor::new(Arc::new(notifier), TIMER.clone()).expect("cannot initialize reactor"))
+});
+
+thread_local! {
+    /// The reactor of the current-thread runtime driven by this thread, if any.
+    static CURRENT: RefCell<Option<Arc<Reactor>>> = const { RefCell::new(None) };
+}
+
+/// Returns the reactor that I/O handles created on the current thread register in.
+fn current() -> Arc<Reactor> {
+    CURRENT
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(|| REACTOR.clone())
+}
+
+/// Waits on the poller for new events and wakes up tasks blocked on I/O handles or timers.
+fn main_loop() -> io::Result<()> {
+    let reactor = &REACTOR;
+    let mut events = mio::Events::with_capacity(1000);
+
+    loop {
+        reactor.turn(&mut events)?;
+    }
+}
+
+/// A reactor and timer wheel driven by the thread running a current-thread runtime.
+///
+/// Instead of a background thread, the thread blocking on the runtime polls the reactor when it
+/// has no tasks to run, until an I/O event comes in, the next timer is due, or a task is woken
+/// up. I/O handles and timers created inside the runtime are registered in this driver, and only
+/// make progress while the runtime is being blocked on.
+#[cfg(feature = "unstable")]
+pub(crate) struct Driver {
+    reactor: Arc<Reactor>,
+
+    /// The buffer of events the reactor is polled into.
+    events: Mutex<mio::Events>,
+}
+
+#[cfg(feature = "unstable")]
+impl Driver {
+    /// Creates a driver with its own reactor and timer wheel.
+    pub(crate) fn new() -> io::Result<Driver> {
+        let notifier = Arc::new(Notifier::new()?);
+        let timer = Arc::new(Timer::new({
+            let notifier = notifier.clone();
+            move || {
+                notifier
+                    .notify()
+                    .expect("cannot wake up the thread driving the runtime")
+            }
+        }));
+
+        Ok(Driver {
+            reactor: Arc::new(Reactor::new(notifier, timer)?),
+            events: Mutex::new(mio::Events::with_capacity(1000)),
+        })
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl Park for Driver {
+    fn enter(&self, f: &mut dyn FnMut()) {
+        CURRENT.with(|current| {
+            let old = current.replace(Some(self.reactor.clone()));
+            defer! {
+                *current.borrow_mut() = old;
+            }
+            wheel::enter(&self.reactor.timer, f)
+        })
+    }
+
+    fn park(&self) {
+        let mut events = self.events.lock().unwrap();
+        self.reactor
+            .turn(&mut events)
+            .expect("cannot poll the reactor of the runtime");
+    }
+
+    fn unpark(&self) {
+        self.reactor
+            .notifier
+            .notify()
+            .expect("cannot wake up the thread driving the runtime");
     }
 }
 
@@ -166,6 +303,9 @@ fn main_loop() -> io::Result<()> {
 /// This handle wraps an I/O event source and exposes a "futurized" interface on top of it,
 /// implementing traits `AsyncRead` and `AsyncWrite`.
 pub struct Watcher<T: Evented> {
+    /// The reactor the I/O handle is registered in.
+    reactor: Arc<Reactor>,
+
     /// Data associated with the I/O handle.
     entry: Arc<Entry>,
 
@@ -179,10 +319,12 @@ impl<T: Evented> Watcher<T> {
     /// The provided I/O event source will be kept registered inside the reactor's poller for the
     /// lifetime of the returned I/O handle.
     pub fn new(source: T) -> Watcher<T> {
+        let reactor = current();
         Watcher {
-            entry: REACTOR
+            entry: reactor
                 .register(&source)
                 .expect("cannot register an I/O event source"),
+            reactor,
             source: Some(source),
         }
     }
@@ -200,6 +342,9 @@ impl<T: Evented> Watcher<T> {
     where
         F: FnMut(&'a T) -> io::Result<R>,
     {
+        // Yield if the task has used up its budget.
+        futures_core::ready!(budget::poll_proceed(cx));
+
         // If the operation isn't blocked, return its result.
         match f(self.source.as_ref().unwrap()) {
             Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
@@ -235,6 +380,9 @@ impl<T: Evented> Watcher<T> {
     where
         F: FnMut(&'a T) -> io::Result<R>,
     {
+        // Yield if the task has used up its budget.
+        futures_core::ready!(budget::poll_proceed(cx));
+
         // If the operation isn't blocked, return its result.
         match f(self.source.as_ref().unwrap()) {
             Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
@@ -264,7 +412,7 @@ impl<T: Evented> Watcher<T> {
     #[allow(dead_code)]
     pub fn into_inner(mut self) -> T {
         let source = self.source.take().unwrap();
-        REACTOR
+        self.reactor
             .deregister(&source, &self.entry)
             .expect("cannot deregister I/O event source");
         source
@@ -274,7 +422,7 @@ impl<T: Evented> Watcher<T> {
 impl<T: Evented> Drop for Watcher<T> {
     fn drop(&mut self) {
         if let Some(ref source) = self.source {
-            REACTOR
+            self.reactor
                 .deregister(source, &self.entry)
                 .expect("cannot deregister I/O event source");
         }
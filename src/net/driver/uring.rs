@@ -0,0 +1,730 @@
+//! A completion-based networking driver built on Linux's io_uring.
+//!
+//! Operations are submitted to a single ring shared by the whole process. A dedicated thread waits
+//! for completions and wakes up the tasks waiting for them.
+//!
+//! The kernel writes into and reads from the buffers of in-flight operations, so every operation
+//! owns its buffer. Reads complete into that buffer and are copied out, and writes copy their data
+//! in and complete in the background.
+
+use std::fmt;
+use std::mem;
+use std::os::unix::io::RawFd;
+use std::ptr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+
+use once_cell::sync::Lazy;
+
+use crate::io;
+#[cfg(feature = "unstable")]
+use crate::runtime::metrics::COUNTERS;
+use crate::task::{budget, Context, Poll, Waker};
+use crate::utils::abort_on_panic;
+
+const IORING_OFF_SQ_RING: libc::off_t = 0;
+const IORING_OFF_CQ_RING: libc::off_t = 0x800_0000;
+const IORING_OFF_SQES: libc::off_t = 0x1000_0000;
+
+const IORING_ENTER_GETEVENTS: u32 = 1;
+
+const IORING_OP_ACCEPT: u8 = 13;
+const IORING_OP_ASYNC_CANCEL: u8 = 14;
+const IORING_OP_SEND: u8 = 26;
+const IORING_OP_RECV: u8 = 27;
+
+/// The number of entries in the submission queue.
+const ENTRIES: u32 = 256;
+
+/// The maximum number of bytes read or written by a single operation.
+const MAX_BUF: usize = 64 * 1024;
+
+#[repr(C)]
+#[derive(Default)]
+struct SqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    flags: u32,
+    dropped: u32,
+    array: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct CqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    overflow: u32,
+    cqes: u32,
+    flags: u32,
+    resv1: u32,
+    user_addr: u64,
+}
+
+#[repr(C)]
+#[derive(Default)]
+struct Params {
+    sq_entries: u32,
+    cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+    features: u32,
+    wq_fd: u32,
+    resv: [u32; 3],
+    sq_off: SqringOffsets,
+    cq_off: CqringOffsets,
+}
+
+/// A submission queue entry.
+#[repr(C)]
+#[derive(Default)]
+struct Sqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    off: u64,
+    addr: u64,
+    len: u32,
+    op_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    addr3: u64,
+    pad: u64,
+}
+
+/// A completion queue entry.
+#[repr(C)]
+struct Cqe {
+    user_data: u64,
+    res: i32,
+    flags: u32,
+}
+
+/// A memory mapping of a part of the ring.
+struct Mmap {
+    ptr: *mut u8,
+    len: usize,
+}
+
+impl Mmap {
+    fn new(fd: RawFd, len: usize, offset: libc::off_t) -> io::Result<Mmap> {
+        let ptr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                len,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_SHARED | libc::MAP_POPULATE,
+                fd,
+                offset,
+            )
+        };
+        if ptr == libc::MAP_FAILED {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mmap {
+            ptr: ptr as *mut u8,
+            len,
+        })
+    }
+
+    /// Returns a pointer at the given offset into the mapping.
+    fn at<T>(&self, offset: u32) -> *mut T {
+        unsafe { self.ptr.add(offset as usize) as *mut T }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr as *mut libc::c_void, self.len);
+        }
+    }
+}
+
+/// The submission side of the ring.
+struct Submission {
+    head: *const AtomicU32,
+    tail: *const AtomicU32,
+    mask: u32,
+    entries: u32,
+    array: *mut u32,
+    sqes: *mut Sqe,
+}
+
+/// The completion side of the ring.
+struct Completion {
+    head: *const AtomicU32,
+    tail: *const AtomicU32,
+    mask: u32,
+    cqes: *const Cqe,
+}
+
+/// An io_uring instance.
+struct Ring {
+    fd: RawFd,
+    sq: Mutex<Submission>,
+    cq: Completion,
+    _maps: [Mmap; 3],
+}
+
+unsafe impl Send for Ring {}
+unsafe impl Sync for Ring {}
+
+impl Ring {
+    fn new() -> io::Result<Ring> {
+        let mut p = Params::default();
+        let fd = unsafe { libc::syscall(libc::SYS_io_uring_setup, ENTRIES, &mut p as *mut Params) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = fd as RawFd;
+
+        let maps = (|| {
+            let sq_len = p.sq_off.array as usize + p.sq_entries as usize * mem::size_of::<u32>();
+            let cq_len = p.cq_off.cqes as usize + p.cq_entries as usize * mem::size_of::<Cqe>();
+            let sqes_len = p.sq_entries as usize * mem::size_of::<Sqe>();
+            Ok::<_, io::Error>([
+                Mmap::new(fd, sq_len, IORING_OFF_SQ_RING)?,
+                Mmap::new(fd, cq_len, IORING_OFF_CQ_RING)?,
+                Mmap::new(fd, sqes_len, IORING_OFF_SQES)?,
+            ])
+        })();
+        let maps = match maps {
+            Ok(maps) => maps,
+            Err(err) => {
+                unsafe { libc::close(fd) };
+                return Err(err);
+            }
+        };
+
+        let [sq_map, cq_map, sqes_map] = &maps;
+        let sq = Submission {
+            head: sq_map.at(p.sq_off.head),
+            tail: sq_map.at(p.sq_off.tail),
+            mask: unsafe { *sq_map.at::<u32>(p.sq_off.ring_mask) },
+            entries: p.sq_entries,
+            array: sq_map.at(p.sq_off.array),
+            sqes: sqes_map.at(0),
+        };
+        let cq = Completion {
+            head: cq_map.at(p.cq_off.head),
+            tail: cq_map.at(p.cq_off.tail),
+            mask: unsafe { *cq_map.at::<u32>(p.cq_off.ring_mask) },
+            cqes: cq_map.at(p.cq_off.cqes),
+        };
+
+        Ok(Ring {
+            fd,
+            sq: Mutex::new(sq),
+            cq,
+            _maps: maps,
+        })
+    }
+
+    /// Calls `io_uring_enter`, retrying if interrupted.
+    fn enter(&self, to_submit: u32, min_complete: u32, flags: u32) -> io::Result<()> {
+        loop {
+            let res = unsafe {
+                libc::syscall(
+                    libc::SYS_io_uring_enter,
+                    self.fd,
+                    to_submit,
+                    min_complete,
+                    flags,
+                    ptr::null::<libc::sigset_t>(),
+                    0,
+                )
+            };
+            if res >= 0 {
+                return Ok(());
+            }
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::Interrupted {
+                return Err(err);
+            }
+        }
+    }
+
+    /// Pushes an entry into the submission queue and submits it.
+    fn submit(&self, sqe: Sqe) -> io::Result<()> {
+        self.submit_with(sqe, |pending| self.enter(pending, 0, 0))
+    }
+
+    /// Pushes an entry into the submission queue and submits it with `enter`.
+    ///
+    /// If `enter` fails before the kernel has taken the entry, the entry is taken back out of the
+    /// queue, so that the caller can free what it points to.
+    fn submit_with(&self, sqe: Sqe, enter: impl FnOnce(u32) -> io::Result<()>) -> io::Result<()> {
+        let sq = self.sq.lock().unwrap();
+        unsafe {
+            let head = (*sq.head).load(Ordering::Acquire);
+            let tail = (*sq.tail).load(Ordering::Relaxed);
+            if tail.wrapping_sub(head) >= sq.entries {
+                return Err(io::Error::other("the io_uring submission queue is full"));
+            }
+
+            let index = tail & sq.mask;
+            ptr::write(sq.sqes.add(index as usize), sqe);
+            *sq.array.add(index as usize) = index;
+            (*sq.tail).store(tail.wrapping_add(1), Ordering::Release);
+
+            // Submit all pending entries, including ones the kernel couldn't take earlier.
+            let pending = tail.wrapping_add(1).wrapping_sub(head);
+            match enter(pending) {
+                Ok(()) => Ok(()),
+                Err(err)
+                    if err.raw_os_error() == Some(libc::EAGAIN)
+                        || err.raw_os_error() == Some(libc::EBUSY) =>
+                {
+                    Ok(())
+                }
+                Err(err) => {
+                    // The kernel takes entries in order, so the new entry is still in the queue
+                    // unless it took all of them.
+                    let taken = (*sq.head).load(Ordering::Acquire).wrapping_sub(head);
+                    if taken == pending {
+                        return Ok(());
+                    }
+                    (*sq.tail).store(tail, Ordering::Release);
+                    Err(err)
+                }
+            }
+        }
+    }
+
+    /// Waits for completions and completes their operations.
+    fn main_loop(&self) -> io::Result<()> {
+        loop {
+            self.enter(0, 1, IORING_ENTER_GETEVENTS)?;
+
+            #[cfg(feature = "unstable")]
+            COUNTERS.reactor_wakeups.fetch_add(1, Ordering::Relaxed);
+
+            let cq = &self.cq;
+            unsafe {
+                let mut head = (*cq.head).load(Ordering::Relaxed);
+                let tail = (*cq.tail).load(Ordering::Acquire);
+
+                #[cfg(feature = "tracing")]
+                tracing::trace!(events = tail.wrapping_sub(head), "reactor wakeup");
+
+                while head != tail {
+                    let cqe = &*cq.cqes.add((head & cq.mask) as usize);
+                    if cqe.user_data != 0 {
+                        let op = Arc::from_raw(cqe.user_data as *const Op);
+                        op.complete(cqe.res);
+                    }
+                    head = head.wrapping_add(1);
+                }
+                (*cq.head).store(head, Ordering::Release);
+            }
+        }
+    }
+}
+
+impl Drop for Ring {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+/// The global ring, or `None` if io_uring isn't available.
+static RING: Lazy<Option<Ring>> = Lazy::new(|| {
+    let ring = Ring::new().ok()?;
+
+    std::thread::Builder::new()
+        .name("async-std/uring".to_string())
+        .spawn(|| {
+            abort_on_panic(|| {
+                let ring = RING.as_ref().unwrap();
+                ring.main_loop().expect("async io_uring thread has panicked");
+            })
+        })
+        .ok()?;
+
+    Some(ring)
+});
+
+/// Returns `true` if io_uring is available.
+///
+/// This sets up the ring on first call.
+pub(crate) fn is_supported() -> bool {
+    RING.is_some()
+}
+
+/// An in-flight operation.
+struct Op {
+    /// Set for accept operations, whose result is a file descriptor.
+    accept: bool,
+    state: Mutex<OpState>,
+}
+
+struct OpState {
+    /// The result of the operation once it has completed.
+    result: Option<i32>,
+
+    /// Tasks waiting for the operation to complete.
+    wakers: Vec<Waker>,
+
+    /// The buffer the kernel reads from or writes into.
+    buf: Vec<u8>,
+}
+
+impl Op {
+    fn new(accept: bool, buf: Vec<u8>) -> Arc<Op> {
+        Arc::new(Op {
+            accept,
+            state: Mutex::new(OpState {
+                result: None,
+                wakers: Vec::new(),
+                buf,
+            }),
+        })
+    }
+
+    /// Submits the operation with the given entry.
+    fn submit(self: &Arc<Op>, sqe: Sqe) -> io::Result<()> {
+        let ring = RING.as_ref().expect("io_uring is not available");
+        self.submit_with(sqe, |sqe| ring.submit(sqe))
+    }
+
+    /// Submits the operation with the given entry through `submit`.
+    ///
+    /// `submit` must only fail if the entry didn't make it into the ring.
+    fn submit_with(
+        self: &Arc<Op>,
+        mut sqe: Sqe,
+        submit: impl FnOnce(Sqe) -> io::Result<()>,
+    ) -> io::Result<()> {
+        // The ring keeps a reference to the operation until it completes.
+        let raw = Arc::into_raw(self.clone());
+        sqe.user_data = raw as u64;
+        if let Err(err) = submit(sqe) {
+            drop(unsafe { Arc::from_raw(raw) });
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    /// Asks the kernel to cancel the operation if it hasn't completed yet.
+    fn cancel(self: &Arc<Op>) {
+        if let Some(ring) = RING.as_ref() {
+            let _ = ring.submit(Sqe {
+                opcode: IORING_OP_ASYNC_CANCEL,
+                addr: Arc::as_ptr(self) as u64,
+                ..Sqe::default()
+            });
+        }
+    }
+
+    /// Stores the result and wakes up the waiting tasks.
+    fn complete(&self, res: i32) {
+        let wakers = {
+            let mut state = self.state.lock().unwrap();
+            state.result = Some(res);
+            mem::take(&mut state.wakers)
+        };
+        for w in wakers {
+            w.wake();
+        }
+    }
+
+    /// Takes the result of the operation, or registers the task for wakeup.
+    fn poll(&self, cx: &mut Context<'_>) -> Poll<(i32, Vec<u8>)> {
+        let mut state = self.state.lock().unwrap();
+        match state.result.take() {
+            Some(res) => Poll::Ready((res, mem::take(&mut state.buf))),
+            None => {
+                if state.wakers.iter().all(|w| !w.will_wake(cx.waker())) {
+                    state.wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for Op {
+    fn drop(&mut self) {
+        // Close connections accepted after their listener stopped waiting for them.
+        if self.accept {
+            if let Some(fd) = self.state.get_mut().unwrap().result {
+                if fd >= 0 {
+                    unsafe { libc::close(fd) };
+                }
+            }
+        }
+    }
+}
+
+/// Converts the result of an operation into an `io::Result`.
+fn to_result(res: i32) -> io::Result<usize> {
+    if res < 0 {
+        Err(io::Error::from_raw_os_error(-res))
+    } else {
+        Ok(res as usize)
+    }
+}
+
+/// Starts receiving into a fresh buffer of `len` bytes.
+fn recv(fd: RawFd, len: usize) -> io::Result<Arc<Op>> {
+    let mut buf = vec![0; len];
+    let sqe = Sqe {
+        opcode: IORING_OP_RECV,
+        fd,
+        addr: buf.as_mut_ptr() as u64,
+        len: len as u32,
+        ..Sqe::default()
+    };
+    let op = Op::new(false, buf);
+    op.submit(sqe)?;
+    Ok(op)
+}
+
+/// Starts sending `buf[pos..]`.
+fn send(fd: RawFd, buf: Vec<u8>, pos: usize) -> io::Result<Arc<Op>> {
+    let sqe = Sqe {
+        opcode: IORING_OP_SEND,
+        fd,
+        addr: buf[pos..].as_ptr() as u64,
+        len: (buf.len() - pos) as u32,
+        op_flags: libc::MSG_NOSIGNAL as u32,
+        ..Sqe::default()
+    };
+    let op = Op::new(false, buf);
+    op.submit(sqe)?;
+    Ok(op)
+}
+
+/// The io_uring state of a connected socket.
+pub(crate) struct Stream {
+    fd: RawFd,
+    read: Mutex<ReadState>,
+    write: Mutex<WriteState>,
+}
+
+struct ReadState {
+    /// The in-flight receive operation.
+    op: Option<Arc<Op>>,
+
+    /// Received bytes that haven't been read yet, starting at `pos`.
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+struct WriteState {
+    /// The in-flight send operation.
+    op: Option<Arc<Op>>,
+
+    /// How many bytes of the operation's buffer have been sent by earlier operations.
+    pos: usize,
+}
+
+impl Stream {
+    pub(crate) fn new(fd: RawFd) -> Stream {
+        Stream {
+            fd,
+            read: Mutex::new(ReadState {
+                op: None,
+                buf: Vec::new(),
+                pos: 0,
+            }),
+            write: Mutex::new(WriteState { op: None, pos: 0 }),
+        }
+    }
+
+    /// Reads received bytes into `out`, starting a receive operation if there are none.
+    pub(crate) fn poll_read(&self, cx: &mut Context<'_>, out: &mut [u8]) -> Poll<io::Result<usize>> {
+        futures_core::ready!(budget::poll_proceed(cx));
+
+        let mut state = self.read.lock().unwrap();
+        loop {
+            if state.pos < state.buf.len() {
+                let n = out.len().min(state.buf.len() - state.pos);
+                out[..n].copy_from_slice(&state.buf[state.pos..state.pos + n]);
+                state.pos += n;
+                return Poll::Ready(Ok(n));
+            }
+
+            match state.op.clone() {
+                None => {
+                    if out.is_empty() {
+                        return Poll::Ready(Ok(0));
+                    }
+                    state.op = Some(recv(self.fd, out.len().min(MAX_BUF))?);
+                }
+                Some(op) => {
+                    let (res, mut buf) = futures_core::ready!(op.poll(cx));
+                    state.op = None;
+                    let n = to_result(res)?;
+                    if n == 0 {
+                        return Poll::Ready(Ok(0));
+                    }
+                    buf.truncate(n);
+                    state.buf = buf;
+                    state.pos = 0;
+                }
+            }
+        }
+    }
+
+    /// Starts sending a copy of `data` once the previous send operation has completed.
+    ///
+    /// The bytes are reported as written once they have been handed to the kernel. Errors are
+    /// reported by the next write or flush.
+    pub(crate) fn poll_write(&self, cx: &mut Context<'_>, data: &[u8]) -> Poll<io::Result<usize>> {
+        futures_core::ready!(budget::poll_proceed(cx));
+
+        let mut state = self.write.lock().unwrap();
+        futures_core::ready!(self.poll_sent(cx, &mut state))?;
+
+        if data.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let len = data.len().min(MAX_BUF);
+        state.op = Some(send(self.fd, data[..len].to_vec(), 0)?);
+        state.pos = 0;
+        Poll::Ready(Ok(len))
+    }
+
+    /// Waits until all written bytes have been sent.
+    pub(crate) fn poll_flush(&self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut state = self.write.lock().unwrap();
+        self.poll_sent(cx, &mut state)
+    }
+
+    /// Waits for the in-flight send operation, resubmitting the rest after short sends.
+    fn poll_sent(&self, cx: &mut Context<'_>, state: &mut WriteState) -> Poll<io::Result<()>> {
+        while let Some(op) = state.op.clone() {
+            let (res, buf) = futures_core::ready!(op.poll(cx));
+            state.op = None;
+
+            let n = to_result(res)?;
+            if n == 0 {
+                return Poll::Ready(Err(io::ErrorKind::WriteZero.into()));
+            }
+            state.pos += n;
+            if state.pos < buf.len() {
+                state.op = Some(send(self.fd, buf, state.pos)?);
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl Drop for Stream {
+    fn drop(&mut self) {
+        // In-flight operations keep the socket open, so they must be cancelled.
+        if let Some(op) = &self.read.get_mut().unwrap().op {
+            op.cancel();
+        }
+        if let Some(op) = &self.write.get_mut().unwrap().op {
+            op.cancel();
+        }
+    }
+}
+
+impl fmt::Debug for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Stream { .. }")
+    }
+}
+
+/// The io_uring state of a listening socket.
+pub(crate) struct Listener {
+    fd: RawFd,
+
+    /// The in-flight accept operation.
+    op: Mutex<Option<Arc<Op>>>,
+}
+
+impl Listener {
+    pub(crate) fn new(fd: RawFd) -> Listener {
+        Listener {
+            fd,
+            op: Mutex::new(None),
+        }
+    }
+
+    /// Accepts a connection, returning its non-blocking socket.
+    pub(crate) fn poll_accept(&self, cx: &mut Context<'_>) -> Poll<io::Result<RawFd>> {
+        futures_core::ready!(budget::poll_proceed(cx));
+
+        let mut op = self.op.lock().unwrap();
+        if op.is_none() {
+            let accept = Op::new(true, Vec::new());
+            accept.submit(Sqe {
+                opcode: IORING_OP_ACCEPT,
+                fd: self.fd,
+                op_flags: (libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC) as u32,
+                ..Sqe::default()
+            })?;
+            *op = Some(accept);
+        }
+
+        let (res, _) = futures_core::ready!(op.as_ref().unwrap().poll(cx));
+        *op = None;
+        Poll::Ready(to_result(res).map(|fd| fd as RawFd))
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        if let Some(op) = self.op.get_mut().unwrap() {
+            op.cancel();
+        }
+    }
+}
+
+impl fmt::Debug for Listener {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Listener { .. }")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_submit_releases_op_once() {
+        // io_uring may not be available, for example in containers.
+        let ring = match Ring::new() {
+            Ok(ring) => ring,
+            Err(_) => return,
+        };
+
+        let op = Op::new(false, vec![0; 16]);
+        let res = op.submit_with(
+            Sqe {
+                opcode: IORING_OP_RECV,
+                fd: -1,
+                ..Sqe::default()
+            },
+            |sqe| ring.submit_with(sqe, |_| Err(io::Error::from_raw_os_error(libc::EINVAL))),
+        );
+        assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EINVAL));
+
+        // Only the caller's reference is left, and the entry pointing at the operation is gone.
+        assert_eq!(Arc::strong_count(&op), 1);
+        let sq = ring.sq.lock().unwrap();
+        unsafe {
+            assert_eq!(
+                (*sq.head).load(Ordering::Acquire),
+                (*sq.tail).load(Ordering::Acquire)
+            );
+        }
+    }
+}
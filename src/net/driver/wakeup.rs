@@ -0,0 +1,212 @@
+//! Wakeups of the reactor thread.
+//!
+//! On Linux, an eventfd wakes the reactor from other threads and a timerfd wakes it at the next
+//! timer deadline, so the poller is never called with a timeout. Elsewhere, a mio registration
+//! wakes the reactor and the deadline is passed to the poller as a timeout.
+
+#[cfg(target_os = "linux")]
+use std::os::unix::io::RawFd;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(target_os = "linux")]
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::io;
+
+/// Wakes the reactor thread from other threads.
+///
+/// Notifications are coalesced: once the reactor has been notified, further notifications don't
+/// make a system call until the reactor has handled the first one.
+pub(super) struct Notifier {
+    /// The eventfd the reactor polls.
+    #[cfg(target_os = "linux")]
+    fd: RawFd,
+
+    /// A dummy I/O handle the reactor polls.
+    #[cfg(not(target_os = "linux"))]
+    registration: (mio::Registration, mio::SetReadiness),
+
+    /// Set when the reactor has been notified and hasn't handled the notification yet.
+    notified: AtomicBool,
+}
+
+impl Notifier {
+    /// Creates a notifier.
+    pub(super) fn new() -> io::Result<Notifier> {
+        Ok(Notifier {
+            #[cfg(target_os = "linux")]
+            fd: cvt(unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) })?,
+            #[cfg(not(target_os = "linux"))]
+            registration: mio::Registration::new2(),
+            notified: AtomicBool::new(false),
+        })
+    }
+
+    /// Registers the notifier in a poller.
+    pub(super) fn register(&self, poller: &mio::Poll, token: mio::Token) -> io::Result<()> {
+        #[cfg(target_os = "linux")]
+        let source = mio::unix::EventedFd(&self.fd);
+        #[cfg(not(target_os = "linux"))]
+        let source = &self.registration.0;
+
+        poller.register(&source, token, mio::Ready::readable(), mio::PollOpt::edge())
+    }
+
+    /// Wakes the reactor, unless it has a notification pending already.
+    pub(super) fn notify(&self) -> io::Result<()> {
+        if self.notified.swap(true, Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            let one = 1u64;
+            let buf = &one as *const u64 as *const libc::c_void;
+            match cvt(unsafe { libc::write(self.fd, buf, 8) }) {
+                // The counter is about to overflow, so the reactor is notified anyway.
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(()),
+                res => res.map(drop),
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        self.registration.1.set_readiness(mio::Ready::readable())
+    }
+
+    /// Marks the pending notification as handled.
+    ///
+    /// This must be called before the reactor checks what it was notified about, so that it
+    /// doesn't miss notifications arriving in the meantime.
+    pub(super) fn clear(&self) -> io::Result<()> {
+        self.notified.store(false, Ordering::SeqCst);
+
+        #[cfg(target_os = "linux")]
+        return drain(self.fd);
+
+        #[cfg(not(target_os = "linux"))]
+        self.registration.1.set_readiness(mio::Ready::empty())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for Notifier {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+/// Wakes the reactor thread once a deadline has passed.
+pub(super) struct Alarm {
+    /// The timerfd the reactor polls.
+    #[cfg(target_os = "linux")]
+    fd: RawFd,
+
+    /// The deadline the timerfd is set to.
+    #[cfg(target_os = "linux")]
+    deadline: Mutex<Option<Instant>>,
+}
+
+impl Alarm {
+    /// Creates an alarm that isn't set.
+    pub(super) fn new() -> io::Result<Alarm> {
+        Ok(Alarm {
+            #[cfg(target_os = "linux")]
+            fd: cvt(unsafe {
+                libc::timerfd_create(
+                    libc::CLOCK_MONOTONIC,
+                    libc::TFD_CLOEXEC | libc::TFD_NONBLOCK,
+                )
+            })?,
+            #[cfg(target_os = "linux")]
+            deadline: Mutex::new(None),
+        })
+    }
+
+    /// Registers the alarm in a poller.
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+    pub(super) fn register(&self, poller: &mio::Poll, token: mio::Token) -> io::Result<()> {
+        #[cfg(target_os = "linux")]
+        return poller.register(
+            &mio::unix::EventedFd(&self.fd),
+            token,
+            mio::Ready::readable(),
+            mio::PollOpt::edge(),
+        );
+
+        #[cfg(not(target_os = "linux"))]
+        Ok(())
+    }
+
+    /// Sets the alarm to go off at `deadline`, or unsets it.
+    ///
+    /// Returns the timeout the poller must be called with.
+    pub(super) fn set(&self, deadline: Option<Instant>) -> io::Result<Option<Duration>> {
+        #[cfg(target_os = "linux")]
+        {
+            // Skip the system call if the deadline hasn't changed.
+            let mut current = self.deadline.lock().unwrap();
+            if *current == deadline {
+                return Ok(None);
+            }
+            *current = deadline;
+
+            let timeout = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+            let mut spec: libc::itimerspec = unsafe { std::mem::zeroed() };
+            if let Some(dur) = timeout {
+                // A zero value unsets the timerfd.
+                let dur = dur.max(Duration::from_nanos(1));
+                spec.it_value.tv_sec = dur.as_secs() as libc::time_t;
+                spec.it_value.tv_nsec = dur.subsec_nanos() as libc::c_long;
+            }
+            cvt(unsafe { libc::timerfd_settime(self.fd, 0, &spec, std::ptr::null_mut()) })?;
+            Ok(None)
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        Ok(deadline.map(|d| d.saturating_duration_since(Instant::now())))
+    }
+
+    /// Marks the alarm as handled after it went off.
+    pub(super) fn clear(&self) -> io::Result<()> {
+        #[cfg(target_os = "linux")]
+        {
+            *self.deadline.lock().unwrap() = None;
+            drain(self.fd)
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for Alarm {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+/// Resets the counter of an eventfd or timerfd.
+#[cfg(target_os = "linux")]
+fn drain(fd: RawFd) -> io::Result<()> {
+    let mut count = 0u64;
+    let buf = &mut count as *mut u64 as *mut libc::c_void;
+    match cvt(unsafe { libc::read(fd, buf, 8) }) {
+        Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(()),
+        res => res.map(drop),
+    }
+}
+
+/// Turns the `-1` returned by a failed system call into the last OS error.
+#[cfg(target_os = "linux")]
+fn cvt<T: Default + PartialOrd>(res: T) -> io::Result<T> {
+    if res < T::default() {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(res)
+    }
+}
@@ -0,0 +1,170 @@
+use std::pin::Pin;
+use std::time::Duration;
+
+use crate::io;
+use crate::net::{SocketAddr, ToSocketAddrs};
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// Resolves a host name and port to socket addresses.
+///
+/// This takes anything [`TcpStream::connect`] does, such as `"example.com:443"` or
+/// `("example.com", 443)`, and resolves it with the default [`ResolverOptions`]. Applications
+/// that want to decide themselves in which order to try addresses, or how many to try at once,
+/// can resolve them up front with this function.
+///
+/// [`TcpStream::connect`]: struct.TcpStream.html#method.connect
+/// [`ResolverOptions`]: struct.ResolverOptions.html
+///
+/// # Errors
+///
+/// An error is returned if the address can't be resolved, or resolves to no addresses.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::net::lookup_host;
+/// use async_std::prelude::*;
+///
+/// let mut addrs = lookup_host("example.com:443").await?;
+/// while let Some(addr) = addrs.next().await {
+///     println!("{}", addr);
+/// }
+/// #
+/// # Ok(()) }) }
+/// ```
+pub async fn lookup_host<A: ToSocketAddrs>(addr: A) -> io::Result<LookupHost> {
+    ResolverOptions::new().lookup_host(addr).await
+}
+
+/// Which addresses to return, and in which order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AddrFamily {
+    /// All addresses, in the order the system resolver returns them.
+    #[default]
+    Any,
+
+    /// Only IPv4 addresses.
+    V4,
+
+    /// Only IPv6 addresses.
+    V6,
+
+    /// All addresses, IPv4 ones first.
+    PreferV4,
+
+    /// All addresses, IPv6 ones first.
+    PreferV6,
+}
+
+/// Options for resolving host names.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::net::{AddrFamily, ResolverOptions};
+///
+/// let addrs = ResolverOptions::new()
+///     .family(AddrFamily::PreferV6)
+///     .timeout(Duration::from_secs(5))
+///     .lookup_host("example.com:443")
+///     .await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ResolverOptions {
+    family: AddrFamily,
+    timeout: Option<Duration>,
+}
+
+impl ResolverOptions {
+    /// Creates options returning all addresses, without a timeout.
+    pub fn new() -> ResolverOptions {
+        ResolverOptions::default()
+    }
+
+    /// Sets which addresses to return, and in which order.
+    ///
+    /// The default is [`AddrFamily::Any`].
+    ///
+    /// [`AddrFamily::Any`]: enum.AddrFamily.html#variant.Any
+    pub fn family(mut self, family: AddrFamily) -> ResolverOptions {
+        self.family = family;
+        self
+    }
+
+    /// Sets how long to wait for the system resolver.
+    ///
+    /// If it takes longer, an error of kind [`TimedOut`] is returned. The resolver keeps running
+    /// on the blocking thread pool until it finishes, as it can't be interrupted.
+    ///
+    /// [`TimedOut`]: https://doc.rust-lang.org/std/io/enum.ErrorKind.html#variant.TimedOut
+    pub fn timeout(mut self, timeout: Duration) -> ResolverOptions {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Resolves a host name and port to socket addresses with these options.
+    ///
+    /// See [`lookup_host`] for details.
+    ///
+    /// [`lookup_host`]: fn.lookup_host.html
+    pub async fn lookup_host<A: ToSocketAddrs>(&self, addr: A) -> io::Result<LookupHost> {
+        let addrs = match self.timeout {
+            Some(timeout) => io::timeout(timeout, addr.to_socket_addrs()).await?,
+            None => addr.to_socket_addrs().await?,
+        };
+
+        let mut addrs: Vec<SocketAddr> = match self.family {
+            AddrFamily::V4 => addrs.filter(|a| a.is_ipv4()).collect(),
+            AddrFamily::V6 => addrs.filter(|a| a.is_ipv6()).collect(),
+            _ => addrs.collect(),
+        };
+        match self.family {
+            AddrFamily::PreferV4 => addrs.sort_by_key(|a| a.is_ipv6()),
+            AddrFamily::PreferV6 => addrs.sort_by_key(|a| a.is_ipv4()),
+            _ => {}
+        }
+
+        if addrs.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "could not resolve to any addresses",
+            ));
+        }
+        Ok(LookupHost {
+            addrs: addrs.into_iter(),
+        })
+    }
+}
+
+/// A stream of resolved socket addresses.
+///
+/// This stream is created by [`lookup_host`] and [`ResolverOptions::lookup_host`]. All addresses
+/// are resolved by the time it is returned.
+///
+/// [`lookup_host`]: fn.lookup_host.html
+/// [`ResolverOptions::lookup_host`]: struct.ResolverOptions.html#method.lookup_host
+#[derive(Clone, Debug)]
+pub struct LookupHost {
+    addrs: std::vec::IntoIter<SocketAddr>,
+}
+
+impl Stream for LookupHost {
+    type Item = SocketAddr;
+
+    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<SocketAddr>> {
+        Poll::Ready(self.addrs.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.addrs.size_hint()
+    }
+}
@@ -8,7 +8,12 @@
 //! # Organization
 //!
 //! * [`TcpListener`] and [`TcpStream`] provide functionality for communication over TCP
-//! * [`UdpSocket`] provides functionality for communication over UDP
+//! * [`UdpSocket`] provides functionality for communication over UDP, and [`UdpFramed`] turns
+//!   it into a stream and sink of messages; TCP connections are framed with the [`codec`]
+//!   adapters
+//! * [`Pool`] keeps a pool of reusable connections of any kind
+//! * The [`raw`] module provides raw IP and packet sockets on Linux, behind the `raw-sockets`
+//!   feature
 //! * [`IpAddr`] represents IP addresses of either IPv4 or IPv6; [`Ipv4Addr`] and
 //!   [`Ipv6Addr`] are respectively IPv4 and IPv6 addresses
 //! * [`SocketAddr`] represents socket addresses of either IPv4 or IPv6; [`SocketAddrV4`]
@@ -27,6 +32,10 @@
 //! [`TcpStream`]: struct.TcpStream.html
 //! [`ToSocketAddrs`]: trait.ToSocketAddrs.html
 //! [`UdpSocket`]: struct.UdpSocket.html
+//! [`UdpFramed`]: struct.UdpFramed.html
+//! [`codec`]: ../io/codec/index.html
+//! [`raw`]: raw/index.html
+//! [`Pool`]: struct.Pool.html
 //!
 //! # Platform-specific extensions
 //!
@@ -63,9 +72,39 @@ pub use std::net::{SocketAddr, SocketAddrV4, SocketAddrV6};
 
 pub use addr::ToSocketAddrs;
 pub use tcp::{Incoming, TcpListener, TcpStream};
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub use tcp::{TcpReadHalf, TcpWriteHalf};
+#[cfg(all(feature = "unstable", any(target_os = "linux", target_os = "android")))]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub use tcp::TcpInfo;
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub use lookup::{lookup_host, AddrFamily, LookupHost, ResolverOptions};
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub use pool::{Pool, PoolBuilder, Pooled};
 pub use udp::UdpSocket;
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub use udp::UdpFramed;
+
+#[cfg(all(
+    feature = "raw-sockets",
+    any(target_os = "linux", target_os = "android")
+))]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "raw-sockets")))]
+pub mod raw;
+
+#[cfg(feature = "tls")]
+#[cfg_attr(feature = "docs", doc(cfg(feature = "tls")))]
+pub mod tls;
 
 mod addr;
 pub(crate) mod driver;
+#[cfg(feature = "unstable")]
+mod lookup;
+#[cfg(feature = "unstable")]
+mod pool;
 mod tcp;
 mod udp;
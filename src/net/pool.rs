@@ -0,0 +1,415 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::io;
+use crate::sync::oneshot;
+
+type Connect<T> =
+    Box<dyn Fn() -> Pin<Box<dyn Future<Output = io::Result<T>> + Send>> + Send + Sync>;
+type HealthCheck<T> =
+    Box<dyn Fn(T) -> Pin<Box<dyn Future<Output = Option<T>> + Send>> + Send + Sync>;
+
+/// A pool of reusable connections.
+///
+/// The pool opens connections with the function it is built with, up to a maximum number of
+/// connections at once. Connections taken out of the pool with [`get`] are returned to it when
+/// the [`Pooled`] guard is dropped, and handed to the next task waiting for one. Tasks waiting
+/// for a connection are served in the order they started waiting.
+///
+/// Connections that have been idle for longer than the idle timeout are closed rather than
+/// reused, and a health check can be run on idle connections before they are handed out.
+///
+/// The pool can be cloned cheaply, with clones sharing the same connections.
+///
+/// [`get`]: #method.get
+/// [`Pooled`]: struct.Pooled.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::net::{Pool, TcpStream};
+/// use async_std::prelude::*;
+///
+/// let pool = Pool::builder(|| TcpStream::connect("127.0.0.1:6379"))
+///     .max_size(16)
+///     .idle_timeout(Duration::from_secs(60))
+///     .build();
+///
+/// let mut conn = pool.get().await?;
+/// conn.write_all(b"PING\r\n").await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+pub struct Pool<T> {
+    inner: Arc<Inner<T>>,
+}
+
+/// A builder for a [`Pool`].
+///
+/// [`Pool`]: struct.Pool.html
+pub struct PoolBuilder<T> {
+    connect: Connect<T>,
+    health_check: Option<HealthCheck<T>>,
+    max_size: usize,
+    idle_timeout: Option<Duration>,
+}
+
+/// A connection taken out of a [`Pool`].
+///
+/// The connection is returned to the pool when the guard is dropped.
+///
+/// [`Pool`]: struct.Pool.html
+pub struct Pooled<T> {
+    /// The connection, taken out when it leaves the guard.
+    conn: Option<T>,
+    inner: Arc<Inner<T>>,
+}
+
+struct Inner<T> {
+    connect: Connect<T>,
+    health_check: Option<HealthCheck<T>>,
+    max_size: usize,
+    idle_timeout: Option<Duration>,
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    /// The idle connections and the times they were returned, most recently returned last.
+    idle: VecDeque<(T, Instant)>,
+
+    /// The number of connections that are open or being opened.
+    open: usize,
+
+    /// The tasks waiting for a connection, in the order they started waiting.
+    waiters: VecDeque<oneshot::Sender<Slot<T>>>,
+}
+
+/// Permission to use a place in the pool, either with a connection or by opening one.
+enum Slot<T> {
+    Conn(Pooled<T>),
+    Empty(EmptySlot<T>),
+}
+
+/// A place in the pool without a connection, given to the next waiter or freed when dropped.
+struct EmptySlot<T> {
+    /// The pool, taken out once the slot has been used for a connection.
+    inner: Option<Arc<Inner<T>>>,
+}
+
+impl<T: Send + 'static> Pool<T> {
+    /// Starts building a pool opening connections with the given function.
+    ///
+    /// By default, the pool holds at most 10 connections and keeps idle connections open
+    /// indefinitely.
+    pub fn builder<F, Fut>(connect: F) -> PoolBuilder<T>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = io::Result<T>> + Send + 'static,
+    {
+        PoolBuilder {
+            connect: Box::new(move || Box::pin(connect())),
+            health_check: None,
+            max_size: 10,
+            idle_timeout: None,
+        }
+    }
+
+    /// Takes a connection out of the pool.
+    ///
+    /// An idle connection is reused if there is one, and otherwise a new connection is opened
+    /// unless the pool is full, in which case this waits for a connection to be returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of opening a new connection.
+    pub async fn get(&self) -> io::Result<Pooled<T>> {
+        let slot = {
+            let mut state = self.inner.state.lock().unwrap();
+
+            // Idle connections are only taken when nobody is waiting, so that waiters are served
+            // in order.
+            let idle = if state.waiters.is_empty() {
+                self.inner.take_idle(&mut state)
+            } else {
+                None
+            };
+            match idle {
+                Some(conn) => Ok(Slot::Conn(self.inner.wrap(conn))),
+                None if state.open < self.inner.max_size => {
+                    state.open += 1;
+                    Ok(Slot::Empty(EmptySlot::new(&self.inner)))
+                }
+                None => {
+                    let (s, r) = oneshot::channel();
+                    state.waiters.push_back(s);
+                    Err(r)
+                }
+            }
+        };
+
+        let slot = match slot {
+            Ok(slot) => slot,
+            Err(r) => r.await.expect("the pool dropped a waiter"),
+        };
+
+        let slot = match slot {
+            Slot::Conn(conn) => match &self.inner.health_check {
+                None => return Ok(conn),
+                Some(check) => {
+                    // The place of the connection is kept while it is being checked.
+                    let slot = EmptySlot::new(&self.inner);
+                    match check(conn.detach_conn()).await {
+                        Some(conn) => return Ok(slot.fill(conn)),
+                        None => slot,
+                    }
+                }
+            },
+            Slot::Empty(slot) => slot,
+        };
+
+        // Open a new connection. If this fails, the slot is given to the next waiter.
+        let conn = (self.inner.connect)().await?;
+        Ok(slot.fill(conn))
+    }
+
+    /// Returns the maximum number of connections in the pool.
+    pub fn max_size(&self) -> usize {
+        self.inner.max_size
+    }
+
+    /// Returns the number of connections that are open or being opened.
+    pub fn size(&self) -> usize {
+        self.inner.state.lock().unwrap().open
+    }
+
+    /// Returns the number of idle connections.
+    pub fn idle(&self) -> usize {
+        self.inner.state.lock().unwrap().idle.len()
+    }
+}
+
+impl<T> Clone for Pool<T> {
+    fn clone(&self) -> Pool<T> {
+        Pool {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Pool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.inner.state.lock().unwrap();
+        f.debug_struct("Pool")
+            .field("max_size", &self.inner.max_size)
+            .field("open", &state.open)
+            .field("idle", &state.idle.len())
+            .field("waiters", &state.waiters.len())
+            .finish()
+    }
+}
+
+impl<T: Send + 'static> PoolBuilder<T> {
+    /// Sets the maximum number of connections in the pool.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `max_size` is zero.
+    pub fn max_size(mut self, max_size: usize) -> PoolBuilder<T> {
+        assert!(
+            max_size > 0,
+            "a pool needs room for at least one connection"
+        );
+        self.max_size = max_size;
+        self
+    }
+
+    /// Closes connections that have been idle for longer than `timeout` instead of reusing them.
+    pub fn idle_timeout(mut self, timeout: Duration) -> PoolBuilder<T> {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Runs a check on idle connections before they are handed out.
+    ///
+    /// The check takes the connection and gives it back if it is still usable. Otherwise, the
+    /// connection is closed and a new one is opened in its place.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::net::{Pool, TcpStream};
+    ///
+    /// let pool = Pool::builder(|| TcpStream::connect("127.0.0.1:6379"))
+    ///     .health_check(|conn: TcpStream| async move {
+    ///         // A connection the peer has closed isn't worth reusing.
+    ///         conn.peer_addr().ok().map(|_| conn)
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn health_check<F, Fut>(mut self, check: F) -> PoolBuilder<T>
+    where
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Option<T>> + Send + 'static,
+    {
+        self.health_check = Some(Box::new(move |conn| Box::pin(check(conn))));
+        self
+    }
+
+    /// Builds the pool.
+    ///
+    /// No connections are opened until they are needed.
+    pub fn build(self) -> Pool<T> {
+        Pool {
+            inner: Arc::new(Inner {
+                connect: self.connect,
+                health_check: self.health_check,
+                max_size: self.max_size,
+                idle_timeout: self.idle_timeout,
+                state: Mutex::new(State {
+                    idle: VecDeque::new(),
+                    open: 0,
+                    waiters: VecDeque::new(),
+                }),
+            }),
+        }
+    }
+}
+
+impl<T> fmt::Debug for PoolBuilder<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolBuilder")
+            .field("max_size", &self.max_size)
+            .field("idle_timeout", &self.idle_timeout)
+            .finish()
+    }
+}
+
+impl<T> Inner<T> {
+    fn wrap(self: &Arc<Self>, conn: T) -> Pooled<T> {
+        Pooled {
+            conn: Some(conn),
+            inner: self.clone(),
+        }
+    }
+
+    /// Takes the most recently returned idle connection, closing the ones that have expired.
+    fn take_idle(&self, state: &mut State<T>) -> Option<T> {
+        if let Some(timeout) = self.idle_timeout {
+            let now = Instant::now();
+            let expired = state
+                .idle
+                .iter()
+                .take_while(|(_, since)| now - *since > timeout)
+                .count();
+            state.idle.drain(..expired);
+            state.open -= expired;
+        }
+        state.idle.pop_back().map(|(conn, _)| conn)
+    }
+
+    /// Gives a connection to the next waiter, or puts it back among the idle connections.
+    fn release(self: &Arc<Self>, conn: T) {
+        let mut state = self.state.lock().unwrap();
+        let mut conn = self.wrap(conn);
+        while let Some(waiter) = state.waiters.pop_front() {
+            match waiter.send(Slot::Conn(conn)) {
+                Ok(()) => return,
+                Err(Slot::Conn(c)) => conn = c,
+                Err(Slot::Empty(_)) => unreachable!(),
+            }
+        }
+        let conn = conn.detach_conn();
+        state.idle.push_back((conn, Instant::now()));
+    }
+
+    /// Gives a place without a connection to the next waiter, or frees it.
+    fn release_empty(self: &Arc<Self>) {
+        let mut state = self.state.lock().unwrap();
+        let mut slot = EmptySlot::new(self);
+        while let Some(waiter) = state.waiters.pop_front() {
+            match waiter.send(Slot::Empty(slot)) {
+                Ok(()) => return,
+                Err(Slot::Empty(s)) => slot = s,
+                Err(Slot::Conn(_)) => unreachable!(),
+            }
+        }
+        slot.inner = None;
+        state.open -= 1;
+    }
+}
+
+impl<T> Pooled<T> {
+    /// Takes the connection out of the pool for good.
+    ///
+    /// The place of the connection in the pool is freed, so that another connection can be
+    /// opened.
+    pub fn detach(this: Pooled<T>) -> T {
+        let inner = this.inner.clone();
+        let conn = this.detach_conn();
+        inner.release_empty();
+        conn
+    }
+
+    /// Takes the connection out of the guard without releasing its place in the pool.
+    fn detach_conn(mut self) -> T {
+        self.conn.take().unwrap()
+    }
+}
+
+impl<T> Deref for Pooled<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.conn.as_ref().unwrap()
+    }
+}
+
+impl<T> DerefMut for Pooled<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.conn.as_mut().unwrap()
+    }
+}
+
+impl<T> Drop for Pooled<T> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            self.inner.release(conn);
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Pooled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T> EmptySlot<T> {
+    fn new(inner: &Arc<Inner<T>>) -> EmptySlot<T> {
+        EmptySlot {
+            inner: Some(inner.clone()),
+        }
+    }
+
+    /// Puts a connection into the slot.
+    fn fill(mut self, conn: T) -> Pooled<T> {
+        self.inner.take().unwrap().wrap(conn)
+    }
+}
+
+impl<T> Drop for EmptySlot<T> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            inner.release_empty();
+        }
+    }
+}
@@ -0,0 +1,238 @@
+//! Raw IP and packet sockets.
+//!
+//! A [`RawSocket`] sends and receives IP packets of a single protocol, such as the ICMP echo
+//! requests and replies of a ping utility. A [`PacketSocket`] sends and receives whole link-layer
+//! frames on a network interface, which network scanners and packet capture tools build on.
+//!
+//! Both can have a classic BPF program attached with `attach_filter`, so that the kernel drops
+//! packets the program isn't interested in before they are copied to it.
+//!
+//! Opening raw sockets requires the `CAP_NET_RAW` capability. This module is only available on
+//! Linux and Android, behind the `raw-sockets` feature.
+//!
+//! [`RawSocket`]: struct.RawSocket.html
+//! [`PacketSocket`]: struct.PacketSocket.html
+//!
+//! # Examples
+//!
+//! Sending an ICMP echo request:
+//!
+//! ```no_run
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::net::raw::{Protocol, RawSocket};
+//!
+//! let socket = RawSocket::v4(Protocol::ICMP)?;
+//! let request = [8, 0, 0xf7, 0xfe, 0, 1, 0, 0];
+//! socket.send_to(&request, "127.0.0.1".parse().unwrap()).await?;
+//!
+//! // Received packets start with the IP header.
+//! let mut buf = vec![0u8; 1500];
+//! let (n, from) = socket.recv_from(&mut buf).await?;
+//! #
+//! # Ok(()) }) }
+//! ```
+
+use std::ffi::CString;
+use std::mem;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use mio::unix::EventedFd;
+use mio::{Evented, Poll, PollOpt, Ready, Token};
+
+use crate::io;
+
+pub use packet::{PacketAddr, PacketSocket};
+pub use socket::RawSocket;
+
+mod packet;
+mod socket;
+
+/// An IP protocol number.
+///
+/// [`RawSocket`]s only send and receive packets of the protocol they are created for.
+///
+/// [`RawSocket`]: struct.RawSocket.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Protocol(i32);
+
+impl Protocol {
+    /// The Internet Control Message Protocol.
+    pub const ICMP: Protocol = Protocol(libc::IPPROTO_ICMP);
+
+    /// The Internet Control Message Protocol for IPv6.
+    pub const ICMPV6: Protocol = Protocol(libc::IPPROTO_ICMPV6);
+
+    /// The Transmission Control Protocol.
+    pub const TCP: Protocol = Protocol(libc::IPPROTO_TCP);
+
+    /// The User Datagram Protocol.
+    pub const UDP: Protocol = Protocol(libc::IPPROTO_UDP);
+
+    /// Creates a protocol from its number.
+    pub const fn new(number: i32) -> Protocol {
+        Protocol(number)
+    }
+
+    /// Returns the number of the protocol.
+    pub const fn number(self) -> i32 {
+        self.0
+    }
+}
+
+/// An instruction of a classic BPF program.
+///
+/// A program is a slice of instructions, run on every packet the socket would receive. It
+/// returns the number of bytes of the packet to keep, and packets it returns zero for are
+/// dropped.
+///
+/// # Examples
+///
+/// A program accepting only ICMP echo replies on a raw IPv4 socket:
+///
+/// ```
+/// use async_std::net::raw::SockFilter;
+///
+/// let filter = [
+///     // Load the length of the IP header into X.
+///     SockFilter::new(0xb1, 0, 0, 0),
+///     // Load the ICMP type following it.
+///     SockFilter::new(0x50, 0, 0, 0),
+///     // Keep the packet if it is an echo reply, and drop it otherwise.
+///     SockFilter::new(0x15, 0, 1, 0),
+///     SockFilter::new(0x06, 0, 0, 0xffff),
+///     SockFilter::new(0x06, 0, 0, 0),
+/// ];
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[repr(C)]
+pub struct SockFilter {
+    /// The operation.
+    pub code: u16,
+
+    /// The offset to jump by if a condition is true.
+    pub jt: u8,
+
+    /// The offset to jump by if a condition is false.
+    pub jf: u8,
+
+    /// The operand.
+    pub k: u32,
+}
+
+impl SockFilter {
+    /// Creates an instruction.
+    pub const fn new(code: u16, jt: u8, jf: u8, k: u32) -> SockFilter {
+        SockFilter { code, jt, jf, k }
+    }
+}
+
+/// Returns the index of the network interface with the given name.
+///
+/// # Examples
+///
+/// ```no_run
+/// use async_std::net::raw;
+///
+/// let lo = raw::interface_index("lo")?;
+/// # std::io::Result::Ok(())
+/// ```
+pub fn interface_index(name: &str) -> io::Result<u32> {
+    let name = CString::new(name)
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid interface name"))?;
+    match unsafe { libc::if_nametoindex(name.as_ptr()) } {
+        0 => match io::Error::last_os_error() {
+            err if err.raw_os_error() == Some(libc::ENODEV) => {
+                Err(io::Error::new(io::ErrorKind::NotFound, "no such interface"))
+            }
+            err => Err(err),
+        },
+        index => Ok(index),
+    }
+}
+
+/// A nonblocking socket file descriptor, closed on drop.
+#[derive(Debug)]
+struct Fd(RawFd);
+
+impl Fd {
+    /// Opens a nonblocking socket.
+    fn socket(domain: i32, ty: i32, protocol: i32) -> io::Result<Fd> {
+        let ty = ty | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC;
+        cvt(unsafe { libc::socket(domain, ty, protocol) }).map(Fd)
+    }
+
+    fn set_option<T>(&self, level: i32, name: i32, value: &T) -> io::Result<()> {
+        cvt(unsafe {
+            libc::setsockopt(
+                self.0,
+                level,
+                name,
+                value as *const T as *const libc::c_void,
+                mem::size_of::<T>() as libc::socklen_t,
+            )
+        })
+        .map(drop)
+    }
+
+    fn attach_filter(&self, filter: &[SockFilter]) -> io::Result<()> {
+        let prog = libc::sock_fprog {
+            len: filter.len() as u16,
+            filter: filter.as_ptr() as *mut libc::sock_filter,
+        };
+        self.set_option(libc::SOL_SOCKET, libc::SO_ATTACH_FILTER, &prog)
+    }
+
+    fn detach_filter(&self) -> io::Result<()> {
+        self.set_option(libc::SOL_SOCKET, libc::SO_DETACH_FILTER, &0 as &libc::c_int)
+    }
+}
+
+impl AsRawFd for Fd {
+    fn as_raw_fd(&self) -> RawFd {
+        self.0
+    }
+}
+
+impl Evented for Fd {
+    fn register(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &Poll) -> io::Result<()> {
+        EventedFd(&self.0).deregister(poll)
+    }
+}
+
+impl Drop for Fd {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
+
+/// Turns the `-1` returned by a failed system call into the last OS error.
+fn cvt<T: Default + PartialOrd>(res: T) -> io::Result<T> {
+    if res < T::default() {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(res)
+    }
+}
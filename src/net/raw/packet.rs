@@ -0,0 +1,182 @@
+use std::fmt;
+use std::mem;
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use crate::future;
+use crate::io;
+use crate::net::driver::Watcher;
+use crate::net::raw::{cvt, Fd, SockFilter};
+
+/// A link-layer packet socket.
+///
+/// The socket sends and receives whole frames, including their link-layer headers, of a single
+/// protocol given by its EtherType, or of all protocols if it is `0x0003` (`ETH_P_ALL`). Until
+/// it is bound to an interface, it receives frames from all interfaces.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::net::raw::{self, PacketSocket};
+///
+/// let socket = PacketSocket::new(0x0003)?;
+/// socket.bind(raw::interface_index("eth0")?)?;
+///
+/// let mut buf = vec![0u8; 65536];
+/// loop {
+///     let (n, addr) = socket.recv_from(&mut buf).await?;
+///     println!("{} byte frame of protocol {:#06x}", n, addr.protocol());
+/// }
+/// #
+/// # }) }
+/// ```
+pub struct PacketSocket {
+    watcher: Watcher<Fd>,
+    protocol: u16,
+}
+
+/// The link-layer address a frame was received from.
+#[derive(Clone, Copy)]
+pub struct PacketAddr(libc::sockaddr_ll);
+
+impl PacketAddr {
+    /// Returns the index of the interface the frame was received on.
+    pub fn interface(&self) -> u32 {
+        self.0.sll_ifindex as u32
+    }
+
+    /// Returns the EtherType of the frame.
+    pub fn protocol(&self) -> u16 {
+        u16::from_be(self.0.sll_protocol)
+    }
+
+    /// Returns `true` if the frame was sent by this host rather than received by it.
+    pub fn is_outgoing(&self) -> bool {
+        self.0.sll_pkttype == libc::PACKET_OUTGOING
+    }
+
+    /// Returns the hardware address of the sender.
+    pub fn hardware_addr(&self) -> &[u8] {
+        let len = (self.0.sll_halen as usize).min(self.0.sll_addr.len());
+        &self.0.sll_addr[..len]
+    }
+}
+
+impl fmt::Debug for PacketAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PacketAddr")
+            .field("interface", &self.interface())
+            .field("protocol", &self.protocol())
+            .field("outgoing", &self.is_outgoing())
+            .field("hardware_addr", &self.hardware_addr())
+            .finish()
+    }
+}
+
+impl PacketSocket {
+    /// Opens a packet socket for the protocol with the given EtherType.
+    pub fn new(protocol: u16) -> io::Result<PacketSocket> {
+        let fd = Fd::socket(libc::AF_PACKET, libc::SOCK_RAW, i32::from(protocol.to_be()))?;
+        Ok(PacketSocket {
+            watcher: Watcher::new(fd),
+            protocol,
+        })
+    }
+
+    /// Binds the socket to a network interface, so that it only receives frames from it.
+    pub fn bind(&self, interface: u32) -> io::Result<()> {
+        let addr = self.sockaddr(interface);
+        cvt(unsafe {
+            libc::bind(
+                self.as_raw_fd(),
+                &addr as *const libc::sockaddr_ll as *const libc::sockaddr,
+                mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+            )
+        })
+        .map(drop)
+    }
+
+    /// Sends a frame on a network interface.
+    ///
+    /// The frame must start with its link-layer header. On success, returns the number of bytes
+    /// written.
+    pub async fn send_to(&self, frame: &[u8], interface: u32) -> io::Result<usize> {
+        let addr = self.sockaddr(interface);
+        future::poll_fn(|cx| {
+            self.watcher.poll_write_with(cx, |fd| {
+                cvt(unsafe {
+                    libc::sendto(
+                        fd.0,
+                        frame.as_ptr() as *const libc::c_void,
+                        frame.len(),
+                        0,
+                        &addr as *const libc::sockaddr_ll as *const libc::sockaddr,
+                        mem::size_of::<libc::sockaddr_ll>() as libc::socklen_t,
+                    )
+                })
+                .map(|n| n as usize)
+            })
+        })
+        .await
+    }
+
+    /// Receives a frame.
+    ///
+    /// On success, returns the number of bytes read and the address the frame came from.
+    pub async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, PacketAddr)> {
+        future::poll_fn(|cx| {
+            self.watcher.poll_read_with(cx, |fd| {
+                let mut addr: libc::sockaddr_ll = unsafe { mem::zeroed() };
+                let mut len = mem::size_of_val(&addr) as libc::socklen_t;
+                let n = cvt(unsafe {
+                    libc::recvfrom(
+                        fd.0,
+                        buf.as_mut_ptr() as *mut libc::c_void,
+                        buf.len(),
+                        0,
+                        &mut addr as *mut libc::sockaddr_ll as *mut libc::sockaddr,
+                        &mut len,
+                    )
+                })?;
+                Ok((n as usize, PacketAddr(addr)))
+            })
+        })
+        .await
+    }
+
+    /// Attaches a BPF program filtering the frames the socket receives.
+    ///
+    /// The program replaces any program attached before.
+    pub fn attach_filter(&self, filter: &[SockFilter]) -> io::Result<()> {
+        self.watcher.get_ref().attach_filter(filter)
+    }
+
+    /// Removes the BPF program attached to the socket.
+    pub fn detach_filter(&self) -> io::Result<()> {
+        self.watcher.get_ref().detach_filter()
+    }
+
+    fn sockaddr(&self, interface: u32) -> libc::sockaddr_ll {
+        let mut addr: libc::sockaddr_ll = unsafe { mem::zeroed() };
+        addr.sll_family = libc::AF_PACKET as u16;
+        addr.sll_protocol = self.protocol.to_be();
+        addr.sll_ifindex = interface as i32;
+        addr
+    }
+}
+
+impl AsRawFd for PacketSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.watcher.get_ref().as_raw_fd()
+    }
+}
+
+impl fmt::Debug for PacketSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PacketSocket")
+            .field("fd", &self.as_raw_fd())
+            .field("protocol", &self.protocol)
+            .finish()
+    }
+}
@@ -0,0 +1,200 @@
+use std::fmt;
+use std::mem;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use crate::future;
+use crate::io;
+use crate::net::driver::Watcher;
+use crate::net::raw::{cvt, Fd, Protocol, SockFilter};
+
+/// A raw IP socket.
+///
+/// The socket sends and receives the packets of a single IP protocol. Packets are sent without
+/// an IP header, which the kernel fills in, unless [`set_header_included`] is enabled. Packets
+/// received on an IPv4 socket start with the IP header, while those received on an IPv6 socket
+/// don't.
+///
+/// [`set_header_included`]: #method.set_header_included
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::net::raw::{Protocol, RawSocket};
+///
+/// let socket = RawSocket::v4(Protocol::ICMP)?;
+/// let mut buf = vec![0u8; 1500];
+///
+/// loop {
+///     let (n, from) = socket.recv_from(&mut buf).await?;
+///     println!("{} bytes of ICMP from {}", n, from);
+/// }
+/// #
+/// # }) }
+/// ```
+pub struct RawSocket {
+    watcher: Watcher<Fd>,
+    ipv6: bool,
+}
+
+impl RawSocket {
+    /// Opens a raw IPv4 socket for a protocol.
+    pub fn v4(protocol: Protocol) -> io::Result<RawSocket> {
+        RawSocket::open(false, protocol)
+    }
+
+    /// Opens a raw IPv6 socket for a protocol.
+    pub fn v6(protocol: Protocol) -> io::Result<RawSocket> {
+        RawSocket::open(true, protocol)
+    }
+
+    fn open(ipv6: bool, protocol: Protocol) -> io::Result<RawSocket> {
+        let domain = if ipv6 { libc::AF_INET6 } else { libc::AF_INET };
+        let fd = Fd::socket(domain, libc::SOCK_RAW, protocol.number())?;
+        Ok(RawSocket {
+            watcher: Watcher::new(fd),
+            ipv6,
+        })
+    }
+
+    /// Binds the socket to a local address, so that it only receives packets sent to it.
+    pub fn bind(&self, addr: IpAddr) -> io::Result<()> {
+        let (addr, len) = to_sockaddr(addr, self.ipv6)?;
+        cvt(unsafe {
+            libc::bind(
+                self.as_raw_fd(),
+                &addr as *const libc::sockaddr_storage as *const libc::sockaddr,
+                len,
+            )
+        })
+        .map(drop)
+    }
+
+    /// Sends a packet to the given address.
+    ///
+    /// On success, returns the number of bytes written.
+    pub async fn send_to(&self, buf: &[u8], addr: IpAddr) -> io::Result<usize> {
+        let (addr, len) = to_sockaddr(addr, self.ipv6)?;
+        future::poll_fn(|cx| {
+            self.watcher.poll_write_with(cx, |fd| {
+                cvt(unsafe {
+                    libc::sendto(
+                        fd.0,
+                        buf.as_ptr() as *const libc::c_void,
+                        buf.len(),
+                        libc::MSG_NOSIGNAL,
+                        &addr as *const libc::sockaddr_storage as *const libc::sockaddr,
+                        len,
+                    )
+                })
+                .map(|n| n as usize)
+            })
+        })
+        .await
+    }
+
+    /// Receives a packet.
+    ///
+    /// On success, returns the number of bytes read and the address the packet came from.
+    pub async fn recv_from(&self, buf: &mut [u8]) -> io::Result<(usize, IpAddr)> {
+        future::poll_fn(|cx| {
+            self.watcher.poll_read_with(cx, |fd| {
+                let mut addr: libc::sockaddr_storage = unsafe { mem::zeroed() };
+                let mut len = mem::size_of_val(&addr) as libc::socklen_t;
+                let n = cvt(unsafe {
+                    libc::recvfrom(
+                        fd.0,
+                        buf.as_mut_ptr() as *mut libc::c_void,
+                        buf.len(),
+                        0,
+                        &mut addr as *mut libc::sockaddr_storage as *mut libc::sockaddr,
+                        &mut len,
+                    )
+                })?;
+                Ok((n as usize, from_sockaddr(&addr)?))
+            })
+        })
+        .await
+    }
+
+    /// Sets the value of the `IP_HDRINCL` option for this socket.
+    ///
+    /// When enabled, packets sent on the socket must start with an IP header. This is only
+    /// supported on IPv4 sockets.
+    pub fn set_header_included(&self, on: bool) -> io::Result<()> {
+        self.watcher
+            .get_ref()
+            .set_option(libc::IPPROTO_IP, libc::IP_HDRINCL, &(on as libc::c_int))
+    }
+
+    /// Attaches a BPF program filtering the packets the socket receives.
+    ///
+    /// The program replaces any program attached before.
+    pub fn attach_filter(&self, filter: &[SockFilter]) -> io::Result<()> {
+        self.watcher.get_ref().attach_filter(filter)
+    }
+
+    /// Removes the BPF program attached to the socket.
+    pub fn detach_filter(&self) -> io::Result<()> {
+        self.watcher.get_ref().detach_filter()
+    }
+}
+
+impl AsRawFd for RawSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.watcher.get_ref().as_raw_fd()
+    }
+}
+
+impl fmt::Debug for RawSocket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RawSocket")
+            .field("fd", &self.as_raw_fd())
+            .field("ipv6", &self.ipv6)
+            .finish()
+    }
+}
+
+fn to_sockaddr(addr: IpAddr, ipv6: bool) -> io::Result<(libc::sockaddr_storage, libc::socklen_t)> {
+    let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+    let len = match (addr, ipv6) {
+        (IpAddr::V4(ip), false) => {
+            let sin = unsafe { &mut *(&mut storage as *mut _ as *mut libc::sockaddr_in) };
+            sin.sin_family = libc::AF_INET as libc::sa_family_t;
+            sin.sin_addr.s_addr = u32::from(ip).to_be();
+            mem::size_of::<libc::sockaddr_in>()
+        }
+        (IpAddr::V6(ip), true) => {
+            let sin6 = unsafe { &mut *(&mut storage as *mut _ as *mut libc::sockaddr_in6) };
+            sin6.sin6_family = libc::AF_INET6 as libc::sa_family_t;
+            sin6.sin6_addr.s6_addr = ip.octets();
+            mem::size_of::<libc::sockaddr_in6>()
+        }
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "address family doesn't match the socket",
+            ));
+        }
+    };
+    Ok((storage, len as libc::socklen_t))
+}
+
+fn from_sockaddr(addr: &libc::sockaddr_storage) -> io::Result<IpAddr> {
+    match addr.ss_family as i32 {
+        libc::AF_INET => {
+            let sin = unsafe { &*(addr as *const _ as *const libc::sockaddr_in) };
+            Ok(Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr)).into())
+        }
+        libc::AF_INET6 => {
+            let sin6 = unsafe { &*(addr as *const _ as *const libc::sockaddr_in6) };
+            Ok(Ipv6Addr::from(sin6.sin6_addr.s6_addr).into())
+        }
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "unknown address family",
+        )),
+    }
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::mem;
+use std::os::unix::io::RawFd;
+use std::time::Duration;
+
+use crate::io;
+
+/// Statistics about the state of a TCP connection, returned by [`TcpStream::tcp_info`].
+///
+/// This is a snapshot of the `TCP_INFO` socket option. Adaptive protocols can use it to react to
+/// the quality of a connection, and services to export it as metrics.
+///
+/// [`TcpStream::tcp_info`]: struct.TcpStream.html#method.tcp_info
+#[derive(Clone, Copy)]
+pub struct TcpInfo(RawTcpInfo);
+
+/// The leading part of `struct tcp_info`, which is the same in all kernel versions.
+#[derive(Clone, Copy, Default)]
+#[repr(C)]
+struct RawTcpInfo {
+    state: u8,
+    ca_state: u8,
+    retransmits: u8,
+    probes: u8,
+    backoff: u8,
+    options: u8,
+    wscale: u8,
+    flags: u8,
+
+    rto: u32,
+    ato: u32,
+    snd_mss: u32,
+    rcv_mss: u32,
+
+    unacked: u32,
+    sacked: u32,
+    lost: u32,
+    retrans: u32,
+    fackets: u32,
+
+    last_data_sent: u32,
+    last_ack_sent: u32,
+    last_data_recv: u32,
+    last_ack_recv: u32,
+
+    pmtu: u32,
+    rcv_ssthresh: u32,
+    rtt: u32,
+    rttvar: u32,
+    snd_ssthresh: u32,
+    snd_cwnd: u32,
+    advmss: u32,
+    reordering: u32,
+
+    rcv_rtt: u32,
+    rcv_space: u32,
+
+    total_retrans: u32,
+}
+
+impl TcpInfo {
+    /// Reads the `TCP_INFO` option of a socket.
+    pub(super) fn get(fd: RawFd) -> io::Result<TcpInfo> {
+        let mut info = RawTcpInfo::default();
+        let mut len = mem::size_of::<RawTcpInfo>() as libc::socklen_t;
+        let res = unsafe {
+            libc::getsockopt(
+                fd,
+                libc::IPPROTO_TCP,
+                libc::TCP_INFO,
+                &mut info as *mut RawTcpInfo as *mut libc::c_void,
+                &mut len,
+            )
+        };
+        if res == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(TcpInfo(info))
+    }
+
+    /// Returns the smoothed round-trip time.
+    pub fn rtt(&self) -> Duration {
+        Duration::from_micros(self.0.rtt.into())
+    }
+
+    /// Returns the variation of the round-trip time.
+    pub fn rtt_var(&self) -> Duration {
+        Duration::from_micros(self.0.rttvar.into())
+    }
+
+    /// Returns the retransmission timeout.
+    pub fn rto(&self) -> Duration {
+        Duration::from_micros(self.0.rto.into())
+    }
+
+    /// Returns the congestion window, in segments.
+    pub fn snd_cwnd(&self) -> u32 {
+        self.0.snd_cwnd
+    }
+
+    /// Returns the slow start threshold, in segments.
+    pub fn snd_ssthresh(&self) -> u32 {
+        self.0.snd_ssthresh
+    }
+
+    /// Returns the maximum segment size for sending, in bytes.
+    pub fn snd_mss(&self) -> u32 {
+        self.0.snd_mss
+    }
+
+    /// Returns the maximum segment size for receiving, in bytes.
+    pub fn rcv_mss(&self) -> u32 {
+        self.0.rcv_mss
+    }
+
+    /// Returns the path MTU, in bytes.
+    pub fn pmtu(&self) -> u32 {
+        self.0.pmtu
+    }
+
+    /// Returns the number of segments sent but not yet acknowledged.
+    pub fn unacked(&self) -> u32 {
+        self.0.unacked
+    }
+
+    /// Returns the number of segments presumed lost.
+    pub fn lost(&self) -> u32 {
+        self.0.lost
+    }
+
+    /// Returns the number of times the oldest unacknowledged segment has been retransmitted in a
+    /// row.
+    pub fn retransmits(&self) -> u8 {
+        self.0.retransmits
+    }
+
+    /// Returns the number of segments retransmitted over the lifetime of the connection.
+    pub fn total_retransmits(&self) -> u32 {
+        self.0.total_retrans
+    }
+
+    /// Returns the time since data was last sent.
+    pub fn last_data_sent(&self) -> Duration {
+        Duration::from_millis(self.0.last_data_sent.into())
+    }
+
+    /// Returns the time since data was last received.
+    pub fn last_data_recv(&self) -> Duration {
+        Duration::from_millis(self.0.last_data_recv.into())
+    }
+}
+
+impl fmt::Debug for TcpInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TcpInfo")
+            .field("rtt", &self.rtt())
+            .field("rtt_var", &self.rtt_var())
+            .field("rto", &self.rto())
+            .field("snd_cwnd", &self.snd_cwnd())
+            .field("snd_mss", &self.snd_mss())
+            .field("unacked", &self.unacked())
+            .field("lost", &self.lost())
+            .field("total_retransmits", &self.total_retransmits())
+            .finish()
+    }
+}
@@ -4,6 +4,8 @@ use std::pin::Pin;
 
 use crate::future;
 use crate::io;
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+use crate::net::driver::uring;
 use crate::net::driver::Watcher;
 use crate::net::{TcpStream, ToSocketAddrs};
 use crate::stream::Stream;
@@ -49,9 +51,30 @@ use crate::task::{Context, Poll};
 #[derive(Debug)]
 pub struct TcpListener {
     watcher: Watcher<mio::net::TcpListener>,
+
+    /// The io_uring state, if the listener uses io_uring.
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    uring: Option<uring::Listener>,
 }
 
 impl TcpListener {
+    /// Wraps a mio listener, using io_uring for it if selected on the current thread.
+    fn new(mio_listener: mio::net::TcpListener) -> TcpListener {
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        let uring = if TcpStream::uring_selected() {
+            use std::os::unix::io::AsRawFd;
+            Some(uring::Listener::new(mio_listener.as_raw_fd()))
+        } else {
+            None
+        };
+
+        TcpListener {
+            watcher: Watcher::new(mio_listener),
+            #[cfg(all(feature = "io-uring", target_os = "linux"))]
+            uring,
+        }
+    }
+
     /// Creates a new `TcpListener` which will be bound to the specified address.
     ///
     /// The returned listener is ready for accepting connections.
@@ -81,11 +104,7 @@ impl TcpListener {
 
         for addr in addrs {
             match mio::net::TcpListener::bind(&addr) {
-                Ok(mio_listener) => {
-                    return Ok(TcpListener {
-                        watcher: Watcher::new(mio_listener),
-                    });
-                }
+                Ok(mio_listener) => return Ok(TcpListener::new(mio_listener)),
                 Err(err) => last_err = Some(err),
             }
         }
@@ -115,14 +134,64 @@ impl TcpListener {
     /// # Ok(()) }) }
     /// ```
     pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        {
+            if let Some(uring) = &self.uring {
+                use std::os::unix::io::FromRawFd;
+
+                let fd = future::poll_fn(|cx| uring.poll_accept(cx)).await?;
+                let io = unsafe { std::net::TcpStream::from_raw_fd(fd) };
+                let addr = io.peer_addr()?;
+                let mio_stream = mio::net::TcpStream::from_stream(io)?;
+                return Ok((TcpStream::new(mio_stream, true), addr));
+            }
+        }
+
         let (io, addr) =
             future::poll_fn(|cx| self.watcher.poll_read_with(cx, |inner| inner.accept_std()))
                 .await?;
 
         let mio_stream = mio::net::TcpStream::from_stream(io)?;
-        let stream = TcpStream {
-            watcher: Watcher::new(mio_stream),
-        };
+        Ok((TcpStream::new(mio_stream, false), addr))
+    }
+
+    /// Accepts a new incoming connection, configuring it before it is returned.
+    ///
+    /// The closure sets options such as [`set_nodelay`], [`set_keepalive`], or the buffer sizes
+    /// on the accepted stream. Since the stream isn't handed out before that, no data is sent or
+    /// read on it with the default options. If the closure fails, the connection is closed and
+    /// the error is returned.
+    ///
+    /// [`set_nodelay`]: struct.TcpStream.html#method.set_nodelay
+    /// [`set_keepalive`]: struct.TcpStream.html#method.set_keepalive
+    ///
+    /// ## Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::net::TcpListener;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:0").await?;
+    /// let (stream, addr) = listener
+    ///     .accept_with(|stream| {
+    ///         stream.set_nodelay(true)?;
+    ///         stream.set_keepalive(Some(Duration::from_secs(60)))
+    ///     })
+    ///     .await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn accept_with<F>(&self, configure: F) -> io::Result<(TcpStream, SocketAddr)>
+    where
+        F: FnOnce(&TcpStream) -> io::Result<()>,
+    {
+        let (stream, addr) = self.accept().await?;
+        configure(&stream)?;
         Ok((stream, addr))
     }
 
@@ -208,9 +277,7 @@ impl From<std::net::TcpListener> for TcpListener {
     /// Converts a `std::net::TcpListener` into its asynchronous equivalent.
     fn from(listener: std::net::TcpListener) -> TcpListener {
         let mio_listener = mio::net::TcpListener::from_std(listener).unwrap();
-        TcpListener {
-            watcher: Watcher::new(mio_listener),
-        }
+        TcpListener::new(mio_listener)
     }
 }
 
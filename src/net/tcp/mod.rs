@@ -1,5 +1,13 @@
+#[cfg(all(feature = "unstable", any(target_os = "linux", target_os = "android")))]
+pub use info::TcpInfo;
 pub use listener::{Incoming, TcpListener};
+#[cfg(feature = "unstable")]
+pub use split::{TcpReadHalf, TcpWriteHalf};
 pub use stream::TcpStream;
 
+#[cfg(all(feature = "unstable", any(target_os = "linux", target_os = "android")))]
+mod info;
 mod listener;
+#[cfg(feature = "unstable")]
+mod split;
 mod stream;
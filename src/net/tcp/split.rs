@@ -0,0 +1,137 @@
+use std::net::{Shutdown, SocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::io::{self, Read, Write};
+use crate::net::TcpStream;
+use crate::task::{Context, Poll};
+
+/// The reading half of a [`TcpStream`], created by [`into_split`].
+///
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`into_split`]: struct.TcpStream.html#method.into_split
+#[derive(Debug)]
+pub struct TcpReadHalf(Arc<TcpStream>);
+
+/// The writing half of a [`TcpStream`], created by [`into_split`].
+///
+/// Closing the half, or dropping it, shuts down the writing portion of the connection, so that
+/// the peer reads the end of the stream while the reading half keeps receiving data.
+///
+/// [`TcpStream`]: struct.TcpStream.html
+/// [`into_split`]: struct.TcpStream.html#method.into_split
+#[derive(Debug)]
+pub struct TcpWriteHalf {
+    stream: Arc<TcpStream>,
+
+    /// Set once the writing portion has been shut down.
+    shut_down: bool,
+}
+
+pub(super) fn split(stream: TcpStream) -> (TcpReadHalf, TcpWriteHalf) {
+    let stream = Arc::new(stream);
+    let write = TcpWriteHalf {
+        stream: stream.clone(),
+        shut_down: false,
+    };
+    (TcpReadHalf(stream), write)
+}
+
+impl TcpReadHalf {
+    /// Returns the local address of the connection.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.0.local_addr()
+    }
+
+    /// Returns the remote address of the connection.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.0.peer_addr()
+    }
+
+    /// Shuts down the reading portion of the connection.
+    ///
+    /// Pending and future reads return the end of the stream. The writing half is unaffected.
+    pub fn shutdown(&self) -> io::Result<()> {
+        self.0.shutdown(Shutdown::Read)
+    }
+
+    /// Puts the halves back together into the stream they were split from.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the halves weren't split from the same stream.
+    pub fn unsplit(self, write: TcpWriteHalf) -> TcpStream {
+        assert!(
+            Arc::ptr_eq(&self.0, &write.stream),
+            "halves of different streams"
+        );
+        let mut write = write;
+        write.shut_down = true;
+        drop(write);
+        match Arc::try_unwrap(self.0) {
+            Ok(stream) => stream,
+            Err(_) => unreachable!("the halves hold the only references to the stream"),
+        }
+    }
+}
+
+impl TcpWriteHalf {
+    /// Returns the local address of the connection.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.local_addr()
+    }
+
+    /// Returns the remote address of the connection.
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.stream.peer_addr()
+    }
+
+    /// Shuts down the writing portion of the connection without waiting for buffered data.
+    ///
+    /// The peer reads the end of the stream once the data already written has arrived. To wait
+    /// for the data in flight first, [`close`] the half instead.
+    ///
+    /// [`close`]: ../io/prelude/trait.WriteExt.html#method.close
+    pub fn shutdown(&mut self) -> io::Result<()> {
+        self.shut_down = true;
+        self.stream.shutdown(Shutdown::Write)
+    }
+}
+
+impl Drop for TcpWriteHalf {
+    fn drop(&mut self) {
+        if !self.shut_down {
+            let _ = self.stream.shutdown(Shutdown::Write);
+        }
+    }
+}
+
+impl Read for TcpReadHalf {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut &*self.0).poll_read(cx, buf)
+    }
+}
+
+impl Write for TcpWriteHalf {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut &*self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut &*self.stream).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        futures_core::ready!(Pin::new(&mut &*self.stream).poll_close(cx))?;
+        self.shut_down = true;
+        Poll::Ready(Ok(()))
+    }
+}
@@ -1,10 +1,18 @@
 use std::io::{IoSlice, IoSliceMut, Read as _, Write as _};
 use std::net::SocketAddr;
 use std::pin::Pin;
+#[cfg(feature = "unstable")]
+use std::time::Duration;
 
 use crate::future;
 use crate::io::{self, Read, Write};
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+use crate::net::driver::uring;
 use crate::net::driver::Watcher;
+#[cfg(all(feature = "unstable", any(target_os = "linux", target_os = "android")))]
+use crate::net::tcp::info::TcpInfo;
+#[cfg(feature = "unstable")]
+use crate::net::tcp::split::{self, TcpReadHalf, TcpWriteHalf};
 use crate::net::ToSocketAddrs;
 use crate::task::{spawn_blocking, Context, Poll};
 use crate::utils::Context as _;
@@ -48,9 +56,41 @@ use crate::utils::Context as _;
 #[derive(Debug)]
 pub struct TcpStream {
     pub(super) watcher: Watcher<mio::net::TcpStream>,
+
+    /// The io_uring state, if the stream uses io_uring.
+    #[cfg(all(feature = "io-uring", target_os = "linux"))]
+    pub(super) uring: Option<uring::Stream>,
 }
 
 impl TcpStream {
+    /// Wraps a mio stream, using io_uring for it if `uring` is set.
+    pub(super) fn new(mio_stream: mio::net::TcpStream, uring: bool) -> TcpStream {
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        let uring = if uring {
+            use std::os::unix::io::AsRawFd;
+            Some(uring::Stream::new(mio_stream.as_raw_fd()))
+        } else {
+            None
+        };
+        #[cfg(not(all(feature = "io-uring", target_os = "linux")))]
+        let _ = uring;
+
+        TcpStream {
+            watcher: Watcher::new(mio_stream),
+            #[cfg(all(feature = "io-uring", target_os = "linux"))]
+            uring,
+        }
+    }
+
+    /// Returns `true` if streams created on the current thread should use io_uring.
+    pub(super) fn uring_selected() -> bool {
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        return crate::net::driver::uring_selected();
+
+        #[cfg(not(all(feature = "io-uring", target_os = "linux")))]
+        return false;
+    }
+
     /// Creates a new TCP stream connected to the specified address.
     ///
     /// This method will create a new TCP socket and attempt to connect it to the `addr`
@@ -75,21 +115,20 @@ impl TcpStream {
         let addrs = addrs
             .to_socket_addrs()
             .await?;
+        let uring = TcpStream::uring_selected();
 
         for addr in addrs {
             let res = spawn_blocking(move || {
                 let std_stream = std::net::TcpStream::connect(addr)
                     .context(|| format!("could not connect to {}", addr))?;
-                let mio_stream = mio::net::TcpStream::from_stream(std_stream)
-                    .context(|| format!("could not open async connection to {}", addr))?;
-                Ok(TcpStream {
-                    watcher: Watcher::new(mio_stream),
-                })
+                mio::net::TcpStream::from_stream(std_stream)
+                    .context(|| format!("could not open async connection to {}", addr))
             })
             .await;
 
             match res {
-                Ok(stream) => return Ok(stream),
+                // Register the stream on the current thread, in the reactor of its runtime.
+                Ok(mio_stream) => return Ok(TcpStream::new(mio_stream, uring)),
                 Err(err) => last_err = Some(err),
             }
         }
@@ -261,12 +300,123 @@ impl TcpStream {
         self.watcher.get_ref().set_nodelay(nodelay)
     }
 
+    /// Gets the keepalive interval of this socket, or `None` if keepalive is disabled.
+    ///
+    /// For more information about this option, see [`set_keepalive`].
+    ///
+    /// [`set_keepalive`]: #method.set_keepalive
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn keepalive(&self) -> io::Result<Option<Duration>> {
+        self.watcher.get_ref().keepalive()
+    }
+
+    /// Sets the keepalive interval of this socket, enabling `SO_KEEPALIVE` if it is `Some`.
+    ///
+    /// Keepalive probes are sent after the connection has been idle for the interval, so that a
+    /// peer that has gone away without closing the connection is noticed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// stream.set_keepalive(Some(Duration::from_secs(60)))?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn set_keepalive(&self, keepalive: Option<Duration>) -> io::Result<()> {
+        self.watcher.get_ref().set_keepalive(keepalive)
+    }
+
+    /// Gets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`set_send_buffer_size`].
+    ///
+    /// [`set_send_buffer_size`]: #method.set_send_buffer_size
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn send_buffer_size(&self) -> io::Result<usize> {
+        self.watcher.get_ref().send_buffer_size()
+    }
+
+    /// Sets the value of the `SO_SNDBUF` option on this socket.
+    ///
+    /// This is the size of the buffer the kernel keeps outgoing data in until the peer has
+    /// acknowledged it. The kernel may adjust the size, usually doubling it.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn set_send_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.watcher.get_ref().set_send_buffer_size(size)
+    }
+
+    /// Gets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// For more information about this option, see [`set_recv_buffer_size`].
+    ///
+    /// [`set_recv_buffer_size`]: #method.set_recv_buffer_size
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn recv_buffer_size(&self) -> io::Result<usize> {
+        self.watcher.get_ref().recv_buffer_size()
+    }
+
+    /// Sets the value of the `SO_RCVBUF` option on this socket.
+    ///
+    /// This is the size of the buffer the kernel keeps incoming data in until it is read, which
+    /// limits the window advertised to the peer. The kernel may adjust the size, usually doubling
+    /// it.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn set_recv_buffer_size(&self, size: usize) -> io::Result<()> {
+        self.watcher.get_ref().set_recv_buffer_size(size)
+    }
+
+    /// Returns statistics about the state of the connection, such as its round-trip time,
+    /// congestion window, and retransmissions.
+    ///
+    /// This is only supported on Linux and Android.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::net::TcpStream;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// let info = stream.tcp_info()?;
+    /// println!("rtt: {:?}, cwnd: {}", info.rtt(), info.snd_cwnd());
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(all(feature = "unstable", any(target_os = "linux", target_os = "android")))]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn tcp_info(&self) -> io::Result<TcpInfo> {
+        use std::os::unix::io::AsRawFd;
+
+        TcpInfo::get(self.watcher.get_ref().as_raw_fd())
+    }
+
     /// Shuts down the read, write, or both halves of this connection.
     ///
     /// This method will cause all pending and future I/O on the specified portions to return
     /// immediately with an appropriate value (see the documentation of [`Shutdown`]).
     ///
+    /// Shutting down the writing portion sends the end of the stream to the peer right away.
+    /// Closing the stream with [`close`] does the same once the bytes in flight have been
+    /// flushed, which is how protocols that end a request by half-closing the connection are
+    /// usually spoken. The connection itself stays open until the stream is dropped.
+    ///
     /// [`Shutdown`]: https://doc.rust-lang.org/std/net/enum.Shutdown.html
+    /// [`close`]: ../io/prelude/trait.WriteExt.html#method.close
     ///
     /// # Examples
     ///
@@ -285,6 +435,41 @@ impl TcpStream {
     pub fn shutdown(&self, how: std::net::Shutdown) -> std::io::Result<()> {
         self.watcher.get_ref().shutdown(how)
     }
+
+    /// Splits the stream into a reading half and a writing half that can be moved to different
+    /// tasks.
+    ///
+    /// Closing or dropping the writing half shuts down the writing portion of the connection,
+    /// while the reading half keeps receiving the response of the peer. The halves can be put
+    /// back together with [`TcpReadHalf::unsplit`].
+    ///
+    /// [`TcpReadHalf::unsplit`]: struct.TcpReadHalf.html#method.unsplit
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::net::TcpStream;
+    /// use async_std::prelude::*;
+    ///
+    /// let stream = TcpStream::connect("127.0.0.1:8080").await?;
+    /// let (mut reader, mut writer) = stream.into_split();
+    ///
+    /// // Send the request, signalling its end by half-closing the connection.
+    /// writer.write_all(b"request").await?;
+    /// writer.close().await?;
+    ///
+    /// let mut response = Vec::new();
+    /// reader.read_to_end(&mut response).await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn into_split(self) -> (TcpReadHalf, TcpWriteHalf) {
+        split::split(self)
+    }
 }
 
 impl Read for TcpStream {
@@ -311,6 +496,13 @@ impl Read for &TcpStream {
         cx: &mut Context<'_>,
         buf: &mut [u8],
     ) -> Poll<io::Result<usize>> {
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        {
+            if let Some(uring) = &self.uring {
+                return uring.poll_read(cx, buf);
+            }
+        }
+
         self.watcher.poll_read_with(cx, |mut inner| inner.read(buf))
     }
 }
@@ -347,15 +539,31 @@ impl Write for &TcpStream {
         cx: &mut Context<'_>,
         buf: &[u8],
     ) -> Poll<io::Result<usize>> {
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        {
+            if let Some(uring) = &self.uring {
+                return uring.poll_write(cx, buf);
+            }
+        }
+
         self.watcher
             .poll_write_with(cx, |mut inner| inner.write(buf))
     }
 
     fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        #[cfg(all(feature = "io-uring", target_os = "linux"))]
+        {
+            if let Some(uring) = &self.uring {
+                return uring.poll_flush(cx);
+            }
+        }
+
         self.watcher.poll_write_with(cx, |mut inner| inner.flush())
     }
 
-    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // Send the bytes still in flight before shutting down.
+        futures_core::ready!(self.as_mut().poll_flush(cx))?;
         self.shutdown(std::net::Shutdown::Write)?;
         Poll::Ready(Ok(()))
     }
@@ -365,9 +573,7 @@ impl From<std::net::TcpStream> for TcpStream {
     /// Converts a `std::net::TcpStream` into its asynchronous equivalent.
     fn from(stream: std::net::TcpStream) -> TcpStream {
         let mio_stream = mio::net::TcpStream::from_stream(stream).unwrap();
-        TcpStream {
-            watcher: Watcher::new(mio_stream),
-        }
+        TcpStream::new(mio_stream, TcpStream::uring_selected())
     }
 }
 
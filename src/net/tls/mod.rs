@@ -0,0 +1,218 @@
+//! TLS streams over rustls.
+//!
+//! A [`TlsConnector`] opens the client side of a TLS session over a connected stream, usually a
+//! [`TcpStream`], and a [`TlsAcceptor`] opens the server side. Both complete the handshake before
+//! returning a [`TlsStream`], which encrypts what is written to it and decrypts what is read from
+//! it.
+//!
+//! Connectors and acceptors are created from a rustls [`ClientConfig`] or [`ServerConfig`], which
+//! hold the trusted roots, certificates, and protocol settings. The version of rustls they are
+//! built with is re-exported as [`rustls`], so that configurations don't depend on a separately
+//! chosen version.
+//!
+//! This module is only available behind the `tls` feature.
+//!
+//! [`TlsConnector`]: struct.TlsConnector.html
+//! [`TlsAcceptor`]: struct.TlsAcceptor.html
+//! [`TlsStream`]: struct.TlsStream.html
+//! [`TcpStream`]: ../struct.TcpStream.html
+//! [`ClientConfig`]: rustls/struct.ClientConfig.html
+//! [`ServerConfig`]: rustls/struct.ServerConfig.html
+//! [`rustls`]: rustls/index.html
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use std::sync::Arc;
+//!
+//! use async_std::net::tls::rustls::{ClientConfig, RootCertStore};
+//! use async_std::net::tls::TlsConnector;
+//! use async_std::net::TcpStream;
+//! use async_std::prelude::*;
+//!
+//! let mut roots = RootCertStore::empty();
+//! let root = async_std::fs::read("root.der").await?;
+//! roots.add(root.into()).map_err(std::io::Error::other)?;
+//!
+//! let config = ClientConfig::builder()
+//!     .with_root_certificates(roots)
+//!     .with_no_client_auth();
+//! let connector = TlsConnector::from(Arc::new(config));
+//!
+//! let stream = TcpStream::connect("example.com:443").await?;
+//! let mut stream = connector.connect("example.com", stream).await?;
+//! stream.write_all(b"GET / HTTP/1.0\r\nHost: example.com\r\n\r\n").await?;
+//!
+//! let mut response = Vec::new();
+//! stream.read_to_end(&mut response).await?;
+//! #
+//! # Ok(()) }) }
+//! ```
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::sync::Arc;
+
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, ServerConfig, ServerConnection};
+
+use crate::future;
+use crate::io::{self, Read, Write};
+
+pub use stream::TlsStream;
+
+#[doc(inline)]
+pub use rustls;
+
+mod stream;
+
+/// Opens the client side of TLS sessions.
+///
+/// A connector is cheap to clone, and all of its clones share the same configuration.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![allow(dead_code)]
+/// use std::sync::Arc;
+///
+/// use async_std::io;
+/// use async_std::net::tls::rustls::ClientConfig;
+/// use async_std::net::tls::{TlsConnector, TlsStream};
+/// use async_std::net::TcpStream;
+///
+/// async fn connect(config: Arc<ClientConfig>) -> io::Result<TlsStream<TcpStream>> {
+///     let connector = TlsConnector::from(config);
+///     let stream = TcpStream::connect("example.com:443").await?;
+///     connector.connect("example.com", stream).await
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TlsConnector {
+    config: Arc<ClientConfig>,
+}
+
+impl TlsConnector {
+    /// Opens a TLS session with the server `domain` over `stream`.
+    ///
+    /// The server's certificate must be valid for `domain`, which is a DNS name or an IP address.
+    /// The returned stream has completed the handshake.
+    ///
+    /// # Errors
+    ///
+    /// An error of the `InvalidInput` kind is returned if `domain` isn't a valid name, and one of
+    /// the `InvalidData` kind if the handshake fails, for example because the server's
+    /// certificate isn't trusted.
+    pub async fn connect<IO>(&self, domain: &str, stream: IO) -> io::Result<TlsStream<IO>>
+    where
+        IO: Read + Write + Unpin,
+    {
+        let name = ServerName::try_from(domain.to_string())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid server name"))?;
+        let conn = ClientConnection::new(self.config.clone(), name).map_err(io::Error::other)?;
+
+        let mut stream = TlsStream::new(stream, conn.into());
+        future::poll_fn(|cx| stream.poll_handshake(cx)).await?;
+        Ok(stream)
+    }
+
+    /// Returns the configuration of the connector.
+    pub fn config(&self) -> &Arc<ClientConfig> {
+        &self.config
+    }
+}
+
+impl From<Arc<ClientConfig>> for TlsConnector {
+    fn from(config: Arc<ClientConfig>) -> TlsConnector {
+        TlsConnector { config }
+    }
+}
+
+impl From<ClientConfig> for TlsConnector {
+    fn from(config: ClientConfig) -> TlsConnector {
+        TlsConnector::from(Arc::new(config))
+    }
+}
+
+impl fmt::Debug for TlsConnector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsConnector").finish()
+    }
+}
+
+/// Opens the server side of TLS sessions.
+///
+/// An acceptor is cheap to clone, and all of its clones share the same configuration.
+///
+/// # Examples
+///
+/// ```no_run
+/// #![allow(dead_code)]
+/// use std::sync::Arc;
+///
+/// use async_std::io;
+/// use async_std::net::tls::rustls::ServerConfig;
+/// use async_std::net::tls::TlsAcceptor;
+/// use async_std::net::TcpListener;
+/// use async_std::prelude::*;
+///
+/// async fn serve(config: Arc<ServerConfig>) -> io::Result<()> {
+///     let acceptor = TlsAcceptor::from(config);
+///     let listener = TcpListener::bind("127.0.0.1:8443").await?;
+///
+///     let (stream, _) = listener.accept().await?;
+///     let mut stream = acceptor.accept(stream).await?;
+///     stream.write_all(b"hello").await?;
+///     stream.close().await
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TlsAcceptor {
+    config: Arc<ServerConfig>,
+}
+
+impl TlsAcceptor {
+    /// Opens a TLS session with the client on the other end of `stream`.
+    ///
+    /// The returned stream has completed the handshake.
+    ///
+    /// # Errors
+    ///
+    /// An error of the `InvalidData` kind is returned if the handshake fails, for example because
+    /// the client doesn't speak TLS.
+    pub async fn accept<IO>(&self, stream: IO) -> io::Result<TlsStream<IO>>
+    where
+        IO: Read + Write + Unpin,
+    {
+        let conn = ServerConnection::new(self.config.clone()).map_err(io::Error::other)?;
+
+        let mut stream = TlsStream::new(stream, conn.into());
+        future::poll_fn(|cx| stream.poll_handshake(cx)).await?;
+        Ok(stream)
+    }
+
+    /// Returns the configuration of the acceptor.
+    pub fn config(&self) -> &Arc<ServerConfig> {
+        &self.config
+    }
+}
+
+impl From<Arc<ServerConfig>> for TlsAcceptor {
+    fn from(config: Arc<ServerConfig>) -> TlsAcceptor {
+        TlsAcceptor { config }
+    }
+}
+
+impl From<ServerConfig> for TlsAcceptor {
+    fn from(config: ServerConfig) -> TlsAcceptor {
+        TlsAcceptor::from(Arc::new(config))
+    }
+}
+
+impl fmt::Debug for TlsAcceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsAcceptor").finish()
+    }
+}
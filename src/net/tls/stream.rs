@@ -0,0 +1,255 @@
+use std::fmt;
+use std::io::{Read as _, Write as _};
+use std::pin::Pin;
+
+use rustls::Connection;
+
+use crate::io::{self, Read, Write};
+use crate::task::{Context, Poll};
+
+/// A TLS session over an underlying stream.
+///
+/// This type is returned by [`TlsConnector::connect`] and [`TlsAcceptor::accept`]. Bytes written
+/// to it are encrypted and sent over the underlying stream, and bytes read from it have been
+/// received and decrypted.
+///
+/// Closing the stream sends a `close_notify` alert before closing the underlying stream. Reading
+/// returns 0 bytes once the peer has done the same, and fails with an error of the
+/// `UnexpectedEof` kind if the underlying stream ends without it, which may mean that the data
+/// has been truncated.
+///
+/// [`TlsConnector::connect`]: struct.TlsConnector.html#method.connect
+/// [`TlsAcceptor::accept`]: struct.TlsAcceptor.html#method.accept
+pub struct TlsStream<IO> {
+    io: IO,
+    conn: Connection,
+    closing: bool,
+}
+
+impl<IO> TlsStream<IO> {
+    pub(super) fn new(io: IO, conn: Connection) -> TlsStream<IO> {
+        TlsStream {
+            io,
+            conn,
+            closing: false,
+        }
+    }
+
+    /// Gets a reference to the underlying stream.
+    pub fn get_ref(&self) -> &IO {
+        &self.io
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    ///
+    /// Reading from or writing to it directly corrupts the TLS session.
+    pub fn get_mut(&mut self) -> &mut IO {
+        &mut self.io
+    }
+
+    /// Returns the rustls connection, which holds the negotiated parameters of the session.
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+
+    /// Unwraps the stream, returning the underlying stream.
+    ///
+    /// Encrypted bytes that haven't been sent yet are lost.
+    pub fn into_inner(self) -> IO {
+        self.io
+    }
+}
+
+impl<IO: Read + Write + Unpin> TlsStream<IO> {
+    /// Drives the handshake until it completes and its last messages have been sent.
+    pub(super) fn poll_handshake(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            if self.conn.wants_write() {
+                match self.write_io(cx) {
+                    Poll::Ready(Ok(_)) => continue,
+                    Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                    Poll::Pending => {}
+                }
+            }
+
+            if !self.conn.is_handshaking() {
+                if self.conn.wants_write() {
+                    return Poll::Pending;
+                }
+                return Pin::new(&mut self.io).poll_flush(cx);
+            }
+
+            if self.conn.wants_read() {
+                match futures_core::ready!(self.read_io(cx))? {
+                    0 => return Poll::Ready(Err(io::ErrorKind::UnexpectedEof.into())),
+                    _ => continue,
+                }
+            }
+
+            // The handshake is waiting for the underlying stream to accept more bytes.
+            return Poll::Pending;
+        }
+    }
+
+    /// Reads encrypted bytes from the underlying stream and processes them.
+    ///
+    /// Returns the number of bytes read, which is 0 at the end of the underlying stream.
+    fn read_io(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        let mut io = SyncIo {
+            io: &mut self.io,
+            cx,
+        };
+        let n = match self.conn.read_tls(&mut io) {
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => return Poll::Pending,
+            Err(err) => return Poll::Ready(Err(err)),
+        };
+
+        if let Err(err) = self.conn.process_new_packets() {
+            // Try to tell the peer why the session ends.
+            let _ = self.write_io(cx);
+            return Poll::Ready(Err(io::Error::new(io::ErrorKind::InvalidData, err)));
+        }
+        Poll::Ready(Ok(n))
+    }
+
+    /// Writes encrypted bytes to the underlying stream.
+    fn write_io(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        let mut io = SyncIo {
+            io: &mut self.io,
+            cx,
+        };
+        match self.conn.write_tls(&mut io) {
+            Ok(0) => Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+            Ok(n) => Poll::Ready(Ok(n)),
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => Poll::Pending,
+            Err(err) => Poll::Ready(Err(err)),
+        }
+    }
+
+    /// Writes as many pending encrypted bytes as the underlying stream accepts.
+    ///
+    /// Returns `true` if all of them have been written.
+    fn write_pending(&mut self, cx: &mut Context<'_>) -> io::Result<bool> {
+        while self.conn.wants_write() {
+            match self.write_io(cx) {
+                Poll::Ready(result) => result?,
+                Poll::Pending => return Ok(false),
+            };
+        }
+        Ok(true)
+    }
+}
+
+impl<IO: Read + Write + Unpin> Read for TlsStream<IO> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        loop {
+            match this.conn.reader().read(buf) {
+                Ok(n) => return Poll::Ready(Ok(n)),
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {}
+                Err(err) => return Poll::Ready(Err(err)),
+            }
+
+            // Reading may have produced messages to send back, such as key updates.
+            this.write_pending(cx)?;
+
+            // Once the underlying stream has ended, the reader reports how the session ended.
+            futures_core::ready!(this.read_io(cx))?;
+        }
+    }
+}
+
+impl<IO: Read + Write + Unpin> Write for TlsStream<IO> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        loop {
+            let n = this.conn.writer().write(buf)?;
+            let flushed = this.write_pending(cx)?;
+
+            if n > 0 || buf.is_empty() {
+                return Poll::Ready(Ok(n));
+            }
+            if !flushed {
+                return Poll::Pending;
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        this.conn.writer().flush()?;
+        if !this.write_pending(cx)? {
+            return Poll::Pending;
+        }
+        Pin::new(&mut this.io).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !self.closing {
+            self.conn.send_close_notify();
+            self.closing = true;
+        }
+
+        futures_core::ready!(self.as_mut().poll_flush(cx))?;
+        Pin::new(&mut self.io).poll_close(cx)
+    }
+}
+
+impl<IO: fmt::Debug> fmt::Debug for TlsStream<IO> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsStream").field("io", &self.io).finish()
+    }
+}
+
+/// Adapts an asynchronous stream to the blocking traits rustls reads and writes through.
+///
+/// Operations that would block fail with an error of the `WouldBlock` kind, after registering
+/// the task to be woken up once they can make progress.
+struct SyncIo<'a, 'b, IO> {
+    io: &'a mut IO,
+    cx: &'a mut Context<'b>,
+}
+
+impl<IO: Read + Unpin> std::io::Read for SyncIo<'_, '_, IO> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match Pin::new(&mut *self.io).poll_read(self.cx, buf) {
+            Poll::Ready(result) => result,
+            Poll::Pending => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+}
+
+impl<IO: Write + Unpin> std::io::Write for SyncIo<'_, '_, IO> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match Pin::new(&mut *self.io).poll_write(self.cx, buf) {
+            Poll::Ready(result) => result,
+            Poll::Pending => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+        match Pin::new(&mut *self.io).poll_write_vectored(self.cx, bufs) {
+            Poll::Ready(result) => result,
+            Poll::Pending => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match Pin::new(&mut *self.io).poll_flush(self.cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    }
+}
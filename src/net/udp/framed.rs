@@ -0,0 +1,234 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::net::SocketAddr;
+use std::pin::Pin;
+
+use crate::io;
+use crate::io::codec::{Decoder, Encoder};
+use crate::net::UdpSocket;
+use crate::sink::Sink;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// The largest payload of a UDP datagram.
+const MAX_DATAGRAM_SIZE: usize = 65_535;
+
+/// A stream and sink of frames sent as UDP datagrams.
+///
+/// Each datagram received by the socket is decoded into frames by the codec's [`Decoder`], and
+/// yielded along with the address it came from. Frames sent into the sink are encoded by the
+/// codec's [`Encoder`] into one datagram each, and sent to the address they are paired with.
+///
+/// Since datagrams are independent of each other, bytes left over at the end of a datagram are
+/// an error, and so is a datagram the decoder can't make sense of. Unlike with [`Framed`], these
+/// errors don't end the stream.
+///
+/// The socket can be anything that borrows a [`UdpSocket`], like an `Arc<UdpSocket>`. This
+/// makes it possible to receive and send frames from separate tasks, with one `UdpFramed` each.
+///
+/// [`Decoder`]: ../io/codec/trait.Decoder.html
+/// [`Encoder`]: ../io/codec/trait.Encoder.html
+/// [`Framed`]: ../io/codec/struct.Framed.html
+/// [`UdpSocket`]: struct.UdpSocket.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::io::codec::LinesCodec;
+/// use async_std::net::{UdpFramed, UdpSocket};
+/// use async_std::prelude::*;
+/// use async_std::sink::SinkExt;
+///
+/// let socket = UdpSocket::bind("127.0.0.1:8080").await?;
+/// let mut lines = UdpFramed::new(socket, LinesCodec::new());
+///
+/// while let Some(res) = lines.next().await {
+///     let (line, peer) = res?;
+///     SinkExt::send(&mut lines, (line.to_uppercase(), peer)).await?;
+/// }
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct UdpFramed<T, C> {
+    socket: T,
+    codec: C,
+
+    /// The datagram being decoded.
+    read_buf: Vec<u8>,
+
+    /// The sender of the datagram being decoded, if there is one.
+    peer: Option<SocketAddr>,
+
+    /// The encoded datagram waiting to be sent.
+    write_buf: Vec<u8>,
+
+    /// The destination of the datagram waiting to be sent, if there is one.
+    target: Option<SocketAddr>,
+}
+
+impl<T: Borrow<UdpSocket>, C> UdpFramed<T, C> {
+    /// Creates a stream and sink of frames over `socket`, encoded and decoded by `codec`.
+    pub fn new(socket: T, codec: C) -> UdpFramed<T, C> {
+        UdpFramed {
+            socket,
+            codec,
+            read_buf: Vec::new(),
+            peer: None,
+            write_buf: Vec::new(),
+            target: None,
+        }
+    }
+
+    /// Gets a reference to the underlying socket.
+    pub fn get_ref(&self) -> &T {
+        &self.socket
+    }
+
+    /// Gets a mutable reference to the underlying socket.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.socket
+    }
+
+    /// Unwraps the adapter, returning the underlying socket.
+    ///
+    /// Frames left in a received datagram and a datagram that hasn't been flushed yet are lost.
+    pub fn into_inner(self) -> T {
+        self.socket
+    }
+
+    /// Returns a reference to the codec.
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Returns a mutable reference to the codec.
+    pub fn codec_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    /// Sends the pending datagram, if there is one.
+    fn poll_send(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let target = match self.target {
+            Some(target) => target,
+            None => return Poll::Ready(Ok(())),
+        };
+
+        let buf = &self.write_buf;
+        let res = futures_core::ready!(
+            self.socket
+                .borrow()
+                .watcher
+                .poll_write_with(cx, |inner| inner.send_to(buf, &target))
+        );
+
+        // The datagram is dropped even if it couldn't be sent, like a lost packet would be.
+        let len = self.write_buf.len();
+        self.write_buf.clear();
+        self.target = None;
+
+        Poll::Ready(match res {
+            Ok(n) if n < len => Err(io::Error::new(
+                io::ErrorKind::WriteZero,
+                "failed to write entire datagram to socket",
+            )),
+            Ok(_) => Ok(()),
+            Err(err) => Err(err),
+        })
+    }
+}
+
+impl<T: Borrow<UdpSocket> + Unpin, C: Decoder + Unpin> Stream for UdpFramed<T, C> {
+    type Item = io::Result<(C::Item, SocketAddr)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            // Decode the rest of the current datagram.
+            if let Some(peer) = this.peer {
+                match this.codec.decode_eof(&mut this.read_buf) {
+                    Ok(Some(frame)) => return Poll::Ready(Some(Ok((frame, peer)))),
+                    Ok(None) => {}
+                    Err(err) => {
+                        this.read_buf.clear();
+                        this.peer = None;
+                        return Poll::Ready(Some(Err(err)));
+                    }
+                }
+                this.read_buf.clear();
+                this.peer = None;
+            }
+
+            // Receive the next datagram.
+            this.read_buf.resize(MAX_DATAGRAM_SIZE, 0);
+            let buf = &mut this.read_buf;
+            let res = this
+                .socket
+                .borrow()
+                .watcher
+                .poll_read_with(cx, |inner| inner.recv_from(buf));
+
+            match res {
+                Poll::Ready(Ok((n, peer))) => {
+                    this.read_buf.truncate(n);
+                    this.peer = Some(peer);
+                }
+                Poll::Ready(Err(err)) => {
+                    this.read_buf.clear();
+                    return Poll::Ready(Some(Err(err)));
+                }
+                Poll::Pending => {
+                    this.read_buf.clear();
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+impl<T, C, Item> Sink<(Item, SocketAddr)> for UdpFramed<T, C>
+where
+    T: Borrow<UdpSocket> + Unpin,
+    C: Encoder<Item> + Unpin,
+{
+    type Error = io::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // Only one datagram is buffered at a time.
+        self.get_mut().poll_send(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, (item, target): (Item, SocketAddr)) -> io::Result<()> {
+        let this = self.get_mut();
+        debug_assert!(
+            this.target.is_none(),
+            "`start_send` called before `poll_ready`"
+        );
+        this.write_buf.clear();
+        this.codec.encode(item, &mut this.write_buf)?;
+        this.target = Some(target);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_send(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_send(cx)
+    }
+}
+
+impl<T: fmt::Debug, C: fmt::Debug> fmt::Debug for UdpFramed<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UdpFramed")
+            .field("socket", &self.socket)
+            .field("codec", &self.codec)
+            .field("peer", &self.peer)
+            .field("target", &self.target)
+            .finish()
+    }
+}
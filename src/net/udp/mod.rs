@@ -7,6 +7,14 @@ use crate::net::driver::Watcher;
 use crate::net::ToSocketAddrs;
 use crate::utils::Context as _;
 
+#[cfg(feature = "unstable")]
+pub use framed::UdpFramed;
+
+#[cfg(feature = "unstable")]
+mod framed;
+#[cfg(all(feature = "unstable", any(target_os = "linux", target_os = "android")))]
+mod recv_err;
+
 /// A UDP socket.
 ///
 /// After creating a `UdpSocket` by [`bind`]ing it to a socket address, data can be [sent to] and
@@ -102,7 +110,7 @@ impl UdpSocket {
     /// ```no_run
     /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
     /// #
-    ///	use async_std::net::UdpSocket;
+    /// use async_std::net::UdpSocket;
     ///
     /// let socket = UdpSocket::bind("127.0.0.1:0").await?;
     /// let addr = socket.local_addr()?;
@@ -205,9 +213,14 @@ impl UdpSocket {
     /// and receiving messages. Additionally, a filter will be applied to [`recv_from`] so that it
     /// only receives messages from that same address.
     ///
+    /// Connecting also makes errors reported by ICMP, such as the peer's port being unreachable,
+    /// fail the next call to [`recv`] instead of leaving it waiting for a reply that never comes.
+    /// On Linux, [`set_recv_err`] reports those errors for unconnected sockets too.
+    ///
     /// [`send`]: #method.send
     /// [`recv`]: #method.recv
     /// [`recv_from`]: #method.recv_from
+    /// [`set_recv_err`]: #method.set_recv_err
     ///
     /// # Examples
     ///
@@ -417,6 +430,80 @@ impl UdpSocket {
         self.watcher.get_ref().set_ttl(ttl)
     }
 
+    /// Gets the value of the `SO_ERROR` option on this socket, clearing it.
+    ///
+    /// This retrieves a pending error, such as one reported by ICMP, without receiving data.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.watcher.get_ref().take_error()
+    }
+
+    /// Gets the value of the `IP_RECVERR` option for this socket.
+    ///
+    /// For more information about this option, see [`set_recv_err`].
+    ///
+    /// This is only supported on Linux and Android.
+    ///
+    /// [`set_recv_err`]: #method.set_recv_err
+    #[cfg(all(feature = "unstable", any(target_os = "linux", target_os = "android")))]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn recv_err(&self) -> io::Result<bool> {
+        recv_err::get(self.as_raw_fd(), self.local_addr()?.is_ipv6())
+    }
+
+    /// Sets the value of the `IP_RECVERR` option for this socket, or `IPV6_RECVERR` for IPv6
+    /// sockets.
+    ///
+    /// When enabled, errors reported by ICMP fail the next receive even if the socket is not
+    /// connected, and are queued with the destination of the datagram that caused them, which
+    /// can be retrieved with [`recv_error`]. Queued errors count against the receive buffer, so
+    /// they should be taken off the queue once a receive has failed.
+    ///
+    /// This is only supported on Linux and Android.
+    ///
+    /// [`recv_error`]: #method.recv_error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0").await?;
+    /// socket.set_recv_err(true)?;
+    /// socket.send_to(b"ping", "127.0.0.1:7878").await?;
+    ///
+    /// let mut buf = vec![0u8; 1024];
+    /// if let Err(err) = socket.recv_from(&mut buf).await {
+    ///     if let Some((err, peer)) = socket.recv_error()? {
+    ///         println!("{} is unreachable: {}", peer, err);
+    ///     }
+    /// }
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(all(feature = "unstable", any(target_os = "linux", target_os = "android")))]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn set_recv_err(&self, on: bool) -> io::Result<()> {
+        recv_err::set(self.as_raw_fd(), self.local_addr()?.is_ipv6(), on)
+    }
+
+    /// Takes the oldest error off the error queue of the socket.
+    ///
+    /// Returns the error along with the destination of the datagram that caused it, or `None`
+    /// if no errors are queued. Errors are only queued while [`set_recv_err`] is enabled.
+    ///
+    /// This is only supported on Linux and Android.
+    ///
+    /// [`set_recv_err`]: #method.set_recv_err
+    #[cfg(all(feature = "unstable", any(target_os = "linux", target_os = "android")))]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn recv_error(&self) -> io::Result<Option<(io::Error, SocketAddr)>> {
+        recv_err::recv(self.as_raw_fd())
+    }
+
     /// Executes an operation of the `IP_ADD_MEMBERSHIP` type.
     ///
     /// This method specifies a new multicast group for this socket to join. The address must be
@@ -0,0 +1,111 @@
+use std::io;
+use std::mem;
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::os::unix::io::RawFd;
+
+/// Sets the `IP_RECVERR` or `IPV6_RECVERR` option, depending on the address family.
+pub(super) fn set(fd: RawFd, ipv6: bool, on: bool) -> io::Result<()> {
+    let (level, name) = option(ipv6);
+    let value = on as libc::c_int;
+    let res = unsafe {
+        libc::setsockopt(
+            fd,
+            level,
+            name,
+            &value as *const libc::c_int as *const libc::c_void,
+            mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    if res == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Gets the `IP_RECVERR` or `IPV6_RECVERR` option, depending on the address family.
+pub(super) fn get(fd: RawFd, ipv6: bool) -> io::Result<bool> {
+    let (level, name) = option(ipv6);
+    let mut value: libc::c_int = 0;
+    let mut len = mem::size_of::<libc::c_int>() as libc::socklen_t;
+    let res = unsafe {
+        libc::getsockopt(
+            fd,
+            level,
+            name,
+            &mut value as *mut libc::c_int as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if res == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(value != 0)
+}
+
+/// Takes the oldest error off the error queue of a socket without blocking.
+///
+/// Returns the error along with the destination of the datagram that caused it.
+pub(super) fn recv(fd: RawFd) -> io::Result<Option<(io::Error, SocketAddr)>> {
+    unsafe {
+        let mut addr: libc::sockaddr_storage = mem::zeroed();
+        // The ancillary data holds the extended error followed by the address of the offender.
+        let mut control = [0u64; 64];
+        let mut msg: libc::msghdr = mem::zeroed();
+        msg.msg_name = &mut addr as *mut libc::sockaddr_storage as *mut libc::c_void;
+        msg.msg_namelen = mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+        msg.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = mem::size_of_val(&control) as _;
+
+        if libc::recvmsg(fd, &mut msg, libc::MSG_ERRQUEUE | libc::MSG_DONTWAIT) == -1 {
+            let err = io::Error::last_os_error();
+            return match err.kind() {
+                io::ErrorKind::WouldBlock => Ok(None),
+                _ => Err(err),
+            };
+        }
+
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            let (level, name) = ((*cmsg).cmsg_level, (*cmsg).cmsg_type);
+            if (level, name) == option(false) || (level, name) == option(true) {
+                let ee = &*(libc::CMSG_DATA(cmsg) as *const libc::sock_extended_err);
+                let err = io::Error::from_raw_os_error(ee.ee_errno as i32);
+                let addr = to_socket_addr(&addr).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidData, "unknown address family")
+                })?;
+                return Ok(Some((err, addr)));
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+
+        Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "queued error without an extended error message",
+        ))
+    }
+}
+
+fn option(ipv6: bool) -> (libc::c_int, libc::c_int) {
+    if ipv6 {
+        (libc::SOL_IPV6, libc::IPV6_RECVERR)
+    } else {
+        (libc::SOL_IP, libc::IP_RECVERR)
+    }
+}
+
+fn to_socket_addr(addr: &libc::sockaddr_storage) -> Option<SocketAddr> {
+    match addr.ss_family as libc::c_int {
+        libc::AF_INET => {
+            let addr = unsafe { &*(addr as *const _ as *const libc::sockaddr_in) };
+            let ip = Ipv4Addr::from(u32::from_be(addr.sin_addr.s_addr));
+            Some(SocketAddrV4::new(ip, u16::from_be(addr.sin_port)).into())
+        }
+        libc::AF_INET6 => {
+            let addr = unsafe { &*(addr as *const _ as *const libc::sockaddr_in6) };
+            let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
+            let port = u16::from_be(addr.sin6_port);
+            Some(SocketAddrV6::new(ip, port, addr.sin6_flowinfo, addr.sin6_scope_id).into())
+        }
+        _ => None,
+    }
+}
@@ -58,14 +58,16 @@ impl UnixStream {
     pub async fn connect<P: AsRef<Path>>(path: P) -> io::Result<UnixStream> {
         let path = path.as_ref().to_owned();
 
-        spawn_blocking(move || {
+        let mio_stream = spawn_blocking(move || {
             let std_stream = std::os::unix::net::UnixStream::connect(path)?;
-            let mio_stream = mio_uds::UnixStream::from_stream(std_stream)?;
-            Ok(UnixStream {
-                watcher: Watcher::new(mio_stream),
-            })
+            mio_uds::UnixStream::from_stream(std_stream)
+        })
+        .await?;
+
+        // Register the stream on the current thread, in the reactor of its runtime.
+        Ok(UnixStream {
+            watcher: Watcher::new(mio_stream),
         })
-        .await
     }
 
     /// Creates an unnamed pair of connected sockets.
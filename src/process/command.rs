@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+use std::env;
+use std::ffi::{OsStr, OsString};
+use std::hash::BuildHasher;
+use std::process::Command;
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+use crate::io;
+
+/// Extension methods for [`std::process::Command`].
+///
+/// The standard library spawns children with `posix_spawn` where it can, which is much faster
+/// than forking a process with a large heap. The methods changing the environment keep that fast
+/// path. [`nice`], [`cpu_affinity`], and [`cgroup`] have to run code in the child before it
+/// executes the program, so commands using them fall back to forking.
+///
+/// [`std::process::Command`]: https://doc.rust-lang.org/std/process/struct.Command.html
+/// [`nice`]: #tymethod.nice
+/// [`cpu_affinity`]: #tymethod.cpu_affinity
+/// [`cgroup`]: #tymethod.cgroup
+pub trait CommandExt {
+    /// Clears the environment of the child, except for the given variables.
+    ///
+    /// Variables that have been set on the command keep their values, and the others keep the
+    /// values they have in the current process. This builds the minimal environment of a
+    /// sandboxed child without going through all variables of the current process.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::process::Command;
+    ///
+    /// use async_std::process::CommandExt;
+    ///
+    /// let mut command = Command::new("cargo");
+    /// command.env_clear_except(&["PATH", "HOME"]).arg("build");
+    /// ```
+    fn env_clear_except<I, K>(&mut self, keys: I) -> &mut Command
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<OsStr>;
+
+    /// Applies a set of changes to the environment of the child.
+    ///
+    /// Variables mapped to `Some` are set, and variables mapped to `None` are removed. A
+    /// `HashMap` describing the difference between two environments can be applied at once.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::collections::HashMap;
+    /// use std::process::Command;
+    ///
+    /// use async_std::process::CommandExt;
+    ///
+    /// let mut diff = HashMap::new();
+    /// diff.insert("RUST_LOG", Some("debug"));
+    /// diff.insert("RUST_BACKTRACE", None);
+    ///
+    /// let mut command = Command::new("server");
+    /// command.env_diff(&diff);
+    /// ```
+    fn env_diff<K, V, S>(&mut self, diff: &HashMap<K, Option<V>, S>) -> &mut Command
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+        S: BuildHasher;
+
+    /// Sets the niceness of the child.
+    ///
+    /// Higher values give the child a lower scheduling priority, so that batch jobs don't slow
+    /// down the work of the current process. Lowering the niceness below that of the current
+    /// process usually needs elevated privileges, and otherwise makes spawning fail.
+    ///
+    /// This is only supported on Linux and Android.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::process::Command;
+    ///
+    /// use async_std::process::CommandExt;
+    ///
+    /// let mut command = Command::new("backup");
+    /// command.nice(19).cpu_affinity(&[3]);
+    /// ```
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn nice(&mut self, niceness: i32) -> &mut Command;
+
+    /// Restricts the child to run on the given CPU cores.
+    ///
+    /// Spawning fails if a core doesn't exist.
+    ///
+    /// This is only supported on Linux and Android.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn cpu_affinity(&mut self, cpus: &[usize]) -> &mut Command;
+
+    /// Places the child into a cgroup.
+    ///
+    /// `path` is the directory of a cgroup v2, such as `/sys/fs/cgroup/workers`. The child
+    /// moves itself into the cgroup before running the program, so the program is subject to
+    /// the limits of the cgroup from its first instruction on, and none of its own children can
+    /// escape them. Spawning fails if the child can't be moved, for example because the cgroup
+    /// doesn't exist or the current user isn't allowed to manage it.
+    ///
+    /// This is only supported on Linux and Android.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::process::Command;
+    ///
+    /// use async_std::process::CommandExt;
+    ///
+    /// let mut command = Command::new("worker");
+    /// command.cgroup("/sys/fs/cgroup/workers");
+    /// ```
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn cgroup<P: AsRef<std::path::Path>>(&mut self, path: P) -> &mut Command;
+}
+
+impl CommandExt for Command {
+    fn env_clear_except<I, K>(&mut self, keys: I) -> &mut Command
+    where
+        I: IntoIterator<Item = K>,
+        K: AsRef<OsStr>,
+    {
+        let explicit: HashMap<OsString, Option<OsString>> = self
+            .get_envs()
+            .map(|(k, v)| (k.to_owned(), v.map(OsStr::to_owned)))
+            .collect();
+
+        let kept: Vec<(OsString, OsString)> = keys
+            .into_iter()
+            .filter_map(|key| {
+                let key = key.as_ref();
+                let value = match explicit.get(key) {
+                    Some(value) => value.clone(),
+                    None => env::var_os(key),
+                };
+                value.map(|value| (key.to_owned(), value))
+            })
+            .collect();
+
+        self.env_clear().envs(kept)
+    }
+
+    fn env_diff<K, V, S>(&mut self, diff: &HashMap<K, Option<V>, S>) -> &mut Command
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+        S: BuildHasher,
+    {
+        for (key, value) in diff {
+            match value {
+                Some(value) => self.env(key, value),
+                None => self.env_remove(key),
+            };
+        }
+        self
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn nice(&mut self, niceness: i32) -> &mut Command {
+        use std::os::unix::process::CommandExt as _;
+
+        // Safety: `setpriority` is async-signal-safe.
+        unsafe {
+            self.pre_exec(move || {
+                if libc::setpriority(libc::PRIO_PROCESS, 0, niceness) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            })
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn cpu_affinity(&mut self, cpus: &[usize]) -> &mut Command {
+        use std::mem;
+        use std::os::unix::process::CommandExt as _;
+
+        // The set is built before forking, where allocating is still allowed.
+        let mut set: libc::cpu_set_t = unsafe { mem::zeroed() };
+        let mut valid = true;
+        for &cpu in cpus {
+            if cpu < libc::CPU_SETSIZE as usize {
+                unsafe { libc::CPU_SET(cpu, &mut set) };
+            } else {
+                valid = false;
+            }
+        }
+
+        // Safety: `sched_setaffinity` is async-signal-safe.
+        unsafe {
+            self.pre_exec(move || {
+                if !valid {
+                    return Err(io::Error::from_raw_os_error(libc::EINVAL));
+                }
+                if libc::sched_setaffinity(0, mem::size_of::<libc::cpu_set_t>(), &set) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            })
+        }
+    }
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn cgroup<P: AsRef<std::path::Path>>(&mut self, path: P) -> &mut Command {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+        use std::os::unix::process::CommandExt as _;
+
+        // The path is prepared before forking, where allocating is still allowed.
+        let procs = path.as_ref().join("cgroup.procs");
+        let procs = CString::new(procs.as_os_str().as_bytes()).ok();
+
+        // Safety: `open`, `write`, and `close` are async-signal-safe.
+        unsafe {
+            self.pre_exec(move || {
+                let procs = match &procs {
+                    Some(procs) => procs,
+                    None => return Err(io::Error::from_raw_os_error(libc::EINVAL)),
+                };
+                let fd = libc::open(procs.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+                if fd == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+
+                // Writing 0 moves the writing process.
+                let res = libc::write(fd, b"0".as_ptr().cast(), 1);
+                let err = io::Error::last_os_error();
+                libc::close(fd);
+                if res == -1 {
+                    return Err(err);
+                }
+                Ok(())
+            })
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt;
+use std::os::unix::io::IntoRawFd;
+use std::pin::Pin;
+use std::process::{Child, ExitStatus};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::future;
+use crate::io;
+use crate::net::driver::Watcher;
+use crate::stream::Stream;
+use crate::sync::{channel, Receiver, Sender};
+use crate::task::{self, Context, JoinHandle, Poll};
+
+use super::unix::{self, Fd};
+
+/// The size of the chunks output is read in.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Extension methods for [`std::process::Child`].
+///
+/// [`std::process::Child`]: https://doc.rust-lang.org/std/process/struct.Child.html
+pub trait ChildExt {
+    /// Turns the child into a stream of its output and exit status.
+    ///
+    /// The stream yields the output of the child's piped stdout and stderr in the order it
+    /// arrives, each chunk along with the time it was read, and finally the child's exit
+    /// status. Handles that aren't piped are ignored.
+    ///
+    /// The pipes are read through the reactor, and on Linux the exit of the child is awaited
+    /// through a pidfd, so no thread is blocked. On older kernels and other systems, a blocking
+    /// thread waits for the exit of the child.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::process::{Command, Stdio};
+    ///
+    /// use async_std::prelude::*;
+    /// use async_std::process::{ChildEvent, ChildExt};
+    ///
+    /// let child = Command::new("make")
+    ///     .stdout(Stdio::piped())
+    ///     .stderr(Stdio::piped())
+    ///     .spawn()
+    ///     .unwrap();
+    ///
+    /// let mut events = child.events();
+    /// while let Some((at, event)) = events.next().await {
+    ///     match event {
+    ///         ChildEvent::Stdout(out) => print!("{:?} {}", at, String::from_utf8_lossy(&out)),
+    ///         ChildEvent::Stderr(err) => eprint!("{:?} {}", at, String::from_utf8_lossy(&err)),
+    ///         ChildEvent::Exited(status) => println!("exited with {}", status),
+    ///         ChildEvent::Error(err) => eprintln!("error: {}", err),
+    ///     }
+    /// }
+    /// #
+    /// # })
+    /// ```
+    fn events(self) -> ChildEvents;
+}
+
+impl ChildExt for Child {
+    fn events(mut self) -> ChildEvents {
+        let (sender, receiver) = channel(16);
+        let id = self.id();
+
+        let stdout = self
+            .stdout
+            .take()
+            .map(|out| task::spawn(read_chunks(out, sender.clone(), ChildEvent::Stdout)));
+        let stderr = self
+            .stderr
+            .take()
+            .map(|err| task::spawn(read_chunks(err, sender.clone(), ChildEvent::Stderr)));
+
+        let child = Arc::new(Mutex::new(self));
+        task::spawn(wait(child.clone(), stdout, stderr, sender));
+
+        ChildEvents {
+            receiver,
+            child,
+            id,
+        }
+    }
+}
+
+/// Something a child process did.
+#[derive(Debug)]
+pub enum ChildEvent {
+    /// The child wrote to its stdout.
+    Stdout(Vec<u8>),
+
+    /// The child wrote to its stderr.
+    Stderr(Vec<u8>),
+
+    /// The child has exited.
+    ///
+    /// This is the last event.
+    Exited(ExitStatus),
+
+    /// Reading the output of the child or waiting for it failed.
+    Error(io::Error),
+}
+
+/// A stream of the output and exit status of a child process.
+///
+/// This stream is created by the [`events`] method on [`ChildExt`]. See its documentation for
+/// more.
+///
+/// [`events`]: trait.ChildExt.html#tymethod.events
+/// [`ChildExt`]: trait.ChildExt.html
+pub struct ChildEvents {
+    receiver: Receiver<(Instant, ChildEvent)>,
+    child: Arc<Mutex<Child>>,
+    id: u32,
+}
+
+impl ChildEvents {
+    /// Returns the process ID of the child.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Kills the child.
+    ///
+    /// The stream still yields the rest of the output and the exit status.
+    pub fn kill(&self) -> io::Result<()> {
+        self.child.lock().unwrap().kill()
+    }
+}
+
+impl Stream for ChildEvents {
+    type Item = (Instant, ChildEvent);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl fmt::Debug for ChildEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChildEvents").field("id", &self.id).finish()
+    }
+}
+
+/// Reads a pipe of the child through the reactor, sending its contents in chunks.
+async fn read_chunks<R: IntoRawFd>(
+    pipe: R,
+    sender: Sender<(Instant, ChildEvent)>,
+    event: fn(Vec<u8>) -> ChildEvent,
+) {
+    let pipe = match Fd::nonblocking(pipe.into_raw_fd()) {
+        Ok(fd) => Watcher::new(fd),
+        Err(err) => {
+            sender.send((Instant::now(), ChildEvent::Error(err))).await;
+            return;
+        }
+    };
+
+    let mut buf = vec![0; CHUNK_SIZE];
+    loop {
+        let res = future::poll_fn(|cx| pipe.poll_read_with(cx, |fd| fd.read(&mut buf))).await;
+        let ev = match res {
+            Ok(0) => break,
+            Ok(n) => event(buf[..n].to_vec()),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => ChildEvent::Error(err),
+        };
+        let failed = matches!(ev, ChildEvent::Error(_));
+        sender.send((Instant::now(), ev)).await;
+        if failed {
+            break;
+        }
+    }
+}
+
+/// Sends the exit status of the child once all of its output has been sent.
+async fn wait(
+    child: Arc<Mutex<Child>>,
+    stdout: Option<JoinHandle<()>>,
+    stderr: Option<JoinHandle<()>>,
+    sender: Sender<(Instant, ChildEvent)>,
+) {
+    for reader in stdout.into_iter().chain(stderr) {
+        reader.await;
+    }
+
+    let ev = match exited(&child).await {
+        Ok(status) => ChildEvent::Exited(status),
+        Err(err) => ChildEvent::Error(err),
+    };
+    sender.send((Instant::now(), ev)).await;
+}
+
+/// Waits for the child to exit and reaps it.
+///
+/// The lock isn't held while waiting so that the child can still be killed.
+async fn exited(child: &Mutex<Child>) -> io::Result<ExitStatus> {
+    let pid = child.lock().unwrap().id();
+    unix::exited(pid, || child.lock().unwrap().try_wait()).await
+}
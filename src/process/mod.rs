@@ -3,12 +3,48 @@
 //! This module is mostly concerned with spawning and interacting with child processes, but it also
 //! provides abort and exit for terminating the current process.
 //!
+//! [`CommandExt`] adds methods for building the environment of a child to
+//! [`std::process::Command`]. A [`Supervisor`] keeps a set of child processes running,
+//! restarting them according to their [`RestartPolicy`] when they exit. On Unix, the output and
+//! exit status of a child can be consumed as a single stream of events with
+//! [`ChildExt::events`], and [`StdioExt`] connects the standard streams of a child to
+//! nonblocking pipes or to async handles such as a [`TcpStream`]. A [`ProcessBuilder`] spawns
+//! children with `posix_spawn` rather than by forking the current process.
+//!
 //! This is an async version of [`std::process`].
 //!
 //! [`std::process`]: https://doc.rust-lang.org/std/process/index.html
+//! [`CommandExt`]: trait.CommandExt.html
+//! [`std::process::Command`]: https://doc.rust-lang.org/std/process/struct.Command.html
+//! [`ChildExt::events`]: trait.ChildExt.html#tymethod.events
+//! [`Supervisor`]: struct.Supervisor.html
+//! [`RestartPolicy`]: enum.RestartPolicy.html
+//! [`StdioExt`]: trait.StdioExt.html
+//! [`ProcessBuilder`]: struct.ProcessBuilder.html
+//! [`TcpStream`]: ../net/struct.TcpStream.html
 
 // Re-export structs.
 pub use std::process::{ExitStatus, Output};
 
 // Re-export functions.
 pub use std::process::{abort, exit, id};
+
+pub use command::CommandExt;
+#[cfg(unix)]
+pub use events::{ChildEvent, ChildEvents, ChildExt};
+#[cfg(unix)]
+pub use spawn::{Process, ProcessBuilder, Redirect};
+#[cfg(unix)]
+pub use stdio::StdioExt;
+pub use supervisor::{ChildSpec, RestartPolicy, Supervised, Supervisor, SupervisorEvent};
+
+mod command;
+#[cfg(unix)]
+mod events;
+#[cfg(unix)]
+mod spawn;
+#[cfg(unix)]
+mod stdio;
+mod supervisor;
+#[cfg(unix)]
+mod unix;
@@ -0,0 +1,557 @@
+use std::collections::BTreeMap;
+#[cfg(target_os = "linux")]
+use std::ffi::CStr;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::os::unix::io::{FromRawFd, IntoRawFd};
+use std::os::unix::process::ExitStatusExt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::Mutex;
+
+use crate::io;
+use crate::os::unix::net::UnixStream;
+
+use super::unix;
+
+/// What a standard stream of a child spawned by a [`ProcessBuilder`] is connected to.
+///
+/// [`ProcessBuilder`]: struct.ProcessBuilder.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Redirect {
+    /// The child inherits the stream of the current process.
+    Inherit,
+
+    /// The stream is connected to `/dev/null`.
+    Null,
+
+    /// The stream is connected to a pipe whose parent end is registered with the reactor.
+    ///
+    /// The parent end is available in the [`stdin`], [`stdout`], or [`stderr`] field of the
+    /// [`Process`].
+    ///
+    /// [`stdin`]: struct.Process.html#structfield.stdin
+    /// [`stdout`]: struct.Process.html#structfield.stdout
+    /// [`stderr`]: struct.Process.html#structfield.stderr
+    /// [`Process`]: struct.Process.html
+    Piped,
+}
+
+/// A builder for spawning child processes with `posix_spawn`.
+///
+/// Forking a process copies its page tables, which gets slow for services with a large heap,
+/// and can fail under strict memory overcommit even though the child immediately executes
+/// another program. On Linux, children are spawned with `posix_spawn` instead, which doesn't
+/// copy the address space of the current process.
+///
+/// [`nice`], [`cpu_affinity`], [`cgroup`], and [`current_dir`] need code to run in the child
+/// before it executes the program, so setting any of them falls back to forking. So do other
+/// systems.
+///
+/// Unlike [`std::process::Command`], the program is looked up in the `PATH` of the current
+/// process, even if the environment of the child sets a different one.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::prelude::*;
+/// use async_std::process::{ProcessBuilder, Redirect};
+///
+/// let mut child = ProcessBuilder::new("date")
+///     .stdout(Redirect::Piped)
+///     .spawn()?;
+///
+/// let mut date = String::new();
+/// child.stdout.take().unwrap().read_to_string(&mut date).await?;
+/// child.status().await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+///
+/// [`nice`]: #method.nice
+/// [`cpu_affinity`]: #method.cpu_affinity
+/// [`cgroup`]: #method.cgroup
+/// [`current_dir`]: #method.current_dir
+/// [`std::process::Command`]: https://doc.rust-lang.org/std/process/struct.Command.html
+pub struct ProcessBuilder {
+    program: OsString,
+    args: Vec<OsString>,
+
+    /// Variables that are set or removed, on top of the inherited environment.
+    envs: BTreeMap<OsString, Option<OsString>>,
+
+    /// Set if the environment of the current process isn't inherited.
+    env_clear: bool,
+
+    current_dir: Option<PathBuf>,
+    stdio: [Redirect; 3],
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    nice: Option<i32>,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    cpu_affinity: Option<Vec<usize>>,
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    cgroup: Option<PathBuf>,
+}
+
+impl ProcessBuilder {
+    /// Creates a builder for running `program`.
+    ///
+    /// By default, the child inherits the environment, working directory, and standard streams
+    /// of the current process.
+    pub fn new<S: AsRef<OsStr>>(program: S) -> ProcessBuilder {
+        ProcessBuilder {
+            program: program.as_ref().to_owned(),
+            args: Vec::new(),
+            envs: BTreeMap::new(),
+            env_clear: false,
+            current_dir: None,
+            stdio: [Redirect::Inherit; 3],
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            nice: None,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            cpu_affinity: None,
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            cgroup: None,
+        }
+    }
+
+    /// Adds an argument to pass to the program.
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut ProcessBuilder {
+        self.args.push(arg.as_ref().to_owned());
+        self
+    }
+
+    /// Adds multiple arguments to pass to the program.
+    pub fn args<I, S>(&mut self, args: I) -> &mut ProcessBuilder
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        for arg in args {
+            self.arg(arg);
+        }
+        self
+    }
+
+    /// Sets an environment variable of the child.
+    pub fn env<K, V>(&mut self, key: K, value: V) -> &mut ProcessBuilder
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.envs
+            .insert(key.as_ref().to_owned(), Some(value.as_ref().to_owned()));
+        self
+    }
+
+    /// Removes an environment variable of the child.
+    pub fn env_remove<K: AsRef<OsStr>>(&mut self, key: K) -> &mut ProcessBuilder {
+        self.envs.insert(key.as_ref().to_owned(), None);
+        self
+    }
+
+    /// Clears the environment of the child, including the variables set so far.
+    pub fn env_clear(&mut self) -> &mut ProcessBuilder {
+        self.envs.clear();
+        self.env_clear = true;
+        self
+    }
+
+    /// Sets the working directory of the child.
+    ///
+    /// This falls back to forking.
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut ProcessBuilder {
+        self.current_dir = Some(dir.as_ref().to_owned());
+        self
+    }
+
+    /// Sets what the stdin of the child is connected to.
+    pub fn stdin(&mut self, redirect: Redirect) -> &mut ProcessBuilder {
+        self.stdio[0] = redirect;
+        self
+    }
+
+    /// Sets what the stdout of the child is connected to.
+    pub fn stdout(&mut self, redirect: Redirect) -> &mut ProcessBuilder {
+        self.stdio[1] = redirect;
+        self
+    }
+
+    /// Sets what the stderr of the child is connected to.
+    pub fn stderr(&mut self, redirect: Redirect) -> &mut ProcessBuilder {
+        self.stdio[2] = redirect;
+        self
+    }
+
+    /// Sets the niceness of the child.
+    ///
+    /// This falls back to forking. See [`CommandExt::nice`] for more.
+    ///
+    /// [`CommandExt::nice`]: trait.CommandExt.html#tymethod.nice
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn nice(&mut self, niceness: i32) -> &mut ProcessBuilder {
+        self.nice = Some(niceness);
+        self
+    }
+
+    /// Restricts the child to run on the given CPU cores.
+    ///
+    /// This falls back to forking. See [`CommandExt::cpu_affinity`] for more.
+    ///
+    /// [`CommandExt::cpu_affinity`]: trait.CommandExt.html#tymethod.cpu_affinity
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn cpu_affinity(&mut self, cpus: &[usize]) -> &mut ProcessBuilder {
+        self.cpu_affinity = Some(cpus.to_vec());
+        self
+    }
+
+    /// Places the child into a cgroup.
+    ///
+    /// This falls back to forking. See [`CommandExt::cgroup`] for more.
+    ///
+    /// [`CommandExt::cgroup`]: trait.CommandExt.html#tymethod.cgroup
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn cgroup<P: AsRef<Path>>(&mut self, path: P) -> &mut ProcessBuilder {
+        self.cgroup = Some(path.as_ref().to_owned());
+        self
+    }
+
+    /// Spawns the child.
+    pub fn spawn(&mut self) -> io::Result<Process> {
+        let mut parent = [None, None, None];
+        let mut child = [None, None, None];
+        for (i, redirect) in self.stdio.iter().enumerate() {
+            if *redirect == Redirect::Piped {
+                // A socket pair serves as a pipe in either direction.
+                let (p, c) = std::os::unix::net::UnixStream::pair()?;
+                parent[i] = Some(UnixStream::from(p));
+                child[i] = Some(c);
+            }
+        }
+
+        #[cfg(target_os = "linux")]
+        let pid = if self.needs_fork() {
+            self.fork(child)?
+        } else {
+            self.posix_spawn(&child)?
+        };
+        #[cfg(not(target_os = "linux"))]
+        let pid = self.fork(child)?;
+
+        let [stdin, stdout, stderr] = parent;
+        Ok(Process {
+            pid,
+            status: Mutex::new(None),
+            stdin,
+            stdout,
+            stderr,
+        })
+    }
+
+    /// Returns `true` if spawning the child needs code to run in it before it executes the
+    /// program.
+    #[cfg(target_os = "linux")]
+    fn needs_fork(&self) -> bool {
+        self.current_dir.is_some()
+            || self.nice.is_some()
+            || self.cpu_affinity.is_some()
+            || self.cgroup.is_some()
+    }
+
+    /// Spawns the child with `posix_spawnp`.
+    #[cfg(target_os = "linux")]
+    fn posix_spawn(&self, pipes: &[Option<std::os::unix::net::UnixStream>; 3]) -> io::Result<u32> {
+        use std::env;
+        use std::ffi::CString;
+        use std::os::unix::ffi::{OsStrExt, OsStringExt};
+        use std::os::unix::io::AsRawFd;
+        use std::ptr;
+
+        let cstring = |s: &[u8]| {
+            CString::new(s).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "nul byte found in provided data",
+                )
+            })
+        };
+
+        let program = cstring(self.program.as_bytes())?;
+        let mut args = vec![program.clone()];
+        for arg in &self.args {
+            args.push(cstring(arg.as_bytes())?);
+        }
+
+        let mut envs = BTreeMap::new();
+        if !self.env_clear {
+            envs.extend(env::vars_os().map(|(k, v)| (k, Some(v))));
+        }
+        envs.extend(self.envs.clone());
+        let mut vars = Vec::new();
+        for (key, value) in envs {
+            if let Some(value) = value {
+                let mut var = key.into_vec();
+                var.push(b'=');
+                var.extend(value.as_bytes());
+                vars.push(cstring(&var)?);
+            }
+        }
+
+        // Both lists are terminated by a null pointer.
+        let argv: Vec<_> = args
+            .iter()
+            .map(|a| a.as_ptr())
+            .chain(Some(ptr::null()))
+            .collect();
+        let envp: Vec<_> = vars
+            .iter()
+            .map(|v| v.as_ptr())
+            .chain(Some(ptr::null()))
+            .collect();
+
+        let mut actions = FileActions::new()?;
+        let attr = SpawnAttr::new()?;
+        let null = CString::new("/dev/null").unwrap();
+        for (fd, redirect) in self.stdio.iter().enumerate() {
+            let fd = fd as libc::c_int;
+            match (redirect, &pipes[fd as usize]) {
+                (Redirect::Piped, Some(pipe)) => actions.dup2(pipe.as_raw_fd(), fd)?,
+                (Redirect::Null, _) => {
+                    let flags = if fd == 0 {
+                        libc::O_RDONLY
+                    } else {
+                        libc::O_WRONLY
+                    };
+                    actions.open(fd, &null, flags)?;
+                }
+                _ => {}
+            }
+        }
+
+        let mut pid = 0;
+        let res = unsafe {
+            libc::posix_spawnp(
+                &mut pid,
+                program.as_ptr(),
+                &actions.0,
+                &attr.0,
+                argv.as_ptr() as *const *mut libc::c_char,
+                envp.as_ptr() as *const *mut libc::c_char,
+            )
+        };
+        check(res)?;
+        Ok(pid as u32)
+    }
+
+    /// Spawns the child with `std::process::Command`, which forks when it needs to run code in
+    /// the child.
+    fn fork(&self, pipes: [Option<std::os::unix::net::UnixStream>; 3]) -> io::Result<u32> {
+        let mut command = Command::new(&self.program);
+        command.args(&self.args);
+        if self.env_clear {
+            command.env_clear();
+        }
+        for (key, value) in &self.envs {
+            match value {
+                Some(value) => command.env(key, value),
+                None => command.env_remove(key),
+            };
+        }
+        if let Some(dir) = &self.current_dir {
+            command.current_dir(dir);
+        }
+
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            use super::CommandExt;
+
+            if let Some(niceness) = self.nice {
+                command.nice(niceness);
+            }
+            if let Some(cpus) = &self.cpu_affinity {
+                command.cpu_affinity(cpus);
+            }
+            if let Some(path) = &self.cgroup {
+                command.cgroup(path);
+            }
+        }
+
+        let [stdin, stdout, stderr] = pipes;
+        let stdio = |redirect, pipe: Option<std::os::unix::net::UnixStream>| match pipe {
+            Some(pipe) => unsafe { Stdio::from_raw_fd(pipe.into_raw_fd()) },
+            None if redirect == Redirect::Null => Stdio::null(),
+            None => Stdio::inherit(),
+        };
+        command.stdin(stdio(self.stdio[0], stdin));
+        command.stdout(stdio(self.stdio[1], stdout));
+        command.stderr(stdio(self.stdio[2], stderr));
+
+        // The child is reaped by the returned `Process`, since dropping it doesn't wait for it.
+        Ok(command.spawn()?.id())
+    }
+}
+
+impl fmt::Debug for ProcessBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProcessBuilder")
+            .field("program", &self.program)
+            .field("args", &self.args)
+            .finish()
+    }
+}
+
+/// A child process spawned by a [`ProcessBuilder`].
+///
+/// Like [`std::process::Child`], dropping a `Process` neither kills nor waits for the child.
+///
+/// [`ProcessBuilder`]: struct.ProcessBuilder.html
+/// [`std::process::Child`]: https://doc.rust-lang.org/std/process/struct.Child.html
+pub struct Process {
+    pid: u32,
+
+    /// The exit status, once the child has been reaped.
+    status: Mutex<Option<ExitStatus>>,
+
+    /// The parent end of the child's stdin, if it is piped.
+    pub stdin: Option<UnixStream>,
+
+    /// The parent end of the child's stdout, if it is piped.
+    pub stdout: Option<UnixStream>,
+
+    /// The parent end of the child's stderr, if it is piped.
+    pub stderr: Option<UnixStream>,
+}
+
+impl Process {
+    /// Returns the process ID of the child.
+    pub fn id(&self) -> u32 {
+        self.pid
+    }
+
+    /// Kills the child.
+    ///
+    /// Fails with an error of the `InvalidInput` kind if the child has already been reaped.
+    pub fn kill(&self) -> io::Result<()> {
+        // The lock keeps the child from being reaped, so its process ID can't be reused.
+        let status = self.status.lock().unwrap();
+        if status.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid argument: can't kill an exited process",
+            ));
+        }
+        unix::cvt(unsafe { libc::kill(self.pid as libc::pid_t, libc::SIGKILL) })?;
+        Ok(())
+    }
+
+    /// Returns the exit status of the child if it has exited, without waiting.
+    pub fn try_status(&self) -> io::Result<Option<ExitStatus>> {
+        let mut status = self.status.lock().unwrap();
+        if status.is_none() {
+            let mut raw = 0;
+            let pid = unix::cvt(unsafe {
+                libc::waitpid(self.pid as libc::pid_t, &mut raw, libc::WNOHANG)
+            })?;
+            if pid != 0 {
+                *status = Some(ExitStatus::from_raw(raw));
+            }
+        }
+        Ok(*status)
+    }
+
+    /// Waits for the child to exit and returns its exit status.
+    ///
+    /// On Linux, this waits through the reactor. On older kernels and other systems, a blocking
+    /// thread waits for the child.
+    pub async fn status(&self) -> io::Result<ExitStatus> {
+        unix::exited(self.pid, || self.try_status()).await
+    }
+}
+
+impl fmt::Debug for Process {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Process").field("pid", &self.pid).finish()
+    }
+}
+
+/// Turns the error number returned by a `posix_spawn` function into an error.
+#[cfg(target_os = "linux")]
+fn check(res: libc::c_int) -> io::Result<()> {
+    if res == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::from_raw_os_error(res))
+    }
+}
+
+/// The file actions of `posix_spawn`, which are destroyed when dropped.
+#[cfg(target_os = "linux")]
+struct FileActions(libc::posix_spawn_file_actions_t);
+
+#[cfg(target_os = "linux")]
+impl FileActions {
+    fn new() -> io::Result<FileActions> {
+        let mut actions = unsafe { std::mem::zeroed() };
+        check(unsafe { libc::posix_spawn_file_actions_init(&mut actions) })?;
+        Ok(FileActions(actions))
+    }
+
+    /// Makes `new` a copy of `old` in the child.
+    fn dup2(&mut self, old: libc::c_int, new: libc::c_int) -> io::Result<()> {
+        check(unsafe { libc::posix_spawn_file_actions_adddup2(&mut self.0, old, new) })
+    }
+
+    /// Opens `path` as `fd` in the child.
+    fn open(&mut self, fd: libc::c_int, path: &CStr, flags: libc::c_int) -> io::Result<()> {
+        check(unsafe {
+            libc::posix_spawn_file_actions_addopen(&mut self.0, fd, path.as_ptr(), flags, 0)
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for FileActions {
+    fn drop(&mut self) {
+        unsafe {
+            libc::posix_spawn_file_actions_destroy(&mut self.0);
+        }
+    }
+}
+
+/// The attributes of `posix_spawn`, which are destroyed when dropped.
+#[cfg(target_os = "linux")]
+struct SpawnAttr(libc::posix_spawnattr_t);
+
+#[cfg(target_os = "linux")]
+impl SpawnAttr {
+    /// Creates attributes that reset the signal mask and the handler of `SIGPIPE`, like forking
+    /// with `std::process::Command` does.
+    fn new() -> io::Result<SpawnAttr> {
+        let mut attr = SpawnAttr(unsafe { std::mem::zeroed() });
+        check(unsafe { libc::posix_spawnattr_init(&mut attr.0) })?;
+
+        unsafe {
+            let mut set = std::mem::zeroed();
+            libc::sigemptyset(&mut set);
+            check(libc::posix_spawnattr_setsigmask(&mut attr.0, &set))?;
+            libc::sigaddset(&mut set, libc::SIGPIPE);
+            check(libc::posix_spawnattr_setsigdefault(&mut attr.0, &set))?;
+
+            let flags = libc::POSIX_SPAWN_SETSIGMASK | libc::POSIX_SPAWN_SETSIGDEF;
+            check(libc::posix_spawnattr_setflags(&mut attr.0, flags as _))?;
+        }
+        Ok(attr)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for SpawnAttr {
+    fn drop(&mut self) {
+        unsafe {
+            libc::posix_spawnattr_destroy(&mut self.0);
+        }
+    }
+}
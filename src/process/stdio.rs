@@ -0,0 +1,84 @@
+use std::os::unix::io::{FromRawFd, IntoRawFd};
+use std::process::Stdio;
+
+use crate::io;
+use crate::os::unix::net::UnixStream;
+
+/// Extension methods for [`std::process::Stdio`].
+///
+/// These connect the standard streams of a child to handles the reactor can wait on, instead of
+/// the blocking pipes created by [`Stdio::piped`].
+///
+/// [`std::process::Stdio`]: https://doc.rust-lang.org/std/process/struct.Stdio.html
+/// [`Stdio::piped`]: https://doc.rust-lang.org/std/process/struct.Stdio.html#method.piped
+pub trait StdioExt: Sized {
+    /// Creates a pipe whose parent end is already registered with the reactor.
+    ///
+    /// Returns the stdio to pass to the child along with the parent end of the pipe, which can
+    /// be read from and written to without blocking a thread. The pipe is a socket pair, so the
+    /// same stream can serve as both the stdin and the stdout of the child.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use std::process::{Command, Stdio};
+    ///
+    /// use async_std::prelude::*;
+    /// use async_std::process::StdioExt;
+    ///
+    /// let (stdout, mut pipe) = Stdio::piped_nonblocking()?;
+    /// let mut child = Command::new("date").stdout(stdout).spawn()?;
+    ///
+    /// let mut date = String::new();
+    /// pipe.read_to_string(&mut date).await?;
+    /// child.wait()?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    fn piped_nonblocking() -> io::Result<(Self, UnixStream)>;
+
+    /// Passes an async handle to the child as one of its standard streams.
+    ///
+    /// The handle is taken out of the reactor and switched back to blocking mode, since the
+    /// child shares its mode with the current process. This allows inetd-style servers to hand
+    /// an accepted connection directly to the program serving it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use std::process::{Command, Stdio};
+    ///
+    /// use async_std::net::TcpListener;
+    /// use async_std::process::StdioExt;
+    ///
+    /// let listener = TcpListener::bind("127.0.0.1:8080").await?;
+    /// let (stream, _) = listener.accept().await?;
+    ///
+    /// Command::new("fortune")
+    ///     .stdout(Stdio::from_async(stream)?)
+    ///     .spawn()?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    fn from_async<T: IntoRawFd>(io: T) -> io::Result<Self>;
+}
+
+impl StdioExt for Stdio {
+    fn piped_nonblocking() -> io::Result<(Stdio, UnixStream)> {
+        let (parent, child) = UnixStream::pair()?;
+        Ok((Stdio::from_async(child)?, parent))
+    }
+
+    fn from_async<T: IntoRawFd>(io: T) -> io::Result<Stdio> {
+        let fd = io.into_raw_fd();
+
+        // Setting the mode works the same way for any kind of file descriptor.
+        let stream = unsafe { std::os::unix::net::UnixStream::from_raw_fd(fd) };
+        stream.set_nonblocking(false)?;
+        Ok(unsafe { Stdio::from_raw_fd(stream.into_raw_fd()) })
+    }
+}
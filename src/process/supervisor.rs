@@ -0,0 +1,426 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::process::{Child, Command, ExitStatus};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::future::{self, Backoff};
+use crate::io;
+use crate::stream::Stream;
+use crate::sync::{channel, CancellationToken, DropGuard, Receiver, Sender};
+use crate::task::{self, Context, JoinHandle, Poll};
+
+/// How often running children are checked for having exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// The number of events buffered for a [`Supervised`] stream before new ones are dropped.
+///
+/// [`Supervised`]: struct.Supervised.html
+const EVENT_CAPACITY: usize = 1024;
+
+/// When a supervised child is restarted after it exits.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RestartPolicy {
+    /// The child is never restarted.
+    Never,
+
+    /// The child is restarted if it exits unsuccessfully, or is killed.
+    OnFailure,
+
+    /// The child is always restarted.
+    Always,
+}
+
+type HealthCheck = Arc<dyn Fn(u32) -> Pin<Box<dyn Future<Output = bool> + Send>> + Send + Sync>;
+
+/// The configuration of a child process run by a [`Supervisor`].
+///
+/// By default, the child is restarted when it fails, with a delay starting at 100 milliseconds
+/// and doubling with every restart up to 30 seconds, as many times as needed.
+///
+/// [`Supervisor`]: struct.Supervisor.html
+pub struct ChildSpec {
+    name: String,
+    command: Command,
+    policy: RestartPolicy,
+    backoff: Backoff,
+    max_restarts: Option<u32>,
+    health_check: Option<(Duration, HealthCheck)>,
+}
+
+impl ChildSpec {
+    /// Creates the configuration of a child called `name`, started by running `command`.
+    pub fn new(name: impl Into<String>, command: Command) -> ChildSpec {
+        ChildSpec {
+            name: name.into(),
+            command,
+            policy: RestartPolicy::OnFailure,
+            backoff: Backoff::exponential(Duration::from_millis(100))
+                .max_delay(Duration::from_secs(30)),
+            max_restarts: None,
+            health_check: None,
+        }
+    }
+
+    /// Sets when the child is restarted.
+    pub fn restart(mut self, policy: RestartPolicy) -> ChildSpec {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets how long to wait before restarting the child.
+    ///
+    /// The delay grows with every restart, and starts over once the child has kept running
+    /// for longer than the longest delay of the backoff. The maximum number of attempts of the
+    /// backoff is ignored in favor of [`max_restarts`].
+    ///
+    /// [`max_restarts`]: #method.max_restarts
+    pub fn backoff(mut self, backoff: Backoff) -> ChildSpec {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets how many times in total the child may be restarted before the supervisor gives up
+    /// on it.
+    pub fn max_restarts(mut self, max_restarts: u32) -> ChildSpec {
+        self.max_restarts = Some(max_restarts);
+        self
+    }
+
+    /// Sets a health check run with the child's process ID every `interval`.
+    ///
+    /// If the check returns `false`, the child is killed, and restarted according to the
+    /// restart policy.
+    pub fn health_check<F, Fut>(mut self, interval: Duration, check: F) -> ChildSpec
+    where
+        F: Fn(u32) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = bool> + Send + 'static,
+    {
+        let check: HealthCheck = Arc::new(move |pid| Box::pin(check(pid)));
+        self.health_check = Some((interval, check));
+        self
+    }
+}
+
+impl fmt::Debug for ChildSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChildSpec")
+            .field("name", &self.name)
+            .field("command", &self.command)
+            .field("policy", &self.policy)
+            .field("max_restarts", &self.max_restarts)
+            .finish()
+    }
+}
+
+/// Something that happened to a supervised child.
+#[derive(Debug)]
+pub enum SupervisorEvent {
+    /// The child has been started.
+    Started {
+        /// The name of the child.
+        name: String,
+        /// The process ID of the child.
+        pid: u32,
+    },
+
+    /// The child could not be started, or waiting for it to exit failed.
+    ///
+    /// This counts as a failure for the restart policy.
+    Error {
+        /// The name of the child.
+        name: String,
+        /// The error returned by the operating system.
+        error: io::Error,
+    },
+
+    /// The child has failed its health check and is being killed.
+    Unhealthy {
+        /// The name of the child.
+        name: String,
+        /// The process ID of the child.
+        pid: u32,
+    },
+
+    /// The child has exited.
+    Exited {
+        /// The name of the child.
+        name: String,
+        /// The exit status of the child.
+        status: ExitStatus,
+    },
+
+    /// The child is going to be restarted after a delay.
+    Restarting {
+        /// The name of the child.
+        name: String,
+        /// The number of times the child has been restarted, including this time.
+        restarts: u32,
+        /// How long the supervisor waits before restarting the child.
+        delay: Duration,
+    },
+
+    /// The child won't be started again.
+    Stopped {
+        /// The name of the child.
+        name: String,
+    },
+}
+
+impl SupervisorEvent {
+    /// Returns the name of the child the event is about.
+    pub fn name(&self) -> &str {
+        match self {
+            SupervisorEvent::Started { name, .. }
+            | SupervisorEvent::Error { name, .. }
+            | SupervisorEvent::Unhealthy { name, .. }
+            | SupervisorEvent::Exited { name, .. }
+            | SupervisorEvent::Restarting { name, .. }
+            | SupervisorEvent::Stopped { name } => name,
+        }
+    }
+}
+
+/// Runs a set of child processes, restarting them when they exit.
+///
+/// Each child is configured by a [`ChildSpec`], which decides when it is restarted, how long
+/// to wait before restarting it, and how its health is checked. Once started, the supervisor
+/// becomes a [`Supervised`] stream of [`SupervisorEvent`]s reporting every start and exit.
+///
+/// [`ChildSpec`]: struct.ChildSpec.html
+/// [`Supervised`]: struct.Supervised.html
+/// [`SupervisorEvent`]: enum.SupervisorEvent.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # async_std::task::block_on(async {
+/// #
+/// use std::process::Command;
+///
+/// use async_std::prelude::*;
+/// use async_std::process::{ChildSpec, RestartPolicy, Supervisor};
+///
+/// let mut worker = Command::new("worker");
+/// worker.arg("--queue").arg("jobs");
+///
+/// let mut supervised = Supervisor::new()
+///     .child(ChildSpec::new("worker", worker).restart(RestartPolicy::Always))
+///     .start();
+///
+/// while let Some(event) = supervised.next().await {
+///     println!("{:?}", event);
+/// }
+/// #
+/// # })
+/// ```
+#[derive(Debug, Default)]
+pub struct Supervisor {
+    specs: Vec<ChildSpec>,
+}
+
+impl Supervisor {
+    /// Creates a supervisor without children.
+    pub fn new() -> Supervisor {
+        Supervisor::default()
+    }
+
+    /// Adds a child to the supervisor.
+    pub fn child(mut self, spec: ChildSpec) -> Supervisor {
+        self.specs.push(spec);
+        self
+    }
+
+    /// Starts all children, each supervised by its own task.
+    pub fn start(self) -> Supervised {
+        let (sender, events) = channel(EVENT_CAPACITY);
+        let token = CancellationToken::new();
+        let tasks = self
+            .specs
+            .into_iter()
+            .map(|spec| task::spawn(supervise(spec, sender.clone(), token.clone())))
+            .collect();
+
+        Supervised {
+            events,
+            tasks,
+            _guard: token.drop_guard(),
+        }
+    }
+}
+
+/// A running [`Supervisor`].
+///
+/// This is a stream of the events of all children, which ends once none of them is going to be
+/// started again. Events are buffered, and dropped if too many of them pile up before they are
+/// consumed.
+///
+/// Dropping it kills all children.
+///
+/// [`Supervisor`]: struct.Supervisor.html
+pub struct Supervised {
+    events: Receiver<SupervisorEvent>,
+    tasks: Vec<JoinHandle<()>>,
+    _guard: DropGuard,
+}
+
+impl Supervised {
+    /// Kills all children and waits until they have exited.
+    pub async fn shutdown(self) {
+        let Supervised { tasks, _guard, .. } = self;
+        drop(_guard);
+        for task in tasks {
+            task.await;
+        }
+    }
+}
+
+impl Stream for Supervised {
+    type Item = SupervisorEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.events).poll_next(cx)
+    }
+}
+
+impl fmt::Debug for Supervised {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Supervised")
+            .field("children", &self.tasks.len())
+            .finish()
+    }
+}
+
+/// Runs a child until it isn't going to be restarted anymore, or the token is cancelled.
+async fn supervise(mut spec: ChildSpec, events: Sender<SupervisorEvent>, token: CancellationToken) {
+    let emit = |event| {
+        // Supervision doesn't wait for slow consumers.
+        if let Ok(permit) = events.try_reserve() {
+            permit.send(event);
+        }
+    };
+    let name = spec.name.clone();
+    let mut restarts = 0;
+    let mut failures = 0;
+
+    loop {
+        let started = Instant::now();
+        let (command, res) = {
+            let mut command = spec.command;
+            task::spawn_blocking(move || {
+                let res = command.spawn();
+                (command, res)
+            })
+            .await
+        };
+        spec.command = command;
+
+        let failed = match res {
+            Ok(child) => {
+                let pid = child.id();
+                emit(SupervisorEvent::Started {
+                    name: name.clone(),
+                    pid,
+                });
+                match run(child, &spec, &token, &emit).await {
+                    Ok(status) => {
+                        emit(SupervisorEvent::Exited {
+                            name: name.clone(),
+                            status,
+                        });
+                        !status.success()
+                    }
+                    Err(error) => {
+                        emit(SupervisorEvent::Error {
+                            name: name.clone(),
+                            error,
+                        });
+                        true
+                    }
+                }
+            }
+            Err(error) => {
+                emit(SupervisorEvent::Error {
+                    name: name.clone(),
+                    error,
+                });
+                true
+            }
+        };
+
+        let restart = match spec.policy {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => failed,
+            RestartPolicy::Always => true,
+        };
+        if !restart
+            || token.is_cancelled()
+            || matches!(spec.max_restarts, Some(max) if restarts >= max)
+        {
+            break;
+        }
+
+        // A child that kept running for a while starts over with the shortest delay.
+        if started.elapsed() > spec.backoff.longest_delay() {
+            failures = 0;
+        }
+        failures += 1;
+        restarts += 1;
+
+        let delay = spec.backoff.jittered_delay(failures);
+        emit(SupervisorEvent::Restarting {
+            name: name.clone(),
+            restarts,
+            delay,
+        });
+        if future::timeout(delay, token.cancelled()).await.is_ok() {
+            break;
+        }
+    }
+
+    emit(SupervisorEvent::Stopped { name });
+}
+
+/// Waits for a child to exit, running its health checks and killing it once the token is
+/// cancelled.
+async fn run(
+    mut child: Child,
+    spec: &ChildSpec,
+    token: &CancellationToken,
+    emit: &impl Fn(SupervisorEvent),
+) -> io::Result<ExitStatus> {
+    let mut next_check = spec
+        .health_check
+        .as_ref()
+        .map(|(interval, _)| Instant::now() + *interval);
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+
+        let cancelled = future::timeout(POLL_INTERVAL, token.cancelled()).await;
+        let mut kill = cancelled.is_ok();
+
+        if let (Some(when), Some((interval, check))) = (next_check, &spec.health_check) {
+            if !kill && Instant::now() >= when {
+                if check(child.id()).await {
+                    next_check = Some(Instant::now() + *interval);
+                } else {
+                    emit(SupervisorEvent::Unhealthy {
+                        name: spec.name.clone(),
+                        pid: child.id(),
+                    });
+                    kill = true;
+                }
+            }
+        }
+
+        if kill {
+            // The child may have exited in the meantime.
+            let _ = child.kill();
+            return task::spawn_blocking(move || child.wait()).await;
+        }
+    }
+}
@@ -0,0 +1,135 @@
+//! Waiting on child processes and their pipes through the reactor.
+
+use std::os::unix::io::RawFd;
+use std::process::ExitStatus;
+
+use mio::unix::EventedFd;
+use mio::{Evented, PollOpt, Ready, Token};
+
+#[cfg(target_os = "linux")]
+use crate::future;
+use crate::io;
+#[cfg(target_os = "linux")]
+use crate::net::driver::Watcher;
+use crate::task;
+
+/// Waits for the child with the given process ID to exit, and reaps it with `try_wait`.
+///
+/// `try_wait` is called whenever the child may have exited. On Linux, this is when a pidfd
+/// registered with the reactor becomes readable. On older kernels and other systems, a blocking
+/// thread waits for the child to exit without reaping it, so that it can still be killed in the
+/// meantime.
+pub(super) async fn exited<F>(pid: u32, mut try_wait: F) -> io::Result<ExitStatus>
+where
+    F: FnMut() -> io::Result<Option<ExitStatus>>,
+{
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(pidfd) = Fd::pidfd(pid) {
+            let watcher = Watcher::new(pidfd);
+
+            // The pidfd becomes readable once the child has exited.
+            return future::poll_fn(|cx| {
+                watcher.poll_read_with(cx, |_| match try_wait()? {
+                    Some(status) => Ok(status),
+                    None => Err(io::ErrorKind::WouldBlock.into()),
+                })
+            })
+            .await;
+        }
+    }
+
+    loop {
+        if let Some(status) = try_wait()? {
+            return Ok(status);
+        }
+        task::spawn_blocking(move || wait_exited(pid)).await?;
+    }
+}
+
+/// Blocks until the process with the given ID has exited, without reaping it.
+fn wait_exited(pid: u32) -> io::Result<()> {
+    loop {
+        let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+        let flags = libc::WEXITED | libc::WNOWAIT;
+        if unsafe { libc::waitid(libc::P_PID, pid as libc::id_t, &mut info, flags) } == 0 {
+            return Ok(());
+        }
+
+        let err = io::Error::last_os_error();
+        if err.kind() != io::ErrorKind::Interrupted {
+            return Err(err);
+        }
+    }
+}
+
+/// An owned file descriptor that can be registered with the reactor.
+pub(super) struct Fd(RawFd);
+
+impl Fd {
+    /// Takes ownership of a file descriptor and puts it into nonblocking mode.
+    pub(super) fn nonblocking(fd: RawFd) -> io::Result<Fd> {
+        let fd = Fd(fd);
+        let flags = cvt(unsafe { libc::fcntl(fd.0, libc::F_GETFL) })?;
+        cvt(unsafe { libc::fcntl(fd.0, libc::F_SETFL, flags | libc::O_NONBLOCK) })?;
+        Ok(fd)
+    }
+
+    /// Opens a pidfd for a child of the current process, which becomes readable when it exits.
+    ///
+    /// This fails on kernels older than 5.3.
+    #[cfg(target_os = "linux")]
+    fn pidfd(pid: u32) -> io::Result<Fd> {
+        let fd = cvt(unsafe { libc::syscall(libc::SYS_pidfd_open, pid as libc::pid_t, 0) })?;
+        Ok(Fd(fd as RawFd))
+    }
+
+    /// Reads from the file descriptor.
+    pub(super) fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = cvt(unsafe { libc::read(self.0, buf.as_mut_ptr().cast(), buf.len()) })?;
+        Ok(n as usize)
+    }
+}
+
+impl Evented for Fd {
+    fn register(
+        &self,
+        poll: &mio::Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &mio::Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+        EventedFd(&self.0).deregister(poll)
+    }
+}
+
+impl Drop for Fd {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
+
+/// Turns the `-1` returned by a failed system call into the last OS error.
+pub(super) fn cvt<T: Default + PartialOrd>(res: T) -> io::Result<T> {
+    if res < T::default() {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(res)
+    }
+}
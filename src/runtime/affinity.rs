@@ -0,0 +1,69 @@
+use std::thread::JoinHandle;
+
+use crate::io;
+
+/// A policy for pinning worker threads to CPU cores.
+///
+/// Pinning keeps the caches of a core warm for the worker thread running on it, which can lower
+/// the latency of latency-sensitive services. It is only supported on Linux and Android.
+///
+/// See [`Builder::worker_affinity`].
+///
+/// [`Builder::worker_affinity`]: struct.Builder.html#method.worker_affinity
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Affinity {
+    /// Pins the `i`-th worker thread to the `i`-th CPU core, wrapping around if there are more
+    /// worker threads than cores.
+    PerCore,
+
+    /// Pins the `i`-th worker thread to the `i`-th CPU core in the list, wrapping around if there
+    /// are more worker threads than listed cores.
+    Cpus(Vec<usize>),
+}
+
+impl Affinity {
+    /// Returns the CPU core the `index`-th worker thread is pinned to.
+    pub(crate) fn cpu(&self, index: usize) -> usize {
+        match self {
+            Affinity::PerCore => index % num_cpus::get().max(1),
+            Affinity::Cpus(cpus) => cpus[index % cpus.len()],
+        }
+    }
+}
+
+/// Pins a thread to a CPU core.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn pin<T>(handle: &JoinHandle<T>, cpu: usize) -> io::Result<()> {
+    use std::mem;
+    use std::os::unix::thread::JoinHandleExt;
+
+    if cpu >= libc::CPU_SETSIZE as usize {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("CPU core {} is out of range", cpu),
+        ));
+    }
+
+    unsafe {
+        let mut set: libc::cpu_set_t = mem::zeroed();
+        libc::CPU_SET(cpu, &mut set);
+        match libc::pthread_setaffinity_np(
+            handle.as_pthread_t(),
+            mem::size_of::<libc::cpu_set_t>(),
+            &set,
+        ) {
+            0 => Ok(()),
+            err => Err(io::Error::from_raw_os_error(err)),
+        }
+    }
+}
+
+/// Pins a thread to a CPU core.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) fn pin<T>(_: &JoinHandle<T>, _: usize) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Other,
+        "pinning threads to CPU cores is not supported on this platform",
+    ))
+}
@@ -0,0 +1,451 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::io;
+use crate::runtime::{Affinity, Runtime};
+use crate::task::executor::{Config, Pool};
+
+/// Builds a [`Runtime`] with custom settings.
+///
+/// [`Runtime`]: struct.Runtime.html
+///
+/// # Examples
+///
+/// ```
+/// use async_std::runtime::Builder;
+///
+/// let rt = Builder::new()
+///     .worker_threads(2)
+///     .thread_name("my-worker")
+///     .build()
+///     .unwrap();
+///
+/// let name = rt.block_on(rt.spawn(async {
+///     std::thread::current().name().map(String::from)
+/// }));
+/// assert_eq!(name.as_deref(), Some("my-worker"));
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Builder {
+    config: Config,
+    current_thread: bool,
+}
+
+impl Builder {
+    /// Creates a builder for a runtime with a pool of worker threads.
+    ///
+    /// By default, there is one worker thread per CPU core.
+    pub fn new() -> Builder {
+        Builder {
+            config: Config::default(),
+            current_thread: false,
+        }
+    }
+
+    /// Creates a builder for a runtime without worker threads.
+    ///
+    /// The tasks of a current-thread runtime only run while [`Runtime::block_on`] is being
+    /// executed, and they run on the thread calling it. This keeps the footprint low and makes
+    /// the order in which tasks run deterministic, which is useful for small command-line tools
+    /// and tests.
+    ///
+    /// There are no background threads for I/O and timers either: when no task is ready to run,
+    /// the thread blocking on the runtime polls the runtime's own reactor, waiting until an I/O
+    /// event comes in, the next timer is due, or a task is woken up. Sockets and timers created
+    /// inside the runtime are therefore only driven while the runtime is being blocked on.
+    ///
+    /// The settings of worker threads have no effect on a current-thread runtime.
+    ///
+    /// [`Runtime::block_on`]: struct.Runtime.html#method.block_on
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::runtime::Builder;
+    /// use async_std::task;
+    ///
+    /// let rt = Builder::new_current_thread().build().unwrap();
+    ///
+    /// let thread = std::thread::current().id();
+    /// let same = rt.block_on(async move {
+    ///     task::spawn(async move { std::thread::current().id() == thread }).await
+    /// });
+    /// assert!(same);
+    /// ```
+    pub fn new_current_thread() -> Builder {
+        Builder {
+            config: Config::default(),
+            current_thread: true,
+        }
+    }
+
+    /// Configures the number of worker threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn worker_threads(mut self, n: usize) -> Builder {
+        assert!(n > 0, "a runtime needs at least one worker thread");
+        self.config.num_threads = n;
+        self
+    }
+
+    /// Configures the name of worker threads.
+    pub fn thread_name(mut self, name: impl Into<String>) -> Builder {
+        self.config.thread_name = name.into();
+        self
+    }
+
+    /// Configures the stack size of worker threads in bytes.
+    pub fn thread_stack_size(mut self, size: usize) -> Builder {
+        self.config.stack_size = Some(size);
+        self
+    }
+
+    /// Configures a hook called on every worker thread when it starts.
+    ///
+    /// The hook runs on the worker thread before it runs any task, which makes it a good place to
+    /// set up thread-local state such as allocator arenas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use std::sync::Arc;
+    ///
+    /// use async_std::runtime::Builder;
+    ///
+    /// let started = Arc::new(AtomicUsize::new(0));
+    /// let rt = Builder::new()
+    ///     .worker_threads(3)
+    ///     .on_thread_start({
+    ///         let started = started.clone();
+    ///         move || {
+    ///             started.fetch_add(1, Ordering::SeqCst);
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// rt.block_on(rt.spawn(async {}));
+    /// # while started.load(Ordering::SeqCst) < 3 { std::thread::yield_now() }
+    /// assert_eq!(started.load(Ordering::SeqCst), 3);
+    /// ```
+    pub fn on_thread_start<F>(mut self, f: F) -> Builder
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.config.on_thread_start = Some(Arc::new(f));
+        self
+    }
+
+    /// Configures a hook called on every worker thread when it stops.
+    ///
+    /// Worker threads stop when the runtime is shut down, and when a worker thread hands its work
+    /// over to a new thread in [`task::block_in_place`].
+    ///
+    /// [`task::block_in_place`]: ../task/fn.block_in_place.html
+    pub fn on_thread_stop<F>(mut self, f: F) -> Builder
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.config.on_thread_stop = Some(Arc::new(f));
+        self
+    }
+
+    /// Configures whether idle worker threads steal tasks from other worker threads.
+    ///
+    /// Work stealing is enabled by default and spreads load evenly across worker threads. With
+    /// work stealing disabled, a task woken by a worker thread stays on that thread, which keeps
+    /// its data in the thread's caches but lets tasks wait behind a busy thread while others idle.
+    ///
+    /// Tasks spawned or woken from outside of the worker threads are still shared through a
+    /// global queue.
+    pub fn work_stealing(mut self, enabled: bool) -> Builder {
+        self.config.work_stealing = enabled;
+        self
+    }
+
+    /// Configures whether a task woken by a worker thread is run next by that thread.
+    ///
+    /// This slot is enabled by default and speeds up message passing between tasks, since the
+    /// receiving task runs right after the sending task yields. With the slot disabled, woken
+    /// tasks are run after the tasks already queued on the worker thread.
+    pub fn lifo_slot(mut self, enabled: bool) -> Builder {
+        self.config.lifo_slot = enabled;
+        self
+    }
+
+    /// Configures whether tasks run in the order they were scheduled.
+    ///
+    /// With FIFO scheduling, all tasks go through a single global queue and worker threads take
+    /// them one at a time, which gives predictable ordering and fairness at the expense of
+    /// throughput. It is disabled by default.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use async_std::runtime::Builder;
+    /// use async_std::task;
+    ///
+    /// let rt = Builder::new()
+    ///     .worker_threads(1)
+    ///     .fifo_scheduling(true)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let order = Arc::new(Mutex::new(Vec::new()));
+    /// let handles = rt.block_on(rt.spawn({
+    ///     let order = order.clone();
+    ///     async move {
+    ///         (0..3)
+    ///             .map(|i| {
+    ///                 let order = order.clone();
+    ///                 task::spawn(async move { order.lock().unwrap().push(i) })
+    ///             })
+    ///             .collect::<Vec<_>>()
+    ///     }
+    /// }));
+    /// for handle in handles {
+    ///     rt.block_on(handle);
+    /// }
+    /// assert_eq!(*order.lock().unwrap(), [0, 1, 2]);
+    /// ```
+    pub fn fifo_scheduling(mut self, enabled: bool) -> Builder {
+        self.config.fifo = enabled;
+        self
+    }
+
+    /// Configures whether TCP sockets created inside the runtime use io_uring.
+    ///
+    /// With io_uring, accepting connections and reading from and writing to TCP streams are
+    /// completion-based operations submitted to the kernel instead of readiness-based
+    /// operations driven by epoll, which avoids a system call per operation in proxy-style
+    /// workloads. Writes are buffered: a write completes once its bytes have been handed to the
+    /// kernel, and errors are reported by the next write or flush.
+    ///
+    /// Sockets use io_uring if they are bound, connected, or accepted from a listener using
+    /// io_uring while running inside the runtime. If io_uring isn't available, because the system
+    /// isn't Linux or the kernel doesn't support it, the runtime falls back to epoll.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::net::{TcpListener, TcpStream};
+    /// use async_std::prelude::*;
+    /// use async_std::runtime::Builder;
+    ///
+    /// let rt = Builder::new().io_uring(true).build().unwrap();
+    ///
+    /// rt.block_on(async {
+    ///     let listener = TcpListener::bind("127.0.0.1:0").await?;
+    ///     let mut client = TcpStream::connect(listener.local_addr()?).await?;
+    ///     let (mut server, _) = listener.accept().await?;
+    ///
+    ///     client.write_all(b"hello").await?;
+    ///     let mut buf = [0; 5];
+    ///     server.read_exact(&mut buf).await?;
+    ///     assert_eq!(&buf, b"hello");
+    ///     std::io::Result::Ok(())
+    /// })
+    /// .unwrap();
+    /// ```
+    #[cfg(feature = "io-uring")]
+    #[cfg_attr(feature = "docs", doc(cfg(feature = "io-uring")))]
+    pub fn io_uring(mut self, enabled: bool) -> Builder {
+        self.config.io_uring = enabled;
+        self
+    }
+
+    /// Configures a policy for pinning worker threads to CPU cores.
+    ///
+    /// By default, worker threads are not pinned. Pinning is only supported on Linux and Android;
+    /// on other platforms, and if a worker thread can't be pinned, [`build`] returns an error.
+    ///
+    /// Threads replacing a worker thread in [`task::block_in_place`] are pinned to the same core.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the policy is [`Affinity::Cpus`] with an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::runtime::{Affinity, Builder};
+    ///
+    /// let rt = Builder::new()
+    ///     .worker_threads(2)
+    ///     .worker_affinity(Affinity::Cpus(vec![2, 3]))
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    ///
+    /// [`build`]: #method.build
+    /// [`task::block_in_place`]: ../task/fn.block_in_place.html
+    /// [`Affinity::Cpus`]: enum.Affinity.html#variant.Cpus
+    pub fn worker_affinity(mut self, affinity: Affinity) -> Builder {
+        if let Affinity::Cpus(cpus) = &affinity {
+            assert!(!cpus.is_empty(), "the list of CPU cores is empty");
+        }
+        self.config.affinity = Some(affinity);
+        self
+    }
+
+    /// Configures the maximum number of threads running blocking tasks.
+    ///
+    /// Blocking tasks spawned with [`task::spawn_blocking`] while all threads are busy wait in a
+    /// queue. The default is 512.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// [`task::spawn_blocking`]: ../task/fn.spawn_blocking.html
+    pub fn max_blocking_threads(mut self, n: usize) -> Builder {
+        assert!(n > 0, "a runtime needs at least one blocking thread");
+        self.config.blocking.max_threads = n;
+        self
+    }
+
+    /// Configures how long an idle blocking thread waits for a new task before it stops.
+    ///
+    /// The default is one second.
+    pub fn blocking_keep_alive(mut self, dur: Duration) -> Builder {
+        self.config.blocking.keep_alive = dur;
+        self
+    }
+
+    /// Configures the maximum number of blocking tasks waiting for a thread.
+    ///
+    /// Once the limit is reached, [`task::try_spawn_blocking`] fails with a [`SpawnError`].
+    /// [`task::spawn_blocking`] ignores the limit. By default, the queue is unbounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::mpsc;
+    ///
+    /// use async_std::runtime::Builder;
+    /// use async_std::task;
+    ///
+    /// let rt = Builder::new()
+    ///     .max_blocking_threads(1)
+    ///     .max_blocking_queue(1)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// rt.block_on(async {
+    ///     let (started, wait) = mpsc::channel();
+    ///     let (release, blocked) = mpsc::channel::<()>();
+    ///
+    ///     // Occupy the only thread, then fill the queue.
+    ///     let busy = task::try_spawn_blocking(move || {
+    ///         started.send(()).unwrap();
+    ///         blocked.recv().unwrap();
+    ///     })
+    ///     .unwrap();
+    ///     wait.recv().unwrap();
+    ///     let queued = task::try_spawn_blocking(|| ()).unwrap();
+    ///
+    ///     let err = task::try_spawn_blocking(|| ()).unwrap_err();
+    ///     assert!(err.is_queue_full());
+    ///
+    ///     release.send(()).unwrap();
+    ///     busy.await;
+    ///     queued.await;
+    /// });
+    /// ```
+    ///
+    /// [`task::try_spawn_blocking`]: ../task/fn.try_spawn_blocking.html
+    /// [`SpawnError`]: ../task/struct.SpawnError.html
+    /// [`task::spawn_blocking`]: ../task/fn.spawn_blocking.html
+    pub fn max_blocking_queue(mut self, n: usize) -> Builder {
+        self.config.blocking.queue_limit = Some(n);
+        self
+    }
+
+    /// Configures the maximum number of threads of a named blocking thread pool.
+    ///
+    /// Blocking tasks spawned with [`task::spawn_blocking_on`] run on the pool with the given
+    /// name, separately from other blocking tasks. Pools without a configured limit use the one
+    /// set with [`max_blocking_threads`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_threads` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::runtime::Builder;
+    /// use async_std::task;
+    ///
+    /// let rt = Builder::new()
+    ///     .blocking_pool("dns", 4)
+    ///     .blocking_pool("fs", 64)
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// rt.block_on(async {
+    ///     let len = task::spawn_blocking_on("fs", || std::fs::metadata(".").map(|m| m.len()));
+    ///     assert!(len.await.is_ok());
+    /// });
+    /// ```
+    ///
+    /// [`task::spawn_blocking_on`]: ../task/fn.spawn_blocking_on.html
+    /// [`max_blocking_threads`]: #method.max_blocking_threads
+    pub fn blocking_pool(mut self, name: impl Into<String>, max_threads: usize) -> Builder {
+        assert!(max_threads > 0, "a blocking pool needs at least one thread");
+        self.config.blocking_limits.insert(name.into(), max_threads);
+        self
+    }
+
+    /// Creates the runtime and starts its worker threads.
+    ///
+    /// Returns an error if a worker thread couldn't be started or pinned to a CPU core.
+    pub fn build(mut self) -> io::Result<Runtime> {
+        if self.current_thread {
+            self.config.num_threads = 0;
+
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                self.config.park = Some(Arc::new(crate::net::driver::Driver::new()?));
+            }
+        }
+        #[cfg(feature = "io-uring")]
+        {
+            if self.config.io_uring && !crate::net::driver::uring_supported() {
+                self.config.io_uring = false;
+            }
+        }
+        let pool = Pool::new(self.config)?;
+        Ok(Runtime { pool })
+    }
+}
+
+impl Default for Builder {
+    fn default() -> Builder {
+        Builder::new()
+    }
+}
+
+impl fmt::Debug for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("current_thread", &self.current_thread)
+            .field("worker_threads", &self.config.num_threads)
+            .field("thread_name", &self.config.thread_name)
+            .field("thread_stack_size", &self.config.stack_size)
+            .field("work_stealing", &self.config.work_stealing)
+            .field("lifo_slot", &self.config.lifo_slot)
+            .field("fifo_scheduling", &self.config.fifo)
+            .field("worker_affinity", &self.config.affinity)
+            .field("max_blocking_threads", &self.config.blocking.max_threads)
+            .field("blocking_keep_alive", &self.config.blocking.keep_alive)
+            .field("max_blocking_queue", &self.config.blocking.queue_limit)
+            .finish()
+    }
+}
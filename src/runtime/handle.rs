@@ -0,0 +1,106 @@
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::task::executor::{self, Pool};
+use crate::task::{self, JoinHandle};
+
+/// A handle to a runtime.
+///
+/// A handle spawns tasks onto the runtime it was obtained from, and blocks on futures with that
+/// runtime entered. It can be cloned and sent to other threads, but doesn't keep the runtime from
+/// being shut down.
+///
+/// # Examples
+///
+/// ```
+/// use std::thread;
+///
+/// use async_std::runtime::Runtime;
+///
+/// let rt = Runtime::new().unwrap();
+/// let handle = rt.handle();
+///
+/// // Drive the runtime from a dedicated thread.
+/// let n = thread::spawn(move || handle.block_on(async { 1 + 2 }))
+///     .join()
+///     .unwrap();
+/// assert_eq!(n, 3);
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone)]
+pub struct Handle {
+    pub(crate) pool: Arc<Pool>,
+}
+
+impl Handle {
+    /// Returns a handle to the runtime tasks spawned from the current thread are spawned onto.
+    ///
+    /// This is the runtime entered with [`Runtime::block_on`], or otherwise the runtime the
+    /// current thread is a worker thread of, or otherwise the global runtime.
+    ///
+    /// [`Runtime::block_on`]: struct.Runtime.html#method.block_on
+    pub fn current() -> Handle {
+        Handle {
+            pool: executor::current(),
+        }
+    }
+
+    /// Blocks the current thread on a future, spawning tasks onto the runtime in the meantime.
+    ///
+    /// Unlike [`task::block_on`], this method may be called from within a task, for when blocking
+    /// is intended. The worker thread then hands its queued tasks over to a new worker thread, as
+    /// with [`task::block_in_place`], so other tasks keep running while it blocks.
+    ///
+    /// [`task::block_on`]: ../task/fn.block_on.html
+    /// [`task::block_in_place`]: ../task/fn.block_in_place.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::runtime::Handle;
+    /// use async_std::task;
+    ///
+    /// task::block_on(async {
+    ///     let n = task::spawn(async {
+    ///         // Deliberately block this task's thread.
+    ///         Handle::current().block_on(async { 1 + 2 })
+    ///     })
+    ///     .await;
+    ///     assert_eq!(n, 3);
+    /// });
+    /// ```
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        executor::block_in_place(|| block_on(&self.pool, future))
+    }
+
+    /// Spawns a task onto the runtime.
+    #[track_caller]
+    pub fn spawn<F, T>(&self, future: F) -> JoinHandle<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        task::Builder::new().spawn_on(self.pool.clone(), future)
+    }
+}
+
+/// Blocks the current thread on a future with the given executor entered.
+///
+/// On a current-thread executor, this also runs the tasks of the executor on the current thread
+/// until the future completes.
+pub(crate) fn block_on<F: Future>(pool: &Arc<Pool>, future: F) -> F::Output {
+    executor::enter(pool, || {
+        if pool.is_current_thread() {
+            task::block_on(pool.run_until(future))
+        } else {
+            task::block_on(future)
+        }
+    })
+}
+
+impl fmt::Debug for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Handle { .. }")
+    }
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
+
+/// The number of buckets in the poll time histogram.
+const BUCKETS: usize = 16;
+
+/// Counters updated by the parts of the runtime.
+pub(crate) struct Counters {
+    /// The number of running worker threads.
+    pub(crate) workers: AtomicUsize,
+
+    /// The number of spawned tasks that haven't completed yet.
+    pub(crate) alive_tasks: AtomicUsize,
+
+    /// The number of tasks waiting in a queue to be run.
+    pub(crate) scheduled_tasks: AtomicUsize,
+
+    /// The number of batches of tasks stolen from other worker threads.
+    pub(crate) steals: AtomicU64,
+
+    /// The number of running blocking threads.
+    pub(crate) blocking_threads: AtomicUsize,
+
+    /// The number of blocking operations waiting for a thread.
+    pub(crate) blocking_queue: AtomicUsize,
+
+    /// The number of times tasks have been polled, by duration of the poll.
+    ///
+    /// Bucket `i` counts polls that took less than `2^i` microseconds. The last bucket counts all
+    /// longer polls.
+    pub(crate) polls: [AtomicU64; BUCKETS],
+
+    /// The number of times the reactor has woken up to process I/O events.
+    pub(crate) reactor_wakeups: AtomicU64,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicU64 = AtomicU64::new(0);
+
+/// The counters of the global runtime.
+pub(crate) static COUNTERS: Counters = Counters {
+    workers: AtomicUsize::new(0),
+    alive_tasks: AtomicUsize::new(0),
+    scheduled_tasks: AtomicUsize::new(0),
+    steals: AtomicU64::new(0),
+    blocking_threads: AtomicUsize::new(0),
+    blocking_queue: AtomicUsize::new(0),
+    polls: [ZERO; BUCKETS],
+    reactor_wakeups: AtomicU64::new(0),
+};
+
+impl Counters {
+    /// Records a poll of a task that took the given time.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn record_poll(&self, elapsed: Duration) {
+        let micros = elapsed.as_micros();
+        let bucket = (0..BUCKETS - 1)
+            .find(|&i| micros < 1 << i)
+            .unwrap_or(BUCKETS - 1);
+        self.polls[bucket].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Returns a handle to the metrics of the runtime.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::runtime;
+/// use async_std::task;
+///
+/// task::spawn(async {}).await;
+///
+/// let metrics = runtime::metrics();
+/// assert!(metrics.num_workers() > 0);
+/// assert!(metrics.poll_count() > 0);
+/// #
+/// # })
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn metrics() -> RuntimeMetrics {
+    RuntimeMetrics { _private: () }
+}
+
+/// A handle to the metrics of the runtime.
+///
+/// Every method returns the current value of a metric, so the handle can be kept around and
+/// sampled periodically, for example by a Prometheus exporter. Counters only ever increase
+/// while gauges go up and down.
+///
+/// This struct is created by the [`metrics`] function. See its documentation for more.
+///
+/// [`metrics`]: fn.metrics.html
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone)]
+pub struct RuntimeMetrics {
+    _private: (),
+}
+
+impl RuntimeMetrics {
+    /// Returns the number of running worker threads.
+    ///
+    /// This is a gauge. Worker threads are started on first use of the executor, and an extra
+    /// worker thread is started every time a task calls [`block_in_place`].
+    ///
+    /// [`block_in_place`]: ../task/fn.block_in_place.html
+    pub fn num_workers(&self) -> usize {
+        COUNTERS.workers.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of spawned tasks that haven't completed yet.
+    ///
+    /// This is a gauge.
+    pub fn num_alive_tasks(&self) -> usize {
+        COUNTERS.alive_tasks.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of tasks that have been woken up and are waiting in a queue to be run.
+    ///
+    /// This is a gauge covering the global queue, the local queues of worker threads, and the
+    /// queues of local sets.
+    pub fn queue_depth(&self) -> usize {
+        COUNTERS.scheduled_tasks.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of times a worker thread has stolen tasks from another worker thread.
+    ///
+    /// This is a counter.
+    pub fn steal_count(&self) -> u64 {
+        COUNTERS.steals.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of running threads in the blocking pool.
+    ///
+    /// This is a gauge.
+    pub fn num_blocking_threads(&self) -> usize {
+        COUNTERS.blocking_threads.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of blocking operations waiting for a thread in the blocking pool.
+    ///
+    /// This is a gauge.
+    pub fn blocking_queue_depth(&self) -> usize {
+        COUNTERS.blocking_queue.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of times tasks have been polled.
+    ///
+    /// This is a counter.
+    pub fn poll_count(&self) -> u64 {
+        COUNTERS
+            .polls
+            .iter()
+            .map(|n| n.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    /// Returns a histogram of how long polls of tasks took.
+    ///
+    /// Each entry holds the exclusive upper bound of a bucket and the number of polls in it. The
+    /// bucket bounds are powers of two microseconds. The last bucket has no upper bound, which is
+    /// reported as `Duration::MAX`. Each count is a counter.
+    pub fn poll_time_histogram(&self) -> Vec<(Duration, u64)> {
+        COUNTERS
+            .polls
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let bound = if i == BUCKETS - 1 {
+                    Duration::MAX
+                } else {
+                    Duration::from_micros(1 << i)
+                };
+                (bound, n.load(Ordering::Relaxed))
+            })
+            .collect()
+    }
+
+    /// Returns the number of times the reactor has woken up to process I/O events.
+    ///
+    /// This is a counter.
+    pub fn reactor_wakeups(&self) -> u64 {
+        COUNTERS.reactor_wakeups.load(Ordering::Relaxed)
+    }
+}
+
+impl fmt::Debug for RuntimeMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RuntimeMetrics")
+            .field("num_workers", &self.num_workers())
+            .field("num_alive_tasks", &self.num_alive_tasks())
+            .field("queue_depth", &self.queue_depth())
+            .field("steal_count", &self.steal_count())
+            .field("num_blocking_threads", &self.num_blocking_threads())
+            .field("blocking_queue_depth", &self.blocking_queue_depth())
+            .field("poll_count", &self.poll_count())
+            .field("reactor_wakeups", &self.reactor_wakeups())
+            .finish()
+    }
+}
@@ -0,0 +1,64 @@
+//! The runtime driving tasks, blocking operations, and I/O.
+//!
+//! Tasks spawned with [`task::spawn`] run on a pool of worker threads, blocking operations
+//! started with [`task::spawn_blocking`] run on a separate pool of blocking threads, and I/O
+//! events are delivered by a reactor thread.
+//!
+//! Tasks run on the global runtime by default, which is started on first use. A [`Runtime`] can
+//! be created with custom settings to run tasks on a separate pool of worker threads.
+//!
+//! [`task::spawn`]: ../task/fn.spawn.html
+//! [`task::spawn_blocking`]: ../task/fn.spawn_blocking.html
+//! [`Runtime`]: struct.Runtime.html
+
+use std::time::Duration;
+
+use crate::task::executor;
+
+pub use affinity::Affinity;
+pub use builder::Builder;
+pub use handle::Handle;
+pub use metrics::{metrics, RuntimeMetrics};
+pub use runtime::Runtime;
+
+pub(crate) mod affinity;
+mod builder;
+mod handle;
+pub(crate) mod metrics;
+mod runtime;
+#[cfg(feature = "tokio-runtime")]
+pub(crate) mod tokio;
+
+/// Shuts the global runtime down, waiting for its tasks and worker threads for at most `dur`.
+///
+/// This is the equivalent of [`Runtime::shutdown_timeout`] for the global runtime, meant to be
+/// called right before the process exits. Tasks spawned with [`task::spawn`] afterwards are
+/// cancelled.
+///
+/// If the global runtime hasn't been started, this function does nothing.
+///
+/// [`Runtime::shutdown_timeout`]: struct.Runtime.html#method.shutdown_timeout
+/// [`task::spawn`]: ../task/fn.spawn.html
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use async_std::runtime;
+/// use async_std::task;
+///
+/// task::block_on(async {
+///     task::spawn(async {
+///         // Flush some buffers...
+///     });
+/// });
+///
+/// runtime::shutdown_timeout(Duration::from_secs(1));
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn shutdown_timeout(dur: Duration) {
+    if let Some(pool) = executor::global() {
+        pool.shutdown_timeout(dur);
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::io;
+use crate::runtime::{handle, Builder, Handle};
+use crate::task::executor::Pool;
+use crate::task::{self, JoinHandle};
+
+/// A runtime with its own pool of worker threads.
+///
+/// By default, tasks are spawned onto a global runtime that is started on first use. A `Runtime`
+/// is isolated from the global runtime and from other runtimes, which is useful for test
+/// harnesses or for giving plugins their own threads.
+///
+/// Tasks spawned from within a runtime's tasks, or from within [`block_on`], are spawned onto
+/// the same runtime.
+///
+/// Dropping the runtime stops its worker threads once the tasks they are running yield. Tasks
+/// waiting to run are dropped, and so are tasks that are woken up afterwards.
+///
+/// [`block_on`]: #method.block_on
+///
+/// # Examples
+///
+/// ```
+/// use async_std::runtime::Runtime;
+/// use async_std::task;
+///
+/// let rt = Runtime::new().unwrap();
+///
+/// let sum = rt.block_on(async {
+///     // Spawned onto `rt`.
+///     let a = task::spawn(async { 1 });
+///     let b = task::spawn(async { 2 });
+///     a.await + b.await
+/// });
+/// assert_eq!(sum, 3);
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Runtime {
+    pub(crate) pool: Arc<Pool>,
+}
+
+impl Runtime {
+    /// Creates a runtime with the default settings.
+    ///
+    /// See [`Builder`] for customizing the settings.
+    ///
+    /// [`Builder`]: struct.Builder.html
+    pub fn new() -> io::Result<Runtime> {
+        Builder::new().build()
+    }
+
+    /// Blocks the current thread on a future, spawning tasks onto this runtime in the meantime.
+    ///
+    /// On a current-thread runtime, this also runs the tasks of the runtime on the current thread
+    /// until the future completes.
+    ///
+    /// # Panics
+    ///
+    /// Like [`task::block_on`], panics if called from within a task. Use [`Handle::block_on`] if
+    /// blocking is intended.
+    ///
+    /// [`task::block_on`]: ../task/fn.block_on.html
+    /// [`Handle::block_on`]: struct.Handle.html#method.block_on
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        handle::block_on(&self.pool, future)
+    }
+
+    /// Returns a handle to this runtime.
+    pub fn handle(&self) -> Handle {
+        Handle {
+            pool: self.pool.clone(),
+        }
+    }
+
+    /// Spawns a task onto this runtime.
+    ///
+    /// On a current-thread runtime, the task only runs while [`block_on`] is being executed.
+    ///
+    /// [`block_on`]: #method.block_on
+    #[track_caller]
+    pub fn spawn<F, T>(&self, future: F) -> JoinHandle<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        task::Builder::new().spawn_on(self.pool.clone(), future)
+    }
+
+    /// Shuts the runtime down, waiting for its tasks and worker threads for at most `dur`.
+    ///
+    /// The runtime stops accepting new tasks right away, so tasks spawned onto it from now on are
+    /// cancelled. Then this method waits until the remaining tasks complete or the timeout
+    /// expires. Tasks waiting to run are then dropped, and so are tasks woken up afterwards.
+    /// Finally, this method waits for the worker threads to stop until the timeout expires.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use async_std::runtime::Runtime;
+    /// use async_std::task;
+    ///
+    /// let rt = Runtime::new().unwrap();
+    /// let handle = rt.spawn(task::sleep(Duration::from_millis(10)));
+    ///
+    /// // The task completes before the timeout expires.
+    /// rt.shutdown_timeout(Duration::from_secs(5));
+    /// assert!(task::block_on(handle.join()).is_ok());
+    /// ```
+    pub fn shutdown_timeout(self, dur: Duration) {
+        self.pool.shutdown_timeout(dur);
+    }
+}
+
+impl Drop for Runtime {
+    fn drop(&mut self) {
+        self.pool.shutdown();
+    }
+}
+
+impl fmt::Debug for Runtime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Runtime { .. }")
+    }
+}
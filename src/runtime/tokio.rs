@@ -0,0 +1,34 @@
+//! A tokio runtime providing the context tokio-based futures expect.
+
+use std::future;
+use std::thread;
+
+use once_cell::sync::Lazy;
+use tokio::runtime::{EnterGuard, Handle};
+
+/// The tokio runtime whose I/O and timer drivers are shared by all tasks.
+///
+/// The drivers are run by a dedicated thread, so they make progress no matter which thread polls
+/// a future using them.
+static TOKIO: Lazy<Handle> = Lazy::new(|| {
+    let rt = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("cannot start the tokio runtime");
+    let handle = rt.handle().clone();
+
+    thread::Builder::new()
+        .name("async-std/tokio".to_string())
+        .spawn(move || rt.block_on(future::pending::<()>()))
+        .expect("cannot start the tokio driver thread");
+
+    handle
+});
+
+/// Enters the context of the tokio runtime on the current thread, starting it on first use.
+///
+/// Futures polled before the returned guard is dropped can use tokio's reactor and timers, and
+/// spawn tokio tasks.
+pub(crate) fn enter() -> EnterGuard<'static> {
+    TOKIO.enter()
+}
@@ -0,0 +1,101 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::sink::Sink;
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// A sink that buffers values in front of another sink.
+    ///
+    /// This sink is created by the [`buffer`] method on [`SinkExt`].
+    ///
+    /// [`buffer`]: trait.SinkExt.html#method.buffer
+    /// [`SinkExt`]: trait.SinkExt.html
+    #[derive(Debug)]
+    #[must_use = "sinks do nothing unless polled"]
+    pub struct Buffer<Si, Item> {
+        #[pin]
+        sink: Si,
+        buf: VecDeque<Item>,
+        capacity: usize,
+    }
+}
+
+impl<Si: Sink<Item>, Item> Buffer<Si, Item> {
+    pub(crate) fn new(sink: Si, capacity: usize) -> Self {
+        Buffer {
+            sink,
+            buf: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Gets a reference to the underlying sink.
+    pub fn get_ref(&self) -> &Si {
+        &self.sink
+    }
+
+    /// Gets a mutable reference to the underlying sink.
+    pub fn get_mut(&mut self) -> &mut Si {
+        &mut self.sink
+    }
+
+    /// Unwraps the adapter, returning the underlying sink.
+    ///
+    /// Buffered values that haven't been sent into the sink yet are dropped.
+    pub fn into_inner(self) -> Si {
+        self.sink
+    }
+
+    /// Sends as many buffered values into the sink as it accepts.
+    fn poll_drain(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Si::Error>> {
+        let mut this = self.project();
+        while !this.buf.is_empty() {
+            futures_core::ready!(this.sink.as_mut().poll_ready(cx))?;
+            let item = this.buf.pop_front().unwrap();
+            this.sink.as_mut().start_send(item)?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<Si: Sink<Item>, Item> Sink<Item> for Buffer<Si, Item> {
+    type Error = Si::Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.capacity == 0 {
+            return self.project().sink.poll_ready(cx);
+        }
+
+        if let Poll::Ready(Err(err)) = self.as_mut().poll_drain(cx) {
+            return Poll::Ready(Err(err));
+        }
+        if self.buf.len() >= self.capacity {
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        let this = self.project();
+        if *this.capacity == 0 {
+            this.sink.start_send(item)
+        } else {
+            this.buf.push_back(item);
+            Ok(())
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        futures_core::ready!(self.as_mut().poll_drain(cx))?;
+        self.project().sink.poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        futures_core::ready!(self.as_mut().poll_drain(cx))?;
+        self.project().sink.poll_close(cx)
+    }
+}
@@ -0,0 +1,154 @@
+//! Asynchronous values that can receive other values.
+//!
+//! A [`Sink`] is the counterpart of a [`Stream`]: where a stream produces values
+//! asynchronously, a sink consumes them. Sending a value into a sink happens in three steps:
+//! waiting until the sink is ready with `poll_ready`, handing over the value with
+//! `start_send`, and waiting with `poll_flush` until the sink has processed everything it was
+//! given. The [`SinkExt`] trait wraps these steps into futures and provides combinators. It is
+//! not part of the prelude, so that it doesn't clash with the `SinkExt` trait of the `futures`
+//! crate.
+//!
+//! The senders of [channels] implement `Sink`, while the receivers implement `Stream`, so that
+//! stages of a pipeline can be connected in either direction.
+//!
+//! [`Sink`]: trait.Sink.html
+//! [`Stream`]: ../stream/trait.Stream.html
+//! [`SinkExt`]: trait.SinkExt.html
+//! [channels]: ../sync/fn.channel.html
+//!
+//! # Examples
+//!
+//! ```
+//! # async_std::task::block_on(async {
+//! #
+//! use async_std::prelude::*;
+//! use async_std::sink::SinkExt;
+//! use async_std::stream;
+//! use async_std::sync::channel;
+//!
+//! let (s, r) = channel(10);
+//! let mut s = Box::pin(s.with(|n: u32| async move { Ok(n * 2) }));
+//!
+//! s.send_all(&mut stream::from_iter(1..=3)).await.unwrap();
+//! drop(s);
+//!
+//! let doubled: Vec<u32> = r.collect().await;
+//! assert_eq!(doubled, [2, 4, 6]);
+//! #
+//! # })
+//! ```
+
+#[doc(inline)]
+pub use futures_sink::Sink;
+
+pub use buffer::Buffer;
+pub use send::{Close, Flush, Send};
+pub use send_all::SendAll;
+pub use with::With;
+
+use std::future::Future;
+
+use crate::stream::Stream;
+
+mod buffer;
+mod send;
+mod send_all;
+mod with;
+
+/// Extension methods for [`Sink`].
+///
+/// [`Sink`]: trait.Sink.html
+pub trait SinkExt<Item>: Sink<Item> {
+    /// Sends a value into the sink and flushes it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sink::SinkExt;
+    /// use async_std::sync::channel;
+    ///
+    /// let (mut s, r) = channel(1);
+    /// SinkExt::send(&mut s, 1).await.unwrap();
+    /// assert_eq!(r.recv().await, Some(1));
+    /// #
+    /// # })
+    /// ```
+    fn send(&mut self, item: Item) -> Send<'_, Self, Item>
+    where
+        Self: Unpin,
+    {
+        Send::new(self, item)
+    }
+
+    /// Sends all values of a stream into the sink and flushes it.
+    ///
+    /// The sink is flushed whenever the stream has no value ready, so that values don't stay
+    /// buffered while waiting for the next one. The sink is not closed at the end.
+    fn send_all<'a, S>(&'a mut self, stream: &'a mut S) -> SendAll<'a, Self, S>
+    where
+        Self: Unpin,
+        S: Stream<Item = Item> + Unpin + ?Sized,
+    {
+        SendAll::new(self, stream)
+    }
+
+    /// Flushes the sink, processing all values sent into it.
+    fn flush(&mut self) -> Flush<'_, Self, Item>
+    where
+        Self: Unpin,
+    {
+        Flush::new(self)
+    }
+
+    /// Closes the sink, flushing it first.
+    fn close(&mut self) -> Close<'_, Self, Item>
+    where
+        Self: Unpin,
+    {
+        Close::new(self)
+    }
+
+    /// Transforms values with an asynchronous function before sending them into the sink.
+    ///
+    /// If the function fails, the error is returned by the sink.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sink::SinkExt;
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel(1);
+    /// let mut s = Box::pin(s.with(|n: u32| async move { Ok(n.to_string()) }));
+    /// SinkExt::send(&mut s, 7).await.unwrap();
+    /// assert_eq!(r.recv().await, Some("7".to_string()));
+    /// #
+    /// # })
+    /// ```
+    fn with<U, F, Fut>(self, f: F) -> With<Self, Item, U, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(U) -> Fut,
+        Fut: Future<Output = Result<Item, Self::Error>>,
+    {
+        With::new(self, f)
+    }
+
+    /// Buffers up to `capacity` values in front of the sink.
+    ///
+    /// Values are accepted as long as there is room in the buffer, even when the sink itself
+    /// isn't ready, and are passed on to the sink as it becomes ready. This decouples a fast
+    /// producer from a sink that accepts values in bursts.
+    fn buffer(self, capacity: usize) -> Buffer<Self, Item>
+    where
+        Self: Sized,
+    {
+        Buffer::new(self, capacity)
+    }
+}
+
+impl<T: Sink<Item> + ?Sized, Item> SinkExt<Item> for T {}
@@ -0,0 +1,105 @@
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+use crate::sink::Sink;
+use crate::task::{Context, Poll};
+
+/// A future that sends a value into a sink and flushes it.
+///
+/// This future is created by the [`send`] method on [`SinkExt`].
+///
+/// [`send`]: trait.SinkExt.html#method.send
+/// [`SinkExt`]: trait.SinkExt.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Send<'a, Si: ?Sized, Item> {
+    sink: &'a mut Si,
+    item: Option<Item>,
+}
+
+impl<'a, Si: ?Sized, Item> Send<'a, Si, Item> {
+    pub(crate) fn new(sink: &'a mut Si, item: Item) -> Self {
+        Send {
+            sink,
+            item: Some(item),
+        }
+    }
+}
+
+impl<Si: ?Sized, Item> Unpin for Send<'_, Si, Item> {}
+
+impl<Si: Sink<Item> + Unpin + ?Sized, Item> Future for Send<'_, Si, Item> {
+    type Output = Result<(), Si::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut sink = Pin::new(&mut *this.sink);
+
+        if this.item.is_some() {
+            futures_core::ready!(sink.as_mut().poll_ready(cx))?;
+            sink.as_mut().start_send(this.item.take().unwrap())?;
+        }
+        sink.poll_flush(cx)
+    }
+}
+
+/// A future that flushes a sink.
+///
+/// This future is created by the [`flush`] method on [`SinkExt`].
+///
+/// [`flush`]: trait.SinkExt.html#method.flush
+/// [`SinkExt`]: trait.SinkExt.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Flush<'a, Si: ?Sized, Item> {
+    sink: &'a mut Si,
+    _marker: PhantomData<fn(Item)>,
+}
+
+impl<'a, Si: ?Sized, Item> Flush<'a, Si, Item> {
+    pub(crate) fn new(sink: &'a mut Si) -> Self {
+        Flush {
+            sink,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<Si: Sink<Item> + Unpin + ?Sized, Item> Future for Flush<'_, Si, Item> {
+    type Output = Result<(), Si::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.sink).poll_flush(cx)
+    }
+}
+
+/// A future that closes a sink.
+///
+/// This future is created by the [`close`] method on [`SinkExt`].
+///
+/// [`close`]: trait.SinkExt.html#method.close
+/// [`SinkExt`]: trait.SinkExt.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Close<'a, Si: ?Sized, Item> {
+    sink: &'a mut Si,
+    _marker: PhantomData<fn(Item)>,
+}
+
+impl<'a, Si: ?Sized, Item> Close<'a, Si, Item> {
+    pub(crate) fn new(sink: &'a mut Si) -> Self {
+        Close {
+            sink,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<Si: Sink<Item> + Unpin + ?Sized, Item> Future for Close<'_, Si, Item> {
+    type Output = Result<(), Si::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.sink).poll_close(cx)
+    }
+}
@@ -0,0 +1,74 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::sink::Sink;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A future that sends all values of a stream into a sink.
+///
+/// This future is created by the [`send_all`] method on [`SinkExt`].
+///
+/// [`send_all`]: trait.SinkExt.html#method.send_all
+/// [`SinkExt`]: trait.SinkExt.html
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct SendAll<'a, Si: ?Sized, St: ?Sized + Stream> {
+    sink: &'a mut Si,
+    stream: &'a mut St,
+    buffered: Option<St::Item>,
+    done: bool,
+}
+
+impl<'a, Si: ?Sized, St: ?Sized + Stream> SendAll<'a, Si, St> {
+    pub(crate) fn new(sink: &'a mut Si, stream: &'a mut St) -> Self {
+        SendAll {
+            sink,
+            stream,
+            buffered: None,
+            done: false,
+        }
+    }
+}
+
+impl<Si: ?Sized, St: ?Sized + Stream> Unpin for SendAll<'_, Si, St> {}
+
+impl<Si, St> Future for SendAll<'_, Si, St>
+where
+    Si: Sink<St::Item> + Unpin + ?Sized,
+    St: Stream + Unpin + ?Sized,
+{
+    type Output = Result<(), Si::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        let mut sink = Pin::new(&mut *this.sink);
+
+        loop {
+            // Hand the value taken from the stream over to the sink.
+            if let Some(item) = this.buffered.take() {
+                match sink.as_mut().poll_ready(cx)? {
+                    Poll::Ready(()) => sink.as_mut().start_send(item)?,
+                    Poll::Pending => {
+                        this.buffered = Some(item);
+                        return Poll::Pending;
+                    }
+                }
+            }
+
+            if this.done {
+                return sink.poll_flush(cx);
+            }
+
+            match Pin::new(&mut *this.stream).poll_next(cx) {
+                Poll::Ready(Some(item)) => this.buffered = Some(item),
+                Poll::Ready(None) => this.done = true,
+                Poll::Pending => {
+                    // Flush what has been sent so far while waiting for more values.
+                    futures_core::ready!(sink.poll_flush(cx))?;
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::sink::Sink;
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// A sink that transforms values with an asynchronous function before sending them.
+    ///
+    /// This sink is created by the [`with`] method on [`SinkExt`].
+    ///
+    /// [`with`]: trait.SinkExt.html#method.with
+    /// [`SinkExt`]: trait.SinkExt.html
+    #[must_use = "sinks do nothing unless polled"]
+    pub struct With<Si, Item, U, F, Fut> {
+        #[pin]
+        sink: Si,
+        f: F,
+        #[pin]
+        pending: Option<Fut>,
+        _marker: PhantomData<fn(U) -> Item>,
+    }
+}
+
+impl<Si, Item, U, F, Fut> With<Si, Item, U, F, Fut> {
+    pub(crate) fn new(sink: Si, f: F) -> Self {
+        With {
+            sink,
+            f,
+            pending: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets a reference to the underlying sink.
+    pub fn get_ref(&self) -> &Si {
+        &self.sink
+    }
+
+    /// Gets a mutable reference to the underlying sink.
+    pub fn get_mut(&mut self) -> &mut Si {
+        &mut self.sink
+    }
+
+    /// Unwraps the adapter, returning the underlying sink.
+    ///
+    /// A value that is still being transformed is dropped.
+    pub fn into_inner(self) -> Si {
+        self.sink
+    }
+}
+
+impl<Si, Item, U, F, Fut> With<Si, Item, U, F, Fut>
+where
+    Si: Sink<Item>,
+    Fut: Future<Output = Result<Item, Si::Error>>,
+{
+    /// Finishes transforming the pending value and sends it into the sink.
+    ///
+    /// The sink has been polled ready before the value was accepted.
+    fn poll_pending(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Si::Error>> {
+        let mut this = self.project();
+        if let Some(fut) = this.pending.as_mut().as_pin_mut() {
+            let item = futures_core::ready!(fut.poll(cx));
+            this.pending.set(None);
+            this.sink.start_send(item?)?;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<Si, Item, U, F, Fut> Sink<U> for With<Si, Item, U, F, Fut>
+where
+    Si: Sink<Item>,
+    F: FnMut(U) -> Fut,
+    Fut: Future<Output = Result<Item, Si::Error>>,
+{
+    type Error = Si::Error;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        futures_core::ready!(self.as_mut().poll_pending(cx))?;
+        self.project().sink.poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: U) -> Result<(), Self::Error> {
+        let mut this = self.project();
+        let fut = (this.f)(item);
+        this.pending.set(Some(fut));
+        Ok(())
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        futures_core::ready!(self.as_mut().poll_pending(cx))?;
+        self.project().sink.poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        futures_core::ready!(self.as_mut().poll_pending(cx))?;
+        self.project().sink.poll_close(cx)
+    }
+}
+
+impl<Si: fmt::Debug, Item, U, F, Fut> fmt::Debug for With<Si, Item, U, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("With")
+            .field("sink", &self.sink)
+            .field("pending", &self.pending.is_some())
+            .finish()
+    }
+}
@@ -5,6 +5,7 @@ use std::task::{Context, Poll};
 
 mod next_back;
 mod nth_back;
+mod rev;
 mod rfind;
 mod rfold;
 mod try_rfold;
@@ -15,6 +16,8 @@ use rfind::RFindFuture;
 use rfold::RFoldFuture;
 use try_rfold::TryRFoldFuture;
 
+pub use rev::Rev;
+
 /// A stream able to yield elements from both ends.
 ///
 /// Something that implements `DoubleEndedStream` has one extra capability
@@ -105,9 +108,9 @@ pub trait DoubleEndedStream: Stream {
             ```
             # fn main() { async_std::task::block_on(async {
             #
-            use async_std::stream::double_ended_stream::{self, DoubleEndedStream};
+            use async_std::stream::{self, DoubleEndedStream};
 
-            let mut s = double_ended_stream::from_iter(vec![7u8]);
+            let mut s = stream::from_iter(vec![7u8]);
 
             assert_eq!(s.next_back().await, Some(7));
             assert_eq!(s.next_back().await, None);
@@ -122,6 +125,35 @@ pub trait DoubleEndedStream: Stream {
         NextBackFuture { stream: self }
     }
 
+    #[doc = r#"
+            Reverses the direction of a stream.
+
+            The returned stream yields the items of this one back to front, without buffering
+            them.
+
+            # Examples
+
+            ```
+            # fn main() { async_std::task::block_on(async {
+            #
+            use async_std::prelude::*;
+            use async_std::stream::{self, DoubleEndedStream};
+
+            let s = stream::from_iter(vec![1u8, 2, 3]).map(|x| x * 10).rev();
+            let v: Vec<u8> = s.collect().await;
+
+            assert_eq!(v, vec![30, 20, 10]);
+            #
+            # }) }
+            ```
+        "#]
+    fn rev(self) -> Rev<Self>
+    where
+        Self: Sized,
+    {
+        Rev::new(self)
+    }
+
     #[doc = r#"
             Returns the nth element from the back of the stream.
 
@@ -132,9 +164,9 @@ pub trait DoubleEndedStream: Stream {
             ```
             # fn main() { async_std::task::block_on(async {
             #
-            use async_std::stream::double_ended_stream::{self, DoubleEndedStream};
+            use async_std::stream::{self, DoubleEndedStream};
 
-            let mut s = double_ended_stream::from_iter(vec![1u8, 2, 3, 4, 5]);
+            let mut s = stream::from_iter(vec![1u8, 2, 3, 4, 5]);
 
             let second = s.nth_back(1).await;
             assert_eq!(second, Some(4));
@@ -159,9 +191,9 @@ pub trait DoubleEndedStream: Stream {
             ```
             # fn main() { async_std::task::block_on(async {
             #
-            use async_std::stream::double_ended_stream::{self, DoubleEndedStream};
+            use async_std::stream::{self, DoubleEndedStream};
 
-            let mut s = double_ended_stream::from_iter(vec![1u8, 2, 3, 4, 5]);
+            let mut s = stream::from_iter(vec![1u8, 2, 3, 4, 5]);
 
             let second = s.rfind(|v| v % 2 == 0).await;
             assert_eq!(second, Some(4));
@@ -185,9 +217,9 @@ pub trait DoubleEndedStream: Stream {
             ```
             # fn main() { async_std::task::block_on(async {
             #
-            use async_std::stream::double_ended_stream::{self, DoubleEndedStream};
+            use async_std::stream::{self, DoubleEndedStream};
 
-            let s = double_ended_stream::from_iter(vec![1u8, 2, 3, 4, 5]);
+            let s = stream::from_iter(vec![1u8, 2, 3, 4, 5]);
 
             let second = s.rfold(0, |acc, v| v + acc).await;
 
@@ -215,9 +247,9 @@ pub trait DoubleEndedStream: Stream {
             ```
             # fn main() { async_std::task::block_on(async {
             #
-            use async_std::stream::double_ended_stream::{self, DoubleEndedStream};
+            use async_std::stream::{self, DoubleEndedStream};
 
-            let s = double_ended_stream::from_iter(vec![1u8, 2, 3, 4, 5]);
+            let s = stream::from_iter(vec![1u8, 2, 3, 4, 5]);
             let sum = s.try_rfold(0, |acc, v| {
                 if (acc+v) % 2 == 1 {
                     Ok(v+3)
@@ -0,0 +1,45 @@
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::stream::{DoubleEndedStream, Stream};
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// A stream that yields the elements of a double-ended stream back to front.
+    ///
+    /// This `struct` is created by the [`rev`] method on [`DoubleEndedStream`]. See its
+    /// documentation for more.
+    ///
+    /// [`rev`]: trait.DoubleEndedStream.html#method.rev
+    /// [`DoubleEndedStream`]: trait.DoubleEndedStream.html
+    #[derive(Clone, Debug)]
+    pub struct Rev<S> {
+        #[pin]
+        stream: S,
+    }
+}
+
+impl<S> Rev<S> {
+    pub(super) fn new(stream: S) -> Self {
+        Self { stream }
+    }
+}
+
+impl<S: DoubleEndedStream> Stream for Rev<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().stream.poll_next_back(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.stream.size_hint()
+    }
+}
+
+impl<S: DoubleEndedStream> DoubleEndedStream for Rev<S> {
+    fn poll_next_back(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().stream.poll_next(cx)
+    }
+}
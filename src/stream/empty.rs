@@ -1,6 +1,8 @@
 use std::marker::PhantomData;
 use std::pin::Pin;
 
+#[cfg(feature = "unstable")]
+use crate::stream::DoubleEndedStream;
 use crate::stream::Stream;
 use crate::task::{Context, Poll};
 
@@ -48,3 +50,10 @@ impl<T> Stream for Empty<T> {
         Poll::Ready(None)
     }
 }
+
+#[cfg(feature = "unstable")]
+impl<T> DoubleEndedStream for Empty<T> {
+    fn poll_next_back(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(None)
+    }
+}
@@ -0,0 +1,89 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// A stream that yields elements by calling an async closure.
+    ///
+    /// This stream is created by the [`from_async_fn`] function. See its
+    /// documentation for more.
+    ///
+    /// [`from_async_fn`]: fn.from_async_fn.html
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    #[derive(Debug)]
+    pub struct FromAsyncFn<F, Fut> {
+        f: F,
+        #[pin]
+        future: Option<Fut>,
+    }
+}
+
+/// Creates a new stream where to produce each new element a provided async closure is called.
+///
+/// This is like [`from_fn`], except that the closure returns a future, which is awaited before
+/// the next element is produced. The stream ends once the future resolves to `None`.
+///
+/// [`from_fn`]: fn.from_fn.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::sync::atomic::{AtomicU8, Ordering};
+///
+/// use async_std::prelude::*;
+/// use async_std::stream;
+///
+/// let count = AtomicU8::new(0);
+/// let s = stream::from_async_fn(|| async {
+///     let n = count.fetch_add(1, Ordering::SeqCst) + 1;
+///     if n > 3 {
+///         None
+///     } else {
+///         Some(n)
+///     }
+/// });
+///
+/// pin_utils::pin_mut!(s);
+///
+/// assert_eq!(s.next().await, Some(1));
+/// assert_eq!(s.next().await, Some(2));
+/// assert_eq!(s.next().await, Some(3));
+/// assert_eq!(s.next().await, None);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn from_async_fn<T, F, Fut>(f: F) -> FromAsyncFn<F, Fut>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    FromAsyncFn { f, future: None }
+}
+
+impl<T, F, Fut> Stream for FromAsyncFn<F, Fut>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Option<T>>,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        if this.future.is_none() {
+            this.future.set(Some((this.f)()));
+        }
+
+        let item = futures_core::ready!(this.future.as_mut().as_pin_mut().unwrap().poll(cx));
+        this.future.set(None);
+        Poll::Ready(item)
+    }
+}
@@ -21,6 +21,10 @@ impl<F> Unpin for FromFn<F> {}
 /// This allows creating a custom stream with any behaviour without using the more verbose
 /// syntax of creating a dedicated type and implementing a `Stream` trait for it.
 ///
+/// To produce elements asynchronously, use [`from_async_fn`] instead.
+///
+/// [`from_async_fn`]: fn.from_async_fn.html
+///
 /// # Examples
 ///
 /// ```
@@ -2,9 +2,9 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 use std::time::{Duration, Instant};
 
-use futures_timer::Delay;
-
 use crate::prelude::*;
+use crate::time::Delay;
+use crate::utils::random;
 
 /// Creates a new stream that yields at a set interval.
 ///
@@ -15,6 +15,10 @@ use crate::prelude::*;
 /// Each interval may be slightly longer than the specified duration, but never
 /// less.
 ///
+/// If the stream is not polled for longer than `dur`, the missed ticks are handled according to
+/// the interval's [`MissedTickBehavior`], which can be changed with
+/// [`Interval::set_missed_tick_behavior`].
+///
 /// Note that intervals are not intended for high resolution timers, but rather
 /// they will likely fire some granularity after the exact instant that they're
 /// otherwise indicated to fire at.
@@ -22,6 +26,8 @@ use crate::prelude::*;
 /// See also: [`task::sleep`].
 ///
 /// [`task::sleep`]: ../task/fn.sleep.html
+/// [`MissedTickBehavior`]: enum.MissedTickBehavior.html
+/// [`Interval::set_missed_tick_behavior`]: struct.Interval.html#method.set_missed_tick_behavior
 ///
 /// # Examples
 ///
@@ -44,9 +50,114 @@ use crate::prelude::*;
 #[cfg(feature = "unstable")]
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
 pub fn interval(dur: Duration) -> Interval {
+    interval_with_jitter(dur, Duration::from_secs(0))
+}
+
+/// Creates a new stream that yields at a set interval, with each tick delayed by a random jitter.
+///
+/// Ticks are scheduled every `dur` like with [`interval`], but each one fires a random amount of
+/// time between zero and `jitter` after its scheduled time. The jitter doesn't accumulate: the
+/// schedule itself doesn't drift.
+///
+/// Spreading ticks out like this keeps many instances of the same periodic work, such as retries
+/// or cache refreshes running on a fleet of machines, from all firing at the same moment.
+///
+/// [`interval`]: fn.interval.html
+///
+/// # Examples
+///
+/// ```
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use std::time::{Duration, Instant};
+///
+/// use async_std::prelude::*;
+/// use async_std::stream;
+///
+/// let start = Instant::now();
+/// let period = Duration::from_millis(20);
+/// let mut interval = stream::interval_with_jitter(period, Duration::from_millis(10));
+///
+/// interval.next().await;
+/// assert!(start.elapsed() >= period);
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn interval_with_jitter(dur: Duration, jitter: Duration) -> Interval {
+    let mut delay = Delay::new(dur);
+    let scheduled = delay.when();
+    delay.reset(scheduled + sample(jitter));
     Interval {
-        delay: Delay::new(dur),
+        delay,
+        scheduled,
         interval: dur,
+        jitter,
+        missed_tick_behavior: MissedTickBehavior::default(),
+    }
+}
+
+/// Picks a random duration between zero and `jitter`.
+fn sample(jitter: Duration) -> Duration {
+    if jitter == Duration::from_secs(0) {
+        return jitter;
+    }
+    jitter.mul_f64(f64::from(random(u32::MAX)) / f64::from(u32::MAX))
+}
+
+/// Defines the behavior of an [`Interval`] when it misses a tick.
+///
+/// A tick is missed when the stream is not polled for longer than the period of the interval,
+/// for example because the consumer was busy processing a previous tick.
+///
+/// The default behavior is [`Skip`].
+///
+/// [`Interval`]: struct.Interval.html
+/// [`Skip`]: #variant.Skip
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use async_std::stream::{self, MissedTickBehavior};
+///
+/// let mut interval = stream::interval(Duration::from_secs(1));
+/// assert_eq!(interval.missed_tick_behavior(), MissedTickBehavior::Skip);
+///
+/// interval.set_missed_tick_behavior(MissedTickBehavior::Burst);
+/// assert_eq!(interval.missed_tick_behavior(), MissedTickBehavior::Burst);
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum MissedTickBehavior {
+    /// Yields all missed ticks as fast as possible until the interval has caught up.
+    ///
+    /// Ticks keep being scheduled at multiples of the period from the original start time.
+    Burst,
+
+    /// Yields a single tick for all missed ticks and schedules the next one a full period after
+    /// the current time.
+    ///
+    /// The schedule is shifted by the time the interval was late.
+    Delay,
+
+    /// Yields a single tick for all missed ticks and schedules the next one at the next multiple
+    /// of the period from the original start time.
+    #[default]
+    Skip,
+}
+
+impl MissedTickBehavior {
+    /// Computes the deadline following `prev` if the tick happened at `now`.
+    fn next(self, prev: Instant, now: Instant, interval: Duration) -> Instant {
+        match self {
+            MissedTickBehavior::Burst => prev + interval,
+            MissedTickBehavior::Delay => now + interval,
+            MissedTickBehavior::Skip => next_interval(prev, now, interval),
+        }
     }
 }
 
@@ -61,7 +172,69 @@ pub fn interval(dur: Duration) -> Interval {
 #[derive(Debug)]
 pub struct Interval {
     delay: Delay,
+
+    /// The time the next tick is scheduled at, before jitter.
+    scheduled: Instant,
+
     interval: Duration,
+    jitter: Duration,
+    missed_tick_behavior: MissedTickBehavior,
+}
+
+impl Interval {
+    /// Returns the behavior of this interval when it misses a tick.
+    pub fn missed_tick_behavior(&self) -> MissedTickBehavior {
+        self.missed_tick_behavior
+    }
+
+    /// Sets the behavior of this interval when it misses a tick.
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+
+    /// Returns the maximum random delay added to each tick.
+    ///
+    /// This is zero unless the interval was created with [`interval_with_jitter`].
+    ///
+    /// [`interval_with_jitter`]: fn.interval_with_jitter.html
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    /// Resets the interval so that the next tick happens one period from now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # fn main() { async_std::task::block_on(async {
+    /// #
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use async_std::prelude::*;
+    /// use async_std::stream;
+    /// use async_std::task;
+    ///
+    /// let start = Instant::now();
+    /// let mut interval = stream::interval(Duration::from_millis(100));
+    ///
+    /// task::sleep(Duration::from_millis(50)).await;
+    /// interval.reset();
+    ///
+    /// interval.next().await;
+    /// assert!(start.elapsed() >= Duration::from_millis(150));
+    /// #
+    /// # }) }
+    /// ```
+    pub fn reset(&mut self) {
+        let now = self.delay.now();
+        self.schedule(now + self.interval);
+    }
+
+    /// Schedules the next tick at `scheduled`, plus jitter.
+    fn schedule(&mut self, scheduled: Instant) {
+        self.scheduled = scheduled;
+        self.delay.reset(scheduled + sample(self.jitter));
+    }
 }
 
 impl Stream for Interval {
@@ -71,9 +244,10 @@ impl Stream for Interval {
         if Pin::new(&mut self.delay).poll(cx).is_pending() {
             return Poll::Pending;
         }
-        let when = Instant::now();
-        let next = next_interval(when, Instant::now(), self.interval);
-        self.delay.reset(next);
+        let next = self
+            .missed_tick_behavior
+            .next(self.scheduled, self.delay.now(), self.interval);
+        self.schedule(next);
         Poll::Ready(Some(()))
     }
 }
@@ -114,7 +288,7 @@ fn next_interval(prev: Instant, now: Instant, interval: Duration) -> Instant {
 
 #[cfg(test)]
 mod test {
-    use super::next_interval;
+    use super::{next_interval, MissedTickBehavior};
     use std::cmp::Ordering;
     use std::time::{Duration, Instant};
 
@@ -180,6 +354,23 @@ mod test {
         ));
     }
 
+    #[test]
+    fn missed_tick_behavior() {
+        let tm = Timeline::new();
+        assert!(almost_eq(
+            MissedTickBehavior::Burst.next(tm.at(10), tm.at(35), dur(10)),
+            tm.at(20)
+        ));
+        assert!(almost_eq(
+            MissedTickBehavior::Delay.next(tm.at(10), tm.at(35), dur(10)),
+            tm.at(45)
+        ));
+        assert!(almost_eq(
+            MissedTickBehavior::Skip.next(tm.at(10), tm.at(35), dur(10)),
+            tm.at(40)
+        ));
+    }
+
     /// TODO: this test actually should be successful, but since we can't
     ///       multiply Duration on anything larger than u32 easily we decided
     ///       to allow it to fail for now
@@ -322,22 +322,26 @@ cfg_unstable! {
     pub mod double_ended_stream;
     mod exact_size_stream;
     mod extend;
+    mod from_async_fn;
     mod from_stream;
     mod fused_stream;
     mod interval;
     mod into_stream;
     mod product;
+    mod repeat_with_interval;
     mod successors;
     mod sum;
 
-    pub use double_ended_stream::DoubleEndedStream;
+    pub use double_ended_stream::{DoubleEndedStream, Rev};
     pub use exact_size_stream::ExactSizeStream;
     pub use extend::{extend, Extend};
+    pub use from_async_fn::{from_async_fn, FromAsyncFn};
     pub use from_stream::FromStream;
     pub use fused_stream::FusedStream;
-    pub use interval::{interval, Interval};
+    pub use interval::{interval, interval_with_jitter, Interval, MissedTickBehavior};
     pub use into_stream::IntoStream;
     pub use product::Product;
+    pub use repeat_with_interval::{repeat_with_interval, RepeatWithInterval};
     pub use stream::Merge;
     pub use successors::{successors, Successors};
     pub use sum::Sum;
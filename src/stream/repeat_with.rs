@@ -18,6 +18,10 @@ impl<F> Unpin for RepeatWith<F> {}
 
 /// Creates a new stream that repeats elements of type `A` endlessly by applying the provided closure.
 ///
+/// To call the closure periodically instead, use [`repeat_with_interval`].
+///
+/// [`repeat_with_interval`]: fn.repeat_with_interval.html
+///
 /// # Examples
 ///
 /// Basic usage:
@@ -0,0 +1,80 @@
+use std::pin::Pin;
+use std::time::Duration;
+
+use crate::stream::{Interval, Stream};
+use crate::task::{Context, Poll};
+
+/// A stream that calls a closure at a set interval.
+///
+/// This stream is created by the [`repeat_with_interval`] function. See its
+/// documentation for more.
+///
+/// [`repeat_with_interval`]: fn.repeat_with_interval.html
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct RepeatWithInterval<F> {
+    f: F,
+    interval: Interval,
+}
+
+impl<F> Unpin for RepeatWithInterval<F> {}
+
+/// Creates a new stream that yields the result of a closure at a set interval.
+///
+/// The closure is first called after `dur`, and every `dur` after that, with ticks scheduled like
+/// with [`interval`]. This makes periodic sampling, like reading a metric every second, a
+/// one-liner.
+///
+/// [`interval`]: fn.interval.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::{Duration, Instant};
+///
+/// use async_std::prelude::*;
+/// use async_std::stream;
+///
+/// let start = Instant::now();
+/// let mut samples = stream::repeat_with_interval(|| start.elapsed(), Duration::from_millis(10));
+///
+/// assert!(samples.next().await.unwrap() >= Duration::from_millis(10));
+/// assert!(samples.next().await.unwrap() >= Duration::from_millis(20));
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn repeat_with_interval<T, F>(f: F, dur: Duration) -> RepeatWithInterval<F>
+where
+    F: FnMut() -> T,
+{
+    RepeatWithInterval {
+        f,
+        interval: crate::stream::interval(dur),
+    }
+}
+
+impl<F> RepeatWithInterval<F> {
+    /// Returns the interval driving the stream.
+    ///
+    /// This can be used to change how missed ticks are handled.
+    pub fn interval_mut(&mut self) -> &mut Interval {
+        &mut self.interval
+    }
+}
+
+impl<T, F> Stream for RepeatWithInterval<F>
+where
+    F: FnMut() -> T,
+{
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        futures_core::ready!(Pin::new(&mut self.interval).poll_next(cx));
+        let item = (self.f)();
+        Poll::Ready(Some(item))
+    }
+}
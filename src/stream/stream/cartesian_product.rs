@@ -0,0 +1,86 @@
+use std::fmt;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::stream::stream::StreamExt;
+use crate::stream::{Fuse, Stream};
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// A stream that pairs every item of a stream with every item of another one.
+    ///
+    /// This `struct` is created by the [`cartesian_product`] method on [`Stream`]. See its
+    /// documentation for more.
+    ///
+    /// [`cartesian_product`]: trait.Stream.html#method.cartesian_product
+    /// [`Stream`]: trait.Stream.html
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub struct CartesianProduct<A: Stream, B> {
+        #[pin]
+        first: Fuse<A>,
+        #[pin]
+        second: B,
+        original: B,
+        current: Option<A::Item>,
+    }
+}
+
+impl<A: Stream + fmt::Debug, B: fmt::Debug> fmt::Debug for CartesianProduct<A, B> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("CartesianProduct")
+            .field("first", &self.first)
+            .field("second", &self.second)
+            .finish()
+    }
+}
+
+impl<A: Stream, B: Clone> CartesianProduct<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first: first.fuse(),
+            second: second.clone(),
+            original: second,
+            current: None,
+        }
+    }
+}
+
+impl<A, B> Stream for CartesianProduct<A, B>
+where
+    A: Stream,
+    A::Item: Clone,
+    B: Stream + Clone,
+{
+    type Item = (A::Item, B::Item);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Start a new pass over the second stream for every item of the first one.
+        if this.current.is_none() {
+            match futures_core::ready!(this.first.as_mut().poll_next(cx)) {
+                Some(item) => {
+                    *this.current = Some(item);
+                    this.second.set(this.original.clone());
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+
+        match futures_core::ready!(this.second.as_mut().poll_next(cx)) {
+            Some(item) => {
+                let current = this.current.as_ref().unwrap().clone();
+                Poll::Ready(Some((current, item)))
+            }
+            None => {
+                // The pass is over. Yield instead of looping so that an empty second stream can't
+                // make this stream spin on an endless first one.
+                *this.current = None;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+}
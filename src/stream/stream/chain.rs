@@ -58,3 +58,33 @@ impl<S: Stream, U: Stream<Item = S::Item>> Stream for Chain<S, U> {
         Poll::Pending
     }
 }
+
+#[cfg(feature = "unstable")]
+impl<S, U> DoubleEndedStream for Chain<S, U>
+where
+    S: DoubleEndedStream,
+    U: DoubleEndedStream<Item = S::Item>,
+{
+    fn poll_next_back(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if !this.second.done {
+            let next = futures_core::ready!(this.second.as_mut().poll_next_back(cx));
+            if let Some(next) = next {
+                return Poll::Ready(Some(next));
+            }
+        }
+
+        if !this.first.done {
+            let next = futures_core::ready!(this.first.as_mut().poll_next_back(cx));
+            if let Some(next) = next {
+                return Poll::Ready(Some(next));
+            }
+        }
+
+        if this.first.done && this.second.done {
+            return Poll::Ready(None);
+        }
+
+        Poll::Pending
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "unstable")]
+use crate::stream::DoubleEndedStream;
 use crate::stream::Stream;
 use crate::task::{Context, Poll};
 use pin_project_lite::pin_project;
@@ -31,3 +33,16 @@ where
         Poll::Ready(next.cloned())
     }
 }
+
+#[cfg(feature = "unstable")]
+impl<'a, S, T: 'a> DoubleEndedStream for Cloned<S>
+where
+    S: DoubleEndedStream<Item = &'a T>,
+    T: Clone,
+{
+    fn poll_next_back(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let next = futures_core::ready!(this.stream.poll_next_back(cx));
+        Poll::Ready(next.cloned())
+    }
+}
@@ -0,0 +1,71 @@
+use std::cmp::Ordering;
+use std::future::Future;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use super::fuse::Fuse;
+use crate::prelude::*;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+pin_project! {
+    // Lexicographically compares the elements of this `Stream` with those
+    // of another using a comparison function.
+    #[doc(hidden)]
+    #[allow(missing_debug_implementations)]
+    pub struct CmpByFuture<L: Stream, R: Stream, F> {
+        #[pin]
+        l: Fuse<L>,
+        #[pin]
+        r: Fuse<R>,
+        l_cache: Option<L::Item>,
+        r_cache: Option<R::Item>,
+        cmp: F,
+    }
+}
+
+impl<L: Stream, R: Stream, F> CmpByFuture<L, R, F> {
+    pub(super) fn new(l: L, r: R, cmp: F) -> Self {
+        Self {
+            l: l.fuse(),
+            r: r.fuse(),
+            l_cache: None,
+            r_cache: None,
+            cmp,
+        }
+    }
+}
+
+impl<L, R, F> Future for CmpByFuture<L, R, F>
+where
+    L: Stream,
+    R: Stream,
+    F: FnMut(&L::Item, &R::Item) -> Ordering,
+{
+    type Output = Ordering;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            // Keep an item that is ready while waiting for the other stream.
+            if !this.l.done && this.l_cache.is_none() {
+                *this.l_cache = futures_core::ready!(this.l.as_mut().poll_next(cx));
+            }
+            if !this.r.done && this.r_cache.is_none() {
+                *this.r_cache = futures_core::ready!(this.r.as_mut().poll_next(cx));
+            }
+
+            // Stream that completes earliest can be considered Less, etc
+            match (this.l_cache.take(), this.r_cache.take()) {
+                (Some(l), Some(r)) => match (this.cmp)(&l, &r) {
+                    Ordering::Equal => {}
+                    result => return Poll::Ready(result),
+                },
+                (None, None) => return Poll::Ready(Ordering::Equal),
+                (None, Some(_)) => return Poll::Ready(Ordering::Less),
+                (Some(_), None) => return Poll::Ready(Ordering::Greater),
+            }
+        }
+    }
+}
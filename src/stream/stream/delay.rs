@@ -14,7 +14,7 @@ pin_project! {
         #[pin]
         stream: S,
         #[pin]
-        delay: futures_timer::Delay,
+        delay: crate::time::Delay,
         delay_done: bool,
     }
 }
@@ -23,7 +23,7 @@ impl<S> Delay<S> {
     pub(super) fn new(stream: S, dur: Duration) -> Self {
         Delay {
             stream,
-            delay: futures_timer::Delay::new(dur),
+            delay: crate::time::Delay::new(dur),
             delay_done: false,
         }
     }
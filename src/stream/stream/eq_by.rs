@@ -0,0 +1,70 @@
+use std::future::Future;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use super::fuse::Fuse;
+use crate::prelude::*;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+pin_project! {
+    // Checks whether the elements of this `Stream` are equal to those of
+    // another with respect to a function.
+    #[doc(hidden)]
+    #[allow(missing_debug_implementations)]
+    pub struct EqByFuture<L: Stream, R: Stream, F> {
+        #[pin]
+        l: Fuse<L>,
+        #[pin]
+        r: Fuse<R>,
+        l_cache: Option<L::Item>,
+        r_cache: Option<R::Item>,
+        eq: F,
+    }
+}
+
+impl<L: Stream, R: Stream, F> EqByFuture<L, R, F> {
+    pub(super) fn new(l: L, r: R, eq: F) -> Self {
+        Self {
+            l: l.fuse(),
+            r: r.fuse(),
+            l_cache: None,
+            r_cache: None,
+            eq,
+        }
+    }
+}
+
+impl<L, R, F> Future for EqByFuture<L, R, F>
+where
+    L: Stream,
+    R: Stream,
+    F: FnMut(&L::Item, &R::Item) -> bool,
+{
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        loop {
+            // Keep an item that is ready while waiting for the other stream.
+            if !this.l.done && this.l_cache.is_none() {
+                *this.l_cache = futures_core::ready!(this.l.as_mut().poll_next(cx));
+            }
+            if !this.r.done && this.r_cache.is_none() {
+                *this.r_cache = futures_core::ready!(this.r.as_mut().poll_next(cx));
+            }
+
+            match (this.l_cache.take(), this.r_cache.take()) {
+                (Some(l), Some(r)) => {
+                    if !(this.eq)(&l, &r) {
+                        return Poll::Ready(false);
+                    }
+                }
+                (None, None) => return Poll::Ready(true),
+                // One stream is longer than the other.
+                _ => return Poll::Ready(false),
+            }
+        }
+    }
+}
@@ -2,6 +2,8 @@ use std::pin::Pin;
 
 use pin_project_lite::pin_project;
 
+#[cfg(feature = "unstable")]
+use crate::stream::DoubleEndedStream;
 use crate::stream::Stream;
 use crate::task::{Context, Poll};
 
@@ -51,3 +53,24 @@ where
         }
     }
 }
+
+#[cfg(feature = "unstable")]
+impl<S, P> DoubleEndedStream for Filter<S, P>
+where
+    S: DoubleEndedStream,
+    P: FnMut(&S::Item) -> bool,
+{
+    fn poll_next_back(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let next = futures_core::ready!(this.stream.poll_next_back(cx));
+
+        match next {
+            Some(v) if (this.predicate)(&v) => Poll::Ready(Some(v)),
+            Some(_) => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            None => Poll::Ready(None),
+        }
+    }
+}
@@ -3,6 +3,8 @@ use std::task::{Context, Poll};
 
 use pin_project_lite::pin_project;
 
+#[cfg(feature = "unstable")]
+use crate::stream::DoubleEndedStream;
 use crate::stream::Stream;
 
 pin_project! {
@@ -42,3 +44,25 @@ where
         }
     }
 }
+
+#[cfg(feature = "unstable")]
+impl<S, F, B> DoubleEndedStream for FilterMap<S, F>
+where
+    S: DoubleEndedStream,
+    F: FnMut(S::Item) -> Option<B>,
+{
+    fn poll_next_back(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let next = futures_core::ready!(this.stream.poll_next_back(cx));
+        match next {
+            Some(v) => match (this.f)(v) {
+                Some(b) => Poll::Ready(Some(b)),
+                None => {
+                    cx.waker().wake_by_ref();
+                    Poll::Pending
+                }
+            },
+            None => Poll::Ready(None),
+        }
+    }
+}
@@ -2,6 +2,8 @@ use std::pin::Pin;
 
 use pin_project_lite::pin_project;
 
+#[cfg(feature = "unstable")]
+use crate::stream::DoubleEndedStream;
 use crate::stream::Stream;
 use crate::task::{Context, Poll};
 
@@ -46,3 +48,19 @@ impl<S: Stream> Stream for Fuse<S> {
         }
     }
 }
+
+#[cfg(feature = "unstable")]
+impl<S: DoubleEndedStream> DoubleEndedStream for Fuse<S> {
+    fn poll_next_back(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        let this = self.project();
+        if *this.done {
+            Poll::Ready(None)
+        } else {
+            let next = futures_core::ready!(this.stream.poll_next_back(cx));
+            if next.is_none() {
+                *this.done = true;
+            }
+            Poll::Ready(next)
+        }
+    }
+}
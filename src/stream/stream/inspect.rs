@@ -2,6 +2,8 @@ use std::pin::Pin;
 
 use pin_project_lite::pin_project;
 
+#[cfg(feature = "unstable")]
+use crate::stream::DoubleEndedStream;
 use crate::stream::Stream;
 use crate::task::{Context, Poll};
 
@@ -47,3 +49,17 @@ where
         }))
     }
 }
+
+#[cfg(feature = "unstable")]
+impl<S, F> DoubleEndedStream for Inspect<S, F>
+where
+    S: DoubleEndedStream,
+    F: FnMut(&S::Item),
+{
+    fn poll_next_back(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        let next = futures_core::ready!(this.stream.as_mut().poll_next_back(cx));
+
+        Poll::Ready(next.inspect(|x| (this.f)(x)))
+    }
+}
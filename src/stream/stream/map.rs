@@ -2,6 +2,8 @@ use std::pin::Pin;
 
 use pin_project_lite::pin_project;
 
+#[cfg(feature = "unstable")]
+use crate::stream::DoubleEndedStream;
 use crate::stream::Stream;
 use crate::task::{Context, Poll};
 
@@ -37,3 +39,16 @@ where
         Poll::Ready(next.map(this.f))
     }
 }
+
+#[cfg(feature = "unstable")]
+impl<S, F, B> DoubleEndedStream for Map<S, F>
+where
+    S: DoubleEndedStream,
+    F: FnMut(S::Item) -> B,
+{
+    fn poll_next_back(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let next = futures_core::ready!(this.stream.poll_next_back(cx));
+        Poll::Ready(next.map(this.f))
+    }
+}
@@ -0,0 +1,85 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::stream::stream::StreamExt;
+use crate::stream::{Fuse, Stream};
+use crate::task::{Context, Poll};
+
+pin_project! {
+    /// A stream that merges two sorted streams into one sorted stream.
+    ///
+    /// This `struct` is created by the [`merge_by`] method on [`Stream`]. See its
+    /// documentation for more.
+    ///
+    /// [`merge_by`]: trait.Stream.html#method.merge_by
+    /// [`Stream`]: trait.Stream.html
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub struct MergeBy<L: Stream, R: Stream, F> {
+        #[pin]
+        left: Fuse<L>,
+        #[pin]
+        right: Fuse<R>,
+        left_cache: Option<L::Item>,
+        right_cache: Option<R::Item>,
+        cmp: F,
+    }
+}
+
+impl<L: Stream + fmt::Debug, R: Stream + fmt::Debug, F> fmt::Debug for MergeBy<L, R, F> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("MergeBy")
+            .field("left", &self.left)
+            .field("right", &self.right)
+            .finish()
+    }
+}
+
+impl<L: Stream, R: Stream, F> MergeBy<L, R, F> {
+    pub(crate) fn new(left: L, right: R, cmp: F) -> Self {
+        Self {
+            left: left.fuse(),
+            right: right.fuse(),
+            left_cache: None,
+            right_cache: None,
+            cmp,
+        }
+    }
+}
+
+impl<L, R, F> Stream for MergeBy<L, R, F>
+where
+    L: Stream,
+    R: Stream<Item = L::Item>,
+    F: FnMut(&L::Item, &L::Item) -> Ordering,
+{
+    type Item = L::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // The next item can only be chosen once the head of both streams is known.
+        if !this.left.done && this.left_cache.is_none() {
+            *this.left_cache = futures_core::ready!(this.left.as_mut().poll_next(cx));
+        }
+        if !this.right.done && this.right_cache.is_none() {
+            *this.right_cache = futures_core::ready!(this.right.as_mut().poll_next(cx));
+        }
+
+        let take_left = match (&*this.left_cache, &*this.right_cache) {
+            // Prefer the left stream on ties so that the merge is stable.
+            (Some(l), Some(r)) => (this.cmp)(l, r) != Ordering::Greater,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+
+        if take_left {
+            Poll::Ready(this.left_cache.take())
+        } else {
+            Poll::Ready(this.right_cache.take())
+        }
+    }
+}
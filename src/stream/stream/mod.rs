@@ -121,19 +121,30 @@ cfg_unstable! {
     use crate::stream::{FromStream, Product, Sum};
     use crate::stream::Extend;
 
+    use cmp_by::CmpByFuture;
     use count::CountFuture;
+    use eq_by::EqByFuture;
     use partition::PartitionFuture;
     use unzip::UnzipFuture;
 
     pub use merge::Merge;
+    pub use merge_by::MergeBy;
     pub use flatten::Flatten;
     pub use flat_map::FlatMap;
     pub use timeout::{TimeoutError, Timeout};
     pub use throttle::Throttle;
     pub use delay::Delay;
+    pub use par_map::ParMap;
+    pub use zip_latest::ZipLatest;
+    pub use zip_longest::{EitherOrBoth, ZipLongest};
+    pub use cartesian_product::CartesianProduct;
+    pub use share::{LaggedError, Share};
 
+    mod cmp_by;
     mod count;
+    mod eq_by;
     mod merge;
+    mod merge_by;
     mod flatten;
     mod flat_map;
     mod partition;
@@ -141,6 +152,11 @@ cfg_unstable! {
     mod throttle;
     mod delay;
     mod unzip;
+    mod par_map;
+    mod zip_latest;
+    mod zip_longest;
+    mod cartesian_product;
+    mod share;
 }
 
 extension_trait! {
@@ -1784,6 +1800,134 @@ extension_trait! {
             Zip::new(self, other)
         }
 
+        #[doc = r#"
+            Combines two streams into a stream of pairs of their most recent items.
+
+            Whenever either stream yields an item, `zip_latest()` yields a tuple of the latest
+            item from each stream. Nothing is yielded until both streams have produced at least
+            one item.
+
+            This is useful when joining a stream of data with a stream of updates to some state
+            it depends on, like a configuration.
+
+            The stream ends once both streams have ended, or as soon as one of them ends without
+            ever producing an item.
+
+            # Examples
+
+            ```
+            # fn main() { async_std::task::block_on(async {
+            #
+            use async_std::prelude::*;
+            use async_std::stream;
+
+            let config = stream::once("v1");
+            let data = stream::from_iter(vec![1u8, 2, 3]);
+            let mut s = config.zip_latest(data);
+
+            let mut pairs = Vec::new();
+            while let Some(pair) = s.next().await {
+                pairs.push(pair);
+            }
+
+            assert!(pairs.contains(&("v1", 3)));
+            assert!(pairs.iter().all(|(c, _)| *c == "v1"));
+            #
+            # }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn zip_latest<U>(self, other: U) -> ZipLatest<Self, U>
+        where
+            Self: Sized,
+            Self::Item: Clone,
+            U: Stream,
+            U::Item: Clone,
+        {
+            ZipLatest::new(self, other)
+        }
+
+        #[doc = r#"
+            Combines two streams into a stream of pairs that lasts as long as the longer one.
+
+            Unlike [`zip`], the stream doesn't end when one of the streams ends. Instead, the
+            remaining items of the other stream are yielded on their own, tagged with the stream
+            they came from.
+
+            This is useful when walking two sorted streams side by side, for example to find
+            out how they differ.
+
+            [`zip`]: #method.zip
+
+            # Examples
+
+            ```
+            # fn main() { async_std::task::block_on(async {
+            #
+            use async_std::prelude::*;
+            use async_std::stream::{self, EitherOrBoth};
+
+            let l = stream::from_iter(vec![1u8, 2]);
+            let r = stream::from_iter(vec!['a', 'b', 'c']);
+            let mut s = l.zip_longest(r);
+
+            assert_eq!(s.next().await, Some(EitherOrBoth::Both(1, 'a')));
+            assert_eq!(s.next().await, Some(EitherOrBoth::Both(2, 'b')));
+            assert_eq!(s.next().await, Some(EitherOrBoth::Right('c')));
+            assert_eq!(s.next().await, None);
+            #
+            # }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn zip_longest<U>(self, other: U) -> ZipLongest<Self, U>
+        where
+            Self: Sized,
+            U: Stream,
+        {
+            ZipLongest::new(self, other)
+        }
+
+        #[doc = r#"
+            Combines two streams into a stream of all pairs of their items.
+
+            For every item of this stream, the `other` stream is cloned and run to completion,
+            and each of its items is paired with a clone of the item of this stream. The pairs
+            are yielded in lexicographic order.
+
+            This is useful for generating every combination of a few sets of parameters, like
+            a test matrix.
+
+            # Examples
+
+            ```
+            # fn main() { async_std::task::block_on(async {
+            #
+            use async_std::prelude::*;
+            use async_std::stream;
+
+            let sizes = stream::from_iter(vec![1u8, 2]);
+            let modes = stream::from_iter(vec!["r", "w"]);
+            let pairs: Vec<_> = sizes.cartesian_product(modes).collect().await;
+
+            assert_eq!(pairs, vec![(1, "r"), (1, "w"), (2, "r"), (2, "w")]);
+            #
+            # }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn cartesian_product<U>(self, other: U) -> CartesianProduct<Self, U>
+        where
+            Self: Sized,
+            Self::Item: Clone,
+            U: Stream + Clone,
+        {
+            CartesianProduct::new(self, other)
+        }
+
         #[doc = r#"
             Converts an stream of pairs into a pair of containers.
 
@@ -1885,6 +2029,45 @@ extension_trait! {
             FromStream::from_stream(self)
         }
 
+        #[doc = r#"
+            Transforms a stream of results into a collection, stopping at the first error.
+
+            This is equivalent to collecting into a `Result<B, E>`, but spells out the intent and
+            needs no annotation on the error type. The stream isn't polled past the first `Err`.
+
+            # Examples
+
+            ```
+            # fn main() -> Result<(), String> { async_std::task::block_on(async {
+            #
+            use std::collections::HashMap;
+
+            use async_std::prelude::*;
+            use async_std::stream;
+
+            let s = stream::from_iter(vec![Ok::<_, String>(("a", 1)), Ok(("b", 2))]);
+            let map: HashMap<&str, i32> = s.try_collect().await?;
+            assert_eq!(map["b"], 2);
+
+            let s = stream::from_iter(vec![Ok(1), Err("oops"), Ok(3)]);
+            let res: Result<Vec<i32>, &str> = s.try_collect().await;
+            assert_eq!(res, Err("oops"));
+            #
+            # Ok(()) }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn try_collect<'a, T, E, B>(
+            self,
+        ) -> impl Future<Output = Result<B, E>> + 'a [Pin<Box<dyn Future<Output = Result<B, E>> + 'a>>]
+        where
+            Self: Stream<Item = Result<T, E>> + Sized + 'a,
+            B: FromStream<T>,
+        {
+            FromStream::from_stream(self)
+        }
+
         #[doc = r#"
             Combines multiple streams into a single stream of all their outputs.
 
@@ -1920,6 +2103,148 @@ extension_trait! {
             Merge::new(self, other)
         }
 
+        #[doc = r#"
+            Merges two sorted streams into one sorted stream.
+
+            Both streams must be sorted with respect to `cmp`. The resulting stream yields the
+            items of both streams in that order, taking the item of this stream first when two
+            items compare equal. Unlike [`merge`], it waits for both streams before yielding an
+            item, so a slow stream holds up the other one.
+
+            This is useful for combining sorted runs, like pages of results fetched from
+            different sources, without collecting them first.
+
+            [`merge`]: #method.merge
+
+            # Examples
+
+            ```
+            # async_std::task::block_on(async {
+            use async_std::prelude::*;
+            use async_std::stream;
+
+            let a = stream::from_iter(vec![1u8, 4, 6]);
+            let b = stream::from_iter(vec![2u8, 3, 7]);
+
+            let v: Vec<u8> = a.merge_by(b, |x, y| x.cmp(y)).collect().await;
+            assert_eq!(v, vec![1, 2, 3, 4, 6, 7]);
+            # });
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn merge_by<U, F>(self, other: U, cmp: F) -> MergeBy<Self, U, F>
+        where
+            Self: Sized,
+            U: Stream<Item = Self::Item> + Sized,
+            F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+        {
+            MergeBy::new(self, other, cmp)
+        }
+
+        #[doc = r#"
+            Maps each element on the blocking thread pool.
+
+            CPU-heavy work such as compression or hashing would stall the async executor if it
+            ran inside a task. `par_map()` instead runs the closure for every element on the
+            same thread pool used by [`spawn_blocking`], with at most `limit` closures in
+            flight at a time.
+
+            Results are yielded in the same order as the elements of the original stream.
+
+            [`spawn_blocking`]: ../task/fn.spawn_blocking.html
+
+            # Panics
+
+            If `limit` is zero, this function will panic.
+
+            # Examples
+
+            ```
+            # fn main() { async_std::task::block_on(async {
+            #
+            use async_std::prelude::*;
+            use async_std::stream;
+
+            let s = stream::from_iter(vec![1u64, 2, 3, 4]);
+            let mut s = s.par_map(2, |n| (1..=n).product::<u64>());
+
+            assert_eq!(s.next().await, Some(1));
+            assert_eq!(s.next().await, Some(2));
+            assert_eq!(s.next().await, Some(6));
+            assert_eq!(s.next().await, Some(24));
+            assert_eq!(s.next().await, None);
+            #
+            # }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn par_map<B, F>(self, limit: usize, f: F) -> ParMap<Self, F, B>
+        where
+            Self: Sized,
+            Self::Item: Send + 'static,
+            F: Fn(Self::Item) -> B + Send + Sync + 'static,
+            B: Send + 'static,
+        {
+            ParMap::new(self, limit, f)
+        }
+
+        #[doc = r#"
+            Turns this stream into a handle that can be cloned to feed several consumers.
+
+            Every handle created by cloning the returned [`Share`] receives every item of this
+            stream, starting at the position of the handle it was cloned from. Items are
+            received from this stream only once and cloned for every handle.
+
+            At most `cap` of the most recent items are buffered for handles that fall behind.
+            When a handle misses items because they were overwritten by newer ones, it yields a
+            [`LaggedError`] reporting how many items were skipped and then continues with the
+            oldest item still buffered.
+
+            [`Share`]: struct.Share.html
+            [`LaggedError`]: struct.LaggedError.html
+
+            # Panics
+
+            If `cap` is zero, this function will panic.
+
+            # Examples
+
+            ```
+            # fn main() { async_std::task::block_on(async {
+            #
+            use async_std::prelude::*;
+            use async_std::stream;
+
+            let mut a = stream::from_iter(vec![1u8, 2, 3]).share(2);
+            let mut b = a.clone();
+
+            assert_eq!(a.next().await, Some(Ok(1)));
+            assert_eq!(a.next().await, Some(Ok(2)));
+            assert_eq!(b.next().await, Some(Ok(1)));
+
+            assert_eq!(a.next().await, Some(Ok(3)));
+            assert_eq!(a.next().await, None);
+
+            // Item `2` is still buffered, but `1` was overwritten by `3`.
+            assert_eq!(b.next().await, Some(Ok(2)));
+            assert_eq!(b.next().await, Some(Ok(3)));
+            assert_eq!(b.next().await, None);
+            #
+            # }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn share(self, cap: usize) -> Share<Self>
+        where
+            Self: Sized,
+            Self::Item: Clone,
+        {
+            Share::new(self, cap)
+        }
+
         #[doc = r#"
             Lexicographically compares the elements of this `Stream` with those
             of another.
@@ -2037,6 +2362,43 @@ extension_trait! {
             CmpFuture::new(self, other)
         }
 
+        #[doc = r#"
+            Lexicographically compares the elements of this `Stream` with those
+            of another with respect to the specified comparison function.
+
+            # Examples
+
+            ```
+            # fn main() { async_std::task::block_on(async {
+            #
+            use async_std::prelude::*;
+            use async_std::stream;
+            use std::cmp::Ordering;
+
+            let a = stream::from_iter(vec!["a", "bb"]);
+            let b = stream::from_iter(vec![1usize, 3]);
+
+            let by_len = a.cmp_by(b, |x, y| x.len().cmp(y));
+            assert_eq!(by_len.await, Ordering::Less);
+            #
+            # }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn cmp_by<S, F>(
+           self,
+           other: S,
+           cmp: F,
+        ) -> impl Future<Output = Ordering> [CmpByFuture<Self, S, F>]
+        where
+            Self: Sized + Stream,
+            S: Stream,
+            F: FnMut(&Self::Item, &S::Item) -> Ordering,
+        {
+            CmpByFuture::new(self, other, cmp)
+        }
+
         #[doc = r#"
             Counts the number of elements in the stream.
 
@@ -2177,6 +2539,44 @@ extension_trait! {
             EqFuture::new(self, other)
         }
 
+        #[doc = r#"
+            Determines if the elements of this `Stream` are equal to those of
+            another with respect to the specified equality function.
+
+            # Examples
+
+            ```
+            # fn main() { async_std::task::block_on(async {
+            #
+            use async_std::prelude::*;
+            use async_std::stream;
+
+            let a = stream::from_iter(vec!["1", "2", "3"]);
+            let b = stream::from_iter(vec![1, 2, 3]);
+            assert!(a.eq_by(b, |x, y| x.parse::<i32>() == Ok(*y)).await);
+
+            let a = stream::from_iter(vec!["1", "2"]);
+            let b = stream::from_iter(vec![1, 2, 3]);
+            assert!(!a.eq_by(b, |x, y| x.parse::<i32>() == Ok(*y)).await);
+            #
+            # }) }
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn eq_by<S, F>(
+           self,
+           other: S,
+           eq: F,
+        ) -> impl Future<Output = bool> [EqByFuture<Self, S, F>]
+        where
+            Self: Sized + Stream,
+            S: Sized + Stream,
+            F: FnMut(&Self::Item, &S::Item) -> bool,
+        {
+            EqByFuture::new(self, other, eq)
+        }
+
         #[doc = r#"
             Determines if the elements of this `Stream` are lexicographically
             greater than those of another.
@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use pin_project_lite::pin_project;
+
+use crate::stream::stream::StreamExt;
+use crate::stream::{Fuse, Stream};
+use crate::task::{self, Context, JoinHandle, Poll};
+
+pin_project! {
+    /// A stream that maps each element on the blocking thread pool.
+    ///
+    /// This `struct` is created by the [`par_map`] method on [`Stream`]. See its
+    /// documentation for more.
+    ///
+    /// [`par_map`]: trait.Stream.html#method.par_map
+    /// [`Stream`]: trait.Stream.html
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub struct ParMap<S, F, B> {
+        #[pin]
+        stream: Fuse<S>,
+        f: Arc<F>,
+        limit: usize,
+        pending: VecDeque<JoinHandle<B>>,
+    }
+}
+
+impl<S: Stream, F, B> ParMap<S, F, B> {
+    pub(super) fn new(stream: S, limit: usize, f: F) -> Self {
+        assert!(limit > 0, "limit must be positive");
+
+        ParMap {
+            stream: stream.fuse(),
+            f: Arc::new(f),
+            limit,
+            pending: VecDeque::with_capacity(limit),
+        }
+    }
+}
+
+impl<S, F, B> Stream for ParMap<S, F, B>
+where
+    S: Stream,
+    S::Item: Send + 'static,
+    F: Fn(S::Item) -> B + Send + Sync + 'static,
+    B: Send + 'static,
+{
+    type Item = B;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Hand items over to the blocking pool until the limit is reached.
+        while this.pending.len() < *this.limit {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let f = this.f.clone();
+                    this.pending
+                        .push_back(task::spawn_blocking(move || f(item)));
+                }
+                Poll::Ready(None) | Poll::Pending => break,
+            }
+        }
+
+        // Yield results in the same order the items were received.
+        match this.pending.front_mut() {
+            Some(handle) => match Pin::new(handle).poll(cx) {
+                Poll::Ready(v) => {
+                    this.pending.pop_front();
+                    Poll::Ready(Some(v))
+                }
+                Poll::Pending => Poll::Pending,
+            },
+            None if this.stream.done => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<S: fmt::Debug, F, B> fmt::Debug for ParMap<S, F, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParMap")
+            .field("stream", &self.stream)
+            .field("limit", &self.limit)
+            .field("pending", &self.pending.len())
+            .finish()
+    }
+}
@@ -0,0 +1,185 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::Waker;
+
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A stream that can be cloned into several handles, each receiving every item.
+///
+/// This `struct` is created by the [`share`] method on [`Stream`]. See its
+/// documentation for more.
+///
+/// [`share`]: trait.Stream.html#method.share
+/// [`Stream`]: trait.Stream.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Share<S: Stream> {
+    /// The state shared between all handles.
+    inner: Arc<Mutex<Inner<S>>>,
+
+    /// The sequence number of the next item this handle will receive.
+    pos: u64,
+}
+
+/// The state shared between all handles of a `Share`.
+struct Inner<S: Stream> {
+    /// The upstream stream.
+    stream: Pin<Box<S>>,
+
+    /// Set to `true` once the upstream stream has been exhausted.
+    done: bool,
+
+    /// The most recently received items, oldest first.
+    buffer: VecDeque<S::Item>,
+
+    /// The maximum number of items kept in `buffer`.
+    cap: usize,
+
+    /// The sequence number of the first item in `buffer`.
+    offset: u64,
+
+    /// Handles waiting for the next item.
+    wakers: Vec<Waker>,
+}
+
+impl<S: Stream> Inner<S> {
+    /// Wakes up all handles waiting for the next item.
+    fn wake_all(&mut self) {
+        for w in self.wakers.drain(..) {
+            w.wake();
+        }
+    }
+}
+
+impl<S: Stream> Share<S> {
+    pub(super) fn new(stream: S, cap: usize) -> Self {
+        assert!(cap > 0, "capacity must be positive");
+
+        let inner = Inner {
+            stream: Box::pin(stream),
+            done: false,
+            buffer: VecDeque::with_capacity(cap),
+            cap,
+            offset: 0,
+            wakers: Vec::new(),
+        };
+        Share {
+            inner: Arc::new(Mutex::new(inner)),
+            pos: 0,
+        }
+    }
+}
+
+impl<S> Stream for Share<S>
+where
+    S: Stream,
+    S::Item: Clone,
+{
+    type Item = Result<S::Item, LaggedError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let mut inner = this.inner.lock().unwrap();
+
+        // Items this handle hasn't received yet were overwritten by newer ones.
+        if this.pos < inner.offset {
+            let skipped = inner.offset - this.pos;
+            this.pos = inner.offset;
+            return Poll::Ready(Some(Err(LaggedError { skipped })));
+        }
+
+        // Another handle has already received the next item from upstream.
+        let index = (this.pos - inner.offset) as usize;
+        if let Some(item) = inner.buffer.get(index) {
+            let item = item.clone();
+            this.pos += 1;
+            return Poll::Ready(Some(Ok(item)));
+        }
+
+        if inner.done {
+            return Poll::Ready(None);
+        }
+
+        // This handle is the first one to need the next item, so receive it from upstream.
+        match inner.stream.as_mut().poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                if inner.buffer.len() == inner.cap {
+                    inner.buffer.pop_front();
+                    inner.offset += 1;
+                }
+                inner.buffer.push_back(item.clone());
+                inner.wake_all();
+
+                this.pos += 1;
+                Poll::Ready(Some(Ok(item)))
+            }
+            Poll::Ready(None) => {
+                inner.done = true;
+                inner.wake_all();
+                Poll::Ready(None)
+            }
+            Poll::Pending => {
+                if !inner.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+                    inner.wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<S: Stream> Clone for Share<S> {
+    /// Creates a new handle that receives the same items as this one, starting from the same
+    /// position.
+    fn clone(&self) -> Share<S> {
+        Share {
+            inner: self.inner.clone(),
+            pos: self.pos,
+        }
+    }
+}
+
+impl<S: Stream> Drop for Share<S> {
+    fn drop(&mut self) {
+        // The upstream stream may be holding the waker of this handle, so make sure the remaining
+        // handles get a chance to poll it instead.
+        if let Ok(mut inner) = self.inner.lock() {
+            inner.wake_all();
+        }
+    }
+}
+
+impl<S: Stream> fmt::Debug for Share<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share").field("pos", &self.pos).finish()
+    }
+}
+
+/// An error returned when a [`Share`] handle fell behind and missed some items.
+///
+/// [`Share`]: struct.Share.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LaggedError {
+    skipped: u64,
+}
+
+impl LaggedError {
+    /// Returns the number of items that were missed.
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+}
+
+impl Error for LaggedError {}
+
+impl fmt::Display for LaggedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "stream lagged behind by {} items", self.skipped)
+    }
+}
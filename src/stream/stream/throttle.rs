@@ -1,12 +1,12 @@
 use std::future::Future;
 use std::pin::Pin;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
-use futures_timer::Delay;
 use pin_project_lite::pin_project;
 
 use crate::stream::Stream;
 use crate::task::{Context, Poll};
+use crate::time::Delay;
 
 pin_project! {
     /// A stream that only yields one element once every `duration`.
@@ -59,7 +59,8 @@ impl<S: Stream> Stream for Throttle<S> {
             Poll::Ready(None) => Poll::Ready(None),
             Poll::Ready(Some(v)) => {
                 *this.blocked = true;
-                this.delay.reset(Instant::now() + *this.duration);
+                let now = this.delay.now();
+                this.delay.reset(now + *this.duration);
                 Poll::Ready(Some(v))
             }
         }
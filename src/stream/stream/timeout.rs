@@ -4,11 +4,11 @@ use std::pin::Pin;
 use std::time::Duration;
 use std::future::Future;
 
-use futures_timer::Delay;
 use pin_project_lite::pin_project;
 
 use crate::stream::Stream;
 use crate::task::{Context, Poll};
+use crate::time::Delay;
 
 pin_project! {
     /// A stream with timeout time set
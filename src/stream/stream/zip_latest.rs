@@ -0,0 +1,102 @@
+use std::fmt;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::stream::stream::StreamExt;
+use crate::stream::{Fuse, Stream};
+use crate::task::{Context, Poll};
+use crate::utils;
+
+pin_project! {
+    /// A stream that yields the latest items of two other streams whenever either one updates.
+    ///
+    /// This `struct` is created by the [`zip_latest`] method on [`Stream`]. See its
+    /// documentation for more.
+    ///
+    /// [`zip_latest`]: trait.Stream.html#method.zip_latest
+    /// [`Stream`]: trait.Stream.html
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub struct ZipLatest<A: Stream, B: Stream> {
+        #[pin]
+        first: Fuse<A>,
+        #[pin]
+        second: Fuse<B>,
+        first_latest: Option<A::Item>,
+        second_latest: Option<B::Item>,
+    }
+}
+
+impl<A: Stream + fmt::Debug, B: Stream + fmt::Debug> fmt::Debug for ZipLatest<A, B> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ZipLatest")
+            .field("first", &self.first)
+            .field("second", &self.second)
+            .finish()
+    }
+}
+
+impl<A: Stream, B: Stream> ZipLatest<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first: first.fuse(),
+            second: second.fuse(),
+            first_latest: None,
+            second_latest: None,
+        }
+    }
+}
+
+impl<A, B> Stream for ZipLatest<A, B>
+where
+    A: Stream,
+    B: Stream,
+    A::Item: Clone,
+    B::Item: Clone,
+{
+    type Item = (A::Item, B::Item);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Pick a random stream to poll first so that a busy stream can't starve the other one.
+        let first_is_first = utils::random(2) == 0;
+        let mut updated = false;
+
+        for i in 0..2 {
+            if (i == 0) == first_is_first {
+                if let Poll::Ready(Some(item)) = this.first.as_mut().poll_next(cx) {
+                    *this.first_latest = Some(item);
+                    updated = true;
+                }
+            } else if let Poll::Ready(Some(item)) = this.second.as_mut().poll_next(cx) {
+                *this.second_latest = Some(item);
+                updated = true;
+            }
+
+            if updated {
+                if let (Some(a), Some(b)) = (&*this.first_latest, &*this.second_latest) {
+                    return Poll::Ready(Some((a.clone(), b.clone())));
+                }
+            }
+        }
+
+        // The stream is exhausted once both inputs are, or once an input ends without ever
+        // producing an item.
+        let first_done = this.first.done;
+        let second_done = this.second.done;
+        if (first_done && (second_done || this.first_latest.is_none()))
+            || (second_done && this.second_latest.is_none())
+        {
+            Poll::Ready(None)
+        } else {
+            // An input produced an item but the other one hasn't produced anything yet, so the
+            // input that updated must be polled again to register for a wakeup.
+            if updated {
+                cx.waker().wake_by_ref();
+            }
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::pin::Pin;
+
+use pin_project_lite::pin_project;
+
+use crate::stream::stream::StreamExt;
+use crate::stream::{Fuse, Stream};
+use crate::task::{Context, Poll};
+
+/// A value from either or both of two zipped streams.
+///
+/// This `enum` is the item type of the [`ZipLongest`] stream.
+///
+/// [`ZipLongest`]: struct.ZipLongest.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EitherOrBoth<A, B> {
+    /// Both streams yielded an item.
+    Both(A, B),
+
+    /// Only the first stream yielded an item, because the second one has ended.
+    Left(A),
+
+    /// Only the second stream yielded an item, because the first one has ended.
+    Right(B),
+}
+
+impl<A, B> EitherOrBoth<A, B> {
+    /// Returns the item of the first stream, if there is one.
+    pub fn left(self) -> Option<A> {
+        match self {
+            EitherOrBoth::Both(a, _) | EitherOrBoth::Left(a) => Some(a),
+            EitherOrBoth::Right(_) => None,
+        }
+    }
+
+    /// Returns the item of the second stream, if there is one.
+    pub fn right(self) -> Option<B> {
+        match self {
+            EitherOrBoth::Both(_, b) | EitherOrBoth::Right(b) => Some(b),
+            EitherOrBoth::Left(_) => None,
+        }
+    }
+
+    /// Returns `true` if both streams yielded an item.
+    pub fn is_both(&self) -> bool {
+        matches!(self, EitherOrBoth::Both(..))
+    }
+}
+
+pin_project! {
+    /// A stream that pairs up the items of two other streams until both have ended.
+    ///
+    /// This `struct` is created by the [`zip_longest`] method on [`Stream`]. See its
+    /// documentation for more.
+    ///
+    /// [`zip_longest`]: trait.Stream.html#method.zip_longest
+    /// [`Stream`]: trait.Stream.html
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub struct ZipLongest<A: Stream, B: Stream> {
+        #[pin]
+        first: Fuse<A>,
+        #[pin]
+        second: Fuse<B>,
+        first_slot: Option<A::Item>,
+        second_slot: Option<B::Item>,
+    }
+}
+
+impl<A: Stream + fmt::Debug, B: Stream + fmt::Debug> fmt::Debug for ZipLongest<A, B> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ZipLongest")
+            .field("first", &self.first)
+            .field("second", &self.second)
+            .finish()
+    }
+}
+
+impl<A: Stream, B: Stream> ZipLongest<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Self {
+            first: first.fuse(),
+            second: second.fuse(),
+            first_slot: None,
+            second_slot: None,
+        }
+    }
+}
+
+impl<A: Stream, B: Stream> Stream for ZipLongest<A, B> {
+    type Item = EitherOrBoth<A::Item, B::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+
+        // Poll both streams, keeping an item that is ready until the other stream is ready too.
+        if this.first_slot.is_none() && !this.first.done {
+            if let Poll::Ready(item) = this.first.as_mut().poll_next(cx) {
+                *this.first_slot = item;
+            }
+        }
+        if this.second_slot.is_none() && !this.second.done {
+            if let Poll::Ready(item) = this.second.as_mut().poll_next(cx) {
+                *this.second_slot = item;
+            }
+        }
+
+        if (this.first_slot.is_none() && !this.first.done)
+            || (this.second_slot.is_none() && !this.second.done)
+        {
+            return Poll::Pending;
+        }
+
+        Poll::Ready(match (this.first_slot.take(), this.second_slot.take()) {
+            (Some(a), Some(b)) => Some(EitherOrBoth::Both(a, b)),
+            (Some(a), None) => Some(EitherOrBoth::Left(a)),
+            (None, Some(b)) => Some(EitherOrBoth::Right(b)),
+            (None, None) => None,
+        })
+    }
+}
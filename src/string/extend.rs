@@ -89,3 +89,20 @@ impl<'b> stream::Extend<Cow<'b, str>> for String {
         })
     }
 }
+
+impl stream::Extend<Box<str>> for String {
+    fn extend<'a, S: IntoStream<Item = Box<str>> + 'a>(
+        &'a mut self,
+        stream: S,
+    ) -> Pin<Box<dyn Future<Output = ()> + 'a>> {
+        let stream = stream.into_stream();
+
+        Box::pin(async move {
+            pin_utils::pin_mut!(stream);
+
+            while let Some(item) = stream.next().await {
+                self.push_str(&item);
+            }
+        })
+    }
+}
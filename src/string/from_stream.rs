@@ -78,3 +78,18 @@ impl<'b> FromStream<Cow<'b, str>> for String {
         })
     }
 }
+
+impl FromStream<Box<str>> for String {
+    #[inline]
+    fn from_stream<'a, S: IntoStream<Item = Box<str>> + 'a>(
+        stream: S,
+    ) -> Pin<Box<dyn Future<Output = Self> + 'a>> {
+        let stream = stream.into_stream();
+
+        Box::pin(async move {
+            let mut out = String::new();
+            stream::extend(&mut out, stream).await;
+            out
+        })
+    }
+}
@@ -0,0 +1,161 @@
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::task::Waker;
+
+/// No task is registering or waking the waker.
+const WAITING: usize = 0;
+
+/// A task is registering a new waker.
+const REGISTERING: usize = 1 << 0;
+
+/// A task is waking the registered waker.
+const WAKING: usize = 1 << 1;
+
+/// A slot holding the waker of a single task, which can be woken from any thread.
+///
+/// This is the building block of futures and streams that are driven by another thread or task,
+/// like a flag set on completion of some work. The task polling the future calls [`register`]
+/// with its waker before checking whether it can make progress, and the other side makes progress
+/// possible before calling [`wake`].
+///
+/// Registering and waking never block, and a wakeup racing with a registration is never lost:
+/// either the newly registered waker is woken, or the registering task is woken right away.
+///
+/// Only one task can be registered at a time. Registering a waker replaces the previous one.
+///
+/// [`register`]: #method.register
+/// [`wake`]: #method.wake
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::thread;
+///
+/// use async_std::future::poll_fn;
+/// use async_std::sync::{Arc, AtomicWaker};
+/// use async_std::task::Poll;
+///
+/// let done = Arc::new((AtomicBool::new(false), AtomicWaker::new()));
+///
+/// let d = done.clone();
+/// thread::spawn(move || {
+///     d.0.store(true, Ordering::SeqCst);
+///     d.1.wake();
+/// });
+///
+/// poll_fn(|cx| {
+///     // Register before checking, so that no wakeup is missed.
+///     done.1.register(cx.waker());
+///     if done.0.load(Ordering::SeqCst) {
+///         Poll::Ready(())
+///     } else {
+///         Poll::Pending
+///     }
+/// })
+/// .await;
+/// #
+/// # })
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct AtomicWaker {
+    /// Holds the `REGISTERING` and `WAKING` bits.
+    state: AtomicUsize,
+
+    /// The registered waker, only accessed by whoever moved the state out of `WAITING`.
+    waker: UnsafeCell<Option<Waker>>,
+}
+
+unsafe impl Send for AtomicWaker {}
+unsafe impl Sync for AtomicWaker {}
+
+impl AtomicWaker {
+    /// Creates an `AtomicWaker` without a registered waker.
+    pub fn new() -> AtomicWaker {
+        AtomicWaker {
+            state: AtomicUsize::new(WAITING),
+            waker: UnsafeCell::new(None),
+        }
+    }
+
+    /// Registers a waker to be woken by the next call to [`wake`].
+    ///
+    /// If [`wake`] is called concurrently, the waker is woken right away instead.
+    ///
+    /// [`wake`]: #method.wake
+    pub fn register(&self, waker: &Waker) {
+        match self
+            .state
+            .compare_exchange(WAITING, REGISTERING, Ordering::Acquire, Ordering::Acquire)
+            .unwrap_or_else(|s| s)
+        {
+            WAITING => unsafe {
+                // Avoid cloning the waker if the same task registers again.
+                let slot = &mut *self.waker.get();
+                match slot {
+                    Some(old) if old.will_wake(waker) => {}
+                    _ => *slot = Some(waker.clone()),
+                }
+
+                let res = self.state.compare_exchange(
+                    REGISTERING,
+                    WAITING,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                );
+                if res.is_err() {
+                    // A wakeup happened while registering, which left the waker in place.
+                    let waker = slot.take();
+                    self.state.swap(WAITING, Ordering::AcqRel);
+                    if let Some(waker) = waker {
+                        waker.wake();
+                    }
+                }
+            },
+            WAKING => {
+                // The waker is being woken, so wake the new one as well.
+                waker.wake_by_ref();
+            }
+            _ => {
+                // Another thread is registering concurrently, which is a misuse. One of the
+                // wakers wins.
+            }
+        }
+    }
+
+    /// Wakes the registered waker, if any, and unregisters it.
+    pub fn wake(&self) {
+        if let Some(waker) = self.take() {
+            waker.wake();
+        }
+    }
+
+    /// Unregisters the registered waker, if any, and returns it.
+    pub fn take(&self) -> Option<Waker> {
+        match self.state.fetch_or(WAKING, Ordering::AcqRel) {
+            WAITING => {
+                let waker = unsafe { (*self.waker.get()).take() };
+                self.state.fetch_and(!WAKING, Ordering::Release);
+                waker
+            }
+            // A registration is in progress and will wake its waker, or another wakeup is in
+            // progress.
+            _ => None,
+        }
+    }
+}
+
+impl Default for AtomicWaker {
+    fn default() -> AtomicWaker {
+        AtomicWaker::new()
+    }
+}
+
+impl fmt::Debug for AtomicWaker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("AtomicWaker { .. }")
+    }
+}
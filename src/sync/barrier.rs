@@ -1,10 +1,22 @@
-use broadcaster::BroadcastChannel;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::time::Duration;
 
-use crate::sync::Mutex;
+use crate::future::{timeout, TimeoutError};
+use crate::sync::WakerSet;
+use crate::task::{Context, Poll};
 
 /// A barrier enables multiple tasks to synchronize the beginning
 /// of some computation.
 ///
+/// A barrier can be reused: once all tasks have rendezvoused, the next call to [`wait`] starts a
+/// new generation. If a task stops waiting before the generation is complete, for example because
+/// its `wait` future was dropped, it is no longer counted towards that generation.
+///
+/// [`wait`]: #method.wait
+///
 /// # Examples
 ///
 /// ```
@@ -33,17 +45,18 @@ use crate::sync::Mutex;
 /// ```
 #[cfg(feature = "unstable")]
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
-#[derive(Debug)]
 pub struct Barrier {
     state: Mutex<BarrierState>,
-    wait: BroadcastChannel<(usize, usize)>,
+    wakers: WakerSet,
     n: usize,
 }
 
-// The inner state of a double barrier
+unsafe impl Send for Barrier {}
+unsafe impl Sync for Barrier {}
+
+// The inner state of a barrier
 #[derive(Debug)]
 struct BarrierState {
-    waker: BroadcastChannel<(usize, usize)>,
     count: usize,
     generation_id: usize,
 }
@@ -82,9 +95,6 @@ impl Barrier {
     /// let barrier = Barrier::new(10);
     /// ```
     pub fn new(mut n: usize) -> Barrier {
-        let waker = BroadcastChannel::new();
-        let wait = waker.clone();
-
         if n == 0 {
             // if n is 0, it's not clear what behavior the user wants.
             // in std::sync::Barrier, an n of 0 exhibits the same behavior as n == 1, where every
@@ -94,12 +104,11 @@ impl Barrier {
 
         Barrier {
             state: Mutex::new(BarrierState {
-                waker,
                 count: 0,
                 generation_id: 1,
             }),
+            wakers: WakerSet::new(),
             n,
-            wait,
         }
     }
 
@@ -113,6 +122,9 @@ impl Barrier {
     /// all other tasks will receive a result that will return `false` from
     /// [`is_leader`].
     ///
+    /// If the returned future is dropped before the barrier is released, the task is removed
+    /// from the current generation as if it had never called `wait`.
+    ///
     /// [`BarrierWaitResult`]: struct.BarrierWaitResult.html
     /// [`is_leader`]: struct.BarrierWaitResult.html#method.is_leader
     ///
@@ -143,36 +155,111 @@ impl Barrier {
     /// # });
     /// ```
     pub async fn wait(&self) -> BarrierWaitResult {
-        let mut lock = self.state.lock().await;
-        let local_gen = lock.generation_id;
+        let generation_id = {
+            let mut state = self.state.lock().unwrap();
+            state.count += 1;
 
-        lock.count += 1;
+            if state.count < self.n {
+                state.generation_id
+            } else {
+                // This is the last task of the generation: release everyone else.
+                state.count = 0;
+                state.generation_id = state.generation_id.wrapping_add(1);
+                drop(state);
 
-        if lock.count < self.n {
-            let mut wait = self.wait.clone();
+                self.wakers.notify_all();
+                return BarrierWaitResult(true);
+            }
+        };
 
-            let mut generation_id = lock.generation_id;
-            let mut count = lock.count;
+        WaitFuture {
+            barrier: self,
+            generation_id,
+            opt_key: None,
+            done: false,
+        }
+        .await;
 
-            drop(lock);
+        BarrierWaitResult(false)
+    }
 
-            while local_gen == generation_id && count < self.n {
-                let (g, c) = wait.recv().await.expect("sender has not been closed");
-                generation_id = g;
-                count = c;
-            }
+    /// Blocks the current task until all tasks have rendezvoused here or the timeout expires.
+    ///
+    /// If the timeout expires first, the task is removed from the current generation and an
+    /// error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::sync::Barrier;
+    ///
+    /// let barrier = Barrier::new(2);
+    /// assert!(barrier.wait_timeout(Duration::from_millis(10)).await.is_err());
+    ///
+    /// // The timed out task does not count towards the next rendezvous.
+    /// assert!(barrier.wait_timeout(Duration::from_millis(10)).await.is_err());
+    /// # });
+    /// ```
+    pub async fn wait_timeout(&self, dur: Duration) -> Result<BarrierWaitResult, TimeoutError> {
+        timeout(dur, self.wait()).await
+    }
+}
+
+impl fmt::Debug for Barrier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Barrier").field("n", &self.n).finish()
+    }
+}
+
+/// A future that waits until the given generation of the barrier is complete.
+struct WaitFuture<'a> {
+    barrier: &'a Barrier,
+    generation_id: usize,
+    opt_key: Option<usize>,
+    done: bool,
+}
+
+impl Future for WaitFuture<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // If the current task is in the set, remove it.
+        if let Some(key) = self.opt_key.take() {
+            self.barrier.wakers.remove(key);
+        }
 
-            BarrierWaitResult(false)
-        } else {
-            lock.count = 0;
-            lock.generation_id = lock.generation_id.wrapping_add(1);
+        let state = self.barrier.state.lock().unwrap();
+        if state.generation_id != self.generation_id {
+            drop(state);
+            self.done = true;
+            return Poll::Ready(());
+        }
 
-            lock.waker
-                .send(&(lock.generation_id, lock.count))
-                .await
-                .expect("there should be at least one receiver");
+        // Insert this wait operation while holding the lock, so that the task completing the
+        // generation is guaranteed to wake it.
+        let key = self.barrier.wakers.insert(cx);
+        drop(state);
+        self.opt_key = Some(key);
+        Poll::Pending
+    }
+}
 
-            BarrierWaitResult(true)
+impl Drop for WaitFuture<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.opt_key {
+            self.barrier.wakers.remove(key);
+        }
+
+        // If the generation is not complete yet, withdraw from it.
+        if !self.done {
+            let mut state = self.barrier.state.lock().unwrap();
+            if state.generation_id == self.generation_id {
+                state.count -= 1;
+            }
         }
     }
 }
@@ -204,6 +291,8 @@ impl BarrierWaitResult {
 
 #[cfg(test)]
 mod test {
+    use std::time::Duration;
+
     use futures::channel::mpsc::unbounded;
     use futures::sink::SinkExt;
     use futures::stream::StreamExt;
@@ -255,4 +344,46 @@ mod test {
             });
         }
     }
+
+    #[test]
+    fn cancelled_waiter_leaves_generation() {
+        task::block_on(async {
+            let barrier = Barrier::new(2);
+
+            // A timed out waiter must not release the next waiter on its own.
+            let res = barrier.wait_timeout(Duration::from_millis(10)).await;
+            assert!(res.is_err());
+            let res = barrier.wait_timeout(Duration::from_millis(10)).await;
+            assert!(res.is_err());
+        });
+    }
+
+    #[test]
+    fn reusable_across_generations() {
+        task::block_on(async {
+            const N: usize = 4;
+
+            let barrier = Arc::new(Barrier::new(N));
+            let mut handles = Vec::new();
+
+            for _ in 0..N {
+                let c = barrier.clone();
+                handles.push(task::spawn(async move {
+                    let mut leaders = 0;
+                    for _ in 0..10 {
+                        if c.wait().await.is_leader() {
+                            leaders += 1;
+                        }
+                    }
+                    leaders
+                }));
+            }
+
+            let mut leaders = 0;
+            for handle in handles {
+                leaders += handle.await;
+            }
+            assert_eq!(leaders, 10);
+        });
+    }
 }
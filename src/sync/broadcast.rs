@@ -0,0 +1,651 @@
+//! A multi-producer, multi-consumer broadcast channel.
+//!
+//! Every message sent into a broadcast channel is received by every [`Receiver`]. The channel
+//! holds at most `cap` messages: when it is full, sending a message overwrites the oldest one.
+//! Receivers that fall behind observe [`RecvError::Lagged`] with the number of messages they
+//! missed, and then continue with the oldest message still in the channel.
+//!
+//! [`Receiver`]: struct.Receiver.html
+//! [`RecvError::Lagged`]: enum.RecvError.html#variant.Lagged
+//!
+//! # Examples
+//!
+//! ```
+//! # async_std::task::block_on(async {
+//! #
+//! use async_std::sync::broadcast;
+//!
+//! let (s, mut r1) = broadcast::channel(16);
+//! let mut r2 = s.subscribe();
+//!
+//! s.send(1).unwrap();
+//! s.send(2).unwrap();
+//!
+//! assert_eq!(r1.recv().await, Ok(1));
+//! assert_eq!(r1.recv().await, Ok(2));
+//! assert_eq!(r2.recv().await, Ok(1));
+//! assert_eq!(r2.recv().await, Ok(2));
+//! #
+//! # })
+//! ```
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use crate::stream::Stream;
+use crate::sync::WakerSet;
+
+/// Creates a bounded broadcast channel.
+///
+/// The channel holds at most `cap` messages at a time. Sending into a full channel never waits:
+/// the oldest message is dropped instead, and receivers that haven't received it yet will
+/// observe [`RecvError::Lagged`].
+///
+/// The returned receiver only receives messages sent after this function returns. More
+/// receivers can be created with [`Sender::subscribe`] or by cloning an existing receiver.
+///
+/// [`RecvError::Lagged`]: enum.RecvError.html#variant.Lagged
+/// [`Sender::subscribe`]: struct.Sender.html#method.subscribe
+///
+/// # Panics
+///
+/// If `cap` is zero, this function will panic.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::broadcast::{self, RecvError};
+///
+/// let (s, mut r) = broadcast::channel(2);
+///
+/// s.send(1).unwrap();
+/// s.send(2).unwrap();
+/// s.send(3).unwrap();
+///
+/// assert_eq!(r.recv().await, Err(RecvError::Lagged(1)));
+/// assert_eq!(r.recv().await, Ok(2));
+/// assert_eq!(r.recv().await, Ok(3));
+///
+/// drop(s);
+/// assert_eq!(r.recv().await, Err(RecvError::Closed));
+/// #
+/// # })
+/// ```
+pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    let channel = Arc::new(Channel::with_capacity(cap));
+    let s = Sender {
+        channel: channel.clone(),
+    };
+    let r = Receiver {
+        channel,
+        pos: 0,
+        opt_key: None,
+    };
+    (s, r)
+}
+
+/// The sending side of a broadcast channel.
+///
+/// This struct is created by the [`channel`] function. See its
+/// documentation for more.
+///
+/// [`channel`]: fn.channel.html
+pub struct Sender<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a message to all receivers.
+    ///
+    /// If the channel is full, the oldest message in it is dropped to make room. On success,
+    /// returns the number of receivers the message was sent to.
+    ///
+    /// If there are no receivers, the message is returned back in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, r) = broadcast::channel(1);
+    /// assert_eq!(s.send(1), Ok(1));
+    ///
+    /// drop(r);
+    /// assert!(s.send(2).is_err());
+    /// ```
+    pub fn send(&self, msg: T) -> Result<usize, SendError<T>> {
+        let receivers = self.channel.receiver_count.load(Ordering::SeqCst);
+        if receivers == 0 {
+            return Err(SendError(msg));
+        }
+
+        {
+            let mut state = self.channel.state.lock().unwrap();
+            if state.buffer.len() == self.channel.cap {
+                state.buffer.pop_front();
+                state.offset += 1;
+            }
+            state.buffer.push_back(msg);
+        }
+
+        // Wake all blocked receive operations.
+        self.channel.recv_wakers.notify_all();
+
+        Ok(receivers)
+    }
+
+    /// Creates a new receiver that receives all messages sent after this call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, _) = broadcast::channel(4);
+    /// s.send(1).unwrap_err();
+    ///
+    /// let mut r = s.subscribe();
+    /// s.send(2).unwrap();
+    /// assert_eq!(r.recv().await, Ok(2));
+    /// #
+    /// # })
+    /// ```
+    pub fn subscribe(&self) -> Receiver<T> {
+        let state = self.channel.state.lock().unwrap();
+        let pos = state.offset + state.buffer.len() as u64;
+        Receiver::with_position(self.channel.clone(), pos)
+    }
+
+    /// Returns the channel capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, _) = broadcast::channel::<i32>(5);
+    /// assert_eq!(s.capacity(), 5);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.channel.cap
+    }
+
+    /// Returns the number of active receivers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, r1) = broadcast::channel::<i32>(5);
+    /// let r2 = s.subscribe();
+    /// assert_eq!(s.receiver_count(), 2);
+    /// ```
+    pub fn receiver_count(&self) -> usize {
+        self.channel.receiver_count.load(Ordering::SeqCst)
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        // Decrement the sender count and close the channel if it drops down to zero.
+        if self.channel.sender_count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.channel.close();
+        }
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        let count = self.channel.sender_count.fetch_add(1, Ordering::Relaxed);
+
+        // Make sure the count never overflows, even if lots of sender clones are leaked.
+        if count > isize::MAX as usize {
+            process::abort();
+        }
+
+        Sender {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Sender { .. }")
+    }
+}
+
+/// The receiving side of a broadcast channel.
+///
+/// This type receives messages by calling `recv`. But it also implements the [`Stream`] trait,
+/// which means it can act as an asynchronous iterator. The stream ends once the channel is
+/// closed and all remaining messages have been received.
+///
+/// Cloning a receiver creates a new receiver that will receive the same messages as the original,
+/// starting at the same position.
+///
+/// This struct is created by the [`channel`] function. See its documentation for more.
+///
+/// [`channel`]: fn.channel.html
+/// [`Stream`]: ../../stream/trait.Stream.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::prelude::*;
+/// use async_std::sync::broadcast;
+///
+/// let (s, mut r) = broadcast::channel(4);
+///
+/// s.send(1).unwrap();
+/// s.send(2).unwrap();
+/// drop(s);
+///
+/// assert_eq!(r.next().await, Some(Ok(1)));
+/// assert_eq!(r.next().await, Some(Ok(2)));
+/// assert_eq!(r.next().await, None);
+/// #
+/// # })
+/// ```
+pub struct Receiver<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+
+    /// The sequence number of the next message this receiver will receive.
+    pos: u64,
+
+    /// The key for this receiver in the `channel.recv_wakers` set.
+    opt_key: Option<usize>,
+}
+
+impl<T> Receiver<T> {
+    /// Creates a new receiver starting at message `pos`.
+    fn with_position(channel: Arc<Channel<T>>, pos: u64) -> Receiver<T> {
+        let count = channel.receiver_count.fetch_add(1, Ordering::Relaxed);
+
+        // Make sure the count never overflows, even if lots of receiver clones are leaked.
+        if count > isize::MAX as usize {
+            process::abort();
+        }
+
+        Receiver {
+            channel,
+            pos,
+            opt_key: None,
+        }
+    }
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Receives the next message from the channel.
+    ///
+    /// If there are no new messages and the channel still has senders, this method will wait
+    /// until a message is sent or until all senders get dropped.
+    ///
+    /// Returns [`RecvError::Lagged`] if this receiver fell behind and messages were dropped
+    /// before it could receive them, and [`RecvError::Closed`] once all senders are dropped and
+    /// no messages remain.
+    ///
+    /// [`RecvError::Lagged`]: enum.RecvError.html#variant.Lagged
+    /// [`RecvError::Closed`]: enum.RecvError.html#variant.Closed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::broadcast::{self, RecvError};
+    /// use async_std::task;
+    ///
+    /// let (s, mut r) = broadcast::channel(1);
+    ///
+    /// task::spawn(async move {
+    ///     s.send(1).unwrap();
+    /// });
+    ///
+    /// assert_eq!(r.recv().await, Ok(1));
+    /// assert_eq!(r.recv().await, Err(RecvError::Closed));
+    /// #
+    /// # })
+    /// ```
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        struct RecvFuture<'a, T> {
+            channel: &'a Channel<T>,
+            pos: &'a mut u64,
+            opt_key: Option<usize>,
+        }
+
+        impl<T: Clone> Future for RecvFuture<'_, T> {
+            type Output = Result<T, RecvError>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                poll_recv(this.channel, this.pos, &mut this.opt_key, cx)
+            }
+        }
+
+        impl<T> Drop for RecvFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                if let Some(key) = self.opt_key {
+                    self.channel.recv_wakers.cancel(key);
+                }
+            }
+        }
+
+        RecvFuture {
+            channel: &self.channel,
+            pos: &mut self.pos,
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Attempts to receive the next message without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast::{self, TryRecvError};
+    ///
+    /// let (s, mut r) = broadcast::channel(1);
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// s.send(1).unwrap();
+    /// assert_eq!(r.try_recv(), Ok(1));
+    ///
+    /// drop(s);
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Closed));
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        self.channel.try_recv(&mut self.pos)
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Returns the number of messages this receiver hasn't received yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, r) = broadcast::channel(4);
+    /// s.send(1).unwrap();
+    /// s.send(2).unwrap();
+    /// assert_eq!(r.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        let state = self.channel.state.lock().unwrap();
+        let tail = state.offset + state.buffer.len() as u64;
+        (tail - self.pos.max(state.offset)) as usize
+    }
+
+    /// Returns `true` if there are no messages this receiver hasn't received yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, r) = broadcast::channel(4);
+    /// assert!(r.is_empty());
+    /// s.send(1).unwrap();
+    /// assert!(!r.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        // If the current task is still in the set, that means it is being cancelled now.
+        if let Some(key) = self.opt_key {
+            self.channel.recv_wakers.cancel(key);
+        }
+
+        self.channel.receiver_count.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        Receiver::with_position(self.channel.clone(), self.pos)
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = Result<T, RecvError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        match poll_recv(&this.channel, &mut this.pos, &mut this.opt_key, cx) {
+            Poll::Ready(Err(RecvError::Closed)) => Poll::Ready(None),
+            Poll::Ready(res) => Poll::Ready(Some(res)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Receiver { .. }")
+    }
+}
+
+/// Polls a receive operation on a channel.
+///
+/// If the receive operation is blocked, the current task will be inserted into
+/// `channel.recv_wakers` and its associated key will then be stored in `opt_key`.
+fn poll_recv<T: Clone>(
+    channel: &Channel<T>,
+    pos: &mut u64,
+    opt_key: &mut Option<usize>,
+    cx: &mut Context<'_>,
+) -> Poll<Result<T, RecvError>> {
+    loop {
+        // If the current task is in the set, remove it.
+        if let Some(key) = opt_key.take() {
+            channel.recv_wakers.remove(key);
+        }
+
+        // Try receiving a message.
+        match channel.try_recv(pos) {
+            Ok(msg) => return Poll::Ready(Ok(msg)),
+            Err(TryRecvError::Lagged(n)) => return Poll::Ready(Err(RecvError::Lagged(n))),
+            Err(TryRecvError::Closed) => return Poll::Ready(Err(RecvError::Closed)),
+            Err(TryRecvError::Empty) => {
+                // Insert this receive operation.
+                *opt_key = Some(channel.recv_wakers.insert(cx));
+
+                // If there is still no new message and the channel is not closed, return.
+                if !channel.has_message_at(*pos) && !channel.is_closed() {
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+/// The mutable state of a broadcast channel.
+struct State<T> {
+    /// The most recently sent messages, oldest first.
+    buffer: VecDeque<T>,
+
+    /// The sequence number of the first message in `buffer`.
+    offset: u64,
+
+    /// Set to `true` once all senders have been dropped.
+    closed: bool,
+}
+
+/// Bounded broadcast channel based on a ring of the most recent messages.
+struct Channel<T> {
+    /// The mutable state of the channel.
+    state: Mutex<State<T>>,
+
+    /// The channel capacity.
+    cap: usize,
+
+    /// Receive operations waiting for a new message.
+    recv_wakers: WakerSet,
+
+    /// The number of currently active `Sender`s.
+    sender_count: AtomicUsize,
+
+    /// The number of currently active `Receivers`s.
+    receiver_count: AtomicUsize,
+}
+
+unsafe impl<T: Send> Send for Channel<T> {}
+unsafe impl<T: Send> Sync for Channel<T> {}
+
+impl<T> Channel<T> {
+    /// Creates a broadcast channel of capacity `cap`.
+    fn with_capacity(cap: usize) -> Self {
+        assert!(cap > 0, "capacity must be positive");
+
+        Channel {
+            state: Mutex::new(State {
+                buffer: VecDeque::with_capacity(cap),
+                offset: 0,
+                closed: false,
+            }),
+            cap,
+            recv_wakers: WakerSet::new(),
+            sender_count: AtomicUsize::new(1),
+            receiver_count: AtomicUsize::new(1),
+        }
+    }
+
+    /// Attempts to receive the message with sequence number `pos`, advancing it on success.
+    fn try_recv(&self, pos: &mut u64) -> Result<T, TryRecvError>
+    where
+        T: Clone,
+    {
+        let state = self.state.lock().unwrap();
+
+        // Messages this receiver hasn't received yet were overwritten by newer ones.
+        if *pos < state.offset {
+            let lagged = state.offset - *pos;
+            *pos = state.offset;
+            return Err(TryRecvError::Lagged(lagged));
+        }
+
+        match state.buffer.get((*pos - state.offset) as usize) {
+            Some(msg) => {
+                *pos += 1;
+                Ok(msg.clone())
+            }
+            None if state.closed => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Returns `true` if a message with sequence number `pos` has been sent.
+    fn has_message_at(&self, pos: u64) -> bool {
+        let state = self.state.lock().unwrap();
+        pos < state.offset + state.buffer.len() as u64
+    }
+
+    /// Returns `true` if the channel is closed.
+    fn is_closed(&self) -> bool {
+        self.state.lock().unwrap().closed
+    }
+
+    /// Closes the channel and wakes up all blocked operations.
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.recv_wakers.notify_all();
+    }
+}
+
+/// An error returned from the [`Sender::send`] method.
+///
+/// The message could not be sent because there are no receivers.
+///
+/// [`Sender::send`]: struct.Sender.html#method.send
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct SendError<T>(pub T);
+
+impl<T> Error for SendError<T> {}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "sending on a channel with no receivers".fmt(f)
+    }
+}
+
+/// An error returned from the [`Receiver::recv`] method.
+///
+/// [`Receiver::recv`]: struct.Receiver.html#method.recv
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecvError {
+    /// The receiver fell behind and the given number of messages were dropped before it could
+    /// receive them.
+    Lagged(u64),
+
+    /// All senders were dropped and no messages remain in the channel.
+    Closed,
+}
+
+impl Error for RecvError {}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvError::Lagged(n) => write!(f, "receiver lagged behind by {} messages", n),
+            RecvError::Closed => "receiving on a closed channel".fmt(f),
+        }
+    }
+}
+
+/// An error returned from the [`Receiver::try_recv`] method.
+///
+/// [`Receiver::try_recv`]: struct.Receiver.html#method.try_recv
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TryRecvError {
+    /// There are no new messages in the channel.
+    Empty,
+
+    /// The receiver fell behind and the given number of messages were dropped before it could
+    /// receive them.
+    Lagged(u64),
+
+    /// All senders were dropped and no messages remain in the channel.
+    Closed,
+}
+
+impl Error for TryRecvError {}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => "receiving on an empty channel".fmt(f),
+            TryRecvError::Lagged(n) => write!(f, "receiver lagged behind by {} messages", n),
+            TryRecvError::Closed => "receiving on a closed channel".fmt(f),
+        }
+    }
+}
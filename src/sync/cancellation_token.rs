@@ -0,0 +1,286 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use crate::sync::WakerSet;
+use crate::task::{Context, Poll};
+
+/// A token for signaling cancellation to a tree of tasks.
+///
+/// Cloning a token creates another handle to the same token. Calling [`child_token`] creates a
+/// new token that gets cancelled whenever its parent is cancelled, but that can also be cancelled
+/// on its own without affecting the parent.
+///
+/// Tasks can check whether a token is cancelled with [`is_cancelled`], or wait for it to be
+/// cancelled with [`cancelled`].
+///
+/// [`child_token`]: #method.child_token
+/// [`is_cancelled`]: #method.is_cancelled
+/// [`cancelled`]: #method.cancelled
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::CancellationToken;
+/// use async_std::task;
+///
+/// let token = CancellationToken::new();
+/// let child = token.child_token();
+///
+/// let handle = task::spawn(async move {
+///     child.cancelled().await;
+///     println!("shutting down");
+/// });
+///
+/// token.cancel();
+/// handle.await;
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone)]
+pub struct CancellationToken {
+    node: Arc<Node>,
+}
+
+/// A node in the tree of cancellation tokens.
+struct Node {
+    /// Set to `true` once the token is cancelled.
+    cancelled: AtomicBool,
+
+    /// Tasks waiting for the token to be cancelled.
+    wakers: WakerSet,
+
+    /// Child tokens that are still alive.
+    children: Mutex<Vec<Weak<Node>>>,
+}
+
+unsafe impl Send for Node {}
+unsafe impl Sync for Node {}
+
+impl Node {
+    fn new(cancelled: bool) -> Node {
+        Node {
+            cancelled: AtomicBool::new(cancelled),
+            wakers: WakerSet::new(),
+            children: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Cancels this node and all of its descendants.
+    fn cancel(&self) {
+        // Hold the lock on children while setting the flag so that no child can be added after
+        // the children have been taken out.
+        let children = {
+            let mut children = self.children.lock().unwrap();
+            if self.cancelled.swap(true, Ordering::SeqCst) {
+                return;
+            }
+            std::mem::take(&mut *children)
+        };
+
+        self.wakers.notify_all();
+
+        for child in children {
+            if let Some(child) = child.upgrade() {
+                child.cancel();
+            }
+        }
+    }
+}
+
+impl CancellationToken {
+    /// Creates a new token that is not cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    /// assert!(!token.is_cancelled());
+    /// ```
+    pub fn new() -> CancellationToken {
+        CancellationToken {
+            node: Arc::new(Node::new(false)),
+        }
+    }
+
+    /// Creates a child token that gets cancelled when this token is cancelled.
+    ///
+    /// Cancelling the child token doesn't cancel this token. If this token is already cancelled,
+    /// the child token is created cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::CancellationToken;
+    ///
+    /// let parent = CancellationToken::new();
+    /// let child = parent.child_token();
+    ///
+    /// child.cancel();
+    /// assert!(!parent.is_cancelled());
+    ///
+    /// let child = parent.child_token();
+    /// parent.cancel();
+    /// assert!(child.is_cancelled());
+    /// ```
+    pub fn child_token(&self) -> CancellationToken {
+        let mut children = self.node.children.lock().unwrap();
+
+        if self.node.cancelled.load(Ordering::SeqCst) {
+            return CancellationToken {
+                node: Arc::new(Node::new(true)),
+            };
+        }
+
+        // Remove children that have been dropped before adding a new one.
+        children.retain(|c| c.strong_count() > 0);
+
+        let node = Arc::new(Node::new(false));
+        children.push(Arc::downgrade(&node));
+        CancellationToken { node }
+    }
+
+    /// Cancels this token and all of its child tokens.
+    ///
+    /// All tasks waiting on [`cancelled`] for any of the cancelled tokens are woken up.
+    ///
+    /// [`cancelled`]: #method.cancelled
+    pub fn cancel(&self) {
+        self.node.cancel();
+    }
+
+    /// Returns `true` if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.node.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Waits until this token is cancelled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    /// token.cancel();
+    /// token.cancelled().await;
+    /// #
+    /// # })
+    /// ```
+    pub async fn cancelled(&self) {
+        struct CancelledFuture<'a> {
+            node: &'a Node,
+            opt_key: Option<usize>,
+        }
+
+        impl Future for CancelledFuture<'_> {
+            type Output = ();
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                loop {
+                    // If the current task is in the set, remove it.
+                    if let Some(key) = self.opt_key.take() {
+                        self.node.wakers.remove(key);
+                    }
+
+                    if self.node.cancelled.load(Ordering::SeqCst) {
+                        return Poll::Ready(());
+                    }
+
+                    // Insert this wait operation.
+                    self.opt_key = Some(self.node.wakers.insert(cx));
+
+                    // If the token is still not cancelled, return.
+                    if !self.node.cancelled.load(Ordering::SeqCst) {
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+
+        impl Drop for CancelledFuture<'_> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                if let Some(key) = self.opt_key {
+                    self.node.wakers.cancel(key);
+                }
+            }
+        }
+
+        CancelledFuture {
+            node: &self.node,
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Returns a guard that cancels this token when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::CancellationToken;
+    ///
+    /// let token = CancellationToken::new();
+    /// let guard = token.clone().drop_guard();
+    ///
+    /// drop(guard);
+    /// assert!(token.is_cancelled());
+    /// ```
+    pub fn drop_guard(self) -> DropGuard {
+        DropGuard { token: Some(self) }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> CancellationToken {
+        CancellationToken::new()
+    }
+}
+
+impl fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("is_cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// A guard that cancels a [`CancellationToken`] when dropped.
+///
+/// This struct is created by the [`drop_guard`] method on [`CancellationToken`].
+///
+/// [`CancellationToken`]: struct.CancellationToken.html
+/// [`drop_guard`]: struct.CancellationToken.html#method.drop_guard
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+#[must_use = "the token is cancelled immediately if the guard is unused"]
+pub struct DropGuard {
+    token: Option<CancellationToken>,
+}
+
+impl DropGuard {
+    /// Returns the token without cancelling it.
+    pub fn disarm(mut self) -> CancellationToken {
+        self.token.take().unwrap()
+    }
+}
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        if let Some(token) = &self.token {
+            token.cancel();
+        }
+    }
+}
@@ -1,20 +1,26 @@
 use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+use std::error::Error;
 use std::fmt;
 use std::future::Future;
 use std::isize;
 use std::marker::PhantomData;
-use std::mem;
+use std::mem::{self, MaybeUninit};
 use std::pin::Pin;
 use std::process;
 use std::ptr;
-use std::sync::atomic::{self, AtomicUsize, Ordering};
-use std::sync::Arc;
-use std::task::{Context, Poll};
+use std::sync::atomic::{self, AtomicPtr, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 
-use crossbeam_utils::Backoff;
+use crossbeam_utils::{Backoff, CachePadded};
 
+use crate::sink::Sink;
 use crate::stream::Stream;
 use crate::sync::WakerSet;
+use crate::task::budget;
+use crate::time::Delay;
 
 /// Creates a bounded multi-producer multi-consumer channel.
 ///
@@ -24,9 +30,23 @@ use crate::sync::WakerSet;
 /// becomes closed. Receive operations on a closed and empty channel return `None` instead of
 /// trying to await a message.
 ///
-/// # Panics
+/// # Rendezvous channels
 ///
-/// If `cap` is zero, this function will panic.
+/// If `cap` is zero, the channel buffers nothing: a message is handed over directly from a
+/// sender to a receiver. [`send`] offers its message and completes only once a receiver has taken
+/// it, so a producer can never get ahead of its consumers. If the send operation is cancelled or
+/// times out before that, or the channel gets closed, the offered message is taken back.
+///
+/// Only one message is offered at a time. Messages sent through a [`Permit`], the [`Sink`] impl or
+/// [`send_batch`] are handed over one by one too, but [`Permit::send`] and [`Sink::start_send`]
+/// don't wait for a receiver to take the message.
+///
+/// [`send`]: struct.Sender.html#method.send
+/// [`send_batch`]: struct.Sender.html#method.send_batch
+/// [`Permit`]: struct.Permit.html
+/// [`Permit::send`]: struct.Permit.html#method.send
+/// [`Sink`]: ../sink/trait.Sink.html
+/// [`Sink::start_send`]: ../sink/trait.Sink.html#tymethod.start_send
 ///
 /// # Examples
 ///
@@ -55,12 +75,39 @@ use crate::sync::WakerSet;
 /// #
 /// # })
 /// ```
+///
+/// A rendezvous channel:
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::future;
+/// use async_std::sync::channel;
+/// use async_std::task;
+///
+/// let (s, r) = channel(0);
+///
+/// // Nobody is receiving, so the message can't be handed over.
+/// assert!(future::timeout(Duration::from_millis(10), s.send(1)).await.is_err());
+///
+/// task::spawn(async move {
+///     s.send(2).await;
+/// });
+/// assert_eq!(r.recv().await, Some(2));
+/// assert_eq!(r.recv().await, None);
+/// #
+/// # })
+/// ```
 #[cfg(feature = "unstable")]
 #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
 pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
     let channel = Arc::new(Channel::with_capacity(cap));
     let s = Sender {
         channel: channel.clone(),
+        ticket: None,
+        reserved: false,
     };
     let r = Receiver {
         channel,
@@ -102,6 +149,12 @@ pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
 pub struct Sender<T> {
     /// The inner channel.
     channel: Arc<Channel<T>>,
+
+    /// The ticket of this sender while the `Sink` impl waits for capacity.
+    ticket: Option<usize>,
+
+    /// Whether `poll_ready` has claimed capacity for the next message passed to `start_send`.
+    reserved: bool,
 }
 
 impl<T> Sender<T> {
@@ -109,6 +162,8 @@ impl<T> Sender<T> {
     ///
     /// If the channel is full, this method will wait until there is space in the channel.
     ///
+    /// If the channel is closed, the message is dropped and this method returns immediately.
+    ///
     /// # Examples
     ///
     /// ```
@@ -134,7 +189,8 @@ impl<T> Sender<T> {
         struct SendFuture<'a, T> {
             channel: &'a Channel<T>,
             msg: Option<T>,
-            opt_key: Option<usize>,
+            ticket: Option<usize>,
+            handoff: Handoff,
         }
 
         impl<T> Unpin for SendFuture<'_, T> {}
@@ -143,55 +199,420 @@ impl<T> Sender<T> {
             type Output = ();
 
             fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-                loop {
-                    let msg = self.msg.take().unwrap();
+                let this = &mut *self;
+
+                if this.handoff.pos.is_none() {
+                    // If the channel is closed, the message is dropped.
+                    match futures_core::ready!(poll_send(
+                        this.channel,
+                        &mut this.msg,
+                        &mut this.ticket,
+                        cx
+                    )) {
+                        Ok(pos) if this.channel.rendezvous => this.handoff.pos = Some(pos),
+                        _ => return Poll::Ready(()),
+                    }
+                }
+
+                // Wait for a receiver to take the message.
+                poll_handoff(this.channel, &mut this.handoff, cx).map(|_| ())
+            }
+        }
+
+        impl<T> Drop for SendFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the operation is still waiting for capacity, it is being cancelled now. Pass
+                // capacity handed to it on to the next one.
+                if let Some(ticket) = self.ticket {
+                    self.channel.cancel_acquire(ticket);
+                }
+                self.handoff.cancel(self.channel);
+            }
+        }
+
+        SendFuture {
+            channel: &self.channel,
+            msg: Some(msg),
+            ticket: None,
+            handoff: Handoff::new(),
+        }
+        .await
+    }
+
+    /// Sends a message into the channel, waiting at most `dur` for space to become available.
+    ///
+    /// If the message could not be sent in time or all receivers have been dropped, the message
+    /// is returned back in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::sync::{channel, SendTimeoutError};
+    ///
+    /// let (s, r) = channel(1);
+    /// let dur = Duration::from_millis(10);
+    ///
+    /// assert_eq!(s.send_timeout(1, dur).await, Ok(()));
+    /// assert_eq!(s.send_timeout(2, dur).await, Err(SendTimeoutError::Timeout(2)));
+    ///
+    /// drop(r);
+    /// assert_eq!(s.send_timeout(3, dur).await, Err(SendTimeoutError::Disconnected(3)));
+    /// #
+    /// # })
+    /// ```
+    pub async fn send_timeout(&self, msg: T, dur: Duration) -> Result<(), SendTimeoutError<T>> {
+        struct SendTimeoutFuture<'a, T> {
+            channel: &'a Channel<T>,
+            msg: Option<T>,
+            ticket: Option<usize>,
+            handoff: Handoff,
+            delay: Delay,
+        }
+
+        impl<T> Unpin for SendTimeoutFuture<'_, T> {}
+
+        impl<T> Future for SendTimeoutFuture<'_, T> {
+            type Output = Result<(), SendTimeoutError<T>>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+
+                if this.handoff.pos.is_none() {
+                    match poll_send(this.channel, &mut this.msg, &mut this.ticket, cx) {
+                        Poll::Ready(Ok(pos)) if this.channel.rendezvous => {
+                            this.handoff.pos = Some(pos)
+                        }
+                        Poll::Ready(Ok(_)) => return Poll::Ready(Ok(())),
+                        Poll::Ready(Err(msg)) => {
+                            return Poll::Ready(Err(SendTimeoutError::Disconnected(msg)));
+                        }
+                        Poll::Pending => {
+                            return match Pin::new(&mut this.delay).poll(cx) {
+                                Poll::Ready(()) => {
+                                    let msg = this.msg.take().unwrap();
+                                    Poll::Ready(Err(SendTimeoutError::Timeout(msg)))
+                                }
+                                Poll::Pending => Poll::Pending,
+                            };
+                        }
+                    }
+                }
+
+                // Wait for a receiver to take the message.
+                match poll_handoff(this.channel, &mut this.handoff, cx) {
+                    Poll::Ready(Ok(())) => Poll::Ready(Ok(())),
+                    Poll::Ready(Err(msg)) => Poll::Ready(Err(SendTimeoutError::Disconnected(msg))),
+                    Poll::Pending => match Pin::new(&mut this.delay).poll(cx) {
+                        Poll::Ready(()) => match this.handoff.take_back(this.channel) {
+                            Some(msg) => Poll::Ready(Err(SendTimeoutError::Timeout(msg))),
+                            // A receiver took the message just in time.
+                            None => Poll::Ready(Ok(())),
+                        },
+                        Poll::Pending => Poll::Pending,
+                    },
+                }
+            }
+        }
+
+        impl<T> Drop for SendTimeoutFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the operation is still waiting for capacity, it is being cancelled now. Pass
+                // capacity handed to it on to the next one.
+                if let Some(ticket) = self.ticket {
+                    self.channel.cancel_acquire(ticket);
+                }
+                self.handoff.cancel(self.channel);
+            }
+        }
+
+        SendTimeoutFuture {
+            channel: &self.channel,
+            msg: Some(msg),
+            ticket: None,
+            handoff: Handoff::new(),
+            delay: Delay::new(dur),
+        }
+        .await
+    }
+
+    /// Sends a batch of messages into the channel, in order.
+    ///
+    /// Capacity is claimed for as many messages as fit at once, and receivers are woken once per
+    /// claimed chunk rather than once per message, which makes this cheaper than calling [`send`]
+    /// in a loop. If the channel is full, this method waits until there is space for the next
+    /// chunk. Messages from other senders may be interleaved between chunks.
+    ///
+    /// If the channel is closed, the remaining messages are dropped and this method returns
+    /// immediately.
+    ///
+    /// [`send`]: #method.send
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::channel;
+    /// use async_std::task;
+    ///
+    /// let (s, r) = channel(2);
+    ///
+    /// task::spawn(async move {
+    ///     s.send_batch(1..=5).await;
+    /// });
+    ///
+    /// let mut buf = Vec::new();
+    /// while r.recv_batch(&mut buf, 10).await > 0 {}
+    /// assert_eq!(buf, [1, 2, 3, 4, 5]);
+    /// #
+    /// # })
+    /// ```
+    pub async fn send_batch<I>(&self, msgs: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        struct SendBatchFuture<'a, T, I> {
+            channel: &'a Channel<T>,
+            msgs: I,
+            next: Option<T>,
+            ticket: Option<usize>,
+        }
+
+        impl<T, I> Unpin for SendBatchFuture<'_, T, I> {}
+
+        impl<T, I: Iterator<Item = T>> Future for SendBatchFuture<'_, T, I> {
+            type Output = ();
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                poll_send_batch(
+                    this.channel,
+                    &mut this.msgs,
+                    &mut this.next,
+                    &mut this.ticket,
+                    cx,
+                )
+            }
+        }
+
+        impl<T, I> Drop for SendBatchFuture<'_, T, I> {
+            fn drop(&mut self) {
+                // If the operation is still waiting for capacity, it is being cancelled now. Pass
+                // capacity handed to it on to the next one.
+                if let Some(ticket) = self.ticket {
+                    self.channel.cancel_acquire(ticket);
+                }
+            }
+        }
+
+        // A rendezvous channel hands over one message at a time.
+        if self.channel.rendezvous {
+            for msg in msgs {
+                if self.channel.is_disconnected() {
+                    break;
+                }
+                self.send(msg).await;
+            }
+            return;
+        }
+
+        SendBatchFuture {
+            channel: &self.channel,
+            msgs: msgs.into_iter(),
+            next: None,
+            ticket: None,
+        }
+        .await
+    }
+
+    /// Waits for capacity in the channel and reserves it for one message.
+    ///
+    /// The returned [`Permit`] sends a message without waiting. Reserving first lets a producer
+    /// wait for capacity before constructing an expensive message, so that no message is ever
+    /// built and then lost because the send operation got cancelled.
+    ///
+    /// Returns `None` if the channel is closed.
+    ///
+    /// [`Permit`]: struct.Permit.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel(1);
+    ///
+    /// let permit = s.reserve().await.unwrap();
+    /// assert!(s.is_full());
+    ///
+    /// permit.send(String::from("expensive"));
+    /// assert_eq!(r.recv().await, Some(String::from("expensive")));
+    /// #
+    /// # })
+    /// ```
+    pub async fn reserve(&self) -> Option<Permit<'_, T>> {
+        struct ReserveFuture<'a, T> {
+            channel: &'a Channel<T>,
+            ticket: Option<usize>,
+        }
+
+        impl<T> Future for ReserveFuture<'_, T> {
+            type Output = Result<(), TryReserveError>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                this.channel.poll_acquire(&mut this.ticket, cx)
+            }
+        }
+
+        impl<T> Drop for ReserveFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the operation is still waiting for capacity, it is being cancelled now. Pass
+                // capacity handed to it on to the next one.
+                if let Some(ticket) = self.ticket {
+                    self.channel.cancel_acquire(ticket);
+                }
+            }
+        }
+
+        ReserveFuture {
+            channel: &self.channel,
+            ticket: None,
+        }
+        .await
+        .ok()
+        .map(|()| Permit {
+            channel: &self.channel,
+        })
+    }
+
+    /// Attempts to reserve capacity for one message without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{channel, TryReserveError};
+    ///
+    /// let (s, r) = channel(1);
+    ///
+    /// let permit = s.try_reserve().unwrap();
+    /// assert_eq!(s.try_reserve().unwrap_err(), TryReserveError::Full);
+    ///
+    /// // Dropping an unused permit releases the capacity.
+    /// drop(permit);
+    /// s.try_reserve().unwrap().send(1);
+    ///
+    /// drop(r);
+    /// assert_eq!(s.try_reserve().unwrap_err(), TryReserveError::Closed);
+    /// ```
+    pub fn try_reserve(&self) -> Result<Permit<'_, T>, TryReserveError> {
+        self.channel.try_acquire()?;
+        Ok(Permit {
+            channel: &self.channel,
+        })
+    }
+
+    /// Waits until the channel is closed.
+    ///
+    /// The channel gets closed when all receivers are dropped or when [`Receiver::close`] is
+    /// called. This lets a producer stop early when nobody is interested in its messages anymore.
+    ///
+    /// [`Receiver::close`]: struct.Receiver.html#method.close
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::channel;
+    /// use async_std::task;
+    ///
+    /// let (s, r) = channel::<i32>(1);
+    ///
+    /// task::spawn(async move {
+    ///     drop(r);
+    /// });
+    ///
+    /// s.closed().await;
+    /// assert!(s.is_closed());
+    /// #
+    /// # })
+    /// ```
+    pub async fn closed(&self) {
+        struct ClosedFuture<'a, T> {
+            channel: &'a Channel<T>,
+            opt_key: Option<usize>,
+        }
+
+        impl<T> Future for ClosedFuture<'_, T> {
+            type Output = ();
 
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                loop {
                     // If the current task is in the set, remove it.
                     if let Some(key) = self.opt_key.take() {
-                        self.channel.send_wakers.remove(key);
+                        self.channel.close_wakers.remove(key);
                     }
 
-                    // Try sending the message.
-                    match self.channel.try_send(msg) {
-                        Ok(()) => return Poll::Ready(()),
-                        Err(TrySendError::Disconnected(msg)) => {
-                            self.msg = Some(msg);
-                            return Poll::Pending;
-                        }
-                        Err(TrySendError::Full(msg)) => {
-                            self.msg = Some(msg);
+                    if self.channel.is_disconnected() {
+                        return Poll::Ready(());
+                    }
 
-                            // Insert this send operation.
-                            self.opt_key = Some(self.channel.send_wakers.insert(cx));
+                    // Insert this wait operation.
+                    self.opt_key = Some(self.channel.close_wakers.insert(cx));
 
-                            // If the channel is still full and not disconnected, return.
-                            if self.channel.is_full() && !self.channel.is_disconnected() {
-                                return Poll::Pending;
-                            }
-                        }
+                    // If the channel is still not disconnected, return.
+                    if !self.channel.is_disconnected() {
+                        return Poll::Pending;
                     }
                 }
             }
         }
 
-        impl<T> Drop for SendFuture<'_, T> {
+        impl<T> Drop for ClosedFuture<'_, T> {
             fn drop(&mut self) {
                 // If the current task is still in the set, that means it is being cancelled now.
-                // Wake up another task instead.
                 if let Some(key) = self.opt_key {
-                    self.channel.send_wakers.cancel(key);
+                    self.channel.close_wakers.cancel(key);
                 }
             }
         }
 
-        SendFuture {
+        ClosedFuture {
             channel: &self.channel,
-            msg: Some(msg),
             opt_key: None,
         }
         .await
     }
 
+    /// Returns `true` if the channel is closed.
+    ///
+    /// The channel is closed when all receivers are dropped or when [`Receiver::close`] is
+    /// called. Messages sent into a closed channel are dropped.
+    ///
+    /// [`Receiver::close`]: struct.Receiver.html#method.close
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel::<i32>(1);
+    /// assert!(!s.is_closed());
+    ///
+    /// drop(r);
+    /// assert!(s.is_closed());
+    /// ```
+    pub fn is_closed(&self) -> bool {
+        self.channel.is_disconnected()
+    }
+
     /// Returns the channel capacity.
     ///
     /// # Examples
@@ -203,7 +624,7 @@ impl<T> Sender<T> {
     /// assert_eq!(s.capacity(), 5);
     /// ```
     pub fn capacity(&self) -> usize {
-        self.channel.cap
+        self.channel.capacity()
     }
 
     /// Returns `true` if the channel is empty.
@@ -273,6 +694,14 @@ impl<T> Sender<T> {
 
 impl<T> Drop for Sender<T> {
     fn drop(&mut self) {
+        // Give back capacity claimed by the `Sink` impl but never used.
+        if self.reserved {
+            self.channel.release();
+        }
+        if let Some(ticket) = self.ticket {
+            self.channel.cancel_acquire(ticket);
+        }
+
         // Decrement the sender count and disconnect the channel if it drops down to zero.
         if self.channel.sender_count.fetch_sub(1, Ordering::AcqRel) == 1 {
             self.channel.disconnect();
@@ -291,7 +720,50 @@ impl<T> Clone for Sender<T> {
 
         Sender {
             channel: self.channel.clone(),
+            ticket: None,
+            reserved: false,
+        }
+    }
+}
+
+impl<T> Sink<T> for Sender<T> {
+    type Error = TryReserveError;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        if this.reserved {
+            return Poll::Ready(Ok(()));
+        }
+
+        let res = futures_core::ready!(this.channel.poll_acquire(&mut this.ticket, cx));
+        this.reserved = res.is_ok();
+        Poll::Ready(res)
+    }
+
+    fn start_send(self: Pin<&mut Self>, msg: T) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        if !this.reserved {
+            this.channel.try_acquire()?;
         }
+        this.reserved = false;
+        this.channel
+            .push(msg)
+            .map(drop)
+            .map_err(|_| TryReserveError::Closed)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Messages are in the channel as soon as they are sent.
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        if this.reserved {
+            this.reserved = false;
+            this.channel.release();
+        }
+        Poll::Ready(Ok(()))
     }
 }
 
@@ -301,19 +773,57 @@ impl<T> fmt::Debug for Sender<T> {
     }
 }
 
-/// The receiving side of a channel.
-///
-/// This type receives messages by calling `recv`. But it also implements the [`Stream`] trait,
-/// which means it can act as an asynchronous iterator. This struct is created by the [`channel`]
-/// function. See its documentation for more.
-///
-/// [`channel`]: fn.channel.html
-/// [`Stream`]: ../stream/trait.Stream.html
+/// Capacity reserved in a channel for one message.
 ///
-/// # Examples
+/// This struct is created by the [`Sender::reserve`] and [`Sender::try_reserve`] methods.
+/// Dropping it without sending a message releases the capacity.
 ///
-/// ```
-/// # async_std::task::block_on(async {
+/// [`Sender::reserve`]: struct.Sender.html#method.reserve
+/// [`Sender::try_reserve`]: struct.Sender.html#method.try_reserve
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Permit<'a, T> {
+    channel: &'a Channel<T>,
+}
+
+impl<T> Permit<'_, T> {
+    /// Sends a message using the reserved capacity.
+    ///
+    /// This never waits. If the channel got closed in the meantime, the message is dropped.
+    pub fn send(self, msg: T) {
+        let channel = self.channel;
+
+        // The capacity is now owned by the message.
+        mem::forget(self);
+        let _ = channel.push(msg);
+    }
+}
+
+impl<T> Drop for Permit<'_, T> {
+    fn drop(&mut self) {
+        self.channel.release();
+    }
+}
+
+impl<T> fmt::Debug for Permit<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Permit { .. }")
+    }
+}
+
+/// The receiving side of a channel.
+///
+/// This type receives messages by calling `recv`. But it also implements the [`Stream`] trait,
+/// which means it can act as an asynchronous iterator. This struct is created by the [`channel`]
+/// function. See its documentation for more.
+///
+/// [`channel`]: fn.channel.html
+/// [`Stream`]: ../stream/trait.Stream.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
 /// #
 /// use std::time::Duration;
 ///
@@ -405,6 +915,199 @@ impl<T> Receiver<T> {
         .await
     }
 
+    /// Receives up to `max` messages from the channel at once, appending them to `buf`.
+    ///
+    /// If the channel is empty and still has senders, this method waits until at least one
+    /// message arrives. It then takes the messages that are already queued, up to `max`, and
+    /// frees their capacity in one step, waking at most one blocked sender per message.
+    ///
+    /// Returns the number of received messages, which is zero only if the channel is closed and
+    /// empty. If this method is cancelled, no messages are lost.
+    ///
+    /// # Panics
+    ///
+    /// If `max` is zero, this method will panic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel(10);
+    ///
+    /// s.send_batch(vec![1, 2, 3]).await;
+    /// drop(s);
+    ///
+    /// let mut buf = Vec::new();
+    /// assert_eq!(r.recv_batch(&mut buf, 2).await, 2);
+    /// assert_eq!(r.recv_batch(&mut buf, 2).await, 1);
+    /// assert_eq!(r.recv_batch(&mut buf, 2).await, 0);
+    /// assert_eq!(buf, [1, 2, 3]);
+    /// #
+    /// # })
+    /// ```
+    pub async fn recv_batch(&self, buf: &mut Vec<T>, max: usize) -> usize {
+        assert!(max > 0, "batch size must be positive");
+
+        struct RecvBatchFuture<'a, T> {
+            channel: &'a Channel<T>,
+            buf: &'a mut Vec<T>,
+            max: usize,
+            opt_key: Option<usize>,
+        }
+
+        impl<T> Unpin for RecvBatchFuture<'_, T> {}
+
+        impl<T> Future for RecvBatchFuture<'_, T> {
+            type Output = usize;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                poll_recv_batch(this.channel, this.buf, this.max, &mut this.opt_key, cx)
+            }
+        }
+
+        impl<T> Drop for RecvBatchFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                if let Some(key) = self.opt_key {
+                    self.channel.recv_wakers.cancel(key);
+                }
+            }
+        }
+
+        RecvBatchFuture {
+            channel: &self.channel,
+            buf,
+            max,
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Receives a message from the channel, waiting at most `dur` for one to arrive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::sync::{channel, RecvTimeoutError};
+    ///
+    /// let (s, r) = channel(1);
+    /// let dur = Duration::from_millis(10);
+    ///
+    /// assert_eq!(r.recv_timeout(dur).await, Err(RecvTimeoutError::Timeout));
+    ///
+    /// s.send(1).await;
+    /// drop(s);
+    /// assert_eq!(r.recv_timeout(dur).await, Ok(1));
+    /// assert_eq!(r.recv_timeout(dur).await, Err(RecvTimeoutError::Disconnected));
+    /// #
+    /// # })
+    /// ```
+    pub async fn recv_timeout(&self, dur: Duration) -> Result<T, RecvTimeoutError> {
+        struct RecvTimeoutFuture<'a, T> {
+            channel: &'a Channel<T>,
+            opt_key: Option<usize>,
+            delay: Delay,
+        }
+
+        impl<T> Future for RecvTimeoutFuture<'_, T> {
+            type Output = Result<T, RecvTimeoutError>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                match poll_recv(
+                    this.channel,
+                    &this.channel.recv_wakers,
+                    &mut this.opt_key,
+                    cx,
+                ) {
+                    Poll::Ready(Some(msg)) => Poll::Ready(Ok(msg)),
+                    Poll::Ready(None) => Poll::Ready(Err(RecvTimeoutError::Disconnected)),
+                    Poll::Pending => match Pin::new(&mut this.delay).poll(cx) {
+                        Poll::Ready(()) => Poll::Ready(Err(RecvTimeoutError::Timeout)),
+                        Poll::Pending => Poll::Pending,
+                    },
+                }
+            }
+        }
+
+        impl<T> Drop for RecvTimeoutFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                if let Some(key) = self.opt_key {
+                    self.channel.recv_wakers.cancel(key);
+                }
+            }
+        }
+
+        RecvTimeoutFuture {
+            channel: &self.channel,
+            opt_key: None,
+            delay: Delay::new(dur),
+        }
+        .await
+    }
+
+    /// Closes the channel.
+    ///
+    /// Senders can no longer send messages, but messages already in the channel can still be
+    /// received. Once the channel is empty, receive operations return `None`.
+    ///
+    /// Tasks waiting in [`Sender::closed`] are woken up.
+    ///
+    /// [`Sender::closed`]: struct.Sender.html#method.closed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel(2);
+    /// s.send(1).await;
+    ///
+    /// r.close();
+    /// assert!(s.is_closed());
+    ///
+    /// assert_eq!(r.recv().await, Some(1));
+    /// assert_eq!(r.recv().await, None);
+    /// #
+    /// # })
+    /// ```
+    pub fn close(&self) {
+        self.channel.disconnect();
+    }
+
+    /// Returns `true` if the channel is closed.
+    ///
+    /// The channel is closed when all senders are dropped or when [`close`] is called. Messages
+    /// in a closed channel can still be received.
+    ///
+    /// [`close`]: #method.close
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel::<i32>(1);
+    /// assert!(!r.is_closed());
+    ///
+    /// drop(s);
+    /// assert!(r.is_closed());
+    /// ```
+    pub fn is_closed(&self) -> bool {
+        self.channel.is_disconnected()
+    }
+
     /// Returns the channel capacity.
     ///
     /// # Examples
@@ -416,7 +1119,7 @@ impl<T> Receiver<T> {
     /// assert_eq!(r.capacity(), 5);
     /// ```
     pub fn capacity(&self) -> usize {
-        self.channel.cap
+        self.channel.capacity()
     }
 
     /// Returns `true` if the channel is empty.
@@ -534,6 +1237,105 @@ impl<T> fmt::Debug for Receiver<T> {
     }
 }
 
+/// Polls a send operation on a channel.
+///
+/// If the send operation is blocked, it waits in line for capacity and its ticket will be stored in
+/// `ticket`. If the channel is disconnected, the message is returned back. On success, returns the
+/// position of the message in the queue.
+fn poll_send<T>(
+    channel: &Channel<T>,
+    msg: &mut Option<T>,
+    ticket: &mut Option<usize>,
+    cx: &mut Context<'_>,
+) -> Poll<Result<usize, T>> {
+    // Yield if the task has used up its budget.
+    futures_core::ready!(budget::poll_proceed(cx));
+
+    // Wait for capacity, then push the message.
+    let res = futures_core::ready!(channel.poll_acquire(ticket, cx));
+    let m = msg.take().unwrap();
+    match res {
+        Ok(()) => Poll::Ready(channel.push(m)),
+        Err(_) => Poll::Ready(Err(m)),
+    }
+}
+
+/// A message offered by a send operation on a rendezvous channel.
+struct Handoff {
+    /// The position of the offered message in the queue, or `None` if nothing is offered.
+    pos: Option<usize>,
+
+    /// The key for the send operation in the `channel.handoff_wakers` set.
+    opt_key: Option<usize>,
+}
+
+impl Handoff {
+    fn new() -> Handoff {
+        Handoff {
+            pos: None,
+            opt_key: None,
+        }
+    }
+
+    /// Takes the offered message back, unless a receiver has taken it already.
+    fn take_back<T>(&mut self, channel: &Channel<T>) -> Option<T> {
+        let pos = self.pos.take()?;
+        if let Some(key) = self.opt_key.take() {
+            channel.handoff_wakers.remove(key);
+        }
+        channel.take_back(pos)
+    }
+
+    /// Withdraws the offered message of a cancelled send operation.
+    fn cancel<T>(&mut self, channel: &Channel<T>) {
+        if let Some(key) = self.opt_key.take() {
+            channel.handoff_wakers.cancel(key);
+        }
+        if let Some(pos) = self.pos.take() {
+            drop(channel.take_back(pos));
+        }
+    }
+}
+
+/// Polls a send operation on a rendezvous channel until a receiver takes the offered message.
+///
+/// If the channel gets disconnected before that, the message is taken back and returned.
+fn poll_handoff<T>(
+    channel: &Channel<T>,
+    handoff: &mut Handoff,
+    cx: &mut Context<'_>,
+) -> Poll<Result<(), T>> {
+    let pos = handoff.pos.unwrap();
+
+    loop {
+        // If the current task is in the set, remove it.
+        if let Some(key) = handoff.opt_key.take() {
+            channel.handoff_wakers.remove(key);
+        }
+
+        // The head moves past the message once a receiver has taken it.
+        if channel.head_position() != pos {
+            handoff.pos = None;
+            return Poll::Ready(Ok(()));
+        }
+
+        if channel.is_disconnected() {
+            if let Some(msg) = handoff.take_back(channel) {
+                return Poll::Ready(Err(msg));
+            }
+            return Poll::Ready(Ok(()));
+        }
+
+        // Insert this send operation.
+        handoff.opt_key = Some(channel.handoff_wakers.insert(cx));
+
+        // If the message is still offered and the channel not disconnected, return.
+        if channel.head_position() == pos && !channel.is_disconnected() {
+            return Poll::Pending;
+        }
+    }
+}
+
 /// Polls a receive operation on a channel.
 ///
 /// If the receive operation is blocked, the current task will be inserted into `wakers` and its
@@ -544,6 +1346,9 @@ fn poll_recv<T>(
     opt_key: &mut Option<usize>,
     cx: &mut Context<'_>,
 ) -> Poll<Option<T>> {
+    // Yield if the task has used up its budget.
+    futures_core::ready!(budget::poll_proceed(cx));
+
     loop {
         // If the current task is in the set, remove it.
         if let Some(key) = opt_key.take() {
@@ -567,50 +1372,291 @@ fn poll_recv<T>(
     }
 }
 
-/// A slot in a channel.
-struct Slot<T> {
-    /// The current stamp.
-    stamp: AtomicUsize,
+/// Polls a batched send operation on a channel.
+///
+/// Messages are taken from `msgs`, with `next` holding one that was taken but couldn't be sent
+/// yet. If the operation is blocked, it waits in line for capacity and its ticket will be stored in
+/// `ticket`. If the channel is disconnected, the remaining messages are dropped.
+fn poll_send_batch<T, I: Iterator<Item = T>>(
+    channel: &Channel<T>,
+    msgs: &mut I,
+    next: &mut Option<T>,
+    ticket: &mut Option<usize>,
+    cx: &mut Context<'_>,
+) -> Poll<()> {
+    // Yield if the task has used up its budget.
+    futures_core::ready!(budget::poll_proceed(cx));
 
+    loop {
+        if next.is_none() {
+            *next = msgs.next();
+        }
+        if next.is_none() {
+            return Poll::Ready(());
+        }
+
+        // Wait for capacity for the next message.
+        if futures_core::ready!(channel.poll_acquire(ticket, cx)).is_err() {
+            return Poll::Ready(());
+        }
+
+        // Try claiming capacity for as many of the following messages as the iterator promises.
+        let more = channel.try_acquire_many(msgs.size_hint().0).unwrap_or(0);
+        let mut claimed = more + 1;
+        let mut sent = 0;
+
+        while claimed > 0 {
+            let msg = match next.take().or_else(|| msgs.next()) {
+                Some(msg) => msg,
+                None => break,
+            };
+            claimed -= 1;
+
+            // A failed push releases the capacity of its message by itself.
+            if channel.push_quiet(msg).is_err() {
+                channel.release_many(claimed);
+                channel.notify_receivers(sent);
+                return Poll::Ready(());
+            }
+            sent += 1;
+        }
+
+        // The iterator ended earlier than its size hint promised.
+        channel.release_many(claimed);
+        channel.notify_receivers(sent);
+    }
+}
+
+/// Polls a batched receive operation on a channel.
+///
+/// If the receive operation is blocked, the current task will be inserted into
+/// `channel.recv_wakers` and its associated key will then be stored in `opt_key`.
+fn poll_recv_batch<T>(
+    channel: &Channel<T>,
+    buf: &mut Vec<T>,
+    max: usize,
+    opt_key: &mut Option<usize>,
+    cx: &mut Context<'_>,
+) -> Poll<usize> {
+    // Yield if the task has used up its budget.
+    futures_core::ready!(budget::poll_proceed(cx));
+
+    loop {
+        // If the current task is in the set, remove it.
+        if let Some(key) = opt_key.take() {
+            channel.recv_wakers.remove(key);
+        }
+
+        // Try receiving messages.
+        match channel.try_recv_many(buf, max) {
+            Ok(n) => return Poll::Ready(n),
+            Err(TryRecvError::Disconnected) => return Poll::Ready(0),
+            Err(TryRecvError::Empty) => {
+                // Insert this receive operation.
+                *opt_key = Some(channel.recv_wakers.insert(cx));
+
+                // If the channel is still empty and not disconnected, return.
+                if channel.is_empty() && !channel.is_disconnected() {
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+/// The slot has a message written into it.
+const WRITE: usize = 1;
+
+/// The message has been read from the slot.
+const READ: usize = 2;
+
+/// The block the slot is in is being destroyed.
+const DESTROY: usize = 4;
+
+/// The number of positions in a block, one of which doesn't hold a message.
+const LAP: usize = 32;
+
+/// The number of messages a block can hold.
+const BLOCK_CAP: usize = LAP - 1;
+
+/// How many lower bits of a position are reserved for metadata.
+const SHIFT: usize = 1;
+
+/// In the tail, this bit indicates that the channel is disconnected. In the head, it indicates
+/// that the block isn't the last one.
+const MARK_BIT: usize = 1;
+
+/// A slot in a block.
+struct Slot<T> {
     /// The message in this slot.
-    msg: UnsafeCell<T>,
+    msg: UnsafeCell<MaybeUninit<T>>,
+
+    /// The state of the slot, made of the `WRITE`, `READ`, and `DESTROY` bits.
+    state: AtomicUsize,
+}
+
+impl<T> Slot<T> {
+    /// Waits until a message is written into the slot.
+    fn wait_write(&self) {
+        let backoff = Backoff::new();
+        while self.state.load(Ordering::Acquire) & WRITE == 0 {
+            backoff.snooze();
+        }
+    }
+}
+
+/// A segment of the queue of messages.
+///
+/// Blocks are allocated as the queue grows and freed once all of their messages have been read.
+struct Block<T> {
+    /// The next block in the queue.
+    next: AtomicPtr<Block<T>>,
+
+    /// The slots of the block.
+    slots: [Slot<T>; BLOCK_CAP],
+}
+
+impl<T> Block<T> {
+    /// Creates an empty block.
+    fn new() -> Block<T> {
+        // This is safe because null pointers, zeroed states, and uninitialized messages are all
+        // valid.
+        unsafe { MaybeUninit::zeroed().assume_init() }
+    }
+
+    /// Waits until the next block is installed.
+    fn wait_next(&self) -> *mut Block<T> {
+        let backoff = Backoff::new();
+        loop {
+            let next = self.next.load(Ordering::Acquire);
+            if !next.is_null() {
+                return next;
+            }
+            backoff.snooze();
+        }
+    }
+
+    /// Frees the block once the messages in slots `start..` have been read.
+    ///
+    /// If a reader is still busy with one of the slots, it takes over freeing the block.
+    unsafe fn destroy(this: *mut Block<T>, start: usize) {
+        // The last slot doesn't need the `DESTROY` bit, since its reader started destroying the
+        // block.
+        for i in start..BLOCK_CAP - 1 {
+            let slot = (*this).slots.get_unchecked(i);
+
+            // Set the `DESTROY` bit if a reader is still using the slot.
+            if slot.state.load(Ordering::Acquire) & READ == 0
+                && slot.state.fetch_or(DESTROY, Ordering::AcqRel) & READ == 0
+            {
+                // The reader will continue destroying the block.
+                return;
+            }
+        }
+
+        // No reader is using the block anymore, so it can be freed.
+        drop(Box::from_raw(this));
+    }
+}
+
+/// A position in the queue of messages.
+struct Position<T> {
+    /// The index, shifted left by `SHIFT`, with the metadata in the lower bits.
+    index: AtomicUsize,
+
+    /// The block the index is in.
+    block: AtomicPtr<Block<T>>,
+}
+
+/// Send operations waiting for capacity in a channel.
+///
+/// Every waiting operation draws a ticket, and freed capacity is handed to the operations in
+/// ticket order, one message's worth each, so a send operation can't be overtaken by ones that
+/// started waiting later.
+struct SendQueue {
+    /// The number of waiting operations, which is checked without locking.
+    len: AtomicUsize,
+
+    /// The waiting operations.
+    inner: Mutex<Tickets>,
+}
+
+struct Tickets {
+    /// The ticket of the next operation to start waiting.
+    next: usize,
+
+    /// The tickets of waiting operations, oldest first, with the tasks to wake.
+    waiting: VecDeque<(usize, Waker)>,
+
+    /// The tickets capacity has been handed to, which their operations haven't noticed yet.
+    granted: Vec<usize>,
+}
+
+impl SendQueue {
+    fn new() -> SendQueue {
+        SendQueue {
+            len: AtomicUsize::new(0),
+            inner: Mutex::new(Tickets {
+                next: 0,
+                waiting: VecDeque::new(),
+                granted: Vec::new(),
+            }),
+        }
+    }
+
+    /// Returns `true` if no send operation is waiting.
+    fn is_empty(&self) -> bool {
+        self.len.load(Ordering::SeqCst) == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Tickets> {
+        self.inner.lock().unwrap()
+    }
+}
+
+impl Tickets {
+    /// Removes the ticket of an operation from the queue, returning `true` if it was there.
+    fn remove(&mut self, ticket: usize, len: &AtomicUsize) -> bool {
+        match self.waiting.iter().position(|(t, _)| *t == ticket) {
+            Some(i) => {
+                self.waiting.remove(i);
+                len.store(self.waiting.len(), Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Hands capacity for one message to the oldest waiting operation, returning its task.
+    fn grant(&mut self, len: &AtomicUsize) -> Option<Waker> {
+        let (ticket, waker) = self.waiting.pop_front()?;
+        len.store(self.waiting.len(), Ordering::SeqCst);
+        self.granted.push(ticket);
+        Some(waker)
+    }
 }
 
-/// Bounded channel based on a preallocated array.
+/// Bounded channel based on a segmented queue.
+///
+/// Messages live in a linked list of blocks, which senders and receivers move through with atomic
+/// operations only. The capacity is enforced by counting claimed capacity separately, in
+/// `occupied`.
 struct Channel<T> {
     /// The head of the channel.
     ///
-    /// This value is a "stamp" consisting of an index into the buffer, a mark bit, and a lap, but
-    /// packed into a single `usize`. The lower bits represent the index, while the upper bits
-    /// represent the lap. The mark bit in the head is always zero.
-    ///
     /// Messages are popped from the head of the channel.
-    head: AtomicUsize,
+    head: CachePadded<Position<T>>,
 
     /// The tail of the channel.
     ///
-    /// This value is a "stamp" consisting of an index into the buffer, a mark bit, and a lap, but
-    /// packed into a single `usize`. The lower bits represent the index, while the upper bits
-    /// represent the lap. The mark bit indicates that the channel is disconnected.
-    ///
     /// Messages are pushed into the tail of the channel.
-    tail: AtomicUsize,
-
-    /// The buffer holding slots.
-    buffer: *mut Slot<T>,
+    tail: CachePadded<Position<T>>,
 
     /// The channel capacity.
     cap: usize,
 
-    /// A stamp with the value of `{ lap: 1, mark: 0, index: 0 }`.
-    one_lap: usize,
-
-    /// If this bit is set in the tail, that means either all senders were dropped or all receivers
-    /// were dropped.
-    mark_bit: usize,
-
     /// Send operations waiting while the channel is full.
-    send_wakers: WakerSet,
+    send_queue: SendQueue,
 
     /// Receive operations waiting while the channel is empty and not disconnected.
     recv_wakers: WakerSet,
@@ -618,12 +1664,30 @@ struct Channel<T> {
     /// Streams waiting while the channel is empty and not disconnected.
     stream_wakers: WakerSet,
 
+    /// `Sender::closed()` operations waiting for the channel to get disconnected.
+    close_wakers: WakerSet,
+
+    /// Send operations on a rendezvous channel waiting for their message to be taken.
+    handoff_wakers: WakerSet,
+
+    /// Set if the channel was created with zero capacity.
+    ///
+    /// A rendezvous channel has room for one message, but send operations wait until their
+    /// message has been received.
+    rendezvous: bool,
+
     /// The number of currently active `Sender`s.
     sender_count: AtomicUsize,
 
     /// The number of currently active `Receivers`s.
     receiver_count: AtomicUsize,
 
+    /// The number of messages in the queue plus the number of outstanding `Permit`s.
+    ///
+    /// Every send operation first claims a unit of capacity here, which guarantees that the queue
+    /// never holds more than `cap` messages.
+    occupied: CachePadded<AtomicUsize>,
+
     /// Indicates that dropping a `Channel<T>` may drop values of type `T`.
     _marker: PhantomData<T>,
 }
@@ -633,195 +1697,393 @@ unsafe impl<T: Send> Sync for Channel<T> {}
 impl<T> Unpin for Channel<T> {}
 
 impl<T> Channel<T> {
-    /// Creates a bounded channel of capacity `cap`.
+    /// Creates a bounded channel of capacity `cap`, or a rendezvous channel if `cap` is zero.
     fn with_capacity(cap: usize) -> Self {
-        assert!(cap > 0, "capacity must be positive");
-
-        // Compute constants `mark_bit` and `one_lap`.
-        let mark_bit = (cap + 1).next_power_of_two();
-        let one_lap = mark_bit * 2;
-
-        // Head is initialized to `{ lap: 0, mark: 0, index: 0 }`.
-        let head = 0;
-        // Tail is initialized to `{ lap: 0, mark: 0, index: 0 }`.
-        let tail = 0;
-
-        // Allocate a buffer of `cap` slots.
-        let buffer = {
-            let mut v = Vec::<Slot<T>>::with_capacity(cap);
-            let ptr = v.as_mut_ptr();
-            mem::forget(v);
-            ptr
+        let position = || {
+            CachePadded::new(Position {
+                index: AtomicUsize::new(0),
+                block: AtomicPtr::new(ptr::null_mut()),
+            })
         };
 
-        // Initialize stamps in the slots.
-        for i in 0..cap {
-            unsafe {
-                // Set the stamp to `{ lap: 0, mark: 0, index: i }`.
-                let slot = buffer.add(i);
-                ptr::write(&mut (*slot).stamp, AtomicUsize::new(i));
-            }
-        }
-
         Channel {
-            buffer,
-            cap,
-            one_lap,
-            mark_bit,
-            head: AtomicUsize::new(head),
-            tail: AtomicUsize::new(tail),
-            send_wakers: WakerSet::new(),
+            head: position(),
+            tail: position(),
+            cap: cap.max(1),
+            send_queue: SendQueue::new(),
             recv_wakers: WakerSet::new(),
             stream_wakers: WakerSet::new(),
+            close_wakers: WakerSet::new(),
+            handoff_wakers: WakerSet::new(),
+            rendezvous: cap == 0,
             sender_count: AtomicUsize::new(1),
             receiver_count: AtomicUsize::new(1),
+            occupied: CachePadded::new(AtomicUsize::new(0)),
             _marker: PhantomData,
         }
     }
 
-    /// Attempts to send a message.
-    fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
-        let backoff = Backoff::new();
-        let mut tail = self.tail.load(Ordering::Relaxed);
+    /// Attempts to claim capacity for one message.
+    ///
+    /// This fails while send operations are waiting for capacity, so that they aren't overtaken.
+    fn try_acquire(&self) -> Result<(), TryReserveError> {
+        if self.is_disconnected() {
+            return Err(TryReserveError::Closed);
+        }
+        if !self.send_queue.is_empty() {
+            return Err(TryReserveError::Full);
+        }
+        self.claim(1).map(drop)
+    }
+
+    /// Attempts to claim capacity for up to `max` messages at once, returning the number of
+    /// messages capacity was claimed for.
+    ///
+    /// Like `try_acquire()`, this fails while send operations are waiting for capacity.
+    fn try_acquire_many(&self, max: usize) -> Result<usize, TryReserveError> {
+        if self.is_disconnected() {
+            return Err(TryReserveError::Closed);
+        }
+        if !self.send_queue.is_empty() {
+            return Err(TryReserveError::Full);
+        }
+        self.claim(max)
+    }
+
+    /// Claims capacity for up to `max` messages, regardless of waiting send operations.
+    fn claim(&self, max: usize) -> Result<usize, TryReserveError> {
+        let mut occupied = self.occupied.load(Ordering::SeqCst);
 
         loop {
-            // Extract mark bit from the tail and unset it.
-            //
-            // If the mark bit was set (which means all receivers have been dropped), we will still
-            // send the message into the channel if there is enough capacity. The message will get
-            // dropped when the channel is dropped (which means when all senders are also dropped).
-            let mark_bit = tail & self.mark_bit;
-            tail ^= mark_bit;
-
-            // Deconstruct the tail.
-            let index = tail & (self.mark_bit - 1);
-            let lap = tail & !(self.one_lap - 1);
-
-            // Inspect the corresponding slot.
-            let slot = unsafe { &*self.buffer.add(index) };
-            let stamp = slot.stamp.load(Ordering::Acquire);
-
-            // If the tail and the stamp match, we may attempt to push.
-            if tail == stamp {
-                let new_tail = if index + 1 < self.cap {
-                    // Same lap, incremented index.
-                    // Set to `{ lap: lap, mark: 0, index: index + 1 }`.
-                    tail + 1
-                } else {
-                    // One lap forward, index wraps around to zero.
-                    // Set to `{ lap: lap.wrapping_add(1), mark: 0, index: 0 }`.
-                    lap.wrapping_add(self.one_lap)
-                };
-
-                // Try moving the tail.
-                match self.tail.compare_exchange_weak(
-                    tail | mark_bit,
-                    new_tail | mark_bit,
-                    Ordering::SeqCst,
-                    Ordering::Relaxed,
-                ) {
-                    Ok(_) => {
-                        // Write the message into the slot and update the stamp.
-                        unsafe { slot.msg.get().write(msg) };
-                        let stamp = tail + 1;
-                        slot.stamp.store(stamp, Ordering::Release);
+            if occupied >= self.cap {
+                return Err(TryReserveError::Full);
+            }
+
+            let n = max.min(self.cap - occupied);
+            match self.occupied.compare_exchange_weak(
+                occupied,
+                occupied + n,
+                Ordering::SeqCst,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Ok(n),
+                Err(o) => occupied = o,
+            }
+        }
+    }
 
-                        // Wake a blocked receive operation.
-                        self.recv_wakers.notify_one();
+    /// Polls for capacity for one message, waiting in line behind send operations that started
+    /// waiting earlier.
+    ///
+    /// While the operation waits, its ticket is stored in `ticket`.
+    fn poll_acquire(
+        &self,
+        ticket: &mut Option<usize>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<(), TryReserveError>> {
+        if let Some(t) = *ticket {
+            let mut tickets = self.send_queue.lock();
+
+            // Capacity may have been handed to this operation.
+            if let Some(i) = tickets.granted.iter().position(|g| *g == t) {
+                tickets.granted.swap_remove(i);
+                *ticket = None;
+                return Poll::Ready(Ok(()));
+            }
 
-                        // Wake all blocked streams.
-                        self.stream_wakers.notify_all();
+            if self.is_disconnected() {
+                tickets.remove(t, &self.send_queue.len);
+                *ticket = None;
+                return Poll::Ready(Err(TryReserveError::Closed));
+            }
 
-                        return Ok(());
-                    }
-                    Err(t) => {
-                        tail = t;
-                        backoff.spin();
-                    }
+            // Keep waiting, but wake the current task.
+            if let Some((_, w)) = tickets.waiting.iter_mut().find(|(g, _)| *g == t) {
+                if !w.will_wake(cx.waker()) {
+                    *w = cx.waker().clone();
                 }
-            } else if stamp.wrapping_add(self.one_lap) == tail + 1 {
-                atomic::fence(Ordering::SeqCst);
-                let head = self.head.load(Ordering::Relaxed);
+            }
+            return Poll::Pending;
+        }
 
-                // If the head lags one lap behind the tail as well...
-                if head.wrapping_add(self.one_lap) == tail {
-                    // ...then the channel is full.
+        // Without anyone waiting, capacity can be claimed without locking.
+        match self.try_acquire() {
+            Err(TryReserveError::Full) => {}
+            res => return Poll::Ready(res),
+        }
 
-                    // Check if the channel is disconnected.
-                    if mark_bit != 0 {
-                        return Err(TrySendError::Disconnected(msg));
-                    } else {
-                        return Err(TrySendError::Full(msg));
-                    }
+        let mut tickets = self.send_queue.lock();
+        let t = tickets.next;
+        tickets.next = t.wrapping_add(1);
+        tickets.waiting.push_back((t, cx.waker().clone()));
+        self.send_queue
+            .len
+            .store(tickets.waiting.len(), Ordering::SeqCst);
+
+        // Capacity may have been released before the ticket was drawn, when there was nobody to
+        // hand it to. Only the first operation in line may take it.
+        if tickets.waiting.len() == 1 {
+            if self.is_disconnected() {
+                tickets.remove(t, &self.send_queue.len);
+                return Poll::Ready(Err(TryReserveError::Closed));
+            }
+            if self.claim(1).is_ok() {
+                tickets.remove(t, &self.send_queue.len);
+                return Poll::Ready(Ok(()));
+            }
+        }
+
+        *ticket = Some(t);
+        Poll::Pending
+    }
+
+    /// Stops waiting for capacity for a cancelled send operation.
+    ///
+    /// If capacity has been handed to the operation already, it is passed on to the next one.
+    fn cancel_acquire(&self, ticket: usize) {
+        let mut tickets = self.send_queue.lock();
+        if tickets.remove(ticket, &self.send_queue.len) {
+            return;
+        }
+        if let Some(i) = tickets.granted.iter().position(|g| *g == ticket) {
+            tickets.granted.swap_remove(i);
+            drop(tickets);
+            self.release();
+        }
+    }
+
+    /// Releases capacity claimed by `try_acquire()` without sending a message.
+    fn release(&self) {
+        self.release_many(1);
+    }
+
+    /// Releases capacity for `n` messages.
+    ///
+    /// The capacity goes to waiting send operations first, one message's worth each in ticket
+    /// order, so that every received message wakes at most one of them.
+    fn release_many(&self, mut n: usize) {
+        if n == 0 {
+            return;
+        }
+
+        let mut wakers = Vec::new();
+        if !self.send_queue.is_empty() {
+            let mut tickets = self.send_queue.lock();
+            while n > 0 {
+                match tickets.grant(&self.send_queue.len) {
+                    Some(w) => wakers.push(w),
+                    None => break,
+                }
+                n -= 1;
+            }
+        }
+
+        if n > 0 {
+            self.occupied.fetch_sub(n, Ordering::SeqCst);
+
+            // A send operation may have drawn a ticket after the check above, without seeing the
+            // released capacity.
+            if !self.send_queue.is_empty() {
+                let mut tickets = self.send_queue.lock();
+                while !tickets.waiting.is_empty() && self.claim(1).is_ok() {
+                    wakers.extend(tickets.grant(&self.send_queue.len));
                 }
+            }
+        }
+
+        for w in wakers {
+            w.wake();
+        }
+
+        // A received message may complete a handoff.
+        if self.rendezvous {
+            self.handoff_wakers.notify_all();
+        }
+    }
+
+    /// Wakes at most `n` blocked receive operations and all blocked streams after `n` messages
+    /// have been pushed.
+    fn notify_receivers(&self, n: usize) {
+        if n == 0 {
+            return;
+        }
+
+        // Wake blocked receive operations, one per message.
+        self.recv_wakers.notify_many(n);
+
+        // Wake all blocked streams.
+        self.stream_wakers.notify_all();
+    }
+
+    /// Pushes a message into the queue after capacity has been claimed for it, and wakes a
+    /// receiver.
+    ///
+    /// If the channel is disconnected, the claimed capacity is released and the message is
+    /// returned back.
+    fn push(&self, msg: T) -> Result<usize, T> {
+        let pos = self.push_quiet(msg)?;
+        self.notify_receivers(1);
+        Ok(pos)
+    }
 
-                backoff.spin();
-                tail = self.tail.load(Ordering::Relaxed);
-            } else {
-                // Snooze because we need to wait for the stamp to get updated.
+    /// Pushes a message like `push()`, but without waking anyone.
+    ///
+    /// Returns the position of the message, which is the position of the head just before the
+    /// message is received.
+    fn push_quiet(&self, msg: T) -> Result<usize, T> {
+        let backoff = Backoff::new();
+        let mut tail = self.tail.index.load(Ordering::Acquire);
+        let mut block = self.tail.block.load(Ordering::Acquire);
+        let mut next_block = None;
+
+        loop {
+            // If the mark bit is set (which means the channel has been closed), the message
+            // cannot be sent anymore.
+            if tail & MARK_BIT != 0 {
+                self.release();
+                return Err(msg);
+            }
+
+            // Calculate the offset of the index into the block.
+            let offset = (tail >> SHIFT) % LAP;
+
+            // If we reached the end of the block, wait until the next one is installed.
+            if offset == BLOCK_CAP {
                 backoff.snooze();
-                tail = self.tail.load(Ordering::Relaxed);
+                tail = self.tail.index.load(Ordering::Acquire);
+                block = self.tail.block.load(Ordering::Acquire);
+                continue;
+            }
+
+            // If we're going to have to install the next block, allocate it in advance in order
+            // to make the wait for other threads as short as possible.
+            if offset + 1 == BLOCK_CAP && next_block.is_none() {
+                next_block = Some(Box::new(Block::<T>::new()));
+            }
+
+            // If this is the first message sent into the channel, install the first block.
+            if block.is_null() {
+                let new = Box::into_raw(Box::new(Block::<T>::new()));
+
+                if self
+                    .tail
+                    .block
+                    .compare_exchange(block, new, Ordering::Release, Ordering::Relaxed)
+                    .is_ok()
+                {
+                    self.head.block.store(new, Ordering::Release);
+                    block = new;
+                } else {
+                    next_block = Some(unsafe { Box::from_raw(new) });
+                    tail = self.tail.index.load(Ordering::Acquire);
+                    block = self.tail.block.load(Ordering::Acquire);
+                    continue;
+                }
+            }
+
+            // Try moving the tail.
+            match self.tail.index.compare_exchange_weak(
+                tail,
+                tail + (1 << SHIFT),
+                Ordering::SeqCst,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => unsafe {
+                    // If we've reached the end of the block, install the next one.
+                    if offset + 1 == BLOCK_CAP {
+                        let next_block = Box::into_raw(next_block.unwrap());
+                        self.tail.block.store(next_block, Ordering::Release);
+                        self.tail.index.fetch_add(1 << SHIFT, Ordering::Release);
+                        (*block).next.store(next_block, Ordering::Release);
+                    }
+
+                    // Write the message into the slot.
+                    let slot = (*block).slots.get_unchecked(offset);
+                    slot.msg.get().write(MaybeUninit::new(msg));
+                    slot.state.fetch_or(WRITE, Ordering::Release);
+
+                    return Ok(tail >> SHIFT);
+                },
+                Err(t) => {
+                    tail = t;
+                    block = self.tail.block.load(Ordering::Acquire);
+                    backoff.spin();
+                }
             }
         }
     }
 
     /// Attempts to receive a message.
     fn try_recv(&self) -> Result<T, TryRecvError> {
+        let msg = self.pop(None)?;
+
+        // Release the capacity and wake a blocked send operation.
+        self.release();
+
+        Ok(msg)
+    }
+
+    /// Attempts to receive up to `max` messages into `buf`.
+    ///
+    /// The capacity of all received messages is released at once. Returns the number of received
+    /// messages, which is at least one.
+    fn try_recv_many(&self, buf: &mut Vec<T>, max: usize) -> Result<usize, TryRecvError> {
+        let mut count = 0;
+
+        while count < max {
+            match self.pop(None) {
+                Ok(msg) => {
+                    buf.push(msg);
+                    count += 1;
+                }
+                Err(err) if count == 0 => return Err(err),
+                Err(_) => break,
+            }
+        }
+
+        // Release the capacity and wake as many blocked send operations.
+        self.release_many(count);
+
+        Ok(count)
+    }
+
+    /// Pops a message from the queue without releasing its capacity.
+    ///
+    /// If `expected` is set, the message is only popped if it is at that position, and the
+    /// operation fails as if the channel was empty otherwise.
+    fn pop(&self, expected: Option<usize>) -> Result<T, TryRecvError> {
         let backoff = Backoff::new();
-        let mut head = self.head.load(Ordering::Relaxed);
+        let mut head = self.head.index.load(Ordering::Acquire);
+        let mut block = self.head.block.load(Ordering::Acquire);
 
         loop {
-            // Deconstruct the head.
-            let index = head & (self.mark_bit - 1);
-            let lap = head & !(self.one_lap - 1);
-
-            // Inspect the corresponding slot.
-            let slot = unsafe { &*self.buffer.add(index) };
-            let stamp = slot.stamp.load(Ordering::Acquire);
-
-            // If the the stamp is ahead of the head by 1, we may attempt to pop.
-            if head + 1 == stamp {
-                let new = if index + 1 < self.cap {
-                    // Same lap, incremented index.
-                    // Set to `{ lap: lap, mark: 0, index: index + 1 }`.
-                    head + 1
-                } else {
-                    // One lap forward, index wraps around to zero.
-                    // Set to `{ lap: lap.wrapping_add(1), mark: 0, index: 0 }`.
-                    lap.wrapping_add(self.one_lap)
-                };
-
-                // Try moving the head.
-                match self.head.compare_exchange_weak(
-                    head,
-                    new,
-                    Ordering::SeqCst,
-                    Ordering::Relaxed,
-                ) {
-                    Ok(_) => {
-                        // Read the message from the slot and update the stamp.
-                        let msg = unsafe { slot.msg.get().read() };
-                        let stamp = head.wrapping_add(self.one_lap);
-                        slot.stamp.store(stamp, Ordering::Release);
+            // Calculate the offset of the index into the block.
+            let offset = (head >> SHIFT) % LAP;
 
-                        // Wake a blocked send operation.
-                        self.send_wakers.notify_one();
+            // If we reached the end of the block, wait until the next one is installed.
+            if offset == BLOCK_CAP {
+                backoff.snooze();
+                head = self.head.index.load(Ordering::Acquire);
+                block = self.head.block.load(Ordering::Acquire);
+                continue;
+            }
 
-                        return Ok(msg);
-                    }
-                    Err(h) => {
-                        head = h;
-                        backoff.spin();
-                    }
+            if let Some(pos) = expected {
+                if pos != head >> SHIFT {
+                    return Err(TryRecvError::Empty);
                 }
-            } else if stamp == head {
+            }
+
+            let mut new_head = head + (1 << SHIFT);
+
+            if new_head & MARK_BIT == 0 {
                 atomic::fence(Ordering::SeqCst);
-                let tail = self.tail.load(Ordering::Relaxed);
+                let tail = self.tail.index.load(Ordering::Relaxed);
 
                 // If the tail equals the head, that means the channel is empty.
-                if (tail & !self.mark_bit) == head {
+                if head >> SHIFT == tail >> SHIFT {
                     // If the channel is disconnected...
-                    if tail & self.mark_bit != 0 {
+                    if tail & MARK_BIT != 0 {
                         return Err(TryRecvError::Disconnected);
                     } else {
                         // Otherwise, the receive operation is not ready.
@@ -829,116 +2091,200 @@ impl<T> Channel<T> {
                     }
                 }
 
-                backoff.spin();
-                head = self.head.load(Ordering::Relaxed);
-            } else {
-                // Snooze because we need to wait for the stamp to get updated.
+                // If head and tail are not in the same block, set the mark bit in the head.
+                if (head >> SHIFT) / LAP != (tail >> SHIFT) / LAP {
+                    new_head |= MARK_BIT;
+                }
+            }
+
+            // The block can be null here only if the first message is being sent into the
+            // channel. In that case, wait until it gets installed.
+            if block.is_null() {
                 backoff.snooze();
-                head = self.head.load(Ordering::Relaxed);
+                head = self.head.index.load(Ordering::Acquire);
+                block = self.head.block.load(Ordering::Acquire);
+                continue;
+            }
+
+            // Try moving the head.
+            match self.head.index.compare_exchange_weak(
+                head,
+                new_head,
+                Ordering::SeqCst,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => unsafe {
+                    // If we've reached the end of the block, move to the next one.
+                    if offset + 1 == BLOCK_CAP {
+                        let next = (*block).wait_next();
+                        let mut next_index = (new_head & !MARK_BIT).wrapping_add(1 << SHIFT);
+                        if !(*next).next.load(Ordering::Relaxed).is_null() {
+                            next_index |= MARK_BIT;
+                        }
+
+                        self.head.block.store(next, Ordering::Release);
+                        self.head.index.store(next_index, Ordering::Release);
+                    }
+
+                    // Read the message from the slot.
+                    let slot = (*block).slots.get_unchecked(offset);
+                    slot.wait_write();
+                    let msg = slot.msg.get().read().assume_init();
+
+                    // Destroy the block if we've reached the end, or if another thread wanted to
+                    // destroy it but couldn't because we were busy reading from the slot.
+                    if offset + 1 == BLOCK_CAP {
+                        Block::destroy(block, 0);
+                    } else if slot.state.fetch_or(READ, Ordering::AcqRel) & DESTROY != 0 {
+                        Block::destroy(block, offset + 1);
+                    }
+
+                    return Ok(msg);
+                },
+                Err(h) => {
+                    head = h;
+                    block = self.head.block.load(Ordering::Acquire);
+                    backoff.spin();
+                }
             }
         }
     }
 
+    /// Returns the position of the head, which is the position of the next message to be
+    /// received.
+    fn head_position(&self) -> usize {
+        self.head.index.load(Ordering::SeqCst) >> SHIFT
+    }
+
+    /// Takes back the message at position `pos` if it hasn't been received yet.
+    fn take_back(&self, pos: usize) -> Option<T> {
+        // Only the message at the head can be taken back, exactly like a receiver would.
+        let msg = self.pop(Some(pos)).ok()?;
+        self.release();
+        Some(msg)
+    }
+
+    /// Returns the capacity the channel was created with.
+    fn capacity(&self) -> usize {
+        if self.rendezvous { 0 } else { self.cap }
+    }
+
     /// Returns the current number of messages inside the channel.
     fn len(&self) -> usize {
         loop {
             // Load the tail, then load the head.
-            let tail = self.tail.load(Ordering::SeqCst);
-            let head = self.head.load(Ordering::SeqCst);
+            let mut tail = self.tail.index.load(Ordering::SeqCst);
+            let mut head = self.head.index.load(Ordering::SeqCst);
 
             // If the tail didn't change, we've got consistent values to work with.
-            if self.tail.load(Ordering::SeqCst) == tail {
-                let hix = head & (self.mark_bit - 1);
-                let tix = tail & (self.mark_bit - 1);
-
-                return if hix < tix {
-                    tix - hix
-                } else if hix > tix {
-                    self.cap - hix + tix
-                } else if (tail & !self.mark_bit) == head {
-                    0
-                } else {
-                    self.cap
-                };
+            if self.tail.index.load(Ordering::SeqCst) == tail {
+                // Erase the lower bits.
+                tail &= !((1 << SHIFT) - 1);
+                head &= !((1 << SHIFT) - 1);
+
+                // Fix up indices if they fall onto block ends.
+                if (tail >> SHIFT) & (LAP - 1) == LAP - 1 {
+                    tail = tail.wrapping_add(1 << SHIFT);
+                }
+                if (head >> SHIFT) & (LAP - 1) == LAP - 1 {
+                    head = head.wrapping_add(1 << SHIFT);
+                }
+
+                // Rotate indices so that head falls into the first block.
+                let lap = (head >> SHIFT) / LAP;
+                tail = tail.wrapping_sub((lap * LAP) << SHIFT);
+                head = head.wrapping_sub((lap * LAP) << SHIFT);
+
+                // Remove the lower bits.
+                tail >>= SHIFT;
+                head >>= SHIFT;
+
+                // Return the difference minus the number of blocks between tail and head.
+                return tail - head - tail / LAP;
             }
         }
     }
 
     /// Returns `true` if the channel is disconnected.
     pub fn is_disconnected(&self) -> bool {
-        self.tail.load(Ordering::SeqCst) & self.mark_bit != 0
+        self.tail.index.load(Ordering::SeqCst) & MARK_BIT != 0
     }
 
     /// Returns `true` if the channel is empty.
     fn is_empty(&self) -> bool {
-        let head = self.head.load(Ordering::SeqCst);
-        let tail = self.tail.load(Ordering::SeqCst);
+        let head = self.head.index.load(Ordering::SeqCst);
+        let tail = self.tail.index.load(Ordering::SeqCst);
 
         // Is the tail equal to the head?
         //
         // Note: If the head changes just before we load the tail, that means there was a moment
         // when the channel was not empty, so it is safe to just return `false`.
-        (tail & !self.mark_bit) == head
+        head >> SHIFT == tail >> SHIFT
     }
 
     /// Returns `true` if the channel is full.
+    ///
+    /// Capacity claimed by outstanding permits counts as occupied.
     fn is_full(&self) -> bool {
-        let tail = self.tail.load(Ordering::SeqCst);
-        let head = self.head.load(Ordering::SeqCst);
-
-        // Is the head lagging one lap behind tail?
-        //
-        // Note: If the tail changes just before we load the head, that means there was a moment
-        // when the channel was not full, so it is safe to just return `false`.
-        head.wrapping_add(self.one_lap) == tail & !self.mark_bit
+        self.occupied.load(Ordering::SeqCst) >= self.cap
     }
 
     /// Disconnects the channel and wakes up all blocked operations.
     fn disconnect(&self) {
-        let tail = self.tail.fetch_or(self.mark_bit, Ordering::SeqCst);
+        let tail = self.tail.index.fetch_or(MARK_BIT, Ordering::SeqCst);
 
-        if tail & self.mark_bit == 0 {
+        if tail & MARK_BIT == 0 {
             // Notify everyone blocked on this channel.
-            self.send_wakers.notify_all();
+            let wakers: Vec<_> = {
+                let tickets = self.send_queue.lock();
+                tickets.waiting.iter().map(|(_, w)| w.clone()).collect()
+            };
+            for w in wakers {
+                w.wake();
+            }
             self.recv_wakers.notify_all();
             self.stream_wakers.notify_all();
+            self.close_wakers.notify_all();
+            self.handoff_wakers.notify_all();
         }
     }
 }
 
 impl<T> Drop for Channel<T> {
     fn drop(&mut self) {
-        // Get the index of the head.
-        let hix = self.head.load(Ordering::Relaxed) & (self.mark_bit - 1);
-
-        // Loop over all slots that hold a message and drop them.
-        for i in 0..self.len() {
-            // Compute the index of the next slot holding a message.
-            let index = if hix + i < self.cap {
-                hix + i
-            } else {
-                hix + i - self.cap
-            };
+        let mut head = *self.head.index.get_mut();
+        let mut tail = *self.tail.index.get_mut();
+        let mut block = *self.head.block.get_mut();
 
-            unsafe {
-                self.buffer.add(index).drop_in_place();
-            }
-        }
+        // Erase the lower bits.
+        head &= !((1 << SHIFT) - 1);
+        tail &= !((1 << SHIFT) - 1);
 
-        // Finally, deallocate the buffer, but don't run any destructors.
         unsafe {
-            Vec::from_raw_parts(self.buffer, 0, self.cap);
-        }
-    }
-}
+            // Drop all messages between head and tail and free the blocks.
+            while head != tail {
+                let offset = (head >> SHIFT) % LAP;
+
+                if offset < BLOCK_CAP {
+                    // Drop the message in the slot.
+                    let slot = (*block).slots.get_unchecked(offset);
+                    (*slot.msg.get()).as_mut_ptr().drop_in_place();
+                } else {
+                    // Free the block and move to the next one.
+                    let next = *(*block).next.get_mut();
+                    drop(Box::from_raw(block));
+                    block = next;
+                }
 
-/// An error returned from the `try_send()` method.
-enum TrySendError<T> {
-    /// The channel is full but not disconnected.
-    Full(T),
+                head = head.wrapping_add(1 << SHIFT);
+            }
 
-    /// The channel is full and disconnected.
-    Disconnected(T),
+            // Free the last remaining block.
+            if !block.is_null() {
+                drop(Box::from_raw(block));
+            }
+        }
+    }
 }
 
 /// An error returned from the `try_recv()` method.
@@ -949,3 +2295,129 @@ enum TryRecvError {
     /// The channel is empty and disconnected.
     Disconnected,
 }
+
+/// An error returned from the [`Sender::send_timeout`] method.
+///
+/// The message that could not be sent is returned back in the error.
+///
+/// [`Sender::send_timeout`]: struct.Sender.html#method.send_timeout
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum SendTimeoutError<T> {
+    /// The channel stayed full until the timeout elapsed.
+    Timeout(T),
+
+    /// All receivers have been dropped.
+    Disconnected(T),
+}
+
+impl<T> SendTimeoutError<T> {
+    /// Returns the message that could not be sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            SendTimeoutError::Timeout(msg) => msg,
+            SendTimeoutError::Disconnected(msg) => msg,
+        }
+    }
+
+    /// Returns `true` if the send operation timed out.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            SendTimeoutError::Timeout(_) => true,
+            SendTimeoutError::Disconnected(_) => false,
+        }
+    }
+
+    /// Returns `true` if the send operation failed because all receivers have been dropped.
+    pub fn is_disconnected(&self) -> bool {
+        match self {
+            SendTimeoutError::Timeout(_) => false,
+            SendTimeoutError::Disconnected(_) => true,
+        }
+    }
+}
+
+impl<T> Error for SendTimeoutError<T> {}
+
+impl<T> fmt::Debug for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendTimeoutError::Timeout(_) => f.pad("Timeout(..)"),
+            SendTimeoutError::Disconnected(_) => f.pad("Disconnected(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for SendTimeoutError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SendTimeoutError::Timeout(_) => "timed out waiting on send operation".fmt(f),
+            SendTimeoutError::Disconnected(_) => "sending on a disconnected channel".fmt(f),
+        }
+    }
+}
+
+/// An error returned from the [`Receiver::recv_timeout`] method.
+///
+/// [`Receiver::recv_timeout`]: struct.Receiver.html#method.recv_timeout
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecvTimeoutError {
+    /// No message was sent before the timeout elapsed.
+    Timeout,
+
+    /// The channel is empty and all senders have been dropped.
+    Disconnected,
+}
+
+impl RecvTimeoutError {
+    /// Returns `true` if the receive operation timed out.
+    pub fn is_timeout(&self) -> bool {
+        *self == RecvTimeoutError::Timeout
+    }
+
+    /// Returns `true` if the receive operation failed because all senders have been dropped.
+    pub fn is_disconnected(&self) -> bool {
+        *self == RecvTimeoutError::Disconnected
+    }
+}
+
+impl Error for RecvTimeoutError {}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => "timed out waiting on receive operation".fmt(f),
+            RecvTimeoutError::Disconnected => {
+                "receiving on an empty and disconnected channel".fmt(f)
+            }
+        }
+    }
+}
+
+/// An error returned from the [`Sender::try_reserve`] method.
+///
+/// [`Sender::try_reserve`]: struct.Sender.html#method.try_reserve
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TryReserveError {
+    /// The channel is full.
+    Full,
+
+    /// The channel is closed.
+    Closed,
+}
+
+impl Error for TryReserveError {}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryReserveError::Full => "reserving capacity in a full channel".fmt(f),
+            TryReserveError::Closed => "reserving capacity in a closed channel".fmt(f),
+        }
+    }
+}
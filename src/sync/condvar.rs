@@ -0,0 +1,360 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex as StdMutex;
+use std::time::{Duration, Instant};
+
+use crate::future::timeout;
+use crate::sync::MutexGuard;
+use crate::task::{Context, Poll, Waker};
+
+/// A condition variable.
+///
+/// Condition variables let tasks wait until some condition on data protected by a [`Mutex`]
+/// becomes true. Waiting atomically releases the mutex and reacquires it before returning.
+///
+/// Waiting tasks are queued in FIFO order: [`notify_one`] always wakes the task that has been
+/// waiting the longest. A waiter that is cancelled after being notified passes its notification
+/// on to the next waiter in the queue, so notifications are never lost.
+///
+/// [`Mutex`]: struct.Mutex.html
+/// [`notify_one`]: #method.notify_one
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::{Arc, Condvar, Mutex};
+/// use async_std::task;
+///
+/// let pair = Arc::new((Mutex::new(false), Condvar::new()));
+/// let pair2 = pair.clone();
+///
+/// task::spawn(async move {
+///     let (lock, cvar) = &*pair2;
+///     *lock.lock().await = true;
+///     cvar.notify_one();
+/// });
+///
+/// let (lock, cvar) = &*pair;
+/// let mut started = lock.lock().await;
+/// while !*started {
+///     started = cvar.wait(started).await;
+/// }
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Condvar {
+    queue: StdMutex<Queue>,
+}
+
+/// The queue of tasks waiting on a condition variable.
+struct Queue {
+    /// Waiters in the order they started waiting.
+    waiters: VecDeque<Waiter>,
+
+    /// The id of the next waiter.
+    next_id: u64,
+}
+
+/// A task waiting on a condition variable.
+struct Waiter {
+    id: u64,
+    waker: Option<Waker>,
+    notified: bool,
+}
+
+impl Queue {
+    /// Notifies the first waiter that has not been notified yet.
+    fn notify_one(&mut self) {
+        if let Some(w) = self.waiters.iter_mut().find(|w| !w.notified) {
+            w.notified = true;
+            if let Some(waker) = w.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl Condvar {
+    /// Creates a new condition variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    /// ```
+    pub fn new() -> Condvar {
+        Condvar {
+            queue: StdMutex::new(Queue {
+                waiters: VecDeque::new(),
+                next_id: 0,
+            }),
+        }
+    }
+
+    /// Releases the mutex and waits for a notification, then reacquires the mutex.
+    ///
+    /// Like with condition variables in `std`, spurious wakeups are possible, so the condition
+    /// should be checked in a loop. [`wait_until`] does that automatically.
+    ///
+    /// [`wait_until`]: #method.wait_until
+    pub async fn wait<'a, T>(&self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+        let mutex = MutexGuard::source(&guard);
+
+        // Register before releasing the mutex so that notifications can't be missed.
+        let wait = self.register();
+        drop(guard);
+        wait.await;
+
+        mutex.lock().await
+    }
+
+    /// Waits until `condition` returns `true`, reacquiring the mutex before each check.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{Arc, Condvar, Mutex};
+    /// use async_std::task;
+    ///
+    /// let pair = Arc::new((Mutex::new(0), Condvar::new()));
+    /// let pair2 = pair.clone();
+    ///
+    /// task::spawn(async move {
+    ///     let (lock, cvar) = &*pair2;
+    ///     for _ in 0..3 {
+    ///         *lock.lock().await += 1;
+    ///         cvar.notify_all();
+    ///     }
+    /// });
+    ///
+    /// let (lock, cvar) = &*pair;
+    /// let n = cvar.wait_until(lock.lock().await, |n| *n == 3).await;
+    /// assert_eq!(*n, 3);
+    /// #
+    /// # })
+    /// ```
+    pub async fn wait_until<'a, T, F>(
+        &self,
+        mut guard: MutexGuard<'a, T>,
+        mut condition: F,
+    ) -> MutexGuard<'a, T>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        while !condition(&mut guard) {
+            guard = self.wait(guard).await;
+        }
+        guard
+    }
+
+    /// Like [`wait`], but gives up waiting for a notification after `dur`.
+    ///
+    /// The mutex is reacquired in either case, which may take longer than `dur`.
+    ///
+    /// [`wait`]: #method.wait
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::sync::{Condvar, Mutex};
+    ///
+    /// let lock = Mutex::new(());
+    /// let cvar = Condvar::new();
+    ///
+    /// let (_guard, res) = cvar.wait_timeout(lock.lock().await, Duration::from_millis(10)).await;
+    /// assert!(res.timed_out());
+    /// #
+    /// # })
+    /// ```
+    pub async fn wait_timeout<'a, T>(
+        &self,
+        guard: MutexGuard<'a, T>,
+        dur: Duration,
+    ) -> (MutexGuard<'a, T>, WaitTimeoutResult) {
+        let mutex = MutexGuard::source(&guard);
+
+        let wait = self.register();
+        drop(guard);
+        let timed_out = timeout(dur, wait).await.is_err();
+
+        (mutex.lock().await, WaitTimeoutResult(timed_out))
+    }
+
+    /// Waits until `condition` returns `true` or `dur` has elapsed in total.
+    ///
+    /// The returned [`WaitTimeoutResult`] reports a timeout only if the condition still doesn't
+    /// hold when the time runs out.
+    ///
+    /// [`WaitTimeoutResult`]: struct.WaitTimeoutResult.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::sync::{Condvar, Mutex};
+    ///
+    /// let lock = Mutex::new(0);
+    /// let cvar = Condvar::new();
+    ///
+    /// let guard = lock.lock().await;
+    /// let (n, res) = cvar
+    ///     .wait_timeout_until(guard, Duration::from_millis(10), |n| *n > 0)
+    ///     .await;
+    /// assert!(res.timed_out());
+    /// assert_eq!(*n, 0);
+    /// #
+    /// # })
+    /// ```
+    pub async fn wait_timeout_until<'a, T, F>(
+        &self,
+        mut guard: MutexGuard<'a, T>,
+        dur: Duration,
+        mut condition: F,
+    ) -> (MutexGuard<'a, T>, WaitTimeoutResult)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        let start = Instant::now();
+        loop {
+            if condition(&mut guard) {
+                return (guard, WaitTimeoutResult(false));
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= dur {
+                return (guard, WaitTimeoutResult(true));
+            }
+
+            guard = self.wait_timeout(guard, dur - elapsed).await.0;
+        }
+    }
+
+    /// Wakes up the task that has been waiting the longest, if any.
+    pub fn notify_one(&self) {
+        self.queue.lock().unwrap().notify_one();
+    }
+
+    /// Wakes up all waiting tasks.
+    pub fn notify_all(&self) {
+        let mut queue = self.queue.lock().unwrap();
+        for w in queue.waiters.iter_mut() {
+            w.notified = true;
+            if let Some(waker) = w.waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Adds a waiter to the back of the queue.
+    fn register(&self) -> WaitFuture<'_> {
+        let mut queue = self.queue.lock().unwrap();
+        let id = queue.next_id;
+        queue.next_id += 1;
+        queue.waiters.push_back(Waiter {
+            id,
+            waker: None,
+            notified: false,
+        });
+
+        WaitFuture {
+            condvar: self,
+            id,
+            done: false,
+        }
+    }
+}
+
+impl Default for Condvar {
+    fn default() -> Condvar {
+        Condvar::new()
+    }
+}
+
+impl fmt::Debug for Condvar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Condvar { .. }")
+    }
+}
+
+/// A future that waits for a waiter in the queue to be notified.
+struct WaitFuture<'a> {
+    condvar: &'a Condvar,
+    id: u64,
+    done: bool,
+}
+
+impl Future for WaitFuture<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut queue = self.condvar.queue.lock().unwrap();
+        let pos = queue
+            .waiters
+            .iter()
+            .position(|w| w.id == self.id)
+            .expect("waiter missing from queue");
+
+        if queue.waiters[pos].notified {
+            queue.waiters.remove(pos);
+            drop(queue);
+            self.done = true;
+            Poll::Ready(())
+        } else {
+            queue.waiters[pos].waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for WaitFuture<'_> {
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+
+        let mut queue = self.condvar.queue.lock().unwrap();
+        if let Some(pos) = queue.waiters.iter().position(|w| w.id == self.id) {
+            let waiter = queue.waiters.remove(pos).unwrap();
+
+            // If this waiter was notified but is being cancelled, pass the notification on.
+            if waiter.notified {
+                queue.notify_one();
+            }
+        }
+    }
+}
+
+/// A type indicating whether a timed wait on a condition variable returned due to a timeout.
+///
+/// This type is returned by [`Condvar::wait_timeout`] and [`Condvar::wait_timeout_until`].
+///
+/// [`Condvar::wait_timeout`]: struct.Condvar.html#method.wait_timeout
+/// [`Condvar::wait_timeout_until`]: struct.Condvar.html#method.wait_timeout_until
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct WaitTimeoutResult(bool);
+
+impl WaitTimeoutResult {
+    /// Returns `true` if the wait timed out.
+    pub fn timed_out(&self) -> bool {
+        self.0
+    }
+}
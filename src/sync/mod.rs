@@ -183,12 +183,44 @@ mod mutex;
 mod rwlock;
 
 cfg_unstable! {
+    pub use atomic_waker::AtomicWaker;
     pub use barrier::{Barrier, BarrierWaitResult};
-    pub use channel::{channel, Sender, Receiver};
+    pub use cancellation_token::{CancellationToken, DropGuard};
+    pub use condvar::{Condvar, WaitTimeoutResult};
+    pub use channel::{
+        channel, Permit, Receiver, RecvTimeoutError, SendTimeoutError, Sender, TryReserveError,
+    };
+    pub use mutex::{MappedMutexGuard, OwnedMutexGuard};
+    pub use notify::{Notified, Notify};
+    pub use once_cell::{Lazy, OnceCell};
+    pub use rwlock::{MappedRwLockReadGuard, RwLockUpgradableReadGuard};
+    pub use semaphore::{OwnedSemaphorePermit, Semaphore, SemaphorePermit};
+    pub use sharded_lock::{ShardedLock, ShardedLockReadGuard, ShardedLockWriteGuard};
+    pub use unbounded::{
+        channel_unbounded, ChannelUsage, SendError, UnboundedOptions, UnboundedReceiver,
+        UnboundedSender,
+    };
+    pub use wait_group::{TaskTracker, WaitGroup};
+    pub use waker_set::WakerSet;
 
+    pub mod broadcast;
+    pub mod oneshot;
+    pub mod priority;
+    pub mod watch;
+
+    mod atomic_waker;
     mod barrier;
+    mod cancellation_token;
     mod channel;
+    mod condvar;
+    mod notify;
+    mod once_cell;
+    mod semaphore;
+    mod sharded_lock;
+    mod unbounded;
+    mod wait_group;
 }
 
 pub(crate) mod waker_set;
+#[cfg(not(feature = "unstable"))]
 pub(crate) use waker_set::WakerSet;
@@ -4,7 +4,15 @@ use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::future::Future;
+#[cfg(feature = "unstable")]
+use std::mem;
+#[cfg(feature = "unstable")]
+use std::sync::Arc;
+#[cfg(feature = "unstable")]
+use std::time::Duration;
 
+#[cfg(feature = "unstable")]
+use crate::future::timeout;
 use crate::sync::WakerSet;
 use crate::task::{Context, Poll};
 
@@ -179,6 +187,98 @@ impl<T> Mutex<T> {
         }
     }
 
+    /// Attempts to acquire the lock, waiting at most `dur`.
+    ///
+    /// If the lock could not be acquired in time, then [`None`] is returned. This is useful to
+    /// detect tasks that are stuck waiting on each other.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::sync::Mutex;
+    ///
+    /// let m = Mutex::new(0);
+    ///
+    /// let guard = m.lock().await;
+    /// assert!(m.try_lock_for(Duration::from_millis(10)).await.is_none());
+    ///
+    /// drop(guard);
+    /// assert!(m.try_lock_for(Duration::from_millis(10)).await.is_some());
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn try_lock_for(&self, dur: Duration) -> Option<MutexGuard<'_, T>> {
+        timeout(dur, self.lock()).await.ok()
+    }
+
+    /// Acquires the lock through an [`Arc`].
+    ///
+    /// Returns a guard that releases the lock when dropped. Unlike [`lock`], the guard keeps the
+    /// mutex alive and is not tied to the lifetime of a borrow, so it can be stored in structs or
+    /// moved into spawned tasks.
+    ///
+    /// [`Arc`]: struct.Arc.html
+    /// [`lock`]: #method.lock
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{Arc, Mutex};
+    /// use async_std::task;
+    ///
+    /// let m = Arc::new(Mutex::new(10));
+    /// let mut guard = m.clone().lock_owned().await;
+    ///
+    /// task::spawn(async move {
+    ///     *guard = 20;
+    /// })
+    /// .await;
+    ///
+    /// assert_eq!(*m.lock().await, 20);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn lock_owned(self: Arc<Self>) -> OwnedMutexGuard<T> {
+        mem::forget(self.lock().await);
+        OwnedMutexGuard(self)
+    }
+
+    /// Attempts to acquire the lock through an [`Arc`].
+    ///
+    /// If the lock could not be acquired at this time, then [`None`] is returned. Otherwise, an
+    /// owned guard is returned that releases the lock when dropped.
+    ///
+    /// [`Arc`]: struct.Arc.html
+    /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{Arc, Mutex};
+    ///
+    /// let m = Arc::new(Mutex::new(10));
+    /// let guard = m.clone().try_lock_owned().unwrap();
+    /// assert!(m.try_lock().is_none());
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn try_lock_owned(self: Arc<Self>) -> Option<OwnedMutexGuard<T>> {
+        mem::forget(self.try_lock()?);
+        Some(OwnedMutexGuard(self))
+    }
+
     /// Consumes the mutex, returning the underlying data.
     ///
     /// # Examples
@@ -250,13 +350,69 @@ pub struct MutexGuard<'a, T>(&'a Mutex<T>);
 unsafe impl<T: Send> Send for MutexGuard<'_, T> {}
 unsafe impl<T: Sync> Sync for MutexGuard<'_, T> {}
 
+/// Releases a mutex given its lock flag and its set of blocked `lock()` operations.
+fn unlock(locked: &AtomicBool, wakers: &WakerSet) {
+    // Use `SeqCst` ordering to synchronize with `WakerSet::insert()` and `WakerSet::update()`.
+    locked.store(false, Ordering::SeqCst);
+
+    // Notify a blocked `lock()` operation if none were notified already.
+    wakers.notify_any();
+}
+
+impl<'a, T> MutexGuard<'a, T> {
+    /// Makes a guard for a component of the locked data.
+    ///
+    /// The mutex stays locked until the returned guard is dropped.
+    ///
+    /// This is an associated function that needs to be used as `MutexGuard::map(...)`, so that
+    /// it doesn't conflict with methods on the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{Mutex, MutexGuard};
+    ///
+    /// let m = Mutex::new((1, String::from("a")));
+    ///
+    /// let mut s = MutexGuard::map(m.lock().await, |pair| &mut pair.1);
+    /// s.push('b');
+    /// drop(s);
+    ///
+    /// assert_eq!(m.lock().await.1, "ab");
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedMutexGuard<'a, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let mutex = this.0;
+        let value = f(unsafe { &mut *mutex.value.get() });
+
+        // The lock is now released by the mapped guard.
+        mem::forget(this);
+
+        MappedMutexGuard {
+            locked: &mutex.locked,
+            wakers: &mutex.wakers,
+            value,
+        }
+    }
+
+    /// Returns the mutex this guard belongs to.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn source(this: &Self) -> &'a Mutex<T> {
+        this.0
+    }
+}
+
 impl<T> Drop for MutexGuard<'_, T> {
     fn drop(&mut self) {
-        // Use `SeqCst` ordering to synchronize with `WakerSet::insert()` and `WakerSet::update()`.
-        self.0.locked.store(false, Ordering::SeqCst);
-
-        // Notify a blocked `lock()` operation if none were notified already.
-        self.0.wakers.notify_any();
+        unlock(&self.0.locked, &self.0.wakers);
     }
 }
 
@@ -285,3 +441,146 @@ impl<T> DerefMut for MutexGuard<'_, T> {
         unsafe { &mut *self.0.value.get() }
     }
 }
+
+/// An owned guard that releases the lock when dropped.
+///
+/// This guard is created by the [`lock_owned`] and [`try_lock_owned`] methods on [`Mutex`].
+///
+/// [`lock_owned`]: struct.Mutex.html#method.lock_owned
+/// [`try_lock_owned`]: struct.Mutex.html#method.try_lock_owned
+/// [`Mutex`]: struct.Mutex.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct OwnedMutexGuard<T>(Arc<Mutex<T>>);
+
+#[cfg(feature = "unstable")]
+unsafe impl<T: Send> Send for OwnedMutexGuard<T> {}
+#[cfg(feature = "unstable")]
+unsafe impl<T: Send + Sync> Sync for OwnedMutexGuard<T> {}
+
+#[cfg(feature = "unstable")]
+impl<T> OwnedMutexGuard<T> {
+    /// Returns the mutex this guard is locking.
+    pub fn mutex(this: &Self) -> &Arc<Mutex<T>> {
+        &this.0
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T> Drop for OwnedMutexGuard<T> {
+    fn drop(&mut self) {
+        unlock(&self.0.locked, &self.0.wakers);
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: fmt::Debug> fmt::Debug for OwnedMutexGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: fmt::Display> fmt::Display for OwnedMutexGuard<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T> Deref for OwnedMutexGuard<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.0.value.get() }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T> DerefMut for OwnedMutexGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.0.value.get() }
+    }
+}
+
+/// A guard for a component of the data locked by a [`MutexGuard`].
+///
+/// This guard is created by [`MutexGuard::map`]. It releases the lock when dropped.
+///
+/// [`MutexGuard`]: struct.MutexGuard.html
+/// [`MutexGuard::map`]: struct.MutexGuard.html#method.map
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct MappedMutexGuard<'a, T: ?Sized> {
+    locked: &'a AtomicBool,
+    wakers: &'a WakerSet,
+    value: &'a mut T,
+}
+
+#[cfg(feature = "unstable")]
+unsafe impl<T: ?Sized + Send> Send for MappedMutexGuard<'_, T> {}
+#[cfg(feature = "unstable")]
+unsafe impl<T: ?Sized + Sync> Sync for MappedMutexGuard<'_, T> {}
+
+#[cfg(feature = "unstable")]
+impl<'a, T: ?Sized> MappedMutexGuard<'a, T> {
+    /// Makes a guard for a component of the data locked by this guard.
+    ///
+    /// This is an associated function that needs to be used as `MappedMutexGuard::map(...)`, so
+    /// that it doesn't conflict with methods on the locked data.
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedMutexGuard<'a, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        let locked = this.locked;
+        let wakers = this.wakers;
+        let value: *mut T = &mut *this.value;
+        let value = f(unsafe { &mut *value });
+
+        // The lock is now released by the new guard.
+        mem::forget(this);
+
+        MappedMutexGuard {
+            locked,
+            wakers,
+            value,
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized> Drop for MappedMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        unlock(self.locked, self.wakers);
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized + fmt::Debug> fmt::Debug for MappedMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized + fmt::Display> fmt::Display for MappedMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized> Deref for MappedMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized> DerefMut for MappedMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value
+    }
+}
@@ -0,0 +1,223 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::sync::WakerSet;
+use crate::task::{Context, Poll};
+
+/// Notifies a single task or all waiting tasks to wake up.
+///
+/// `Notify` carries no data: it is a building block for signaling between tasks. Waiting is done
+/// by awaiting the future returned by [`notified`].
+///
+/// [`notify_one`] wakes up a single waiting task. If no task is waiting, a permit is stored so
+/// that the next call to [`notified`] completes immediately. At most one permit is stored.
+///
+/// [`notify_waiters`] wakes up all tasks that called [`notified`] before it, without storing a
+/// permit.
+///
+/// [`notified`]: #method.notified
+/// [`notify_one`]: #method.notify_one
+/// [`notify_waiters`]: #method.notify_waiters
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::{Arc, Notify};
+/// use async_std::task;
+///
+/// let notify = Arc::new(Notify::new());
+/// let notify2 = notify.clone();
+///
+/// let handle = task::spawn(async move {
+///     notify2.notified().await;
+///     println!("received notification");
+/// });
+///
+/// notify.notify_one();
+/// handle.await;
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Notify {
+    /// Set when `notify_one()` was called but no waiting task consumed the notification yet.
+    permit: AtomicBool,
+
+    /// Incremented on every call to `notify_waiters()`.
+    generation: AtomicUsize,
+
+    /// Tasks waiting for a notification.
+    wakers: WakerSet,
+}
+
+unsafe impl Send for Notify {}
+unsafe impl Sync for Notify {}
+
+impl Notify {
+    /// Creates a new `Notify` without a stored permit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Notify;
+    ///
+    /// let notify = Notify::new();
+    /// ```
+    pub fn new() -> Notify {
+        Notify {
+            permit: AtomicBool::new(false),
+            generation: AtomicUsize::new(0),
+            wakers: WakerSet::new(),
+        }
+    }
+
+    /// Waits for a notification.
+    ///
+    /// The returned future completes after a call to [`notify_one`] or, if it was created before
+    /// the call, after a call to [`notify_waiters`].
+    ///
+    /// [`notify_one`]: #method.notify_one
+    /// [`notify_waiters`]: #method.notify_waiters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Notify;
+    ///
+    /// let notify = Notify::new();
+    ///
+    /// // The permit is stored because no task is waiting yet.
+    /// notify.notify_one();
+    /// notify.notified().await;
+    /// #
+    /// # })
+    /// ```
+    pub fn notified(&self) -> Notified<'_> {
+        Notified {
+            notify: self,
+            generation: self.generation.load(Ordering::SeqCst),
+            opt_key: None,
+        }
+    }
+
+    /// Notifies a single waiting task, or stores a permit if no task is waiting.
+    pub fn notify_one(&self) {
+        // Use `SeqCst` ordering to synchronize with `WakerSet::insert()`.
+        self.permit.store(true, Ordering::SeqCst);
+        self.wakers.notify_one();
+    }
+
+    /// Notifies all tasks currently waiting.
+    ///
+    /// Futures returned by [`notified`] after this call are not affected.
+    ///
+    /// [`notified`]: #method.notified
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Notify;
+    ///
+    /// let notify = Notify::new();
+    /// let a = notify.notified();
+    /// let b = notify.notified();
+    ///
+    /// notify.notify_waiters();
+    /// a.await;
+    /// b.await;
+    /// #
+    /// # })
+    /// ```
+    pub fn notify_waiters(&self) {
+        // Use `SeqCst` ordering to synchronize with `WakerSet::insert()`.
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.wakers.notify_all();
+    }
+
+    /// Returns `true` if a future created at `generation` may complete.
+    fn is_notified(&self, generation: usize) -> bool {
+        self.generation.load(Ordering::SeqCst) != generation || self.permit.load(Ordering::SeqCst)
+    }
+}
+
+impl Default for Notify {
+    fn default() -> Notify {
+        Notify::new()
+    }
+}
+
+impl fmt::Debug for Notify {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Notify { .. }")
+    }
+}
+
+/// A future waiting for a notification from a [`Notify`].
+///
+/// This future is created by the [`notified`] method on [`Notify`]. See its documentation for
+/// more.
+///
+/// [`Notify`]: struct.Notify.html
+/// [`notified`]: struct.Notify.html#method.notified
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Notified<'a> {
+    notify: &'a Notify,
+    generation: usize,
+    opt_key: Option<usize>,
+}
+
+impl Future for Notified<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            // If the current task is in the set, remove it.
+            if let Some(key) = self.opt_key.take() {
+                self.notify.wakers.remove(key);
+            }
+
+            // Complete if `notify_waiters()` was called since this future was created, or if a
+            // permit can be consumed.
+            if self.notify.generation.load(Ordering::SeqCst) != self.generation
+                || self.notify.permit.swap(false, Ordering::SeqCst)
+            {
+                return Poll::Ready(());
+            }
+
+            // Insert this wait operation.
+            self.opt_key = Some(self.notify.wakers.insert(cx));
+
+            // If there is still no notification, return.
+            if !self.notify.is_notified(self.generation) {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl Drop for Notified<'_> {
+    fn drop(&mut self) {
+        // If the current task is still in the set, that means it is being cancelled now. A
+        // notification it received is passed on to another waiting task.
+        if let Some(key) = self.opt_key {
+            self.notify.wakers.cancel(key);
+        }
+    }
+}
+
+impl fmt::Debug for Notified<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Notified { .. }")
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ::once_cell::sync::{Lazy as SyncLazy, OnceCell as SyncOnceCell};
+
+use crate::sync::WakerSet;
+use crate::task::{Context, Poll};
+
+/// A cell that can be written to only once, with asynchronous initialization.
+///
+/// When several tasks call [`get_or_init`] concurrently, only one of them runs its initializer.
+/// The others wait until the value is available. If the running initializer is cancelled or
+/// panics, one of the waiting tasks runs its own initializer instead.
+///
+/// [`get_or_init`]: #method.get_or_init
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::OnceCell;
+///
+/// static CONFIG: OnceCell<String> = OnceCell::new();
+///
+/// async fn load_config() -> String {
+///     String::from("debug = true")
+/// }
+///
+/// let config = CONFIG.get_or_init(load_config).await;
+/// assert_eq!(config, "debug = true");
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct OnceCell<T> {
+    /// The value, once initialized.
+    value: SyncOnceCell<T>,
+
+    /// Set while an initializer is running.
+    initializing: AtomicBool,
+
+    /// Tasks waiting for a running initializer to finish.
+    wakers: SyncLazy<WakerSet>,
+}
+
+unsafe impl<T: Send> Send for OnceCell<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+impl<T> OnceCell<T> {
+    /// Creates a new empty cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::<i32>::new();
+    /// assert!(cell.get().is_none());
+    /// ```
+    pub const fn new() -> OnceCell<T> {
+        OnceCell {
+            value: SyncOnceCell::new(),
+            initializing: AtomicBool::new(false),
+            wakers: SyncLazy::new(WakerSet::new),
+        }
+    }
+
+    /// Returns a reference to the value if the cell is initialized.
+    pub fn get(&self) -> Option<&T> {
+        self.value.get()
+    }
+
+    /// Returns a mutable reference to the value if the cell is initialized.
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.value.get_mut()
+    }
+
+    /// Sets the value of the cell.
+    ///
+    /// If the cell is already initialized or is being initialized, the value is returned back in
+    /// the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    /// assert_eq!(cell.set(1), Ok(()));
+    /// assert_eq!(cell.set(2), Err(2));
+    /// assert_eq!(cell.get(), Some(&1));
+    /// ```
+    pub fn set(&self, value: T) -> Result<(), T> {
+        if self.initializing.swap(true, Ordering::SeqCst) {
+            return Err(value);
+        }
+
+        let res = self.value.set(value);
+        self.finish_init();
+        res
+    }
+
+    /// Returns the value, initializing it with `f` if the cell is empty.
+    ///
+    /// Only one initializer runs at a time. Other callers wait for it and then return the value
+    /// it produced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    /// assert_eq!(*cell.get_or_init(|| async { 1 }).await, 1);
+    /// assert_eq!(*cell.get_or_init(|| async { 2 }).await, 1);
+    /// #
+    /// # })
+    /// ```
+    pub async fn get_or_init<F, Fut>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        enum Never {}
+
+        match self
+            .get_or_try_init(|| async { Ok::<T, Never>(f().await) })
+            .await
+        {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Returns the value, initializing it with `f` if the cell is empty.
+    ///
+    /// If the initializer fails, the error is returned, the cell stays empty, and another caller
+    /// may try to initialize it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    /// assert_eq!(cell.get_or_try_init(|| async { Err(()) }).await, Err(()));
+    /// assert_eq!(cell.get_or_try_init(|| async { Ok::<_, ()>(1) }).await, Ok(&1));
+    /// #
+    /// # })
+    /// ```
+    pub async fn get_or_try_init<F, Fut, E>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut f = Some(f);
+
+        loop {
+            if let Some(value) = self.value.get() {
+                return Ok(value);
+            }
+
+            if !self.initializing.swap(true, Ordering::SeqCst) {
+                // Let other tasks initialize the cell if this initializer is cancelled or panics.
+                let guard = InitGuard(self);
+
+                let value = (f.take().unwrap())().await?;
+                let _ = self.value.set(value);
+
+                drop(guard);
+                return Ok(self.value.get().unwrap());
+            }
+
+            WaitFuture {
+                cell: self,
+                opt_key: None,
+            }
+            .await;
+        }
+    }
+
+    /// Consumes the cell, returning the value if it is initialized.
+    pub fn into_inner(self) -> Option<T> {
+        self.value.into_inner()
+    }
+
+    /// Takes the value out of the cell, leaving it empty.
+    pub fn take(&mut self) -> Option<T> {
+        self.value.take()
+    }
+
+    /// Marks the running initialization as finished and wakes up waiting tasks.
+    fn finish_init(&self) {
+        // Use `SeqCst` ordering to synchronize with `WakerSet::insert()`.
+        self.initializing.store(false, Ordering::SeqCst);
+        self.wakers.notify_all();
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    fn default() -> OnceCell<T> {
+        OnceCell::new()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.get() {
+            Some(value) => f.debug_tuple("OnceCell").field(value).finish(),
+            None => f.write_str("OnceCell(<uninit>)"),
+        }
+    }
+}
+
+impl<T> From<T> for OnceCell<T> {
+    fn from(value: T) -> OnceCell<T> {
+        let cell = OnceCell::new();
+        let _ = cell.set(value);
+        cell
+    }
+}
+
+/// Finishes a running initialization when dropped, whether it succeeded or not.
+struct InitGuard<'a, T>(&'a OnceCell<T>);
+
+impl<T> Drop for InitGuard<'_, T> {
+    fn drop(&mut self) {
+        self.0.finish_init();
+    }
+}
+
+/// A future that waits until no initializer is running.
+struct WaitFuture<'a, T> {
+    cell: &'a OnceCell<T>,
+    opt_key: Option<usize>,
+}
+
+impl<T> Future for WaitFuture<'_, T> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            // If the current task is in the set, remove it.
+            if let Some(key) = self.opt_key.take() {
+                self.cell.wakers.remove(key);
+            }
+
+            if !self.cell.initializing.load(Ordering::SeqCst) {
+                return Poll::Ready(());
+            }
+
+            // Insert this wait operation.
+            self.opt_key = Some(self.cell.wakers.insert(cx));
+
+            // If the initializer is still running, return.
+            if self.cell.initializing.load(Ordering::SeqCst) {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl<T> Drop for WaitFuture<'_, T> {
+    fn drop(&mut self) {
+        // If the current task is still in the set, that means it is being cancelled now.
+        if let Some(key) = self.opt_key {
+            self.cell.wakers.cancel(key);
+        }
+    }
+}
+
+/// A value that is initialized asynchronously on first access.
+///
+/// The initializer is a function returning a future. It runs at most once to completion, even
+/// when several tasks access the value concurrently.
+///
+/// The default type of the initializer is a function pointer returning a boxed future, so that
+/// `Lazy` values can be declared as statics.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::Lazy;
+///
+/// static GREETING: Lazy<String> = Lazy::new(|| Box::pin(async { String::from("hello") }));
+///
+/// assert_eq!(Lazy::force(&GREETING).await, "hello");
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Lazy<T, F = fn() -> Pin<Box<dyn Future<Output = T> + Send>>> {
+    cell: OnceCell<T>,
+    init: F,
+}
+
+impl<T, F> Lazy<T, F> {
+    /// Creates a new lazy value with the given initializer.
+    pub const fn new(init: F) -> Lazy<T, F> {
+        Lazy {
+            cell: OnceCell::new(),
+            init,
+        }
+    }
+
+    /// Returns a reference to the value if it is initialized.
+    ///
+    /// This is an associated function that needs to be used as `Lazy::get(...)`.
+    pub fn get(this: &Lazy<T, F>) -> Option<&T> {
+        this.cell.get()
+    }
+}
+
+impl<T, F, Fut> Lazy<T, F>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = T>,
+{
+    /// Returns the value, running the initializer if needed.
+    ///
+    /// This is an associated function that needs to be used as `Lazy::force(...)`.
+    pub async fn force(this: &Lazy<T, F>) -> &T {
+        this.cell.get_or_init(&this.init).await
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Lazy<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lazy").field("cell", &self.cell).finish()
+    }
+}
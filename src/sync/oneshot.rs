@@ -0,0 +1,344 @@
+//! A channel for sending a single value between tasks.
+//!
+//! The [`Receiver`] is a future that resolves to the value sent through the [`Sender`], or to an
+//! error if the sender is dropped without sending anything.
+//!
+//! [`Sender`]: struct.Sender.html
+//! [`Receiver`]: struct.Receiver.html
+//!
+//! # Examples
+//!
+//! ```
+//! # async_std::task::block_on(async {
+//! #
+//! use async_std::sync::oneshot;
+//! use async_std::task;
+//!
+//! let (s, r) = oneshot::channel();
+//!
+//! task::spawn(async move {
+//!     s.send(1 + 2).unwrap();
+//! });
+//!
+//! assert_eq!(r.await, Ok(3));
+//! #
+//! # })
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// Creates a new oneshot channel.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::oneshot;
+///
+/// let (s, r) = oneshot::channel::<i32>();
+/// drop(s);
+/// assert!(r.await.is_err());
+/// #
+/// # })
+/// ```
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let channel = Arc::new(Channel {
+        state: Mutex::new(State {
+            value: None,
+            sender_dropped: false,
+            receiver_dropped: false,
+            recv_waker: None,
+            closed_waker: None,
+        }),
+    });
+    let s = Sender {
+        channel: channel.clone(),
+    };
+    let r = Receiver { channel };
+    (s, r)
+}
+
+/// The sending side of a oneshot channel.
+///
+/// This struct is created by the [`channel`] function. See its
+/// documentation for more.
+///
+/// [`channel`]: fn.channel.html
+pub struct Sender<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a value to the receiver.
+    ///
+    /// If the receiver has been dropped or closed, the value is returned back in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::oneshot;
+    ///
+    /// let (s, r) = oneshot::channel();
+    /// drop(r);
+    /// assert_eq!(s.send(1), Err(1));
+    /// ```
+    pub fn send(self, value: T) -> Result<(), T> {
+        let mut state = self.channel.state.lock().unwrap();
+        if state.receiver_dropped {
+            return Err(value);
+        }
+
+        state.value = Some(value);
+        if let Some(w) = state.recv_waker.take() {
+            w.wake();
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the receiver has been dropped or closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::oneshot;
+    ///
+    /// let (s, r) = oneshot::channel::<i32>();
+    /// assert!(!s.is_closed());
+    /// drop(r);
+    /// assert!(s.is_closed());
+    /// ```
+    pub fn is_closed(&self) -> bool {
+        self.channel.state.lock().unwrap().receiver_dropped
+    }
+
+    /// Waits until the receiver is dropped or closed.
+    ///
+    /// This is useful to stop computing a value nobody is interested in anymore.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::oneshot;
+    /// use async_std::task;
+    ///
+    /// let (mut s, r) = oneshot::channel::<i32>();
+    ///
+    /// task::spawn(async move {
+    ///     drop(r);
+    /// });
+    ///
+    /// s.closed().await;
+    /// assert!(s.is_closed());
+    /// #
+    /// # })
+    /// ```
+    pub async fn closed(&mut self) {
+        struct ClosedFuture<'a, T> {
+            channel: &'a Channel<T>,
+        }
+
+        impl<T> Future for ClosedFuture<'_, T> {
+            type Output = ();
+
+            fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let mut state = self.channel.state.lock().unwrap();
+                if state.receiver_dropped {
+                    Poll::Ready(())
+                } else {
+                    state.closed_waker = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }
+
+        ClosedFuture {
+            channel: &self.channel,
+        }
+        .await
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.channel.state.lock().unwrap();
+        state.sender_dropped = true;
+        if let Some(w) = state.recv_waker.take() {
+            w.wake();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Sender { .. }")
+    }
+}
+
+/// The receiving side of a oneshot channel.
+///
+/// The receiver is a future resolving to the sent value. If the sender is dropped without
+/// sending a value, it resolves to an error.
+///
+/// This struct is created by the [`channel`] function. See its
+/// documentation for more.
+///
+/// [`channel`]: fn.channel.html
+pub struct Receiver<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Attempts to receive the value without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::oneshot::{self, TryRecvError};
+    ///
+    /// let (s, mut r) = oneshot::channel();
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// s.send(1).unwrap();
+    /// assert_eq!(r.try_recv(), Ok(1));
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Closed));
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut state = self.channel.state.lock().unwrap();
+        match state.value.take() {
+            Some(value) => Ok(value),
+            None if state.sender_dropped => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+
+    /// Closes the channel, preventing the sender from sending a value.
+    ///
+    /// A value that was sent before the channel was closed can still be received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::oneshot;
+    ///
+    /// let (s, mut r) = oneshot::channel();
+    /// r.close();
+    /// assert_eq!(s.send(1), Err(1));
+    /// ```
+    pub fn close(&mut self) {
+        self.channel.close();
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.channel.state.lock().unwrap();
+        match state.value.take() {
+            Some(value) => Poll::Ready(Ok(value)),
+            None if state.sender_dropped => Poll::Ready(Err(RecvError { _private: () })),
+            None => {
+                state.recv_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.channel.close();
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Receiver { .. }")
+    }
+}
+
+/// The mutable state of a oneshot channel.
+struct State<T> {
+    /// The sent value, if it hasn't been received yet.
+    value: Option<T>,
+
+    /// Set to `true` once the sender has sent a value or has been dropped.
+    sender_dropped: bool,
+
+    /// Set to `true` once the receiver has been closed or dropped.
+    receiver_dropped: bool,
+
+    /// The waker of a receive operation waiting for the value.
+    recv_waker: Option<Waker>,
+
+    /// The waker of a `Sender::closed()` operation.
+    closed_waker: Option<Waker>,
+}
+
+/// The state shared between the sender and the receiver of a oneshot channel.
+struct Channel<T> {
+    /// The mutable state of the channel.
+    state: Mutex<State<T>>,
+}
+
+impl<T> Channel<T> {
+    /// Closes the channel from the receiving side.
+    fn close(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.receiver_dropped = true;
+        if let Some(w) = state.closed_waker.take() {
+            w.wake();
+        }
+    }
+}
+
+/// An error returned when awaiting a [`Receiver`].
+///
+/// The sender was dropped without sending a value.
+///
+/// [`Receiver`]: struct.Receiver.html
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RecvError {
+    _private: (),
+}
+
+impl Error for RecvError {}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "the sender was dropped without sending a value".fmt(f)
+    }
+}
+
+/// An error returned from the [`Receiver::try_recv`] method.
+///
+/// [`Receiver::try_recv`]: struct.Receiver.html#method.try_recv
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TryRecvError {
+    /// The value hasn't been sent yet.
+    Empty,
+
+    /// The sender was dropped without sending a value, or the value was already received.
+    Closed,
+}
+
+impl Error for TryRecvError {}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => "receiving on an empty channel".fmt(f),
+            TryRecvError::Closed => "receiving on a closed channel".fmt(f),
+        }
+    }
+}
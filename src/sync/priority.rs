@@ -0,0 +1,702 @@
+//! A multi-producer, multi-consumer channel that delivers the greatest message first.
+//!
+//! Messages are ordered by their [`Ord`] implementation: a receive operation always gets the
+//! greatest message currently in the channel. Messages that compare equal are received in the
+//! order they were sent.
+//!
+//! To attach a priority to messages that are not ordered themselves, send tuples such as
+//! `(priority, sequence, job)`, or wrap the priority in [`Reverse`] to receive the smallest
+//! message first.
+//!
+//! [`Ord`]: https://doc.rust-lang.org/std/cmp/trait.Ord.html
+//! [`Reverse`]: https://doc.rust-lang.org/std/cmp/struct.Reverse.html
+//!
+//! # Examples
+//!
+//! ```
+//! # async_std::task::block_on(async {
+//! #
+//! use async_std::sync::priority;
+//!
+//! let (s, r) = priority::channel(16);
+//!
+//! s.send(1).await.unwrap();
+//! s.send(3).await.unwrap();
+//! s.send(2).await.unwrap();
+//!
+//! assert_eq!(r.recv().await, Some(3));
+//! assert_eq!(r.recv().await, Some(2));
+//! assert_eq!(r.recv().await, Some(1));
+//! #
+//! # })
+//! ```
+
+use std::cmp::Ordering as CmpOrdering;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use crate::stream::Stream;
+use crate::sync::WakerSet;
+
+/// Creates a bounded priority channel.
+///
+/// The channel holds at most `cap` messages at a time. Sending into a full channel waits until
+/// a message is received.
+///
+/// When all senders are dropped, receivers can still receive the remaining messages and then
+/// get `None`. When all receivers are dropped, sending returns an error.
+///
+/// # Panics
+///
+/// If `cap` is zero, this function will panic.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::cmp::Reverse;
+///
+/// use async_std::sync::priority;
+///
+/// // Receive jobs with the smallest deadline first.
+/// let (s, r) = priority::channel(4);
+///
+/// s.send((Reverse(20), "later")).await.unwrap();
+/// s.send((Reverse(10), "sooner")).await.unwrap();
+/// drop(s);
+///
+/// assert_eq!(r.recv().await, Some((Reverse(10), "sooner")));
+/// assert_eq!(r.recv().await, Some((Reverse(20), "later")));
+/// assert_eq!(r.recv().await, None);
+/// #
+/// # })
+/// ```
+pub fn channel<T: Ord>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    let channel = Arc::new(Channel::with_capacity(cap));
+    let s = Sender {
+        channel: channel.clone(),
+    };
+    let r = Receiver {
+        channel,
+        opt_key: None,
+    };
+    (s, r)
+}
+
+/// The sending side of a priority channel.
+///
+/// This struct is created by the [`channel`] function. See its
+/// documentation for more.
+///
+/// [`channel`]: fn.channel.html
+pub struct Sender<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+}
+
+impl<T: Ord> Sender<T> {
+    /// Sends a message into the channel.
+    ///
+    /// If the channel is full, this method will wait until there is space in the channel. If all
+    /// receivers have been dropped, the message is returned back in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::priority;
+    ///
+    /// let (s, r) = priority::channel(1);
+    /// assert!(s.send(1).await.is_ok());
+    ///
+    /// drop(r);
+    /// assert!(s.send(2).await.is_err());
+    /// #
+    /// # })
+    /// ```
+    pub async fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        struct SendFuture<'a, T> {
+            channel: &'a Channel<T>,
+            msg: Option<T>,
+            opt_key: Option<usize>,
+        }
+
+        impl<T> Unpin for SendFuture<'_, T> {}
+
+        impl<T: Ord> Future for SendFuture<'_, T> {
+            type Output = Result<(), SendError<T>>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                loop {
+                    let msg = self.msg.take().unwrap();
+
+                    // If the current task is in the set, remove it.
+                    if let Some(key) = self.opt_key.take() {
+                        self.channel.send_wakers.remove(key);
+                    }
+
+                    // Try sending the message.
+                    match self.channel.try_send(msg) {
+                        Ok(()) => return Poll::Ready(Ok(())),
+                        Err(TrySendError::Closed(msg)) => return Poll::Ready(Err(SendError(msg))),
+                        Err(TrySendError::Full(msg)) => {
+                            self.msg = Some(msg);
+
+                            // Insert this send operation.
+                            self.opt_key = Some(self.channel.send_wakers.insert(cx));
+
+                            // If the channel is still full and not closed, return.
+                            if self.channel.is_full() && !self.channel.is_closed() {
+                                return Poll::Pending;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        impl<T> Drop for SendFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                // Wake up another task instead.
+                if let Some(key) = self.opt_key {
+                    self.channel.send_wakers.cancel(key);
+                }
+            }
+        }
+
+        SendFuture {
+            channel: &self.channel,
+            msg: Some(msg),
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Attempts to send a message into the channel without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::priority::{self, TrySendError};
+    ///
+    /// let (s, r) = priority::channel(1);
+    /// assert_eq!(s.try_send(1), Ok(()));
+    /// assert_eq!(s.try_send(2), Err(TrySendError::Full(2)));
+    ///
+    /// drop(r);
+    /// assert_eq!(s.try_send(3), Err(TrySendError::Closed(3)));
+    /// ```
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        self.channel.try_send(msg)
+    }
+}
+
+impl<T> Sender<T> {
+    /// Returns the channel capacity.
+    pub fn capacity(&self) -> usize {
+        self.channel.cap
+    }
+
+    /// Returns the number of messages in the channel.
+    pub fn len(&self) -> usize {
+        self.channel.len()
+    }
+
+    /// Returns `true` if the channel is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the channel is full.
+    pub fn is_full(&self) -> bool {
+        self.channel.is_full()
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        // Decrement the sender count and close the channel if it drops down to zero.
+        if self.channel.sender_count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.channel.close();
+        }
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        let count = self.channel.sender_count.fetch_add(1, Ordering::Relaxed);
+
+        // Make sure the count never overflows, even if lots of sender clones are leaked.
+        if count > isize::MAX as usize {
+            process::abort();
+        }
+
+        Sender {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Sender { .. }")
+    }
+}
+
+/// The receiving side of a priority channel.
+///
+/// This type receives messages by calling `recv`. But it also implements the [`Stream`] trait,
+/// which means it can act as an asynchronous iterator. This struct is created by the [`channel`]
+/// function. See its documentation for more.
+///
+/// [`channel`]: fn.channel.html
+/// [`Stream`]: ../../stream/trait.Stream.html
+pub struct Receiver<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+
+    /// The key for this receiver in the `channel.stream_wakers` set.
+    opt_key: Option<usize>,
+}
+
+impl<T: Ord> Receiver<T> {
+    /// Receives the greatest message from the channel.
+    ///
+    /// If the channel is empty and still has senders, this method will wait until a message is
+    /// sent into the channel or until all senders get dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::priority;
+    /// use async_std::task;
+    ///
+    /// let (s, r) = priority::channel(1);
+    ///
+    /// task::spawn(async move {
+    ///     s.send(1).await.unwrap();
+    /// });
+    ///
+    /// assert_eq!(r.recv().await, Some(1));
+    /// assert_eq!(r.recv().await, None);
+    /// #
+    /// # })
+    /// ```
+    pub async fn recv(&self) -> Option<T> {
+        struct RecvFuture<'a, T> {
+            channel: &'a Channel<T>,
+            opt_key: Option<usize>,
+        }
+
+        impl<T: Ord> Future for RecvFuture<'_, T> {
+            type Output = Option<T>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                poll_recv(
+                    this.channel,
+                    &this.channel.recv_wakers,
+                    &mut this.opt_key,
+                    cx,
+                )
+            }
+        }
+
+        impl<T> Drop for RecvFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                if let Some(key) = self.opt_key {
+                    self.channel.recv_wakers.cancel(key);
+                }
+            }
+        }
+
+        RecvFuture {
+            channel: &self.channel,
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Attempts to receive the greatest message from the channel without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::priority::{self, TryRecvError};
+    ///
+    /// let (s, r) = priority::channel(2);
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// s.send(1).await.unwrap();
+    /// s.send(2).await.unwrap();
+    /// drop(s);
+    ///
+    /// assert_eq!(r.try_recv(), Ok(2));
+    /// assert_eq!(r.try_recv(), Ok(1));
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Closed));
+    /// #
+    /// # })
+    /// ```
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.channel.try_recv()
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Returns the channel capacity.
+    pub fn capacity(&self) -> usize {
+        self.channel.cap
+    }
+
+    /// Returns the number of messages in the channel.
+    pub fn len(&self) -> usize {
+        self.channel.len()
+    }
+
+    /// Returns `true` if the channel is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns `true` if the channel is full.
+    pub fn is_full(&self) -> bool {
+        self.channel.is_full()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        // If the current task is still in the stream set, that means it is being cancelled now.
+        if let Some(key) = self.opt_key {
+            self.channel.stream_wakers.cancel(key);
+        }
+
+        // Decrement the receiver count and close the channel if it drops down to zero.
+        if self.channel.receiver_count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.channel.close();
+        }
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        let count = self.channel.receiver_count.fetch_add(1, Ordering::Relaxed);
+
+        // Make sure the count never overflows, even if lots of receiver clones are leaked.
+        if count > isize::MAX as usize {
+            process::abort();
+        }
+
+        Receiver {
+            channel: self.channel.clone(),
+            opt_key: None,
+        }
+    }
+}
+
+impl<T: Ord> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        poll_recv(
+            &this.channel,
+            &this.channel.stream_wakers,
+            &mut this.opt_key,
+            cx,
+        )
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Receiver { .. }")
+    }
+}
+
+/// Polls a receive operation on a channel.
+///
+/// If the receive operation is blocked, the current task will be inserted into `wakers` and its
+/// associated key will then be stored in `opt_key`.
+fn poll_recv<T: Ord>(
+    channel: &Channel<T>,
+    wakers: &WakerSet,
+    opt_key: &mut Option<usize>,
+    cx: &mut Context<'_>,
+) -> Poll<Option<T>> {
+    loop {
+        // If the current task is in the set, remove it.
+        if let Some(key) = opt_key.take() {
+            wakers.remove(key);
+        }
+
+        // Try receiving a message.
+        match channel.try_recv() {
+            Ok(msg) => return Poll::Ready(Some(msg)),
+            Err(TryRecvError::Closed) => return Poll::Ready(None),
+            Err(TryRecvError::Empty) => {
+                // Insert this receive operation.
+                *opt_key = Some(wakers.insert(cx));
+
+                // If the channel is still empty and not closed, return.
+                if channel.len() == 0 && !channel.is_closed() {
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+/// A message in the channel, ordered by the message first and by sequence number second.
+struct Entry<T> {
+    msg: T,
+    seq: u64,
+}
+
+impl<T: Ord> Ord for Entry<T> {
+    fn cmp(&self, other: &Entry<T>) -> CmpOrdering {
+        // Among equal messages, the one that was sent first is the greatest.
+        self.msg
+            .cmp(&other.msg)
+            .then_with(|| other.seq.cmp(&self.seq))
+    }
+}
+
+impl<T: Ord> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Entry<T>) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> PartialEq for Entry<T> {
+    fn eq(&self, other: &Entry<T>) -> bool {
+        self.cmp(other) == CmpOrdering::Equal
+    }
+}
+
+impl<T: Ord> Eq for Entry<T> {}
+
+/// The mutable state of a priority channel.
+struct State<T> {
+    /// The messages in the channel.
+    heap: BinaryHeap<Entry<T>>,
+
+    /// The sequence number of the next sent message.
+    next_seq: u64,
+
+    /// Set to `true` once all senders or all receivers have been dropped.
+    closed: bool,
+}
+
+/// Bounded priority channel based on a binary heap.
+struct Channel<T> {
+    /// The mutable state of the channel.
+    state: Mutex<State<T>>,
+
+    /// The channel capacity.
+    cap: usize,
+
+    /// Send operations waiting while the channel is full.
+    send_wakers: WakerSet,
+
+    /// Receive operations waiting while the channel is empty and not closed.
+    recv_wakers: WakerSet,
+
+    /// Streams waiting while the channel is empty and not closed.
+    stream_wakers: WakerSet,
+
+    /// The number of currently active `Sender`s.
+    sender_count: AtomicUsize,
+
+    /// The number of currently active `Receivers`s.
+    receiver_count: AtomicUsize,
+}
+
+unsafe impl<T: Send> Send for Channel<T> {}
+unsafe impl<T: Send> Sync for Channel<T> {}
+
+impl<T> Channel<T> {
+    /// Creates a priority channel of capacity `cap`.
+    fn with_capacity(cap: usize) -> Self {
+        assert!(cap > 0, "capacity must be positive");
+
+        Channel {
+            state: Mutex::new(State {
+                heap: BinaryHeap::new(),
+                next_seq: 0,
+                closed: false,
+            }),
+            cap,
+            send_wakers: WakerSet::new(),
+            recv_wakers: WakerSet::new(),
+            stream_wakers: WakerSet::new(),
+            sender_count: AtomicUsize::new(1),
+            receiver_count: AtomicUsize::new(1),
+        }
+    }
+
+    /// Attempts to send a message.
+    fn try_send(&self, msg: T) -> Result<(), TrySendError<T>>
+    where
+        T: Ord,
+    {
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.closed {
+                return Err(TrySendError::Closed(msg));
+            }
+            if state.heap.len() == self.cap {
+                return Err(TrySendError::Full(msg));
+            }
+
+            let seq = state.next_seq;
+            state.next_seq += 1;
+            state.heap.push(Entry { msg, seq });
+        }
+
+        // Wake a blocked receive operation.
+        self.recv_wakers.notify_one();
+
+        // Wake all blocked streams.
+        self.stream_wakers.notify_all();
+
+        Ok(())
+    }
+
+    /// Attempts to receive the greatest message.
+    fn try_recv(&self) -> Result<T, TryRecvError>
+    where
+        T: Ord,
+    {
+        let entry = {
+            let mut state = self.state.lock().unwrap();
+            match state.heap.pop() {
+                Some(entry) => entry,
+                None if state.closed => return Err(TryRecvError::Closed),
+                None => return Err(TryRecvError::Empty),
+            }
+        };
+
+        // Wake a blocked send operation.
+        self.send_wakers.notify_one();
+
+        Ok(entry.msg)
+    }
+
+    /// Returns the current number of messages inside the channel.
+    fn len(&self) -> usize {
+        self.state.lock().unwrap().heap.len()
+    }
+
+    /// Returns `true` if the channel is full.
+    fn is_full(&self) -> bool {
+        self.len() == self.cap
+    }
+
+    /// Returns `true` if the channel is closed.
+    fn is_closed(&self) -> bool {
+        self.state.lock().unwrap().closed
+    }
+
+    /// Closes the channel and wakes up all blocked operations.
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+
+        // Notify everyone blocked on this channel.
+        self.send_wakers.notify_all();
+        self.recv_wakers.notify_all();
+        self.stream_wakers.notify_all();
+    }
+}
+
+/// An error returned from the [`Sender::send`] method.
+///
+/// The message could not be sent because all receivers have been dropped.
+///
+/// [`Sender::send`]: struct.Sender.html#method.send
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct SendError<T>(pub T);
+
+impl<T> Error for SendError<T> {}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "sending on a closed channel".fmt(f)
+    }
+}
+
+/// An error returned from the [`Sender::try_send`] method.
+///
+/// [`Sender::try_send`]: struct.Sender.html#method.try_send
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum TrySendError<T> {
+    /// The channel is full.
+    Full(T),
+
+    /// All receivers have been dropped.
+    Closed(T),
+}
+
+impl<T> Error for TrySendError<T> {}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.pad("Full(..)"),
+            TrySendError::Closed(_) => f.pad("Closed(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => "sending on a full channel".fmt(f),
+            TrySendError::Closed(_) => "sending on a closed channel".fmt(f),
+        }
+    }
+}
+
+/// An error returned from the [`Receiver::try_recv`] method.
+///
+/// [`Receiver::try_recv`]: struct.Receiver.html#method.try_recv
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TryRecvError {
+    /// The channel is empty but still has senders.
+    Empty,
+
+    /// The channel is empty and all senders have been dropped.
+    Closed,
+}
+
+impl Error for TryRecvError {}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => "receiving on an empty channel".fmt(f),
+            TryRecvError::Closed => "receiving on an empty and closed channel".fmt(f),
+        }
+    }
+}
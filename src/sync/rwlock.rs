@@ -6,7 +6,13 @@ use std::pin::Pin;
 use std::process;
 use std::future::Future;
 use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(feature = "unstable")]
+use std::mem;
+#[cfg(feature = "unstable")]
+use std::time::Duration;
 
+#[cfg(feature = "unstable")]
+use crate::future::timeout;
 use crate::sync::WakerSet;
 use crate::task::{Context, Poll};
 
@@ -14,8 +20,11 @@ use crate::task::{Context, Poll};
 #[allow(clippy::identity_op)]
 const WRITE_LOCK: usize = 1 << 0;
 
+/// Set if an upgradable read lock is held.
+const UPGRADABLE: usize = 1 << 1;
+
 /// The value of a single blocked read contributing to the read count.
-const ONE_READ: usize = 1 << 1;
+const ONE_READ: usize = 1 << 2;
 
 /// The bits in which the read count is stored.
 const READ_COUNT_MASK: usize = !(ONE_READ - 1);
@@ -53,6 +62,7 @@ pub struct RwLock<T> {
     state: AtomicUsize,
     read_wakers: WakerSet,
     write_wakers: WakerSet,
+    upgrade_wakers: WakerSet,
     value: UnsafeCell<T>,
 }
 
@@ -74,6 +84,7 @@ impl<T> RwLock<T> {
             state: AtomicUsize::new(0),
             read_wakers: WakerSet::new(),
             write_wakers: WakerSet::new(),
+            upgrade_wakers: WakerSet::new(),
             value: UnsafeCell::new(t),
         }
     }
@@ -203,6 +214,174 @@ impl<T> RwLock<T> {
         }
     }
 
+    /// Attempts to acquire a read lock, waiting at most `dur`.
+    ///
+    /// If a read lock could not be acquired in time, then [`None`] is returned.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let w = lock.write().await;
+    /// assert!(lock.try_read_for(Duration::from_millis(10)).await.is_none());
+    ///
+    /// drop(w);
+    /// assert!(lock.try_read_for(Duration::from_millis(10)).await.is_some());
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn try_read_for(&self, dur: Duration) -> Option<RwLockReadGuard<'_, T>> {
+        timeout(dur, self.read()).await.ok()
+    }
+
+    /// Acquires an upgradable read lock.
+    ///
+    /// An upgradable read lock can be held together with any number of ordinary read locks, but
+    /// not with another upgradable read lock or a write lock. It can later be turned into a write
+    /// lock with [`RwLockUpgradableReadGuard::upgrade`] without releasing the lock in between.
+    ///
+    /// Returns a guard that releases the lock when dropped.
+    ///
+    /// [`RwLockUpgradableReadGuard::upgrade`]: struct.RwLockUpgradableReadGuard.html#method.upgrade
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{RwLock, RwLockUpgradableReadGuard};
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let n = lock.upgradable_read().await;
+    /// assert_eq!(*n, 1);
+    /// assert!(lock.try_read().is_some());
+    /// assert!(lock.try_upgradable_read().is_none());
+    ///
+    /// let mut n = RwLockUpgradableReadGuard::upgrade(n).await;
+    /// *n = 2;
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn upgradable_read(&self) -> RwLockUpgradableReadGuard<'_, T> {
+        pub struct UpgradableReadFuture<'a, T> {
+            lock: &'a RwLock<T>,
+            opt_key: Option<usize>,
+        }
+
+        impl<'a, T> Future for UpgradableReadFuture<'a, T> {
+            type Output = RwLockUpgradableReadGuard<'a, T>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                loop {
+                    // If the current task is in the set, remove it.
+                    if let Some(key) = self.opt_key.take() {
+                        self.lock.read_wakers.remove(key);
+                    }
+
+                    // Try acquiring an upgradable read lock.
+                    match self.lock.try_upgradable_read() {
+                        Some(guard) => return Poll::Ready(guard),
+                        None => {
+                            // Insert this lock operation.
+                            self.opt_key = Some(self.lock.read_wakers.insert(cx));
+
+                            // If the lock is still acquired for writing or upgradable reading,
+                            // return.
+                            if self.lock.state.load(Ordering::SeqCst) & (WRITE_LOCK | UPGRADABLE)
+                                != 0
+                            {
+                                return Poll::Pending;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        impl<T> Drop for UpgradableReadFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                if let Some(key) = self.opt_key {
+                    self.lock.read_wakers.cancel(key);
+
+                    // If there are no active readers, notify a blocked writer if none were
+                    // notified already.
+                    if self.lock.state.load(Ordering::SeqCst) & READ_COUNT_MASK == 0 {
+                        self.lock.write_wakers.notify_any();
+                    }
+                }
+            }
+        }
+
+        UpgradableReadFuture {
+            lock: self,
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Attempts to acquire an upgradable read lock.
+    ///
+    /// If an upgradable read lock could not be acquired at this time, then [`None`] is returned.
+    /// Otherwise, a guard is returned that releases the lock when dropped.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let n = lock.try_upgradable_read().unwrap();
+    /// assert!(lock.try_upgradable_read().is_none());
+    /// assert!(lock.try_write().is_none());
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn try_upgradable_read(&self) -> Option<RwLockUpgradableReadGuard<'_, T>> {
+        let mut state = self.state.load(Ordering::SeqCst);
+
+        loop {
+            // If a write lock or another upgradable read lock is currently held, then an
+            // upgradable read lock cannot be acquired.
+            if state & (WRITE_LOCK | UPGRADABLE) != 0 {
+                return None;
+            }
+
+            // Make sure the number of readers doesn't overflow.
+            if state > isize::MAX as usize {
+                process::abort();
+            }
+
+            // Increment the number of active reads and mark the lock as upgradable.
+            match self.state.compare_exchange_weak(
+                state,
+                (state + ONE_READ) | UPGRADABLE,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return Some(RwLockUpgradableReadGuard(self)),
+                Err(s) => state = s,
+            }
+        }
+    }
+
     /// Acquires a write lock.
     ///
     /// Returns a guard that releases the lock when dropped.
@@ -306,6 +485,37 @@ impl<T> RwLock<T> {
         }
     }
 
+    /// Attempts to acquire a write lock, waiting at most `dur`.
+    ///
+    /// If a write lock could not be acquired in time, then [`None`] is returned.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let r = lock.read().await;
+    /// assert!(lock.try_write_for(Duration::from_millis(10)).await.is_none());
+    ///
+    /// drop(r);
+    /// assert!(lock.try_write_for(Duration::from_millis(10)).await.is_some());
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn try_write_for(&self, dur: Duration) -> Option<RwLockWriteGuard<'_, T>> {
+        timeout(dur, self.write()).await.ok()
+    }
+
     /// Consumes the lock, returning the underlying data.
     ///
     /// # Examples
@@ -377,17 +587,69 @@ pub struct RwLockReadGuard<'a, T>(&'a RwLock<T>);
 unsafe impl<T: Send> Send for RwLockReadGuard<'_, T> {}
 unsafe impl<T: Sync> Sync for RwLockReadGuard<'_, T> {}
 
-impl<T> Drop for RwLockReadGuard<'_, T> {
-    fn drop(&mut self) {
-        let state = self.0.state.fetch_sub(ONE_READ, Ordering::SeqCst);
+/// Releases a read lock given the lock state and the set of blocked `write()` operations.
+fn unlock_read(state: &AtomicUsize, write_wakers: &WakerSet, upgrade_wakers: &WakerSet) {
+    let state = state.fetch_sub(ONE_READ, Ordering::SeqCst);
 
+    if state & READ_COUNT_MASK == ONE_READ {
         // If this was the last reader, notify a blocked writer if none were notified already.
-        if state & READ_COUNT_MASK == ONE_READ {
-            self.0.write_wakers.notify_any();
+        write_wakers.notify_any();
+    } else if state & UPGRADABLE != 0 && state & READ_COUNT_MASK == 2 * ONE_READ {
+        // Only the upgradable reader is left, so notify it if it's waiting to upgrade.
+        upgrade_wakers.notify_all();
+    }
+}
+
+impl<'a, T> RwLockReadGuard<'a, T> {
+    /// Makes a guard for a component of the locked data.
+    ///
+    /// The read lock is held until the returned guard is dropped.
+    ///
+    /// This is an associated function that needs to be used as `RwLockReadGuard::map(...)`, so
+    /// that it doesn't conflict with methods on the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{RwLock, RwLockReadGuard};
+    ///
+    /// let lock = RwLock::new((1, String::from("a")));
+    ///
+    /// let s = RwLockReadGuard::map(lock.read().await, |pair| pair.1.as_str());
+    /// assert_eq!(&*s, "a");
+    /// assert!(lock.try_write().is_none());
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedRwLockReadGuard<'a, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let lock = this.0;
+        let value = f(unsafe { &*lock.value.get() });
+
+        // The read lock is now released by the mapped guard.
+        mem::forget(this);
+
+        MappedRwLockReadGuard {
+            state: &lock.state,
+            write_wakers: &lock.write_wakers,
+            upgrade_wakers: &lock.upgrade_wakers,
+            value,
         }
     }
 }
 
+impl<T> Drop for RwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        unlock_read(&self.0.state, &self.0.write_wakers, &self.0.upgrade_wakers);
+    }
+}
+
 impl<T: fmt::Debug> fmt::Debug for RwLockReadGuard<'_, T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
@@ -408,6 +670,263 @@ impl<T> Deref for RwLockReadGuard<'_, T> {
     }
 }
 
+/// A guard for a component of the data locked by a [`RwLockReadGuard`].
+///
+/// This guard is created by [`RwLockReadGuard::map`]. It releases the read lock when dropped.
+///
+/// [`RwLockReadGuard`]: struct.RwLockReadGuard.html
+/// [`RwLockReadGuard::map`]: struct.RwLockReadGuard.html#method.map
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct MappedRwLockReadGuard<'a, T: ?Sized> {
+    state: &'a AtomicUsize,
+    write_wakers: &'a WakerSet,
+    upgrade_wakers: &'a WakerSet,
+    value: &'a T,
+}
+
+#[cfg(feature = "unstable")]
+unsafe impl<T: ?Sized + Sync> Send for MappedRwLockReadGuard<'_, T> {}
+#[cfg(feature = "unstable")]
+unsafe impl<T: ?Sized + Sync> Sync for MappedRwLockReadGuard<'_, T> {}
+
+#[cfg(feature = "unstable")]
+impl<'a, T: ?Sized> MappedRwLockReadGuard<'a, T> {
+    /// Makes a guard for a component of the data locked by this guard.
+    ///
+    /// This is an associated function that needs to be used as `MappedRwLockReadGuard::map(...)`,
+    /// so that it doesn't conflict with methods on the locked data.
+    pub fn map<U: ?Sized, F>(this: Self, f: F) -> MappedRwLockReadGuard<'a, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        let state = this.state;
+        let write_wakers = this.write_wakers;
+        let upgrade_wakers = this.upgrade_wakers;
+        let value = f(this.value);
+
+        // The read lock is now released by the new guard.
+        mem::forget(this);
+
+        MappedRwLockReadGuard {
+            state,
+            write_wakers,
+            upgrade_wakers,
+            value,
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized> Drop for MappedRwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        unlock_read(self.state, self.write_wakers, self.upgrade_wakers);
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized + fmt::Debug> fmt::Debug for MappedRwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized + fmt::Display> fmt::Display for MappedRwLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: ?Sized> Deref for MappedRwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+/// A guard that releases the upgradable read lock when dropped.
+///
+/// This guard is created by the [`upgradable_read`] and [`try_upgradable_read`] methods on
+/// [`RwLock`].
+///
+/// [`upgradable_read`]: struct.RwLock.html#method.upgradable_read
+/// [`try_upgradable_read`]: struct.RwLock.html#method.try_upgradable_read
+/// [`RwLock`]: struct.RwLock.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct RwLockUpgradableReadGuard<'a, T>(&'a RwLock<T>);
+
+#[cfg(feature = "unstable")]
+unsafe impl<T: Send> Send for RwLockUpgradableReadGuard<'_, T> {}
+#[cfg(feature = "unstable")]
+unsafe impl<T: Sync> Sync for RwLockUpgradableReadGuard<'_, T> {}
+
+#[cfg(feature = "unstable")]
+impl<'a, T> RwLockUpgradableReadGuard<'a, T> {
+    /// Upgrades to a write lock, waiting until all other readers have released the lock.
+    ///
+    /// The lock is not released in between, so no writer can modify the data before the upgrade
+    /// completes. If the returned future is dropped before completion, the upgradable read lock
+    /// is released.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `RwLockUpgradableReadGuard::upgrade(...)`, so that it doesn't conflict with methods on the
+    /// locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{RwLock, RwLockUpgradableReadGuard};
+    ///
+    /// let lock = RwLock::new(Vec::new());
+    ///
+    /// let cache = lock.upgradable_read().await;
+    /// if cache.is_empty() {
+    ///     let mut cache = RwLockUpgradableReadGuard::upgrade(cache).await;
+    ///     cache.push(1);
+    /// }
+    ///
+    /// assert_eq!(*lock.read().await, [1]);
+    /// #
+    /// # })
+    /// ```
+    pub async fn upgrade(this: Self) -> RwLockWriteGuard<'a, T> {
+        struct UpgradeFuture<'a, T> {
+            guard: Option<RwLockUpgradableReadGuard<'a, T>>,
+            opt_key: Option<usize>,
+        }
+
+        impl<'a, T> Future for UpgradeFuture<'a, T> {
+            type Output = RwLockWriteGuard<'a, T>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let lock = self.guard.as_ref().unwrap().0;
+
+                loop {
+                    // If the current task is in the set, remove it.
+                    if let Some(key) = self.opt_key.take() {
+                        lock.upgrade_wakers.remove(key);
+                    }
+
+                    // Try upgrading.
+                    match RwLockUpgradableReadGuard::try_upgrade(self.guard.take().unwrap()) {
+                        Ok(guard) => return Poll::Ready(guard),
+                        Err(guard) => {
+                            self.guard = Some(guard);
+
+                            // Insert this upgrade operation.
+                            self.opt_key = Some(lock.upgrade_wakers.insert(cx));
+
+                            // If there are still other readers, return.
+                            if lock.state.load(Ordering::SeqCst) & READ_COUNT_MASK != ONE_READ {
+                                return Poll::Pending;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        impl<T> Drop for UpgradeFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                if let Some(key) = self.opt_key {
+                    if let Some(guard) = &self.guard {
+                        guard.0.upgrade_wakers.cancel(key);
+                    }
+                }
+            }
+        }
+
+        UpgradeFuture {
+            guard: Some(this),
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Attempts to upgrade to a write lock without waiting.
+    ///
+    /// If other readers are holding the lock, the upgradable read guard is returned back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{RwLock, RwLockUpgradableReadGuard};
+    ///
+    /// let lock = RwLock::new(1);
+    /// let n = lock.try_upgradable_read().unwrap();
+    ///
+    /// let r = lock.try_read().unwrap();
+    /// let n = RwLockUpgradableReadGuard::try_upgrade(n).unwrap_err();
+    ///
+    /// drop(r);
+    /// assert!(RwLockUpgradableReadGuard::try_upgrade(n).is_ok());
+    /// ```
+    pub fn try_upgrade(this: Self) -> Result<RwLockWriteGuard<'a, T>, Self> {
+        let lock = this.0;
+        match lock.state.compare_exchange(
+            UPGRADABLE | ONE_READ,
+            WRITE_LOCK,
+            Ordering::SeqCst,
+            Ordering::SeqCst,
+        ) {
+            Ok(_) => {
+                // The lock is now released by the write guard.
+                mem::forget(this);
+                Ok(RwLockWriteGuard(lock))
+            }
+            Err(_) => Err(this),
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T> Drop for RwLockUpgradableReadGuard<'_, T> {
+    fn drop(&mut self) {
+        let state = self
+            .0
+            .state
+            .fetch_sub(UPGRADABLE | ONE_READ, Ordering::SeqCst);
+
+        if state & READ_COUNT_MASK == ONE_READ {
+            // If this was the last reader, notify a blocked writer if none were notified already.
+            self.0.write_wakers.notify_any();
+        }
+
+        // Notify blocked upgradable readers.
+        self.0.read_wakers.notify_all();
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: fmt::Debug> fmt::Debug for RwLockUpgradableReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: fmt::Display> fmt::Display for RwLockUpgradableReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T> Deref for RwLockUpgradableReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.0.value.get() }
+    }
+}
+
 /// A guard that releases the write lock when dropped.
 pub struct RwLockWriteGuard<'a, T>(&'a RwLock<T>);
 
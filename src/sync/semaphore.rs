@@ -0,0 +1,418 @@
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::sync::WakerSet;
+use crate::task::{Context, Poll};
+
+/// A counting semaphore limiting access to a shared resource.
+///
+/// A semaphore holds a number of permits. Acquiring permits waits until enough of them are
+/// available, and returns a guard that gives the permits back to the semaphore when dropped.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::{Arc, Semaphore};
+/// use async_std::task;
+///
+/// let sem = Arc::new(Semaphore::new(2));
+/// let mut tasks = vec![];
+///
+/// for _ in 0..10 {
+///     let sem = sem.clone();
+///     tasks.push(task::spawn(async move {
+///         // At most two tasks are running this section at the same time.
+///         let _permit = sem.acquire().await;
+///     }));
+/// }
+///
+/// for t in tasks {
+///     t.await;
+/// }
+/// assert_eq!(sem.available_permits(), 2);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Semaphore {
+    permits: AtomicUsize,
+    wakers: WakerSet,
+}
+
+unsafe impl Send for Semaphore {}
+unsafe impl Sync for Semaphore {}
+
+impl Semaphore {
+    /// Creates a new semaphore with the given number of permits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let sem = Semaphore::new(3);
+    /// ```
+    pub fn new(permits: usize) -> Semaphore {
+        Semaphore {
+            permits: AtomicUsize::new(permits),
+            wakers: WakerSet::new(),
+        }
+    }
+
+    /// Returns the number of permits currently available.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let sem = Semaphore::new(3);
+    /// let _permit = sem.try_acquire().unwrap();
+    /// assert_eq!(sem.available_permits(), 2);
+    /// ```
+    pub fn available_permits(&self) -> usize {
+        self.permits.load(Ordering::SeqCst)
+    }
+
+    /// Adds `n` new permits to the semaphore.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let sem = Semaphore::new(0);
+    /// sem.add_permits(2);
+    /// assert_eq!(sem.available_permits(), 2);
+    /// ```
+    pub fn add_permits(&self, n: usize) {
+        if n > 0 {
+            // Use `SeqCst` ordering to synchronize with `WakerSet::insert()`.
+            self.permits.fetch_add(n, Ordering::SeqCst);
+
+            // Blocked operations may be waiting for different numbers of permits, so notify all
+            // of them and let them race.
+            self.wakers.notify_all();
+        }
+    }
+
+    /// Acquires a single permit.
+    ///
+    /// Returns a guard that gives the permit back when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let sem = Semaphore::new(1);
+    /// let permit = sem.acquire().await;
+    /// assert!(sem.try_acquire().is_none());
+    ///
+    /// drop(permit);
+    /// assert!(sem.try_acquire().is_some());
+    /// #
+    /// # })
+    /// ```
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.acquire_many(1).await
+    }
+
+    /// Acquires `n` permits at once.
+    ///
+    /// The permits are acquired all together: no permits are held while waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let sem = Semaphore::new(5);
+    /// let permit = sem.acquire_many(3).await;
+    /// assert_eq!(permit.permits(), 3);
+    /// assert_eq!(sem.available_permits(), 2);
+    /// #
+    /// # })
+    /// ```
+    pub async fn acquire_many(&self, n: usize) -> SemaphorePermit<'_> {
+        AcquireFuture {
+            semaphore: self,
+            n,
+            opt_key: None,
+        }
+        .await;
+
+        SemaphorePermit {
+            semaphore: self,
+            permits: n,
+        }
+    }
+
+    /// Attempts to acquire a single permit without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let sem = Semaphore::new(1);
+    /// let permit = sem.try_acquire().unwrap();
+    /// assert!(sem.try_acquire().is_none());
+    /// ```
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.try_acquire_many(1)
+    }
+
+    /// Attempts to acquire `n` permits without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let sem = Semaphore::new(2);
+    /// assert!(sem.try_acquire_many(3).is_none());
+    /// assert!(sem.try_acquire_many(2).is_some());
+    /// ```
+    pub fn try_acquire_many(&self, n: usize) -> Option<SemaphorePermit<'_>> {
+        if self.try_take(n) {
+            Some(SemaphorePermit {
+                semaphore: self,
+                permits: n,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Acquires a single permit that can outlive a borrow of the semaphore.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{Arc, Semaphore};
+    /// use async_std::task;
+    ///
+    /// let sem = Arc::new(Semaphore::new(1));
+    /// let permit = sem.clone().acquire_owned().await;
+    ///
+    /// task::spawn(async move {
+    ///     // The permit is given back when the task completes.
+    ///     drop(permit);
+    /// })
+    /// .await;
+    ///
+    /// assert_eq!(sem.available_permits(), 1);
+    /// #
+    /// # })
+    /// ```
+    pub async fn acquire_owned(self: Arc<Self>) -> OwnedSemaphorePermit {
+        self.acquire_many_owned(1).await
+    }
+
+    /// Acquires `n` permits at once that can outlive a borrow of the semaphore.
+    pub async fn acquire_many_owned(self: Arc<Self>, n: usize) -> OwnedSemaphorePermit {
+        AcquireFuture {
+            semaphore: &self,
+            n,
+            opt_key: None,
+        }
+        .await;
+
+        OwnedSemaphorePermit {
+            semaphore: self,
+            permits: n,
+        }
+    }
+
+    /// Attempts to acquire a single permit that can outlive a borrow of the semaphore, without
+    /// waiting.
+    pub fn try_acquire_owned(self: Arc<Self>) -> Option<OwnedSemaphorePermit> {
+        self.try_acquire_many_owned(1)
+    }
+
+    /// Attempts to acquire `n` permits that can outlive a borrow of the semaphore, without
+    /// waiting.
+    pub fn try_acquire_many_owned(self: Arc<Self>, n: usize) -> Option<OwnedSemaphorePermit> {
+        if self.try_take(n) {
+            Some(OwnedSemaphorePermit {
+                semaphore: self,
+                permits: n,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Attempts to take `n` permits.
+    fn try_take(&self, n: usize) -> bool {
+        let mut current = self.permits.load(Ordering::SeqCst);
+        loop {
+            if current < n {
+                return false;
+            }
+
+            match self.permits.compare_exchange_weak(
+                current,
+                current - n,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return true,
+                Err(c) => current = c,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("permits", &self.available_permits())
+            .finish()
+    }
+}
+
+/// A future that takes `n` permits from a semaphore.
+struct AcquireFuture<'a> {
+    semaphore: &'a Semaphore,
+    n: usize,
+    opt_key: Option<usize>,
+}
+
+impl Future for AcquireFuture<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        loop {
+            // If the current task is in the set, remove it.
+            if let Some(key) = self.opt_key.take() {
+                self.semaphore.wakers.remove(key);
+            }
+
+            // Try taking the permits.
+            if self.semaphore.try_take(self.n) {
+                return Poll::Ready(());
+            }
+
+            // Insert this acquire operation.
+            self.opt_key = Some(self.semaphore.wakers.insert(cx));
+
+            // If there are still not enough permits, return.
+            if self.semaphore.available_permits() < self.n {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+impl Drop for AcquireFuture<'_> {
+    fn drop(&mut self) {
+        // If the current task is still in the set, that means it is being cancelled now.
+        if let Some(key) = self.opt_key {
+            self.semaphore.wakers.cancel(key);
+        }
+    }
+}
+
+/// A guard holding permits acquired from a [`Semaphore`].
+///
+/// The permits are given back to the semaphore when the guard is dropped.
+///
+/// [`Semaphore`]: struct.Semaphore.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[must_use = "permits are given back to the semaphore immediately if unused"]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+}
+
+impl SemaphorePermit<'_> {
+    /// Returns the number of permits held by this guard.
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Forgets the permits without giving them back to the semaphore.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let sem = Semaphore::new(2);
+    /// sem.try_acquire().unwrap().forget();
+    /// assert_eq!(sem.available_permits(), 1);
+    /// ```
+    pub fn forget(self) {
+        mem::forget(self);
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.semaphore.add_permits(self.permits);
+    }
+}
+
+impl fmt::Debug for SemaphorePermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphorePermit")
+            .field("permits", &self.permits)
+            .finish()
+    }
+}
+
+/// A guard holding permits acquired from a [`Semaphore`] behind an [`Arc`].
+///
+/// The permits are given back to the semaphore when the guard is dropped.
+///
+/// [`Semaphore`]: struct.Semaphore.html
+/// [`Arc`]: struct.Arc.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[must_use = "permits are given back to the semaphore immediately if unused"]
+pub struct OwnedSemaphorePermit {
+    semaphore: Arc<Semaphore>,
+    permits: usize,
+}
+
+impl OwnedSemaphorePermit {
+    /// Returns the number of permits held by this guard.
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
+
+    /// Forgets the permits without giving them back to the semaphore.
+    pub fn forget(mut self) {
+        self.permits = 0;
+    }
+}
+
+impl Drop for OwnedSemaphorePermit {
+    fn drop(&mut self) {
+        self.semaphore.add_permits(self.permits);
+    }
+}
+
+impl fmt::Debug for OwnedSemaphorePermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedSemaphorePermit")
+            .field("permits", &self.permits)
+            .finish()
+    }
+}
@@ -0,0 +1,295 @@
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crossbeam_utils::CachePadded;
+
+use crate::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A reader-writer lock optimized for read-mostly data.
+///
+/// The lock is split into shards, one per CPU core. A read operation only locks the shard
+/// associated with the current thread, so readers on different threads rarely contend with each
+/// other. A write operation locks all shards, which makes writes considerably more expensive than
+/// with [`RwLock`].
+///
+/// [`RwLock`]: struct.RwLock.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::ShardedLock;
+///
+/// let lock = ShardedLock::new(5);
+///
+/// // Any number of read locks can be held at once.
+/// {
+///     let r1 = lock.read().await;
+///     let r2 = lock.read().await;
+///     assert_eq!(*r1 + *r2, 10);
+/// }
+///
+/// // Only one write lock can be held at a time.
+/// {
+///     let mut w = lock.write().await;
+///     *w += 1;
+///     assert_eq!(*w, 6);
+/// }
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct ShardedLock<T: ?Sized> {
+    shards: Box<[CachePadded<RwLock<()>>]>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for ShardedLock<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for ShardedLock<T> {}
+
+impl<T> ShardedLock<T> {
+    /// Creates a new sharded lock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::ShardedLock;
+    ///
+    /// let lock = ShardedLock::new(0);
+    /// ```
+    pub fn new(t: T) -> ShardedLock<T> {
+        let shards = (0..num_cpus::get().max(1))
+            .map(|_| CachePadded::new(RwLock::new(())))
+            .collect();
+
+        ShardedLock {
+            shards,
+            value: UnsafeCell::new(t),
+        }
+    }
+
+    /// Unwraps the lock and returns the inner value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::ShardedLock;
+    ///
+    /// let lock = ShardedLock::new(10);
+    /// assert_eq!(lock.into_inner(), 10);
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ?Sized> ShardedLock<T> {
+    /// Acquires a read lock.
+    ///
+    /// Returns a guard that releases the lock when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::ShardedLock;
+    ///
+    /// let lock = ShardedLock::new(1);
+    ///
+    /// let n = lock.read().await;
+    /// assert_eq!(*n, 1);
+    /// #
+    /// # })
+    /// ```
+    pub async fn read(&self) -> ShardedLockReadGuard<'_, T> {
+        let guard = self.shards[self.current_shard()].read().await;
+        ShardedLockReadGuard {
+            lock: self,
+            _guard: guard,
+        }
+    }
+
+    /// Attempts to acquire a read lock.
+    ///
+    /// If a read lock could not be acquired at this time, then `None` is returned. Otherwise, a
+    /// guard is returned that releases the lock when dropped.
+    pub fn try_read(&self) -> Option<ShardedLockReadGuard<'_, T>> {
+        let guard = self.shards[self.current_shard()].try_read()?;
+        Some(ShardedLockReadGuard {
+            lock: self,
+            _guard: guard,
+        })
+    }
+
+    /// Acquires a write lock.
+    ///
+    /// Returns a guard that releases the lock when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::ShardedLock;
+    ///
+    /// let lock = ShardedLock::new(1);
+    ///
+    /// let mut n = lock.write().await;
+    /// *n = 2;
+    ///
+    /// assert!(lock.try_read().is_none());
+    /// #
+    /// # })
+    /// ```
+    pub async fn write(&self) -> ShardedLockWriteGuard<'_, T> {
+        // Lock the shards in order so that concurrent writers can't deadlock.
+        let mut guards = Vec::with_capacity(self.shards.len());
+        for shard in self.shards.iter() {
+            guards.push(shard.write().await);
+        }
+
+        ShardedLockWriteGuard {
+            lock: self,
+            _guards: guards,
+        }
+    }
+
+    /// Attempts to acquire a write lock.
+    ///
+    /// If a write lock could not be acquired at this time, then `None` is returned. Otherwise, a
+    /// guard is returned that releases the lock when dropped.
+    pub fn try_write(&self) -> Option<ShardedLockWriteGuard<'_, T>> {
+        let mut guards = Vec::with_capacity(self.shards.len());
+        for shard in self.shards.iter() {
+            guards.push(shard.try_write()?);
+        }
+
+        Some(ShardedLockWriteGuard {
+            lock: self,
+            _guards: guards,
+        })
+    }
+
+    /// Returns a mutable reference to the inner value.
+    ///
+    /// The mutable borrow statically guarantees there are no active locks.
+    pub fn get_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.value.get() }
+    }
+
+    /// Returns the index of the shard used by the current thread.
+    fn current_shard(&self) -> usize {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+
+        thread_local! {
+            // Threads are assigned shards in round-robin order.
+            static INDEX: usize = NEXT.fetch_add(1, Ordering::Relaxed);
+        }
+
+        INDEX
+            .try_with(|i| *i)
+            .unwrap_or(0)
+            % self.shards.len()
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for ShardedLock<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Locked;
+        impl fmt::Debug for Locked {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("<locked>")
+            }
+        }
+
+        match self.try_read() {
+            None => f.debug_struct("ShardedLock").field("data", &Locked).finish(),
+            Some(guard) => f.debug_struct("ShardedLock").field("data", &&*guard).finish(),
+        }
+    }
+}
+
+impl<T> From<T> for ShardedLock<T> {
+    fn from(val: T) -> ShardedLock<T> {
+        ShardedLock::new(val)
+    }
+}
+
+impl<T: Default> Default for ShardedLock<T> {
+    fn default() -> ShardedLock<T> {
+        ShardedLock::new(Default::default())
+    }
+}
+
+/// A guard that releases the read lock when dropped.
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct ShardedLockReadGuard<'a, T: ?Sized> {
+    lock: &'a ShardedLock<T>,
+    _guard: RwLockReadGuard<'a, ()>,
+}
+
+unsafe impl<T: ?Sized + Sync> Send for ShardedLockReadGuard<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for ShardedLockReadGuard<'_, T> {}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for ShardedLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for ShardedLockReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized> Deref for ShardedLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+/// A guard that releases the write lock when dropped.
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct ShardedLockWriteGuard<'a, T: ?Sized> {
+    lock: &'a ShardedLock<T>,
+    _guards: Vec<RwLockWriteGuard<'a, ()>>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for ShardedLockWriteGuard<'_, T> {}
+unsafe impl<T: ?Sized + Sync> Sync for ShardedLockWriteGuard<'_, T> {}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for ShardedLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for ShardedLockWriteGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized> Deref for ShardedLockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for ShardedLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
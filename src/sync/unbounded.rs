@@ -0,0 +1,669 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use crate::stream::Stream;
+use crate::sync::WakerSet;
+
+/// A function measuring a message in bytes.
+type SizeFn<T> = Arc<dyn Fn(&T) -> usize + Send + Sync>;
+
+/// A function called when a channel reaches a high-water mark.
+type Callback = Arc<dyn Fn(ChannelUsage) + Send + Sync>;
+
+/// Creates an unbounded multi-producer multi-consumer channel.
+///
+/// The channel has no capacity limit, so sending never waits and the sender doesn't need to be
+/// polled. The price is that a producer outrunning its consumers grows the queue without bounds.
+/// To detect that early, create the channel with [`UnboundedOptions`], which measures the queue
+/// and reports when it crosses a high-water mark.
+///
+/// When all senders get dropped, receivers can still receive the remaining messages and then get
+/// `None`. When all receivers get dropped, sending returns an error.
+///
+/// [`UnboundedOptions`]: struct.UnboundedOptions.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::channel_unbounded;
+///
+/// let (s, r) = channel_unbounded();
+///
+/// // Sending never waits.
+/// for i in 0..1000 {
+///     s.send(i).unwrap();
+/// }
+/// drop(s);
+///
+/// let mut sum = 0;
+/// while let Some(i) = r.recv().await {
+///     sum += i;
+/// }
+/// assert_eq!(sum, 499_500);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn channel_unbounded<T>() -> (UnboundedSender<T>, UnboundedReceiver<T>) {
+    UnboundedOptions::new().channel()
+}
+
+/// Options for creating an unbounded channel with memory accounting.
+///
+/// The channel always counts its messages. With [`message_size`], it also adds up their sizes in
+/// bytes, as measured by a user-supplied function. When either count reaches its high-water mark,
+/// the [`on_high_water`] callback is called with the current [`ChannelUsage`]. The callback is
+/// called once per crossing: it is armed again only after the queue has been drained below both
+/// marks.
+///
+/// The callback runs on the sending task, after the message has been queued and outside of any
+/// lock, so it may log, update metrics or even receive from the channel.
+///
+/// [`message_size`]: #method.message_size
+/// [`on_high_water`]: #method.on_high_water
+/// [`ChannelUsage`]: struct.ChannelUsage.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+///
+/// use async_std::sync::UnboundedOptions;
+///
+/// let alarm = Arc::new(AtomicBool::new(false));
+/// let a = alarm.clone();
+///
+/// let (s, r) = UnboundedOptions::new()
+///     .message_size(|msg: &Vec<u8>| msg.len())
+///     .bytes_high_water_mark(1024)
+///     .on_high_water(move |usage| {
+///         eprintln!("queue is at {} bytes", usage.bytes);
+///         a.store(true, Ordering::SeqCst);
+///     })
+///     .channel();
+///
+/// s.send(vec![0; 1000]).unwrap();
+/// assert!(!alarm.load(Ordering::SeqCst));
+///
+/// s.send(vec![0; 100]).unwrap();
+/// assert!(alarm.load(Ordering::SeqCst));
+/// assert_eq!(r.usage().bytes, 1100);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct UnboundedOptions<T> {
+    size_fn: Option<SizeFn<T>>,
+    len_mark: Option<usize>,
+    bytes_mark: Option<usize>,
+    callback: Option<Callback>,
+}
+
+impl<T> UnboundedOptions<T> {
+    /// Creates a blank set of options.
+    ///
+    /// Without further configuration, the channel only counts its messages.
+    pub fn new() -> UnboundedOptions<T> {
+        UnboundedOptions {
+            size_fn: None,
+            len_mark: None,
+            bytes_mark: None,
+            callback: None,
+        }
+    }
+
+    /// Sets the function measuring the size of a message in bytes.
+    ///
+    /// Each message is measured once, when it is sent.
+    pub fn message_size<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&T) -> usize + Send + Sync + 'static,
+    {
+        self.size_fn = Some(Arc::new(f));
+        self
+    }
+
+    /// Sets the number of queued messages at which the high-water callback is called.
+    pub fn len_high_water_mark(&mut self, len: usize) -> &mut Self {
+        self.len_mark = Some(len);
+        self
+    }
+
+    /// Sets the number of queued bytes at which the high-water callback is called.
+    ///
+    /// This has no effect unless the size of messages is measured with [`message_size`].
+    ///
+    /// [`message_size`]: #method.message_size
+    pub fn bytes_high_water_mark(&mut self, bytes: usize) -> &mut Self {
+        self.bytes_mark = Some(bytes);
+        self
+    }
+
+    /// Sets the function called when the queue reaches a high-water mark.
+    pub fn on_high_water<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(ChannelUsage) + Send + Sync + 'static,
+    {
+        self.callback = Some(Arc::new(f));
+        self
+    }
+
+    /// Creates an unbounded channel with these options.
+    pub fn channel(&self) -> (UnboundedSender<T>, UnboundedReceiver<T>) {
+        let channel = Arc::new(Channel {
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                bytes: 0,
+                closed: false,
+                above_mark: false,
+            }),
+            size_fn: self.size_fn.clone(),
+            len_mark: self.len_mark,
+            bytes_mark: self.bytes_mark,
+            callback: self.callback.clone(),
+            recv_wakers: WakerSet::new(),
+            stream_wakers: WakerSet::new(),
+            sender_count: AtomicUsize::new(1),
+            receiver_count: AtomicUsize::new(1),
+        });
+        let s = UnboundedSender {
+            channel: channel.clone(),
+        };
+        let r = UnboundedReceiver {
+            channel,
+            opt_key: None,
+        };
+        (s, r)
+    }
+}
+
+impl<T> Default for UnboundedOptions<T> {
+    fn default() -> UnboundedOptions<T> {
+        UnboundedOptions::new()
+    }
+}
+
+impl<T> Clone for UnboundedOptions<T> {
+    fn clone(&self) -> UnboundedOptions<T> {
+        UnboundedOptions {
+            size_fn: self.size_fn.clone(),
+            len_mark: self.len_mark,
+            bytes_mark: self.bytes_mark,
+            callback: self.callback.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for UnboundedOptions<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UnboundedOptions")
+            .field("message_size", &self.size_fn.is_some())
+            .field("len_high_water_mark", &self.len_mark)
+            .field("bytes_high_water_mark", &self.bytes_mark)
+            .field("on_high_water", &self.callback.is_some())
+            .finish()
+    }
+}
+
+/// The amount of data queued in an unbounded channel.
+///
+/// This struct is returned by [`UnboundedSender::usage`] and [`UnboundedReceiver::usage`], and
+/// passed to the high-water callback set with [`UnboundedOptions::on_high_water`].
+///
+/// [`UnboundedSender::usage`]: struct.UnboundedSender.html#method.usage
+/// [`UnboundedReceiver::usage`]: struct.UnboundedReceiver.html#method.usage
+/// [`UnboundedOptions::on_high_water`]: struct.UnboundedOptions.html#method.on_high_water
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ChannelUsage {
+    /// The number of queued messages.
+    pub len: usize,
+
+    /// The total size of the queued messages in bytes, or zero if messages aren't measured.
+    pub bytes: usize,
+}
+
+/// The sending side of an unbounded channel.
+///
+/// This struct is created by the [`channel_unbounded`] function and by
+/// [`UnboundedOptions::channel`]. See their documentation for more.
+///
+/// [`channel_unbounded`]: fn.channel_unbounded.html
+/// [`UnboundedOptions::channel`]: struct.UnboundedOptions.html#method.channel
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct UnboundedSender<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> UnboundedSender<T> {
+    /// Sends a message into the channel.
+    ///
+    /// This method never waits. If all receivers have been dropped, the message is returned back
+    /// in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel_unbounded;
+    ///
+    /// let (s, r) = channel_unbounded();
+    /// assert!(s.send(1).is_ok());
+    ///
+    /// drop(r);
+    /// assert!(s.send(2).is_err());
+    /// ```
+    pub fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        self.channel.send(msg)
+    }
+
+    /// Returns the number of messages in the channel.
+    pub fn len(&self) -> usize {
+        self.channel.usage().len
+    }
+
+    /// Returns `true` if the channel is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the amount of data queued in the channel.
+    pub fn usage(&self) -> ChannelUsage {
+        self.channel.usage()
+    }
+
+    /// Returns `true` if all receivers have been dropped.
+    pub fn is_closed(&self) -> bool {
+        self.channel.state.lock().unwrap().closed
+    }
+}
+
+impl<T> Drop for UnboundedSender<T> {
+    fn drop(&mut self) {
+        // Decrement the sender count and close the channel if it drops down to zero.
+        if self.channel.sender_count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.channel.close();
+        }
+    }
+}
+
+impl<T> Clone for UnboundedSender<T> {
+    fn clone(&self) -> UnboundedSender<T> {
+        let count = self.channel.sender_count.fetch_add(1, Ordering::Relaxed);
+
+        // Make sure the count never overflows, even if lots of sender clones are leaked.
+        if count > isize::MAX as usize {
+            process::abort();
+        }
+
+        UnboundedSender {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for UnboundedSender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("UnboundedSender { .. }")
+    }
+}
+
+/// The receiving side of an unbounded channel.
+///
+/// This type receives messages by calling `recv`. But it also implements the [`Stream`] trait,
+/// which means it can act as an asynchronous iterator. This struct is created by the
+/// [`channel_unbounded`] function and by [`UnboundedOptions::channel`].
+///
+/// [`Stream`]: ../stream/trait.Stream.html
+/// [`channel_unbounded`]: fn.channel_unbounded.html
+/// [`UnboundedOptions::channel`]: struct.UnboundedOptions.html#method.channel
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct UnboundedReceiver<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+
+    /// The key for this receiver in the `channel.stream_wakers` set.
+    opt_key: Option<usize>,
+}
+
+impl<T> UnboundedReceiver<T> {
+    /// Receives a message from the channel.
+    ///
+    /// If the channel is empty and still has senders, this method will wait until a message is
+    /// sent into the channel or until all senders get dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::channel_unbounded;
+    /// use async_std::task;
+    ///
+    /// let (s, r) = channel_unbounded();
+    ///
+    /// task::spawn(async move {
+    ///     s.send(1).unwrap();
+    /// });
+    ///
+    /// assert_eq!(r.recv().await, Some(1));
+    /// assert_eq!(r.recv().await, None);
+    /// #
+    /// # })
+    /// ```
+    pub async fn recv(&self) -> Option<T> {
+        struct RecvFuture<'a, T> {
+            channel: &'a Channel<T>,
+            opt_key: Option<usize>,
+        }
+
+        impl<T> Unpin for RecvFuture<'_, T> {}
+
+        impl<T> Future for RecvFuture<'_, T> {
+            type Output = Option<T>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                poll_recv(
+                    this.channel,
+                    &this.channel.recv_wakers,
+                    &mut this.opt_key,
+                    cx,
+                )
+            }
+        }
+
+        impl<T> Drop for RecvFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                if let Some(key) = self.opt_key {
+                    self.channel.recv_wakers.cancel(key);
+                }
+            }
+        }
+
+        RecvFuture {
+            channel: &self.channel,
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Returns the number of messages in the channel.
+    pub fn len(&self) -> usize {
+        self.channel.usage().len
+    }
+
+    /// Returns `true` if the channel is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the amount of data queued in the channel.
+    pub fn usage(&self) -> ChannelUsage {
+        self.channel.usage()
+    }
+}
+
+impl<T> Drop for UnboundedReceiver<T> {
+    fn drop(&mut self) {
+        // If the current task is still in the stream set, that means it is being cancelled now.
+        if let Some(key) = self.opt_key {
+            self.channel.stream_wakers.cancel(key);
+        }
+
+        // Decrement the receiver count and close the channel if it drops down to zero.
+        if self.channel.receiver_count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.channel.close();
+        }
+    }
+}
+
+impl<T> Clone for UnboundedReceiver<T> {
+    fn clone(&self) -> UnboundedReceiver<T> {
+        let count = self.channel.receiver_count.fetch_add(1, Ordering::Relaxed);
+
+        // Make sure the count never overflows, even if lots of receiver clones are leaked.
+        if count > isize::MAX as usize {
+            process::abort();
+        }
+
+        UnboundedReceiver {
+            channel: self.channel.clone(),
+            opt_key: None,
+        }
+    }
+}
+
+impl<T> Stream for UnboundedReceiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        poll_recv(
+            &this.channel,
+            &this.channel.stream_wakers,
+            &mut this.opt_key,
+            cx,
+        )
+    }
+}
+
+impl<T> fmt::Debug for UnboundedReceiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("UnboundedReceiver { .. }")
+    }
+}
+
+/// Polls a receive operation on a channel.
+///
+/// If the receive operation is blocked, the current task will be inserted into `wakers` and its
+/// associated key will then be stored in `opt_key`.
+fn poll_recv<T>(
+    channel: &Channel<T>,
+    wakers: &WakerSet,
+    opt_key: &mut Option<usize>,
+    cx: &mut Context<'_>,
+) -> Poll<Option<T>> {
+    loop {
+        // If the current task is in the set, remove it.
+        if let Some(key) = opt_key.take() {
+            wakers.remove(key);
+        }
+
+        // Try receiving a message.
+        match channel.try_recv() {
+            Ok(msg) => return Poll::Ready(Some(msg)),
+            Err(true) => return Poll::Ready(None),
+            Err(false) => {
+                // Insert this receive operation.
+                *opt_key = Some(wakers.insert(cx));
+
+                // If the channel is still empty and not closed, return.
+                let state = channel.state.lock().unwrap();
+                if state.queue.is_empty() && !state.closed {
+                    return Poll::Pending;
+                }
+            }
+        }
+    }
+}
+
+/// The mutable state of an unbounded channel.
+struct State<T> {
+    /// The messages in the channel, with their sizes in bytes.
+    queue: VecDeque<(T, usize)>,
+
+    /// The total size of the messages in bytes.
+    bytes: usize,
+
+    /// Set to `true` once all senders or all receivers have been dropped.
+    closed: bool,
+
+    /// Set when the high-water callback has been called and the queue hasn't been drained below
+    /// the marks since.
+    above_mark: bool,
+}
+
+/// Unbounded channel based on a queue behind a lock.
+struct Channel<T> {
+    /// The mutable state of the channel.
+    state: Mutex<State<T>>,
+
+    /// Measures messages in bytes.
+    size_fn: Option<SizeFn<T>>,
+
+    /// The high-water mark for the number of messages.
+    len_mark: Option<usize>,
+
+    /// The high-water mark for the number of bytes.
+    bytes_mark: Option<usize>,
+
+    /// Called when the queue reaches a high-water mark.
+    callback: Option<Callback>,
+
+    /// Receive operations waiting while the channel is empty and not closed.
+    recv_wakers: WakerSet,
+
+    /// Streams waiting while the channel is empty and not closed.
+    stream_wakers: WakerSet,
+
+    /// The number of currently active `UnboundedSender`s.
+    sender_count: AtomicUsize,
+
+    /// The number of currently active `UnboundedReceiver`s.
+    receiver_count: AtomicUsize,
+}
+
+unsafe impl<T: Send> Send for Channel<T> {}
+unsafe impl<T: Send> Sync for Channel<T> {}
+
+impl<T> Channel<T> {
+    /// Sends a message.
+    fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        let size = self.size_fn.as_ref().map_or(0, |f| f(&msg));
+
+        let crossed = {
+            let mut state = self.state.lock().unwrap();
+            if state.closed {
+                return Err(SendError(msg));
+            }
+
+            state.queue.push_back((msg, size));
+            state.bytes += size;
+
+            let usage = ChannelUsage {
+                len: state.queue.len(),
+                bytes: state.bytes,
+            };
+            if !state.above_mark && self.is_above_mark(usage) {
+                state.above_mark = true;
+                Some(usage)
+            } else {
+                None
+            }
+        };
+
+        // Wake a blocked receive operation.
+        self.recv_wakers.notify_one();
+
+        // Wake all blocked streams.
+        self.stream_wakers.notify_all();
+
+        if let (Some(usage), Some(callback)) = (crossed, &self.callback) {
+            callback(usage);
+        }
+
+        Ok(())
+    }
+
+    /// Attempts to receive a message.
+    ///
+    /// On failure, returns `true` if the channel is closed.
+    fn try_recv(&self) -> Result<T, bool> {
+        let mut state = self.state.lock().unwrap();
+        let (msg, size) = match state.queue.pop_front() {
+            Some(entry) => entry,
+            None => return Err(state.closed),
+        };
+        state.bytes -= size;
+
+        if state.above_mark {
+            let usage = ChannelUsage {
+                len: state.queue.len(),
+                bytes: state.bytes,
+            };
+            if !self.is_above_mark(usage) {
+                state.above_mark = false;
+            }
+        }
+
+        Ok(msg)
+    }
+
+    /// Returns `true` if `usage` has reached one of the high-water marks.
+    fn is_above_mark(&self, usage: ChannelUsage) -> bool {
+        let len = matches!(self.len_mark, Some(mark) if usage.len >= mark);
+        let bytes = matches!(self.bytes_mark, Some(mark) if usage.bytes >= mark);
+        len || (bytes && self.size_fn.is_some())
+    }
+
+    /// Returns the amount of data queued in the channel.
+    fn usage(&self) -> ChannelUsage {
+        let state = self.state.lock().unwrap();
+        ChannelUsage {
+            len: state.queue.len(),
+            bytes: state.bytes,
+        }
+    }
+
+    /// Closes the channel and wakes up all blocked operations.
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+
+        // Notify everyone blocked on this channel.
+        self.recv_wakers.notify_all();
+        self.stream_wakers.notify_all();
+    }
+}
+
+/// An error returned from the [`UnboundedSender::send`] method.
+///
+/// The message could not be sent because all receivers have been dropped.
+///
+/// [`UnboundedSender::send`]: struct.UnboundedSender.html#method.send
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct SendError<T>(pub T);
+
+impl<T> Error for SendError<T> {}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "sending on a closed channel".fmt(f)
+    }
+}
@@ -0,0 +1,287 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::sync::WakerSet;
+use crate::task::{self, Context, JoinHandle, Poll};
+
+/// Enables tasks to wait until a group of other tasks has finished.
+///
+/// Every clone of a `WaitGroup` is a registration in the group. Dropping a clone deregisters it,
+/// and [`wait`] completes once all registrations are gone.
+///
+/// [`wait`]: #method.wait
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::WaitGroup;
+/// use async_std::task;
+///
+/// let wg = WaitGroup::new();
+///
+/// for _ in 0..4 {
+///     let wg = wg.clone();
+///     task::spawn(async move {
+///         // Do some work.
+///         drop(wg);
+///     });
+/// }
+///
+/// // Wait for all spawned tasks to drop their registration.
+/// wg.wait().await;
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct WaitGroup {
+    counter: Arc<Counter>,
+}
+
+impl WaitGroup {
+    /// Creates a new wait group with a single registration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::WaitGroup;
+    ///
+    /// let wg = WaitGroup::new();
+    /// ```
+    pub fn new() -> WaitGroup {
+        WaitGroup::register(Arc::new(Counter::new()))
+    }
+
+    /// Drops this registration and waits until all other registrations are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::WaitGroup;
+    ///
+    /// let wg = WaitGroup::new();
+    /// let other = wg.clone();
+    ///
+    /// drop(other);
+    /// wg.wait().await;
+    /// #
+    /// # })
+    /// ```
+    pub async fn wait(self) {
+        let counter = self.counter.clone();
+        drop(self);
+        counter.wait().await
+    }
+
+    /// Returns the number of registrations in the group.
+    pub fn count(&self) -> usize {
+        self.counter.count.load(Ordering::SeqCst)
+    }
+
+    /// Adds a registration to the counter.
+    fn register(counter: Arc<Counter>) -> WaitGroup {
+        let count = counter.count.fetch_add(1, Ordering::Relaxed);
+
+        // Make sure the count never overflows, even if lots of registrations are leaked.
+        if count > isize::MAX as usize {
+            process::abort();
+        }
+
+        WaitGroup { counter }
+    }
+}
+
+impl Clone for WaitGroup {
+    fn clone(&self) -> WaitGroup {
+        WaitGroup::register(self.counter.clone())
+    }
+}
+
+impl Drop for WaitGroup {
+    fn drop(&mut self) {
+        // Use `SeqCst` ordering to synchronize with `WakerSet::insert()`.
+        if self.counter.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.counter.wakers.notify_all();
+        }
+    }
+}
+
+impl Default for WaitGroup {
+    fn default() -> WaitGroup {
+        WaitGroup::new()
+    }
+}
+
+impl fmt::Debug for WaitGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitGroup")
+            .field("count", &self.count())
+            .finish()
+    }
+}
+
+/// Spawns tasks and waits for all of them to complete.
+///
+/// This is useful for graceful shutdown: a server spawns its request handlers through a
+/// `TaskTracker`, stops accepting new requests, and then waits for the outstanding handlers.
+///
+/// Cloning a `TaskTracker` returns a handle to the same set of tasks.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::sync::TaskTracker;
+/// use async_std::task;
+///
+/// let tracker = TaskTracker::new();
+///
+/// for i in 0..4 {
+///     tracker.spawn(async move {
+///         task::sleep(Duration::from_millis(i * 10)).await;
+///     });
+/// }
+///
+/// tracker.wait().await;
+/// assert!(tracker.is_empty());
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone)]
+pub struct TaskTracker {
+    counter: Arc<Counter>,
+}
+
+impl TaskTracker {
+    /// Creates a new tracker with no tasks.
+    pub fn new() -> TaskTracker {
+        TaskTracker {
+            counter: Arc::new(Counter::new()),
+        }
+    }
+
+    /// Spawns a task tracked by this tracker.
+    ///
+    /// The task counts as outstanding until its future completes or is dropped.
+    pub fn spawn<F, T>(&self, future: F) -> JoinHandle<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let registration = WaitGroup::register(self.counter.clone());
+        task::spawn(async move {
+            let _registration = registration;
+            future.await
+        })
+    }
+
+    /// Returns the number of outstanding tasks.
+    pub fn len(&self) -> usize {
+        self.counter.count.load(Ordering::SeqCst)
+    }
+
+    /// Returns `true` if there are no outstanding tasks.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Waits until there are no outstanding tasks.
+    ///
+    /// Tasks spawned while waiting are waited for as well.
+    pub async fn wait(&self) {
+        self.counter.wait().await
+    }
+}
+
+impl Default for TaskTracker {
+    fn default() -> TaskTracker {
+        TaskTracker::new()
+    }
+}
+
+impl fmt::Debug for TaskTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskTracker")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// A counter of registrations with a set of tasks waiting for it to reach zero.
+struct Counter {
+    count: AtomicUsize,
+    wakers: WakerSet,
+}
+
+unsafe impl Send for Counter {}
+unsafe impl Sync for Counter {}
+
+impl Counter {
+    fn new() -> Counter {
+        Counter {
+            count: AtomicUsize::new(0),
+            wakers: WakerSet::new(),
+        }
+    }
+
+    /// Waits until the count drops down to zero.
+    async fn wait(&self) {
+        struct WaitFuture<'a> {
+            counter: &'a Counter,
+            opt_key: Option<usize>,
+        }
+
+        impl Future for WaitFuture<'_> {
+            type Output = ();
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                loop {
+                    // If the current task is in the set, remove it.
+                    if let Some(key) = self.opt_key.take() {
+                        self.counter.wakers.remove(key);
+                    }
+
+                    if self.counter.count.load(Ordering::SeqCst) == 0 {
+                        return Poll::Ready(());
+                    }
+
+                    // Insert this wait operation.
+                    self.opt_key = Some(self.counter.wakers.insert(cx));
+
+                    // If there are still registrations, return.
+                    if self.counter.count.load(Ordering::SeqCst) != 0 {
+                        return Poll::Pending;
+                    }
+                }
+            }
+        }
+
+        impl Drop for WaitFuture<'_> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                if let Some(key) = self.opt_key {
+                    self.counter.wakers.cancel(key);
+                }
+            }
+        }
+
+        WaitFuture {
+            counter: self,
+            opt_key: None,
+        }
+        .await
+    }
+}
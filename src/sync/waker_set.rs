@@ -5,6 +5,8 @@
 //! notifying them when they may make progress.
 
 use std::cell::UnsafeCell;
+use std::collections::VecDeque;
+use std::fmt;
 use std::ops::{Deref, DerefMut};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::task::{Context, Waker};
@@ -26,18 +28,108 @@ const NOTIFIABLE: usize = 1 << 2;
 struct Inner {
     /// A list of entries in the set.
     ///
-    /// Each entry has an optional waker associated with the task that is executing the operation.
-    /// If the waker is set to `None`, that means the task has been woken up but hasn't removed
-    /// itself from the `WakerSet` yet.
-    ///
     /// The key of each entry is its index in the `Slab`.
-    entries: Slab<Option<Waker>>,
+    entries: Slab<Entry>,
+
+    /// The keys and sequence numbers of entries in the order they were inserted.
+    ///
+    /// Entries that have been removed are dropped lazily, so a key only refers to an entry if the
+    /// sequence numbers match.
+    order: VecDeque<(usize, u64)>,
+
+    /// The sequence number of the next inserted entry.
+    next_seq: u64,
 
     /// The number of notifiable entries.
     notifiable: usize,
 }
 
-/// A set holding wakers.
+/// An entry in the set.
+struct Entry {
+    /// The waker associated with the task that is executing the operation.
+    ///
+    /// If the waker is set to `None`, that means the task has been woken up but hasn't removed
+    /// itself from the `WakerSet` yet.
+    waker: Option<Waker>,
+
+    /// The position of the entry in the insertion order.
+    seq: u64,
+}
+
+/// A set of wakers of blocked operations.
+///
+/// This is the building block of the synchronization primitives in this module. An operation that
+/// cannot make progress [`insert`]s the waker of its task into the set and keeps the returned key.
+/// Once it may make progress, one of the `notify` methods wakes it up. When the operation polls
+/// again, it [`remove`]s its entry, or [`cancel`]s it if the operation is dropped before
+/// completing, so that a notification it consumed is passed on to another operation.
+///
+/// Operations are notified in the order they were inserted, so that no operation waits forever
+/// while others keep getting notified. Notifying is cheap when no operation is waiting, since the
+/// set isn't locked then.
+///
+/// [`insert`]: #method.insert
+/// [`remove`]: #method.remove
+/// [`cancel`]: #method.cancel
+///
+/// # Examples
+///
+/// A flag that tasks can wait for:
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// use async_std::future::poll_fn;
+/// use async_std::sync::{Arc, WakerSet};
+/// use async_std::task::{self, Poll};
+///
+/// struct Flag {
+///     set: AtomicBool,
+///     wakers: WakerSet,
+/// }
+///
+/// impl Flag {
+///     fn set(&self) {
+///         self.set.store(true, Ordering::SeqCst);
+///         self.wakers.notify_all();
+///     }
+///
+///     async fn wait(&self) {
+///         let mut key = None;
+///         poll_fn(|cx| {
+///             if let Some(key) = key.take() {
+///                 self.wakers.remove(key);
+///             }
+///             if self.set.load(Ordering::SeqCst) {
+///                 return Poll::Ready(());
+///             }
+///             key = Some(self.wakers.insert(cx));
+///             // Check again in case the flag was set in the meantime.
+///             if self.set.load(Ordering::SeqCst) {
+///                 self.wakers.remove(key.take().unwrap());
+///                 return Poll::Ready(());
+///             }
+///             Poll::Pending
+///         })
+///         .await
+///     }
+/// }
+///
+/// let flag = Arc::new(Flag {
+///     set: AtomicBool::new(false),
+///     wakers: WakerSet::new(),
+/// });
+///
+/// let f = flag.clone();
+/// let handle = task::spawn(async move { f.wait().await });
+/// flag.set();
+/// handle.await;
+/// #
+/// # })
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
 pub struct WakerSet {
     /// Holds three bits: `LOCKED`, `NOTIFY_ONE`, and `NOTIFY_ALL`.
     flag: AtomicUsize,
@@ -54,18 +146,32 @@ impl WakerSet {
             flag: AtomicUsize::new(0),
             inner: UnsafeCell::new(Inner {
                 entries: Slab::new(),
+                order: VecDeque::new(),
+                next_seq: 0,
                 notifiable: 0,
             }),
         }
     }
 
     /// Inserts a waker for a blocked operation and returns a key associated with it.
+    ///
+    /// The key must be passed to [`remove`] or [`cancel`] once the operation is no longer
+    /// blocked.
+    ///
+    /// [`remove`]: #method.remove
+    /// [`cancel`]: #method.cancel
     #[cold]
     pub fn insert(&self, cx: &Context<'_>) -> usize {
         let w = cx.waker().clone();
         let mut inner = self.lock();
 
-        let key = inner.entries.insert(Some(w));
+        let seq = inner.next_seq;
+        inner.next_seq += 1;
+        let key = inner.entries.insert(Entry {
+            waker: Some(w),
+            seq,
+        });
+        inner.order.push_back((key, seq));
         inner.notifiable += 1;
         key
     }
@@ -75,9 +181,10 @@ impl WakerSet {
     pub fn remove(&self, key: usize) {
         let mut inner = self.lock();
 
-        if inner.entries.remove(key).is_some() {
+        if inner.entries.remove(key).waker.is_some() {
             inner.notifiable -= 1;
         }
+        inner.trim();
     }
 
     /// Removes the waker of a cancelled operation.
@@ -87,22 +194,16 @@ impl WakerSet {
     pub fn cancel(&self, key: usize) -> bool {
         let mut inner = self.lock();
 
-        match inner.entries.remove(key) {
-            Some(_) => inner.notifiable -= 1,
-            None => {
-                // The operation was cancelled and notified so notify another operation instead.
-                for (_, opt_waker) in inner.entries.iter_mut() {
-                    // If there is no waker in this entry, that means it was already woken.
-                    if let Some(w) = opt_waker.take() {
-                        w.wake();
-                        inner.notifiable -= 1;
-                        return true;
-                    }
-                }
+        let notified = match inner.entries.remove(key).waker {
+            Some(_) => {
+                inner.notifiable -= 1;
+                false
             }
-        }
-
-        false
+            // The operation was cancelled and notified so notify another operation instead.
+            None => inner.notify(Notify::One),
+        };
+        inner.trim();
+        notified
     }
 
     /// Notifies a blocked operation if none have been notified already.
@@ -134,6 +235,20 @@ impl WakerSet {
         }
     }
 
+    /// Notifies up to `n` additional blocked operations while holding the lock only once.
+    ///
+    /// Returns `true` if at least one operation was notified.
+    #[inline]
+    #[cfg(feature = "unstable")]
+    pub fn notify_many(&self, n: usize) -> bool {
+        // Use `SeqCst` ordering to synchronize with `Lock::drop()`.
+        if n > 0 && self.flag.load(Ordering::SeqCst) & NOTIFIABLE != 0 {
+            self.notify(Notify::Many(n))
+        } else {
+            false
+        }
+    }
+
     /// Notifies all blocked operations.
     ///
     /// Returns `true` if at least one operation was notified.
@@ -147,23 +262,47 @@ impl WakerSet {
         }
     }
 
-    /// Notifies blocked operations, either one or all of them.
+    /// Notifies blocked operations, either one, a number, or all of them.
     ///
     /// Returns `true` if at least one operation was notified.
     #[cold]
     fn notify(&self, n: Notify) -> bool {
-        let mut inner = &mut *self.lock();
-        let mut notified = false;
+        self.lock().notify(n)
+    }
+
+    /// Locks the list of entries.
+    fn lock(&self) -> Lock<'_> {
+        let backoff = Backoff::new();
+        while self.flag.fetch_or(LOCKED, Ordering::Acquire) & LOCKED != 0 {
+            backoff.snooze();
+        }
+        Lock { waker_set: self }
+    }
+}
+
+impl Inner {
+    /// Notifies blocked operations in the order they were inserted.
+    ///
+    /// Returns `true` if at least one operation was notified.
+    fn notify(&mut self, n: Notify) -> bool {
+        let mut count = 0;
+
+        for &(key, seq) in self.order.iter() {
+            let entry = match self.entries.get_mut(key) {
+                Some(entry) if entry.seq == seq => entry,
+                _ => continue,
+            };
 
-        for (_, opt_waker) in inner.entries.iter_mut() {
             // If there is no waker in this entry, that means it was already woken.
-            if let Some(w) = opt_waker.take() {
+            if let Some(w) = entry.waker.take() {
                 w.wake();
-                inner.notifiable -= 1;
-                notified = true;
+                self.notifiable -= 1;
+                count += 1;
 
-                if n == Notify::One {
-                    break;
+                match n {
+                    Notify::One => break,
+                    Notify::Many(max) if count >= max => break,
+                    _ => {}
                 }
             }
 
@@ -172,19 +311,39 @@ impl WakerSet {
             }
         }
 
-        notified
+        count > 0
     }
 
-    /// Locks the list of entries.
-    fn lock(&self) -> Lock<'_> {
-        let backoff = Backoff::new();
-        while self.flag.fetch_or(LOCKED, Ordering::Acquire) & LOCKED != 0 {
-            backoff.snooze();
+    /// Drops removed entries from the insertion order.
+    fn trim(&mut self) {
+        let entries = &self.entries;
+        let live = |&(key, seq): &(usize, u64)| matches!(entries.get(key), Some(e) if e.seq == seq);
+
+        if self.order.len() > 2 * entries.len() + 16 {
+            self.order.retain(live);
+        } else {
+            while matches!(self.order.front(), Some(front) if !live(front)) {
+                self.order.pop_front();
+            }
         }
-        Lock { waker_set: self }
     }
 }
 
+impl Default for WakerSet {
+    fn default() -> WakerSet {
+        WakerSet::new()
+    }
+}
+
+impl fmt::Debug for WakerSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("WakerSet { .. }")
+    }
+}
+
+unsafe impl Send for WakerSet {}
+unsafe impl Sync for WakerSet {}
+
 /// A guard holding a `WakerSet` locked.
 struct Lock<'a> {
     waker_set: &'a WakerSet,
@@ -233,6 +392,8 @@ enum Notify {
     Any,
     /// Notify one additional entry.
     One,
+    /// Notify up to a number of additional entries.
+    Many(usize),
     /// Notify all entries.
     All,
 }
@@ -0,0 +1,544 @@
+//! A single-producer, multi-consumer channel that only retains the latest value.
+//!
+//! A watch channel distributes a piece of state: the [`Sender`] replaces the current value, and
+//! every [`Receiver`] can look at the current value or wait until it changes. Receivers don't
+//! see every intermediate value, only the most recent one.
+//!
+//! [`Sender`]: struct.Sender.html
+//! [`Receiver`]: struct.Receiver.html
+//!
+//! # Examples
+//!
+//! ```
+//! # async_std::task::block_on(async {
+//! #
+//! use async_std::sync::watch;
+//! use async_std::task;
+//!
+//! let (s, mut r) = watch::channel("hello");
+//!
+//! task::spawn(async move {
+//!     s.send("world").unwrap();
+//! });
+//!
+//! r.changed().await.unwrap();
+//! assert_eq!(*r.borrow(), "world");
+//! #
+//! # })
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::process;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+use std::task::{Context, Poll};
+
+use crate::stream::Stream;
+use crate::sync::WakerSet;
+
+/// Creates a watch channel holding `init` as its initial value.
+///
+/// The returned receiver considers the initial value as already seen, so [`Receiver::changed`]
+/// waits for the first value sent after it.
+///
+/// [`Receiver::changed`]: struct.Receiver.html#method.changed
+///
+/// # Examples
+///
+/// ```
+/// use async_std::sync::watch;
+///
+/// let (s, r) = watch::channel(1);
+/// assert_eq!(*r.borrow(), 1);
+///
+/// s.send(2).unwrap();
+/// assert_eq!(*r.borrow(), 2);
+/// ```
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let channel = Arc::new(Channel {
+        value: RwLock::new(init),
+        version: AtomicUsize::new(0),
+        closed: AtomicBool::new(false),
+        wakers: WakerSet::new(),
+        receiver_count: AtomicUsize::new(1),
+    });
+    let s = Sender {
+        channel: channel.clone(),
+    };
+    let r = Receiver {
+        channel,
+        seen: 0,
+        opt_key: None,
+    };
+    (s, r)
+}
+
+/// The sending side of a watch channel.
+///
+/// Dropping the sender closes the channel. Receivers can still look at the last value, but
+/// [`Receiver::changed`] returns an error once it has been seen.
+///
+/// This struct is created by the [`channel`] function. See its
+/// documentation for more.
+///
+/// [`channel`]: fn.channel.html
+/// [`Receiver::changed`]: struct.Receiver.html#method.changed
+pub struct Sender<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Replaces the current value and notifies all receivers.
+    ///
+    /// If there are no receivers, the value is returned back in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, r) = watch::channel(0);
+    /// assert!(s.send(1).is_ok());
+    ///
+    /// drop(r);
+    /// assert!(s.send(2).is_err());
+    /// ```
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if self.channel.receiver_count.load(Ordering::SeqCst) == 0 {
+            return Err(SendError(value));
+        }
+
+        self.replace(value);
+        Ok(())
+    }
+
+    /// Replaces the current value and notifies all receivers, returning the previous value.
+    ///
+    /// Unlike [`send`], this method updates the value even if there are no receivers.
+    ///
+    /// [`send`]: #method.send
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, _) = watch::channel(0);
+    /// assert_eq!(s.replace(1), 0);
+    /// assert_eq!(*s.borrow(), 1);
+    /// ```
+    pub fn replace(&self, value: T) -> T {
+        self.channel.update(|v| mem::replace(v, value))
+    }
+
+    /// Modifies the current value in place and notifies all receivers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, r) = watch::channel(vec![1]);
+    /// s.modify(|v| v.push(2));
+    /// assert_eq!(*r.borrow(), [1, 2]);
+    /// ```
+    pub fn modify<F>(&self, f: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        self.channel.update(f)
+    }
+
+    /// Returns a reference to the current value.
+    ///
+    /// The value is locked for writing while the reference is held, so it should be dropped
+    /// quickly.
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref(self.channel.value.read().unwrap())
+    }
+
+    /// Creates a new receiver that considers the current value as already seen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, _) = watch::channel(0);
+    /// let r = s.subscribe();
+    /// assert_eq!(s.receiver_count(), 1);
+    /// assert!(!r.has_changed());
+    /// ```
+    pub fn subscribe(&self) -> Receiver<T> {
+        let seen = self.channel.version.load(Ordering::SeqCst);
+        Receiver::new(self.channel.clone(), seen)
+    }
+
+    /// Returns the number of active receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.channel.receiver_count.load(Ordering::SeqCst)
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.channel.closed.store(true, Ordering::SeqCst);
+        self.channel.wakers.notify_all();
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("value", &*self.borrow())
+            .finish()
+    }
+}
+
+/// The receiving side of a watch channel.
+///
+/// This type waits for changes by calling `changed`. But it also implements the [`Stream`]
+/// trait, yielding a clone of the value every time it changes. The stream ends once the sender
+/// is dropped and the last value has been seen.
+///
+/// Cloning a receiver creates a new receiver that has seen the same values as the original.
+///
+/// This struct is created by the [`channel`] function. See its documentation for more.
+///
+/// [`channel`]: fn.channel.html
+/// [`Stream`]: ../../stream/trait.Stream.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::prelude::*;
+/// use async_std::sync::watch;
+///
+/// let (s, mut r) = watch::channel(0);
+///
+/// s.send(1).unwrap();
+/// s.send(2).unwrap();
+/// drop(s);
+///
+/// // Only the latest value is observed.
+/// assert_eq!(r.next().await, Some(2));
+/// assert_eq!(r.next().await, None);
+/// #
+/// # })
+/// ```
+pub struct Receiver<T> {
+    /// The inner channel.
+    channel: Arc<Channel<T>>,
+
+    /// The version of the value this receiver has last seen.
+    seen: usize,
+
+    /// The key for this receiver in the `channel.wakers` set.
+    opt_key: Option<usize>,
+}
+
+impl<T> Receiver<T> {
+    /// Creates a new receiver that has seen version `seen` of the value.
+    fn new(channel: Arc<Channel<T>>, seen: usize) -> Receiver<T> {
+        let count = channel.receiver_count.fetch_add(1, Ordering::Relaxed);
+
+        // Make sure the count never overflows, even if lots of receiver clones are leaked.
+        if count > isize::MAX as usize {
+            process::abort();
+        }
+
+        Receiver {
+            channel,
+            seen,
+            opt_key: None,
+        }
+    }
+
+    /// Returns a reference to the current value without marking it as seen.
+    ///
+    /// The value is locked for writing while the reference is held, so it should be dropped
+    /// quickly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, r) = watch::channel(1);
+    /// s.send(2).unwrap();
+    ///
+    /// assert_eq!(*r.borrow(), 2);
+    /// assert!(r.has_changed());
+    /// ```
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref(self.channel.value.read().unwrap())
+    }
+
+    /// Returns a reference to the current value and marks it as seen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, mut r) = watch::channel(1);
+    /// s.send(2).unwrap();
+    ///
+    /// assert_eq!(*r.borrow_and_update(), 2);
+    /// assert!(!r.has_changed());
+    /// ```
+    pub fn borrow_and_update(&mut self) -> Ref<'_, T> {
+        let guard = self.channel.value.read().unwrap();
+        // The version can't change while the value is locked for reading.
+        self.seen = self.channel.version.load(Ordering::SeqCst);
+        Ref(guard)
+    }
+
+    /// Returns `true` if the value has changed since it was last seen by this receiver.
+    pub fn has_changed(&self) -> bool {
+        self.channel.version.load(Ordering::SeqCst) != self.seen
+    }
+
+    /// Waits until the value changes and marks the new value as seen.
+    ///
+    /// Returns an error if the sender was dropped and there is no unseen value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::watch;
+    /// use async_std::task;
+    ///
+    /// let (s, mut r) = watch::channel(0);
+    ///
+    /// task::spawn(async move {
+    ///     s.send(1).unwrap();
+    /// });
+    ///
+    /// assert!(r.changed().await.is_ok());
+    /// assert_eq!(*r.borrow(), 1);
+    /// assert!(r.changed().await.is_err());
+    /// #
+    /// # })
+    /// ```
+    pub async fn changed(&mut self) -> Result<(), RecvError> {
+        struct ChangedFuture<'a, T> {
+            channel: &'a Channel<T>,
+            seen: &'a mut usize,
+            opt_key: Option<usize>,
+        }
+
+        impl<T> Future for ChangedFuture<'_, T> {
+            type Output = Result<(), RecvError>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                poll_changed(this.channel, this.seen, &mut this.opt_key, cx)
+            }
+        }
+
+        impl<T> Drop for ChangedFuture<'_, T> {
+            fn drop(&mut self) {
+                // If the current task is still in the set, that means it is being cancelled now.
+                if let Some(key) = self.opt_key {
+                    self.channel.wakers.cancel(key);
+                }
+            }
+        }
+
+        ChangedFuture {
+            channel: &self.channel,
+            seen: &mut self.seen,
+            opt_key: None,
+        }
+        .await
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        // If the current task is still in the set, that means it is being cancelled now.
+        if let Some(key) = self.opt_key {
+            self.channel.wakers.cancel(key);
+        }
+
+        self.channel.receiver_count.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        Receiver::new(self.channel.clone(), self.seen)
+    }
+}
+
+impl<T: Clone> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        match poll_changed(&this.channel, &mut this.seen, &mut this.opt_key, cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(Some(this.borrow().clone())),
+            Poll::Ready(Err(_)) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("value", &*self.borrow())
+            .finish()
+    }
+}
+
+/// Polls for a change of the value.
+///
+/// If the operation is blocked, the current task will be inserted into `channel.wakers` and its
+/// associated key will then be stored in `opt_key`.
+fn poll_changed<T>(
+    channel: &Channel<T>,
+    seen: &mut usize,
+    opt_key: &mut Option<usize>,
+    cx: &mut Context<'_>,
+) -> Poll<Result<(), RecvError>> {
+    loop {
+        // If the current task is in the set, remove it.
+        if let Some(key) = opt_key.take() {
+            channel.wakers.remove(key);
+        }
+
+        let version = channel.version.load(Ordering::SeqCst);
+        if version != *seen {
+            *seen = version;
+            return Poll::Ready(Ok(()));
+        }
+        if channel.closed.load(Ordering::SeqCst) {
+            return Poll::Ready(Err(RecvError { _private: () }));
+        }
+
+        // Insert this operation.
+        *opt_key = Some(channel.wakers.insert(cx));
+
+        // If the value still hasn't changed and the channel is not closed, return.
+        if channel.version.load(Ordering::SeqCst) == *seen && !channel.closed.load(Ordering::SeqCst)
+        {
+            return Poll::Pending;
+        }
+    }
+}
+
+/// A reference to the value in a watch channel.
+///
+/// This struct is created by the `borrow` methods on [`Sender`] and [`Receiver`].
+///
+/// [`Sender`]: struct.Sender.html
+/// [`Receiver`]: struct.Receiver.html
+pub struct Ref<'a, T>(RwLockReadGuard<'a, T>);
+
+impl<T> Deref for Ref<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Ref<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+/// The state shared between the sender and receivers of a watch channel.
+struct Channel<T> {
+    /// The current value.
+    value: RwLock<T>,
+
+    /// Incremented every time the value changes.
+    version: AtomicUsize,
+
+    /// Set to `true` once the sender has been dropped.
+    closed: AtomicBool,
+
+    /// Operations waiting for the value to change.
+    wakers: WakerSet,
+
+    /// The number of currently active `Receivers`s.
+    receiver_count: AtomicUsize,
+}
+
+unsafe impl<T: Send + Sync> Send for Channel<T> {}
+unsafe impl<T: Send + Sync> Sync for Channel<T> {}
+
+impl<T> Channel<T> {
+    /// Modifies the value and wakes up all operations waiting for a change.
+    fn update<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let res = {
+            let mut value = self.value.write().unwrap();
+            let res = f(&mut value);
+
+            // Bump the version while the value is still locked so that readers holding the lock
+            // always observe a version matching the value.
+            self.version.fetch_add(1, Ordering::SeqCst);
+            res
+        };
+
+        self.wakers.notify_all();
+        res
+    }
+}
+
+/// An error returned from the [`Sender::send`] method.
+///
+/// The value could not be sent because there are no receivers.
+///
+/// [`Sender::send`]: struct.Sender.html#method.send
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct SendError<T>(pub T);
+
+impl<T> Error for SendError<T> {}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "sending on a channel with no receivers".fmt(f)
+    }
+}
+
+/// An error returned from the [`Receiver::changed`] method.
+///
+/// The sender was dropped and there is no unseen value.
+///
+/// [`Receiver::changed`]: struct.Receiver.html#method.changed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RecvError {
+    _private: (),
+}
+
+impl Error for RecvError {}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "the sender was dropped".fmt(f)
+    }
+}
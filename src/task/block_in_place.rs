@@ -0,0 +1,43 @@
+use crate::task::executor;
+
+/// Runs blocking code in place without blocking the executor.
+///
+/// When called from a task, the worker thread running the task hands its queue of tasks over to
+/// a new worker thread before running `f`, so other tasks are not held up while `f` blocks. The
+/// calling task stays on the current thread until it completes or yields.
+///
+/// Unlike [`spawn_blocking`], the closure doesn't have to be `Send` or `'static`, so this can be
+/// used from existing call stacks that borrow from their environment.
+///
+/// When called outside of the executor, `f` simply runs on the current thread.
+///
+/// Since each call starts a new worker thread, prefer [`spawn_blocking`] where possible.
+///
+/// [`spawn_blocking`]: fn.spawn_blocking.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::task;
+///
+/// let mut lines = Vec::new();
+/// task::spawn(async move {
+///     task::block_in_place(|| {
+///         lines.push(std::fs::read_to_string("Cargo.toml").is_ok());
+///     });
+///     assert_eq!(lines, [true]);
+/// })
+/// .await;
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn block_in_place<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    executor::block_in_place(f)
+}
@@ -1,4 +1,8 @@
 use std::cell::Cell;
+#[cfg(feature = "unstable")]
+use std::error::Error;
+#[cfg(feature = "unstable")]
+use std::fmt;
 use std::future::Future;
 use std::mem::{self, ManuallyDrop};
 use std::sync::Arc;
@@ -9,7 +13,8 @@ use crossbeam_utils::sync::Parker;
 use kv_log_macro::trace;
 use log::log_enabled;
 
-use crate::task::{Context, Poll, Task, Waker};
+use crate::task::{budget, executor, Context, Poll, Priority, Task, Waker};
+use crate::time;
 
 /// Spawns a task and blocks the current thread on its result.
 ///
@@ -23,6 +28,15 @@ use crate::task::{Context, Poll, Task, Waker};
 /// [spawning]: https://doc.rust-lang.org/std/thread/fn.spawn.html
 /// [joining]: https://doc.rust-lang.org/std/thread/struct.JoinHandle.html#method.join
 ///
+/// # Panics
+///
+/// Panics if called from a worker thread of an executor, that is from within a task. Blocking a
+/// worker thread holds up the other tasks queued on it, and deadlocks if the future waits for one
+/// of them. Use `.await` instead, or wrap the call in [`task::block_in_place`] if blocking is
+/// intended.
+///
+/// [`task::block_in_place`]: fn.block_in_place.html
+///
 /// # Examples
 ///
 /// ```no_run
@@ -35,11 +49,90 @@ use crate::task::{Context, Poll, Task, Waker};
 /// }
 /// ```
 pub fn block_on<F, T>(future: F) -> T
+where
+    F: Future<Output = T>,
+{
+    if executor::is_worker_thread() {
+        panic!("{}", NESTED_BLOCK_ON);
+    }
+    block_on_unchecked(future)
+}
+
+/// Spawns a task and blocks the current thread on its result, unless called from within a task.
+///
+/// This is like [`block_on`], except that it returns a [`BlockOnError`] instead of panicking when
+/// called from a worker thread of an executor.
+///
+/// [`block_on`]: fn.block_on.html
+/// [`BlockOnError`]: struct.BlockOnError.html
+///
+/// # Examples
+///
+/// ```
+/// use async_std::task;
+///
+/// assert_eq!(task::try_block_on(async { 1 + 2 }), Ok(3));
+///
+/// task::block_on(async {
+///     task::spawn(async {
+///         // Blocking a worker thread is refused.
+///         assert!(task::try_block_on(async { 1 + 2 }).is_err());
+///     })
+///     .await;
+/// });
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn try_block_on<F, T>(future: F) -> Result<T, BlockOnError>
+where
+    F: Future<Output = T>,
+{
+    if executor::is_worker_thread() {
+        return Err(BlockOnError { _private: () });
+    }
+    Ok(block_on_unchecked(future))
+}
+
+/// The message of the panic raised by [`block_on`] on a worker thread.
+///
+/// [`block_on`]: fn.block_on.html
+const NESTED_BLOCK_ON: &str = "cannot block on a future from within a task, as this blocks a \
+                               worker thread of the executor";
+
+/// An error returned by [`try_block_on`] when called from within a task.
+///
+/// [`try_block_on`]: fn.try_block_on.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct BlockOnError {
+    _private: (),
+}
+
+#[cfg(feature = "unstable")]
+impl fmt::Debug for BlockOnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("BlockOnError")
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl fmt::Display for BlockOnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        NESTED_BLOCK_ON.fmt(f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl Error for BlockOnError {}
+
+/// Spawns a task and blocks the current thread on its result, even from within a task.
+pub(crate) fn block_on_unchecked<F, T>(future: F) -> T
 where
     F: Future<Output = T>,
 {
     // Create a new task handle.
-    let task = Task::new(None);
+    let task = Task::new(None, Priority::Normal);
 
     // Log this `block_on` operation.
     if log_enabled!(log::Level::Trace) {
@@ -69,6 +162,16 @@ where
         future.await
     };
 
+    #[cfg(feature = "tracing")]
+    let future = tracing::Instrument::instrument(
+        future,
+        tracing::trace_span!(
+            "block_on",
+            task_id = task.id().0,
+            parent_task_id = Task::get_current(|t| t.id().0).unwrap_or(0),
+        ),
+    );
+
     // Run the future as a task.
     unsafe { Task::set_current(&task, || run(future)) }
 }
@@ -127,7 +230,12 @@ where
 
         let mut step = 0;
         loop {
-            if let Poll::Ready(t) = future.as_mut().poll(cx) {
+            // Let the future use tokio's reactor and timers.
+            #[cfg(feature = "tokio-runtime")]
+            let _tokio = crate::runtime::tokio::enter();
+
+            let poll = budget::with_budget(|| time::with_cached_now(|| future.as_mut().poll(cx)));
+            if let Poll::Ready(t) = poll {
                 // Save the parker for the next invocation of `block`.
                 cache.set(Some(arc_parker));
                 return t;
@@ -138,6 +246,14 @@ where
                 thread::yield_now();
                 step += 1;
             } else {
+                // Let the paused clock jump forward instead if nothing else is left to do.
+                #[cfg(feature = "unstable")]
+                {
+                    if time::park(cx.waker()) {
+                        continue;
+                    }
+                }
+
                 arc_parker.park();
                 step = 0;
             }
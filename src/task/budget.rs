@@ -0,0 +1,166 @@
+use std::cell::Cell;
+#[cfg(feature = "unstable")]
+use std::future::Future;
+#[cfg(feature = "unstable")]
+use std::pin::Pin;
+
+#[cfg(feature = "unstable")]
+use pin_project_lite::pin_project;
+
+use crate::task::{Context, Poll};
+
+thread_local! {
+    /// The number of operations the current task may still complete before it yields.
+    ///
+    /// This is `None` if the current task is not constrained by a budget.
+    static BUDGET: Cell<Option<u8>> = const { Cell::new(None) };
+}
+
+/// The budget a task gets each time it is polled by the executor.
+const INITIAL: u8 = 128;
+
+/// Runs a closure that polls a task with a fresh budget.
+pub(crate) fn with_budget<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    BUDGET.with(|budget| {
+        let old = budget.replace(Some(INITIAL));
+        defer! {
+            budget.set(old);
+        }
+        f()
+    })
+}
+
+/// Consumes a unit of the current task's budget.
+///
+/// If the budget is exhausted, the task is rescheduled and `Poll::Pending` is returned.
+#[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+pub(crate) fn poll_proceed(cx: &mut Context<'_>) -> Poll<()> {
+    let exhausted = BUDGET
+        .try_with(|budget| match budget.get() {
+            Some(0) => true,
+            Some(n) => {
+                budget.set(Some(n - 1));
+                false
+            }
+            None => false,
+        })
+        .unwrap_or(false);
+
+    if exhausted {
+        // Like `yield_now`, tell the executor first so the task doesn't run again right away.
+        #[cfg(feature = "default")]
+        crate::task::executor::yield_current();
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    } else {
+        Poll::Ready(())
+    }
+}
+
+/// Consumes a unit of the current task's budget, yielding if the budget is exhausted.
+///
+/// Every time the executor polls a task, the task gets a budget of operations it may complete.
+/// Operations on resources like channels and sockets consume the budget and yield once it is
+/// exhausted, so that a task looping over a resource that is always ready can't starve the
+/// other tasks on its worker thread.
+///
+/// This function lets CPU-bound loops take part in the same scheme without yielding on every
+/// iteration like [`yield_now`] does.
+///
+/// [`yield_now`]: fn.yield_now.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::task;
+///
+/// let mut sum = 0u64;
+/// for i in 0..10_000 {
+///     sum += i;
+///     task::consume_budget().await;
+/// }
+/// assert_eq!(sum, 49_995_000);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn consume_budget() {
+    crate::future::poll_fn(poll_proceed).await
+}
+
+/// Turns off budgeting for a future.
+///
+/// Operations inside the future never yield because of an exhausted budget. See
+/// [`consume_budget`] for more on budgeting.
+///
+/// [`consume_budget`]: fn.consume_budget.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::channel;
+/// use async_std::task;
+///
+/// let (s, r) = channel(1000);
+/// for i in 0..1000 {
+///     s.send(i).await;
+/// }
+/// drop(s);
+///
+/// // Drain the channel without yielding in between.
+/// let n = task::unconstrained(async {
+///     let mut n = 0;
+///     while r.recv().await.is_some() {
+///         n += 1;
+///     }
+///     n
+/// })
+/// .await;
+/// assert_eq!(n, 1000);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn unconstrained<F: Future>(future: F) -> Unconstrained<F> {
+    Unconstrained { future }
+}
+
+#[cfg(feature = "unstable")]
+pin_project! {
+    /// A future that is not constrained by a budget.
+    ///
+    /// This future is created by the [`unconstrained`] function. See its documentation for more.
+    ///
+    /// [`unconstrained`]: fn.unconstrained.html
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    #[derive(Debug)]
+    pub struct Unconstrained<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<F: Future> Future for Unconstrained<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = self.project().future;
+        BUDGET.with(|budget| {
+            let old = budget.replace(None);
+            defer! {
+                budget.set(old);
+            }
+            future.poll(cx)
+        })
+    }
+}
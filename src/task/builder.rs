@@ -1,23 +1,53 @@
 use kv_log_macro::trace;
 use log::log_enabled;
+#[cfg(feature = "unstable")]
+use std::error::Error;
+use std::fmt;
 use std::future::Future;
+#[cfg(feature = "unstable")]
+use std::panic::Location;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+#[cfg(feature = "unstable")]
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+#[cfg(all(feature = "unstable", not(target_arch = "wasm32")))]
+use std::time::Instant;
+
+use pin_project_lite::pin_project;
 
 use crate::io;
-use crate::task::executor;
-use crate::task::{JoinHandle, Task};
+#[cfg(feature = "unstable")]
+use crate::runtime::metrics::COUNTERS;
+use crate::task::budget;
+use crate::task::executor::{self, Pool};
+use crate::task::{Context, JoinError, JoinHandle, Poll, Task};
+#[cfg(feature = "unstable")]
+use crate::task::{dump, spawn_blocking, LocalKey, TaskState};
+use crate::time;
 use crate::utils::abort_on_panic;
 
 /// Task builder that configures the settings of a new task.
-#[derive(Debug, Default)]
+#[derive(Default)]
 pub struct Builder {
     pub(crate) name: Option<String>,
+    pub(crate) abort_on_panic: bool,
+    pub(crate) priority: Priority,
+    pub(crate) local: bool,
+    pub(crate) locals: Vec<(u32, Box<dyn Send>)>,
 }
 
 impl Builder {
     /// Creates a new builder.
     #[inline]
     pub fn new() -> Builder {
-        Builder { name: None }
+        Builder {
+            name: None,
+            abort_on_panic: false,
+            priority: Priority::Normal,
+            local: false,
+            locals: Vec::new(),
+        }
     }
 
     /// Configures the name of the task.
@@ -27,29 +57,304 @@ impl Builder {
         self
     }
 
+    /// Configures whether a panic in the task aborts the process.
+    ///
+    /// By default, a panic in a task is caught and stops only that task. The panic is then
+    /// reported through its [`JoinHandle`].
+    ///
+    /// [`JoinHandle`]: struct.JoinHandle.html
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    #[inline]
+    pub fn abort_on_panic(mut self, abort: bool) -> Builder {
+        self.abort_on_panic = abort;
+        self
+    }
+
+    /// Configures the scheduling priority hint of the task.
+    ///
+    /// The executor may use the hint to decide which task to run next, but doesn't guarantee any
+    /// particular order.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    #[inline]
+    pub fn priority(mut self, priority: Priority) -> Builder {
+        self.priority = priority;
+        self
+    }
+
+    /// Configures whether the task is spawned onto the [`LocalSet`] running on the current thread
+    /// instead of the executor.
+    ///
+    /// Spawning a local task outside of a `LocalSet` fails with a [`SpawnError`].
+    ///
+    /// [`LocalSet`]: struct.LocalSet.html
+    /// [`SpawnError`]: struct.SpawnError.html
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    #[inline]
+    pub fn local(mut self, local: bool) -> Builder {
+        self.local = local;
+        self
+    }
+
+    /// Sets the initial value of a task-local for the task.
+    ///
+    /// The task-local starts out with this value instead of running its initializer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::cell::Cell;
+    ///
+    /// use async_std::prelude::*;
+    /// use async_std::task;
+    ///
+    /// task_local! {
+    ///     static NUMBER: Cell<u32> = Cell::new(0);
+    /// }
+    ///
+    /// let handle = task::Builder::new()
+    ///     .task_local(&NUMBER, Cell::new(7))
+    ///     .spawn(async { NUMBER.with(|n| n.get()) })
+    ///     .unwrap();
+    /// assert_eq!(handle.await, 7);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn task_local<T: Send + 'static>(mut self, key: &'static LocalKey<T>, value: T) -> Builder {
+        let key = key.key();
+        self.locals.retain(|(k, _)| *k != key);
+        self.locals.push((key, Box::new(value)));
+        self
+    }
+
+    /// Passes the current value of a task-local on to the task.
+    ///
+    /// The task-local starts out with a clone of the value it has in the current task, including
+    /// a value set by [`LocalKey::scope`]. Outside of a task, this does nothing.
+    ///
+    /// [`LocalKey::scope`]: struct.LocalKey.html#method.scope
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::prelude::*;
+    /// use async_std::task;
+    ///
+    /// task_local! {
+    ///     static REQUEST_ID: u64 = 0;
+    /// }
+    ///
+    /// let id = REQUEST_ID
+    ///     .scope(42, async {
+    ///         task::Builder::new()
+    ///             .inherit(&REQUEST_ID)
+    ///             .spawn(async { REQUEST_ID.with(|id| *id) })
+    ///             .unwrap()
+    ///             .await
+    ///     })
+    ///     .await;
+    /// assert_eq!(id, 42);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn inherit<T: Clone + Send + 'static>(self, key: &'static LocalKey<T>) -> Builder {
+        match key.try_with(|value| value.clone()) {
+            Ok(value) => self.task_local(key, value),
+            Err(_) => self,
+        }
+    }
+
+    /// Spawns a blocking task with the configured settings.
+    ///
+    /// This is like [`spawn_blocking`], except that the closure can access the task-locals set
+    /// with [`task_local`] and [`inherit`], and [`is_cancelled`] inside the closure also returns
+    /// `true` once the task calling this method has been aborted. The [`local`] setting is
+    /// ignored.
+    ///
+    /// [`spawn_blocking`]: fn.spawn_blocking.html
+    /// [`task_local`]: #method.task_local
+    /// [`inherit`]: #method.inherit
+    /// [`is_cancelled`]: fn.is_cancelled.html
+    /// [`local`]: #method.local
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::prelude::*;
+    /// use async_std::task;
+    ///
+    /// task_local! {
+    ///     static REQUEST_ID: u64 = 0;
+    /// }
+    ///
+    /// let id = REQUEST_ID
+    ///     .scope(7, async {
+    ///         task::Builder::new()
+    ///             .inherit(&REQUEST_ID)
+    ///             .spawn_blocking(|| REQUEST_ID.with(|id| *id))
+    ///             .await
+    ///     })
+    ///     .await;
+    /// assert_eq!(id, 7);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    #[track_caller]
+    pub fn spawn_blocking<F, T>(self, f: F) -> JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let parent = Task::get_current(|t| t.clone());
+        let (task, future) = self.build(async move { spawn_blocking::with_parent(parent, f) });
+        spawn_blocking::spawn_task(task, future)
+    }
+
     /// Spawns a task with the configured settings.
+    ///
+    /// If the task is configured as [`local`] and no [`LocalSet`] is running on the current
+    /// thread, an error wrapping a [`SpawnError`] is returned.
+    ///
+    /// [`local`]: #method.local
+    /// [`LocalSet`]: struct.LocalSet.html
+    /// [`SpawnError`]: struct.SpawnError.html
+    #[track_caller]
     pub fn spawn<F, T>(self, future: F) -> io::Result<JoinHandle<T>>
     where
         F: Future<Output = T> + Send + 'static,
         T: Send + 'static,
+    {
+        #[cfg(feature = "unstable")]
+        {
+            if self.local {
+                #[cfg(not(target_arch = "wasm32"))]
+                return crate::task::local_set::spawn_current(self, future)
+                    .ok_or_else(|| io::Error::other(SpawnError::no_local_set()));
+
+                // Local sets aren't available on `wasm32`.
+                #[cfg(target_arch = "wasm32")]
+                return Err(io::Error::other(SpawnError::no_local_set()));
+            }
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        return Ok(self.spawn_js(future));
+
+        #[cfg(not(target_arch = "wasm32"))]
+        Ok(self.spawn_on(executor::current(), future))
+    }
+
+    /// Spawns a task onto the JavaScript event loop.
+    #[cfg(target_arch = "wasm32")]
+    #[track_caller]
+    fn spawn_js<F, T>(self, future: F) -> JoinHandle<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (task, future) = self.build(future);
+
+        let schedule = |t| executor::schedule_js(Runnable::new(t));
+        let (task, handle) = async_task::spawn(future, schedule, task);
+        task.schedule();
+        JoinHandle::new(handle)
+    }
+
+    /// Spawns a task onto the given executor.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    #[track_caller]
+    pub(crate) fn spawn_on<F, T>(self, pool: Arc<Pool>, future: F) -> JoinHandle<T>
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (task, future) = self.build(future);
+
+        // Count the task until it completes or is dropped.
+        let registration = pool.register();
+        let accepted = registration.is_some();
+        let future = async move {
+            let _registration = registration;
+            future.await
+        };
+
+        let schedule = move |t| pool.schedule(Runnable::new(t));
+        let (task, handle) = async_task::spawn(future, schedule, task);
+
+        // If the executor doesn't accept new tasks, the task is cancelled right away.
+        if accepted {
+            task.schedule();
+        }
+        JoinHandle::new(handle)
+    }
+
+    /// Creates the handle of a new task and wraps its future with the configured settings.
+    #[track_caller]
+    pub(crate) fn build<F, T>(self, future: F) -> (Task, impl Future<Output = Result<T, JoinError>>)
+    where
+        F: Future<Output = T>,
     {
         // Create a new task handle.
-        let task = Task::new(self.name);
+        #[cfg(not(feature = "unstable"))]
+        let task = Task::new(self.name, self.priority);
+        #[cfg(feature = "unstable")]
+        let task = Task::with_location(self.name, self.priority, Location::caller());
+
+        // List the task in task dumps until it completes.
+        #[cfg(feature = "unstable")]
+        let registration = dump::Registration::new(&task);
 
         // Log this `spawn` operation.
         if log_enabled!(log::Level::Trace) {
             trace!("spawn", {
                 task_id: task.id().0,
+                task_name: task.name().unwrap_or(""),
                 parent_task_id: Task::get_current(|t| t.id().0).unwrap_or(0),
             });
         }
 
+        // Open the span of the task, which is entered whenever it is polled and closed once it
+        // completes.
+        #[cfg(feature = "tracing")]
+        let span = tracing::trace_span!(
+            "task",
+            task_id = task.id().0,
+            task_name = task.name().unwrap_or(""),
+            parent_task_id = Task::get_current(|t| t.id().0).unwrap_or(0),
+        );
+
+        let abort_on_panic = self.abort_on_panic;
+        let locals = self.locals;
         let future = async move {
+            #[cfg(feature = "unstable")]
+            let _registration = registration;
+
             // Drop task-locals on exit.
             defer! {
                 Task::get_current(|t| unsafe { t.drop_locals() });
             }
 
+            // Insert the preset task-locals.
+            Task::get_current(|t| {
+                for (key, value) in locals {
+                    t.locals().get_or_insert(key, || value);
+                }
+            });
+
             // Log completion on exit.
             defer! {
                 if log_enabled!(log::Level::Trace) {
@@ -61,24 +366,212 @@ impl Builder {
                 }
             }
 
-            future.await
+            TaskFuture {
+                future,
+                abort_on_panic,
+            }
+            .await
         };
 
-        let schedule = move |t| executor::schedule(Runnable(t));
-        let (task, handle) = async_task::spawn(future, schedule, task);
-        task.schedule();
-        Ok(JoinHandle::new(handle))
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument(future, span);
+
+        (task, future)
+    }
+}
+
+impl fmt::Debug for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("name", &self.name)
+            .field("abort_on_panic", &self.abort_on_panic)
+            .field("priority", &self.priority)
+            .field("local", &self.local)
+            .finish()
+    }
+}
+
+/// The scheduling priority hint of a task.
+///
+/// See [`Builder::priority`].
+///
+/// [`Builder::priority`]: struct.Builder.html#method.priority
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Priority {
+    /// The task may run after other tasks.
+    Low,
+    /// The default priority.
+    Normal,
+    /// The task should run before other tasks.
+    High,
+}
+
+impl Default for Priority {
+    fn default() -> Priority {
+        Priority::Normal
+    }
+}
+
+/// An error returned when a task couldn't be spawned.
+///
+/// This error is returned:
+///
+/// * by [`Builder::spawn`], wrapped in an [`io::Error`], when the task is configured as
+///   [`local`] but no [`LocalSet`] is running on the current thread.
+/// * by [`try_spawn_blocking`] when the queue of the blocking thread pool is full.
+///
+/// [`Builder::spawn`]: struct.Builder.html#method.spawn
+/// [`io::Error`]: ../io/struct.Error.html
+/// [`local`]: struct.Builder.html#method.local
+/// [`LocalSet`]: struct.LocalSet.html
+/// [`try_spawn_blocking`]: fn.try_spawn_blocking.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct SpawnError {
+    kind: SpawnErrorKind,
+}
+
+#[cfg(feature = "unstable")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum SpawnErrorKind {
+    NoLocalSet,
+    QueueFull,
+}
+
+#[cfg(feature = "unstable")]
+impl SpawnError {
+    fn no_local_set() -> SpawnError {
+        SpawnError {
+            kind: SpawnErrorKind::NoLocalSet,
+        }
+    }
+
+    pub(crate) fn queue_full() -> SpawnError {
+        SpawnError {
+            kind: SpawnErrorKind::QueueFull,
+        }
+    }
+
+    /// Returns `true` if the error was caused by a full blocking queue.
+    pub fn is_queue_full(&self) -> bool {
+        self.kind == SpawnErrorKind::QueueFull
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl fmt::Debug for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpawnError").field("kind", &self.kind).finish()
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            SpawnErrorKind::NoLocalSet => "no `LocalSet` is running on the current thread".fmt(f),
+            SpawnErrorKind::QueueFull => "the queue of the blocking thread pool is full".fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl Error for SpawnError {}
+
+pin_project! {
+    /// The future of a spawned task.
+    ///
+    /// Completes with an error once the task has been aborted or if the future panics.
+    struct TaskFuture<F> {
+        #[pin]
+        future: F,
+        abort_on_panic: bool,
+    }
+}
+
+impl<F: Future> Future for TaskFuture<F> {
+    type Output = Result<F::Output, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if Task::get_current(|t| t.is_aborted()).unwrap_or(false) {
+            return Poll::Ready(Err(JoinError::cancelled()));
+        }
+
+        #[cfg(feature = "unstable")]
+        Task::get_current(|t| t.set_state(None, TaskState::Running));
+
+        // Let the task use tokio's reactor and timers.
+        #[cfg(feature = "tokio-runtime")]
+        let _tokio = crate::runtime::tokio::enter();
+
+        // The span of the task is entered at this point, so this one is nested in it.
+        #[cfg(feature = "tracing")]
+        let span = tracing::trace_span!("poll", ready = tracing::field::Empty);
+        #[cfg(feature = "tracing")]
+        let _enter = span.enter();
+
+        let this = self.project();
+        let poll = if *this.abort_on_panic {
+            this.future.poll(cx).map(Ok)
+        } else {
+            let future = this.future;
+            match panic::catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+                Ok(poll) => poll.map(Ok),
+                Err(payload) => Poll::Ready(Err(JoinError::panic(payload))),
+            }
+        };
+
+        #[cfg(feature = "tracing")]
+        span.record("ready", poll.is_ready());
+
+        // Unless the task has been woken up in the meantime, it is now idle.
+        #[cfg(feature = "unstable")]
+        Task::get_current(|t| t.set_state(Some(TaskState::Running), TaskState::Idle));
+
+        poll
     }
 }
 
 /// A runnable task.
-pub(crate) struct Runnable(async_task::Task<Task>);
+pub(crate) struct Runnable(pub(crate) async_task::Task<Task>);
 
 impl Runnable {
+    /// Wraps a task that has been scheduled.
+    pub(crate) fn new(task: async_task::Task<Task>) -> Runnable {
+        #[cfg(feature = "unstable")]
+        {
+            task.tag().set_state(None, TaskState::Scheduled);
+            COUNTERS.scheduled_tasks.fetch_add(1, Ordering::Relaxed);
+        }
+        Runnable(task)
+    }
+
     /// Runs the task by polling its future once.
     pub fn run(self) {
+        #[cfg(feature = "unstable")]
+        COUNTERS.scheduled_tasks.fetch_sub(1, Ordering::Relaxed);
+
+        // The standard clock isn't available on `wasm32`, so polls aren't timed there.
+        #[cfg(all(feature = "unstable", not(target_arch = "wasm32")))]
+        let start = Instant::now();
+
         unsafe {
-            Task::set_current(self.0.tag(), || abort_on_panic(|| self.0.run()));
+            Task::set_current(self.0.tag(), || {
+                abort_on_panic(|| budget::with_budget(|| time::with_cached_now(|| self.0.run())))
+            });
         }
+
+        #[cfg(all(feature = "unstable", not(target_arch = "wasm32")))]
+        COUNTERS.record_poll(start.elapsed());
+    }
+
+    /// Drops the task without running it.
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    pub(crate) fn discard(self) {
+        COUNTERS.scheduled_tasks.fetch_sub(1, Ordering::Relaxed);
     }
 }
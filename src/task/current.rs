@@ -26,3 +26,46 @@ pub fn current() -> Task {
     Task::get_current(|t| t.clone())
         .expect("`task::current()` called outside the context of a task")
 }
+
+/// Returns `true` if the current task has been asked to stop.
+///
+/// This is the case once the task has been aborted or cancelled through its [`JoinHandle`] or an
+/// [`AbortHandle`]. Async tasks stop at their next yield point on their own, but blocking tasks
+/// can't be interrupted, so long computations in [`spawn_blocking`] can check this function to
+/// stop early. Blocking tasks spawned with [`Builder::spawn_blocking`] are also asked to stop
+/// when the task that spawned them is aborted.
+///
+/// Outside the context of a task, this returns `false`.
+///
+/// [`JoinHandle`]: struct.JoinHandle.html
+/// [`AbortHandle`]: struct.AbortHandle.html
+/// [`spawn_blocking`]: fn.spawn_blocking.html
+/// [`Builder::spawn_blocking`]: struct.Builder.html#method.spawn_blocking
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::task;
+///
+/// let handle = task::spawn_blocking(|| {
+///     while !task::is_cancelled() {
+///         // Do a slice of a long computation.
+///         std::thread::sleep(Duration::from_millis(1));
+///     }
+///     "stopped"
+/// });
+///
+/// assert_eq!(handle.cancel().await, Some("stopped"));
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn is_cancelled() -> bool {
+    Task::get_current(|t| t.is_aborted()).unwrap_or(false)
+        || crate::task::spawn_blocking::parent_is_aborted()
+}
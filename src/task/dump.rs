@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::panic::Location;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::runtime::metrics::COUNTERS;
+use crate::task::{Task, TaskId};
+
+/// Spawned tasks that haven't completed yet.
+static TASKS: Lazy<Mutex<HashMap<TaskId, Task>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Returns a snapshot of all spawned tasks that haven't completed yet.
+///
+/// This includes tasks spawned with [`spawn`], [`spawn_local`], and [`Builder::spawn`], but not
+/// futures run by [`block_on`] or closures run by [`spawn_blocking`].
+///
+/// The snapshot is not atomic: tasks may change their state while it is being taken. This is
+/// meant for diagnosing hangs, for example by logging the dump when a request takes too long.
+///
+/// [`spawn`]: fn.spawn.html
+/// [`spawn_local`]: fn.spawn_local.html
+/// [`Builder::spawn`]: struct.Builder.html#method.spawn
+/// [`block_on`]: fn.block_on.html
+/// [`spawn_blocking`]: fn.spawn_blocking.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::task;
+///
+/// let handle = task::Builder::new()
+///     .name("sleeper".to_string())
+///     .spawn(task::sleep(Duration::from_secs(1)))
+///     .unwrap();
+///
+/// for info in task::dump() {
+///     println!("{} {:?} at {}: {:?}", info.id(), info.name(), info.location(), info.state());
+/// }
+/// # drop(handle);
+/// #
+/// # })
+/// ```
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn dump() -> Vec<TaskInfo> {
+    let tasks = TASKS.lock().unwrap();
+    tasks
+        .values()
+        .map(|task| TaskInfo {
+            task: task.clone(),
+            state: task.state(),
+        })
+        .collect()
+}
+
+/// Information about a task in a [`dump`].
+///
+/// [`dump`]: fn.dump.html
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct TaskInfo {
+    task: Task,
+    state: TaskState,
+}
+
+impl TaskInfo {
+    /// Returns the task's unique identifier.
+    pub fn id(&self) -> TaskId {
+        self.task.id()
+    }
+
+    /// Returns the name of the task.
+    pub fn name(&self) -> Option<&str> {
+        self.task.name()
+    }
+
+    /// Returns the location in the source code the task was spawned at.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.task.location().unwrap()
+    }
+
+    /// Returns the scheduling state of the task at the time of the dump.
+    pub fn state(&self) -> TaskState {
+        self.state
+    }
+
+    /// Returns a handle to the task.
+    pub fn task(&self) -> &Task {
+        &self.task
+    }
+}
+
+impl fmt::Debug for TaskInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TaskInfo")
+            .field("id", &self.id())
+            .field("name", &self.name())
+            .field("location", &self.location())
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+/// The scheduling state of a task.
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum TaskState {
+    /// The task is waiting to be woken up.
+    Idle,
+    /// The task has been woken up and is waiting to be polled.
+    Scheduled,
+    /// The task is being polled.
+    Running,
+}
+
+impl TaskState {
+    pub(crate) fn from_u8(state: u8) -> TaskState {
+        match state {
+            0 => TaskState::Idle,
+            1 => TaskState::Scheduled,
+            _ => TaskState::Running,
+        }
+    }
+}
+
+/// Keeps a task in the list of dumped tasks until dropped.
+pub(crate) struct Registration(TaskId);
+
+impl Registration {
+    /// Adds a task to the list of dumped tasks.
+    pub(crate) fn new(task: &Task) -> Registration {
+        let id = task.id();
+        TASKS.lock().unwrap().insert(id, task.clone());
+        COUNTERS.alive_tasks.fetch_add(1, Ordering::Relaxed);
+        Registration(id)
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        TASKS.lock().unwrap().remove(&self.0);
+        COUNTERS.alive_tasks.fetch_sub(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+#[cfg(feature = "unstable")]
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+
+#[cfg(feature = "unstable")]
+use crate::runtime::metrics::COUNTERS;
+use crate::task::Task;
+use crate::utils::abort_on_panic;
+
+/// A blocking operation waiting to run.
+pub(crate) type Job = async_task::Task<Task>;
+
+/// The settings of a blocking thread pool.
+#[derive(Clone, Debug)]
+pub(crate) struct BlockingConfig {
+    /// The maximum number of threads.
+    pub(crate) max_threads: usize,
+
+    /// How long an idle thread waits for a new job before it stops.
+    pub(crate) keep_alive: Duration,
+
+    /// The maximum number of jobs waiting for a thread, if any.
+    pub(crate) queue_limit: Option<usize>,
+
+    /// The name of the threads.
+    pub(crate) thread_name: String,
+}
+
+impl Default for BlockingConfig {
+    fn default() -> BlockingConfig {
+        BlockingConfig {
+            max_threads: 512,
+            keep_alive: Duration::from_secs(1),
+            queue_limit: None,
+            thread_name: "async-std/blocking".to_string(),
+        }
+    }
+}
+
+/// A pool of threads running blocking operations.
+///
+/// Threads are started on demand up to the configured maximum, and stop after they have been idle
+/// for the keep-alive duration.
+pub(crate) struct BlockingPool {
+    config: BlockingConfig,
+    state: Mutex<State>,
+
+    /// Notified when a job is pushed into the queue.
+    available: Condvar,
+}
+
+struct State {
+    /// Jobs waiting for a thread.
+    queue: VecDeque<Job>,
+
+    /// The number of running threads.
+    threads: usize,
+
+    /// The number of threads waiting for a job.
+    idle: usize,
+}
+
+/// Blocking thread pool used outside of custom runtimes.
+static BLOCKING: Lazy<Arc<BlockingPool>> =
+    Lazy::new(|| BlockingPool::new(BlockingConfig::default()));
+
+/// Returns the global blocking thread pool.
+pub(crate) fn global_blocking() -> Arc<BlockingPool> {
+    BLOCKING.clone()
+}
+
+/// Named blocking thread pools used outside of custom runtimes.
+static NAMED: Lazy<Arc<NamedPools>> =
+    Lazy::new(|| NamedPools::new(BlockingConfig::default(), HashMap::new()));
+
+/// Returns the global named blocking thread pools.
+pub(crate) fn global_named() -> Arc<NamedPools> {
+    NAMED.clone()
+}
+
+/// Blocking thread pools isolated from each other by name.
+///
+/// Each pool is created on first use. Pools share the settings of the executor's blocking pool,
+/// except for their own thread limits.
+#[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+pub(crate) struct NamedPools {
+    /// The settings pools are created with.
+    config: BlockingConfig,
+
+    /// The maximum number of threads of each pool that has its own limit.
+    limits: HashMap<String, usize>,
+
+    /// The pools that have been used so far.
+    pools: Mutex<HashMap<String, Arc<BlockingPool>>>,
+}
+
+impl NamedPools {
+    /// Creates a set of named pools without any pools.
+    pub(crate) fn new(config: BlockingConfig, limits: HashMap<String, usize>) -> Arc<NamedPools> {
+        Arc::new(NamedPools {
+            config,
+            limits,
+            pools: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Returns the pool with the given name, creating it if it hasn't been used yet.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn get(&self, name: &str) -> Arc<BlockingPool> {
+        let mut pools = self.pools.lock().unwrap();
+        if let Some(pool) = pools.get(name) {
+            return pool.clone();
+        }
+
+        let mut config = self.config.clone();
+        config.thread_name = format!("{}/{}", config.thread_name, name);
+        if let Some(&max_threads) = self.limits.get(name) {
+            config.max_threads = max_threads;
+        }
+        let pool = BlockingPool::new(config);
+        pools.insert(name.to_string(), pool.clone());
+        pool
+    }
+
+    /// Returns `true` if no job is running or waiting for a thread in any of the pools.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn is_idle(&self) -> bool {
+        let pools = self.pools.lock().unwrap();
+        pools.values().all(|pool| pool.is_idle())
+    }
+}
+
+impl fmt::Debug for NamedPools {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NamedPools")
+            .field("limits", &self.limits)
+            .finish()
+    }
+}
+
+impl BlockingPool {
+    /// Creates a blocking thread pool without any threads.
+    pub(crate) fn new(config: BlockingConfig) -> Arc<BlockingPool> {
+        Arc::new(BlockingPool {
+            config,
+            state: Mutex::new(State {
+                queue: VecDeque::new(),
+                threads: 0,
+                idle: 0,
+            }),
+            available: Condvar::new(),
+        })
+    }
+
+    /// Pushes a job into the queue, starting a new thread for it if no thread is idle.
+    ///
+    /// If `bounded` is set and the queue is full, the job is handed back.
+    pub(crate) fn push(self: &Arc<BlockingPool>, job: Job, bounded: bool) -> Result<(), Job> {
+        let mut state = self.state.lock().unwrap();
+        if bounded {
+            if let Some(limit) = self.config.queue_limit {
+                if state.queue.len() >= limit {
+                    return Err(job);
+                }
+            }
+        }
+
+        state.queue.push_back(job);
+        #[cfg(feature = "unstable")]
+        COUNTERS.blocking_queue.fetch_add(1, Ordering::Relaxed);
+
+        if state.queue.len() <= state.idle {
+            self.available.notify_one();
+        } else if state.threads < self.config.max_threads {
+            state.threads += 1;
+            let pool = self.clone();
+            thread::Builder::new()
+                .name(self.config.thread_name.clone())
+                .spawn(move || pool.main_loop())
+                .expect("cannot start a blocking thread");
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if no job is running or waiting for a thread.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn is_idle(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.queue.is_empty() && state.idle == state.threads
+    }
+
+    /// Runs jobs until the thread has been idle for the keep-alive duration.
+    fn main_loop(&self) {
+        #[cfg(feature = "unstable")]
+        COUNTERS.blocking_threads.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "unstable")]
+        defer! {
+            COUNTERS.blocking_threads.fetch_sub(1, Ordering::Relaxed);
+        }
+
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                drop(state);
+                #[cfg(feature = "unstable")]
+                COUNTERS.blocking_queue.fetch_sub(1, Ordering::Relaxed);
+
+                let task: *const Task = job.tag();
+                abort_on_panic(|| unsafe { Task::set_current(task, || job.run()) });
+
+                state = self.state.lock().unwrap();
+                continue;
+            }
+
+            state.idle += 1;
+            let (s, res) = self
+                .available
+                .wait_timeout(state, self.config.keep_alive)
+                .unwrap();
+            state = s;
+            state.idle -= 1;
+
+            if res.timed_out() && state.queue.is_empty() {
+                break;
+            }
+        }
+        state.threads -= 1;
+    }
+}
+
+impl fmt::Debug for BlockingPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockingPool")
+            .field("config", &self.config)
+            .finish()
+    }
+}
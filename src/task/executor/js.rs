@@ -0,0 +1,9 @@
+use crate::task::Runnable;
+
+/// Schedules a task to run on the JavaScript event loop.
+///
+/// On `wasm32` there are no threads for an executor to run on, so every time a task is woken up,
+/// it is polled once from a microtask queued with `wasm_bindgen_futures::spawn_local`.
+pub(crate) fn schedule_js(task: Runnable) {
+    wasm_bindgen_futures::spawn_local(async move { task.run() });
+}
@@ -2,12 +2,34 @@
 //!
 //! API bindings between `crate::task` and this module are very simple:
 //!
-//! * The only export is the `schedule` function.
-//! * The only import is the `crate::task::Runnable` type.
+//! * The exports are the `Pool`, `BlockingPool`, and `NamedPools` types with their settings, the
+//!   `Park` trait implemented by drivers of current-thread executors, and the `current`, `blocking`, `named_blocking`, `clock`, `is_idle`, `is_worker_thread`, `enter`,
+//!   `block_in_place`, `yield_current`, and `start_global` functions, and the `schedule_js`
+//!   function on `wasm32`.
+//! * The only imports are the `crate::task::Runnable`, `crate::task::Task`, and
+//!   `crate::task::TaskId` types, and the `crate::time::Clock` type.
 
-pub(crate) use pool::schedule;
+// Tasks are spawned onto the JavaScript event loop on `wasm32`, which leaves the thread pools
+// unused unless a runtime is built explicitly.
+#![cfg_attr(target_arch = "wasm32", allow(dead_code, unused_imports))]
+
+pub(crate) use blocking::{
+    global_blocking, global_named, BlockingConfig, BlockingPool, Job, NamedPools,
+};
+pub(crate) use pool::{blocking, clock, current, is_worker_thread, yield_current, Pool};
+#[cfg(feature = "unstable")]
+pub(crate) use pool::{
+    block_in_place, enter, global, is_idle, named_blocking, start_global, Config, Park,
+};
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub(crate) use pool::io_uring;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use js::schedule_js;
 
 use sleepers::Sleepers;
 
+mod blocking;
+#[cfg(target_arch = "wasm32")]
+mod js;
 mod pool;
 mod sleepers;
@@ -1,18 +1,151 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "unstable")]
+use std::future::Future;
+use std::io;
 use std::iter;
+#[cfg(feature = "unstable")]
+use std::pin::Pin;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "unstable")]
+use std::mem;
+use std::sync::{Arc, Condvar, Mutex};
+#[cfg(feature = "unstable")]
+use std::task::Wake;
 use std::thread;
 use std::time::Duration;
+#[cfg(feature = "unstable")]
+use std::time::Instant;
 
-use crossbeam_deque::{Injector, Stealer, Worker};
+use crossbeam_deque::{Injector, Steal, Stealer, Worker};
 use once_cell::sync::Lazy;
-use once_cell::unsync::OnceCell;
+#[cfg(feature = "unstable")]
+use pin_project_lite::pin_project;
 
-use crate::task::executor::Sleepers;
-use crate::task::Runnable;
+#[cfg(feature = "unstable")]
+use crate::runtime::affinity::{self, Affinity};
+#[cfg(feature = "unstable")]
+use crate::runtime::metrics::COUNTERS;
+use crate::task::executor::{
+    global_blocking, global_named, BlockingConfig, BlockingPool, NamedPools, Sleepers,
+};
+use crate::task::{Runnable, Task, TaskId, Waker};
+use crate::time::{global_clock, Clock};
+#[cfg(feature = "unstable")]
+use crate::task::{budget, Context, Poll};
+#[cfg(feature = "unstable")]
+use crate::time;
 use crate::utils::{abort_on_panic, random};
 
+/// A hook called by worker threads.
+pub(crate) type Hook = Arc<dyn Fn() + Send + Sync>;
+
+/// Drives I/O and timers on the thread running the tasks of an executor without worker threads.
+#[cfg(feature = "unstable")]
+pub(crate) trait Park: Send + Sync {
+    /// Runs a closure during which I/O handles and timers created on the current thread are
+    /// driven by this driver.
+    fn enter(&self, f: &mut dyn FnMut());
+
+    /// Blocks until an I/O event comes in, a timer fires, or [`unpark`] is called.
+    ///
+    /// [`unpark`]: #tymethod.unpark
+    fn park(&self);
+
+    /// Wakes up the thread blocked in [`park`], or makes its next call return right away.
+    ///
+    /// [`park`]: #tymethod.park
+    fn unpark(&self);
+}
+
+/// The settings of an executor.
+#[derive(Clone)]
+pub(crate) struct Config {
+    /// The number of worker threads.
+    pub(crate) num_threads: usize,
+
+    /// The name of worker threads.
+    pub(crate) thread_name: String,
+
+    /// The stack size of worker threads.
+    pub(crate) stack_size: Option<usize>,
+
+    /// Called on every worker thread when it starts.
+    pub(crate) on_thread_start: Option<Hook>,
+
+    /// Called on every worker thread when it stops.
+    pub(crate) on_thread_stop: Option<Hook>,
+
+    /// Whether idle worker threads steal tasks from the local queues of other worker threads.
+    pub(crate) work_stealing: bool,
+
+    /// Whether a task woken by a worker thread is run next by that thread.
+    pub(crate) lifo_slot: bool,
+
+    /// Whether all tasks go through the global queue and run in the order they were scheduled.
+    pub(crate) fifo: bool,
+
+    /// The settings of the blocking thread pool.
+    pub(crate) blocking: BlockingConfig,
+
+    /// The maximum number of threads of named blocking thread pools with their own limit.
+    pub(crate) blocking_limits: HashMap<String, usize>,
+
+    /// Whether sockets created inside the executor use io_uring.
+    #[cfg(feature = "io-uring")]
+    pub(crate) io_uring: bool,
+
+    /// The policy for pinning worker threads to CPU cores.
+    #[cfg(feature = "unstable")]
+    pub(crate) affinity: Option<Affinity>,
+
+    /// Drives I/O and timers on the thread running the tasks if there are no worker threads.
+    #[cfg(feature = "unstable")]
+    pub(crate) park: Option<Arc<dyn Park>>,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            num_threads: num_cpus::get().max(1),
+            thread_name: "async-std/executor".to_string(),
+            stack_size: None,
+            on_thread_start: None,
+            on_thread_stop: None,
+            work_stealing: true,
+            lifo_slot: true,
+            fifo: false,
+            blocking: BlockingConfig::default(),
+            blocking_limits: HashMap::new(),
+            #[cfg(feature = "io-uring")]
+            io_uring: false,
+            #[cfg(feature = "unstable")]
+            affinity: None,
+            #[cfg(feature = "unstable")]
+            park: None,
+        }
+    }
+}
+
+impl fmt::Debug for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Config")
+            .field("num_threads", &self.num_threads)
+            .field("thread_name", &self.thread_name)
+            .field("stack_size", &self.stack_size)
+            .field("work_stealing", &self.work_stealing)
+            .field("lifo_slot", &self.lifo_slot)
+            .field("fifo", &self.fifo)
+            .field("blocking", &self.blocking)
+            .field("blocking_limits", &self.blocking_limits)
+            .finish()
+    }
+}
+
 /// The state of an executor.
-struct Pool {
+pub(crate) struct Pool {
     /// The global queue of tasks.
     injector: Injector<Runnable>,
 
@@ -21,42 +154,380 @@ struct Pool {
 
     /// Used for putting idle workers to sleep and notifying them when new tasks come in.
     sleepers: Sleepers,
+
+    /// Set when the executor stops accepting new tasks.
+    closed: AtomicBool,
+
+    /// Set when the executor has been shut down.
+    shutdown: AtomicBool,
+
+    /// The number of spawned tasks that haven't completed or been dropped.
+    tasks: Mutex<usize>,
+
+    /// Notified when the last task completes.
+    no_tasks: Condvar,
+
+    /// The settings the executor was created with.
+    config: Config,
+
+    /// Handles to the worker threads.
+    threads: Mutex<Vec<thread::JoinHandle<()>>>,
+
+    /// Wakes the thread driving tasks if the executor has no worker threads.
+    driver: Mutex<Option<Waker>>,
+
+    /// Runs blocking operations started from the executor.
+    blocking: Arc<BlockingPool>,
+
+    /// Runs blocking operations started from the executor on a named pool.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    named: Arc<NamedPools>,
+
+    /// The clock timers created inside the executor run on.
+    clock: Arc<Clock>,
 }
 
 /// Global executor that runs spawned tasks.
-static POOL: Lazy<Pool> = Lazy::new(|| {
-    let num_threads = num_cpus::get().max(1);
-    let mut stealers = Vec::new();
-
-    // Spawn worker threads.
-    for _ in 0..num_threads {
-        let worker = Worker::new_fifo();
-        stealers.push(worker.stealer());
-
-        let proc = Processor {
-            worker,
-            slot: Cell::new(None),
-            slot_runs: Cell::new(0),
+static POOL: Lazy<Arc<Pool>> = Lazy::new(|| {
+    Pool::with_shared(
+        Config::default(),
+        global_blocking(),
+        global_named(),
+        global_clock(),
+    )
+        .expect("cannot start a thread driving tasks")
+});
+
+impl Pool {
+    /// Creates an executor and starts its worker threads.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn new(config: Config) -> io::Result<Arc<Pool>> {
+        let blocking = BlockingPool::new(config.blocking.clone());
+        let named = NamedPools::new(config.blocking.clone(), config.blocking_limits.clone());
+        Pool::with_shared(config, blocking, named, Arc::new(Clock::new()))
+    }
+
+    /// Creates an executor running blocking operations on the given pools and timers on the given
+    /// clock.
+    fn with_shared(
+        config: Config,
+        blocking: Arc<BlockingPool>,
+        named: Arc<NamedPools>,
+        clock: Arc<Clock>,
+    ) -> io::Result<Arc<Pool>> {
+        let workers: Vec<Worker<Runnable>> =
+            (0..config.num_threads).map(|_| Worker::new_fifo()).collect();
+
+        let pool = Arc::new(Pool {
+            injector: Injector::new(),
+            stealers: workers.iter().map(|w| w.stealer()).collect(),
+            sleepers: Sleepers::new(),
+            closed: AtomicBool::new(false),
+            shutdown: AtomicBool::new(false),
+            tasks: Mutex::new(0),
+            no_tasks: Condvar::new(),
+            config,
+            threads: Mutex::new(Vec::new()),
+            driver: Mutex::new(None),
+            blocking,
+            named,
+            clock,
+        });
+
+        // Spawn worker threads.
+        for (index, worker) in workers.into_iter().enumerate() {
+            let proc = Processor {
+                pool: pool.clone(),
+                index,
+                worker,
+                slot: Cell::new(None),
+                slot_runs: Cell::new(0),
+                yielded: Cell::new(None),
+            };
+            if let Err(err) = start_worker(proc) {
+                pool.shutdown();
+                return Err(err);
+            }
+        }
+
+        Ok(pool)
+    }
+
+    /// Schedules a new runnable task for execution.
+    pub(crate) fn schedule(&self, task: Runnable) {
+        // Tasks woken up after shutdown are dropped.
+        if self.shutdown.load(Ordering::SeqCst) {
+            drop(task);
+            return;
+        }
+
+        PROCESSOR.with(|proc| {
+            // If the current thread is a worker thread of this executor, store it into its task
+            // slot or push it into its local task queue. Otherwise, or if tasks must run in the
+            // order they were scheduled, push it into the global task queue.
+            match proc.borrow().as_ref() {
+                Some(proc) if ptr::eq(&*proc.pool, self) && !self.config.fifo => {
+                    // A task that yielded goes behind the other tasks in the local queue.
+                    let yielded = proc.yielded.get() == Some(task.0.tag().id());
+                    if yielded {
+                        proc.yielded.set(None);
+                    }
+
+                    if !self.config.lifo_slot || yielded {
+                        proc.worker.push(task);
+                        self.sleepers.notify_one();
+                    } else if let Some(task) = proc.slot.replace(Some(task)) {
+                        // If the slot already contained a task, push it into the local task queue.
+                        proc.worker.push(task);
+                        self.sleepers.notify_one();
+                    }
+                }
+                _ => {
+                    self.injector.push(task);
+                    self.sleepers.notify_one();
+
+                    if self.stealers.is_empty() {
+                        if let Some(w) = self.driver.lock().unwrap().as_ref() {
+                            w.wake_by_ref();
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Registers a new task.
+    ///
+    /// Returns `None` if the executor doesn't accept new tasks anymore. Otherwise, the task is
+    /// counted until the returned guard is dropped.
+    pub(crate) fn register(self: &Arc<Pool>) -> Option<Registration> {
+        let mut tasks = self.tasks.lock().unwrap();
+        if self.closed.load(Ordering::SeqCst) {
+            return None;
+        }
+        *tasks += 1;
+        Some(Registration(self.clone()))
+    }
+
+    /// Returns `true` if the executor doesn't accept new tasks anymore.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Stops the worker threads and drops the tasks waiting to run.
+    ///
+    /// Worker threads stop once the tasks they are currently running yield. Tasks woken up
+    /// afterwards are dropped.
+    pub(crate) fn shutdown(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.shutdown.store(true, Ordering::SeqCst);
+        self.sleepers.close();
+        self.drain();
+    }
+
+    /// Stops accepting new tasks, waits for the remaining tasks to complete, and then shuts down.
+    ///
+    /// Waits for the worker threads to stop until the deadline.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn shutdown_timeout(&self, dur: Duration) {
+        let deadline = Instant::now() + dur;
+
+        // Wait for the remaining tasks.
+        {
+            let mut tasks = self.tasks.lock().unwrap();
+            self.closed.store(true, Ordering::SeqCst);
+            while *tasks > 0 {
+                let now = Instant::now();
+                if now >= deadline {
+                    break;
+                }
+                tasks = self.no_tasks.wait_timeout(tasks, deadline - now).unwrap().0;
+            }
+        }
+
+        self.shutdown();
+
+        // Wait for the worker threads to stop.
+        let current = thread::current().id();
+        let threads = mem::take(&mut *self.threads.lock().unwrap());
+        for handle in threads {
+            if handle.thread().id() == current {
+                continue;
+            }
+            while !handle.is_finished() && Instant::now() < deadline {
+                thread::sleep(Duration::from_millis(1));
+            }
+            if handle.is_finished() {
+                let _ = handle.join();
+            }
+        }
+    }
+
+    /// Returns `true` if the executor has no worker threads and its tasks are run by
+    /// [`run_until`] instead.
+    ///
+    /// [`run_until`]: #method.run_until
+    #[cfg(feature = "unstable")]
+    pub(crate) fn is_current_thread(&self) -> bool {
+        self.stealers.is_empty()
+    }
+
+    /// Returns `true` if no task of the executor is running or waiting to run, and no blocking
+    /// operation is in progress.
+    #[cfg(feature = "unstable")]
+    fn is_idle(&self) -> bool {
+        self.injector.is_empty()
+            && self.sleepers.sleeping() >= self.stealers.len()
+            && self.blocking.is_idle()
+            && self.named.is_idle()
+    }
+
+    /// Runs a future to completion, running the tasks of the executor in the meantime.
+    ///
+    /// If the executor has a driver, the current thread blocks in it when there is nothing to
+    /// run.
+    #[cfg(feature = "unstable")]
+    pub(crate) async fn run_until<F: Future>(&self, future: F) -> F::Output {
+        RunUntil {
+            pool: self,
+            future,
+            unpark: None,
+        }
+        .await
+    }
+
+    /// Drops all tasks in the global queue.
+    fn drain(&self) {
+        loop {
+            match self.injector.steal() {
+                Steal::Success(task) => drop(task),
+                Steal::Retry => {}
+                Steal::Empty => break,
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Pool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool").field("config", &self.config).finish()
+    }
+}
+
+/// Counts a task as spawned onto an executor until dropped.
+pub(crate) struct Registration(Arc<Pool>);
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let mut tasks = self.0.tasks.lock().unwrap();
+        *tasks -= 1;
+        if *tasks == 0 {
+            self.0.no_tasks.notify_all();
+        }
+    }
+}
+
+#[cfg(feature = "unstable")]
+pin_project! {
+    /// A future that runs the tasks of an executor while polling another future.
+    struct RunUntil<'a, F> {
+        pool: &'a Pool,
+        #[pin]
+        future: F,
+        // Wakes up the driver of the executor, once it has been needed.
+        unpark: Option<Waker>,
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<F: Future> Future for RunUntil<'_, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        /// The maximum number of tasks run in a row before polling the future again.
+        const BUDGET: usize = 64;
+
+        let this = self.project();
+        let pool = *this.pool;
+        let mut future = this.future;
+
+        // If the executor has a driver, the current thread blocks in it rather than returning, so
+        // it has to be woken up through the driver.
+        let park = pool.config.park.as_ref();
+        let waker = match park {
+            Some(park) => &*this
+                .unpark
+                .get_or_insert_with(|| Waker::from(Arc::new(Unpark(park.clone())))),
+            None => cx.waker(),
         };
+        let cx = &mut Context::from_waker(waker);
 
-        thread::Builder::new()
-            .name("async-std/executor".to_string())
-            .spawn(|| {
-                let _ = PROCESSOR.with(|p| p.set(proc));
-                abort_on_panic(main_loop);
-            })
-            .expect("cannot start a thread driving tasks");
+        // Make sure tasks scheduled from now on wake the current thread.
+        {
+            let mut driver = pool.driver.lock().unwrap();
+            match driver.as_ref() {
+                Some(w) if w.will_wake(waker) => {}
+                _ => *driver = Some(waker.clone()),
+            }
+        }
+
+        loop {
+            let poll =
+                budget::with_budget(|| time::with_cached_now(|| future.as_mut().poll(cx)));
+            if let Poll::Ready(val) = poll {
+                return Poll::Ready(val);
+            }
+
+            let mut ran = 0;
+            while ran < BUDGET {
+                match pool.injector.steal() {
+                    Steal::Success(task) => task.run(),
+                    Steal::Retry => continue,
+                    Steal::Empty => break,
+                }
+                ran += 1;
+            }
+
+            if ran == 0 {
+                match park {
+                    // Wait for I/O events and timers, unless a paused clock has been advanced.
+                    Some(park) => {
+                        if !time::park(waker) {
+                            park.park();
+                        }
+                    }
+                    None => return Poll::Pending,
+                }
+            }
+        }
     }
+}
 
-    Pool {
-        injector: Injector::new(),
-        stealers,
-        sleepers: Sleepers::new(),
+/// Wakes up the driver of an executor without worker threads.
+#[cfg(feature = "unstable")]
+struct Unpark(Arc<dyn Park>);
+
+#[cfg(feature = "unstable")]
+impl Wake for Unpark {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
     }
-});
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
 
 /// The state of a worker thread.
 struct Processor {
+    /// The executor this worker thread belongs to.
+    pool: Arc<Pool>,
+
+    /// The index of the worker thread in the executor.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    index: usize,
+
     /// The local task queue.
     worker: Worker<Runnable>,
 
@@ -65,35 +536,215 @@ struct Processor {
 
     /// How many times in a row tasks have been taked from the slot rather than the queue.
     slot_runs: Cell<u32>,
+
+    /// The task that yielded during its last poll, which must not be put into the slot.
+    yielded: Cell<Option<TaskId>>,
 }
 
 thread_local! {
     /// Worker thread state.
-    static PROCESSOR: OnceCell<Processor> = OnceCell::new();
+    ///
+    /// This is `None` if the current thread is not a worker thread or has handed its processor
+    /// over to another thread.
+    static PROCESSOR: RefCell<Option<Processor>> = const { RefCell::new(None) };
+
+    /// The executor entered on this thread, which tasks are spawned onto.
+    static ENTERED: RefCell<Option<Arc<Pool>>> = const { RefCell::new(None) };
 }
 
-/// Schedules a new runnable task for execution.
-pub(crate) fn schedule(task: Runnable) {
-    PROCESSOR.with(|proc| {
-        // If the current thread is a worker thread, store it into its task slot or push it into
-        // its local task queue. Otherwise, push it into the global task queue.
-        match proc.get() {
-            Some(proc) => {
-                // Replace the task in the slot.
-                if let Some(task) = proc.slot.replace(Some(task)) {
-                    // If the slot already contained a task, push it into the local task queue.
-                    proc.worker.push(task);
-                    POOL.sleepers.notify_one();
-                }
+/// Returns the global executor if it has been started.
+#[cfg(feature = "unstable")]
+pub(crate) fn global() -> Option<&'static Arc<Pool>> {
+    Lazy::get(&POOL)
+}
+
+/// Returns the global executor, starting it if necessary.
+#[cfg(feature = "unstable")]
+pub(crate) fn start_global() -> Arc<Pool> {
+    POOL.clone()
+}
+
+/// Returns the executor tasks spawned from the current thread are spawned onto.
+///
+/// This is the executor entered with [`enter`], or otherwise the executor the current thread is a
+/// worker thread of, or otherwise the global executor.
+pub(crate) fn current() -> Arc<Pool> {
+    local().unwrap_or_else(|| POOL.clone())
+}
+
+/// Returns the blocking thread pool blocking operations started from the current thread run on.
+///
+/// This is the blocking pool of the executor [`current`] would return, except that the global
+/// blocking pool is used without starting the global executor.
+pub(crate) fn blocking() -> Arc<BlockingPool> {
+    match local() {
+        Some(pool) => pool.blocking.clone(),
+        None => global_blocking(),
+    }
+}
+
+/// Returns the named blocking thread pool blocking operations started from the current thread run
+/// on.
+///
+/// Like [`blocking`], this doesn't start the global executor.
+#[cfg(feature = "unstable")]
+pub(crate) fn named_blocking(name: &str) -> Arc<BlockingPool> {
+    match local() {
+        Some(pool) => pool.named.get(name),
+        None => global_named().get(name),
+    }
+}
+
+/// Returns the clock timers created on the current thread run on.
+///
+/// This is the clock of the executor [`current`] would return, except that the global clock is
+/// used without starting the global executor.
+pub(crate) fn clock() -> Arc<Clock> {
+    match local() {
+        Some(pool) => pool.clock.clone(),
+        None => global_clock(),
+    }
+}
+
+/// Returns `true` if the executor tasks spawned from the current thread are spawned onto has
+/// nothing to do.
+///
+/// An executor that hasn't been started yet is idle.
+#[cfg(feature = "unstable")]
+pub(crate) fn is_idle() -> bool {
+    match local().or_else(|| global().cloned()) {
+        Some(pool) => pool.is_idle(),
+        None => true,
+    }
+}
+
+/// Returns `true` if the executor tasks spawned from the current thread are spawned onto uses
+/// io_uring for its sockets.
+#[cfg(all(feature = "io-uring", target_os = "linux"))]
+pub(crate) fn io_uring() -> bool {
+    matches!(local(), Some(pool) if pool.config.io_uring)
+}
+
+/// Makes the task currently running on this worker thread go to the back of the local queue the
+/// next time it is scheduled, instead of into the slot where it would run again right away.
+pub(crate) fn yield_current() {
+    if let Some(id) = Task::get_current(|task| task.id()) {
+        PROCESSOR.with(|proc| {
+            if let Some(proc) = proc.borrow().as_ref() {
+                proc.yielded.set(Some(id));
             }
-            None => {
-                POOL.injector.push(task);
-                POOL.sleepers.notify_one();
+        });
+    }
+}
+
+/// Returns the executor entered on the current thread or the current thread is a worker thread of.
+fn local() -> Option<Arc<Pool>> {
+    if let Some(pool) = ENTERED.with(|entered| entered.borrow().clone()) {
+        return Some(pool);
+    }
+    PROCESSOR.with(|proc| proc.borrow().as_ref().map(|p| p.pool.clone()))
+}
+
+/// Returns `true` if the current thread is a worker thread of an executor.
+///
+/// Worker threads running a closure passed to [`block_in_place`] don't count.
+pub(crate) fn is_worker_thread() -> bool {
+    PROCESSOR.with(|proc| proc.borrow().is_some())
+}
+
+/// Runs a closure with `pool` as the executor tasks are spawned onto.
+///
+/// If the executor has a driver, I/O handles and timers created within the closure are driven by
+/// it.
+#[cfg(feature = "unstable")]
+pub(crate) fn enter<F, R>(pool: &Arc<Pool>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    ENTERED.with(|entered| {
+        let old = entered.replace(Some(pool.clone()));
+        defer! {
+            *entered.borrow_mut() = old;
+        }
+
+        match &pool.config.park {
+            Some(park) => {
+                let mut f = Some(f);
+                let mut out = None;
+                park.enter(&mut || out = Some((f.take().unwrap())()));
+                out.unwrap()
             }
+            None => f(),
         }
     })
 }
 
+/// Starts a worker thread driving tasks with the given processor.
+fn start_worker(proc: Processor) -> io::Result<()> {
+    let pool = proc.pool.clone();
+    let config = &pool.config;
+
+    let mut builder = thread::Builder::new().name(config.thread_name.clone());
+    if let Some(size) = config.stack_size {
+        builder = builder.stack_size(size);
+    }
+
+    #[cfg(feature = "unstable")]
+    let cpu = config.affinity.as_ref().map(|a| a.cpu(proc.index));
+
+    let on_thread_start = config.on_thread_start.clone();
+    let on_thread_stop = config.on_thread_stop.clone();
+    let handle = builder.spawn(move || {
+        #[cfg(feature = "unstable")]
+        COUNTERS.workers.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "unstable")]
+        defer! {
+            COUNTERS.workers.fetch_sub(1, Ordering::Relaxed);
+        }
+
+        if let Some(hook) = on_thread_start {
+            hook();
+        }
+        defer! {
+            if let Some(hook) = &on_thread_stop {
+                hook();
+            }
+        }
+
+        PROCESSOR.with(|p| *p.borrow_mut() = Some(proc));
+        abort_on_panic(main_loop);
+    })?;
+
+    #[cfg(feature = "unstable")]
+    {
+        if let Some(cpu) = cpu {
+            affinity::pin(&handle, cpu)?;
+        }
+    }
+
+    pool.threads.lock().unwrap().push(handle);
+    Ok(())
+}
+
+/// Runs a blocking closure on the current thread.
+///
+/// If the current thread is a worker thread, its processor is first handed over to a new worker
+/// thread so that the tasks in its queue keep making progress. The current thread stops being a
+/// worker thread once the task that called this function yields back to it.
+#[cfg(feature = "unstable")]
+pub(crate) fn block_in_place<F, T>(f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    if let Some(proc) = PROCESSOR.with(|proc| proc.borrow_mut().take()) {
+        // Keep spawning tasks onto the same executor while `f` runs.
+        let pool = proc.pool.clone();
+        start_worker(proc).expect("cannot start a thread driving tasks");
+        return enter(&pool, f);
+    }
+    f()
+}
+
 /// Main loop running a worker thread.
 fn main_loop() {
     /// Number of yields when no runnable task is found.
@@ -105,6 +756,27 @@ fn main_loop() {
     let mut fails = 0;
 
     loop {
+        // Stop the thread if the executor has been shut down.
+        let shutdown = PROCESSOR.with(|proc| {
+            let proc = proc.borrow();
+            let proc = proc.as_ref().unwrap();
+            if !proc.pool.shutdown.load(Ordering::SeqCst) {
+                return false;
+            }
+
+            // Drop the tasks this worker thread owns.
+            drop(proc.slot.take());
+            while let Some(task) = proc.worker.pop() {
+                drop(task);
+            }
+            proc.pool.drain();
+            true
+        });
+        if shutdown {
+            PROCESSOR.with(|proc| proc.borrow_mut().take());
+            return;
+        }
+
         // Try to find a runnable task.
         match find_runnable() {
             Some(task) => {
@@ -112,6 +784,11 @@ fn main_loop() {
 
                 // Run the found task.
                 task.run();
+
+                // Stop the thread if the task has handed the processor over to another thread.
+                if PROCESSOR.with(|proc| proc.borrow().is_none()) {
+                    return;
+                }
             }
             None => {
                 fails += 1;
@@ -122,7 +799,8 @@ fn main_loop() {
                 } else if fails <= YIELDS + SLEEPS {
                     thread::sleep(Duration::from_micros(10));
                 } else {
-                    POOL.sleepers.wait();
+                    let pool = PROCESSOR.with(|proc| proc.borrow().as_ref().unwrap().pool.clone());
+                    pool.sleepers.wait(|| pool.clock.notify_idle());
                     fails = 0;
                 }
             }
@@ -136,7 +814,9 @@ fn find_runnable() -> Option<Runnable> {
     const SLOT_LIMIT: u32 = 16;
 
     PROCESSOR.with(|proc| {
-        let proc = proc.get().unwrap();
+        let proc = proc.borrow();
+        let proc = proc.as_ref().unwrap();
+        let pool = &proc.pool;
 
         // Try taking a task from the slot.
         let runs = proc.slot_runs.get();
@@ -152,23 +832,40 @@ fn find_runnable() -> Option<Runnable> {
         proc.worker.pop().or_else(|| {
             // Otherwise, we need to look for a task elsewhere.
             iter::repeat_with(|| {
-                // Try stealing a batch of tasks from the global queue.
-                POOL.injector
-                    .steal_batch_and_pop(&proc.worker)
-                    // Or try stealing a batch of tasks from one of the other threads.
-                    .or_else(|| {
-                        // First, pick a random starting point in the list of local queues.
-                        let len = POOL.stealers.len();
-                        let start = random(len as u32) as usize;
-
-                        // Try stealing a batch of tasks from each local queue starting from the
-                        // chosen point.
-                        let (l, r) = POOL.stealers.split_at(start);
-                        let stealers = r.iter().chain(l.iter());
-                        stealers
-                            .map(|s| s.steal_batch_and_pop(&proc.worker))
-                            .collect()
-                    })
+                // Try stealing a batch of tasks from the global queue. Tasks that must run in the
+                // order they were scheduled are taken one at a time.
+                let steal = if pool.config.fifo {
+                    pool.injector.steal()
+                } else {
+                    pool.injector.steal_batch_and_pop(&proc.worker)
+                };
+
+                // Or try stealing a batch of tasks from one of the other threads.
+                steal.or_else(|| {
+                    if !pool.config.work_stealing {
+                        return Steal::Empty;
+                    }
+
+                    // First, pick a random starting point in the list of local queues.
+                    let len = pool.stealers.len();
+                    let start = random(len as u32) as usize;
+
+                    // Try stealing a batch of tasks from each local queue starting from the
+                    // chosen point.
+                    let (l, r) = pool.stealers.split_at(start);
+                    let stealers = r.iter().chain(l.iter());
+                    let steal: Steal<Runnable> = stealers
+                        .map(|s| s.steal_batch_and_pop(&proc.worker))
+                        .collect();
+
+                    #[cfg(feature = "unstable")]
+                    {
+                        if steal.is_success() {
+                            COUNTERS.steals.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                    steal
+                })
             })
             // Loop while no task was stolen and any steal operation needs to be retried.
             .find(|s| !s.is_retry())
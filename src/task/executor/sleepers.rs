@@ -14,6 +14,9 @@ pub struct Sleepers {
 
     /// Set to `true` if a notification came up while nobody was sleeping.
     notified: AtomicBool,
+
+    /// Set to `true` once threads should stop going to sleep.
+    closed: AtomicBool,
 }
 
 impl Sleepers {
@@ -23,19 +26,35 @@ impl Sleepers {
             sleep: Mutex::new(0),
             wake: Condvar::new(),
             notified: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
         }
     }
 
     /// Puts the current thread to sleep.
-    pub fn wait(&self) {
+    ///
+    /// Once the thread counts as sleeping and right before it blocks, `on_sleep` is called.
+    ///
+    /// Returns immediately if the sleepers have been closed.
+    pub fn wait(&self, on_sleep: impl FnOnce()) {
         let mut sleep = self.sleep.lock().unwrap();
 
+        if self.closed.load(Ordering::SeqCst) {
+            return;
+        }
+
         if !self.notified.swap(false, Ordering::SeqCst) {
             *sleep += 1;
+            on_sleep();
             let _ = self.wake.wait(sleep).unwrap();
         }
     }
 
+    /// Returns the number of sleeping threads.
+    #[cfg(feature = "unstable")]
+    pub fn sleeping(&self) -> usize {
+        *self.sleep.lock().unwrap()
+    }
+
     /// Notifies one thread.
     pub fn notify_one(&self) {
         if !self.notified.load(Ordering::SeqCst) {
@@ -49,4 +68,12 @@ impl Sleepers {
             }
         }
     }
+
+    /// Wakes up all sleeping threads and keeps threads from going to sleep from now on.
+    pub fn close(&self) {
+        let mut sleep = self.sleep.lock().unwrap();
+        self.closed.store(true, Ordering::SeqCst);
+        *sleep = 0;
+        self.wake.notify_all();
+    }
 }
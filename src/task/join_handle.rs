@@ -1,6 +1,12 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt;
 use std::future::Future;
+use std::panic;
 use std::pin::Pin;
 
+#[cfg(feature = "unstable")]
+use crate::task::Waker;
 use crate::task::{Context, Poll, Task};
 
 /// A handle that awaits the result of a task.
@@ -8,18 +14,22 @@ use crate::task::{Context, Poll, Task};
 /// Dropping a [`JoinHandle`] will detach the task, meaning that there is no longer
 /// a handle to the task and no way to `join` on it.
 ///
+/// If the task panics, the panic is caught and stops only that task. Awaiting the handle then
+/// resumes the panic in the awaiting task, while [`join`] returns it as an error.
+///
 /// Created when a task is [spawned].
 ///
 /// [spawned]: fn.spawn.html
+/// [`join`]: #method.join
 #[derive(Debug)]
-pub struct JoinHandle<T>(async_task::JoinHandle<T, Task>);
+pub struct JoinHandle<T>(async_task::JoinHandle<Result<T, JoinError>, Task>);
 
-unsafe impl<T> Send for JoinHandle<T> {}
+unsafe impl<T: Send> Send for JoinHandle<T> {}
 unsafe impl<T> Sync for JoinHandle<T> {}
 
 impl<T> JoinHandle<T> {
     /// Creates a new `JoinHandle`.
-    pub(crate) fn new(inner: async_task::JoinHandle<T, Task>) -> JoinHandle<T> {
+    pub(crate) fn new(inner: async_task::JoinHandle<Result<T, JoinError>, Task>) -> JoinHandle<T> {
         JoinHandle(inner)
     }
 
@@ -41,6 +51,106 @@ impl<T> JoinHandle<T> {
     pub fn task(&self) -> &Task {
         self.0.tag()
     }
+
+    /// Cancels the task and waits for it to stop.
+    ///
+    /// The task stops at its next yield point and its future is dropped before this method
+    /// returns. Returns the output of the task if it completed before it could be cancelled.
+    ///
+    /// Tasks spawned with [`spawn_blocking`] can't be interrupted, so this waits for them to
+    /// complete. They can check [`is_cancelled`] to stop early.
+    ///
+    /// [`spawn_blocking`]: fn.spawn_blocking.html
+    /// [`is_cancelled`]: fn.is_cancelled.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::task;
+    ///
+    /// let handle = task::spawn(async {
+    ///     task::sleep(Duration::from_secs(10)).await;
+    /// });
+    ///
+    /// assert_eq!(handle.cancel().await, None);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn cancel(self) -> Option<T> {
+        self.abort_handle().abort();
+        self.0.await.and_then(Result::ok)
+    }
+
+    /// Waits for the task to complete and returns its output, or the reason it failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::task;
+    ///
+    /// let handle = task::spawn(async {
+    ///     panic!("boom");
+    /// });
+    ///
+    /// let err = handle.join().await.unwrap_err();
+    /// assert!(err.is_panic());
+    /// assert_eq!(*err.into_panic().downcast::<&str>().unwrap(), "boom");
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn join(mut self) -> Result<T, JoinError> {
+        crate::future::poll_fn(|cx| self.poll_join(cx)).await
+    }
+
+    /// Polls the task for its output, or the reason it failed.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn poll_join(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, JoinError>> {
+        match Pin::new(&mut self.0).poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Some(res)) => Poll::Ready(res),
+            Poll::Ready(None) => Poll::Ready(Err(JoinError::cancelled())),
+        }
+    }
+
+    /// Returns a handle that can abort the task without owning the `JoinHandle`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::task;
+    ///
+    /// let handle = task::spawn(async {
+    ///     task::sleep(Duration::from_secs(10)).await;
+    /// });
+    ///
+    /// let abort = handle.abort_handle();
+    /// abort.abort();
+    /// assert_eq!(handle.cancel().await, None);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn abort_handle(&self) -> AbortHandle {
+        AbortHandle {
+            task: self.0.tag().clone(),
+            waker: self.0.waker(),
+        }
+    }
 }
 
 impl<T> Future for JoinHandle<T> {
@@ -49,8 +159,128 @@ impl<T> Future for JoinHandle<T> {
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         match Pin::new(&mut self.0).poll(cx) {
             Poll::Pending => Poll::Pending,
-            Poll::Ready(None) => panic!("cannot await the result of a panicked task"),
-            Poll::Ready(Some(val)) => Poll::Ready(val),
+            Poll::Ready(Some(Ok(val))) => Poll::Ready(val),
+            Poll::Ready(Some(Err(JoinError(Repr::Panic(payload))))) => panic::resume_unwind(payload),
+            Poll::Ready(Some(Err(_))) | Poll::Ready(None) => {
+                panic!("cannot await the result of an aborted task")
+            }
         }
     }
 }
+
+/// An error returned when joining a task that did not complete.
+///
+/// Returned by [`JoinHandle::join`].
+///
+/// [`JoinHandle::join`]: struct.JoinHandle.html#method.join
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct JoinError(Repr);
+
+enum Repr {
+    Cancelled,
+    Panic(Box<dyn Any + Send + 'static>),
+}
+
+impl JoinError {
+    pub(crate) fn cancelled() -> JoinError {
+        JoinError(Repr::Cancelled)
+    }
+
+    pub(crate) fn panic(payload: Box<dyn Any + Send + 'static>) -> JoinError {
+        JoinError(Repr::Panic(payload))
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl JoinError {
+    /// Returns `true` if the task was aborted or cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        match self.0 {
+            Repr::Cancelled => true,
+            Repr::Panic(_) => false,
+        }
+    }
+
+    /// Returns `true` if the task panicked.
+    pub fn is_panic(&self) -> bool {
+        !self.is_cancelled()
+    }
+
+    /// Returns the panic payload.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the task didn't panic.
+    pub fn into_panic(self) -> Box<dyn Any + Send + 'static> {
+        self.try_into_panic()
+            .expect("`JoinError` reason is not a panic")
+    }
+
+    /// Returns the panic payload, or the error back if the task didn't panic.
+    pub fn try_into_panic(self) -> Result<Box<dyn Any + Send + 'static>, JoinError> {
+        match self.0 {
+            Repr::Panic(payload) => Ok(payload),
+            Repr::Cancelled => Err(self),
+        }
+    }
+}
+
+// The panic payload is only ever accessed by value.
+unsafe impl Sync for JoinError {}
+
+impl Error for JoinError {}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Repr::Cancelled => f.pad("JoinError::Cancelled"),
+            Repr::Panic(_) => f.pad("JoinError::Panic(..)"),
+        }
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Repr::Cancelled => "task was cancelled".fmt(f),
+            Repr::Panic(_) => "task panicked".fmt(f),
+        }
+    }
+}
+
+/// A handle that aborts a task.
+///
+/// Unlike a [`JoinHandle`], an `AbortHandle` can be cloned and doesn't detach the task when
+/// dropped. Aborting a task stops it at its next yield point and drops its future. Tasks
+/// spawned with [`spawn_blocking`] can't be interrupted and are not affected.
+///
+/// Created by [`JoinHandle::abort_handle`].
+///
+/// [`JoinHandle`]: struct.JoinHandle.html
+/// [`JoinHandle::abort_handle`]: struct.JoinHandle.html#method.abort_handle
+/// [`spawn_blocking`]: fn.spawn_blocking.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Debug)]
+pub struct AbortHandle {
+    task: Task,
+    waker: Waker,
+}
+
+#[cfg(feature = "unstable")]
+impl AbortHandle {
+    /// Aborts the task.
+    ///
+    /// If the task has already completed, this has no effect.
+    pub fn abort(&self) {
+        self.task.abort();
+
+        // Wake the task so that it notices the request.
+        self.waker.wake_by_ref();
+    }
+
+    /// Returns a handle to the underlying task.
+    pub fn task(&self) -> &Task {
+        &self.task
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+use std::future::Future;
+
+use crate::future::poll_fn;
+use crate::task::{self, AbortHandle, Context, JoinError, JoinHandle, Poll};
+use crate::utils::random;
+
+/// A collection of tasks spawned onto the executor.
+///
+/// Tasks are spawned with [`spawn`] and their results are collected with [`join_next`] in the
+/// order the tasks complete.
+///
+/// When a `JoinSet` is dropped, all tasks in it are aborted.
+///
+/// [`spawn`]: #method.spawn
+/// [`join_next`]: #method.join_next
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::task::JoinSet;
+///
+/// let mut set = JoinSet::new();
+///
+/// for i in 0..10 {
+///     set.spawn(async move { i });
+/// }
+///
+/// let mut sum = 0;
+/// while let Some(res) = set.join_next().await {
+///     sum += res.unwrap();
+/// }
+/// assert_eq!(sum, 45);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct JoinSet<T> {
+    handles: Vec<JoinHandle<T>>,
+}
+
+impl<T> JoinSet<T> {
+    /// Creates an empty set.
+    pub fn new() -> JoinSet<T> {
+        JoinSet {
+            handles: Vec::new(),
+        }
+    }
+
+    /// Returns the number of tasks in the set.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns `true` if there are no tasks in the set.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Spawns a task onto the set.
+    ///
+    /// Returns a handle that can abort the task. The task's result is still reported by
+    /// [`join_next`].
+    ///
+    /// [`join_next`]: #method.join_next
+    #[track_caller]
+    pub fn spawn<F>(&mut self, future: F) -> AbortHandle
+    where
+        F: Future<Output = T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let handle = task::spawn(future);
+        let abort = handle.abort_handle();
+        self.handles.push(handle);
+        abort
+    }
+
+    /// Waits for any task in the set to complete and returns its result.
+    ///
+    /// Returns `None` if the set is empty.
+    ///
+    /// Every call polls all tasks in the set, so this is best suited for sets of moderate size.
+    pub async fn join_next(&mut self) -> Option<Result<T, JoinError>> {
+        poll_fn(|cx| self.poll_join_next(cx)).await
+    }
+
+    /// Aborts all tasks in the set.
+    ///
+    /// The tasks stay in the set until their results are collected with [`join_next`].
+    ///
+    /// [`join_next`]: #method.join_next
+    pub fn abort_all(&self) {
+        for handle in &self.handles {
+            handle.abort_handle().abort();
+        }
+    }
+
+    /// Aborts all tasks in the set and waits for them to stop.
+    ///
+    /// The set is empty afterwards.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::task::{self, JoinSet};
+    ///
+    /// let mut set = JoinSet::new();
+    /// set.spawn(task::sleep(Duration::from_secs(10)));
+    ///
+    /// set.shutdown().await;
+    /// assert!(set.is_empty());
+    /// #
+    /// # })
+    /// ```
+    pub async fn shutdown(&mut self) {
+        self.abort_all();
+        while self.join_next().await.is_some() {}
+    }
+
+    /// Polls the tasks, starting at a random one so that no task is favored.
+    fn poll_join_next(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<T, JoinError>>> {
+        let len = self.handles.len();
+        if len == 0 {
+            return Poll::Ready(None);
+        }
+
+        let start = random(len as u32) as usize;
+        for i in 0..len {
+            let index = (start + i) % len;
+            if let Poll::Ready(res) = self.handles[index].poll_join(cx) {
+                self.handles.swap_remove(index);
+                return Poll::Ready(Some(res));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for JoinSet<T> {
+    fn drop(&mut self) {
+        self.abort_all();
+    }
+}
+
+impl<T> Default for JoinSet<T> {
+    fn default() -> JoinSet<T> {
+        JoinSet::new()
+    }
+}
+
+impl<T> fmt::Debug for JoinSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinSet").field("len", &self.len()).finish()
+    }
+}
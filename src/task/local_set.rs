@@ -0,0 +1,275 @@
+use std::cell::Cell;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::ptr;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use crossbeam_channel::{unbounded, Receiver, Sender};
+use pin_project_lite::pin_project;
+
+use crate::task::{self, Builder, Context, JoinHandle, Poll, Runnable, Waker};
+
+thread_local! {
+    /// The local set currently running on this thread.
+    static CURRENT: Cell<*const Arc<Shared>> = const { Cell::new(ptr::null()) };
+}
+
+/// The maximum number of tasks run in a row before yielding to the enclosing executor.
+const BUDGET: usize = 64;
+
+/// Spawns a `!Send` task onto the [`LocalSet`] running on the current thread.
+///
+/// # Panics
+///
+/// Panics if called outside of [`LocalSet::run_until`] or [`LocalSet::block_on`].
+///
+/// [`LocalSet`]: struct.LocalSet.html
+/// [`LocalSet::run_until`]: struct.LocalSet.html#method.run_until
+/// [`LocalSet::block_on`]: struct.LocalSet.html#method.block_on
+///
+/// # Examples
+///
+/// ```
+/// use std::rc::Rc;
+///
+/// use async_std::task::{self, LocalSet};
+///
+/// let local = LocalSet::new();
+/// local.block_on(async {
+///     let shared = Rc::new(5);
+///     let handle = task::spawn_local({
+///         let shared = shared.clone();
+///         async move { *shared + 1 }
+///     });
+///     assert_eq!(handle.await, 6);
+/// });
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[track_caller]
+pub fn spawn_local<F, T>(future: F) -> JoinHandle<T>
+where
+    F: Future<Output = T> + 'static,
+    T: 'static,
+{
+    spawn_current(Builder::new(), future).expect("`spawn_local` called outside of a `LocalSet`")
+}
+
+/// Spawns a local task with the given settings onto the set running on the current thread.
+///
+/// Returns `None` if no set is running.
+#[track_caller]
+pub(crate) fn spawn_current<F, T>(builder: Builder, future: F) -> Option<JoinHandle<T>>
+where
+    F: Future<Output = T> + 'static,
+    T: 'static,
+{
+    let shared = CURRENT.with(|current| current.get());
+    let shared = unsafe { shared.as_ref() }?;
+    Some(spawn_on(shared, builder, future))
+}
+
+/// A set of `!Send` tasks that run on the current thread.
+///
+/// Tasks are spawned onto the set with [`spawn_local`] and only make progress while the set is
+/// running, that is while [`run_until`] or [`block_on`] is being executed on the thread that
+/// owns the set.
+///
+/// Dropping the set drops all of its tasks that haven't completed.
+///
+/// [`spawn_local`]: #method.spawn_local
+/// [`run_until`]: #method.run_until
+/// [`block_on`]: #method.block_on
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::RefCell;
+/// use std::rc::Rc;
+///
+/// use async_std::task::LocalSet;
+///
+/// let local = LocalSet::new();
+/// let log = Rc::new(RefCell::new(Vec::new()));
+///
+/// for i in 0..3 {
+///     let log = log.clone();
+///     local.spawn_local(async move { log.borrow_mut().push(i) });
+/// }
+///
+/// local.block_on(async_std::task::yield_now());
+/// assert_eq!(log.borrow().len(), 3);
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct LocalSet {
+    shared: Arc<Shared>,
+
+    /// Makes the set `!Send` and `!Sync`.
+    _marker: PhantomData<Rc<()>>,
+}
+
+/// The state of a local set shared with the schedule functions of its tasks.
+struct Shared {
+    /// Scheduled tasks.
+    sender: Sender<Runnable>,
+    receiver: Receiver<Runnable>,
+
+    /// Wakes the task running the set.
+    waker: Mutex<Option<Waker>>,
+}
+
+impl LocalSet {
+    /// Creates an empty local set.
+    pub fn new() -> LocalSet {
+        let (sender, receiver) = unbounded();
+        LocalSet {
+            shared: Arc::new(Shared {
+                sender,
+                receiver,
+                waker: Mutex::new(None),
+            }),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Spawns a `!Send` task onto the set.
+    ///
+    /// The task starts running the next time the set runs.
+    #[track_caller]
+    pub fn spawn_local<F, T>(&self, future: F) -> JoinHandle<T>
+    where
+        F: Future<Output = T> + 'static,
+        T: 'static,
+    {
+        spawn_on(&self.shared, Builder::new(), future)
+    }
+
+    /// Runs a future to completion, running the tasks of the set in the meantime.
+    ///
+    /// The future and the tasks all run in the task calling this method, so this method can be
+    /// used from within another executor.
+    pub async fn run_until<F: Future>(&self, future: F) -> F::Output {
+        RunUntil {
+            shared: &self.shared,
+            future,
+        }
+        .await
+    }
+
+    /// Blocks the current thread on a future, running the tasks of the set in the meantime.
+    ///
+    /// This is a shorthand for calling [`run_until`] inside [`task::block_on`].
+    ///
+    /// [`run_until`]: #method.run_until
+    /// [`task::block_on`]: fn.block_on.html
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        task::block_on(self.run_until(future))
+    }
+}
+
+impl Default for LocalSet {
+    fn default() -> LocalSet {
+        LocalSet::new()
+    }
+}
+
+impl Drop for LocalSet {
+    fn drop(&mut self) {
+        // Drop the scheduled tasks on this thread. This also breaks the reference cycle between
+        // the queue and the schedule functions of the tasks in it.
+        while let Ok(runnable) = self.shared.receiver.try_recv() {
+            runnable.discard();
+        }
+    }
+}
+
+impl fmt::Debug for LocalSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("LocalSet { .. }")
+    }
+}
+
+/// Spawns a local task with the given settings onto a set.
+#[track_caller]
+fn spawn_on<F, T>(shared: &Arc<Shared>, builder: Builder, future: F) -> JoinHandle<T>
+where
+    F: Future<Output = T> + 'static,
+    T: 'static,
+{
+    let (task, future) = builder.build(future);
+
+    let shared = shared.clone();
+    let schedule = move |t| {
+        shared.sender.send(Runnable::new(t)).unwrap();
+        if let Some(w) = shared.waker.lock().unwrap().as_ref() {
+            w.wake_by_ref();
+        }
+    };
+
+    let (task, handle) = async_task::spawn_local(future, schedule, task);
+    task.schedule();
+    JoinHandle::new(handle)
+}
+
+pin_project! {
+    /// A future that runs the tasks of a set while polling another future.
+    struct RunUntil<'a, F> {
+        shared: &'a Arc<Shared>,
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F: Future> Future for RunUntil<'_, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let shared: &Arc<Shared> = this.shared;
+        let mut future = this.future;
+
+        // Make sure tasks scheduled from now on wake the current task.
+        {
+            let mut waker = shared.waker.lock().unwrap();
+            match waker.as_ref() {
+                Some(w) if w.will_wake(cx.waker()) => {}
+                _ => *waker = Some(cx.waker().clone()),
+            }
+        }
+
+        CURRENT.with(|current| {
+            let old = current.replace(shared);
+            defer! {
+                current.set(old);
+            }
+
+            loop {
+                if let Poll::Ready(val) = future.as_mut().poll(cx) {
+                    return Poll::Ready(val);
+                }
+
+                let mut ran = 0;
+                while ran < BUDGET {
+                    match shared.receiver.try_recv() {
+                        Ok(runnable) => runnable.run(),
+                        Err(_) => break,
+                    }
+                    ran += 1;
+                }
+
+                if ran == 0 {
+                    return Poll::Pending;
+                }
+                if ran == BUDGET {
+                    // Let other tasks in the enclosing executor run.
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending;
+                }
+            }
+        })
+    }
+}
@@ -96,6 +96,9 @@
 //! closure. Task-local keys allow only shared access to values, as there would be no
 //! way to guarantee uniqueness if mutable borrows were allowed.
 //!
+//! A task-local can also be set for a part of a task only, by running a future in a
+//! [`scope`][`LocalKey::scope`] with a value of the task-local.
+//!
 //! ## Naming tasks
 //!
 //! Tasks are able to have associated names for identification purposes. By default, spawned
@@ -103,6 +106,33 @@
 //! the desired task name to [`Builder::name`]. To retrieve the task name from within the
 //! task, use [`Task::name`].
 //!
+//! ## Instrumentation
+//!
+//! The runtime logs structured events through the [`log`] crate at the `trace` level, which
+//! tools can use to follow the life of tasks.
+//!
+//! * `spawn` when a task is spawned, with its `task_id`, `task_name`, and `parent_task_id`.
+//! * `completed` when a task completes, with its `task_id`.
+//!
+//! With the `tracing` Cargo feature enabled, the runtime also reports to [`tracing`] at the
+//! `trace` level:
+//!
+//! * A `task` span for every spawned task, with its `task_id`, `task_name`, and
+//!   `parent_task_id`. It is opened when the task is spawned, entered whenever the task is polled,
+//!   and closed once the task completes.
+//! * A `block_on` span around the future passed to [`block_on`], with the same fields except for
+//!   the name.
+//! * A `poll` span nested in the `task` span for every poll of a task, recording whether the task
+//!   is `ready` afterwards.
+//! * A `reactor wakeup` event when the I/O reactor wakes up, with the number of `events`.
+//! * A `timer fired` event when a timeout, sleep, or interval fires, with the `task_id` of the
+//!   task awaiting it.
+//!
+//! Task names are empty for unnamed tasks, and a `task_id` of 0 means no task.
+//!
+//! [`log`]: https://docs.rs/log
+//! [`tracing`]: https://docs.rs/tracing
+//! [`block_on`]: fn.block_on.html
 //! [`Arc`]: ../gsync/struct.Arc.html
 //! [`spawn`]: fn.spawn.html
 //! [`JoinHandle`]: struct.JoinHandle.html
@@ -115,6 +145,7 @@
 //! [`Task`]: struct.Task.html
 //! [`Task::name`]: struct.Task.html#method.name
 //! [`task_local!`]: ../macro.task_local.html
+//! [`LocalKey::scope`]: struct.LocalKey.html#method.scope
 //! [`with`]: struct.LocalKey.html#method.with
 
 cfg_std! {
@@ -129,6 +160,7 @@ cfg_std! {
 
 cfg_default! {
     pub use block_on::block_on;
+    pub(crate) use block_on::block_on_unchecked;
     pub use builder::Builder;
     pub use current::current;
     pub use task::Task;
@@ -142,19 +174,68 @@ cfg_default! {
     use task_local::LocalsMap;
 
     mod block_on;
+    pub(crate) mod budget;
     mod builder;
     mod current;
-    mod executor;
+    pub(crate) mod executor;
     mod join_handle;
     mod sleep;
     mod spawn;
-    mod spawn_blocking;
+    pub(crate) mod spawn_blocking;
     mod task;
     mod task_id;
     mod task_local;
 
+    #[cfg(feature = "unstable")]
+    pub use block_in_place::block_in_place;
+    #[cfg(feature = "unstable")]
+    pub use block_on::{try_block_on, BlockOnError};
+    #[cfg(feature = "unstable")]
+    pub use current::is_cancelled;
+    #[cfg(feature = "unstable")]
+    pub use budget::{consume_budget, unconstrained, Unconstrained};
+    #[cfg(feature = "unstable")]
+    pub use builder::{Priority, SpawnError};
+    #[cfg(not(feature = "unstable"))]
+    pub(crate) use builder::Priority;
+    #[cfg(feature = "unstable")]
+    pub use dump::{dump, TaskInfo, TaskState};
+    #[cfg(feature = "unstable")]
+    pub use join_handle::{AbortHandle, JoinError};
+    #[cfg(not(feature = "unstable"))]
+    pub(crate) use join_handle::JoinError;
+    #[cfg(feature = "unstable")]
+    pub use join_set::JoinSet;
+    #[cfg(all(feature = "unstable", not(target_arch = "wasm32")))]
+    pub use local_set::{spawn_local, LocalSet};
+    #[cfg(feature = "unstable")]
+    pub use scope::{scope, Scope, ScopedJoinHandle};
+    #[cfg(feature = "unstable")]
+    pub use sleep::sleep_until;
+    #[cfg(feature = "unstable")]
+    pub use spawn_with_cleanup::spawn_with_cleanup;
+    #[cfg(feature = "unstable")]
+    pub use task_local::LocalScope;
+
+    #[cfg(feature = "unstable")]
+    mod block_in_place;
+    #[cfg(feature = "unstable")]
+    mod dump;
+    #[cfg(feature = "unstable")]
+    mod join_set;
+    #[cfg(all(feature = "unstable", not(target_arch = "wasm32")))]
+    pub(crate) mod local_set;
+    #[cfg(feature = "unstable")]
+    mod scope;
+    #[cfg(feature = "unstable")]
+    mod spawn_with_cleanup;
+
     #[cfg(any(feature = "unstable", test))]
     pub use spawn_blocking::spawn_blocking;
+    #[cfg(feature = "unstable")]
+    pub use spawn_blocking::try_spawn_blocking;
+    #[cfg(feature = "unstable")]
+    pub use spawn_blocking::spawn_blocking_on;
     #[cfg(not(any(feature = "unstable", test)))]
     pub(crate) use spawn_blocking::spawn_blocking;
 }
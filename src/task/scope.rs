@@ -0,0 +1,230 @@
+use std::any::Any;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use pin_project_lite::pin_project;
+
+use crate::sync::WaitGroup;
+use crate::task::{self, Context, JoinError, JoinHandle, Poll};
+
+/// Creates a scope for spawning tasks that borrow from the enclosing stack frame.
+///
+/// All tasks spawned on the scope are run on the executor and awaited before this function
+/// returns, which is what allows them to borrow non-`'static` data. This function blocks the
+/// current thread until then, like [`block_on`].
+///
+/// If any task panicked and its panic was not collected through [`ScopedJoinHandle::join`], this
+/// function panics after all tasks have completed.
+///
+/// [`block_on`]: fn.block_on.html
+/// [`ScopedJoinHandle::join`]: struct.ScopedJoinHandle.html#method.join
+///
+/// # Examples
+///
+/// ```
+/// use async_std::task;
+///
+/// let mut numbers = vec![1, 2, 3];
+/// let sum = task::scope(|s| {
+///     let a = s.spawn(async { numbers.iter().sum::<i32>() });
+///     let b = s.spawn(async { numbers.len() });
+///     task::block_on(async { (a.join().await.unwrap(), b.join().await.unwrap()) })
+/// });
+/// assert_eq!(sum, (6, 3));
+///
+/// // The borrows have ended.
+/// numbers.push(4);
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn scope<'env, F, R>(f: F) -> R
+where
+    F: FnOnce(&Scope<'env>) -> R,
+{
+    let scope = Scope {
+        registration: WaitGroup::new(),
+        slots: Mutex::new(Vec::new()),
+        _marker: PhantomData,
+    };
+
+    // Tasks must complete before the borrowed data goes away, even if `f` panics.
+    let res = panic::catch_unwind(AssertUnwindSafe(|| f(&scope)));
+
+    let Scope {
+        registration,
+        slots,
+        ..
+    } = scope;
+    task::block_on_unchecked(registration.wait());
+
+    match res {
+        Err(payload) => panic::resume_unwind(payload),
+        Ok(val) => {
+            for slot in slots.into_inner().unwrap() {
+                if let Some(payload) = slot.take_panic() {
+                    panic::resume_unwind(payload);
+                }
+            }
+            val
+        }
+    }
+}
+
+/// A scope for spawning tasks that may borrow from the environment.
+///
+/// This struct is created by the [`scope`] function. See its documentation for more.
+///
+/// [`scope`]: fn.scope.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Scope<'env> {
+    /// Registers running tasks.
+    registration: WaitGroup,
+
+    /// The result slots of all spawned tasks.
+    slots: Mutex<Vec<Arc<dyn Unjoined + 'env>>>,
+
+    /// Makes the scope invariant over `'env`.
+    _marker: PhantomData<&'env mut &'env ()>,
+}
+
+impl<'env> Scope<'env> {
+    /// Spawns a task on the scope.
+    ///
+    /// The task may borrow anything that outlives the scope.
+    pub fn spawn<'scope, F, T>(&'scope self, future: F) -> ScopedJoinHandle<'scope, T>
+    where
+        F: Future<Output = T> + Send + 'env,
+        T: Send + 'env,
+    {
+        let slot = Arc::new(Slot {
+            result: Mutex::new(None),
+        });
+        self.slots.lock().unwrap().push(slot.clone());
+
+        let future: Pin<Box<dyn Future<Output = ()> + Send + 'env>> = Box::pin({
+            let slot = slot.clone();
+            async move {
+                let res = CatchUnwind { future }.await;
+                *slot.result.lock().unwrap() = Some(res);
+            }
+        });
+
+        // Safety: `scope()` doesn't return before this future is dropped, so the future can't
+        // outlive `'env`.
+        let future: Pin<Box<dyn Future<Output = ()> + Send + 'static>> =
+            unsafe { mem::transmute(future) };
+
+        let handle = task::spawn(Registered {
+            future,
+            _registration: self.registration.clone(),
+        });
+
+        ScopedJoinHandle {
+            handle,
+            slot,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl fmt::Debug for Scope<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Scope { .. }")
+    }
+}
+
+/// A handle that awaits the result of a scoped task.
+///
+/// This struct is created by the [`Scope::spawn`] method.
+///
+/// [`Scope::spawn`]: struct.Scope.html#method.spawn
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct ScopedJoinHandle<'scope, T> {
+    handle: JoinHandle<()>,
+    slot: Arc<Slot<T>>,
+    _marker: PhantomData<&'scope ()>,
+}
+
+impl<T> ScopedJoinHandle<'_, T> {
+    /// Waits for the task to complete and returns its output, or the reason it failed.
+    pub async fn join(self) -> Result<T, JoinError> {
+        self.handle.await;
+
+        match self.slot.result.lock().unwrap().take() {
+            Some(Ok(val)) => Ok(val),
+            Some(Err(payload)) => Err(JoinError::panic(payload)),
+            None => Err(JoinError::cancelled()),
+        }
+    }
+}
+
+impl<T> fmt::Debug for ScopedJoinHandle<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScopedJoinHandle")
+            .field("task", self.handle.task())
+            .finish()
+    }
+}
+
+/// The result of a scoped task.
+struct Slot<T> {
+    result: Mutex<Option<thread::Result<T>>>,
+}
+
+/// A slot that may hold a panic nobody has collected.
+trait Unjoined: Send + Sync {
+    fn take_panic(&self) -> Option<Box<dyn Any + Send + 'static>>;
+}
+
+impl<T: Send> Unjoined for Slot<T> {
+    fn take_panic(&self) -> Option<Box<dyn Any + Send + 'static>> {
+        match self.result.lock().unwrap().take() {
+            Some(Err(payload)) => Some(payload),
+            _ => None,
+        }
+    }
+}
+
+/// A scoped task's future together with its registration in the scope.
+///
+/// The fields are dropped in order, so the registration outlives the future.
+struct Registered {
+    future: Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
+    _registration: WaitGroup,
+}
+
+impl Future for Registered {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.future.as_mut().poll(cx)
+    }
+}
+
+pin_project! {
+    /// A future that catches panics of the inner future.
+    struct CatchUnwind<F> {
+        #[pin]
+        future: F,
+    }
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = thread::Result<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = self.project().future;
+        match panic::catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(poll) => poll.map(Ok),
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
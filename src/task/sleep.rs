@@ -1,7 +1,11 @@
 use std::time::Duration;
+#[cfg(feature = "unstable")]
+use std::time::Instant;
 
 use crate::future;
 use crate::io;
+#[cfg(feature = "unstable")]
+use crate::time::Sleep;
 
 /// Sleeps for the specified amount of time.
 ///
@@ -31,3 +35,38 @@ use crate::io;
 pub async fn sleep(dur: Duration) {
     let _: io::Result<()> = io::timeout(dur, future::pending()).await;
 }
+
+/// Sleeps until the specified deadline.
+///
+/// This is the deadline-based counterpart to [`sleep`]. The returned [`Sleep`] can be
+/// [`reset`] to a new deadline without creating a new timer.
+///
+/// Deadlines are measured on the clock of the current runtime, which only differs from
+/// [`Instant::now`] when it has been paused. See [`time::now`].
+///
+/// [`sleep`]: fn.sleep.html
+/// [`Sleep`]: ../time/struct.Sleep.html
+/// [`reset`]: ../time/struct.Sleep.html#method.reset
+/// [`Instant::now`]: https://doc.rust-lang.org/std/time/struct.Instant.html#method.now
+/// [`time::now`]: ../time/fn.now.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::{Duration, Instant};
+///
+/// use async_std::task;
+///
+/// let deadline = Instant::now() + Duration::from_millis(10);
+/// task::sleep_until(deadline).await;
+/// assert!(Instant::now() >= deadline);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn sleep_until(deadline: Instant) -> Sleep {
+    Sleep::new(deadline)
+}
@@ -23,6 +23,7 @@ use crate::task::{Builder, JoinHandle};
 /// #
 /// # })
 /// ```
+#[track_caller]
 pub fn spawn<F, T>(future: F) -> JoinHandle<T>
 where
     F: Future<Output = T> + Send + 'static,
@@ -1,12 +1,13 @@
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::thread;
-use std::time::Duration;
+#[cfg(feature = "unstable")]
+use std::cell::RefCell;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
 
-use crossbeam_channel::{unbounded, Receiver, Sender};
-use once_cell::sync::Lazy;
-
-use crate::task::{JoinHandle, Task};
-use crate::utils::abort_on_panic;
+use crate::task::executor::{self, BlockingPool};
+#[cfg(feature = "unstable")]
+use crate::task::SpawnError;
+use crate::task::{JoinError, JoinHandle, Priority, Task};
 
 /// Spawns a blocking task.
 ///
@@ -14,6 +15,18 @@ use crate::utils::abort_on_panic;
 /// is useful to prevent long-running synchronous operations from blocking the main futures
 /// executor.
 ///
+/// Threads are started on demand, up to a maximum number, and stop after they have been idle for a
+/// while. Once the maximum is reached, blocking tasks wait in a queue for a thread to become
+/// available. These settings can be configured for a [`Runtime`] with its [`Builder`].
+///
+/// This function ignores the queue limit configured with [`Builder::max_blocking_queue`]. Use
+/// [`try_spawn_blocking`] to have the limit apply.
+///
+/// [`Runtime`]: ../runtime/struct.Runtime.html
+/// [`Builder`]: ../runtime/struct.Builder.html
+/// [`Builder::max_blocking_queue`]: ../runtime/struct.Builder.html#method.max_blocking_queue
+/// [`try_spawn_blocking`]: fn.try_spawn_blocking.html
+///
 /// See also: [`task::block_on`], [`task::spawn`].
 ///
 /// [`task::block_on`]: fn.block_on.html
@@ -42,80 +55,163 @@ where
     F: FnOnce() -> T + Send + 'static,
     T: Send + 'static,
 {
-    let schedule = |task| POOL.sender.send(task).unwrap();
-    let (task, handle) = async_task::spawn(async { f() }, schedule, Task::new(None));
-    task.schedule();
-    JoinHandle::new(handle)
+    let pool = executor::blocking();
+    let (job, handle) = create(&pool, f);
+    let _ = pool.push(job, false);
+    handle
 }
 
-type Runnable = async_task::Task<Task>;
-
-/// The number of sleeping worker threads.
-static SLEEPING: AtomicUsize = AtomicUsize::new(0);
-
-struct Pool {
-    sender: Sender<Runnable>,
-    receiver: Receiver<Runnable>,
+/// Spawns a blocking task, unless the queue of the blocking thread pool is full.
+///
+/// This is like [`spawn_blocking`], except that it fails with a [`SpawnError`] if the number of
+/// blocking tasks waiting for a thread has reached the limit configured with
+/// [`Builder::max_blocking_queue`]. This lets callers push back on a flood of blocking operations
+/// instead of queueing them without bound.
+///
+/// [`spawn_blocking`]: fn.spawn_blocking.html
+/// [`SpawnError`]: struct.SpawnError.html
+/// [`Builder::max_blocking_queue`]: ../runtime/struct.Builder.html#method.max_blocking_queue
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::task;
+///
+/// let handle = task::try_spawn_blocking(|| 1 + 2).expect("the queue is full");
+/// assert_eq!(handle.await, 3);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn try_spawn_blocking<F, T>(f: F) -> Result<JoinHandle<T>, SpawnError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = executor::blocking();
+    let (job, handle) = create(&pool, f);
+    match pool.push(job, true) {
+        Ok(()) => Ok(handle),
+        Err(_) => Err(SpawnError::queue_full()),
+    }
 }
 
-static POOL: Lazy<Pool> = Lazy::new(|| {
-    // Start a single worker thread waiting for the first task.
-    start_thread();
-
-    let (sender, receiver) = unbounded();
-    Pool { sender, receiver }
-});
-
-fn start_thread() {
-    SLEEPING.fetch_add(1, Ordering::SeqCst);
-    let timeout = Duration::from_secs(1);
-
-    thread::Builder::new()
-        .name("async-std/blocking".to_string())
-        .spawn(move || {
-            loop {
-                let mut task = match POOL.receiver.recv_timeout(timeout) {
-                    Ok(task) => task,
-                    Err(_) => {
-                        // Check whether this is the last sleeping thread.
-                        if SLEEPING.fetch_sub(1, Ordering::SeqCst) == 1 {
-                            // If so, then restart the thread to make sure there is always at least
-                            // one sleeping thread.
-                            if SLEEPING.compare_and_swap(0, 1, Ordering::SeqCst) == 0 {
-                                continue;
-                            }
-                        }
+/// Spawns a blocking task onto a named blocking thread pool.
+///
+/// This is like [`spawn_blocking`], except that the task runs on a pool of threads reserved for
+/// blocking tasks spawned with the same `pool` name. Separating blocking operations of different
+/// kinds, like file system access, DNS lookups, or hashing passwords, keeps a slow kind from
+/// occupying the threads the others need.
+///
+/// Named pools are started on first use. They have the same settings as the blocking pool of the
+/// runtime, except that their maximum number of threads can be configured separately with
+/// [`Builder::blocking_pool`]. Their threads are named after the pool, like
+/// `async-std/blocking/dns`.
+///
+/// [`spawn_blocking`]: fn.spawn_blocking.html
+/// [`Builder::blocking_pool`]: ../runtime/struct.Builder.html#method.blocking_pool
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::net::ToSocketAddrs;
+///
+/// use async_std::task;
+///
+/// let addrs = task::spawn_blocking_on("dns", || {
+///     ("localhost", 80).to_socket_addrs().map(|addrs| addrs.count())
+/// })
+/// .await;
+/// assert!(addrs.unwrap() > 0);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn spawn_blocking_on<F, T>(pool: &str, f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = executor::named_blocking(pool);
+    let (job, handle) = create(&pool, f);
+    let _ = pool.push(job, false);
+    handle
+}
 
-                        // Stop the thread.
-                        return;
-                    }
-                };
+/// Creates a blocking task that hasn't been scheduled yet.
+fn create<F, T>(pool: &Arc<BlockingPool>, f: F) -> (executor::Job, JoinHandle<T>)
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let future = async { panic::catch_unwind(AssertUnwindSafe(f)).map_err(JoinError::panic) };
+    create_task(pool, Task::new(None, Priority::Normal), future)
+}
 
-                // If there are no sleeping threads, then start one to make sure there is always at
-                // least one sleeping thread.
-                if SLEEPING.fetch_sub(1, Ordering::SeqCst) == 1 {
-                    start_thread();
-                }
+/// Creates a blocking task running a future that completes on its first poll.
+fn create_task<F, T>(
+    pool: &Arc<BlockingPool>,
+    task: Task,
+    future: F,
+) -> (executor::Job, JoinHandle<T>)
+where
+    F: Future<Output = Result<T, JoinError>> + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = pool.clone();
+    let schedule = move |job| {
+        let _ = pool.push(job, false);
+    };
+    let (job, handle) = async_task::spawn(future, schedule, task);
+    (job, JoinHandle::new(handle))
+}
 
-                loop {
-                    // Run the task.
-                    abort_on_panic(|| task.run());
+/// Spawns a blocking task built by [`Builder::spawn_blocking`].
+///
+/// [`Builder::spawn_blocking`]: struct.Builder.html#method.spawn_blocking
+#[cfg(feature = "unstable")]
+pub(crate) fn spawn_task<F, T>(task: Task, future: F) -> JoinHandle<T>
+where
+    F: Future<Output = Result<T, JoinError>> + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = executor::blocking();
+    let (job, handle) = create_task(&pool, task, future);
+    let _ = pool.push(job, false);
+    handle
+}
 
-                    // Try taking another task if there are any available.
-                    task = match POOL.receiver.try_recv() {
-                        Ok(task) => task,
-                        Err(_) => break,
-                    };
-                }
+#[cfg(feature = "unstable")]
+thread_local! {
+    /// The task that spawned the blocking closure running on this thread, if it is tracked.
+    static PARENT: RefCell<Option<Task>> = const { RefCell::new(None) };
+}
 
-                // If there is at least one sleeping thread, stop this thread instead of putting it
-                // to sleep.
-                if SLEEPING.load(Ordering::SeqCst) > 0 {
-                    return;
-                }
+/// Runs a blocking closure on behalf of the task that spawned it.
+#[cfg(feature = "unstable")]
+pub(crate) fn with_parent<F, T>(parent: Option<Task>, f: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    PARENT.with(|p| {
+        let old = p.replace(parent);
+        defer! {
+            p.replace(old);
+        }
+        f()
+    })
+}
 
-                SLEEPING.fetch_add(1, Ordering::SeqCst);
-            }
-        })
-        .expect("cannot start a blocking thread");
+/// Returns `true` if the task that spawned the running blocking closure has been aborted.
+#[cfg(feature = "unstable")]
+pub(crate) fn parent_is_aborted() -> bool {
+    PARENT
+        .try_with(|p| matches!(&*p.borrow(), Some(parent) if parent.is_aborted()))
+        .unwrap_or(false)
 }
@@ -0,0 +1,110 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::task::executor::{self, Pool};
+use crate::task::{Builder, Context, JoinHandle, Poll};
+
+/// Spawns a task with an async cleanup future that runs once the task is done.
+///
+/// Rust has no async `Drop`, so resources that need an async operation to be released, like
+/// locks held by a remote service or registrations in a directory, leak when the task owning them
+/// is cancelled. This function attaches such an operation to a task: `cleanup` runs after the
+/// task's future completes, and also when the task is aborted, cancelled, or panics.
+///
+/// If the future completes, the cleanup runs as part of the task, so the task's output is only
+/// available once the cleanup has finished. Otherwise, the cleanup is spawned as a separate task
+/// onto the same executor, or onto the global executor if that one is shutting down. A cleanup
+/// that is itself interrupted by an abort keeps running in a separate task as well.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+/// use std::time::Duration;
+///
+/// use async_std::task;
+///
+/// let released = Arc::new(AtomicBool::new(false));
+/// let r = released.clone();
+///
+/// let handle = task::spawn_with_cleanup(
+///     async {
+///         // Hold a lease until cancelled.
+///         task::sleep(Duration::from_secs(10)).await;
+///     },
+///     async move {
+///         // Release the lease.
+///         r.store(true, Ordering::SeqCst);
+///     },
+/// );
+///
+/// handle.cancel().await;
+/// while !released.load(Ordering::SeqCst) {
+///     task::yield_now().await;
+/// }
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[track_caller]
+pub fn spawn_with_cleanup<F, C, T>(future: F, cleanup: C) -> JoinHandle<T>
+where
+    F: Future<Output = T> + Send + 'static,
+    C: Future<Output = ()> + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = executor::current();
+    let mut guard = Cleanup {
+        future: Some(Box::pin(cleanup)),
+        pool: pool.clone(),
+    };
+
+    let future = async move {
+        let output = future.await;
+        guard.run().await;
+        output
+    };
+    Builder::new().spawn_on(pool, future)
+}
+
+/// Runs a cleanup future, or spawns it if dropped before the future completes.
+struct Cleanup {
+    /// The cleanup future, until it has completed.
+    future: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+
+    /// The executor the task was spawned onto.
+    pool: Arc<Pool>,
+}
+
+impl Cleanup {
+    /// Runs the cleanup future to completion.
+    async fn run(&mut self) {
+        crate::future::poll_fn(|cx: &mut Context<'_>| {
+            if let Some(future) = &mut self.future {
+                futures_core::ready!(future.as_mut().poll(cx));
+                self.future = None;
+            }
+            Poll::Ready(())
+        })
+        .await
+    }
+}
+
+impl Drop for Cleanup {
+    fn drop(&mut self) {
+        // The cleanup hasn't completed, so the task was interrupted.
+        if let Some(future) = self.future.take() {
+            let pool = if self.pool.is_closed() {
+                executor::start_global()
+            } else {
+                self.pool.clone()
+            };
+            drop(Builder::new().spawn_on(pool, future));
+        }
+    }
+}
@@ -1,11 +1,17 @@
 use std::cell::Cell;
 use std::fmt;
 use std::mem::ManuallyDrop;
+#[cfg(feature = "unstable")]
+use std::panic::Location;
 use std::ptr;
-use std::sync::atomic::{AtomicPtr, Ordering};
+#[cfg(feature = "unstable")]
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::{AtomicBool, AtomicPtr, Ordering};
 use std::sync::Arc;
 
-use crate::task::{LocalsMap, TaskId};
+#[cfg(feature = "unstable")]
+use crate::task::TaskState;
+use crate::task::{LocalsMap, Priority, TaskId};
 use crate::utils::abort_on_panic;
 
 thread_local! {
@@ -21,17 +27,38 @@ struct Inner {
     /// The optional task name.
     name: Option<Box<str>>,
 
+    /// The scheduling priority hint.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    priority: Priority,
+
     /// The map holding task-local values.
     locals: LocalsMap,
+
+    /// Set when the task has been asked to stop at its next yield point.
+    aborted: AtomicBool,
+
+    /// The location the task was spawned at, if the task is reported by task dumps.
+    #[cfg(feature = "unstable")]
+    location: Option<&'static Location<'static>>,
+
+    /// The scheduling state reported by task dumps.
+    #[cfg(feature = "unstable")]
+    state: AtomicU8,
 }
 
 impl Inner {
     #[inline]
-    fn new(name: Option<String>) -> Inner {
+    fn new(name: Option<String>, priority: Priority) -> Inner {
         Inner {
             id: TaskId::generate(),
             name: name.map(String::into_boxed_str),
+            priority,
             locals: LocalsMap::new(),
+            aborted: AtomicBool::new(false),
+            #[cfg(feature = "unstable")]
+            location: None,
+            #[cfg(feature = "unstable")]
+            state: AtomicU8::new(TaskState::Scheduled as u8),
         }
     }
 }
@@ -51,20 +78,35 @@ unsafe impl Sync for Task {}
 impl Task {
     /// Creates a new task handle.
     ///
-    /// If the task is unnamed, the inner representation of the task will be lazily allocated on
-    /// demand.
+    /// If the task is unnamed and has the default priority, the inner representation of the task
+    /// will be lazily allocated on demand.
     #[inline]
-    pub(crate) fn new(name: Option<String>) -> Task {
-        let inner = match name {
-            None => AtomicPtr::default(),
-            Some(name) => {
-                let raw = Arc::into_raw(Arc::new(Inner::new(Some(name))));
+    pub(crate) fn new(name: Option<String>, priority: Priority) -> Task {
+        let inner = match (name, priority) {
+            (None, Priority::Normal) => AtomicPtr::default(),
+            (name, priority) => {
+                let raw = Arc::into_raw(Arc::new(Inner::new(name, priority)));
                 AtomicPtr::new(raw as *mut Inner)
             }
         };
         Task { inner }
     }
 
+    /// Creates a new task handle that records where the task was spawned.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn with_location(
+        name: Option<String>,
+        priority: Priority,
+        location: &'static Location<'static>,
+    ) -> Task {
+        let mut inner = Inner::new(name, priority);
+        inner.location = Some(location);
+        let raw = Arc::into_raw(Arc::new(inner));
+        Task {
+            inner: AtomicPtr::new(raw as *mut Inner),
+        }
+    }
+
     /// Gets the task's unique identifier.
     #[inline]
     pub fn id(&self) -> TaskId {
@@ -80,11 +122,75 @@ impl Task {
         self.inner().name.as_ref().map(|s| &**s)
     }
 
+    /// Returns the scheduling priority hint of this task.
+    ///
+    /// The priority is configured by [`Builder::priority`] before spawning.
+    ///
+    /// [`Builder::priority`]: struct.Builder.html#method.priority
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn priority(&self) -> Priority {
+        self.inner().priority
+    }
+
     /// Returns the map holding task-local values.
     pub(crate) fn locals(&self) -> &LocalsMap {
         &self.inner().locals
     }
 
+    /// Asks the task to stop at its next yield point.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn abort(&self) {
+        self.inner().aborted.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if the task has been asked to stop.
+    ///
+    /// This doesn't initialize the inner representation if it's not initialized yet.
+    #[inline]
+    pub(crate) fn is_aborted(&self) -> bool {
+        let raw = self.inner.load(Ordering::Acquire);
+        match unsafe { raw.as_ref() } {
+            None => false,
+            Some(inner) => inner.aborted.load(Ordering::SeqCst),
+        }
+    }
+
+    /// Returns the location the task was spawned at.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn location(&self) -> Option<&'static Location<'static>> {
+        self.inner().location
+    }
+
+    /// Returns the scheduling state of the task.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn state(&self) -> TaskState {
+        TaskState::from_u8(self.inner().state.load(Ordering::SeqCst))
+    }
+
+    /// Sets the scheduling state of the task.
+    ///
+    /// When `from` is given, the state is only set if it currently equals `from`. This doesn't
+    /// initialize the inner representation if it's not initialized yet.
+    #[cfg(feature = "unstable")]
+    #[inline]
+    pub(crate) fn set_state(&self, from: Option<TaskState>, to: TaskState) {
+        let raw = self.inner.load(Ordering::Acquire);
+        if let Some(inner) = unsafe { raw.as_ref() } {
+            match from {
+                None => inner.state.store(to as u8, Ordering::SeqCst),
+                Some(from) => {
+                    let _ = inner.state.compare_exchange(
+                        from as u8,
+                        to as u8,
+                        Ordering::SeqCst,
+                        Ordering::SeqCst,
+                    );
+                }
+            }
+        }
+    }
+
     /// Drops all task-local values.
     ///
     /// This method is only safe to call at the end of the task.
@@ -107,7 +213,7 @@ impl Task {
                 return unsafe { &*raw };
             }
 
-            let new = Arc::into_raw(Arc::new(Inner::new(None))) as *mut Inner;
+            let new = Arc::into_raw(Arc::new(Inner::new(None, Priority::Normal))) as *mut Inner;
             if self.inner.compare_and_swap(raw, new, Ordering::AcqRel) != raw {
                 unsafe {
                     drop(Arc::from_raw(new));
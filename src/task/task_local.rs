@@ -1,9 +1,20 @@
 use std::cell::UnsafeCell;
 use std::error::Error;
 use std::fmt;
+#[cfg(feature = "unstable")]
+use std::future::Future;
+#[cfg(feature = "unstable")]
+use std::mem;
+#[cfg(feature = "unstable")]
+use std::pin::Pin;
 use std::sync::atomic::{AtomicU32, Ordering};
 
+#[cfg(feature = "unstable")]
+use pin_project_lite::pin_project;
+
 use crate::task::Task;
+#[cfg(feature = "unstable")]
+use crate::task::{Context, Poll};
 
 /// The key for accessing a task-local value.
 ///
@@ -112,9 +123,68 @@ impl<T: Send + 'static> LocalKey<T> {
         .ok_or(AccessError { _private: () })
     }
 
+    /// Runs a future with the task-local set to `value`.
+    ///
+    /// While the future is being polled, the task-local holds `value`, and the value it had
+    /// before is restored in between polls and once the future completes. Scopes can be nested,
+    /// and changes made through interior mutability stay with the scope they were made in.
+    ///
+    /// This makes it possible to set context such as a request ID for a part of a task only,
+    /// across `.await` points. Tasks spawned from within the scope don't see the value unless it
+    /// is passed on with [`Builder::inherit`].
+    ///
+    /// [`Builder::inherit`]: struct.Builder.html#method.inherit
+    ///
+    /// # Panics
+    ///
+    /// The returned future panics if it is polled outside the context of a task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::prelude::*;
+    /// use async_std::task;
+    ///
+    /// task_local! {
+    ///     static REQUEST_ID: u64 = 0;
+    /// }
+    ///
+    /// async fn log(msg: &str) -> String {
+    ///     task::yield_now().await;
+    ///     format!("[{}] {}", REQUEST_ID.with(|id| *id), msg)
+    /// }
+    ///
+    /// let msg = REQUEST_ID.scope(42, log("handled")).await;
+    /// assert_eq!(msg, "[42] handled");
+    /// assert_eq!(log("idle").await, "[0] idle");
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn scope<F: Future>(&'static self, value: T, future: F) -> LocalScope<T, F> {
+        LocalScope {
+            key: self,
+            value: Box::new(value),
+            future,
+        }
+    }
+
+    /// Swaps the task-local value of the current task with `value`.
+    #[cfg(feature = "unstable")]
+    fn swap(&'static self, value: &mut Box<dyn Send>) {
+        Task::get_current(|task| {
+            let init = || Box::new((self.__init)()) as Box<dyn Send>;
+            task.locals().swap(self.key(), value, init);
+        })
+        .expect("`LocalKey::scope` polled outside the context of a task")
+    }
+
     /// Returns the numeric key associated with this task-local.
     #[inline]
-    fn key(&self) -> u32 {
+    pub(crate) fn key(&self) -> u32 {
         #[cold]
         fn init(key: &AtomicU32) -> u32 {
             static COUNTER: AtomicU32 = AtomicU32::new(1);
@@ -137,6 +207,53 @@ impl<T: Send + 'static> LocalKey<T> {
     }
 }
 
+#[cfg(feature = "unstable")]
+pin_project! {
+    /// A future running with a task-local set to a value.
+    ///
+    /// This future is created by the [`scope`] method on [`LocalKey`]. See its documentation for
+    /// more.
+    ///
+    /// [`scope`]: struct.LocalKey.html#method.scope
+    /// [`LocalKey`]: struct.LocalKey.html
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct LocalScope<T, F>
+    where
+        T: Send,
+        T: 'static,
+    {
+        key: &'static LocalKey<T>,
+        value: Box<dyn Send>,
+        #[pin]
+        future: F,
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: Send + 'static, F: Future> Future for LocalScope<T, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = self.project();
+        let key = *this.key;
+        let value = this.value;
+
+        key.swap(value);
+        defer! {
+            key.swap(value);
+        }
+        this.future.poll(cx)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: Send + 'static, F> fmt::Debug for LocalScope<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("LocalScope { .. }")
+    }
+}
+
 /// An error returned by [`LocalKey::try_with`].
 ///
 /// [`LocalKey::try_with`]: struct.LocalKey.html#method.try_with
@@ -201,6 +318,25 @@ impl LocalsMap {
         }
     }
 
+    /// Swaps the task-local value associated with `key`, inserting one constructed by `init`
+    /// first if there is none.
+    #[cfg(feature = "unstable")]
+    pub fn swap(&self, key: u32, value: &mut Box<dyn Send>, init: impl FnOnce() -> Box<dyn Send>) {
+        match unsafe { (*self.entries.get()).as_mut() } {
+            None => panic!("can't access task-locals while the task is being dropped"),
+            Some(entries) => {
+                let index = match entries.binary_search_by_key(&key, |e| e.key) {
+                    Ok(i) => i,
+                    Err(i) => {
+                        entries.insert(i, Entry { key, value: init() });
+                        i
+                    }
+                };
+                mem::swap(&mut entries[index].value, value);
+            }
+        }
+    }
+
     /// Clears the map and drops all task-locals.
     ///
     /// This method is only safe to call at the end of the task.
@@ -9,9 +9,16 @@ use crate::task::{Context, Poll};
 /// of the execution queue, making room for other futures to execute. This is
 /// especially useful after running CPU-intensive operations inside a future.
 ///
+/// Tasks that are ready to run on the same worker thread are guaranteed to run
+/// before the yielding task resumes.
+///
+/// Yielding doesn't reset the budget that operations on resources like channels
+/// consume. To opt a future out of budgeting, wrap it in [`task::unconstrained`].
+///
 /// See also [`task::spawn_blocking`].
 ///
 /// [`task::spawn_blocking`]: fn.spawn_blocking.html
+/// [`task::unconstrained`]: fn.unconstrained.html
 ///
 /// # Examples
 ///
@@ -38,10 +45,13 @@ impl Future for YieldNow {
 
     // The futures executor is implemented as a FIFO queue, so all this future
     // does is re-schedule the future back to the end of the queue, giving room
-    // for other futures to progress.
+    // for other futures to progress. The executor is told first, so that it
+    // doesn't put the task into the slot that runs the next task right away.
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         if !self.0 {
             self.0 = true;
+            #[cfg(feature = "default")]
+            crate::task::executor::yield_current();
             cx.waker().wake_by_ref();
             Poll::Pending
         } else {
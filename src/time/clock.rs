@@ -0,0 +1,248 @@
+// Timers don't wait on the clock on `wasm32`, where they wait on the JavaScript event loop.
+#![cfg_attr(target_arch = "wasm32", allow(dead_code))]
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+
+/// The source of time of an executor.
+///
+/// The clock follows the system clock until it is paused. While paused, it only moves forward
+/// when advanced, and timers waiting on it are fired by the clock itself instead of the timer
+/// thread.
+pub(crate) struct Clock {
+    /// Set while the clock is paused.
+    paused: AtomicBool,
+
+    /// Set once the clock has been paused, after which it may run ahead of the system clock.
+    shifted: AtomicBool,
+
+    state: Mutex<State>,
+}
+
+struct State {
+    /// The time the clock is frozen at while paused.
+    frozen: Option<Instant>,
+
+    /// How far the clock runs ahead of the system clock while not paused.
+    offset: Duration,
+
+    /// Timers waiting for the clock to be advanced, keyed by deadline and registration order.
+    timers: BTreeMap<(Instant, u64), Waker>,
+
+    /// The key of the next registered timer.
+    next_key: u64,
+
+    /// Threads waiting for the executor to become idle so that they can advance the clock.
+    drivers: Vec<Waker>,
+}
+
+/// Clock used outside of custom runtimes.
+static CLOCK: Lazy<Arc<Clock>> = Lazy::new(|| Arc::new(Clock::new()));
+
+/// Returns the global clock.
+pub(crate) fn global_clock() -> Arc<Clock> {
+    CLOCK.clone()
+}
+
+impl Clock {
+    /// Creates a clock following the system clock.
+    pub(crate) fn new() -> Clock {
+        Clock {
+            paused: AtomicBool::new(false),
+            shifted: AtomicBool::new(false),
+            state: Mutex::new(State {
+                frozen: None,
+                offset: Duration::from_secs(0),
+                timers: BTreeMap::new(),
+                next_key: 0,
+                drivers: Vec::new(),
+            }),
+        }
+    }
+
+    /// Returns the current time.
+    pub(crate) fn now(&self) -> Instant {
+        if !self.shifted.load(Ordering::SeqCst) {
+            return Instant::now();
+        }
+        let state = self.state.lock().unwrap();
+        state
+            .frozen
+            .unwrap_or_else(|| Instant::now() + state.offset)
+    }
+
+    /// Returns `true` if the clock is paused.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Freezes the clock at the current time.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn pause(&self) {
+        let mut state = self.state.lock().unwrap();
+        if state.frozen.is_none() {
+            state.frozen = Some(Instant::now() + state.offset);
+            self.shifted.store(true, Ordering::SeqCst);
+            self.paused.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Lets the clock follow the system clock again, starting from the time it was frozen at.
+    ///
+    /// Timers waiting on the clock are woken up so that they wait on the timer thread instead.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn resume(&self) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(frozen) = state.frozen.take() {
+            state.offset = frozen.saturating_duration_since(Instant::now());
+            self.paused.store(false, Ordering::SeqCst);
+
+            for (_, waker) in mem::take(&mut state.timers) {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Moves the paused clock forward, firing the timers that are due.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the clock is not paused.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn advance(&self, dur: Duration) {
+        let mut state = self.state.lock().unwrap();
+        let now = state.frozen.expect("the clock is not paused") + dur;
+        state.advance_to(now);
+    }
+
+    /// Polls a timer waiting for `deadline`.
+    ///
+    /// Returns `None` if the clock is not paused, in which case the timer must wait on the timer
+    /// thread. Otherwise, the timer is registered under `key` until it is due.
+    pub(crate) fn poll_timer(
+        &self,
+        deadline: Instant,
+        key: &mut Option<u64>,
+        waker: &Waker,
+    ) -> Option<Poll<()>> {
+        if !self.is_paused() {
+            // Timers are dropped from the clock when it is resumed.
+            *key = None;
+            return None;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let now = match state.frozen {
+            Some(now) => now,
+            None => {
+                *key = None;
+                return None;
+            }
+        };
+
+        if now >= deadline {
+            if let Some(key) = key.take() {
+                state.timers.remove(&(deadline, key));
+            }
+            return Some(Poll::Ready(()));
+        }
+
+        let k = *key.get_or_insert_with(|| {
+            state.next_key += 1;
+            state.next_key
+        });
+        match state.timers.get_mut(&(deadline, k)) {
+            Some(w) if w.will_wake(waker) => {}
+            Some(w) => *w = waker.clone(),
+            None => {
+                state.timers.insert((deadline, k), waker.clone());
+            }
+        }
+        Some(Poll::Pending)
+    }
+
+    /// Unregisters a timer that is no longer waiting for `deadline`.
+    pub(crate) fn remove_timer(&self, deadline: Instant, key: u64) {
+        let mut state = self.state.lock().unwrap();
+        state.timers.remove(&(deadline, key));
+    }
+
+    /// Advances the paused clock to the next timer if the executor has nothing else to do.
+    ///
+    /// The `waker` is registered to be woken when a worker thread of the executor goes idle, so
+    /// that the caller can try again before blocking.
+    ///
+    /// Returns `true` if the clock has been advanced.
+    #[cfg(feature = "unstable")]
+    pub(crate) fn auto_advance(&self, waker: &Waker, idle: impl FnOnce() -> bool) -> bool {
+        if !self.is_paused() {
+            return false;
+        }
+
+        {
+            let mut state = self.state.lock().unwrap();
+            if !state.drivers.iter().any(|w| w.will_wake(waker)) {
+                state.drivers.push(waker.clone());
+            }
+        }
+
+        // The driver is registered before checking, so a worker thread going idle right after
+        // the check will wake it up.
+        if !idle() {
+            return false;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        let next = match state.timers.keys().next() {
+            Some(&(deadline, _)) => deadline,
+            None => return false,
+        };
+        let now = state.frozen.map_or(next, |now| now.max(next));
+        state.advance_to(now);
+        true
+    }
+
+    /// Wakes up the threads waiting to advance the paused clock.
+    ///
+    /// Called by worker threads right before they go to sleep.
+    pub(crate) fn notify_idle(&self) {
+        if self.is_paused() {
+            let drivers = mem::take(&mut self.state.lock().unwrap().drivers);
+            for waker in drivers {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl State {
+    /// Moves the frozen time forward to `now` and wakes the timers that are due.
+    fn advance_to(&mut self, now: Instant) {
+        self.frozen = Some(now);
+
+        while let Some(&(deadline, key)) = self.timers.keys().next() {
+            if deadline > now {
+                break;
+            }
+            if let Some(waker) = self.timers.remove(&(deadline, key)) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clock")
+            .field("paused", &self.is_paused())
+            .finish()
+    }
+}
@@ -0,0 +1,168 @@
+use std::cell::Cell;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use pin_project_lite::pin_project;
+
+use crate::future::TimeoutError;
+use crate::task::executor;
+use crate::time::Delay;
+
+thread_local! {
+    /// The deadline of the scope the current future is polled in.
+    static CURRENT: Cell<Option<Instant>> = const { Cell::new(None) };
+}
+
+/// A point in time by which an operation must complete.
+///
+/// A deadline is set for an operation by running it in a [`scope`]. Code running inside the scope,
+/// however deeply nested, can then look up how much time is left with [`Deadline::current`]
+/// instead of having a timeout passed down through every call. Nested scopes can shorten the
+/// deadline but never extend it.
+///
+/// Spawned tasks don't inherit the deadline; the current deadline can be passed on explicitly by
+/// running the spawned future in its own scope.
+///
+/// Deadlines are measured on the clock of the current runtime. See [`time::now`].
+///
+/// [`scope`]: #method.scope
+/// [`Deadline::current`]: #method.current
+/// [`time::now`]: fn.now.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::task;
+/// use async_std::time::Deadline;
+///
+/// async fn query() -> Option<Duration> {
+///     // Deep inside a service, see how much time the caller has left.
+///     Deadline::current().map(|d| d.remaining())
+/// }
+///
+/// let deadline = Deadline::after(Duration::from_secs(5));
+/// let remaining = deadline.scope(query()).await.unwrap().unwrap();
+/// assert!(remaining <= Duration::from_secs(5));
+///
+/// // Pass the deadline on to a spawned task.
+/// let deadline = Deadline::after(Duration::from_secs(5));
+/// let handle = task::spawn(deadline.scope(query()));
+/// assert!(handle.await.unwrap().is_some());
+///
+/// assert!(Deadline::current().is_none());
+/// #
+/// # })
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Deadline {
+    instant: Instant,
+}
+
+impl Deadline {
+    /// Creates a deadline at an instant.
+    pub fn at(instant: Instant) -> Deadline {
+        Deadline { instant }
+    }
+
+    /// Creates a deadline a duration from now.
+    pub fn after(dur: Duration) -> Deadline {
+        Deadline::at(executor::clock().now() + dur)
+    }
+
+    /// Returns the deadline of the scope the current future is running in, if any.
+    pub fn current() -> Option<Deadline> {
+        CURRENT
+            .try_with(|current| current.get())
+            .ok()
+            .flatten()
+            .map(Deadline::at)
+    }
+
+    /// Returns the instant of the deadline.
+    pub fn instant(&self) -> Instant {
+        self.instant
+    }
+
+    /// Returns how much time is left until the deadline, or zero if it has passed.
+    pub fn remaining(&self) -> Duration {
+        self.instant
+            .saturating_duration_since(executor::clock().now())
+    }
+
+    /// Returns `true` if the deadline has passed.
+    pub fn is_expired(&self) -> bool {
+        executor::clock().now() >= self.instant
+    }
+
+    /// Runs a future with this deadline, timing out if it doesn't complete by then.
+    ///
+    /// While the future runs, [`Deadline::current`] returns this deadline, or the deadline of the
+    /// enclosing scope if that one is earlier.
+    ///
+    /// [`Deadline::current`]: #method.current
+    pub fn scope<F: Future>(self, future: F) -> DeadlineScope<F> {
+        DeadlineScope {
+            future,
+            instant: self.instant,
+            delay: None,
+        }
+    }
+}
+
+pin_project! {
+    /// A future running with a deadline.
+    ///
+    /// This future is created by the [`scope`] method on [`Deadline`]. See its documentation for
+    /// more.
+    ///
+    /// [`scope`]: struct.Deadline.html#method.scope
+    /// [`Deadline`]: struct.Deadline.html
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct DeadlineScope<F> {
+        #[pin]
+        future: F,
+        instant: Instant,
+        delay: Option<Delay>,
+    }
+}
+
+impl<F: Future> Future for DeadlineScope<F> {
+    type Output = Result<F::Output, TimeoutError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        // The deadline is fixed on the first poll, once the enclosing scope is known.
+        if this.delay.is_none() {
+            if let Some(outer) = Deadline::current() {
+                *this.instant = (*this.instant).min(outer.instant);
+            }
+            *this.delay = Some(Delay::at(*this.instant));
+        }
+        let instant = *this.instant;
+        let future = this.future;
+
+        let poll = CURRENT.with(|current| {
+            let old = current.replace(Some(instant));
+            defer! {
+                current.set(old);
+            }
+            future.poll(cx)
+        });
+        if let Poll::Ready(v) = poll {
+            return Poll::Ready(Ok(v));
+        }
+
+        match Pin::new(this.delay.as_mut().unwrap()).poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(TimeoutError::new(()))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
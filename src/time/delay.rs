@@ -0,0 +1,144 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "tracing")]
+use crate::task::Task;
+use crate::task::executor;
+use crate::time::Clock;
+use crate::time::wheel::{self, Timer};
+
+/// A future that completes at a deadline of the clock it was created on.
+///
+/// The timer waits on the timer wheel while the clock follows the system clock, and on the clock
+/// itself while it is paused. The timer wheel is the one of the current-thread runtime driven by
+/// the thread the timer is created on, if any, and the global one otherwise.
+pub(crate) struct Delay {
+    clock: Arc<Clock>,
+    deadline: Instant,
+
+    /// The timer wheel the timer registers in.
+    timer: Arc<Timer>,
+
+    /// The key of the timer registered in the timer wheel, if any.
+    entry: Option<usize>,
+
+    /// The registration of the timer waiting on the paused clock, if any.
+    key: Option<u64>,
+}
+
+impl Delay {
+    /// Creates a timer that completes after `dur` on the clock of the current executor.
+    pub(crate) fn new(dur: Duration) -> Delay {
+        let mut delay = Delay::at(Instant::now());
+        delay.deadline = delay.now() + dur;
+        delay
+    }
+
+    /// Creates a timer that completes at `deadline` on the clock of the current executor.
+    pub(crate) fn at(deadline: Instant) -> Delay {
+        Delay {
+            clock: executor::clock(),
+            deadline,
+            timer: wheel::current(),
+            entry: None,
+            key: None,
+        }
+    }
+
+    /// Returns the deadline of the timer.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn when(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns the current time on the clock of the timer.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Changes the deadline of the timer.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn reset(&mut self, deadline: Instant) {
+        self.unregister();
+        self.cancel();
+        self.deadline = deadline;
+    }
+
+    /// Removes the timer from the paused clock.
+    fn unregister(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.clock.remove_timer(self.deadline, key);
+        }
+    }
+
+    /// Removes the timer from the timer wheel.
+    fn cancel(&mut self) {
+        if let Some(entry) = self.entry.take() {
+            self.timer.cancel(entry);
+        }
+    }
+
+    /// Checks whether the timer has fired, registering it if it hasn't been yet.
+    fn poll_fire(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if let Some(poll) = self
+            .clock
+            .poll_timer(self.deadline, &mut self.key, cx.waker())
+        {
+            self.cancel();
+            return poll;
+        }
+
+        match self.entry {
+            Some(entry) => self.timer.poll(entry, cx.waker()),
+            None => {
+                // The clock may run ahead of the system clock after having been paused.
+                let remaining = self.deadline.saturating_duration_since(self.clock.now());
+                match self.timer.register(Instant::now() + remaining, cx.waker()) {
+                    Some(entry) => {
+                        self.entry = Some(entry);
+                        Poll::Pending
+                    }
+                    None => Poll::Ready(()),
+                }
+            }
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let poll = self.poll_fire(cx);
+
+        #[cfg(feature = "tracing")]
+        if poll.is_ready() {
+            tracing::trace!(
+                task_id = Task::get_current(|t| t.id().0).unwrap_or(0),
+                "timer fired"
+            );
+        }
+
+        poll
+    }
+}
+
+impl Drop for Delay {
+    fn drop(&mut self) {
+        self.unregister();
+        self.cancel();
+    }
+}
+
+impl fmt::Debug for Delay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Delay")
+            .field("deadline", &self.deadline)
+            .finish()
+    }
+}
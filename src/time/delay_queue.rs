@@ -0,0 +1,284 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use slab::Slab;
+
+use crate::stream::Stream;
+use crate::time::Delay;
+
+/// A queue of values that are yielded once their deadlines have passed.
+///
+/// Values are inserted with a timeout or a deadline, and the queue is a [`Stream`] yielding them
+/// as [`Expired`] entries in the order their deadlines pass. Each inserted value is identified
+/// by a [`Key`], which can be used to remove it or move its deadline before it expires.
+///
+/// All values share a single timer, so that tracking many deadlines, such as the idle timeouts
+/// of connections or the expiration times of cache entries, stays cheap.
+///
+/// The stream yields `None` while the queue is empty, but it can be polled again after more
+/// values have been inserted.
+///
+/// [`Stream`]: ../stream/trait.Stream.html
+/// [`Expired`]: struct.Expired.html
+/// [`Key`]: struct.Key.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::prelude::*;
+/// use async_std::time::DelayQueue;
+///
+/// let mut queue = DelayQueue::new();
+/// queue.insert("b", Duration::from_millis(20));
+/// let a = queue.insert("a", Duration::from_millis(10));
+/// let c = queue.insert("c", Duration::from_millis(10));
+///
+/// queue.remove(&a);
+/// queue.reset(&c, Duration::from_millis(30));
+///
+/// assert_eq!(queue.next().await.unwrap().into_inner(), "b");
+/// assert_eq!(queue.next().await.unwrap().into_inner(), "c");
+/// assert!(queue.next().await.is_none());
+/// #
+/// # })
+/// ```
+pub struct DelayQueue<T> {
+    /// The values in the queue and their deadlines.
+    entries: Slab<(T, Instant)>,
+
+    /// The keys of the values ordered by deadline.
+    deadlines: BTreeSet<(Instant, usize)>,
+
+    /// The timer waiting for the earliest deadline.
+    delay: Delay,
+
+    /// The task polling the queue, woken when the earliest deadline moves.
+    waker: Option<Waker>,
+}
+
+/// A key identifying a value in a [`DelayQueue`].
+///
+/// [`DelayQueue`]: struct.DelayQueue.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Key(usize);
+
+/// A value yielded by a [`DelayQueue`] once its deadline has passed.
+///
+/// [`DelayQueue`]: struct.DelayQueue.html
+#[derive(Debug)]
+pub struct Expired<T> {
+    value: T,
+    deadline: Instant,
+    key: Key,
+}
+
+impl<T> Expired<T> {
+    /// Returns a reference to the value.
+    pub fn get_ref(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns a mutable reference to the value.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Consumes the entry, returning the value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the deadline the value expired at.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Returns the key the value was inserted under.
+    ///
+    /// The key is no longer valid, and may be reused by values inserted afterwards.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+}
+
+impl<T> DelayQueue<T> {
+    /// Creates an empty queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::DelayQueue;
+    ///
+    /// let queue: DelayQueue<u32> = DelayQueue::new();
+    /// assert!(queue.is_empty());
+    /// ```
+    pub fn new() -> DelayQueue<T> {
+        DelayQueue::with_capacity(0)
+    }
+
+    /// Creates an empty queue with space for at least `capacity` values.
+    pub fn with_capacity(capacity: usize) -> DelayQueue<T> {
+        DelayQueue {
+            entries: Slab::with_capacity(capacity),
+            deadlines: BTreeSet::new(),
+            delay: Delay::new(Duration::from_secs(0)),
+            waker: None,
+        }
+    }
+
+    /// Inserts a value that expires after `timeout`.
+    ///
+    /// Returns the key identifying the value in the queue.
+    pub fn insert(&mut self, value: T, timeout: Duration) -> Key {
+        let deadline = self.delay.now() + timeout;
+        self.insert_at(value, deadline)
+    }
+
+    /// Inserts a value that expires at `deadline`.
+    ///
+    /// Returns the key identifying the value in the queue.
+    pub fn insert_at(&mut self, value: T, deadline: Instant) -> Key {
+        let key = self.entries.insert((value, deadline));
+        self.deadlines.insert((deadline, key));
+        self.wake_if_first(deadline, key);
+        Key(key)
+    }
+
+    /// Removes a value from the queue before it expires.
+    ///
+    /// Returns `None` if the key doesn't identify a value in the queue.
+    pub fn remove(&mut self, key: &Key) -> Option<T> {
+        if !self.entries.contains(key.0) {
+            return None;
+        }
+        let (value, deadline) = self.entries.remove(key.0);
+        self.deadlines.remove(&(deadline, key.0));
+        Some(value)
+    }
+
+    /// Moves the deadline of a value to `timeout` from now.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the key doesn't identify a value in the queue.
+    pub fn reset(&mut self, key: &Key, timeout: Duration) {
+        let deadline = self.delay.now() + timeout;
+        self.reset_at(key, deadline);
+    }
+
+    /// Moves the deadline of a value to `deadline`.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the key doesn't identify a value in the queue.
+    pub fn reset_at(&mut self, key: &Key, deadline: Instant) {
+        let entry = self
+            .entries
+            .get_mut(key.0)
+            .expect("invalid key for the delay queue");
+        let old = mem::replace(&mut entry.1, deadline);
+        self.deadlines.remove(&(old, key.0));
+        self.deadlines.insert((deadline, key.0));
+        self.wake_if_first(deadline, key.0);
+    }
+
+    /// Returns the deadline of a value, or `None` if the key doesn't identify a value in the
+    /// queue.
+    pub fn deadline(&self, key: &Key) -> Option<Instant> {
+        self.entries.get(key.0).map(|(_, deadline)| *deadline)
+    }
+
+    /// Returns the number of values in the queue.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the queue contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all values from the queue.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.deadlines.clear();
+    }
+
+    /// Polls for the next value whose deadline has passed.
+    ///
+    /// Returns `Poll::Ready(None)` if the queue is empty.
+    pub fn poll_expired(&mut self, cx: &mut Context<'_>) -> Poll<Option<Expired<T>>> {
+        loop {
+            let (deadline, key) = match self.deadlines.iter().next() {
+                Some(&first) => first,
+                None => {
+                    self.waker = None;
+                    return Poll::Ready(None);
+                }
+            };
+
+            if self.delay.now() >= deadline {
+                self.deadlines.remove(&(deadline, key));
+                let (value, _) = self.entries.remove(key);
+                return Poll::Ready(Some(Expired {
+                    value,
+                    deadline,
+                    key: Key(key),
+                }));
+            }
+
+            if self.delay.when() != deadline {
+                self.delay.reset(deadline);
+            }
+            if Pin::new(&mut self.delay).poll(cx).is_pending() {
+                match &self.waker {
+                    Some(w) if w.will_wake(cx.waker()) => {}
+                    _ => self.waker = Some(cx.waker().clone()),
+                }
+                return Poll::Pending;
+            }
+        }
+    }
+
+    /// Wakes the polling task if `deadline` is now the earliest one, so that the timer is moved.
+    fn wake_if_first(&mut self, deadline: Instant, key: usize) {
+        if self.deadlines.iter().next() == Some(&(deadline, key)) {
+            if let Some(w) = self.waker.take() {
+                w.wake();
+            }
+        }
+    }
+}
+
+impl<T> Default for DelayQueue<T> {
+    fn default() -> DelayQueue<T> {
+        DelayQueue::new()
+    }
+}
+
+impl<T> Unpin for DelayQueue<T> {}
+
+impl<T> Stream for DelayQueue<T> {
+    type Item = Expired<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_expired(cx)
+    }
+}
+
+impl<T> fmt::Debug for DelayQueue<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DelayQueue")
+            .field("len", &self.len())
+            .finish()
+    }
+}
@@ -0,0 +1,214 @@
+//! Timers on the JavaScript event loop.
+//!
+//! On `wasm32` there are no threads to drive a timer wheel, and the standard clock is not
+//! available, so timers wait on `setTimeout` instead.
+
+use std::cell::RefCell;
+use std::cmp;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+use slab::Slab;
+use wasm_bindgen::prelude::*;
+
+#[cfg(feature = "tracing")]
+use crate::task::Task;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_name = setTimeout)]
+    fn set_timeout(handler: &Callback, timeout: i32) -> JsValue;
+
+    #[wasm_bindgen(js_name = clearTimeout)]
+    fn clear_timeout(handle: &JsValue);
+}
+
+/// The longest timeout `setTimeout` accepts, in milliseconds.
+///
+/// Longer timeouts fire immediately, so longer delays wait on several timeouts in a row.
+const MAX_TIMEOUT: u128 = i32::MAX as u128;
+
+/// The closure called when a timeout fires.
+type Callback = Closure<dyn FnMut()>;
+
+thread_local! {
+    /// The timeouts that haven't been cleared yet, with the closures they call.
+    static TIMEOUTS: RefCell<Slab<(JsValue, Callback)>> = RefCell::new(Slab::new());
+}
+
+/// A future that completes after a duration, waiting on `setTimeout`.
+pub(crate) struct Delay {
+    /// The deadline of the timer, if it was created from one.
+    deadline: Option<Instant>,
+
+    /// The time left to wait once the current timeout fires.
+    remaining: Duration,
+
+    /// The timeout being waited on, if any.
+    timeout: Option<Timeout>,
+}
+
+impl Delay {
+    /// Creates a timer that completes after `dur`.
+    pub(crate) fn new(dur: Duration) -> Delay {
+        Delay {
+            deadline: None,
+            remaining: dur,
+            timeout: None,
+        }
+    }
+
+    /// Creates a timer that completes at `deadline`.
+    ///
+    /// Like the other methods dealing with instants, this only works where the standard clock
+    /// is available, which isn't the case on `wasm32-unknown-unknown`.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn at(deadline: Instant) -> Delay {
+        let mut delay = Delay::new(Duration::from_secs(0));
+        delay.reset(deadline);
+        delay
+    }
+
+    /// Returns the deadline of the timer.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn when(&self) -> Instant {
+        self.deadline.unwrap_or_else(|| self.now() + self.remaining)
+    }
+
+    /// Returns the current time.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// Changes the deadline of the timer.
+    #[cfg_attr(not(feature = "unstable"), allow(dead_code))]
+    pub(crate) fn reset(&mut self, deadline: Instant) {
+        self.timeout = None;
+        self.deadline = Some(deadline);
+        self.remaining = deadline.saturating_duration_since(self.now());
+    }
+
+    /// Checks whether the timer has fired, starting the next timeout if it hasn't been yet.
+    fn poll_fire(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        loop {
+            if let Some(timeout) = &self.timeout {
+                if !timeout.poll(cx.waker()) {
+                    return Poll::Pending;
+                }
+                self.timeout = None;
+            }
+
+            if self.remaining == Duration::from_secs(0) {
+                return Poll::Ready(());
+            }
+
+            // Round up so that timers never fire early.
+            let nanos = self.remaining.subsec_nanos() % 1_000_000;
+            let ms = self.remaining.as_millis() + u128::from(nanos > 0);
+            let ms = cmp::min(ms, MAX_TIMEOUT) as u64;
+
+            self.remaining = self
+                .remaining
+                .checked_sub(Duration::from_millis(ms))
+                .unwrap_or_default();
+            self.timeout = Some(Timeout::new(ms as i32, cx.waker()));
+        }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let poll = self.poll_fire(cx);
+
+        #[cfg(feature = "tracing")]
+        if poll.is_ready() {
+            tracing::trace!(
+                task_id = Task::get_current(|t| t.id().0).unwrap_or(0),
+                "timer fired"
+            );
+        }
+
+        poll
+    }
+}
+
+impl fmt::Debug for Delay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Delay")
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
+
+/// A pending call to `setTimeout`, which is cleared when dropped.
+///
+/// The JavaScript handle and closure live in `TIMEOUTS`, so that the timer itself can be sent
+/// along with the task waiting on it.
+struct Timeout {
+    /// The key of the timeout in `TIMEOUTS`.
+    key: usize,
+
+    state: Arc<Mutex<State>>,
+}
+
+struct State {
+    /// Set once the timeout has fired.
+    fired: bool,
+
+    /// The task waiting for the timeout.
+    waker: Option<Waker>,
+}
+
+impl Timeout {
+    /// Starts a timeout firing after `ms` milliseconds.
+    fn new(ms: i32, waker: &Waker) -> Timeout {
+        let state = Arc::new(Mutex::new(State {
+            fired: false,
+            waker: Some(waker.clone()),
+        }));
+
+        let closure = Closure::new({
+            let state = state.clone();
+            move || {
+                let waker = {
+                    let mut state = state.lock().unwrap();
+                    state.fired = true;
+                    state.waker.take()
+                };
+                if let Some(w) = waker {
+                    w.wake();
+                }
+            }
+        });
+        let handle = set_timeout(&closure, ms);
+
+        let key = TIMEOUTS.with(|t| t.borrow_mut().insert((handle, closure)));
+        Timeout { key, state }
+    }
+
+    /// Returns `true` if the timeout has fired, and otherwise updates the waker to wake.
+    fn poll(&self, waker: &Waker) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if !state.fired {
+            match &state.waker {
+                Some(w) if w.will_wake(waker) => {}
+                _ => state.waker = Some(waker.clone()),
+            }
+        }
+        state.fired
+    }
+}
+
+impl Drop for Timeout {
+    fn drop(&mut self) {
+        let (handle, _closure) = TIMEOUTS.with(|t| t.borrow_mut().remove(self.key));
+        clear_timeout(&handle);
+    }
+}
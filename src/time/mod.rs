@@ -0,0 +1,280 @@
+//! Utilities for tracking time.
+//!
+//! Timers created by [`task::sleep`], [`stream::interval`], [`future::timeout`], and the other
+//! time-based operations run on the clock of the runtime they are created on. In tests, the
+//! clock can be paused with [`pause`] so that timers only fire when the clock is moved forward
+//! with [`advance`], or automatically once the runtime has nothing else to do. Timer-dependent
+//! code can then be tested deterministically and without waiting.
+//!
+//! A [`DelayQueue`] tracks many deadlines at once, yielding values as their deadlines pass. A
+//! [`Deadline`] is passed down implicitly to the code running within its scope, which can check
+//! how much time it has left, and a [`Stopwatch`] measures elapsed time.
+//!
+//! Timers are kept in a hierarchical timer wheel with millisecond resolution, driven by the thread
+//! that also waits for I/O events, so that starting and cancelling a timer takes constant time no
+//! matter how many timers are pending. Timers fire at the first millisecond tick after their
+//! deadline, and never early.
+//!
+//! On `wasm32`, timers wait on the JavaScript event loop through `setTimeout` instead, and
+//! aren't affected by pausing the clock.
+//!
+//! [`task::sleep`]: ../task/fn.sleep.html
+//! [`stream::interval`]: ../stream/fn.interval.html
+//! [`future::timeout`]: ../future/fn.timeout.html
+//! [`pause`]: fn.pause.html
+//! [`advance`]: fn.advance.html
+//! [`DelayQueue`]: struct.DelayQueue.html
+//! [`Deadline`]: struct.Deadline.html
+//! [`Stopwatch`]: struct.Stopwatch.html
+//!
+//! # Examples
+//!
+//! ```
+//! use std::time::{Duration, Instant};
+//!
+//! use async_std::runtime::Builder;
+//! use async_std::task;
+//! use async_std::time;
+//!
+//! let rt = Builder::new().build().unwrap();
+//! rt.block_on(async {
+//!     time::pause();
+//!
+//!     let start = Instant::now();
+//!     task::sleep(Duration::from_secs(60)).await;
+//!     assert!(start.elapsed() < Duration::from_secs(60));
+//! });
+//! ```
+
+pub(crate) use clock::{Clock, global_clock};
+#[cfg(feature = "unstable")]
+pub use deadline::{Deadline, DeadlineScope};
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use delay::Delay;
+#[cfg(feature = "unstable")]
+pub use delay_queue::{DelayQueue, Expired, Key};
+#[cfg(target_arch = "wasm32")]
+pub(crate) use js::Delay;
+#[cfg(feature = "unstable")]
+pub use sleep::Sleep;
+#[cfg(feature = "unstable")]
+pub use stopwatch::Stopwatch;
+
+use std::cell::Cell;
+#[cfg(feature = "unstable")]
+use std::time::Duration;
+use std::time::Instant;
+
+#[cfg(feature = "unstable")]
+use crate::task::executor;
+
+mod clock;
+#[cfg(feature = "unstable")]
+mod deadline;
+#[cfg(not(target_arch = "wasm32"))]
+mod delay;
+#[cfg(feature = "unstable")]
+mod delay_queue;
+#[cfg(target_arch = "wasm32")]
+mod js;
+#[cfg(feature = "unstable")]
+mod sleep;
+#[cfg(feature = "unstable")]
+mod stopwatch;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod wheel;
+
+/// Pauses the clock of the current runtime.
+///
+/// While the clock is paused, timers created on the runtime only fire when the clock is moved
+/// forward. This happens when [`advance`] is called, or automatically when the runtime has no
+/// tasks to run and no blocking operations in progress, in which case the clock jumps straight
+/// to the next timer. Tasks waiting on I/O don't keep the clock from jumping forward.
+///
+/// Timers started before the clock was paused keep running on the system clock until they are
+/// polled again. Pausing an already paused clock does nothing.
+///
+/// [`advance`]: fn.advance.html
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use async_std::future;
+/// use async_std::runtime::Builder;
+/// use async_std::time;
+///
+/// let rt = Builder::new().build().unwrap();
+/// rt.block_on(async {
+///     time::pause();
+///
+///     // The clock jumps forward instead of waiting for an hour.
+///     let res = future::timeout(Duration::from_secs(3600), future::pending::<()>()).await;
+///     assert!(res.is_err());
+/// });
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn pause() {
+    executor::clock().pause();
+    refresh_cached_now();
+}
+
+/// Resumes the clock of the current runtime.
+///
+/// The clock follows the system clock again, starting from the time it was paused at plus the
+/// time it has been advanced by. Resuming a clock that is not paused does nothing.
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn resume() {
+    executor::clock().resume();
+    refresh_cached_now();
+}
+
+/// Moves the paused clock of the current runtime forward.
+///
+/// Timers that are due by the new time fire, and the current task yields so that the tasks
+/// waiting on them get a chance to run.
+///
+/// # Panics
+///
+/// This function panics if the clock is not paused.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use async_std::prelude::*;
+/// use async_std::runtime::Builder;
+/// use async_std::stream;
+/// use async_std::task;
+/// use async_std::time;
+///
+/// let rt = Builder::new().build().unwrap();
+/// rt.block_on(async {
+///     time::pause();
+///     let mut interval = stream::interval(Duration::from_secs(10));
+///
+///     time::advance(Duration::from_secs(10)).await;
+///     interval.next().await;
+///
+///     let tick = task::spawn(async move { interval.next().await });
+///     time::advance(Duration::from_secs(10)).await;
+///     tick.await;
+/// });
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn advance(dur: Duration) {
+    executor::clock().advance(dur);
+    refresh_cached_now();
+    crate::task::yield_now().await;
+}
+
+/// Returns the current time on the clock of the current runtime.
+///
+/// This is the same as [`Instant::now`] unless the clock has been paused.
+///
+/// [`Instant::now`]: https://doc.rust-lang.org/std/time/struct.Instant.html#method.now
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use async_std::runtime::Builder;
+/// use async_std::time;
+///
+/// let rt = Builder::new().build().unwrap();
+/// rt.block_on(async {
+///     time::pause();
+///     let start = time::now();
+///
+///     time::advance(Duration::from_secs(5)).await;
+///     assert_eq!(time::now() - start, Duration::from_secs(5));
+/// });
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn now() -> Instant {
+    executor::clock().now()
+}
+
+/// Returns the current time, cached until the current task yields.
+///
+/// The first call while a task is being polled reads the clock, and later calls return the same
+/// instant until the task yields. This makes reading the time cheap in busy loops, such as when
+/// stamping every message of a batch, at the cost of the time standing still while the task runs.
+///
+/// Outside of tasks, this is the same as [`now`].
+///
+/// [`now`]: fn.now.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::time;
+///
+/// let first = time::now_cached();
+/// let stamps: Vec<_> = (0..1000).map(|_| time::now_cached()).collect();
+/// assert!(stamps.iter().all(|&t| t == first));
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn now_cached() -> Instant {
+    CACHED_NOW
+        .try_with(|cached| match cached.get() {
+            Some(Some(now)) => now,
+            Some(None) => {
+                let now = executor::clock().now();
+                cached.set(Some(Some(now)));
+                now
+            }
+            None => executor::clock().now(),
+        })
+        .unwrap_or_else(|_| executor::clock().now())
+}
+
+thread_local! {
+    /// The time cached by `now_cached`, which is `Some` while a task is being polled.
+    static CACHED_NOW: Cell<Option<Option<Instant>>> = const { Cell::new(None) };
+}
+
+/// Runs a closure polling a task, during which `now_cached` returns the same time.
+pub(crate) fn with_cached_now<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    CACHED_NOW.with(|cached| {
+        let old = cached.replace(Some(None));
+        defer! {
+            cached.set(old);
+        }
+        f()
+    })
+}
+
+/// Makes `now_cached` read the clock again after it has been changed.
+#[cfg(feature = "unstable")]
+fn refresh_cached_now() {
+    let _ = CACHED_NOW.try_with(|cached| {
+        if cached.get().is_some() {
+            cached.set(Some(None));
+        }
+    });
+}
+
+/// Advances the paused clock of the current executor if it has nothing else to do.
+///
+/// Called by threads blocking on a future right before they go to sleep. Returns `true` if the
+/// clock has been advanced, in which case the future should be polled again.
+#[cfg(feature = "unstable")]
+pub(crate) fn park(waker: &std::task::Waker) -> bool {
+    executor::clock().auto_advance(waker, executor::is_idle)
+}
@@ -0,0 +1,76 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use crate::time::Delay;
+
+/// A future that completes at a deadline, which can be moved.
+///
+/// This future is created by the [`task::sleep_until`] function. See its documentation for more.
+///
+/// Resetting the deadline with [`reset`] reuses the timer, which makes this future suited for
+/// keepalive and idle timers that are pushed back over and over again.
+///
+/// [`task::sleep_until`]: ../task/fn.sleep_until.html
+/// [`reset`]: #method.reset
+#[derive(Debug)]
+pub struct Sleep {
+    delay: Delay,
+}
+
+impl Sleep {
+    pub(crate) fn new(deadline: Instant) -> Sleep {
+        Sleep {
+            delay: Delay::at(deadline),
+        }
+    }
+
+    /// Returns the deadline of the sleep.
+    pub fn deadline(&self) -> Instant {
+        self.delay.when()
+    }
+
+    /// Returns `true` if the deadline has passed.
+    pub fn is_elapsed(&self) -> bool {
+        self.delay.now() >= self.delay.when()
+    }
+
+    /// Moves the deadline of the sleep.
+    ///
+    /// The sleep can be reset after it has completed, in which case it can be awaited again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use async_std::task;
+    ///
+    /// let start = Instant::now();
+    /// let mut sleep = task::sleep_until(start + Duration::from_millis(10));
+    ///
+    /// // Activity pushes the idle timeout back.
+    /// sleep.reset(start + Duration::from_millis(50));
+    /// (&mut sleep).await;
+    /// assert!(start.elapsed() >= Duration::from_millis(50));
+    /// #
+    /// # })
+    /// ```
+    pub fn reset(&mut self, deadline: Instant) {
+        self.delay.reset(deadline);
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if Pin::new(&mut self.delay).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        Poll::Ready(())
+    }
+}
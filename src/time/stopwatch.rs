@@ -0,0 +1,64 @@
+use std::time::{Duration, Instant};
+
+use crate::task::executor;
+
+/// Measures elapsed time on the clock of the current runtime.
+///
+/// Unlike [`Instant::elapsed`], a stopwatch follows the runtime clock, so that it keeps working
+/// when the clock is paused and advanced in tests. See [`time::pause`].
+///
+/// [`Instant::elapsed`]: https://doc.rust-lang.org/std/time/struct.Instant.html#method.elapsed
+/// [`time::pause`]: fn.pause.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::task;
+/// use async_std::time::Stopwatch;
+///
+/// let mut stopwatch = Stopwatch::start();
+/// task::sleep(Duration::from_millis(10)).await;
+///
+/// let lap = stopwatch.lap();
+/// assert!(lap >= Duration::from_millis(10));
+/// assert!(stopwatch.elapsed() < lap);
+/// #
+/// # })
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Stopwatch {
+    start: Instant,
+}
+
+impl Stopwatch {
+    /// Starts a stopwatch at the current time.
+    pub fn start() -> Stopwatch {
+        Stopwatch {
+            start: executor::clock().now(),
+        }
+    }
+
+    /// Returns the time the stopwatch was started or last restarted at.
+    pub fn started_at(&self) -> Instant {
+        self.start
+    }
+
+    /// Returns the time elapsed since the stopwatch was started.
+    pub fn elapsed(&self) -> Duration {
+        executor::clock()
+            .now()
+            .saturating_duration_since(self.start)
+    }
+
+    /// Restarts the stopwatch, returning the time elapsed until now.
+    pub fn lap(&mut self) -> Duration {
+        let now = executor::clock().now();
+        let elapsed = now.saturating_duration_since(self.start);
+        self.start = now;
+        elapsed
+    }
+}
@@ -0,0 +1,425 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::mem;
+use std::sync::{Arc, Mutex};
+use std::task::{Poll, Waker};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use slab::Slab;
+
+/// The number of bits of a slot index.
+const SLOT_BITS: u32 = 6;
+
+/// The number of slots in a level.
+const SLOTS: usize = 1 << SLOT_BITS;
+
+/// The number of levels in the wheel.
+const LEVELS: usize = 6;
+
+/// The longest delay the wheel can represent, in milliseconds.
+///
+/// Timers further in the future are parked in the last level until they come within range.
+const MAX_DELAY: u64 = (1 << (SLOT_BITS * LEVELS as u32)) - 1;
+
+/// A hierarchical timing wheel with millisecond resolution.
+///
+/// Level `n` has 64 slots, each covering `64^n` milliseconds. A timer is stored in the lowest
+/// level whose current span contains its deadline, so that registering and cancelling a timer
+/// is `O(1)`. As time advances, the timers in a slot of a higher level are moved down into the
+/// lower levels, until they end up in the first level and fire.
+struct Wheel {
+    /// The number of milliseconds since the start of the wheel that have been processed.
+    elapsed: u64,
+
+    /// The timers in the wheel.
+    entries: Slab<Entry>,
+
+    /// The keys of the timers in each slot of each level.
+    slots: Vec<Vec<Vec<usize>>>,
+
+    /// A bitmap of non-empty slots for each level.
+    occupied: [u64; LEVELS],
+
+    /// The tick the reactor thread is sleeping until, if it is sleeping.
+    sleeping_until: Option<u64>,
+}
+
+struct Entry {
+    /// The tick the timer fires at.
+    when: u64,
+
+    /// The task waiting for the timer.
+    waker: Option<Waker>,
+
+    /// The position of the timer in the wheel, or `None` if it has fired.
+    position: Option<Position>,
+}
+
+#[derive(Clone, Copy)]
+struct Position {
+    level: usize,
+    slot: usize,
+    index: usize,
+}
+
+/// A timer wheel driving timers that wait on the system clock.
+///
+/// Timers are fired by the thread polling a reactor, which is woken up when a timer is registered
+/// before the tick it is sleeping until.
+pub(crate) struct Timer {
+    /// The instant tick zero corresponds to.
+    start: Instant,
+
+    wheel: Mutex<Wheel>,
+
+    /// Wakes up the thread firing the timers.
+    unpark: Box<dyn Fn() + Send + Sync>,
+}
+
+/// The global timer, fired by the reactor thread.
+pub(crate) static TIMER: Lazy<Arc<Timer>> =
+    Lazy::new(|| Arc::new(Timer::new(crate::net::driver::notify)));
+
+thread_local! {
+    /// The timer of the current-thread runtime driven by this thread, if any.
+    static CURRENT: RefCell<Option<Arc<Timer>>> = const { RefCell::new(None) };
+}
+
+/// Returns the timer that timers created on the current thread register in.
+pub(crate) fn current() -> Arc<Timer> {
+    CURRENT
+        .with(|current| current.borrow().clone())
+        .unwrap_or_else(|| TIMER.clone())
+}
+
+/// Makes timers created within a closure register in the given timer.
+#[cfg(feature = "unstable")]
+pub(crate) fn enter<F, R>(timer: &Arc<Timer>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    CURRENT.with(|current| {
+        let old = current.replace(Some(timer.clone()));
+        defer! {
+            *current.borrow_mut() = old;
+        }
+        f()
+    })
+}
+
+impl Timer {
+    /// Creates a timer woken up by calling `unpark`.
+    pub(crate) fn new(unpark: impl Fn() + Send + Sync + 'static) -> Timer {
+        Timer {
+            start: Instant::now(),
+            wheel: Mutex::new(Wheel::new()),
+            unpark: Box::new(unpark),
+        }
+    }
+
+    /// Registers a timer firing at `deadline`.
+    ///
+    /// Returns `None` if the deadline has already passed. Otherwise, returns the key of the
+    /// timer, which must be passed to [`cancel`] once the timer is no longer needed.
+    ///
+    /// [`cancel`]: #method.cancel
+    pub(crate) fn register(&self, deadline: Instant, waker: &Waker) -> Option<usize> {
+        // Round up so that timers never fire early.
+        let when = self.ticks(deadline, true);
+
+        let mut wheel = self.wheel.lock().unwrap();
+        if when <= wheel.elapsed || when <= self.ticks(Instant::now(), false) {
+            return None;
+        }
+
+        let key = wheel.insert(when, waker.clone());
+        if matches!(wheel.sleeping_until, Some(until) if when < until) {
+            wheel.sleeping_until = None;
+            drop(wheel);
+            (self.unpark)();
+        }
+        Some(key)
+    }
+
+    /// Polls a registered timer, updating the waker to wake when it fires.
+    pub(crate) fn poll(&self, key: usize, waker: &Waker) -> Poll<()> {
+        let mut wheel = self.wheel.lock().unwrap();
+        let entry = &mut wheel.entries[key];
+        if entry.position.is_none() {
+            return Poll::Ready(());
+        }
+        match &entry.waker {
+            Some(w) if w.will_wake(waker) => {}
+            _ => entry.waker = Some(waker.clone()),
+        }
+        Poll::Pending
+    }
+
+    /// Removes a registered timer.
+    pub(crate) fn cancel(&self, key: usize) {
+        let mut wheel = self.wheel.lock().unwrap();
+        wheel.unlink(key);
+        wheel.entries.remove(key);
+    }
+
+    /// Converts an instant into a tick, rounding up or down to the millisecond.
+    fn ticks(&self, instant: Instant, round_up: bool) -> u64 {
+        let dur = instant.saturating_duration_since(self.start);
+        let ms = dur.as_secs() * 1000 + u64::from(dur.subsec_millis());
+        if round_up && dur.subsec_nanos() > dur.subsec_millis() * 1_000_000 {
+            ms + 1
+        } else {
+            ms
+        }
+    }
+
+    /// Fires the timers that are due, and returns the deadline of the next timer, if any.
+    ///
+    /// This is called by the thread polling the reactor before it goes to sleep until the
+    /// returned deadline.
+    pub(crate) fn fire(&self) -> Option<Instant> {
+        let mut fired = Vec::new();
+        let mut wheel = self.wheel.lock().unwrap();
+        loop {
+            let now = self.ticks(Instant::now(), false);
+            wheel.advance(now, &mut fired);
+
+            if fired.is_empty() {
+                let next = wheel.next_expiration().map(|(when, _, _)| when);
+                wheel.sleeping_until = Some(next.unwrap_or(u64::MAX));
+                return next.map(|when| self.start + Duration::from_millis(when));
+            }
+
+            drop(wheel);
+            for waker in fired.drain(..) {
+                waker.wake();
+            }
+            wheel = self.wheel.lock().unwrap();
+        }
+    }
+}
+
+impl fmt::Debug for Timer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Timer { .. }")
+    }
+}
+
+impl Wheel {
+    fn new() -> Wheel {
+        Wheel {
+            elapsed: 0,
+            entries: Slab::new(),
+            slots: (0..LEVELS)
+                .map(|_| (0..SLOTS).map(|_| Vec::new()).collect())
+                .collect(),
+            occupied: [0; LEVELS],
+            // The reactor thread needs to be woken up for the first timer.
+            sleeping_until: Some(u64::MAX),
+        }
+    }
+
+    /// Inserts a timer firing at tick `when`, which must be after the elapsed tick.
+    fn insert(&mut self, when: u64, waker: Waker) -> usize {
+        let key = self.entries.insert(Entry {
+            when,
+            waker: Some(waker),
+            position: None,
+        });
+        self.link(key);
+        key
+    }
+
+    /// Puts a timer into the slot its deadline belongs to.
+    fn link(&mut self, key: usize) {
+        let when = self.entries[key].when.min(self.elapsed + MAX_DELAY);
+
+        // The level is determined by the highest bit in which the deadline and the elapsed tick
+        // differ.
+        let significant = 63 - ((self.elapsed ^ when) | (SLOTS as u64 - 1)).leading_zeros();
+        let level = (significant / SLOT_BITS) as usize;
+        let level = level.min(LEVELS - 1);
+        let slot = ((when >> (level as u32 * SLOT_BITS)) as usize) & (SLOTS - 1);
+
+        let keys = &mut self.slots[level][slot];
+        keys.push(key);
+        self.occupied[level] |= 1 << slot;
+        self.entries[key].position = Some(Position {
+            level,
+            slot,
+            index: keys.len() - 1,
+        });
+    }
+
+    /// Takes a timer out of its slot, if it is in one.
+    fn unlink(&mut self, key: usize) {
+        let pos = match self.entries[key].position.take() {
+            Some(pos) => pos,
+            None => return,
+        };
+
+        let keys = &mut self.slots[pos.level][pos.slot];
+        keys.swap_remove(pos.index);
+        if let Some(&moved) = keys.get(pos.index) {
+            if let Some(p) = &mut self.entries[moved].position {
+                p.index = pos.index;
+            }
+        }
+        if keys.is_empty() {
+            self.occupied[pos.level] &= !(1 << pos.slot);
+        }
+    }
+
+    /// Returns the tick at which the next non-empty slot needs to be processed, along with the
+    /// level and index of the slot.
+    fn next_expiration(&self) -> Option<(u64, usize, usize)> {
+        (0..LEVELS).find_map(|level| {
+            let occupied = self.occupied[level];
+            if occupied == 0 {
+                return None;
+            }
+
+            // Find the first non-empty slot, wrapping around from the current one.
+            let slot_range = 1u64 << (level as u32 * SLOT_BITS);
+            let level_range = slot_range << SLOT_BITS;
+            let now_slot = ((self.elapsed / slot_range) as u32) & (SLOTS as u32 - 1);
+            let slot = (now_slot + occupied.rotate_right(now_slot).trailing_zeros()) as usize
+                & (SLOTS - 1);
+
+            let level_start = self.elapsed & !(level_range - 1);
+            let mut when = level_start + slot as u64 * slot_range;
+            if when <= self.elapsed {
+                when += level_range;
+            }
+            Some((when, level, slot))
+        })
+    }
+
+    /// Processes all slots up to tick `now`, collecting the wakers of timers that fire.
+    fn advance(&mut self, now: u64, fired: &mut Vec<Waker>) {
+        while let Some((when, level, slot)) = self.next_expiration() {
+            if when > now {
+                break;
+            }
+            self.elapsed = when;
+
+            self.occupied[level] &= !(1 << slot);
+            for key in mem::take(&mut self.slots[level][slot]) {
+                let entry = &mut self.entries[key];
+                entry.position = None;
+                if entry.when <= self.elapsed {
+                    fired.extend(entry.waker.take());
+                } else {
+                    // Move the timer down into a lower level.
+                    self.link(key);
+                }
+            }
+        }
+        self.elapsed = self.elapsed.max(now);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MAX_DELAY, Wheel};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::task::{Wake, Waker};
+
+    struct Counter(AtomicUsize);
+
+    impl Wake for Counter {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Inserts timers at the given ticks and returns the ticks at which each one fired.
+    fn fire_times(whens: &[u64], until: u64) -> Vec<Option<u64>> {
+        let mut wheel = Wheel::new();
+        let counters: Vec<_> = whens
+            .iter()
+            .map(|&when| {
+                let counter = Arc::new(Counter(AtomicUsize::new(0)));
+                wheel.insert(when, Waker::from(counter.clone()));
+                counter
+            })
+            .collect();
+
+        let mut fired_at = vec![None; whens.len()];
+        let mut fired = Vec::new();
+        while let Some((next, _, _)) = wheel.next_expiration() {
+            if next > until {
+                break;
+            }
+            wheel.advance(next, &mut fired);
+            fired.drain(..).for_each(|w| w.wake());
+            for (i, counter) in counters.iter().enumerate() {
+                if counter.0.load(Ordering::SeqCst) > 0 && fired_at[i].is_none() {
+                    fired_at[i] = Some(next);
+                }
+            }
+        }
+        fired_at
+    }
+
+    #[test]
+    fn fires_at_deadline() {
+        let whens = [
+            1,
+            5,
+            63,
+            64,
+            65,
+            4095,
+            4096,
+            100_000,
+            1 << 30,
+            (1 << 30) + 7,
+        ];
+        let fired = fire_times(&whens, u64::MAX >> 1);
+        for (when, fired) in whens.iter().zip(fired) {
+            assert_eq!(Some(*when), fired);
+        }
+    }
+
+    #[test]
+    fn beyond_max_delay() {
+        let when = MAX_DELAY * 3 + 12345;
+        assert_eq!(fire_times(&[when], u64::MAX >> 1), [Some(when)]);
+    }
+
+    #[test]
+    fn advance_in_steps() {
+        let mut wheel = Wheel::new();
+        let counter = Arc::new(Counter(AtomicUsize::new(0)));
+        wheel.insert(1000, Waker::from(counter.clone()));
+
+        let mut fired = Vec::new();
+        for now in (0..1000).step_by(7) {
+            wheel.advance(now, &mut fired);
+            assert!(fired.is_empty());
+        }
+        wheel.advance(1000, &mut fired);
+        assert_eq!(fired.len(), 1);
+    }
+
+    #[test]
+    fn cancel() {
+        let mut wheel = Wheel::new();
+        let counter = Arc::new(Counter(AtomicUsize::new(0)));
+        let keys: Vec<_> = (1..=10)
+            .map(|i| wheel.insert(i * 10, Waker::from(counter.clone())))
+            .collect();
+        for key in keys.iter().step_by(2) {
+            wheel.unlink(*key);
+            wheel.entries.remove(*key);
+        }
+
+        let mut fired = Vec::new();
+        wheel.advance(1000, &mut fired);
+        assert_eq!(fired.len(), 5);
+        assert_eq!(wheel.next_expiration(), None);
+    }
+}
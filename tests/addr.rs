@@ -82,3 +82,41 @@ fn to_socket_addr_string() {
 fn to_socket_addr_str_bad() {
     assert!(blocking_resolve("1200::AB00:1234::2552:7777:1313:34300").is_err());
 }
+
+#[cfg(feature = "unstable")]
+#[test]
+fn lookup_host_orders_by_family() -> std::io::Result<()> {
+    use async_std::net::{lookup_host, AddrFamily, ResolverOptions};
+    use async_std::prelude::*;
+
+    let v4 = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::new(77, 88, 21, 11), 443));
+    let v6 = SocketAddr::V6(SocketAddrV6::new(
+        Ipv6Addr::new(0x2a02, 0x6b8, 0, 0, 0, 0, 0x11, 0x11),
+        443,
+        0,
+        0,
+    ));
+    let addrs = &[v4, v6][..];
+
+    task::block_on(async {
+        let all: Vec<_> = lookup_host(addrs).await?.collect().await;
+        assert_eq!(all, [v4, v6]);
+
+        let resolve = |family| async move {
+            let options = ResolverOptions::new().family(family);
+            let addrs = options.lookup_host(addrs).await?;
+            Ok::<Vec<_>, std::io::Error>(addrs.collect().await)
+        };
+        assert_eq!(resolve(AddrFamily::PreferV6).await?, [v6, v4]);
+        assert_eq!(resolve(AddrFamily::PreferV4).await?, [v4, v6]);
+        assert_eq!(resolve(AddrFamily::V6).await?, [v6]);
+
+        let err = ResolverOptions::new()
+            .family(AddrFamily::V6)
+            .lookup_host("127.0.0.1:80")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        Ok(())
+    })
+}
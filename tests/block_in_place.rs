@@ -0,0 +1,52 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use async_std::task;
+
+#[test]
+fn borrows_from_the_stack() {
+    task::block_on(async {
+        let handle = task::spawn(async {
+            let mut v = vec![1, 2];
+            task::block_in_place(|| v.push(3));
+            v
+        });
+        assert_eq!(handle.await, [1, 2, 3]);
+    });
+}
+
+#[test]
+fn outside_executor() {
+    assert_eq!(task::block_in_place(|| 5), 5);
+}
+
+#[test]
+fn other_tasks_keep_running() {
+    task::block_on(async {
+        let flag = Arc::new(AtomicBool::new(false));
+
+        // Block more tasks than there are worker threads until the last task below has run.
+        let mut blockers = Vec::new();
+        for _ in 0..64 {
+            let flag = flag.clone();
+            blockers.push(task::spawn(async move {
+                task::block_in_place(|| {
+                    while !flag.load(Ordering::SeqCst) {
+                        thread::sleep(Duration::from_millis(1));
+                    }
+                });
+            }));
+        }
+
+        let flag = flag.clone();
+        task::spawn(async move { flag.store(true, Ordering::SeqCst) }).await;
+
+        for b in blockers {
+            b.await;
+        }
+    });
+}
@@ -14,3 +14,38 @@ fn panic() {
         panic!("boom");
     });
 }
+
+#[cfg(feature = "unstable")]
+#[test]
+fn nested_in_task() {
+    let res = task::block_on(async {
+        task::spawn(async {
+            // Blocking a worker thread panics instead of holding up its tasks.
+            task::block_on(async {});
+        })
+        .join()
+        .await
+    });
+    assert!(res.unwrap_err().is_panic());
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn try_block_on() {
+    assert_eq!(task::try_block_on(async { 1 + 2 }), Ok(3));
+
+    let err = task::block_on(task::spawn(async {
+        task::try_block_on(async { 1 + 2 }).unwrap_err()
+    }));
+    assert_eq!(
+        err.to_string(),
+        "cannot block on a future from within a task, as this blocks a worker thread of the \
+         executor"
+    );
+
+    // Blocking is allowed in place.
+    let res = task::block_on(task::spawn(async {
+        task::block_in_place(|| task::try_block_on(async { 1 + 2 }))
+    }));
+    assert_eq!(res, Ok(3));
+}
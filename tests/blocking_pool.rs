@@ -0,0 +1,208 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use async_std::runtime::Builder;
+use async_std::task;
+
+#[test]
+fn max_blocking_threads() {
+    let rt = Builder::new().max_blocking_threads(2).build().unwrap();
+
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    rt.block_on(async {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let running = running.clone();
+                let peak = peak.clone();
+                task::spawn_blocking(move || {
+                    let n = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(n, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await;
+        }
+    });
+
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+}
+
+#[test]
+fn queue_full() {
+    let rt = Builder::new()
+        .max_blocking_threads(1)
+        .max_blocking_queue(2)
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        let (started, wait) = mpsc::channel();
+        let (release, blocked) = mpsc::channel::<()>();
+
+        let busy = task::try_spawn_blocking(move || {
+            started.send(()).unwrap();
+            blocked.recv().unwrap();
+        })
+        .unwrap();
+        wait.recv().unwrap();
+
+        let a = task::try_spawn_blocking(|| 1).unwrap();
+        let b = task::try_spawn_blocking(|| 2).unwrap();
+        let err = task::try_spawn_blocking(|| 3).unwrap_err();
+        assert!(err.is_queue_full());
+        assert_eq!(
+            err.to_string(),
+            "the queue of the blocking thread pool is full"
+        );
+
+        // `spawn_blocking` ignores the limit.
+        let c = task::spawn_blocking(|| 4);
+
+        release.send(()).unwrap();
+        busy.await;
+        assert_eq!(a.await + b.await + c.await, 7);
+
+        // The queue has drained.
+        assert_eq!(task::try_spawn_blocking(|| 5).unwrap().await, 5);
+    });
+}
+
+#[test]
+fn keep_alive() {
+    let rt = Builder::new()
+        .max_blocking_threads(1)
+        .blocking_keep_alive(Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    let thread_id = || {
+        rt.block_on(async { task::spawn_blocking(|| thread::current().id()).await })
+    };
+
+    let first = thread_id();
+    let reused = thread_id();
+    assert_eq!(first, reused);
+
+    // The idle thread has stopped, so a new one is started.
+    thread::sleep(Duration::from_millis(200));
+    let fresh = thread_id();
+    assert_ne!(first, fresh);
+}
+
+#[test]
+fn cancel_blocking() {
+    task::block_on(async {
+        let (s, r) = mpsc::channel();
+        let handle = task::spawn_blocking(move || {
+            s.send(()).unwrap();
+            while !task::is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+            }
+            "stopped"
+        });
+
+        r.recv().unwrap();
+        assert!(!task::is_cancelled());
+        assert_eq!(handle.cancel().await, Some("stopped"));
+    });
+}
+
+#[test]
+fn blocking_with_context() {
+    async_std::task_local! {
+        static REQUEST_ID: u64 = 0;
+    }
+
+    task::block_on(async {
+        let (s, r) = mpsc::channel();
+        let parent = task::spawn(REQUEST_ID.scope(7, async move {
+            let handle = task::Builder::new()
+                .inherit(&REQUEST_ID)
+                .spawn_blocking(move || {
+                    s.send(REQUEST_ID.with(|id| *id)).unwrap();
+                    while !task::is_cancelled() {
+                        thread::sleep(Duration::from_millis(1));
+                    }
+                    s.send(0).unwrap();
+                });
+            handle.await
+        }));
+
+        // Aborting the parent asks the blocking task to stop.
+        assert_eq!(r.recv().unwrap(), 7);
+        assert!(parent.cancel().await.is_none());
+        assert_eq!(r.recv_timeout(Duration::from_secs(10)), Ok(0));
+    });
+}
+
+#[test]
+fn named_pools_are_isolated() {
+    let rt = Builder::new()
+        .max_blocking_threads(1)
+        .blocking_pool("dns", 1)
+        .build()
+        .unwrap();
+
+    rt.block_on(async {
+        let (started, wait) = mpsc::channel();
+        let (release, blocked) = mpsc::channel::<()>();
+
+        // Occupy the only thread of the "dns" pool.
+        let busy = task::spawn_blocking_on("dns", move || {
+            let name = thread::current().name().map(String::from);
+            started.send(name).unwrap();
+            blocked.recv().unwrap();
+        });
+        let name = wait.recv().unwrap();
+        assert_eq!(name.as_deref(), Some("async-std/blocking/dns"));
+
+        // Other pools aren't affected.
+        assert_eq!(task::spawn_blocking(|| 1).await, 1);
+        assert_eq!(task::spawn_blocking_on("fs", || 2).await, 2);
+
+        release.send(()).unwrap();
+        busy.await;
+    });
+}
+
+#[test]
+fn named_pool_limit() {
+    let rt = Builder::new()
+        .blocking_pool("crypto", 2)
+        .build()
+        .unwrap();
+
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    rt.block_on(async {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let running = running.clone();
+                let peak = peak.clone();
+                task::spawn_blocking_on("crypto", move || {
+                    let n = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(n, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.await;
+        }
+    });
+
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::prelude::*;
+use async_std::sync::broadcast::{self, RecvError, TryRecvError};
+use async_std::task;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let (s, mut r) = broadcast::channel(1);
+
+        s.send(7).unwrap();
+        assert_eq!(r.recv().await, Ok(7));
+
+        s.send(8).unwrap();
+        assert_eq!(r.recv().await, Ok(8));
+
+        drop(s);
+        assert_eq!(r.recv().await, Err(RecvError::Closed));
+    });
+
+    task::block_on(async {
+        let (s, r) = broadcast::channel(10);
+        drop(r);
+        assert_eq!(s.send(1).unwrap_err().0, 1);
+    });
+}
+
+#[test]
+fn every_receiver_gets_every_message() {
+    task::block_on(async {
+        let (s, r) = broadcast::channel(4);
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let mut r = r.clone();
+                task::spawn(async move {
+                    let mut msgs = Vec::new();
+                    while let Some(msg) = r.next().await {
+                        msgs.push(msg.unwrap());
+                    }
+                    msgs
+                })
+            })
+            .collect();
+        drop(r);
+
+        task::sleep(ms(100)).await;
+        for i in 0..3 {
+            assert_eq!(s.send(i), Ok(4));
+        }
+        drop(s);
+
+        for h in handles {
+            assert_eq!(h.await, vec![0, 1, 2]);
+        }
+    });
+}
+
+#[test]
+fn lagged() {
+    task::block_on(async {
+        let (s, mut r1) = broadcast::channel(3);
+        let mut r2 = s.subscribe();
+
+        for i in 0..10 {
+            s.send(i).unwrap();
+        }
+
+        assert_eq!(r1.len(), 3);
+        assert_eq!(r1.try_recv(), Err(TryRecvError::Lagged(7)));
+        assert_eq!(r1.try_recv(), Ok(7));
+
+        assert_eq!(r2.recv().await, Err(RecvError::Lagged(7)));
+        assert_eq!(r2.recv().await, Ok(7));
+        assert_eq!(r2.recv().await, Ok(8));
+        assert_eq!(r2.recv().await, Ok(9));
+        assert_eq!(r2.try_recv(), Err(TryRecvError::Empty));
+    });
+}
+
+#[test]
+fn subscribe_starts_at_tail() {
+    task::block_on(async {
+        let (s, mut r1) = broadcast::channel(4);
+        s.send(1).unwrap();
+
+        let mut r2 = s.subscribe();
+        s.send(2).unwrap();
+
+        assert_eq!(r1.recv().await, Ok(1));
+        assert_eq!(r1.recv().await, Ok(2));
+        assert_eq!(r2.recv().await, Ok(2));
+        assert_eq!(s.receiver_count(), 2);
+    });
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "unstable")]
+
+use std::cell::Cell;
+use std::mem;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, mpsc};
+use std::time::Duration;
+
+use async_std::runtime::Builder;
+use async_std::sync::channel;
+use async_std::task::{self, LocalSet};
+
+#[test]
+fn busy_channel_loop_yields() {
+    let local = LocalSet::new();
+    let done = Rc::new(Cell::new(false));
+
+    local.block_on(async {
+        let (s, r) = channel(1);
+
+        // This loop only stops once the other task has run on the same thread.
+        let done2 = done.clone();
+        let busy = task::spawn_local(async move {
+            let mut n = 0;
+            while !done2.get() {
+                s.send(()).await;
+                r.recv().await.unwrap();
+                n += 1;
+            }
+            n
+        });
+
+        let done3 = done.clone();
+        task::spawn_local(async move { done3.set(true) }).await;
+        assert!(busy.await > 0);
+    });
+}
+
+#[test]
+fn consume_budget_yields() {
+    let local = LocalSet::new();
+    let done = Rc::new(Cell::new(false));
+
+    local.block_on(async {
+        let done2 = done.clone();
+        let busy = task::spawn_local(async move {
+            while !done2.get() {
+                task::consume_budget().await;
+            }
+        });
+
+        let done3 = done.clone();
+        task::spawn_local(async move { done3.set(true) }).await;
+        busy.await;
+    });
+}
+
+#[test]
+fn unconstrained_never_yields() {
+    task::block_on(async {
+        let (s, r) = channel(1000);
+        for i in 0..1000 {
+            s.send(i).await;
+        }
+
+        let drain = |r: async_std::sync::Receiver<i32>| async move {
+            for _ in 0..500 {
+                r.recv().await.unwrap();
+            }
+            r
+        };
+
+        let mut constrained = Box::pin(drain(r));
+        assert!(futures::poll!(constrained.as_mut()).is_pending());
+        let r = constrained.await;
+
+        let mut unconstrained = Box::pin(task::unconstrained(drain(r)));
+        assert!(futures::poll!(unconstrained.as_mut()).is_ready());
+    });
+}
+
+#[test]
+fn exhausted_budget_yields_to_tasks_on_the_same_worker() {
+    const ITERATIONS: usize = 10_000;
+
+    let rt = Builder::new().worker_threads(1).build().unwrap();
+    let counts = Arc::new([AtomicUsize::new(0), AtomicUsize::new(0)]);
+    let (sender, receiver) = mpsc::channel();
+
+    // Spawn both tasks from the worker thread, so they go into its local queue.
+    rt.spawn(async move {
+        for i in 0..2 {
+            let counts = counts.clone();
+            let sender = sender.clone();
+            task::spawn(async move {
+                // Count how many iterations in a row this task runs while the other one is
+                // still going but doesn't get to run.
+                let mut seen = 0;
+                let (mut streak, mut longest) = (0, 0);
+                while counts[i].load(Ordering::SeqCst) < ITERATIONS {
+                    let other = counts[1 - i].load(Ordering::SeqCst);
+                    if other == seen && other < ITERATIONS {
+                        streak += 1;
+                        longest = longest.max(streak);
+                    } else {
+                        streak = 0;
+                    }
+                    seen = other;
+
+                    counts[i].fetch_add(1, Ordering::SeqCst);
+                    task::consume_budget().await;
+                }
+                sender.send(longest).unwrap();
+            });
+        }
+    });
+
+    for _ in 0..2 {
+        match receiver.recv_timeout(Duration::from_secs(10)) {
+            Ok(longest) => assert!(longest < 1000, "ran {} times in a row", longest),
+            Err(_) => {
+                // The worker is stuck running one of the tasks, so the runtime can't shut down.
+                mem::forget(rt);
+                panic!("a task never got to run");
+            }
+        }
+    }
+}
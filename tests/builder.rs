@@ -0,0 +1,64 @@
+#![cfg(feature = "unstable")]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use async_std::task::{self, Builder, LocalSet, Priority, SpawnError};
+use async_std::task_local;
+
+task_local! {
+    static NUMBER: Cell<u32> = Cell::new(1);
+    static NAME: Cell<&'static str> = Cell::new("none");
+}
+
+#[test]
+fn name_and_priority() {
+    task::block_on(async {
+        let handle = Builder::new()
+            .name("worker".to_string())
+            .priority(Priority::High)
+            .spawn(async { task::current().priority() })
+            .unwrap();
+
+        assert_eq!(handle.task().name(), Some("worker"));
+        assert_eq!(handle.await, Priority::High);
+        assert_eq!(task::spawn(async { task::current().priority() }).await, Priority::Normal);
+    });
+}
+
+#[test]
+fn preset_task_locals() {
+    task::block_on(async {
+        let handle = Builder::new()
+            .task_local(&NUMBER, Cell::new(5))
+            .task_local(&NUMBER, Cell::new(6))
+            .spawn(async { (NUMBER.with(|n| n.get()), NAME.with(|n| n.get())) })
+            .unwrap();
+        assert_eq!(handle.await, (6, "none"));
+    });
+}
+
+#[test]
+fn local_outside_local_set() {
+    task::block_on(async {
+        let err = Builder::new().local(true).spawn(async {}).unwrap_err();
+        assert!(err.get_ref().unwrap().is::<SpawnError>());
+    });
+}
+
+#[test]
+fn local_inside_local_set() {
+    let local = LocalSet::new();
+    let ran = Rc::new(Cell::new(false));
+
+    local.block_on(async {
+        let thread = std::thread::current().id();
+        let handle = Builder::new()
+            .local(true)
+            .spawn(async move { std::thread::current().id() == thread })
+            .unwrap();
+        assert!(handle.await);
+        ran.set(true);
+    });
+    assert!(ran.get());
+}
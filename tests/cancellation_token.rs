@@ -0,0 +1,67 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::sync::CancellationToken;
+use async_std::task;
+
+#[test]
+fn cancel_wakes_waiting_tasks() {
+    task::block_on(async {
+        let token = CancellationToken::new();
+        let mut handles = vec![];
+
+        for _ in 0..3 {
+            let token = token.clone();
+            handles.push(task::spawn(async move { token.cancelled().await }));
+        }
+
+        task::sleep(Duration::from_millis(10)).await;
+        token.cancel();
+
+        for h in handles {
+            h.await;
+        }
+    });
+}
+
+#[test]
+fn cancellation_propagates_to_descendants_only() {
+    task::block_on(async {
+        let root = CancellationToken::new();
+        let child = root.child_token();
+        let grandchild = child.child_token();
+        let sibling = root.child_token();
+
+        child.cancel();
+        assert!(grandchild.is_cancelled());
+        assert!(!root.is_cancelled());
+        assert!(!sibling.is_cancelled());
+
+        let waiting = task::spawn({
+            let sibling = sibling.clone();
+            async move { sibling.cancelled().await }
+        });
+        assert!(future::timeout(Duration::from_millis(10), sibling.cancelled())
+            .await
+            .is_err());
+
+        root.cancel();
+        waiting.await;
+        assert!(sibling.is_cancelled());
+        assert!(root.child_token().is_cancelled());
+    });
+}
+
+#[test]
+fn drop_guard() {
+    let token = CancellationToken::new();
+
+    let guard = token.clone().drop_guard();
+    let _ = guard.disarm();
+    assert!(!token.is_cancelled());
+
+    drop(token.clone().drop_guard());
+    assert!(token.is_cancelled());
+}
@@ -4,7 +4,8 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
-use async_std::sync::channel;
+use async_std::future;
+use async_std::sync::{channel, RecvTimeoutError, SendTimeoutError, TryReserveError};
 use async_std::task;
 use rand::{thread_rng, Rng};
 
@@ -357,3 +358,425 @@ fn drops() {
         })
     }
 }
+
+#[test]
+fn send_timeout() {
+    task::block_on(async {
+        let (s, r) = channel(1);
+        let dur = ms(10);
+
+        assert_eq!(s.send_timeout(1, dur).await, Ok(()));
+        let err = s.send_timeout(2, dur).await.unwrap_err();
+        assert!(err.is_timeout());
+        assert_eq!(err.into_inner(), 2);
+
+        let s2 = s.clone();
+        let handle = task::spawn(async move { s2.send_timeout(3, ms(1000)).await });
+        task::sleep(dur).await;
+        assert_eq!(r.recv().await, Some(1));
+        assert_eq!(handle.await, Ok(()));
+
+        drop(r);
+        assert_eq!(
+            s.send_timeout(4, dur).await,
+            Err(SendTimeoutError::Disconnected(4))
+        );
+    })
+}
+
+#[test]
+fn recv_timeout() {
+    task::block_on(async {
+        let (s, r) = channel(1);
+
+        assert_eq!(r.recv_timeout(ms(10)).await, Err(RecvTimeoutError::Timeout));
+
+        task::spawn(async move {
+            task::sleep(ms(10)).await;
+            s.send(1).await;
+        });
+        assert_eq!(r.recv_timeout(ms(1000)).await, Ok(1));
+        assert_eq!(
+            r.recv_timeout(ms(1000)).await,
+            Err(RecvTimeoutError::Disconnected)
+        );
+    })
+}
+
+#[test]
+fn close_drains_queued_messages() {
+    task::block_on(async {
+        let (s, r) = channel(3);
+        s.send(1).await;
+        s.send(2).await;
+        assert!(!r.is_closed());
+
+        r.close();
+        assert!(r.is_closed());
+        assert!(s.is_closed());
+        assert_eq!(r.len(), 2);
+
+        // Messages sent into a closed channel are dropped.
+        s.send(3).await;
+        assert_eq!(r.len(), 2);
+
+        assert_eq!(r.recv().await, Some(1));
+        assert_eq!(r.recv().await, Some(2));
+        assert_eq!(r.recv().await, None);
+        assert!(r.is_empty());
+    })
+}
+
+#[test]
+fn sender_closed() {
+    task::block_on(async {
+        let (s, r) = channel::<i32>(1);
+        let r2 = r.clone();
+
+        assert!(future::timeout(ms(10), s.closed()).await.is_err());
+
+        let s2 = s.clone();
+        let handle = task::spawn(async move { s2.closed().await });
+
+        drop(r);
+        task::sleep(ms(10)).await;
+        assert!(!s.is_closed());
+
+        r2.close();
+        handle.await;
+        s.closed().await;
+    })
+}
+
+#[test]
+fn reserve_waits_for_capacity() {
+    task::block_on(async {
+        let (s, r) = channel(1);
+
+        let permit = s.reserve().await.unwrap();
+        assert!(s.is_full());
+        assert!(future::timeout(ms(10), s.send(1)).await.is_err());
+        assert!(future::timeout(ms(10), s.reserve()).await.is_err());
+
+        // Dropping the permit lets a waiting sender through.
+        let s2 = s.clone();
+        let handle = task::spawn(async move { s2.send(2).await });
+        task::sleep(ms(10)).await;
+        drop(permit);
+        handle.await;
+        assert_eq!(r.recv().await, Some(2));
+
+        s.reserve().await.unwrap().send(3);
+        assert_eq!(r.recv().await, Some(3));
+        assert_eq!(s.try_reserve().map(drop), Ok(()));
+    })
+}
+
+#[test]
+fn reserve_on_closed_channel() {
+    task::block_on(async {
+        let (s, r) = channel::<i32>(2);
+
+        let permit = s.try_reserve().unwrap();
+        drop(r);
+
+        assert!(s.reserve().await.is_none());
+        assert_eq!(s.try_reserve().unwrap_err(), TryReserveError::Closed);
+        permit.send(1);
+    })
+}
+
+#[test]
+fn blocked_senders_are_served_in_order() {
+    task::block_on(async {
+        let (s, r) = channel(1);
+        s.send(0).await;
+
+        // Start the senders one by one so that they start waiting in order.
+        let mut handles = Vec::new();
+        for i in 1..=5 {
+            let s = s.clone();
+            handles.push(task::spawn(async move { s.send(i).await }));
+            task::sleep(ms(10)).await;
+        }
+
+        // Capacity freed while senders are waiting goes to the oldest one.
+        assert_eq!(s.try_reserve().unwrap_err(), TryReserveError::Full);
+        for i in 0..=5 {
+            assert_eq!(r.recv().await, Some(i));
+        }
+        for handle in handles {
+            handle.await;
+        }
+    })
+}
+
+#[test]
+fn cancelled_sender_passes_capacity_on() {
+    task::block_on(async {
+        let (s, r) = channel(1);
+        s.send(0).await;
+
+        // The first waiting sender gives up, so the second one takes its place.
+        let s1 = s.clone();
+        let first = task::spawn(async move { future::timeout(ms(30), s1.send(1)).await });
+        task::sleep(ms(10)).await;
+        let s2 = s.clone();
+        let handle = task::spawn(async move { s2.send(2).await });
+        assert!(first.await.is_err());
+
+        assert_eq!(r.recv().await, Some(0));
+        handle.await;
+        assert_eq!(r.recv().await, Some(2));
+        assert!(r.is_empty());
+    })
+}
+
+#[test]
+fn send_batch_waits_for_capacity() {
+    task::block_on(async {
+        let (s, r) = channel(3);
+
+        let handle = task::spawn(async move { s.send_batch(0..10).await });
+
+        let mut buf = Vec::new();
+        while r.recv_batch(&mut buf, 4).await > 0 {
+            assert!(buf.len() <= 10);
+        }
+        handle.await;
+        assert_eq!(buf, (0..10).collect::<Vec<_>>());
+    })
+}
+
+#[test]
+fn recv_batch_takes_queued_messages() {
+    task::block_on(async {
+        let (s, r) = channel(10);
+
+        s.send_batch(vec![1, 2, 3, 4, 5]).await;
+        assert_eq!(s.len(), 5);
+
+        let mut buf = vec![0];
+        assert_eq!(r.recv_batch(&mut buf, 3).await, 3);
+        assert_eq!(buf, [0, 1, 2, 3]);
+        assert_eq!(r.recv_batch(&mut buf, 10).await, 2);
+        assert_eq!(buf, [0, 1, 2, 3, 4, 5]);
+        assert!(r.is_empty());
+
+        // An empty channel waits for the first message.
+        let mut buf = Vec::new();
+        let res = future::timeout(ms(10), r.recv_batch(&mut buf, 10)).await;
+        assert!(res.is_err());
+        assert!(buf.is_empty());
+
+        drop(s);
+        assert_eq!(r.recv_batch(&mut buf, 10).await, 0);
+    })
+}
+
+#[test]
+fn recv_batch_wakes_blocked_senders() {
+    task::block_on(async {
+        let (s, r) = channel(2);
+        s.send_batch(vec![0, 0]).await;
+
+        let handles: Vec<_> = (1..=4)
+            .map(|i| {
+                let s = s.clone();
+                task::spawn(async move { s.send(i).await })
+            })
+            .collect();
+        task::sleep(ms(10)).await;
+
+        let mut buf = Vec::new();
+        while buf.len() < 6 {
+            r.recv_batch(&mut buf, 6).await;
+        }
+        for h in handles {
+            h.await;
+        }
+
+        buf.sort();
+        assert_eq!(buf, [0, 0, 1, 2, 3, 4]);
+        assert!(s.is_empty());
+    })
+}
+
+#[test]
+fn send_batch_on_closed_channel() {
+    task::block_on(async {
+        #[derive(Debug)]
+        struct DropCounter(Arc<AtomicUsize>);
+
+        impl Drop for DropCounter {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let drops = Arc::new(AtomicUsize::new(0));
+        let (s, r) = channel(2);
+
+        let msgs: Vec<_> = (0..5).map(|_| DropCounter(drops.clone())).collect();
+        let handle = task::spawn(async move { s.send_batch(msgs).await });
+        task::sleep(ms(10)).await;
+        drop(r);
+        handle.await;
+
+        assert_eq!(drops.load(Ordering::SeqCst), 5);
+    })
+}
+
+#[test]
+fn mpmc_batches() {
+    const COUNT: usize = 25_000;
+    const TASKS: usize = 4;
+
+    task::block_on(async {
+        let (s, r) = channel::<usize>(64);
+        let total = Arc::new(AtomicUsize::new(0));
+        let mut tasks = Vec::new();
+
+        for _ in 0..TASKS {
+            let r = r.clone();
+            let total = total.clone();
+            tasks.push(task::spawn(async move {
+                let mut buf = Vec::new();
+                loop {
+                    buf.clear();
+                    if r.recv_batch(&mut buf, 16).await == 0 {
+                        break;
+                    }
+                    total.fetch_add(buf.iter().sum::<usize>(), Ordering::SeqCst);
+                }
+            }));
+        }
+
+        for t in 0..TASKS {
+            let s = s.clone();
+            tasks.push(task::spawn(async move {
+                let start = t * COUNT;
+                s.send_batch(start..start + COUNT).await;
+            }));
+        }
+        drop(s);
+        drop(r);
+
+        for t in tasks {
+            t.await;
+        }
+
+        let n = TASKS * COUNT;
+        assert_eq!(total.load(Ordering::SeqCst), n * (n - 1) / 2);
+    })
+}
+
+#[test]
+fn rendezvous_waits_for_receiver() {
+    task::block_on(async {
+        let (s, r) = channel(0);
+        assert_eq!(s.capacity(), 0);
+        assert_eq!(r.capacity(), 0);
+
+        // Without a receiver, the send doesn't complete and the message is taken back.
+        assert!(future::timeout(ms(10), s.send(1)).await.is_err());
+        assert!(s.is_empty());
+
+        let done = Arc::new(AtomicUsize::new(0));
+        let d = done.clone();
+        let handle = task::spawn(async move {
+            s.send(2).await;
+            d.fetch_add(1, Ordering::SeqCst);
+            s.send(3).await;
+            d.fetch_add(1, Ordering::SeqCst);
+        });
+
+        task::sleep(ms(20)).await;
+        assert_eq!(done.load(Ordering::SeqCst), 0);
+
+        assert_eq!(r.recv().await, Some(2));
+        task::sleep(ms(20)).await;
+        assert_eq!(done.load(Ordering::SeqCst), 1);
+
+        assert_eq!(r.recv().await, Some(3));
+        handle.await;
+        assert_eq!(done.load(Ordering::SeqCst), 2);
+        assert_eq!(r.recv().await, None);
+    })
+}
+
+#[test]
+fn rendezvous_send_timeout() {
+    task::block_on(async {
+        let (s, r) = channel(0);
+
+        assert_eq!(
+            s.send_timeout(1, ms(10)).await,
+            Err(SendTimeoutError::Timeout(1))
+        );
+
+        let handle = task::spawn(async move { r.recv().await });
+        assert_eq!(s.send_timeout(2, ms(1000)).await, Ok(()));
+        assert_eq!(handle.await, Some(2));
+
+        assert_eq!(
+            s.send_timeout(3, ms(10)).await,
+            Err(SendTimeoutError::Disconnected(3))
+        );
+    })
+}
+
+#[test]
+fn rendezvous_close_takes_back_message() {
+    task::block_on(async {
+        let (s, r) = channel(0);
+
+        let handle = task::spawn(async move { s.send_timeout(1, ms(1000)).await });
+        task::sleep(ms(10)).await;
+        r.close();
+
+        assert_eq!(handle.await, Err(SendTimeoutError::Disconnected(1)));
+        assert_eq!(r.recv().await, None);
+    })
+}
+
+#[test]
+fn rendezvous_mpmc() {
+    const COUNT: usize = 1000;
+
+    task::block_on(async {
+        let (s, r) = channel::<usize>(0);
+        let total = Arc::new(AtomicUsize::new(0));
+        let mut tasks = Vec::new();
+
+        for _ in 0..4 {
+            let r = r.clone();
+            let total = total.clone();
+            tasks.push(task::spawn(async move {
+                while let Some(i) = r.recv().await {
+                    total.fetch_add(i, Ordering::SeqCst);
+                }
+            }));
+        }
+        for t in 0..4 {
+            let s = s.clone();
+            tasks.push(task::spawn(async move {
+                if t == 0 {
+                    s.send_batch(0..COUNT).await;
+                } else {
+                    for i in 0..COUNT {
+                        s.send(t * COUNT + i).await;
+                    }
+                }
+            }));
+        }
+        drop(s);
+        drop(r);
+
+        for t in tasks {
+            t.await;
+        }
+        let n = 4 * COUNT;
+        assert_eq!(total.load(Ordering::SeqCst), n * (n - 1) / 2);
+    })
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "tokio-compat")]
+
+use async_std::compat::{ReadCompatExt, TokioReadCompatExt, TokioWriteCompatExt};
+use async_std::io::{self, BufReader};
+use async_std::net::{TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::task;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
+
+#[test]
+fn async_std_stream_as_tokio() -> io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let client = TcpStream::connect(listener.local_addr()?).await?;
+        let (mut server, _) = listener.accept().await?;
+
+        // Shutting down the adapter closes the writing half of the stream.
+        let mut client = client.compat();
+        AsyncWriteExt::write_all(&mut client, b"hello").await?;
+        client.shutdown().await?;
+
+        let mut received = Vec::new();
+        server.read_to_end(&mut received).await?;
+        assert_eq!(received, b"hello");
+
+        server.write_all(b"world").await?;
+        drop(server);
+
+        let mut received = String::new();
+        AsyncReadExt::read_to_string(&mut client, &mut received).await?;
+        assert_eq!(received, "world");
+
+        Ok(())
+    })
+}
+
+#[test]
+fn async_std_buf_read_as_tokio() -> io::Result<()> {
+    task::block_on(async {
+        let mut reader = BufReader::new(&b"first\nsecond\n"[..]).compat();
+
+        let mut line = String::new();
+        AsyncBufReadExt::read_line(&mut reader, &mut line).await?;
+        assert_eq!(line, "first\n");
+
+        // The rest is still buffered in the underlying reader.
+        let mut rest = String::new();
+        reader.into_inner().read_to_string(&mut rest).await?;
+        assert_eq!(rest, "second\n");
+
+        Ok(())
+    })
+}
+
+#[test]
+fn tokio_stream_as_async_std() -> io::Result<()> {
+    task::block_on(async {
+        let (client, server) = tokio::io::duplex(4);
+        let mut client = client.compat();
+        let mut server = tokio::io::BufReader::new(server).compat();
+
+        // The duplex buffer is smaller than the message, so both sides have to make progress.
+        let writer = task::spawn(async move {
+            client.write_all(b"a longer line\nand another\n").await?;
+            client.close().await?;
+            io::Result::Ok(())
+        });
+
+        let mut lines = Vec::new();
+        let mut line = String::new();
+        while server.read_line(&mut line).await? != 0 {
+            lines.push(line.clone());
+            line.clear();
+        }
+        assert_eq!(lines, ["a longer line\n", "and another\n"]);
+
+        writer.await
+    })
+}
+
+#[test]
+fn tokio_writer_as_async_std() -> io::Result<()> {
+    task::block_on(async {
+        let (writer, mut reader) = tokio::io::duplex(64);
+
+        // Closing the adapter shuts the writer down.
+        let mut writer = writer.compat_write();
+        writer.write_all(b"hello").await?;
+        writer.close().await?;
+
+        let mut received = Vec::new();
+        reader.read_to_end(&mut received).await?;
+        assert_eq!(received, b"hello");
+
+        Ok(())
+    })
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::sync::{Arc, Condvar, Mutex};
+use async_std::task;
+
+#[test]
+fn notify_one_is_fifo() {
+    task::block_on(async {
+        let pair = Arc::new((Mutex::new(Vec::new()), Condvar::new()));
+        let mut handles = vec![];
+
+        for i in 0..3 {
+            let pair = pair.clone();
+            handles.push(task::spawn(async move {
+                let (lock, cvar) = &*pair;
+                let guard = lock.lock().await;
+                let mut guard = cvar.wait(guard).await;
+                guard.push(i);
+            }));
+            // Let the task start waiting before spawning the next one.
+            task::sleep(Duration::from_millis(10)).await;
+        }
+
+        let (lock, cvar) = &*pair;
+        for _ in 0..3 {
+            cvar.notify_one();
+            task::sleep(Duration::from_millis(10)).await;
+        }
+        for h in handles {
+            h.await;
+        }
+        assert_eq!(*lock.lock().await, [0, 1, 2]);
+    });
+}
+
+#[test]
+fn cancelled_waiter_passes_notification_on() {
+    task::block_on(async {
+        let pair = Arc::new((Mutex::new(()), Condvar::new()));
+        let (lock, cvar) = &*pair;
+
+        // Start waiting, which releases the mutex.
+        let mut first = Box::pin(cvar.wait(lock.lock().await));
+        assert!(futures::poll!(first.as_mut()).is_pending());
+
+        let second = task::spawn({
+            let pair = pair.clone();
+            async move {
+                let (lock, cvar) = &*pair;
+                drop(cvar.wait(lock.lock().await).await);
+            }
+        });
+        task::sleep(Duration::from_millis(10)).await;
+
+        // The notification goes to the first waiter, which is then cancelled.
+        cvar.notify_one();
+        drop(first);
+
+        let res = future::timeout(Duration::from_secs(5), second).await;
+        assert!(res.is_ok());
+    });
+}
+
+#[test]
+fn wait_timeout_until_checks_condition() {
+    task::block_on(async {
+        let pair = Arc::new((Mutex::new(0), Condvar::new()));
+        let pair2 = pair.clone();
+
+        task::spawn(async move {
+            let (lock, cvar) = &*pair2;
+            for _ in 0..5 {
+                task::sleep(Duration::from_millis(5)).await;
+                *lock.lock().await += 1;
+                cvar.notify_all();
+            }
+        });
+
+        let (lock, cvar) = &*pair;
+        let (n, res) = cvar
+            .wait_timeout_until(lock.lock().await, Duration::from_secs(5), |n| *n == 5)
+            .await;
+        assert!(!res.timed_out());
+        assert_eq!(*n, 5);
+    });
+}
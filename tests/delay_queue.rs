@@ -0,0 +1,83 @@
+#![cfg(feature = "unstable")]
+
+use std::task::Poll;
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::runtime::{Builder, Runtime};
+use async_std::time::{self, DelayQueue};
+
+fn runtime() -> Runtime {
+    Builder::new().build().unwrap()
+}
+
+#[test]
+fn expires_in_deadline_order() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+
+        let mut queue = DelayQueue::new();
+        for (i, secs) in [30, 10, 20, 10].iter().enumerate() {
+            queue.insert(i, Duration::from_secs(*secs));
+        }
+        assert_eq!(queue.len(), 4);
+
+        let mut expired = Vec::new();
+        while let Some(entry) = queue.next().await {
+            assert_eq!(entry.deadline(), time::now());
+            expired.push((entry.into_inner(), (time::now() - start).as_secs()));
+        }
+        assert_eq!(expired, [(1, 10), (3, 10), (2, 20), (0, 30)]);
+        assert!(queue.is_empty());
+    });
+}
+
+#[test]
+fn remove_and_reset() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+
+        let mut queue = DelayQueue::new();
+        let a = queue.insert("a", Duration::from_secs(10));
+        let b = queue.insert("b", Duration::from_secs(20));
+        let c = queue.insert("c", Duration::from_secs(30));
+
+        assert_eq!(queue.remove(&b), Some("b"));
+        assert_eq!(queue.remove(&b), None);
+        queue.reset(&a, Duration::from_secs(40));
+        assert_eq!(queue.deadline(&a), Some(start + Duration::from_secs(40)));
+
+        let entry = queue.next().await.unwrap();
+        assert_eq!(entry.key(), c);
+        assert_eq!(time::now() - start, Duration::from_secs(30));
+
+        let entry = queue.next().await.unwrap();
+        assert_eq!(entry.into_inner(), "a");
+        assert_eq!(time::now() - start, Duration::from_secs(40));
+        assert!(queue.next().await.is_none());
+    });
+}
+
+#[test]
+fn earlier_insert_moves_timer() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+
+        let mut queue = DelayQueue::new();
+        queue.insert("late", Duration::from_secs(60));
+
+        // Start waiting for the late value.
+        let polled = future::poll_fn(|cx| Poll::Ready(queue.poll_expired(cx).is_pending())).await;
+        assert!(polled);
+
+        queue.insert("early", Duration::from_secs(10));
+        assert_eq!(queue.next().await.unwrap().into_inner(), "early");
+        assert_eq!(time::now() - start, Duration::from_secs(10));
+        assert_eq!(queue.next().await.unwrap().into_inner(), "late");
+        assert_eq!(time::now() - start, Duration::from_secs(60));
+    });
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::sync::channel;
+use async_std::task::{self, Builder, TaskState};
+
+#[test]
+fn lists_live_tasks() {
+    task::block_on(async {
+        let (s, r) = channel::<()>(1);
+
+        let handle = Builder::new()
+            .name("waiting".to_string())
+            .spawn(async move { r.recv().await })
+            .unwrap();
+        let line = line!() - 2;
+        let id = handle.task().id();
+
+        // Wait for the task to block on the channel.
+        task::sleep(Duration::from_millis(50)).await;
+
+        let dump = task::dump();
+        let info = dump.iter().find(|info| info.id() == id).unwrap();
+        assert_eq!(info.name(), Some("waiting"));
+        assert_eq!(info.state(), TaskState::Idle);
+        assert_eq!(info.location().file(), file!());
+        assert_eq!(info.location().line(), line);
+
+        drop(s);
+        handle.await;
+        assert!(task::dump().iter().all(|info| info.id() != id));
+    });
+}
+
+#[test]
+fn reports_running_task() {
+    task::block_on(async {
+        let handle = task::spawn(async {
+            let id = task::current().id();
+            task::dump()
+                .into_iter()
+                .find(|info| info.id() == id)
+                .map(|info| info.state())
+        });
+        assert_eq!(handle.await, Some(TaskState::Running));
+    });
+}
+
+#[test]
+fn cancelled_task_is_removed() {
+    task::block_on(async {
+        let handle = task::spawn(task::sleep(Duration::from_secs(10)));
+        let id = handle.task().id();
+        assert!(task::dump().iter().any(|info| info.id() == id));
+
+        handle.cancel().await;
+        assert!(task::dump().iter().all(|info| info.id() != id));
+    });
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "unstable")]
+
+use std::path::Path;
+
+use async_std::fs;
+use async_std::io;
+use async_std::prelude::*;
+use async_std::task;
+use tempdir::TempDir;
+
+fn touch(root: &Path, paths: &[&str]) -> io::Result<()> {
+    for path in paths {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(path, b"")?;
+    }
+    Ok(())
+}
+
+async fn glob(root: &Path, pattern: &str) -> io::Result<Vec<String>> {
+    let pattern = format!("{}/{}", root.display(), pattern);
+    fs::glob(&pattern)?
+        .map(|res| {
+            res.map(|path| {
+                let path = path.strip_prefix(root).unwrap();
+                path.to_string_lossy().into_owned()
+            })
+        })
+        .collect()
+        .await
+}
+
+#[test]
+fn wildcards() -> io::Result<()> {
+    let dir = TempDir::new("glob")?;
+    touch(
+        dir.path(),
+        &["a.gz", "b.gz", "c.txt", "ab.gz", ".hidden.gz", "sub/d.gz"],
+    )?;
+
+    task::block_on(async {
+        assert_eq!(glob(dir.path(), "*.gz").await?, ["a.gz", "ab.gz", "b.gz"]);
+        assert_eq!(glob(dir.path(), "?.gz").await?, ["a.gz", "b.gz"]);
+        assert_eq!(glob(dir.path(), "[!a].*").await?, ["b.gz", "c.txt"]);
+        assert_eq!(glob(dir.path(), "[a-b]*.gz").await?, ["a.gz", "ab.gz", "b.gz"]);
+        assert_eq!(glob(dir.path(), ".*").await?, [".hidden.gz"]);
+        assert_eq!(glob(dir.path(), "*/d.gz").await?, ["sub/d.gz"]);
+        assert_eq!(glob(dir.path(), "sub/*").await?, ["sub/d.gz"]);
+        assert_eq!(glob(dir.path(), "c.txt").await?, ["c.txt"]);
+        assert!(glob(dir.path(), "missing/*").await?.is_empty());
+        Ok(())
+    })
+}
+
+#[test]
+fn recursive() -> io::Result<()> {
+    let dir = TempDir::new("glob")?;
+    touch(
+        dir.path(),
+        &[
+            "logs/a.gz",
+            "logs/2020/b.gz",
+            "logs/2020/01/c.gz",
+            "logs/2020/01/c.txt",
+            "logs/.cache/d.gz",
+        ],
+    )?;
+
+    task::block_on(async {
+        assert_eq!(
+            glob(dir.path(), "logs/**/*.gz").await?,
+            ["logs/a.gz", "logs/2020/b.gz", "logs/2020/01/c.gz"]
+        );
+        assert_eq!(
+            glob(dir.path(), "**/01/*").await?,
+            ["logs/2020/01/c.gz", "logs/2020/01/c.txt"]
+        );
+        assert_eq!(
+            glob(dir.path(), "logs/**").await?,
+            ["logs", "logs/2020", "logs/2020/01"]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn braces() -> io::Result<()> {
+    let dir = TempDir::new("glob")?;
+    touch(dir.path(), &["a.gz", "b.zst", "c.txt", "x/y/e.gz"])?;
+
+    task::block_on(async {
+        assert_eq!(glob(dir.path(), "*.{gz,zst}").await?, ["a.gz", "b.zst"]);
+        assert_eq!(
+            glob(dir.path(), "{*.gz,a.*,x/{y,z}/*}").await?,
+            ["a.gz", "x/y/e.gz"]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn invalid_patterns() {
+    for pattern in &["a/[bc", "{a,b", "a}"] {
+        let err = fs::glob(pattern).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "unstable")]
+
+use std::io::{Read as _, Seek as _, Write as _};
+
+use async_std::io::{self, AsyncAsSync, Cursor, SeekFrom, SyncAsAsync};
+use async_std::prelude::*;
+use async_std::task;
+
+#[test]
+fn async_as_sync() -> io::Result<()> {
+    task::block_on(async {
+        let cursor = Cursor::new(Vec::new());
+        let cursor = task::spawn_blocking(move || {
+            let mut io = AsyncAsSync::new(cursor);
+            io.write_all(b"hello world")?;
+            io.flush()?;
+            io.seek(SeekFrom::Start(6))?;
+
+            let mut word = String::new();
+            io.read_to_string(&mut word)?;
+            assert_eq!(word, "world");
+            io::Result::Ok(io.into_inner())
+        })
+        .await?;
+        assert_eq!(cursor.into_inner(), b"hello world");
+        Ok(())
+    })
+}
+
+#[test]
+fn sync_as_async() -> io::Result<()> {
+    task::block_on(async {
+        let data: Vec<u8> = (0..100_000).map(|i| i as u8).collect();
+
+        let mut reader = SyncAsAsync::new(std::io::Cursor::new(data.clone()));
+        let mut read = Vec::new();
+        let mut buf = [0; 1000];
+        loop {
+            let n = reader.read(&mut buf).await?;
+            if n == 0 {
+                break;
+            }
+            read.extend_from_slice(&buf[..n]);
+        }
+        assert_eq!(read, data);
+
+        let mut writer = SyncAsAsync::new(Vec::new());
+        for chunk in data.chunks(777) {
+            writer.write_all(chunk).await?;
+        }
+        writer.flush().await?;
+        assert_eq!(writer.into_inner().await, data);
+        Ok(())
+    })
+}
+
+#[test]
+fn sync_as_async_errors() {
+    struct Failing;
+
+    impl std::io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "broken"))
+        }
+    }
+
+    task::block_on(async {
+        let mut reader = SyncAsAsync::new(Failing);
+        let err = reader.read(&mut [0; 8]).await.unwrap_err();
+        assert_eq!(err.to_string(), "broken");
+    });
+}
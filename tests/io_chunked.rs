@@ -0,0 +1,96 @@
+#![cfg(feature = "unstable")]
+
+use std::pin::Pin;
+
+use async_std::io::chunked::{ChunkedReader, ChunkedWriter};
+use async_std::io::{self, BufReader};
+use async_std::prelude::*;
+use async_std::task::{self, Context, Poll};
+
+/// A reader yielding its data one byte at a time.
+struct Trickle(Vec<u8>);
+
+impl io::Read for Trickle {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        buf[0] = self.0.remove(0);
+        Poll::Ready(Ok(1))
+    }
+}
+
+#[test]
+fn decode() -> io::Result<()> {
+    task::block_on(async {
+        let input = b"4;name=value\r\nWiki\r\n5\r\npedia\r\nE\r\n in\r\n\r\nchunks.\r\n0\r\nExpires: never\r\n\r\nNEXT";
+        let mut reader = ChunkedReader::new(BufReader::new(Trickle(input.to_vec())));
+
+        let mut body = String::new();
+        reader.read_to_string(&mut body).await?;
+        assert_eq!(body, "Wikipedia in\r\n\r\nchunks.");
+        assert!(reader.is_done());
+
+        // The bytes after the body are left in the reader.
+        let mut rest = String::new();
+        reader.into_inner().read_to_string(&mut rest).await?;
+        assert_eq!(rest, "NEXT");
+
+        Ok(())
+    })
+}
+
+#[test]
+fn decode_errors() {
+    task::block_on(async {
+        for (input, kind) in [
+            (&b"zz\r\n"[..], io::ErrorKind::InvalidData),
+            (&b"+5\r\nhello\r\n0\r\n\r\n"[..], io::ErrorKind::InvalidData),
+            (&b" 5\r\nhello\r\n0\r\n\r\n"[..], io::ErrorKind::InvalidData),
+            (&b"5 \r\nhello\r\n0\r\n\r\n"[..], io::ErrorKind::InvalidData),
+            (&b"5\t;ext\r\nhello\r\n"[..], io::ErrorKind::InvalidData),
+            (&b";ext\r\n"[..], io::ErrorKind::InvalidData),
+            (&b"0x5\r\n"[..], io::ErrorKind::InvalidData),
+            (&b"10000000000000000\r\n"[..], io::ErrorKind::InvalidData),
+            (&b"3\r\nabcd\r\n"[..], io::ErrorKind::InvalidData),
+            (&b"5\r\nabc"[..], io::ErrorKind::UnexpectedEof),
+            (&b"3\r\nabc\r\n"[..], io::ErrorKind::UnexpectedEof),
+        ] {
+            let mut body = Vec::new();
+            let err = ChunkedReader::new(input)
+                .read_to_end(&mut body)
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), kind);
+        }
+    })
+}
+
+#[test]
+fn round_trip() -> io::Result<()> {
+    task::block_on(async {
+        let data: Vec<u8> = (0..100_000u32).map(|i| i as u8).collect();
+
+        let mut writer = ChunkedWriter::new(Vec::new());
+        for part in data.chunks(7_000) {
+            writer.write_all(part).await?;
+        }
+        writer.finish().await?;
+        assert!(writer.write(b"more").await.is_err());
+
+        let encoded = writer.into_inner();
+        assert!(encoded.ends_with(b"\r\n0\r\n\r\n"));
+
+        let mut decoded = Vec::new();
+        ChunkedReader::new(&encoded[..])
+            .read_to_end(&mut decoded)
+            .await?;
+        assert_eq!(decoded, data);
+
+        Ok(())
+    })
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "unstable")]
+
+use std::pin::Pin;
+
+use async_std::io::codec::{
+    Decoder, Framed, FramedRead, FramedWrite, LengthDelimitedCodec, LinesCodec,
+};
+use async_std::io::{self, Cursor};
+use async_std::net::{TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::sink::SinkExt;
+use async_std::task::{self, Context, Poll};
+
+/// A reader yielding its data one byte at a time.
+struct Trickle(Vec<u8>);
+
+impl io::Read for Trickle {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        buf[0] = self.0.remove(0);
+        Poll::Ready(Ok(1))
+    }
+}
+
+#[test]
+fn lines() -> io::Result<()> {
+    task::block_on(async {
+        let input = b"one\r\ntwo\n\nthree".to_vec();
+        let lines: Vec<String> = FramedRead::new(Trickle(input), LinesCodec::new())
+            .collect::<io::Result<_>>()
+            .await?;
+        assert_eq!(lines, ["one", "two", "", "three"]);
+
+        let mut codec = LinesCodec::with_max_length(3);
+        let mut buf = b"four\n".to_vec();
+        assert_eq!(
+            codec.decode(&mut buf).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+
+        let mut reader = FramedRead::new(&[0xff, b'\n'][..], LinesCodec::new());
+        assert!(reader.next().await.unwrap().is_err());
+        assert!(reader.next().await.is_none());
+        Ok(())
+    })
+}
+
+#[test]
+fn length_delimited() -> io::Result<()> {
+    task::block_on(async {
+        let codec = LengthDelimitedCodec::new()
+            .length_field_length(2)
+            .little_endian()
+            .max_frame_length(300);
+        let mut writer = FramedWrite::new(Vec::new(), codec);
+        SinkExt::send(&mut writer, vec![7; 258]).await?;
+        SinkExt::send(&mut writer, b"").await?;
+        let bytes = writer.into_inner();
+        assert_eq!(&bytes[..2], [2, 1]);
+
+        let frames: Vec<Vec<u8>> = FramedRead::new(Trickle(bytes.clone()), codec)
+            .collect::<io::Result<_>>()
+            .await?;
+        assert_eq!(frames, [vec![7; 258], vec![]]);
+
+        // A truncated frame is an error.
+        let mut reader = FramedRead::new(&bytes[..100], codec);
+        let err = reader.next().await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        // So is a frame above the maximum length.
+        let mut writer = FramedWrite::new(Cursor::new(Vec::new()), codec);
+        let err = SinkExt::send(&mut writer, vec![0; 301]).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        Ok(())
+    })
+}
+
+#[test]
+fn framed_echo() -> io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        let server = task::spawn(async move {
+            let (stream, _) = listener.accept().await?;
+            let mut framed = Framed::new(stream, LinesCodec::new());
+            while let Some(line) = framed.next().await {
+                SinkExt::send(&mut framed, line?.to_uppercase()).await?;
+            }
+            io::Result::Ok(())
+        });
+
+        let stream = TcpStream::connect(addr).await?;
+        let mut framed = Framed::new(stream, LinesCodec::new());
+        for word in &["hello", "world"] {
+            SinkExt::send(&mut framed, word).await?;
+            assert_eq!(framed.next().await.unwrap()?, word.to_uppercase());
+        }
+        drop(framed);
+        server.await
+    })
+}
@@ -0,0 +1,149 @@
+#![cfg(feature = "compress")]
+
+use std::pin::Pin;
+
+use async_std::io::compress::{
+    DeflateDecoder, DeflateEncoder, GzipDecoder, GzipEncoder, ZlibDecoder, ZlibEncoder,
+};
+use async_std::io::{self, BufReader};
+use async_std::prelude::*;
+use async_std::task::{self, Context, Poll};
+
+/// A gzip file with a file name in its header, as written by Python's `gzip` module.
+const GZIP_FILE: &[u8] = &[
+    31, 139, 8, 8, 0, 0, 0, 0, 2, 255, 104, 101, 108, 108, 111, 46, 116, 120, 116, 0, 203, 72, 205,
+    201, 201, 215, 81, 72, 175, 202, 44, 80, 40, 207, 47, 202, 73, 225, 202, 32, 66, 4, 0, 68, 20,
+    233, 145, 54, 0, 0, 0,
+];
+
+/// `zlib data zlib data` in the zlib format, as written by Python's `zlib` module.
+const ZLIB_DATA: &[u8] = &[
+    120, 156, 171, 202, 201, 76, 82, 72, 73, 44, 73, 84, 168, 130, 177, 0, 70, 99, 6, 247,
+];
+
+/// A reader yielding its data one byte at a time.
+struct Trickle(Vec<u8>);
+
+impl io::Read for Trickle {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        buf[0] = self.0.remove(0);
+        Poll::Ready(Ok(1))
+    }
+}
+
+/// Data that compresses well but isn't trivial.
+fn sample() -> Vec<u8> {
+    (0..200_000u32)
+        .map(|i| (i % 251) as u8 ^ (i / 1000) as u8)
+        .collect()
+}
+
+#[test]
+fn decode_foreign_data() -> io::Result<()> {
+    task::block_on(async {
+        let mut text = String::new();
+        GzipDecoder::new(BufReader::new(Trickle(GZIP_FILE.to_vec())))
+            .read_to_string(&mut text)
+            .await?;
+        assert_eq!(text, "hello, gzip world\n".repeat(3));
+
+        let mut text = String::new();
+        ZlibDecoder::new(ZLIB_DATA)
+            .read_to_string(&mut text)
+            .await?;
+        assert_eq!(text, "zlib data zlib data");
+
+        Ok(())
+    })
+}
+
+#[test]
+fn decode_errors() {
+    task::block_on(async {
+        let mut corrupt = GZIP_FILE.to_vec();
+        let len = corrupt.len();
+        corrupt[len - 8] ^= 1;
+        let err = GzipDecoder::new(&corrupt[..])
+            .read_to_end(&mut Vec::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let err = GzipDecoder::new(&GZIP_FILE[..30])
+            .read_to_end(&mut Vec::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+        let err = ZlibDecoder::new(&b"not zlib"[..])
+            .read_to_end(&mut Vec::new())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    })
+}
+
+#[test]
+fn round_trip() -> io::Result<()> {
+    task::block_on(async {
+        let data = sample();
+
+        let mut encoder = GzipEncoder::new(Vec::new());
+        for part in data.chunks(3_000) {
+            encoder.write_all(part).await?;
+        }
+        encoder.close().await?;
+        let compressed = encoder.into_inner();
+        assert!(compressed.len() < data.len() / 10);
+        let mut decoded = Vec::new();
+        GzipDecoder::new(&compressed[..])
+            .read_to_end(&mut decoded)
+            .await?;
+        assert_eq!(decoded, data);
+
+        let mut encoder = ZlibEncoder::with_level(Vec::new(), 1);
+        encoder.write_all(&data).await?;
+        encoder.finish().await?;
+        assert!(encoder.write(b"more").await.is_err());
+        let mut decoded = Vec::new();
+        ZlibDecoder::new(&encoder.into_inner()[..])
+            .read_to_end(&mut decoded)
+            .await?;
+        assert_eq!(decoded, data);
+
+        let mut encoder = DeflateEncoder::with_level(Vec::new(), 0);
+        encoder.write_all(&data).await?;
+        encoder.finish().await?;
+        let mut decoded = Vec::new();
+        DeflateDecoder::new(&encoder.into_inner()[..])
+            .read_to_end(&mut decoded)
+            .await?;
+        assert_eq!(decoded, data);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn flush_outputs_everything_written() -> io::Result<()> {
+    task::block_on(async {
+        let mut encoder = DeflateEncoder::new(Vec::new());
+        encoder.write_all(b"first message").await?;
+        encoder.flush().await?;
+
+        // The flushed data can be decompressed before the stream has ended.
+        let mut decoder = DeflateDecoder::new(&encoder.get_ref()[..]);
+        let mut buf = [0; 13];
+        decoder.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"first message");
+
+        Ok(())
+    })
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "unstable")]
+
+use async_std::io::hash::{Crc32, Digest, HashingReader, HashingWriter, Sha256};
+use async_std::io::{self, Read};
+use async_std::prelude::*;
+use async_std::task;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// A reader returning one byte at a time.
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        if self.0.is_empty() || buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        buf[0] = self.0[0];
+        self.0 = &self.0[1..];
+        Poll::Ready(Ok(1))
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    let mut sha = Sha256::new();
+    sha.update(bytes);
+    hex(&sha.finalize())
+}
+
+#[test]
+fn sha256_vectors() {
+    assert_eq!(
+        sha256(b""),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        sha256(b"abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+    assert_eq!(
+        sha256(&[b'a'; 1_000_000]),
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+    );
+}
+
+#[test]
+fn reader_hashes_in_pieces() -> io::Result<()> {
+    task::block_on(async {
+        let data = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq".repeat(3);
+
+        let mut reader = HashingReader::new(Trickle(&data), Sha256::new());
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).await?;
+
+        assert_eq!(out, data);
+        assert_eq!(hex(&reader.digest().finalize()), sha256(&data));
+        Ok(())
+    })
+}
+
+#[test]
+fn writer_hashes_accepted_bytes() -> io::Result<()> {
+    task::block_on(async {
+        let mut writer = HashingWriter::new(Vec::new(), Crc32::new()).with_offload_threshold(4);
+        writer.write_all(b"1234").await?;
+        writer.write_all(b"56789").await?;
+        writer.flush().await?;
+
+        let (bytes, crc) = writer.into_parts();
+        assert_eq!(bytes, b"123456789");
+        assert_eq!(crc.finalize(), 0xcbf4_3926);
+        Ok(())
+    })
+}
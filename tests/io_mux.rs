@@ -0,0 +1,116 @@
+#![cfg(feature = "unstable")]
+
+use async_std::io::{self, mux::Session};
+use async_std::net::{TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::task;
+
+/// Connects a client and a server session over TCP.
+async fn sessions() -> io::Result<(Session, Session)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let client = TcpStream::connect(listener.local_addr()?).await?;
+    let (server, _) = listener.accept().await?;
+    Ok((Session::client(client), Session::server(server)))
+}
+
+#[test]
+fn channels_in_both_directions() -> io::Result<()> {
+    task::block_on(async {
+        let (client, server) = sessions().await?;
+
+        let mut a = client.open()?;
+        let mut b = server.open()?;
+        assert_eq!(a.id() % 2, 1);
+        assert_eq!(b.id() % 2, 0);
+
+        a.write_all(b"from client").await?;
+        a.close().await?;
+        b.write_all(b"from server").await?;
+        b.close().await?;
+
+        let mut a2 = server.accept().await?;
+        let mut b2 = client.accept().await?;
+        assert_eq!(a2.id(), a.id());
+        assert_eq!(b2.id(), b.id());
+
+        let mut s = String::new();
+        a2.read_to_string(&mut s).await?;
+        assert_eq!(s, "from client");
+
+        s.clear();
+        b2.read_to_string(&mut s).await?;
+        assert_eq!(s, "from server");
+        Ok(())
+    })
+}
+
+#[test]
+fn flow_control_does_not_block_other_channels() -> io::Result<()> {
+    task::block_on(async {
+        let (client, server) = sessions().await?;
+        let data: Vec<u8> = (0..1024 * 1024).map(|i| i as u8).collect();
+
+        // Nobody reads this channel until the end, so the writer has to wait for its window.
+        let mut big = client.open()?;
+        let expected = data.clone();
+        let writer = task::spawn(async move {
+            big.write_all(&expected).await?;
+            big.close().await?;
+            io::Result::Ok(big)
+        });
+
+        let mut small = client.open()?;
+        small.write_all(b"ping").await?;
+        small.close().await?;
+
+        let mut big2 = server.accept().await?;
+        let mut small2 = server.accept().await?;
+        let mut s = String::new();
+        small2.read_to_string(&mut s).await?;
+        assert_eq!(s, "ping");
+
+        let mut received = Vec::new();
+        big2.read_to_end(&mut received).await?;
+        assert!(received == data);
+        writer.await?;
+        Ok(())
+    })
+}
+
+#[test]
+fn dropped_channel_is_reset() -> io::Result<()> {
+    task::block_on(async {
+        let (client, server) = sessions().await?;
+
+        let mut a = client.open()?;
+        a.write_all(b"partial").await?;
+        a.flush().await?;
+        drop(a);
+
+        let mut a2 = server.accept().await?;
+        let mut buf = [0; 7];
+        a2.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"partial");
+        let err = a2.read(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionReset);
+        Ok(())
+    })
+}
+
+#[test]
+fn closed_session_refuses_channels() -> io::Result<()> {
+    task::block_on(async {
+        let (client, server) = sessions().await?;
+        server.close();
+
+        let err = server.accept().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionAborted);
+
+        // Once the client learns the server went away, it can't open channels anymore.
+        while client.open().is_ok() {
+            task::yield_now().await;
+        }
+        assert!(client.accept().await.is_err());
+        Ok(())
+    })
+}
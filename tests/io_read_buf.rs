@@ -0,0 +1,95 @@
+#![cfg(feature = "unstable")]
+
+use async_std::fs::File;
+use async_std::io::{self, BufferPool, Cursor};
+use async_std::net::{TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::task;
+
+#[test]
+fn read_into_vec() -> io::Result<()> {
+    task::block_on(async {
+        let data: Vec<u8> = (0..20_000u32).map(|i| i as u8).collect();
+        let mut reader = Cursor::new(data.clone());
+
+        // The bytes are appended after the existing ones.
+        let mut buf = b"head".to_vec();
+        while reader.read_buf(&mut buf).await? > 0 {}
+        assert_eq!(&buf[..4], b"head");
+        assert_eq!(&buf[4..], &data[..]);
+        Ok(())
+    })
+}
+
+#[test]
+fn pooled_buffers() -> io::Result<()> {
+    task::block_on(async {
+        let pool = BufferPool::with_max_pooled(16, 1);
+        let mut reader = Cursor::new(b"hello world, this is more than sixteen bytes".to_vec());
+
+        let mut buf = pool.get();
+        assert!(buf.is_empty());
+        assert_eq!(reader.read_buf(&mut buf).await?, 16);
+        assert_eq!(&buf[..], b"hello world, thi");
+
+        // A full buffer grows.
+        while reader.read_buf(&mut buf).await? > 0 {}
+        assert_eq!(buf.len(), 44);
+
+        let other = pool.get();
+        drop(buf);
+        drop(other);
+        assert_eq!(pool.pooled(), 1);
+
+        // Buffers come back empty and shrunk to the size of the pool.
+        let buf = pool.get();
+        assert!(buf.is_empty());
+        assert_eq!(buf.capacity(), 16);
+        assert_eq!(pool.pooled(), 0);
+
+        // Detached buffers don't return to the pool.
+        assert!(buf.into_vec().is_empty());
+        assert_eq!(pool.pooled(), 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn read_from_tcp_and_file() -> io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        task::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await?;
+            stream.write_all(b"over the network").await?;
+            io::Result::Ok(())
+        });
+
+        let pool = BufferPool::new(1024);
+        let (mut stream, _) = listener.accept().await?;
+        let mut buf = pool.get();
+        while stream.read_buf(&mut buf).await? > 0 {}
+        assert_eq!(&buf[..], b"over the network");
+
+        let dir = tempdir::TempDir::new("read_buf")?;
+        let path = dir.path().join("file");
+        async_std::fs::write(&path, b"from a file").await?;
+        let mut file = File::open(&path).await?;
+        buf.clear();
+        while file.read_buf(&mut buf).await? > 0 {}
+        assert_eq!(&buf[..], b"from a file");
+        Ok(())
+    })
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn read_into_bytes_mut() -> io::Result<()> {
+    task::block_on(async {
+        let mut reader = Cursor::new(vec![7; 10_000]);
+        let mut buf = bytes::BytesMut::new();
+        while reader.read_buf(&mut buf).await? > 0 {}
+        assert_eq!(&buf[..], &[7; 10_000][..]);
+        Ok(())
+    })
+}
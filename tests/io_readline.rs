@@ -0,0 +1,90 @@
+#![cfg(feature = "unstable")]
+
+use async_std::io::{self, readline::Editor};
+use async_std::task;
+
+/// Feeds `input` to the editor, returning the lines it reads until the end of input.
+async fn lines(editor: &mut Editor, input: &[u8]) -> io::Result<Vec<String>> {
+    let mut input = input;
+    let mut lines = Vec::new();
+    while let Some(line) = editor.read_line_with("> ", &mut input, io::sink()).await? {
+        lines.push(line);
+    }
+    Ok(lines)
+}
+
+#[test]
+fn editing_keys() -> io::Result<()> {
+    task::block_on(async {
+        let mut editor = Editor::new();
+        let input = concat!(
+            "world\x01hello \r",          // Ctrl-A
+            "abc\x08\x08x\x1b[3~\r",      // Backspace and Delete
+            "one two three\x17\x17\r",    // Ctrl-W
+            "keep\x1b[D\x1b[D\x0b\r",     // Ctrl-K
+            "drop keep\x1bb\x15\r",       // Alt-B and Ctrl-U
+            "caf\u{e9} \u{1f980}\x02!\r"  // Unicode and Ctrl-B
+        );
+        assert_eq!(
+            lines(&mut editor, input.as_bytes()).await?,
+            ["hello world", "ax", "one ", "ke", "keep", "café !🦀"],
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn history() -> io::Result<()> {
+    task::block_on(async {
+        let mut editor = Editor::new();
+        editor.set_max_history(2);
+        editor.add_history("first");
+
+        let input = "second\r\nsecond\r\r\x1b[A\x1b[A!\r\x10\x10\x10\x0e\rabc\x10\x0e\r";
+        assert_eq!(
+            lines(&mut editor, input.as_bytes()).await?,
+            ["second", "second", "", "first!", "first!", "abc"],
+        );
+        assert_eq!(editor.history().collect::<Vec<_>>(), ["first!", "abc"]);
+        Ok(())
+    })
+}
+
+#[test]
+fn completion() -> io::Result<()> {
+    task::block_on(async {
+        let mut editor = Editor::new();
+        editor.set_completer(|line, pos| {
+            let start = line[..pos].rfind(' ').map_or(0, |i| i + 1);
+            let candidates = ["history", "help", "quit"]
+                .iter()
+                .filter(|w| w.starts_with(&line[start..pos]))
+                .map(|w| w.to_string())
+                .collect();
+            (start, candidates)
+        });
+
+        let mut output = Vec::new();
+        let line = editor
+            .read_line_with("> ", &b"q\t h\tis\t\r"[..], &mut output)
+            .await?;
+        assert_eq!(line.as_deref(), Some("quit history"));
+        Ok(())
+    })
+}
+
+#[test]
+fn end_of_input() -> io::Result<()> {
+    task::block_on(async {
+        let mut editor = Editor::new();
+        assert_eq!(lines(&mut editor, b"a\x04b\x04\x04").await?, ["ab"]);
+        assert_eq!(lines(&mut editor, b"unterminated").await?, ["unterminated"]);
+
+        let err = editor
+            .read_line_with("> ", &b"oops\x03"[..], io::sink())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        Ok(())
+    })
+}
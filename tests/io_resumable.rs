@@ -0,0 +1,151 @@
+#![cfg(feature = "unstable")]
+
+use std::pin::Pin;
+
+use async_std::io::{self, BufReader, Read, Write};
+use async_std::prelude::*;
+use async_std::task::{self, Context, Poll};
+use futures::FutureExt;
+
+/// Moves a byte at a time, and only on every other poll.
+struct Trickle {
+    data: Vec<u8>,
+    pos: usize,
+    ready: bool,
+}
+
+impl Trickle {
+    fn new(data: &[u8]) -> Trickle {
+        Trickle {
+            data: data.to_vec(),
+            pos: 0,
+            ready: false,
+        }
+    }
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.ready = !self.ready;
+        if self.ready {
+            Poll::Ready(())
+        } else {
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+impl Read for Trickle {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        futures::ready!(self.poll_ready(cx));
+        if self.pos == self.data.len() || buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        buf[0] = self.data[self.pos];
+        self.pos += 1;
+        Poll::Ready(Ok(1))
+    }
+}
+
+impl Write for Trickle {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        futures::ready!(self.poll_ready(cx));
+        self.data.extend_from_slice(&buf[..1]);
+        Poll::Ready(Ok(1))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+#[test]
+fn read_exact_resumable() -> io::Result<()> {
+    let mut reader = Trickle::new(b"hello world");
+    let mut buf = [0; 5];
+    let mut filled = 0;
+
+    let mut attempts = 0;
+    while reader
+        .read_exact_resumable(&mut buf, &mut filled)
+        .now_or_never()
+        .is_none()
+    {
+        attempts += 1;
+    }
+    assert!(attempts > 0);
+    assert_eq!(&buf, b"hello");
+    assert_eq!(filled, 5);
+
+    let mut rest = [0; 10];
+    let mut filled = 0;
+    let err = task::block_on(reader.read_exact_resumable(&mut rest, &mut filled)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(&rest[..filled], b" world");
+    Ok(())
+}
+
+#[test]
+fn write_all_resumable() -> io::Result<()> {
+    let mut writer = Trickle::new(b"");
+    let mut written = 0;
+
+    while writer
+        .write_all_resumable(b"hello", &mut written)
+        .now_or_never()
+        .is_none()
+    {}
+    assert_eq!(writer.data, b"hello");
+    assert_eq!(written, 5);
+    Ok(())
+}
+
+#[test]
+fn read_line_resumable() -> io::Result<()> {
+    let mut reader = BufReader::with_capacity(1, Trickle::new("héllo\nwörld".as_bytes()));
+    let mut line = String::from("> ");
+    let mut partial = Vec::new();
+
+    let n = loop {
+        if let Some(res) = reader
+            .read_line_resumable(&mut line, &mut partial)
+            .now_or_never()
+        {
+            break res?;
+        }
+    };
+    assert_eq!(n, 7);
+    assert_eq!(line, "> héllo\n");
+    assert!(partial.is_empty());
+
+    line.clear();
+    task::block_on(reader.read_line_resumable(&mut line, &mut partial))?;
+    assert_eq!(line, "wörld");
+    Ok(())
+}
+
+#[test]
+fn read_line_keeps_buffer_when_cancelled() -> io::Result<()> {
+    let mut reader = BufReader::with_capacity(1, Trickle::new(b"hello\n"));
+    let mut line = String::from("previous");
+
+    assert!(reader.read_line(&mut line).now_or_never().is_none());
+    assert_eq!(line, "previous");
+
+    // The part of the line read before cancellation is lost.
+    let n = task::block_on(reader.read_line(&mut line))?;
+    assert_eq!(n, 5);
+    assert_eq!(line, "previousello\n");
+    Ok(())
+}
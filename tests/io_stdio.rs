@@ -0,0 +1,164 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::io::{self, BufferMode};
+use async_std::prelude::*;
+use async_std::task;
+
+#[test]
+fn owned_locks_exclude_each_other() -> io::Result<()> {
+    task::block_on(async {
+        let lock = io::stdout().lock_owned().await;
+
+        let waiter = task::spawn(async {
+            let mut lock = io::stdout().lock_owned().await;
+            lock.flush().await
+        });
+        let res = future::timeout(Duration::from_millis(50), io::stdout().lock_owned()).await;
+        assert!(res.is_err());
+
+        // The guard can be moved into another task and held across await points.
+        task::spawn(async move {
+            let mut lock = lock;
+            task::yield_now().await;
+            lock.flush().await
+        })
+        .await?;
+        waiter.await?;
+
+        drop(io::stdin().lock_owned().await);
+        let mut stderr = io::stderr().lock_owned().await;
+        stderr.flush().await
+    })
+}
+
+#[test]
+fn buffer_mode() -> io::Result<()> {
+    task::block_on(async {
+        let stdout = io::stdout();
+        assert_eq!(stdout.buffer_mode(), BufferMode::Line);
+
+        stdout.set_buffer_mode(BufferMode::Block);
+        assert_eq!(stdout.buffer_mode(), BufferMode::Block);
+        stdout.set_buffer_mode(BufferMode::Line);
+        assert_eq!(stdout.buffer_mode(), BufferMode::Line);
+        Ok(())
+    })
+}
+
+/// Writes numbered lines in block-buffered mode when run by `block_buffered_output`.
+#[test]
+fn block_buffered_child() -> io::Result<()> {
+    if std::env::var_os("ASYNC_STD_STDIO_CHILD").is_none() {
+        return Ok(());
+    }
+    task::block_on(async {
+        let mut stdout = io::stdout();
+        stdout.set_buffer_mode(BufferMode::Block);
+        for i in 0..2000 {
+            stdout.write_all(format!("line {}\n", i).as_bytes()).await?;
+        }
+        stdout.write_all(b"flushed\n").await?;
+        stdout.flush().await?;
+
+        let mut lock = stdout.lock_owned().await;
+        lock.write_all(b"locked\n").await?;
+        drop(lock);
+
+        stdout.write_all(b"dropped\n").await
+    })
+}
+
+#[test]
+fn block_buffered_output() -> io::Result<()> {
+    let output = std::process::Command::new(std::env::current_exe()?)
+        .args([
+            "--exact",
+            "block_buffered_child",
+            "--nocapture",
+            "--test-threads=1",
+        ])
+        .env("ASYNC_STD_STDIO_CHILD", "1")
+        .output()?;
+    assert!(output.status.success());
+
+    let mut expected: String = (0..2000).map(|i| format!("line {}\n", i)).collect();
+    expected.push_str("flushed\nlocked\n");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&expected));
+    assert!(stdout.contains("dropped\n"));
+    Ok(())
+}
+
+/// Reads lines from stdin with a short timeout when run by `cancelled_reads_keep_input`.
+#[test]
+fn cancelled_reads_child() -> io::Result<()> {
+    if std::env::var_os("ASYNC_STD_STDIN_CHILD").is_none() {
+        return Ok(());
+    }
+    task::block_on(async {
+        let mut timeouts = 0;
+        loop {
+            let mut line = String::new();
+            match io::timeout(Duration::from_millis(5), io::stdin().read_line(&mut line)).await {
+                Ok(0) => break,
+                Ok(_) => print!("read_line: {}", line),
+                Err(_) => timeouts += 1,
+            }
+            if line.starts_with("switch") {
+                break;
+            }
+        }
+        assert!(timeouts > 0);
+
+        let mut lines = io::stdin().lines();
+        loop {
+            match future::timeout(Duration::from_millis(5), lines.next()).await {
+                Ok(Some(line)) => println!("lines: {}", line?),
+                Ok(None) => break,
+                Err(_) => {}
+            }
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn cancelled_reads_keep_input() -> io::Result<()> {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(std::env::current_exe()?)
+        .args(["--exact", "cancelled_reads_child", "--nocapture"])
+        .env("ASYNC_STD_STDIN_CHILD", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+
+    let mut stdin = child.stdin.take().unwrap();
+    for line in &[
+        "one\n",
+        "two\n",
+        "switch\n",
+        "three\r\n",
+        "four\n",
+        "fi",
+        "ve",
+    ] {
+        std::thread::sleep(Duration::from_millis(30));
+        stdin.write_all(line.as_bytes())?;
+        stdin.flush()?;
+    }
+    drop(stdin);
+
+    let output = child.wait_with_output()?;
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(
+        "read_line: one\nread_line: two\nread_line: switch\n\
+         lines: three\nlines: four\nlines: five\n"
+    ));
+    Ok(())
+}
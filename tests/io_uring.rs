@@ -0,0 +1,125 @@
+#![cfg(all(feature = "io-uring", target_os = "linux"))]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::io;
+use async_std::net::{Shutdown, TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::runtime::{Builder, Runtime};
+use async_std::task;
+
+fn runtime() -> Runtime {
+    Builder::new().io_uring(true).build().unwrap()
+}
+
+#[test]
+fn echo() -> io::Result<()> {
+    runtime().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        let server = task::spawn(async move {
+            let (stream, _) = listener.accept().await?;
+            let mut writer = &stream;
+            io::copy(&stream, &mut writer).await?;
+            writer.flush().await?;
+            stream.shutdown(Shutdown::Write)
+        });
+
+        // More than a single operation can carry.
+        let data: Vec<u8> = (0..1_000_000u32).map(|i| i as u8).collect();
+
+        let stream = Arc::new(TcpStream::connect(addr).await?);
+        let writer = task::spawn({
+            let stream = stream.clone();
+            let data = data.clone();
+            async move {
+                (&*stream).write_all(&data).await?;
+                (&*stream).flush().await?;
+                stream.shutdown(Shutdown::Write)
+            }
+        });
+
+        let mut echoed = Vec::new();
+        (&*stream).read_to_end(&mut echoed).await?;
+        writer.await?;
+        assert_eq!(echoed, data);
+
+        server.await
+    })
+}
+
+#[test]
+fn drop_cancels_pending_read() -> io::Result<()> {
+    runtime().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let mut client = TcpStream::connect(listener.local_addr()?).await?;
+        let (server, _) = listener.accept().await?;
+
+        // Leave a read in flight, then drop the stream.
+        let mut buf = [0; 16];
+        let res = io::timeout(Duration::from_millis(50), (&server).read(&mut buf)).await;
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::TimedOut);
+        drop(server);
+
+        // The connection is closed.
+        let n = io::timeout(Duration::from_secs(5), client.read(&mut buf)).await?;
+        assert_eq!(n, 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn read_after_cancelled_read() -> io::Result<()> {
+    runtime().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let mut client = TcpStream::connect(listener.local_addr()?).await?;
+        let (mut server, _) = listener.accept().await?;
+
+        // A read that timed out leaves its operation in flight; its data isn't lost.
+        let mut buf = [0; 16];
+        let res = io::timeout(Duration::from_millis(50), server.read(&mut buf)).await;
+        assert!(res.is_err());
+
+        client.write_all(b"hello").await?;
+        let mut buf = [0; 5];
+        server.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"hello");
+        Ok(())
+    })
+}
+
+#[test]
+fn accept_many() -> io::Result<()> {
+    runtime().block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        let clients: Vec<_> = (0..20)
+            .map(|i| {
+                task::spawn(async move {
+                    let mut stream = TcpStream::connect(addr).await?;
+                    stream.write_all(&[i]).await?;
+                    stream.flush().await?;
+                    io::Result::Ok(stream)
+                })
+            })
+            .collect();
+
+        let mut sum = 0;
+        for _ in 0..20 {
+            let (mut stream, peer) = listener.accept().await?;
+            assert!(peer.ip().is_loopback());
+            let mut byte = [0];
+            stream.read_exact(&mut byte).await?;
+            sum += byte[0] as u32;
+        }
+        assert_eq!(sum, 190);
+
+        for client in clients {
+            client.await?;
+        }
+        Ok(())
+    })
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "unstable")]
+
+use std::pin::Pin;
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::task;
+
+#[test]
+fn join_many() {
+    task::block_on(async {
+        let slow = async {
+            task::sleep(Duration::from_millis(20)).await;
+            "slow"
+        };
+        let out = future::join!(future::ready(1), slow, async { 2.5 }, future::ready('c'));
+        assert_eq!(out, (1, "slow", 2.5, 'c'));
+        assert_eq!(future::join!(), ());
+    });
+}
+
+#[test]
+fn try_join_short_circuits() {
+    task::block_on(async {
+        let out = future::try_join!(future::ready(Ok::<_, &str>(1)), async { Ok("two") });
+        assert_eq!(out, Ok((1, "two")));
+
+        // The error is returned without waiting for the other futures.
+        let out = future::try_join!(future::pending::<Result<(), &str>>(), async {
+            task::sleep(Duration::from_millis(10)).await;
+            Err::<(), _>("failed")
+        });
+        assert_eq!(out, Err("failed"));
+    });
+}
+
+#[test]
+fn race_ok_first_success() {
+    task::block_on(async {
+        async fn attempt(n: u64) -> Result<u64, u64> {
+            task::sleep(Duration::from_millis(n * 10)).await;
+            if matches!(n, 2 | 4) { Ok(n) } else { Err(n) }
+        }
+
+        assert_eq!(future::race_ok((1..=5).map(attempt)).await, Ok(2));
+        assert_eq!(
+            future::race_ok(vec![5, 1, 3].into_iter().map(attempt)).await,
+            Err(vec![5, 1, 3])
+        );
+        assert_eq!(future::race_ok((0..0).map(attempt)).await, Err(vec![]));
+    });
+}
+
+#[test]
+fn poll_once() {
+    task::block_on(async {
+        let mut sleep = Box::pin(task::sleep(Duration::from_millis(10)));
+        assert_eq!(future::poll_once(&mut sleep).await, None);
+        assert_eq!(future::poll_once(future::ready(1)).await, Some(1));
+        assert_eq!(future::poll_once(future::pending::<()>()).await, None);
+
+        // The future can still be awaited afterwards.
+        sleep.await;
+    });
+}
+
+#[test]
+fn concurrent_queue() {
+    task::block_on(async {
+        fn sleep(i: u64) -> Pin<Box<dyn Future<Output = u64>>> {
+            Box::pin(async move {
+                task::sleep(Duration::from_millis(i * 20)).await;
+                i
+            })
+        }
+
+        let mut queue: future::ConcurrentQueue<_> = (1..=3).rev().map(sleep).collect();
+        assert_eq!(queue.len(), 3);
+
+        // Outputs are yielded in completion order, and futures pushed while the queue is being
+        // consumed are polled too.
+        assert_eq!(queue.next().await, Some(1));
+        queue.push(sleep(0));
+        let rest: Vec<_> = queue.by_ref().collect().await;
+        assert_eq!(rest, [0, 2, 3]);
+        assert!(queue.is_empty());
+        assert_eq!(queue.next().await, None);
+    });
+}
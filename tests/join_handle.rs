@@ -0,0 +1,192 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_std::sync::Arc;
+use async_std::task;
+
+struct SetOnDrop(Arc<AtomicBool>);
+
+impl Drop for SetOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn cancel_pending_task() {
+    task::block_on(async {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+
+        let handle = task::spawn(async move {
+            let _guard = guard;
+            task::sleep(Duration::from_secs(10)).await;
+            1
+        });
+        task::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(handle.cancel().await, None);
+        assert!(dropped.load(Ordering::SeqCst));
+    });
+}
+
+#[test]
+fn cancel_completed_task() {
+    task::block_on(async {
+        let handle = task::spawn(async { 1 });
+        task::sleep(Duration::from_millis(10)).await;
+        assert_eq!(handle.cancel().await, Some(1));
+    });
+}
+
+#[test]
+fn abort_handle_stops_detached_task() {
+    task::block_on(async {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+
+        let handle = task::spawn(async move {
+            let _guard = guard;
+            task::sleep(Duration::from_secs(10)).await;
+        });
+        let abort = handle.abort_handle();
+        drop(handle);
+
+        task::sleep(Duration::from_millis(10)).await;
+        assert!(!dropped.load(Ordering::SeqCst));
+
+        abort.clone().abort();
+        task::sleep(Duration::from_millis(10)).await;
+        assert!(dropped.load(Ordering::SeqCst));
+    });
+}
+
+#[test]
+fn panic_is_caught_and_joined() {
+    task::block_on(async {
+        let handle = task::spawn(async {
+            panic!("boom");
+        });
+
+        let err = handle.join().await.unwrap_err();
+        assert!(err.is_panic());
+        assert_eq!(*err.into_panic().downcast::<&str>().unwrap(), "boom");
+
+        // Other tasks keep running.
+        assert_eq!(task::spawn(async { 1 }).await, 1);
+    });
+}
+
+#[test]
+#[should_panic(expected = "boom")]
+fn awaiting_panicked_task_resumes_panic() {
+    task::block_on(async {
+        task::spawn(async {
+            panic!("boom");
+        })
+        .await
+    });
+}
+
+#[test]
+fn join_aborted_task() {
+    task::block_on(async {
+        let handle = task::spawn(async {
+            task::sleep(Duration::from_secs(10)).await;
+        });
+        handle.abort_handle().abort();
+        assert!(handle.join().await.unwrap_err().is_cancelled());
+
+        let blocking = task::spawn_blocking(|| panic!("blocking boom"));
+        assert!(blocking.join().await.unwrap_err().is_panic());
+    });
+}
+
+/// Waits until `flag` is set.
+async fn wait_for(flag: &AtomicBool) {
+    for _ in 0..1000 {
+        if flag.load(Ordering::SeqCst) {
+            return;
+        }
+        task::sleep(Duration::from_millis(1)).await;
+    }
+    panic!("flag was never set");
+}
+
+#[test]
+fn cleanup_runs_before_output() {
+    task::block_on(async {
+        let cleaned = Arc::new(AtomicBool::new(false));
+        let c = cleaned.clone();
+
+        let handle = task::spawn_with_cleanup(async { 1 }, async move {
+            task::sleep(Duration::from_millis(10)).await;
+            c.store(true, Ordering::SeqCst);
+        });
+
+        assert_eq!(handle.await, 1);
+        assert!(cleaned.load(Ordering::SeqCst));
+    });
+}
+
+#[test]
+fn cleanup_runs_on_cancel() {
+    task::block_on(async {
+        let cleaned = Arc::new(AtomicBool::new(false));
+        let c = cleaned.clone();
+
+        let handle = task::spawn_with_cleanup(
+            async {
+                task::sleep(Duration::from_secs(10)).await;
+                1
+            },
+            async move {
+                task::yield_now().await;
+                c.store(true, Ordering::SeqCst);
+            },
+        );
+        task::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(handle.cancel().await, None);
+        wait_for(&cleaned).await;
+    });
+}
+
+#[test]
+fn cleanup_runs_on_panic() {
+    task::block_on(async {
+        let cleaned = Arc::new(AtomicBool::new(false));
+        let c = cleaned.clone();
+
+        let handle = task::spawn_with_cleanup(async { panic!("boom") }, async move {
+            c.store(true, Ordering::SeqCst);
+        });
+
+        let res: Result<(), _> = handle.join().await;
+        assert!(res.unwrap_err().is_panic());
+        wait_for(&cleaned).await;
+    });
+}
+
+#[test]
+fn interrupted_cleanup_keeps_running() {
+    task::block_on(async {
+        let started = Arc::new(AtomicBool::new(false));
+        let cleaned = Arc::new(AtomicBool::new(false));
+        let (s, c) = (started.clone(), cleaned.clone());
+
+        let handle = task::spawn_with_cleanup(async {}, async move {
+            s.store(true, Ordering::SeqCst);
+            task::sleep(Duration::from_millis(50)).await;
+            c.store(true, Ordering::SeqCst);
+        });
+        wait_for(&started).await;
+
+        // Aborting the task while its cleanup runs doesn't stop the cleanup.
+        assert_eq!(handle.cancel().await, None);
+        assert!(!cleaned.load(Ordering::SeqCst));
+        wait_for(&cleaned).await;
+    });
+}
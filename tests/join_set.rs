@@ -0,0 +1,46 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::task::{self, JoinSet};
+
+#[test]
+fn results_in_completion_order() {
+    task::block_on(async {
+        let mut set = JoinSet::new();
+        for i in (0..3).rev() {
+            set.spawn(async move {
+                task::sleep(Duration::from_millis(i * 20)).await;
+                i
+            });
+        }
+        assert_eq!(set.len(), 3);
+
+        let mut order = vec![];
+        while let Some(res) = set.join_next().await {
+            order.push(res.unwrap());
+        }
+        assert_eq!(order, [0, 1, 2]);
+        assert!(set.join_next().await.is_none());
+    });
+}
+
+#[test]
+fn abort_all_and_panics() {
+    task::block_on(async {
+        let mut set = JoinSet::new();
+        set.spawn(async {
+            task::sleep(Duration::from_secs(10)).await;
+        });
+        set.spawn(async { panic!("boom") });
+        task::sleep(Duration::from_millis(10)).await;
+
+        let res = set.join_next().await.unwrap();
+        assert!(res.unwrap_err().is_panic());
+
+        set.abort_all();
+        let res = set.join_next().await.unwrap();
+        assert!(res.unwrap_err().is_cancelled());
+        assert!(set.is_empty());
+    });
+}
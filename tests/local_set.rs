@@ -0,0 +1,70 @@
+#![cfg(feature = "unstable")]
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_std::task::{self, LocalSet};
+
+#[test]
+fn spawn_local_runs_non_send_tasks() {
+    let local = LocalSet::new();
+    let counter = Rc::new(Cell::new(0));
+
+    local.block_on(async {
+        let mut handles = vec![];
+        for _ in 0..10 {
+            let counter = counter.clone();
+            handles.push(task::spawn_local(async move {
+                task::sleep(Duration::from_millis(1)).await;
+                counter.set(counter.get() + 1);
+            }));
+        }
+        for h in handles {
+            h.await;
+        }
+    });
+
+    assert_eq!(counter.get(), 10);
+}
+
+#[test]
+fn run_until_inside_another_executor() {
+    task::block_on(async {
+        let local = LocalSet::new();
+        let log = Rc::new(RefCell::new(vec![]));
+
+        let log2 = log.clone();
+        let handle = local.spawn_local(async move {
+            log2.borrow_mut().push("task");
+            7
+        });
+
+        let n = local.run_until(handle).await;
+        assert_eq!(n, 7);
+        assert_eq!(*log.borrow(), ["task"]);
+    });
+}
+
+#[test]
+fn tasks_pause_while_set_is_idle() {
+    let local = LocalSet::new();
+    let done = Rc::new(Cell::new(false));
+
+    let done2 = done.clone();
+    let handle = local.spawn_local(async move {
+        task::sleep(Duration::from_millis(20)).await;
+        done2.set(true);
+    });
+
+    local.block_on(task::sleep(Duration::from_millis(1)));
+    assert!(!done.get());
+    local.block_on(handle);
+    assert!(done.get());
+}
+
+#[test]
+#[should_panic(expected = "outside of a `LocalSet`")]
+fn spawn_local_outside_set_panics() {
+    task::spawn_local(async {});
+}
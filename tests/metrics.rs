@@ -0,0 +1,64 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::runtime;
+use async_std::task;
+
+#[test]
+fn counts_tasks_and_polls() {
+    task::block_on(async {
+        let metrics = runtime::metrics();
+        let polls = metrics.poll_count();
+
+        let handle = task::spawn(task::sleep(Duration::from_secs(10)));
+        task::sleep(Duration::from_millis(10)).await;
+        assert!(metrics.num_alive_tasks() >= 1);
+        assert!(metrics.num_workers() >= 1);
+        assert!(metrics.poll_count() > polls);
+
+        handle.cancel().await;
+    });
+}
+
+#[test]
+fn poll_time_histogram() {
+    task::block_on(async {
+        task::spawn(async {}).await;
+
+        let histogram = runtime::metrics().poll_time_histogram();
+        assert_eq!(histogram.last().unwrap().0, Duration::MAX);
+        assert!(histogram.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(histogram.iter().map(|(_, n)| n).sum::<u64>() > 0);
+    });
+}
+
+#[test]
+fn blocking_pool() {
+    task::block_on(async {
+        task::spawn_blocking(|| {
+            assert!(runtime::metrics().num_blocking_threads() >= 1);
+        })
+        .await;
+    });
+}
+
+#[test]
+fn timers_coalesce_reactor_wakeups() {
+    task::block_on(async {
+        let metrics = runtime::metrics();
+        task::sleep(Duration::from_millis(1)).await;
+        let wakeups = metrics.reactor_wakeups();
+
+        // Timers sharing a few deadlines wake the reactor once per deadline, not once per timer.
+        let handles: Vec<_> = (0..200)
+            .map(|i| task::spawn(task::sleep(Duration::from_millis(100 + i % 4))))
+            .collect();
+        for handle in handles {
+            handle.await;
+        }
+
+        let wakeups = metrics.reactor_wakeups() - wakeups;
+        assert!(wakeups < 50, "{} reactor wakeups", wakeups);
+    });
+}
@@ -62,3 +62,60 @@ fn contention() {
         assert_eq!(num_tasks, *lock);
     });
 }
+
+#[cfg(feature = "unstable")]
+#[test]
+fn owned_guard_outlives_borrow() {
+    task::block_on(async {
+        let m = Arc::new(Mutex::new(0));
+        let mut guard = m.clone().lock_owned().await;
+        assert!(m.clone().try_lock_owned().is_none());
+
+        task::spawn(async move {
+            *guard += 1;
+        })
+        .await;
+
+        assert_eq!(*m.lock().await, 1);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn mapped_guard_keeps_lock() {
+    use async_std::sync::{MappedMutexGuard, MutexGuard};
+
+    task::block_on(async {
+        let m = Mutex::new((0, vec![1, 2, 3]));
+
+        let v = MutexGuard::map(m.lock().await, |pair| &mut pair.1);
+        let mut last = MappedMutexGuard::map(v, |v| v.last_mut().unwrap());
+        *last = 4;
+        assert!(m.try_lock().is_none());
+
+        drop(last);
+        assert_eq!(m.lock().await.1, [1, 2, 4]);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn try_lock_for() {
+    use std::time::Duration;
+
+    task::block_on(async {
+        let m = Arc::new(Mutex::new(0));
+        let guard = m.lock().await;
+        assert!(m.try_lock_for(Duration::from_millis(10)).await.is_none());
+
+        let m2 = m.clone();
+        let handle = task::spawn(async move {
+            *m2.try_lock_for(Duration::from_secs(10)).await.unwrap() += 1;
+        });
+        task::sleep(Duration::from_millis(10)).await;
+        drop(guard);
+
+        handle.await;
+        assert_eq!(*m.lock().await, 1);
+    });
+}
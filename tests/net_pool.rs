@@ -0,0 +1,167 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_std::future;
+use async_std::io;
+use async_std::net::{Pool, PoolBuilder, Pooled};
+use async_std::task;
+
+/// Builds a pool of numbered connections, counting how many have been opened.
+fn numbered() -> (PoolBuilder<usize>, Arc<AtomicUsize>) {
+    let opened = Arc::new(AtomicUsize::new(0));
+    let counter = opened.clone();
+    let builder = Pool::builder(move || {
+        let id = counter.fetch_add(1, Ordering::SeqCst);
+        async move { Ok(id) }
+    });
+    (builder, opened)
+}
+
+#[test]
+fn reuse() -> io::Result<()> {
+    task::block_on(async {
+        let (builder, opened) = numbered();
+        let pool = builder.build();
+
+        let conn = pool.get().await?;
+        assert_eq!(*conn, 0);
+        drop(conn);
+        assert_eq!(pool.idle(), 1);
+
+        let a = pool.get().await?;
+        let b = pool.get().await?;
+        assert_eq!((*a, *b), (0, 1));
+        assert_eq!(opened.load(Ordering::SeqCst), 2);
+        assert_eq!(pool.size(), 2);
+        Ok(())
+    })
+}
+
+#[test]
+fn fair_waiters() -> io::Result<()> {
+    task::block_on(async {
+        let (builder, opened) = numbered();
+        let pool = builder.max_size(1).build();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let conn = pool.get().await?;
+        let mut handles = Vec::new();
+        for i in 0..4 {
+            let pool = pool.clone();
+            let order = order.clone();
+            handles.push(task::spawn(async move {
+                let conn = pool.get().await?;
+                order.lock().unwrap().push(i);
+                task::sleep(Duration::from_millis(5)).await;
+                drop(conn);
+                io::Result::Ok(())
+            }));
+            // Let the task start waiting before spawning the next one.
+            task::sleep(Duration::from_millis(20)).await;
+        }
+
+        drop(conn);
+        for handle in handles {
+            handle.await?;
+        }
+        assert_eq!(*order.lock().unwrap(), [0, 1, 2, 3]);
+        assert_eq!(opened.load(Ordering::SeqCst), 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn cancelled_waiter() -> io::Result<()> {
+    task::block_on(async {
+        let (builder, _) = numbered();
+        let pool = builder.max_size(1).build();
+
+        let conn = pool.get().await?;
+        let res = future::timeout(Duration::from_millis(20), pool.get()).await;
+        assert!(res.is_err());
+
+        // The connection skips the waiter that gave up.
+        let waiter = task::spawn({
+            let pool = pool.clone();
+            async move { pool.get().await.map(|conn| *conn) }
+        });
+        task::sleep(Duration::from_millis(20)).await;
+        drop(conn);
+        assert_eq!(waiter.await?, 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn idle_timeout() -> io::Result<()> {
+    task::block_on(async {
+        let (builder, _) = numbered();
+        let pool = builder.idle_timeout(Duration::from_millis(20)).build();
+
+        drop(pool.get().await?);
+        task::sleep(Duration::from_millis(50)).await;
+        assert_eq!(*pool.get().await?, 1);
+        assert_eq!(pool.size(), 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn health_check() -> io::Result<()> {
+    task::block_on(async {
+        let (builder, _) = numbered();
+        let pool = builder
+            .health_check(|id| async move { if id == 0 { None } else { Some(id) } })
+            .build();
+
+        drop(pool.get().await?);
+        let conn = pool.get().await?;
+        assert_eq!(*conn, 1);
+        drop(conn);
+        assert_eq!(*pool.get().await?, 1);
+        assert_eq!(pool.size(), 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn connect_error() {
+    task::block_on(async {
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let pool = Pool::builder(move || {
+            let attempt = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt == 0 {
+                    Err(io::Error::from(io::ErrorKind::ConnectionRefused))
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .max_size(1)
+        .build();
+
+        let err = pool.get().await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+        assert_eq!(pool.size(), 0);
+        assert_eq!(*pool.get().await.unwrap(), 1);
+    })
+}
+
+#[test]
+fn detach() -> io::Result<()> {
+    task::block_on(async {
+        let (builder, _) = numbered();
+        let pool = builder.max_size(1).build();
+
+        let conn = Pooled::detach(pool.get().await?);
+        assert_eq!(conn, 0);
+        assert_eq!(pool.size(), 0);
+        assert_eq!(*pool.get().await?, 1);
+        Ok(())
+    })
+}
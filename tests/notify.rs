@@ -0,0 +1,60 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_std::future;
+use async_std::sync::{Arc, Notify};
+use async_std::task;
+
+#[test]
+fn notify_one_stores_a_single_permit() {
+    task::block_on(async {
+        let notify = Notify::new();
+        notify.notify_one();
+        notify.notify_one();
+
+        notify.notified().await;
+        assert!(future::timeout(Duration::from_millis(10), notify.notified())
+            .await
+            .is_err());
+    });
+}
+
+#[test]
+fn notify_one_wakes_a_single_task() {
+    task::block_on(async {
+        let notify = Arc::new(Notify::new());
+        let woken = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..3 {
+            let notify = notify.clone();
+            let woken = woken.clone();
+            task::spawn(async move {
+                notify.notified().await;
+                woken.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        task::sleep(Duration::from_millis(50)).await;
+        notify.notify_one();
+        task::sleep(Duration::from_millis(50)).await;
+        assert_eq!(woken.load(Ordering::SeqCst), 1);
+
+        notify.notify_waiters();
+        task::sleep(Duration::from_millis(50)).await;
+        assert_eq!(woken.load(Ordering::SeqCst), 3);
+    });
+}
+
+#[test]
+fn notify_waiters_does_not_store_a_permit() {
+    task::block_on(async {
+        let notify = Notify::new();
+        notify.notify_waiters();
+
+        assert!(future::timeout(Duration::from_millis(10), notify.notified())
+            .await
+            .is_err());
+    });
+}
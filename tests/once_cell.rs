@@ -0,0 +1,89 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_std::future;
+use async_std::sync::{Arc, Lazy, OnceCell};
+use async_std::task;
+
+#[test]
+fn concurrent_initializers_run_once() {
+    task::block_on(async {
+        let cell = Arc::new(OnceCell::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut tasks = vec![];
+
+        for i in 0..10 {
+            let cell = cell.clone();
+            let calls = calls.clone();
+            tasks.push(task::spawn(async move {
+                *cell
+                    .get_or_init(|| async move {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        task::sleep(Duration::from_millis(10)).await;
+                        i
+                    })
+                    .await
+            }));
+        }
+
+        let mut values = vec![];
+        for t in tasks {
+            values.push(t.await);
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(values.iter().all(|v| *v == values[0]));
+    });
+}
+
+#[test]
+fn cancelled_initializer_lets_others_retry() {
+    task::block_on(async {
+        let cell = OnceCell::new();
+
+        let res = future::timeout(
+            Duration::from_millis(10),
+            cell.get_or_init(future::pending::<i32>),
+        )
+        .await;
+        assert!(res.is_err());
+        assert!(cell.get().is_none());
+
+        assert_eq!(*cell.get_or_init(|| async { 2 }).await, 2);
+    });
+}
+
+#[test]
+fn failed_initializer_leaves_cell_empty() {
+    task::block_on(async {
+        let cell = OnceCell::new();
+        assert_eq!(cell.get_or_try_init(|| async { Err("oops") }).await, Err("oops"));
+        assert_eq!(cell.set(3), Ok(()));
+        assert_eq!(cell.into_inner(), Some(3));
+    });
+}
+
+static COUNTER: AtomicUsize = AtomicUsize::new(0);
+static LAZY: Lazy<usize> = Lazy::new(|| {
+    Box::pin(async {
+        task::sleep(Duration::from_millis(10)).await;
+        COUNTER.fetch_add(1, Ordering::SeqCst) + 10
+    })
+});
+
+#[test]
+fn lazy_static_initializes_once() {
+    task::block_on(async {
+        assert_eq!(Lazy::get(&LAZY), None);
+
+        let tasks: Vec<_> = (0..5)
+            .map(|_| task::spawn(async { *Lazy::force(&LAZY).await }))
+            .collect();
+        for t in tasks {
+            assert_eq!(t.await, 10);
+        }
+        assert_eq!(COUNTER.load(Ordering::SeqCst), 1);
+    });
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::sync::oneshot;
+use async_std::task;
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let (s, r) = oneshot::channel();
+        s.send(7).unwrap();
+        assert_eq!(r.await, Ok(7));
+
+        let (s, r) = oneshot::channel::<i32>();
+        drop(s);
+        assert!(r.await.is_err());
+
+        let (s, r) = oneshot::channel();
+        drop(r);
+        assert_eq!(s.send(7), Err(7));
+    });
+}
+
+#[test]
+fn send_from_another_task() {
+    task::block_on(async {
+        let (s, r) = oneshot::channel();
+
+        task::spawn(async move {
+            task::sleep(Duration::from_millis(100)).await;
+            s.send("done").unwrap();
+        });
+
+        assert_eq!(r.await, Ok("done"));
+    });
+}
+
+#[test]
+fn cancelled_receive_closes_channel() {
+    task::block_on(async {
+        let (mut s, r) = oneshot::channel::<i32>();
+
+        assert!(future::timeout(Duration::from_millis(10), r).await.is_err());
+        s.closed().await;
+        assert!(s.is_closed());
+    });
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::prelude::*;
+use async_std::sync::priority;
+use async_std::task;
+
+#[test]
+fn greatest_first() {
+    task::block_on(async {
+        let (s, r) = priority::channel(8);
+
+        s.send((1, 'a')).await.unwrap();
+        s.send((2, 'b')).await.unwrap();
+        s.send((1, 'c')).await.unwrap();
+        s.send((2, 'd')).await.unwrap();
+        drop(s);
+
+        let v: Vec<_> = r.collect().await;
+        assert_eq!(v, [(2, 'd'), (2, 'b'), (1, 'c'), (1, 'a')]);
+    });
+}
+
+#[test]
+fn equal_messages_keep_send_order() {
+    #[derive(Debug, PartialEq, Eq)]
+    struct Job(u8, &'static str);
+
+    impl PartialOrd for Job {
+        fn partial_cmp(&self, other: &Job) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Job {
+        fn cmp(&self, other: &Job) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+
+    task::block_on(async {
+        let (s, r) = priority::channel(8);
+        for name in &["a", "b", "c"] {
+            s.send(Job(0, name)).await.unwrap();
+        }
+
+        assert_eq!(r.recv().await, Some(Job(0, "a")));
+        assert_eq!(r.recv().await, Some(Job(0, "b")));
+        assert_eq!(r.recv().await, Some(Job(0, "c")));
+    });
+}
+
+#[test]
+fn full_channel_waits_for_receiver() {
+    task::block_on(async {
+        let (s, r) = priority::channel(1);
+        s.send(1).await.unwrap();
+
+        let handle = task::spawn(async move {
+            s.send(2).await.unwrap();
+            s.send(3).await.unwrap();
+        });
+
+        task::sleep(Duration::from_millis(10)).await;
+        assert!(r.is_full());
+        assert_eq!(r.recv().await, Some(1));
+        assert_eq!(r.recv().await, Some(2));
+        assert_eq!(r.recv().await, Some(3));
+        handle.await;
+        assert_eq!(r.recv().await, None);
+    });
+}
+
+#[test]
+fn send_fails_without_receivers() {
+    task::block_on(async {
+        let (s, r) = priority::channel(1);
+        s.send(1).await.unwrap();
+
+        let handle = task::spawn(async move { s.send(2).await });
+        task::sleep(Duration::from_millis(10)).await;
+        drop(r);
+
+        assert_eq!(handle.await.unwrap_err().0, 2);
+    });
+}
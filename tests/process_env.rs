@@ -0,0 +1,52 @@
+#![cfg(all(unix, feature = "unstable"))]
+
+use std::collections::HashMap;
+use std::process::Command;
+
+use async_std::process::CommandExt;
+
+fn env_of(command: &mut Command) -> Vec<String> {
+    let output = command.output().unwrap();
+    let mut vars: Vec<_> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(String::from)
+        .collect();
+    vars.sort();
+    vars
+}
+
+#[test]
+fn env_clear_except() {
+    std::env::set_var("ASYNC_STD_INHERITED", "1");
+    std::env::set_var("ASYNC_STD_DROPPED", "1");
+
+    let mut command = Command::new("/usr/bin/env");
+    command
+        .env("ASYNC_STD_EXPLICIT", "2")
+        .env("ASYNC_STD_EXPLICIT_DROPPED", "2")
+        .env_clear_except([
+            "ASYNC_STD_INHERITED",
+            "ASYNC_STD_EXPLICIT",
+            "ASYNC_STD_UNSET",
+        ]);
+    assert_eq!(
+        env_of(&mut command),
+        ["ASYNC_STD_EXPLICIT=2", "ASYNC_STD_INHERITED=1"]
+    );
+}
+
+#[test]
+fn env_diff() {
+    let mut diff = HashMap::new();
+    diff.insert("ASYNC_STD_A", Some("a"));
+    diff.insert("ASYNC_STD_B", None);
+
+    let mut command = Command::new("/usr/bin/env");
+    command
+        .env_clear()
+        .env("ASYNC_STD_B", "b")
+        .env("ASYNC_STD_C", "c")
+        .env_diff(&diff);
+    assert_eq!(env_of(&mut command), ["ASYNC_STD_A=a", "ASYNC_STD_C=c"]);
+}
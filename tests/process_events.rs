@@ -0,0 +1,62 @@
+#![cfg(all(unix, feature = "unstable"))]
+
+use std::process::{Command, Stdio};
+
+use async_std::prelude::*;
+use async_std::process::{ChildEvent, ChildExt};
+use async_std::task;
+
+fn spawn(script: &str) -> std::process::Child {
+    Command::new("sh")
+        .arg("-c")
+        .arg(script)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap()
+}
+
+#[test]
+fn merged_output() {
+    task::block_on(async {
+        let script = "echo out; sleep 0.1; echo err >&2; sleep 0.1; echo out2; exit 4";
+        let events: Vec<_> = spawn(script).events().collect().await;
+
+        let mut last = None;
+        let mut kinds = Vec::new();
+        for (at, event) in events {
+            if let Some(last) = last {
+                assert!(at >= last);
+            }
+            last = Some(at);
+            kinds.push(match event {
+                ChildEvent::Stdout(out) => format!("out:{}", String::from_utf8(out).unwrap()),
+                ChildEvent::Stderr(err) => format!("err:{}", String::from_utf8(err).unwrap()),
+                ChildEvent::Exited(status) => format!("exit:{:?}", status.code()),
+                ChildEvent::Error(err) => panic!("{}", err),
+            });
+        }
+        assert_eq!(
+            kinds,
+            ["out:out\n", "err:err\n", "out:out2\n", "exit:Some(4)"]
+        );
+    })
+}
+
+#[test]
+fn kill() {
+    task::block_on(async {
+        let mut events = spawn("echo started; exec sleep 60").events();
+        match events.next().await {
+            Some((_, ChildEvent::Stdout(out))) => assert_eq!(out, b"started\n"),
+            event => panic!("unexpected event: {:?}", event),
+        }
+
+        events.kill().unwrap();
+        match events.next().await {
+            Some((_, ChildEvent::Exited(status))) => assert!(!status.success()),
+            event => panic!("unexpected event: {:?}", event),
+        }
+        assert!(events.next().await.is_none());
+    })
+}
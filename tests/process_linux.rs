@@ -0,0 +1,75 @@
+#![cfg(all(any(target_os = "linux", target_os = "android"), feature = "unstable"))]
+
+use std::process::Command;
+
+use async_std::process::CommandExt;
+
+fn status_field(command: &mut Command, field: usize) -> String {
+    // Reads a field of `/proc/self/stat` of the child.
+    let output = command
+        .arg("-c")
+        .arg("cat /proc/self/stat")
+        .output()
+        .unwrap();
+    let stat = String::from_utf8(output.stdout).unwrap();
+    let rest = &stat[stat.rfind(')').unwrap() + 2..];
+    rest.split(' ').nth(field - 3).unwrap().to_string()
+}
+
+#[test]
+fn nice() {
+    let mut command = Command::new("sh");
+    command.nice(15);
+    assert_eq!(status_field(&mut command, 19), "15");
+}
+
+#[test]
+fn cpu_affinity() {
+    let output = Command::new("sh")
+        .arg("-c")
+        .arg("grep Cpus_allowed_list /proc/self/status")
+        .cpu_affinity(&[0])
+        .output()
+        .unwrap();
+    let status = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(status.split_whitespace().nth(1), Some("0"));
+
+    let err = Command::new("true")
+        .cpu_affinity(&[1 << 20])
+        .spawn()
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EINVAL));
+}
+
+#[test]
+fn cgroup() {
+    let err = Command::new("true")
+        .cgroup("/nonexistent/cgroup")
+        .spawn()
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+
+    // Creating a cgroup needs privileges the test may not have.
+    let root = ["/sys/fs/cgroup/unified", "/sys/fs/cgroup"]
+        .iter()
+        .map(std::path::Path::new)
+        .find(|root| root.join("cgroup.procs").exists());
+    let dir = match root {
+        Some(root) => root.join(format!("async-std-test-{}", std::process::id())),
+        None => return,
+    };
+    if std::fs::create_dir(&dir).is_err() {
+        return;
+    }
+
+    let output = Command::new("cat")
+        .arg("/proc/self/cgroup")
+        .cgroup(&dir)
+        .output();
+    std::fs::remove_dir(&dir).unwrap();
+
+    let cgroups = String::from_utf8(output.unwrap().stdout).unwrap();
+    let name = dir.file_name().unwrap().to_str().unwrap();
+    let unified = cgroups.lines().find(|line| line.starts_with("0::"));
+    assert!(unified.unwrap().ends_with(name));
+}
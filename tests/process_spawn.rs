@@ -0,0 +1,130 @@
+#![cfg(all(unix, feature = "unstable"))]
+
+use async_std::prelude::*;
+use async_std::process::{ProcessBuilder, Redirect};
+use async_std::task;
+
+#[test]
+fn output_and_status() {
+    task::block_on(async {
+        let mut child = ProcessBuilder::new("sh")
+            .arg("-c")
+            .arg("echo $GREETING; echo err >&2; exit 3")
+            .env("GREETING", "hello")
+            .stdout(Redirect::Piped)
+            .stderr(Redirect::Null)
+            .spawn()
+            .unwrap();
+
+        let mut out = String::new();
+        let mut stdout = child.stdout.take().unwrap();
+        stdout.read_to_string(&mut out).await.unwrap();
+        assert_eq!(out, "hello\n");
+        assert!(child.stderr.is_none());
+
+        let status = child.status().await.unwrap();
+        assert_eq!(status.code(), Some(3));
+        assert_eq!(child.try_status().unwrap(), Some(status));
+    })
+}
+
+#[test]
+fn env_clear() {
+    task::block_on(async {
+        std::env::set_var("ASYNC_STD_INHERITED", "1");
+        let mut child = ProcessBuilder::new("sh")
+            .arg("-c")
+            .arg("echo \"$ASYNC_STD_INHERITED-$ASYNC_STD_SET\"")
+            .env("ASYNC_STD_SET", "lost")
+            .env_clear()
+            .env("ASYNC_STD_SET", "2")
+            .stdout(Redirect::Piped)
+            .spawn()
+            .unwrap();
+
+        let mut out = String::new();
+        let mut stdout = child.stdout.take().unwrap();
+        stdout.read_to_string(&mut out).await.unwrap();
+        assert_eq!(out, "-2\n");
+        assert!(child.status().await.unwrap().success());
+    })
+}
+
+#[test]
+fn stdin() {
+    task::block_on(async {
+        let mut child = ProcessBuilder::new("cat")
+            .stdin(Redirect::Piped)
+            .stdout(Redirect::Piped)
+            .spawn()
+            .unwrap();
+
+        let mut stdin = child.stdin.take().unwrap();
+        stdin.write_all(b"echo").await.unwrap();
+        drop(stdin);
+
+        let mut out = String::new();
+        let mut stdout = child.stdout.take().unwrap();
+        stdout.read_to_string(&mut out).await.unwrap();
+        assert_eq!(out, "echo");
+        assert!(child.status().await.unwrap().success());
+    })
+}
+
+#[test]
+fn not_found() {
+    let err = ProcessBuilder::new("async-std-does-not-exist")
+        .spawn()
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn kill() {
+    task::block_on(async {
+        let child = ProcessBuilder::new("sleep").arg("60").spawn().unwrap();
+        assert_eq!(child.try_status().unwrap(), None);
+
+        child.kill().unwrap();
+        assert!(!child.status().await.unwrap().success());
+        assert!(child.kill().is_err());
+    })
+}
+
+#[test]
+fn current_dir() {
+    task::block_on(async {
+        let mut child = ProcessBuilder::new("pwd")
+            .current_dir("/")
+            .stdout(Redirect::Piped)
+            .spawn()
+            .unwrap();
+
+        let mut out = String::new();
+        let mut stdout = child.stdout.take().unwrap();
+        stdout.read_to_string(&mut out).await.unwrap();
+        assert_eq!(out, "/\n");
+        assert!(child.status().await.unwrap().success());
+    })
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[test]
+fn nice() {
+    task::block_on(async {
+        let mut child = ProcessBuilder::new("sh")
+            .arg("-c")
+            .arg("cat /proc/self/stat")
+            .nice(15)
+            .stdout(Redirect::Piped)
+            .spawn()
+            .unwrap();
+
+        let mut stat = String::new();
+        let mut stdout = child.stdout.take().unwrap();
+        stdout.read_to_string(&mut stat).await.unwrap();
+        let rest = &stat[stat.rfind(')').unwrap() + 2..];
+        assert_eq!(rest.split(' ').nth(19 - 3), Some("15"));
+        assert!(child.status().await.unwrap().success());
+    })
+}
@@ -0,0 +1,47 @@
+#![cfg(all(unix, feature = "unstable"))]
+
+use std::process::{Command, Stdio};
+
+use async_std::io;
+use async_std::net::{TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::process::StdioExt;
+use async_std::task;
+
+#[test]
+fn piped_nonblocking() -> io::Result<()> {
+    task::block_on(async {
+        let (stdin, mut input) = Stdio::piped_nonblocking()?;
+        let (stdout, mut output) = Stdio::piped_nonblocking()?;
+        let mut child = Command::new("cat").stdin(stdin).stdout(stdout).spawn()?;
+
+        input.write_all(b"hello").await?;
+        drop(input);
+
+        let mut buf = String::new();
+        output.read_to_string(&mut buf).await?;
+        assert_eq!(buf, "hello");
+        assert!(child.wait()?.success());
+        Ok(())
+    })
+}
+
+#[test]
+fn from_async() -> io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let mut peer = TcpStream::connect(listener.local_addr()?).await?;
+        let (stream, _) = listener.accept().await?;
+
+        let mut child = Command::new("sh")
+            .args(["-c", "echo hi"])
+            .stdout(Stdio::from_async(stream)?)
+            .spawn()?;
+        assert!(child.wait()?.success());
+
+        let mut buf = String::new();
+        peer.read_to_string(&mut buf).await?;
+        assert_eq!(buf, "hi\n");
+        Ok(())
+    })
+}
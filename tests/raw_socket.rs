@@ -0,0 +1,114 @@
+#![cfg(all(feature = "raw-sockets", target_os = "linux"))]
+
+use std::net::IpAddr;
+
+use async_std::io;
+use async_std::net::raw::{self, PacketSocket, Protocol, RawSocket, SockFilter};
+use async_std::task;
+
+/// Opens a socket, or returns `None` if the process isn't allowed to open raw sockets.
+fn open<T>(socket: io::Result<T>) -> Option<T> {
+    match socket {
+        Err(err) if err.kind() == io::ErrorKind::PermissionDenied => None,
+        socket => Some(socket.unwrap()),
+    }
+}
+
+/// Builds an ICMP echo request with a valid checksum.
+fn echo_request(id: u16, seq: u16) -> Vec<u8> {
+    let mut packet = vec![8, 0, 0, 0];
+    packet.extend_from_slice(&id.to_be_bytes());
+    packet.extend_from_slice(&seq.to_be_bytes());
+    packet.extend_from_slice(b"async-std");
+    packet.push(0);
+
+    let sum: u32 = packet
+        .chunks(2)
+        .map(|c| u32::from(u16::from_be_bytes([c[0], c[1]])))
+        .sum();
+    let sum = (sum & 0xffff) + (sum >> 16);
+    let sum = !((sum & 0xffff) + (sum >> 16)) as u16;
+    packet[2..4].copy_from_slice(&sum.to_be_bytes());
+    packet
+}
+
+#[test]
+fn ping_with_filter() -> io::Result<()> {
+    task::block_on(async {
+        let socket = match open(RawSocket::v4(Protocol::ICMP)) {
+            Some(socket) => socket,
+            None => return Ok(()),
+        };
+
+        // Only keep echo replies, so that the request looped back to the socket is dropped.
+        socket.attach_filter(&[
+            SockFilter::new(0xb1, 0, 0, 0),
+            SockFilter::new(0x50, 0, 0, 0),
+            SockFilter::new(0x15, 0, 1, 0),
+            SockFilter::new(0x06, 0, 0, 0xffff),
+            SockFilter::new(0x06, 0, 0, 0),
+        ])?;
+
+        let localhost: IpAddr = "127.0.0.1".parse().unwrap();
+        socket.send_to(&echo_request(0x4153, 1), localhost).await?;
+
+        let mut buf = [0u8; 1500];
+        loop {
+            let (n, from) = socket.recv_from(&mut buf).await?;
+            assert_eq!(from, localhost);
+
+            let icmp = &buf[usize::from(buf[0] & 0xf) * 4..n];
+            assert_eq!(icmp[0], 0);
+            if icmp[4..8] == [0x41, 0x53, 0, 1] {
+                break;
+            }
+        }
+
+        socket.detach_filter()?;
+        Ok(())
+    })
+}
+
+#[test]
+fn mismatched_family() {
+    if let Some(socket) = open(RawSocket::v4(Protocol::ICMP)) {
+        let res = task::block_on(socket.send_to(&[0], "::1".parse().unwrap()));
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
+
+#[test]
+fn packet_loopback() -> io::Result<()> {
+    task::block_on(async {
+        // An EtherType reserved for experiments.
+        const PROTOCOL: u16 = 0x88b5;
+
+        let lo = raw::interface_index("lo")?;
+        let (sender, receiver) = match (
+            open(PacketSocket::new(PROTOCOL)),
+            open(PacketSocket::new(PROTOCOL)),
+        ) {
+            (Some(sender), Some(receiver)) => (sender, receiver),
+            _ => return Ok(()),
+        };
+        receiver.bind(lo)?;
+
+        let mut frame = vec![0u8; 12];
+        frame.extend_from_slice(&PROTOCOL.to_be_bytes());
+        frame.extend_from_slice(b"hello");
+        sender.send_to(&frame, lo).await?;
+
+        let mut buf = [0u8; 1500];
+        let (n, addr) = receiver.recv_from(&mut buf).await?;
+        assert_eq!(&buf[..n], &frame[..]);
+        assert_eq!(addr.interface(), lo);
+        assert_eq!(addr.protocol(), PROTOCOL);
+        Ok(())
+    })
+}
+
+#[test]
+fn unknown_interface() {
+    let err = raw::interface_index("does-not-exist0").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
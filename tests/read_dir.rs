@@ -0,0 +1,107 @@
+#![cfg(feature = "unstable")]
+
+use std::ffi::OsString;
+
+use async_std::fs::{self, ReadDirOptions};
+use async_std::io;
+use async_std::prelude::*;
+use async_std::task;
+use tempdir::TempDir;
+
+fn populate(dir: &TempDir, count: usize) -> io::Result<()> {
+    for i in 0..count {
+        std::fs::write(dir.path().join(format!("{:03}", i)), vec![0; i])?;
+    }
+    std::fs::create_dir(dir.path().join("dir"))
+}
+
+#[test]
+fn batched_metadata() -> io::Result<()> {
+    let dir = TempDir::new("read_dir")?;
+    populate(&dir, 50)?;
+
+    task::block_on(async {
+        let mut entries = ReadDirOptions::new()
+            .batch_size(16)
+            .metadata(true)
+            .file_type(true)
+            .read_dir(dir.path())
+            .await?;
+
+        let mut count = 0;
+        while let Some(res) = entries.next().await {
+            let entry = res?;
+            let metadata = entry.metadata().await?;
+            let name = entry.file_name().into_string().unwrap();
+            if name == "dir" {
+                assert!(metadata.is_dir());
+                assert!(entry.file_type().await?.is_dir());
+            } else {
+                assert_eq!(metadata.len(), name.parse::<u64>().unwrap());
+                assert!(entry.file_type().await?.is_file());
+            }
+            count += 1;
+        }
+        assert_eq!(count, 51);
+        Ok(())
+    })
+}
+
+#[test]
+fn prefetched_metadata() -> io::Result<()> {
+    let dir = TempDir::new("read_dir")?;
+    populate(&dir, 3)?;
+
+    task::block_on(async {
+        let mut entries = ReadDirOptions::new()
+            .batch_size(8)
+            .metadata(true)
+            .read_dir(dir.path())
+            .await?;
+
+        // The whole directory fits in one batch, so its metadata outlives the files.
+        let entry = entries.next().await.unwrap()?;
+        std::fs::remove_dir_all(dir.path())?;
+        assert!(entry.metadata().await.is_ok());
+        while let Some(res) = entries.next().await {
+            assert!(res?.metadata().await.is_ok());
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn sorted() -> io::Result<()> {
+    let dir = TempDir::new("read_dir")?;
+    populate(&dir, 40)?;
+
+    task::block_on(async {
+        let names: Vec<OsString> = ReadDirOptions::new()
+            .sorted(true)
+            .read_dir(dir.path())
+            .await?
+            .map(|res| res.map(|entry| entry.file_name()))
+            .collect::<io::Result<_>>()
+            .await?;
+
+        let mut expected = names.clone();
+        expected.sort();
+        assert_eq!(names, expected);
+        assert_eq!(names.len(), 41);
+        Ok(())
+    })
+}
+
+#[test]
+fn metadata_on_demand() -> io::Result<()> {
+    let dir = TempDir::new("read_dir")?;
+    populate(&dir, 3)?;
+
+    task::block_on(async {
+        let mut entries = fs::read_dir(dir.path()).await?;
+        let entry = entries.next().await.unwrap()?;
+        std::fs::remove_dir_all(dir.path())?;
+        assert!(entry.metadata().await.is_err());
+        Ok(())
+    })
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::future::{self, Backoff};
+use async_std::runtime::Builder;
+use async_std::time;
+
+#[test]
+fn exponential_delays() {
+    let rt = Builder::new().build().unwrap();
+    rt.block_on(async {
+        time::pause();
+
+        let backoff = Backoff::exponential(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(5))
+            .max_attempts(5);
+        let mut attempts = Vec::new();
+        let res: Result<(), usize> = future::retry(backoff, || {
+            attempts.push(time::now());
+            let n = attempts.len();
+            async move { Err(n) }
+        })
+        .await;
+
+        // The last error is returned after the maximum number of attempts.
+        assert_eq!(res, Err(5));
+        let delays: Vec<_> = attempts.windows(2).map(|w| w[1] - w[0]).collect();
+        let secs = |s| Duration::from_secs(s);
+        assert_eq!(delays, [secs(1), secs(2), secs(4), secs(5)]);
+    });
+}
+
+#[test]
+fn jitter_shortens_delays() {
+    let rt = Builder::new().build().unwrap();
+    rt.block_on(async {
+        time::pause();
+        let start = time::now();
+
+        let backoff = Backoff::fixed(Duration::from_secs(10)).jitter(0.5);
+        let mut attempts = 0;
+        let res = future::retry(backoff, || {
+            attempts += 1;
+            let n = attempts;
+            async move { if n < 11 { Err(()) } else { Ok(n) } }
+        })
+        .await;
+
+        assert_eq!(res, Ok(11));
+        let elapsed = time::now() - start;
+        assert!(elapsed >= Duration::from_secs(50));
+        assert!(elapsed < Duration::from_secs(100));
+    });
+}
+
+#[test]
+fn retry_if_stops_on_permanent_errors() {
+    let rt = Builder::new().build().unwrap();
+    rt.block_on(async {
+        time::pause();
+
+        let mut attempts = 0;
+        let res: Result<(), &str> = future::retry_if(
+            Backoff::fixed(Duration::from_secs(1)),
+            || {
+                attempts += 1;
+                let n = attempts;
+                async move { Err(if n < 3 { "transient" } else { "permanent" }) }
+            },
+            |err| *err == "transient",
+        )
+        .await;
+
+        assert_eq!(res, Err("permanent"));
+        assert_eq!(attempts, 3);
+    });
+}
@@ -0,0 +1,304 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use async_std::runtime::{Affinity, Builder, Handle, Runtime};
+use async_std::task;
+
+fn thread_name() -> Option<String> {
+    thread::current().name().map(String::from)
+}
+
+#[test]
+fn block_on_spawns_onto_runtime() {
+    let rt = Builder::new().thread_name("rt-a").build().unwrap();
+
+    let names = rt.block_on(async {
+        let outer = task::spawn(async {
+            // Spawned from a worker thread of the runtime.
+            let inner = task::spawn(async { thread_name() }).await;
+            (thread_name(), inner)
+        });
+        outer.await
+    });
+
+    assert_eq!(names.0.as_deref(), Some("rt-a"));
+    assert_eq!(names.1.as_deref(), Some("rt-a"));
+}
+
+#[test]
+fn runtimes_are_isolated() {
+    let a = Builder::new().thread_name("rt-one").build().unwrap();
+    let b = Builder::new().thread_name("rt-two").build().unwrap();
+
+    let name_a = task::block_on(a.spawn(async { thread_name() }));
+    let name_b = task::block_on(b.spawn(async { thread_name() }));
+    let global = task::block_on(task::spawn(async { thread_name() }));
+
+    assert_eq!(name_a.as_deref(), Some("rt-one"));
+    assert_eq!(name_b.as_deref(), Some("rt-two"));
+    assert_eq!(global.as_deref(), Some("async-std/executor"));
+}
+
+#[test]
+fn thread_hooks() {
+    let started = Arc::new(AtomicUsize::new(0));
+    let stopped = Arc::new(AtomicUsize::new(0));
+
+    let rt = {
+        let started = started.clone();
+        let stopped = stopped.clone();
+        Builder::new()
+            .worker_threads(3)
+            .on_thread_start(move || {
+                started.fetch_add(1, Ordering::SeqCst);
+            })
+            .on_thread_stop(move || {
+                stopped.fetch_add(1, Ordering::SeqCst);
+            })
+            .build()
+            .unwrap()
+    };
+    rt.block_on(rt.spawn(async {}));
+    drop(rt);
+
+    // Worker threads stop shortly after the runtime is dropped.
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while stopped.load(Ordering::SeqCst) < 3 && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(1));
+    }
+    assert_eq!(started.load(Ordering::SeqCst), 3);
+    assert_eq!(stopped.load(Ordering::SeqCst), 3);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn worker_affinity() {
+    let rt = Builder::new()
+        .worker_threads(2)
+        .worker_affinity(Affinity::PerCore)
+        .build()
+        .unwrap();
+    assert_eq!(rt.block_on(rt.spawn(async { 1 + 2 })), 3);
+
+    let err = Builder::new()
+        .worker_threads(1)
+        .worker_affinity(Affinity::Cpus(vec![1 << 20]))
+        .build()
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+/// Spawns three tasks from a single worker thread and returns the order in which they ran.
+fn spawn_order(builder: Builder) -> Vec<i32> {
+    let rt = builder.worker_threads(1).build().unwrap();
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    let handles = rt.block_on(rt.spawn({
+        let order = order.clone();
+        async move {
+            (0..3)
+                .map(|i| {
+                    let order = order.clone();
+                    task::spawn(async move { order.lock().unwrap().push(i) })
+                })
+                .collect::<Vec<_>>()
+        }
+    }));
+    for handle in handles {
+        rt.block_on(handle);
+    }
+
+    let order = order.lock().unwrap().clone();
+    order
+}
+
+#[test]
+fn scheduler_policy() {
+    // The last woken task runs first.
+    assert_eq!(spawn_order(Builder::new()), [2, 0, 1]);
+
+    assert_eq!(spawn_order(Builder::new().lifo_slot(false)), [0, 1, 2]);
+    assert_eq!(spawn_order(Builder::new().fifo_scheduling(true)), [0, 1, 2]);
+}
+
+#[test]
+fn yield_now_runs_other_tasks_first() {
+    let rt = Builder::new().worker_threads(1).build().unwrap();
+    let order = Arc::new(Mutex::new(Vec::new()));
+
+    rt.block_on(rt.spawn({
+        let order = order.clone();
+        async move {
+            let other = task::spawn({
+                let order = order.clone();
+                async move { order.lock().unwrap().push("other") }
+            });
+
+            // The spawned task sits in the slot, but yielding must not jump ahead of it.
+            task::yield_now().await;
+            order.lock().unwrap().push("yielded");
+            other.await;
+        }
+    }));
+
+    assert_eq!(*order.lock().unwrap(), ["other", "yielded"]);
+}
+
+#[test]
+fn without_work_stealing() {
+    let rt = Builder::new()
+        .worker_threads(4)
+        .work_stealing(false)
+        .build()
+        .unwrap();
+
+    let sum = rt.block_on(async {
+        let handles: Vec<_> = (0..100).map(|i| task::spawn(async move { i })).collect();
+        let mut sum = 0;
+        for handle in handles {
+            sum += handle.await;
+        }
+        sum
+    });
+    assert_eq!(sum, 4950);
+}
+
+#[test]
+fn drop_cancels_pending_tasks() {
+    let rt = Runtime::new().unwrap();
+    let handle = rt.spawn(task::sleep(Duration::from_secs(10)));
+    drop(rt);
+
+    let res = task::block_on(handle.join());
+    assert!(res.unwrap_err().is_cancelled());
+}
+
+#[test]
+fn drop_runs_cleanup_on_global_executor() {
+    let rt = Builder::new().thread_name("rt-cleanup").build().unwrap();
+    let (s, r) = std::sync::mpsc::channel();
+
+    rt.block_on(async {
+        task::spawn_with_cleanup(task::sleep(Duration::from_secs(10)), async move {
+            s.send(thread_name()).unwrap();
+        });
+    });
+    drop(rt);
+
+    let name = r.recv_timeout(Duration::from_secs(5)).unwrap();
+    assert_eq!(name.as_deref(), Some("async-std/executor"));
+}
+
+#[test]
+fn current_thread_runs_tasks_in_block_on() {
+    let rt = Builder::new_current_thread().build().unwrap();
+    let log = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    // Tasks spawned before `block_on` wait for it.
+    let handles: Vec<_> = (0..3)
+        .map(|i| {
+            let log = log.clone();
+            rt.spawn(async move {
+                log.lock().unwrap().push(i);
+            })
+        })
+        .collect();
+    thread::sleep(Duration::from_millis(10));
+    assert!(log.lock().unwrap().is_empty());
+
+    let caller = thread::current().id();
+    let same_thread = rt.block_on(async move {
+        for h in handles {
+            h.await;
+        }
+        task::spawn(async move {
+            // Woken up by the runtime's own timer.
+            task::sleep(Duration::from_millis(10)).await;
+            thread::current().id() == caller
+        })
+        .await
+    });
+
+    assert!(same_thread);
+    assert_eq!(*log.lock().unwrap(), [0, 1, 2]);
+}
+
+#[test]
+fn shutdown_timeout_waits_for_tasks_and_threads() {
+    let stopped = Arc::new(AtomicUsize::new(0));
+    let rt = {
+        let stopped = stopped.clone();
+        Builder::new()
+            .worker_threads(2)
+            .on_thread_stop(move || {
+                stopped.fetch_add(1, Ordering::SeqCst);
+            })
+            .build()
+            .unwrap()
+    };
+
+    let handle = rt.spawn(async {
+        task::sleep(Duration::from_millis(50)).await;
+
+        // The runtime doesn't accept new tasks anymore.
+        task::spawn(async {}).join().await.unwrap_err().is_cancelled()
+    });
+
+    rt.shutdown_timeout(Duration::from_secs(5));
+    assert_eq!(stopped.load(Ordering::SeqCst), 2);
+    assert!(task::block_on(handle.join()).unwrap());
+}
+
+#[test]
+fn shutdown_timeout_expires() {
+    let rt = Runtime::new().unwrap();
+    let _handle = rt.spawn(task::sleep(Duration::from_secs(10)));
+
+    let start = Instant::now();
+    rt.shutdown_timeout(Duration::from_millis(50));
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn handle_block_on_from_thread() {
+    let rt = Builder::new().thread_name("rt-handle").build().unwrap();
+    let handle = rt.handle();
+
+    let name = thread::spawn(move || {
+        // Spawned onto the runtime of the handle.
+        handle.block_on(async { task::spawn(async { thread_name() }).await })
+    })
+    .join()
+    .unwrap();
+    assert_eq!(name.as_deref(), Some("rt-handle"));
+}
+
+#[test]
+fn handle_block_on_in_task() {
+    let rt = Builder::new().worker_threads(1).build().unwrap();
+
+    let n = rt.block_on(async {
+        task::spawn(async {
+            // The only worker thread blocks while the inner task runs.
+            let inner = task::spawn(async { 1 + 2 });
+            Handle::current().block_on(inner)
+        })
+        .await
+    });
+    assert_eq!(n, 3);
+}
+
+#[test]
+#[should_panic = "cannot block on a future from within a task"]
+fn runtime_block_on_in_task() {
+    let rt = Arc::new(Runtime::new().unwrap());
+    let inner = rt.clone();
+    let res = rt.block_on(task::spawn(async move { inner.block_on(async {}) }).join());
+    if let Err(err) = res {
+        std::panic::resume_unwind(err.into_panic());
+    }
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "unstable")]
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use async_std::net::{TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::runtime::Builder;
+use async_std::sync::channel;
+use async_std::task;
+
+/// Returns the names of the threads of this process.
+#[cfg(target_os = "linux")]
+fn thread_names() -> Vec<String> {
+    std::fs::read_dir("/proc/self/task")
+        .unwrap()
+        .map(|entry| {
+            let comm = entry.unwrap().path().join("comm");
+            std::fs::read_to_string(comm).unwrap().trim().to_string()
+        })
+        .collect()
+}
+
+// This is the only test in this binary, so that no other test starts the global reactor.
+#[test]
+fn drives_io_and_timers_on_the_calling_thread() {
+    let rt = Builder::new_current_thread().build().unwrap();
+
+    rt.block_on(async {
+        // I/O between tasks of the runtime.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = task::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0; 5];
+            stream.read_exact(&mut buf).await.unwrap();
+            stream.write_all(&buf).await.unwrap();
+        });
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"hello").await.unwrap();
+        let mut buf = [0; 5];
+        stream.read_exact(&mut buf).await.unwrap();
+        assert_eq!(&buf, b"hello");
+        server.await;
+
+        // Timers are fired once their deadline has passed.
+        let start = Instant::now();
+        task::sleep(Duration::from_millis(20)).await;
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        // A task woken up from another thread interrupts the wait in the reactor.
+        let (s, r) = channel(1);
+        let start = Instant::now();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            task::block_on(s.send(1));
+        });
+        let received = task::spawn(async move { r.recv().await }).await;
+        assert_eq!(received, Some(1));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    });
+
+    #[cfg(target_os = "linux")]
+    assert!(!thread_names().iter().any(|name| name == "async-std/net"));
+}
@@ -182,3 +182,99 @@ fn writer_and_readers() {
         assert_eq!(*lock, 10);
     });
 }
+
+#[cfg(feature = "unstable")]
+#[test]
+fn mapped_read_guard() {
+    use async_std::sync::{MappedRwLockReadGuard, RwLockReadGuard};
+
+    task::block_on(async {
+        let lock = RwLock::new((0, String::from("abc")));
+
+        let s = RwLockReadGuard::map(lock.read().await, |pair| &pair.1);
+        let s = MappedRwLockReadGuard::map(s, |s| &s[1..]);
+        assert_eq!(&*s, "bc");
+        assert!(lock.try_read().is_some());
+        assert!(lock.try_write().is_none());
+
+        drop(s);
+        assert!(lock.try_write().is_some());
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn upgradable_read_waits_for_readers() {
+    use async_std::sync::RwLockUpgradableReadGuard;
+
+    task::block_on(async {
+        let lock = Arc::new(RwLock::new(0));
+
+        let r = lock.read().await;
+        let u = lock.upgradable_read().await;
+        assert!(lock.try_upgradable_read().is_none());
+        assert!(lock.try_write().is_none());
+
+        let upgrade = async {
+            let mut w = RwLockUpgradableReadGuard::upgrade(u).await;
+            *w += 1;
+        };
+        let release = async {
+            task::sleep(std::time::Duration::from_millis(10)).await;
+            assert_eq!(*r, 0);
+            drop(r);
+        };
+        upgrade.join(release).await;
+
+        assert_eq!(*lock.read().await, 1);
+        assert!(lock.try_upgradable_read().is_some());
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn upgradable_readers_are_exclusive() {
+    task::block_on(async {
+        let lock = Arc::new(RwLock::new(0));
+        let num_tasks = 100;
+        let mut tasks = vec![];
+
+        for _ in 0..num_tasks {
+            let lock = lock.clone();
+            tasks.push(task::spawn(async move {
+                use async_std::sync::RwLockUpgradableReadGuard;
+
+                let u = lock.upgradable_read().await;
+                let n = *u;
+                task::yield_now().await;
+                let mut w = RwLockUpgradableReadGuard::upgrade(u).await;
+                *w = n + 1;
+            }));
+        }
+
+        for t in tasks {
+            t.await;
+        }
+        assert_eq!(*lock.read().await, num_tasks);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn timed_acquisition() {
+    use std::time::Duration;
+
+    task::block_on(async {
+        let lock = RwLock::new(0);
+
+        let w = lock.write().await;
+        assert!(lock.try_read_for(Duration::from_millis(10)).await.is_none());
+        assert!(lock.try_write_for(Duration::from_millis(10)).await.is_none());
+        drop(w);
+
+        let r = lock.try_read_for(Duration::from_millis(10)).await.unwrap();
+        assert!(lock.try_write_for(Duration::from_millis(10)).await.is_none());
+        drop(r);
+        assert!(lock.try_write_for(Duration::from_millis(10)).await.is_some());
+    });
+}
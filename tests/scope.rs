@@ -0,0 +1,44 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_std::task;
+
+#[test]
+fn tasks_borrow_and_complete_before_return() {
+    let counter = AtomicUsize::new(0);
+    let data = [1, 2, 3];
+
+    task::scope(|s| {
+        for _ in 0..10 {
+            s.spawn(async {
+                task::sleep(Duration::from_millis(5)).await;
+                counter.fetch_add(data.len(), Ordering::SeqCst);
+            });
+        }
+    });
+
+    assert_eq!(counter.load(Ordering::SeqCst), 30);
+}
+
+#[test]
+fn joined_panic_is_returned() {
+    let n = task::scope(|s| {
+        let h = s.spawn(async {
+            panic!("boom");
+        });
+        let err = task::block_on(h.join()).unwrap_err();
+        assert!(err.is_panic());
+        1
+    });
+    assert_eq!(n, 1);
+}
+
+#[test]
+#[should_panic(expected = "unjoined")]
+fn unjoined_panic_propagates() {
+    task::scope(|s| {
+        s.spawn(async { panic!("unjoined") });
+    });
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::select;
+use async_std::sync::channel;
+use async_std::task;
+
+#[test]
+fn first_ready_branch_wins() {
+    task::block_on(async {
+        let slow = task::sleep(Duration::from_secs(10));
+        let fast = future::ready(7);
+
+        let n = select! {
+            _ = slow => 0,
+            n = fast => n,
+        };
+        assert_eq!(n, 7);
+    });
+}
+
+#[test]
+fn biased_polls_in_order() {
+    task::block_on(async {
+        for _ in 0..20 {
+            let n = select! {
+                biased;
+                n = future::ready(1) => n,
+                n = future::ready(2) => n,
+            };
+            assert_eq!(n, 1);
+        }
+    });
+}
+
+#[test]
+fn unbiased_is_fair() {
+    task::block_on(async {
+        let mut seen = [false; 2];
+        for _ in 0..100 {
+            let i = select! {
+                i = future::ready(0) => i,
+                i = future::ready(1) => i,
+            };
+            seen[i] = true;
+        }
+        assert_eq!(seen, [true, true]);
+    });
+}
+
+#[test]
+fn unmatched_branches_are_disabled() {
+    task::block_on(async {
+        let (s, r) = channel::<i32>(1);
+        let (s2, r2) = channel::<i32>(1);
+        drop(s);
+
+        task::spawn(async move {
+            task::sleep(Duration::from_millis(10)).await;
+            s2.send(5).await;
+        });
+
+        let n = select! {
+            Some(n) = r.recv() => n,
+            Some(n) = r2.recv() => { n * 2 }
+            else => 0,
+        };
+        assert_eq!(n, 10);
+
+        let n = select! {
+            Some(n) = r.recv() => n,
+            Some(n) = r2.recv() => n,
+            else => -1,
+        };
+        assert_eq!(n, -1);
+    });
+}
+
+#[test]
+fn races_send_and_recv() {
+    task::block_on(async {
+        let (s, r) = channel::<i32>(1);
+        let (_s2, r2) = channel::<i32>(1);
+
+        let sent = select! {
+            _ = s.send(1) => true,
+            _ = r2.recv() => false,
+        };
+        assert!(sent);
+        assert_eq!(r.recv().await, Some(1));
+    });
+}
+
+#[test]
+fn handlers_can_use_control_flow() {
+    let res: Result<i32, &str> = task::block_on(async {
+        let mut total = 0;
+        loop {
+            select! {
+                n = future::ready(total) => {
+                    if n >= 3 {
+                        break;
+                    }
+                    total += 1;
+                }
+            }
+        }
+        let n = select! {
+            r = future::ready(Err::<i32, _>("nope")) => r?,
+        };
+        Ok(total + n)
+    });
+    assert_eq!(res, Err("nope"));
+}
+
+#[test]
+#[should_panic(expected = "all branches are disabled")]
+fn panics_without_else() {
+    task::block_on(async {
+        select! {
+            Some(n) = future::ready(None::<i32>) => n,
+        };
+    });
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_std::future;
+use async_std::sync::{Arc, Semaphore};
+use async_std::task;
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let sem = Semaphore::new(2);
+
+        let a = sem.acquire().await;
+        let b = sem.acquire().await;
+        assert!(sem.try_acquire().is_none());
+
+        drop(a);
+        assert_eq!(sem.available_permits(), 1);
+        drop(b);
+        assert_eq!(sem.available_permits(), 2);
+    });
+}
+
+#[test]
+fn limits_concurrency() {
+    task::block_on(async {
+        let sem = Arc::new(Semaphore::new(3));
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..20)
+            .map(|_| {
+                let sem = sem.clone();
+                let running = running.clone();
+                let peak = peak.clone();
+                task::spawn(async move {
+                    let _permit = sem.acquire_owned().await;
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    task::sleep(Duration::from_millis(5)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        for t in tasks {
+            t.await;
+        }
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert_eq!(sem.available_permits(), 3);
+    });
+}
+
+#[test]
+fn acquire_many_waits_for_all_permits() {
+    task::block_on(async {
+        let sem = Arc::new(Semaphore::new(3));
+        let held = sem.acquire_many(2).await;
+
+        let s = sem.clone();
+        let waiter = task::spawn(async move { s.acquire_many_owned(3).await.permits() });
+
+        task::sleep(Duration::from_millis(50)).await;
+        assert_eq!(sem.available_permits(), 1);
+
+        drop(held);
+        assert_eq!(waiter.await, 3);
+        assert_eq!(sem.available_permits(), 3);
+    });
+}
+
+#[test]
+fn cancelled_acquire_does_not_leak() {
+    task::block_on(async {
+        let sem = Semaphore::new(1);
+        let permit = sem.acquire().await;
+
+        assert!(future::timeout(Duration::from_millis(10), sem.acquire())
+            .await
+            .is_err());
+
+        drop(permit);
+        assert!(sem.try_acquire().is_some());
+    });
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::sync::{Arc, ShardedLock};
+use async_std::task;
+
+#[test]
+fn write_excludes_readers() {
+    task::block_on(async {
+        let lock = ShardedLock::new(0);
+
+        let w = lock.write().await;
+        assert!(lock.try_read().is_none());
+        assert!(lock.try_write().is_none());
+        drop(w);
+
+        let r = lock.read().await;
+        assert!(lock.try_read().is_some());
+        assert!(lock.try_write().is_none());
+        drop(r);
+
+        assert!(lock.try_write().is_some());
+    });
+}
+
+#[test]
+fn contention() {
+    const N: u32 = 10;
+    const M: usize = 1000;
+
+    task::block_on(async {
+        let lock = Arc::new(ShardedLock::new(0u32));
+        let mut handles = vec![];
+
+        for i in 0..N {
+            let lock = lock.clone();
+            handles.push(task::spawn(async move {
+                for _ in 0..M {
+                    if i % 2 == 0 {
+                        *lock.write().await += 1;
+                    } else {
+                        drop(lock.read().await);
+                    }
+                }
+            }));
+        }
+
+        for h in handles {
+            h.await;
+        }
+        assert_eq!(*lock.read().await, N / 2 * M as u32);
+    });
+}
+
+#[test]
+fn writer_waits_for_readers_on_other_threads() {
+    task::block_on(async {
+        let lock = Arc::new(ShardedLock::new(()));
+        let lock2 = lock.clone();
+
+        let reader = task::spawn(async move {
+            let _r = lock2.read().await;
+            task::sleep(Duration::from_millis(20)).await;
+        });
+        task::sleep(Duration::from_millis(5)).await;
+
+        let _w = lock.write().await;
+        reader.await;
+    });
+}
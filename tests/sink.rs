@@ -0,0 +1,95 @@
+#![cfg(feature = "unstable")]
+
+use std::pin::Pin;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sink::{Sink, SinkExt};
+use async_std::stream;
+use async_std::sync::{channel, TryReserveError};
+use async_std::task;
+
+#[test]
+fn send_into_channel() {
+    task::block_on(async {
+        let (mut s, r) = channel(1);
+
+        let handle = task::spawn(async move {
+            for i in 0..10 {
+                SinkExt::send(&mut s, i).await.unwrap();
+            }
+            s
+        });
+        let received: Vec<_> = r.clone().take(10).collect().await;
+        assert_eq!(received, (0..10).collect::<Vec<_>>());
+
+        // Sending into a closed channel fails.
+        let mut s = handle.await;
+        drop(r);
+        assert_eq!(
+            SinkExt::send(&mut s, 10).await,
+            Err(TryReserveError::Closed)
+        );
+    })
+}
+
+#[test]
+fn send_all_and_with() {
+    task::block_on(async {
+        let (s, r) = channel(2);
+
+        let handle = task::spawn(async move {
+            // The adapter holds an async block, so it needs to be pinned.
+            let mut s = Box::pin(s.with(|n: u32| async move {
+                task::yield_now().await;
+                Ok::<_, TryReserveError>(n * 10)
+            }));
+            s.send_all(&mut stream::from_iter(1..=5)).await.unwrap();
+        });
+
+        let received: Vec<_> = r.collect().await;
+        assert_eq!(received, [10, 20, 30, 40, 50]);
+        handle.await;
+    })
+}
+
+#[test]
+fn buffer() {
+    task::block_on(async {
+        let (s, r) = channel(1);
+        let mut s = s.buffer(3);
+
+        // The buffer accepts values while the channel is full.
+        for i in 0..4 {
+            ready(&mut s).await;
+            Pin::new(&mut s).start_send(i).unwrap();
+        }
+        assert_eq!(r.len(), 1);
+
+        task::spawn(async move {
+            SinkExt::close(&mut s).await.unwrap();
+        });
+        let received: Vec<_> = r.collect().await;
+        assert_eq!(received, [0, 1, 2, 3]);
+    })
+}
+
+#[test]
+fn unused_reservation_is_released() {
+    task::block_on(async {
+        let (mut s1, r) = channel::<i32>(1);
+        let s2 = s1.clone();
+
+        // Readiness claims the only slot until the sender is dropped.
+        ready(&mut s1).await;
+        assert!(s2.try_reserve().is_err());
+        drop(s1);
+        s2.try_reserve().unwrap().send(1);
+        assert_eq!(r.recv().await, Some(1));
+    })
+}
+
+async fn ready<S: Sink<i32> + Unpin>(sink: &mut S) {
+    let res = future::poll_fn(|cx| Pin::new(&mut *sink).poll_ready(cx)).await;
+    assert!(res.is_ok());
+}
@@ -98,3 +98,250 @@ fn merge_works_with_unfused_streams() {
     });
     assert_eq!(xs, vec![92, 92]);
 }
+
+#[test]
+/// Checks that `par_map` keeps the original order and never runs more than `limit` closures at
+/// once.
+fn par_map_preserves_order_and_limit() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+
+    let r = running.clone();
+    let p = peak.clone();
+    let s = stream::from_iter(0..10u64).par_map(3, move |n| {
+        let now = r.fetch_add(1, Ordering::SeqCst) + 1;
+        p.fetch_max(now, Ordering::SeqCst);
+        thread::sleep(Duration::from_millis(10 * (10 - n)));
+        r.fetch_sub(1, Ordering::SeqCst);
+        n * 2
+    });
+
+    let xs: Vec<u64> = task::block_on(s.collect());
+    assert_eq!(xs, (0..10).map(|n| n * 2).collect::<Vec<_>>());
+    assert!(peak.load(Ordering::SeqCst) <= 3);
+}
+
+#[test]
+/// Checks that a lagging `share` handle reports the number of missed items and that handles
+/// waiting for the same item are all woken up.
+fn share_reports_lag_and_wakes_all_handles() {
+    task::block_on(async {
+        let mut a = stream::from_iter(0..5u8).share(2);
+        let mut b = a.clone();
+
+        for i in 0..5 {
+            assert_eq!(a.next().await.unwrap().unwrap(), i);
+        }
+        assert_eq!(b.next().await.unwrap().unwrap_err().skipped(), 3);
+        assert_eq!(b.next().await.unwrap().unwrap(), 3);
+        assert_eq!(b.next().await.unwrap().unwrap(), 4);
+        assert!(b.next().await.is_none());
+    });
+
+    task::block_on(async {
+        let (sender, receiver) = channel::<i32>(10);
+        let shared = receiver.share(10);
+
+        let handles: Vec<_> = (0..3)
+            .map(|_| {
+                let mut s = shared.clone();
+                task::spawn(async move {
+                    let mut xs = Vec::new();
+                    while let Some(x) = s.next().await {
+                        xs.push(x.unwrap());
+                    }
+                    xs
+                })
+            })
+            .collect();
+        drop(shared);
+
+        task::sleep(std::time::Duration::from_millis(100)).await;
+        sender.send(1).await;
+        sender.send(2).await;
+        drop(sender);
+
+        for h in handles {
+            assert_eq!(h.await, vec![1, 2]);
+        }
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn from_async_fn_awaits_each_element() {
+    use std::time::Duration;
+
+    task::block_on(async {
+        let mut n = 0;
+        let s = stream::from_async_fn(|| {
+            n += 1;
+            let n = n;
+            async move {
+                task::sleep(Duration::from_millis(1)).await;
+                if n <= 3 { Some(n) } else { None }
+            }
+        });
+        pin_utils::pin_mut!(s);
+
+        let mut items = Vec::new();
+        while let Some(n) = s.next().await {
+            items.push(n);
+        }
+        assert_eq!(items, [1, 2, 3]);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn repeat_with_interval_samples_periodically() {
+    use std::time::{Duration, Instant};
+
+    task::block_on(async {
+        let start = Instant::now();
+        let mut calls = 0;
+        let s = stream::repeat_with_interval(
+            || {
+                calls += 1;
+                calls
+            },
+            Duration::from_millis(20),
+        )
+        .take(3);
+        pin_utils::pin_mut!(s);
+
+        let mut items = Vec::new();
+        while let Some(n) = s.next().await {
+            items.push(n);
+        }
+        assert_eq!(items, [1, 2, 3]);
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn try_collect_stops_at_first_error() {
+    use std::collections::BTreeSet;
+
+    task::block_on(async {
+        let mut polled = 0;
+        let s = stream::from_iter(vec![Ok(1), Ok(2), Err("bad"), Ok(4)]).inspect(|_| polled += 1);
+        let res: Result<BTreeSet<i32>, &str> = s.try_collect().await;
+        assert_eq!(res, Err("bad"));
+        assert_eq!(polled, 3);
+
+        let s = stream::from_iter(vec![Ok::<_, ()>("a".into()), Ok("b".into())]);
+        let joined: Result<String, ()> = s.try_collect::<Box<str>, _, _>().await;
+        assert_eq!(joined, Ok("ab".to_string()));
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn double_ended_adapters_consume_from_the_back() {
+    use async_std::stream::DoubleEndedStream;
+
+    task::block_on(async {
+        let s = stream::from_iter(vec![1, 2, 3])
+            .chain(stream::from_iter(vec![4, 5, 6]))
+            .filter(|x| x % 2 == 0)
+            .map(|x| x * 10)
+            .rev();
+        let v: Vec<i32> = s.collect().await;
+        assert_eq!(v, [60, 40, 20]);
+
+        let mut s = stream::from_iter(vec![1, 2, 3, 4, 5]).fuse();
+        assert_eq!(s.nth_back(1).await, Some(4));
+        assert_eq!(s.next().await, Some(1));
+        assert_eq!(s.rfind(|x| *x < 3).await, Some(2));
+        assert_eq!(s.next_back().await, None);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn zip_longest_keeps_the_longer_stream() {
+    use async_std::stream::EitherOrBoth;
+
+    task::block_on(async {
+        let l = stream::from_iter(vec![1, 2, 3]);
+        let r = stream::from_iter(vec![10]);
+        let v: Vec<_> = l.zip_longest(r).collect().await;
+        assert_eq!(
+            v,
+            [
+                EitherOrBoth::Both(1, 10),
+                EitherOrBoth::Left(2),
+                EitherOrBoth::Left(3)
+            ]
+        );
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn cartesian_product_restarts_the_second_stream() {
+    task::block_on(async {
+        let l = stream::from_iter(vec!['a', 'b', 'c']);
+        let r = stream::from_iter(vec![1, 2]);
+        let v: Vec<_> = l.cartesian_product(r).collect().await;
+        assert_eq!(
+            v,
+            [('a', 1), ('a', 2), ('b', 1), ('b', 2), ('c', 1), ('c', 2)]
+        );
+
+        let l = stream::from_iter(vec![1, 2]);
+        let v: Vec<(i32, i32)> = l
+            .cartesian_product(stream::from_iter(vec![]))
+            .collect()
+            .await;
+        assert!(v.is_empty());
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn eq_by_and_cmp_by_account_for_length() {
+    use std::cmp::Ordering;
+
+    task::block_on(async {
+        let a = stream::from_iter(vec![1, 2]);
+        let b = stream::from_iter(vec![1, 2, 3]);
+        assert!(!a.eq_by(b, |x, y| x == y).await);
+
+        let a = stream::from_iter(vec![1, 2, 3]);
+        let b = stream::from_iter(vec![-1, -2, -3]);
+        assert!(a.eq_by(b, |x, y| *x == -y).await);
+
+        let a = stream::from_iter(vec![1, 2]);
+        let b = stream::from_iter(vec![1, 2, 3]);
+        assert_eq!(a.cmp_by(b, |x, y| x.cmp(y)).await, Ordering::Less);
+
+        let a = stream::from_iter(vec![3, 1]);
+        let b = stream::from_iter(vec![1, 2, 3]);
+        assert_eq!(a.cmp_by(b, |x, y| y.cmp(x)).await, Ordering::Less);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn merge_by_is_sorted_and_stable() {
+    use std::time::Duration;
+
+    task::block_on(async {
+        let a = stream::from_iter(vec![(1, 'a'), (3, 'a'), (3, 'a')])
+            .throttle(Duration::from_millis(5));
+        let b = stream::from_iter(vec![(0, 'b'), (3, 'b'), (9, 'b')]);
+        let v: Vec<_> = a.merge_by(b, |x, y| x.0.cmp(&y.0)).collect().await;
+        assert_eq!(
+            v,
+            [(0, 'b'), (1, 'a'), (3, 'a'), (3, 'a'), (3, 'b'), (9, 'b')]
+        );
+    });
+}
@@ -0,0 +1,110 @@
+#![cfg(all(unix, feature = "unstable"))]
+
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::future::Backoff;
+use async_std::prelude::*;
+use async_std::process::{ChildSpec, RestartPolicy, Supervisor, SupervisorEvent};
+use async_std::task;
+
+fn sh(script: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(script);
+    command
+}
+
+fn backoff() -> Backoff {
+    Backoff::fixed(Duration::from_millis(1))
+}
+
+#[test]
+fn restart_on_failure() {
+    task::block_on(async {
+        let supervised = Supervisor::new()
+            .child(
+                ChildSpec::new("failing", sh("exit 3"))
+                    .backoff(backoff())
+                    .max_restarts(2),
+            )
+            .child(ChildSpec::new("succeeding", sh("exit 0")).backoff(backoff()))
+            .start();
+        let events: Vec<_> = supervised.collect().await;
+
+        let of = |name: &str| -> Vec<&SupervisorEvent> {
+            events.iter().filter(|e| e.name() == name).collect()
+        };
+        let failing = of("failing");
+        let exit_codes: Vec<_> = failing
+            .iter()
+            .filter_map(|e| match e {
+                SupervisorEvent::Exited { status, .. } => status.code(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(exit_codes, [3, 3, 3]);
+        match &failing[failing.len() - 2..] {
+            [
+                SupervisorEvent::Exited { .. },
+                SupervisorEvent::Stopped { .. },
+            ] => {}
+            events => panic!("unexpected events: {:?}", events),
+        }
+
+        // A successful exit isn't restarted.
+        let succeeding = of("succeeding");
+        assert_eq!(succeeding.len(), 3);
+        assert!(matches!(succeeding[0], SupervisorEvent::Started { .. }));
+    })
+}
+
+#[test]
+fn spawn_error() {
+    task::block_on(async {
+        let command = Command::new("/nonexistent/async-std-test");
+        let supervised = Supervisor::new()
+            .child(
+                ChildSpec::new("missing", command)
+                    .restart(RestartPolicy::Never)
+                    .backoff(backoff()),
+            )
+            .start();
+        let events: Vec<_> = supervised.collect().await;
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], SupervisorEvent::Error { .. }));
+    })
+}
+
+#[test]
+fn health_check_and_shutdown() {
+    task::block_on(async {
+        let checks = Arc::new(AtomicUsize::new(0));
+        let counter = checks.clone();
+        let spec = ChildSpec::new("sleeper", sh("sleep 60"))
+            .restart(RestartPolicy::Always)
+            .backoff(backoff())
+            .health_check(Duration::from_millis(10), move |_pid| {
+                // The first child fails its second check.
+                let n = counter.fetch_add(1, Ordering::SeqCst);
+                async move { n != 1 }
+            });
+        let mut supervised = Supervisor::new().child(spec).start();
+
+        let mut pids = Vec::new();
+        while pids.len() < 2 {
+            match supervised.next().await.unwrap() {
+                SupervisorEvent::Started { pid, .. } => pids.push(pid),
+                SupervisorEvent::Unhealthy { pid, .. } => assert_eq!(pid, pids[0]),
+                _ => {}
+            }
+        }
+        assert!(checks.load(Ordering::SeqCst) >= 2);
+
+        // Shutting down kills the restarted child.
+        let start = std::time::Instant::now();
+        supervised.shutdown().await;
+        assert!(start.elapsed() < Duration::from_secs(30));
+    })
+}
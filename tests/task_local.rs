@@ -32,3 +32,80 @@ fn drop_local() {
         drop(task);
     });
 }
+
+#[cfg(feature = "unstable")]
+#[test]
+fn scope() {
+    use std::cell::Cell;
+
+    task_local! {
+        static NUMBER: Cell<u32> = Cell::new(1);
+    }
+
+    task::block_on(async {
+        let inner = NUMBER.scope(Cell::new(2), async {
+            assert_eq!(NUMBER.with(|n| n.get()), 2);
+            task::yield_now().await;
+
+            let nested = NUMBER.scope(Cell::new(3), async {
+                task::yield_now().await;
+                NUMBER.with(|n| n.get())
+            });
+            assert_eq!(nested.await, 3);
+
+            // Changes stay within the scope they were made in.
+            NUMBER.with(|n| n.set(4));
+            task::yield_now().await;
+            NUMBER.with(|n| n.get())
+        });
+        assert_eq!(inner.await, 4);
+        assert_eq!(NUMBER.with(|n| n.get()), 1);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn scope_interleaved() {
+    use async_std::future;
+
+    task_local! {
+        static NAME: &'static str = "none";
+    }
+
+    task::block_on(async {
+        let a = NAME.scope("a", async {
+            task::yield_now().await;
+            NAME.with(|n| *n)
+        });
+        let b = NAME.scope("b", async {
+            task::yield_now().await;
+            NAME.with(|n| *n)
+        });
+        assert_eq!(future::join!(a, b), ("a", "b"));
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn inherit() {
+    task_local! {
+        static NAME: String = String::new();
+    }
+
+    task::block_on(async {
+        let name = NAME.scope("parent".to_string(), async {
+            task::Builder::new()
+                .inherit(&NAME)
+                .spawn(async { NAME.with(|n| n.clone()) })
+                .unwrap()
+                .await
+        });
+        assert_eq!(name.await, "parent");
+
+        // Without `inherit`, the task-local is initialized as usual.
+        let name = NAME.scope("parent".to_string(), async {
+            task::spawn(async { NAME.with(|n| n.clone()) }).await
+        });
+        assert_eq!(name.await, "");
+    });
+}
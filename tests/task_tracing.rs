@@ -0,0 +1,182 @@
+#![cfg(feature = "tracing")]
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_std::task;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Dispatch, Event, Metadata, Subscriber};
+
+/// A span along with its fields and parent.
+#[derive(Debug, Default)]
+struct SpanData {
+    name: &'static str,
+    fields: HashMap<String, String>,
+    parent: Option<u64>,
+    refs: usize,
+    closed: bool,
+}
+
+/// An event along with its fields and the span it was recorded in.
+#[derive(Debug)]
+struct EventData {
+    fields: HashMap<String, String>,
+    parent: Option<u64>,
+}
+
+struct Fields<'a>(&'a mut HashMap<String, String>);
+
+impl Visit for Fields<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0
+            .insert(field.name().to_string(), format!("{:?}", value));
+    }
+}
+
+thread_local! {
+    /// The spans entered on this thread.
+    static STACK: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A subscriber recording every span and event.
+struct Recorder {
+    next_id: AtomicU64,
+    spans: Mutex<BTreeMap<u64, SpanData>>,
+    events: Mutex<Vec<EventData>>,
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let mut span = SpanData {
+            name: attrs.metadata().name(),
+            parent: match attrs.parent() {
+                Some(parent) => Some(parent.into_u64()),
+                None if attrs.is_contextual() => STACK.with(|s| s.borrow().last().copied()),
+                None => None,
+            },
+            refs: 1,
+            ..SpanData::default()
+        };
+        attrs.record(&mut Fields(&mut span.fields));
+        self.spans.lock().unwrap().insert(id, span);
+        Id::from_u64(id)
+    }
+
+    fn record(&self, span: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().unwrap();
+        values.record(&mut Fields(
+            &mut spans.get_mut(&span.into_u64()).unwrap().fields,
+        ));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = HashMap::new();
+        event.record(&mut Fields(&mut fields));
+        let parent = STACK.with(|s| s.borrow().last().copied());
+        self.events
+            .lock()
+            .unwrap()
+            .push(EventData { fields, parent });
+    }
+
+    fn enter(&self, span: &Id) {
+        STACK.with(|s| s.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &Id) {
+        STACK.with(|s| {
+            let mut s = s.borrow_mut();
+            let i = s.iter().rposition(|&id| id == span.into_u64()).unwrap();
+            s.remove(i);
+        });
+    }
+
+    fn clone_span(&self, id: &Id) -> Id {
+        self.spans
+            .lock()
+            .unwrap()
+            .get_mut(&id.into_u64())
+            .unwrap()
+            .refs += 1;
+        id.clone()
+    }
+
+    fn try_close(&self, id: Id) -> bool {
+        let mut spans = self.spans.lock().unwrap();
+        let span = spans.get_mut(&id.into_u64()).unwrap();
+        span.refs -= 1;
+        span.closed = span.refs == 0;
+        span.closed
+    }
+}
+
+#[test]
+fn task_spans_and_events() {
+    let recorder = Arc::new(Recorder {
+        next_id: AtomicU64::new(1),
+        spans: Mutex::new(BTreeMap::new()),
+        events: Mutex::new(Vec::new()),
+    });
+    tracing::dispatcher::set_global_default(Dispatch::new(recorder.clone())).unwrap();
+
+    let handle = task::Builder::new()
+        .name("traced".to_string())
+        .spawn(async {
+            task::sleep(Duration::from_millis(1)).await;
+            task::current().id()
+        })
+        .unwrap();
+    let id = task::block_on(handle).to_string();
+
+    let spans = recorder.spans.lock().unwrap();
+    let events = recorder.events.lock().unwrap();
+
+    // The task has a span of its own, which is closed once it completes.
+    let (&task_span, span) = spans
+        .iter()
+        .find(|(_, s)| s.name == "task" && s.fields["task_id"] == id)
+        .expect("no span for the task");
+    assert_eq!(span.fields["task_name"], "traced");
+    assert_eq!(span.fields["parent_task_id"], "0");
+    assert!(span.closed);
+
+    // Every poll of the task has a span nested in it.
+    let polls: Vec<_> = spans
+        .iter()
+        .filter(|(_, s)| s.name == "poll" && s.parent == Some(task_span))
+        .collect();
+    assert!(polls.iter().any(|(_, s)| s.fields["ready"] == "false"));
+    assert!(polls.iter().any(|(_, s)| s.fields["ready"] == "true"));
+
+    // The timer fires in one of those polls.
+    let fired = events
+        .iter()
+        .find(|e| e.fields["message"] == "timer fired" && e.fields["task_id"] == id)
+        .expect("no `timer fired` event for the task");
+    assert!(polls.iter().any(|(&poll, _)| fired.parent == Some(poll)));
+
+    // Waiting on the task blocks on a future of its own.
+    assert!(spans.values().any(|s| s.name == "block_on"));
+
+    assert!(
+        events
+            .iter()
+            .any(|e| e.fields["message"] == "reactor wakeup" && e.fields.contains_key("events"))
+    );
+}
@@ -94,3 +94,161 @@ fn smoke_async_stream_to_std_listener() -> io::Result<()> {
 
     Ok(())
 }
+
+#[cfg(feature = "unstable")]
+#[test]
+fn accept_with() -> io::Result<()> {
+    use std::time::Duration;
+
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        let client = task::spawn(async move {
+            let mut stream = TcpStream::connect(addr).await?;
+            stream.write_all(b"hello").await?;
+            io::Result::Ok(())
+        });
+
+        let (mut stream, _) = listener
+            .accept_with(|stream| {
+                stream.set_nodelay(true)?;
+                stream.set_keepalive(Some(Duration::from_secs(30)))?;
+                stream.set_recv_buffer_size(64 * 1024)
+            })
+            .await?;
+        assert!(stream.nodelay()?);
+        assert_eq!(stream.keepalive()?, Some(Duration::from_secs(30)));
+        assert!(stream.recv_buffer_size()? >= 64 * 1024);
+
+        let mut buf = Vec::new();
+        stream.read_to_end(&mut buf).await?;
+        assert_eq!(buf, b"hello");
+        client.await
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn accept_with_error() -> io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let mut client = TcpStream::connect(listener.local_addr()?).await?;
+
+        let err = listener
+            .accept_with(|_| Err(io::ErrorKind::PermissionDenied.into()))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+        // The rejected connection has been closed.
+        let mut buf = Vec::new();
+        client.read_to_end(&mut buf).await?;
+        assert!(buf.is_empty());
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn split_half_close() -> io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        // The server reads the request until the client half-closes, then responds.
+        let server = task::spawn(async move {
+            let (mut stream, _) = listener.accept().await?;
+            let mut request = Vec::new();
+            stream.read_to_end(&mut request).await?;
+            stream.write_all(&request).await?;
+            stream.close().await
+        });
+
+        let (mut reader, mut writer) = TcpStream::connect(addr).await?.into_split();
+        let response = task::spawn(async move {
+            let mut response = Vec::new();
+            reader.read_to_end(&mut response).await?;
+            io::Result::Ok(response)
+        });
+
+        writer.write_all(THE_WINTERS_TALE).await?;
+        writer.close().await?;
+        assert!(writer.write_all(b"more").await.is_err());
+
+        assert_eq!(response.await?, THE_WINTERS_TALE);
+        server.await
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn split_shutdown_and_unsplit() -> io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let client = TcpStream::connect(listener.local_addr()?).await?;
+        let (mut server, _) = listener.accept().await?;
+
+        let (mut reader, writer) = client.into_split();
+        assert_eq!(reader.peer_addr()?, server.local_addr()?);
+        reader.shutdown()?;
+        let mut buf = [0u8; 16];
+        assert_eq!(reader.read(&mut buf).await?, 0);
+
+        // Unsplitting doesn't shut down the writing portion.
+        let mut client = reader.unsplit(writer);
+        client.write_all(b"still open").await?;
+        drop(client);
+
+        let mut buf = Vec::new();
+        server.read_to_end(&mut buf).await?;
+        assert_eq!(buf, b"still open");
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn drop_write_half() -> io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let client = TcpStream::connect(listener.local_addr()?).await?;
+        let (mut server, _) = listener.accept().await?;
+
+        let (mut reader, writer) = client.into_split();
+        drop(writer);
+
+        // The peer sees the end of the stream, but can still respond.
+        let mut buf = Vec::new();
+        server.read_to_end(&mut buf).await?;
+        assert!(buf.is_empty());
+        server.write_all(b"bye").await?;
+        drop(server);
+
+        reader.read_to_end(&mut buf).await?;
+        assert_eq!(buf, b"bye");
+        Ok(())
+    })
+}
+
+#[cfg(all(feature = "unstable", target_os = "linux"))]
+#[test]
+fn tcp_info() -> io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let mut client = TcpStream::connect(listener.local_addr()?).await?;
+        let (mut server, _) = listener.accept().await?;
+
+        client.write_all(THE_WINTERS_TALE).await?;
+        let mut buf = vec![0; THE_WINTERS_TALE.len()];
+        server.read_exact(&mut buf).await?;
+
+        let info = client.tcp_info()?;
+        assert!(info.rtt() > std::time::Duration::from_secs(0));
+        assert!(info.rto() >= info.rtt());
+        assert!(info.snd_cwnd() > 0);
+        assert!(info.snd_mss() > 0);
+        assert_eq!(info.total_retransmits(), 0);
+        Ok(())
+    })
+}
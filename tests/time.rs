@@ -0,0 +1,335 @@
+#![cfg(feature = "unstable")]
+
+use std::time::{Duration, Instant};
+
+use async_std::future;
+use async_std::io;
+use async_std::prelude::*;
+use async_std::runtime::{Builder, Runtime};
+use async_std::stream;
+use async_std::sync::channel;
+use async_std::task;
+use async_std::time;
+
+fn runtime() -> Runtime {
+    Builder::new().build().unwrap()
+}
+
+#[test]
+fn advance_fires_due_timers() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+
+        let (s, r) = channel(3);
+        for secs in 1..=3 {
+            // The timers start when the intervals are created.
+            let mut interval = stream::interval(Duration::from_secs(secs));
+            let s = s.clone();
+            task::spawn(async move {
+                interval.next().await;
+                s.send(time::now()).await;
+            });
+        }
+
+        time::advance(Duration::from_secs(2)).await;
+        assert_eq!(r.recv().await.unwrap() - start, Duration::from_secs(2));
+        assert_eq!(r.recv().await.unwrap() - start, Duration::from_secs(2));
+    });
+}
+
+#[test]
+fn auto_advance() {
+    runtime().block_on(async {
+        time::pause();
+
+        let start = Instant::now();
+        let virtual_start = time::now();
+        task::sleep(Duration::from_secs(3600)).await;
+
+        assert!(start.elapsed() < Duration::from_secs(60));
+        assert!(time::now() - virtual_start >= Duration::from_secs(3600));
+    });
+}
+
+#[test]
+fn auto_advance_timeouts_in_order() {
+    runtime().block_on(async {
+        time::pause();
+
+        let res = future::timeout(Duration::from_secs(10), async {
+            task::sleep(Duration::from_secs(5)).await;
+            1
+        })
+        .await;
+        assert_eq!(res, Ok(1));
+
+        let res = io::timeout(Duration::from_secs(5), async {
+            task::sleep(Duration::from_secs(10)).await;
+            Ok(())
+        })
+        .await;
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::TimedOut);
+    });
+}
+
+#[test]
+fn interval_ticks() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+
+        let mut interval = stream::interval(Duration::from_secs(60));
+        for i in 1..=5 {
+            interval.next().await;
+            assert_eq!(time::now() - start, Duration::from_secs(60 * i));
+        }
+    });
+}
+
+#[test]
+fn current_thread_runtime() {
+    let rt = Builder::new_current_thread().build().unwrap();
+    rt.block_on(async {
+        time::pause();
+        let start = time::now();
+
+        let handle = task::spawn(async {
+            task::sleep(Duration::from_secs(30)).await;
+            time::now()
+        });
+        task::sleep(Duration::from_secs(10)).await;
+        assert_eq!(time::now() - start, Duration::from_secs(10));
+        assert_eq!(handle.await - start, Duration::from_secs(30));
+    });
+}
+
+#[test]
+fn resume() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+        time::advance(Duration::from_secs(100)).await;
+        time::resume();
+
+        // The clock keeps running from where it was advanced to.
+        assert!(time::now() - start >= Duration::from_secs(100));
+        task::sleep(Duration::from_millis(10)).await;
+        assert!(time::now() - start >= Duration::from_millis(100_010));
+    });
+}
+
+#[test]
+#[should_panic(expected = "not paused")]
+fn advance_without_pause() {
+    runtime().block_on(time::advance(Duration::from_secs(1)));
+}
+
+#[test]
+fn sleep_until_deadline() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+
+        task::sleep_until(start + Duration::from_secs(10)).await;
+        assert_eq!(time::now() - start, Duration::from_secs(10));
+
+        // A deadline in the past completes immediately.
+        task::sleep_until(start).await;
+        assert_eq!(time::now() - start, Duration::from_secs(10));
+    });
+}
+
+#[test]
+fn reset_sleep() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+
+        let mut sleep = task::sleep_until(start + Duration::from_secs(10));
+        assert!(!sleep.is_elapsed());
+
+        time::advance(Duration::from_secs(5)).await;
+        sleep.reset(start + Duration::from_secs(20));
+        assert_eq!(sleep.deadline(), start + Duration::from_secs(20));
+
+        // The old deadline doesn't fire.
+        time::advance(Duration::from_secs(10)).await;
+        assert!(future::timeout_at(time::now(), &mut sleep).await.is_err());
+
+        (&mut sleep).await;
+        assert!(sleep.is_elapsed());
+        assert_eq!(time::now() - start, Duration::from_secs(20));
+
+        // A completed sleep can be reset and awaited again.
+        sleep.reset(start + Duration::from_secs(30));
+        (&mut sleep).await;
+        assert_eq!(time::now() - start, Duration::from_secs(30));
+    });
+}
+
+#[test]
+fn timeout_at_deadline() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+        let deadline = start + Duration::from_secs(10);
+
+        let res = future::timeout_at(deadline, task::sleep(Duration::from_secs(5))).await;
+        assert_eq!(res, Ok(()));
+
+        let res = future::timeout_at(deadline, future::pending::<()>()).await;
+        assert!(res.is_err());
+        assert_eq!(time::now(), deadline);
+    });
+}
+
+#[test]
+fn timeout_returns_future() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+
+        let mut slow = Box::pin(async {
+            task::sleep(Duration::from_secs(25)).await;
+            "done"
+        });
+        let mut retries = 0;
+        let out = loop {
+            match slow.timeout(Duration::from_secs(10)).await {
+                Ok(out) => break out,
+                Err(err) => {
+                    assert_eq!(err.to_string(), "future has timed out");
+                    slow = err.into_inner();
+                    retries += 1;
+                }
+            }
+        };
+
+        // The in-flight sleep wasn't restarted by the retries.
+        assert_eq!(out, "done");
+        assert_eq!(retries, 2);
+        assert_eq!(time::now() - start, Duration::from_secs(25));
+    });
+}
+
+#[test]
+fn many_timers() {
+    runtime().block_on(async {
+        let start = Instant::now();
+        let handles: Vec<_> = (0..10_000u64)
+            .map(|i| {
+                task::spawn(async move {
+                    let dur = Duration::from_millis(i % 50);
+                    let started = Instant::now();
+                    task::sleep(dur).await;
+                    assert!(started.elapsed() >= dur);
+
+                    // Most timers are cancelled before they fire.
+                    let res = future::timeout(Duration::from_secs(60), async {}).await;
+                    assert!(res.is_ok());
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.await;
+        }
+        assert!(start.elapsed() < Duration::from_secs(30));
+    });
+}
+
+#[test]
+fn now_cached() {
+    runtime().block_on(async {
+        let first = time::now_cached();
+        std::thread::sleep(Duration::from_millis(2));
+        assert_eq!(time::now_cached(), first);
+
+        // The cache is refreshed once the task yields.
+        task::yield_now().await;
+        assert!(time::now_cached() > first);
+
+        time::pause();
+        let paused = time::now_cached();
+        time::advance(Duration::from_secs(1)).await;
+        assert_eq!(time::now_cached() - paused, Duration::from_secs(1));
+    });
+}
+
+#[test]
+fn interval_with_jitter() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+        let period = Duration::from_secs(60);
+        let jitter = Duration::from_secs(10);
+
+        let mut interval = stream::interval_with_jitter(period, jitter);
+        assert_eq!(interval.jitter(), jitter);
+
+        let mut offsets = Vec::new();
+        for i in 1..=20 {
+            interval.next().await;
+            let scheduled = start + period * i;
+            let offset = time::now() - scheduled;
+            assert!(offset <= jitter);
+            offsets.push(offset);
+        }
+
+        // The ticks are spread out rather than all delayed by the same amount.
+        offsets.sort();
+        offsets.dedup();
+        assert!(offsets.len() > 1);
+    });
+}
+
+#[test]
+fn deadline_scope() {
+    runtime().block_on(async {
+        time::pause();
+        let start = time::now();
+        assert!(time::Deadline::current().is_none());
+
+        let deadline = time::Deadline::after(Duration::from_secs(10));
+        let res = deadline
+            .scope(async {
+                let current = time::Deadline::current().unwrap();
+                assert_eq!(current.remaining(), Duration::from_secs(10));
+
+                // A nested scope can't extend the deadline.
+                time::Deadline::after(Duration::from_secs(60))
+                    .scope(async {
+                        assert_eq!(time::Deadline::current(), Some(current));
+                        task::sleep(Duration::from_secs(4)).await;
+                        time::Deadline::current().unwrap().remaining()
+                    })
+                    .await
+                    .unwrap()
+            })
+            .await;
+        assert_eq!(res, Ok(Duration::from_secs(6)));
+        assert!(time::Deadline::current().is_none());
+
+        // The deadline can be passed on to a spawned task.
+        let handle = task::spawn(deadline.scope(future::pending::<()>()));
+        assert!(handle.await.is_err());
+        assert!(deadline.is_expired());
+        assert_eq!(time::now() - start, Duration::from_secs(10));
+    });
+}
+
+#[test]
+fn stopwatch() {
+    runtime().block_on(async {
+        time::pause();
+
+        let mut stopwatch = time::Stopwatch::start();
+        time::advance(Duration::from_secs(5)).await;
+        assert_eq!(stopwatch.elapsed(), Duration::from_secs(5));
+
+        assert_eq!(stopwatch.lap(), Duration::from_secs(5));
+        time::advance(Duration::from_secs(2)).await;
+        assert_eq!(stopwatch.lap(), Duration::from_secs(2));
+        assert_eq!(stopwatch.elapsed(), Duration::from_secs(0));
+    });
+}
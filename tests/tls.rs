@@ -0,0 +1,166 @@
+#![cfg(feature = "tls")]
+
+use std::sync::Arc;
+
+use async_std::io;
+use async_std::net::tls::rustls::pki_types::{PrivateKeyDer, PrivatePkcs8KeyDer};
+use async_std::net::tls::rustls::{ClientConfig, RootCertStore, ServerConfig};
+use async_std::net::tls::{TlsAcceptor, TlsConnector};
+use async_std::net::{TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::task;
+
+/// Creates an acceptor with a self-signed certificate for `localhost`, and a connector trusting
+/// it.
+fn configs() -> (TlsAcceptor, TlsConnector) {
+    let key = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    let cert = key.cert.der().clone();
+    let private_key = PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(key.key_pair.serialize_der()));
+
+    let server = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(vec![cert.clone()], private_key)
+        .unwrap();
+
+    let mut roots = RootCertStore::empty();
+    roots.add(cert).unwrap();
+    let client = ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    (TlsAcceptor::from(server), TlsConnector::from(client))
+}
+
+/// Connects a pair of TCP streams.
+async fn tcp_pair() -> io::Result<(TcpStream, TcpStream)> {
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let client = TcpStream::connect(listener.local_addr()?).await?;
+    let (server, _) = listener.accept().await?;
+    Ok((client, server))
+}
+
+#[test]
+fn round_trip() -> io::Result<()> {
+    task::block_on(async {
+        let (acceptor, connector) = configs();
+        let (client, server) = tcp_pair().await?;
+
+        let server = task::spawn(async move {
+            let mut stream = acceptor.accept(server).await?;
+            let mut buf = [0; 5];
+            stream.read_exact(&mut buf).await?;
+            assert_eq!(&buf, b"hello");
+
+            stream.write_all(b"world").await?;
+            stream.close().await?;
+            io::Result::Ok(())
+        });
+
+        let mut stream = connector.connect("localhost", client).await?;
+        stream.write_all(b"hello").await?;
+        stream.flush().await?;
+
+        // The server's close_notify ends the stream cleanly.
+        let mut received = String::new();
+        stream.read_to_string(&mut received).await?;
+        assert_eq!(received, "world");
+
+        server.await
+    })
+}
+
+#[test]
+fn large_transfer() -> io::Result<()> {
+    task::block_on(async {
+        let (acceptor, connector) = configs();
+        let (client, server) = tcp_pair().await?;
+        let data: Vec<u8> = (0..4 << 20).map(|i| (i % 251) as u8).collect();
+
+        // Echo everything back, so both directions have to make progress at the same time.
+        let server = task::spawn(async move {
+            let stream = acceptor.accept(server).await?;
+            let (mut reader, mut writer) = futures::AsyncReadExt::split(stream);
+            io::copy(&mut reader, &mut writer).await?;
+            writer.close().await?;
+            io::Result::Ok(())
+        });
+
+        let stream = connector.connect("localhost", client).await?;
+        let (mut reader, mut writer) = futures::AsyncReadExt::split(stream);
+        let expected = data.clone();
+        let sender = task::spawn(async move {
+            writer.write_all(&data).await?;
+            writer.close().await?;
+            io::Result::Ok(())
+        });
+
+        let mut received = Vec::new();
+        reader.read_to_end(&mut received).await?;
+        assert!(received == expected);
+
+        sender.await?;
+        server.await
+    })
+}
+
+#[test]
+fn untrusted_certificate() -> io::Result<()> {
+    task::block_on(async {
+        let (acceptor, _) = configs();
+        let (_, connector) = configs();
+        let (client, server) = tcp_pair().await?;
+
+        let server = task::spawn(async move { acceptor.accept(server).await.map(drop) });
+
+        let err = connector.connect("localhost", client).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(server.await.is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn truncated_stream() -> io::Result<()> {
+    task::block_on(async {
+        let (acceptor, connector) = configs();
+        let (client, server) = tcp_pair().await?;
+
+        let server = task::spawn(async move {
+            let mut stream = acceptor.accept(server).await?;
+            stream.write_all(b"partial").await?;
+            stream.flush().await?;
+
+            // Close the TCP stream without a close_notify.
+            drop(stream.into_inner());
+            io::Result::Ok(())
+        });
+
+        let mut stream = connector.connect("localhost", client).await?;
+        server.await?;
+
+        let mut received = Vec::new();
+        let err = stream.read_to_end(&mut received).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        assert_eq!(received, b"partial");
+        Ok(())
+    })
+}
+
+#[test]
+fn invalid_server_name() -> io::Result<()> {
+    task::block_on(async {
+        let (_, connector) = configs();
+        let (client, _server) = tcp_pair().await?;
+
+        let err = connector.connect("not a name", client).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        Ok(())
+    })
+}
+
+#[test]
+fn shared_config() {
+    let (acceptor, connector) = configs();
+    assert!(Arc::ptr_eq(connector.clone().config(), connector.config()));
+    assert!(Arc::ptr_eq(acceptor.clone().config(), acceptor.config()));
+}
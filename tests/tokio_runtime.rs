@@ -0,0 +1,59 @@
+#![cfg(feature = "tokio-runtime")]
+
+use std::time::Duration;
+
+use async_std::io;
+use async_std::task;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[test]
+fn tokio_timer_in_block_on() {
+    task::block_on(async {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    })
+}
+
+#[test]
+fn tokio_io_in_spawned_tasks() -> io::Result<()> {
+    task::block_on(async {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        // Tasks on async-std's worker threads use tokio's reactor.
+        let server = task::spawn(async move {
+            let (mut stream, _) = listener.accept().await?;
+            let mut buf = [0; 5];
+            stream.read_exact(&mut buf).await?;
+            stream.write_all(&buf).await?;
+            io::Result::Ok(())
+        });
+
+        let client = task::spawn(async move {
+            let mut stream = tokio::net::TcpStream::connect(addr).await?;
+            stream.write_all(b"hello").await?;
+            let mut buf = [0; 5];
+            stream.read_exact(&mut buf).await?;
+            io::Result::Ok(buf)
+        });
+
+        server.await?;
+        assert_eq!(&client.await?, b"hello");
+        Ok(())
+    })
+}
+
+#[test]
+fn tokio_tasks_from_async_std_tasks() {
+    task::block_on(async {
+        let value = task::spawn(async {
+            tokio::spawn(async {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                7
+            })
+            .await
+            .unwrap()
+        })
+        .await;
+        assert_eq!(value, 7);
+    })
+}
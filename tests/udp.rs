@@ -27,3 +27,83 @@ fn send_recv() -> io::Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn connected_port_unreachable() -> io::Result<()> {
+    task::block_on(async {
+        let socket = UdpSocket::bind("127.0.0.1:0").await?;
+        let closed = UdpSocket::bind("127.0.0.1:0").await?;
+        let addr = closed.local_addr()?;
+        drop(closed);
+
+        socket.connect(addr).await?;
+        socket.send(THE_MERCHANT_OF_VENICE).await?;
+
+        let mut buf = [0u8; 1024];
+        let err = socket.recv(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+        Ok(())
+    })
+}
+
+#[cfg(all(feature = "unstable", target_os = "linux"))]
+#[test]
+fn recv_err() -> io::Result<()> {
+    task::block_on(async {
+        let socket = UdpSocket::bind("127.0.0.1:0").await?;
+        let closed = UdpSocket::bind("127.0.0.1:0").await?;
+        let addr = closed.local_addr()?;
+        drop(closed);
+
+        assert!(!socket.recv_err()?);
+        socket.set_recv_err(true)?;
+        assert!(socket.recv_err()?);
+        assert!(socket.recv_error()?.is_none());
+
+        // Without being connected, the error still fails the receive.
+        socket.send_to(THE_MERCHANT_OF_VENICE, addr).await?;
+        let mut buf = [0u8; 1024];
+        let err = socket.recv_from(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+
+        let (err, peer) = socket.recv_error()?.unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::ConnectionRefused);
+        assert_eq!(peer, addr);
+        assert!(socket.recv_error()?.is_none());
+        assert!(socket.take_error()?.is_none());
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn framed_datagrams() -> io::Result<()> {
+    use std::sync::Arc;
+
+    use async_std::io::codec::LinesCodec;
+    use async_std::net::UdpFramed;
+    use async_std::prelude::*;
+    use async_std::sink::SinkExt;
+
+    task::block_on(async {
+        let socket = Arc::new(UdpSocket::bind("127.0.0.1:0").await?);
+        let addr = socket.local_addr()?;
+        let mut recv = UdpFramed::new(socket.clone(), LinesCodec::new());
+        let mut send = UdpFramed::new(socket, LinesCodec::new());
+
+        SinkExt::send(&mut send, ("hello", addr)).await?;
+        let (line, peer) = recv.next().await.unwrap()?;
+        assert_eq!(line, "hello");
+        assert_eq!(peer, addr);
+
+        // A datagram can hold several frames.
+        let raw = UdpSocket::bind("127.0.0.1:0").await?;
+        raw.send_to(b"one\ntwo\n", addr).await?;
+        let (line, peer) = recv.next().await.unwrap()?;
+        assert_eq!((line.as_str(), peer), ("one", raw.local_addr()?));
+        let (line, _) = recv.next().await.unwrap()?;
+        assert_eq!(line, "two");
+
+        Ok(())
+    })
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::{
+    channel_unbounded, ChannelUsage, SendError, UnboundedOptions, UnboundedReceiver,
+};
+use async_std::task;
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let (s, r) = channel_unbounded();
+
+        for i in 0..100 {
+            s.send(i).unwrap();
+        }
+        assert_eq!(s.len(), 100);
+        assert_eq!(r.usage(), ChannelUsage { len: 100, bytes: 0 });
+
+        for i in 0..100 {
+            assert_eq!(r.recv().await, Some(i));
+        }
+        assert!(r.is_empty());
+        assert!(future::timeout(ms(10), r.recv()).await.is_err());
+
+        drop(s);
+        assert_eq!(r.recv().await, None);
+    })
+}
+
+#[test]
+fn send_after_receivers_dropped() {
+    let (s, r) = channel_unbounded();
+    let r2 = r.clone();
+
+    drop(r);
+    assert_eq!(s.send(1), Ok(()));
+    assert!(!s.is_closed());
+
+    drop(r2);
+    assert!(s.is_closed());
+    assert_eq!(s.send(2), Err(SendError(2)));
+}
+
+#[test]
+fn wakes_receivers_and_streams() {
+    task::block_on(async {
+        let (s, r) = channel_unbounded();
+        let r2 = r.clone();
+
+        let recv = task::spawn(async move { r.recv().await });
+        let stream = task::spawn(async move {
+            let mut r2 = r2;
+            vec![r2.next().await.unwrap(), r2.next().await.unwrap()]
+        });
+        task::sleep(ms(10)).await;
+
+        s.send(1).unwrap();
+        s.send(2).unwrap();
+        s.send(3).unwrap();
+
+        let first = recv.await.unwrap();
+        let mut all = stream.await;
+        all.push(first);
+        all.sort();
+        assert_eq!(all, [1, 2, 3]);
+    })
+}
+
+#[test]
+fn byte_accounting() {
+    task::block_on(async {
+        let (s, r) = UnboundedOptions::new()
+            .message_size(|msg: &String| msg.len())
+            .channel();
+
+        s.send("hello".to_string()).unwrap();
+        s.send("world!".to_string()).unwrap();
+        assert_eq!(s.usage(), ChannelUsage { len: 2, bytes: 11 });
+
+        r.recv().await.unwrap();
+        assert_eq!(r.usage(), ChannelUsage { len: 1, bytes: 6 });
+        r.recv().await.unwrap();
+        assert_eq!(r.usage(), ChannelUsage::default());
+    })
+}
+
+#[test]
+fn high_water_callback_fires_once_per_crossing() {
+    task::block_on(async {
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let c = calls.clone();
+
+        let (s, r) = UnboundedOptions::new()
+            .message_size(|msg: &Vec<u8>| msg.len())
+            .len_high_water_mark(3)
+            .bytes_high_water_mark(100)
+            .on_high_water(move |usage| c.lock().unwrap().push(usage))
+            .channel();
+
+        s.send(vec![0; 1]).unwrap();
+        s.send(vec![0; 60]).unwrap();
+        assert!(calls.lock().unwrap().is_empty());
+        s.send(vec![0; 60]).unwrap();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            [ChannelUsage { len: 3, bytes: 121 }]
+        );
+
+        // Still above the byte mark, so the callback isn't armed again.
+        r.recv().await.unwrap();
+        s.send(vec![0; 1]).unwrap();
+        assert_eq!(calls.lock().unwrap().len(), 1);
+
+        // Drain below both marks, then cross the length mark.
+        r.recv().await.unwrap();
+        r.recv().await.unwrap();
+        s.send(vec![0; 1]).unwrap();
+        s.send(vec![0; 1]).unwrap();
+        assert_eq!(
+            calls.lock().unwrap().last(),
+            Some(&ChannelUsage { len: 3, bytes: 3 })
+        );
+        assert_eq!(calls.lock().unwrap().len(), 2);
+    })
+}
+
+#[test]
+fn callback_runs_outside_the_lock() {
+    let seen = Arc::new(AtomicUsize::new(0));
+    let slot = Arc::new(Mutex::new(None));
+
+    let (s, r) = {
+        let seen = seen.clone();
+        let slot = slot.clone();
+        UnboundedOptions::new()
+            .len_high_water_mark(2)
+            .on_high_water(move |_| {
+                // Inspecting the channel from the callback must not deadlock.
+                let guard = slot.lock().unwrap();
+                let r: &UnboundedReceiver<i32> = guard.as_ref().unwrap();
+                seen.store(r.len(), Ordering::SeqCst);
+            })
+            .channel()
+    };
+    *slot.lock().unwrap() = Some(r);
+
+    s.send(1).unwrap();
+    s.send(2).unwrap();
+    assert_eq!(seen.load(Ordering::SeqCst), 2);
+}
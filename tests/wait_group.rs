@@ -0,0 +1,69 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use async_std::future;
+use async_std::sync::{Arc, TaskTracker, WaitGroup};
+use async_std::task;
+
+#[test]
+fn wait_group_waits_for_all_clones() {
+    task::block_on(async {
+        let wg = WaitGroup::new();
+        let done = Arc::new(AtomicUsize::new(0));
+
+        for i in 0..5 {
+            let wg = wg.clone();
+            let done = done.clone();
+            task::spawn(async move {
+                task::sleep(Duration::from_millis(i * 5)).await;
+                done.fetch_add(1, Ordering::SeqCst);
+                drop(wg);
+            });
+        }
+
+        wg.wait().await;
+        assert_eq!(done.load(Ordering::SeqCst), 5);
+    });
+}
+
+#[test]
+fn wait_group_blocks_while_registered() {
+    task::block_on(async {
+        let wg = WaitGroup::new();
+        let other = wg.clone();
+        assert_eq!(wg.count(), 2);
+
+        let res = future::timeout(Duration::from_millis(10), wg.wait()).await;
+        assert!(res.is_err());
+        assert_eq!(other.count(), 1);
+    });
+}
+
+#[test]
+fn task_tracker_waits_for_spawned_tasks() {
+    task::block_on(async {
+        let tracker = TaskTracker::new();
+        tracker.wait().await;
+
+        let handles: Vec<_> = (0..5u64)
+            .map(|i| {
+                tracker.spawn(async move {
+                    task::sleep(Duration::from_millis(i * 5)).await;
+                    i
+                })
+            })
+            .collect();
+        assert_eq!(tracker.len(), 5);
+
+        tracker.wait().await;
+        assert!(tracker.is_empty());
+
+        let mut sum = 0;
+        for h in handles {
+            sum += h.await;
+        }
+        assert_eq!(sum, 10);
+    });
+}
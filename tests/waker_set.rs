@@ -0,0 +1,131 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Wake, Waker};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use async_std::sync::{AtomicWaker, WakerSet};
+
+struct Counter(AtomicUsize);
+
+impl Wake for Counter {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn counter() -> (Arc<Counter>, Waker) {
+    let counter = Arc::new(Counter(AtomicUsize::new(0)));
+    let waker = Waker::from(counter.clone());
+    (counter, waker)
+}
+
+fn woken(counter: &Counter) -> usize {
+    counter.0.load(Ordering::SeqCst)
+}
+
+#[test]
+fn notify_in_insertion_order() {
+    let set = WakerSet::new();
+    let (a, wa) = counter();
+    let (b, wb) = counter();
+    let (c, wc) = counter();
+    let (d, wd) = counter();
+
+    let ka = set.insert(&Context::from_waker(&wa));
+    let _kb = set.insert(&Context::from_waker(&wb));
+    let _kc = set.insert(&Context::from_waker(&wc));
+
+    // `d` reuses the key of `a`, but was inserted last.
+    set.remove(ka);
+    let kd = set.insert(&Context::from_waker(&wd));
+    assert_eq!(kd, ka);
+
+    assert!(set.notify_one());
+    assert_eq!([woken(&a), woken(&b), woken(&c), woken(&d)], [0, 1, 0, 0]);
+
+    assert!(set.notify_many(2));
+    assert_eq!([woken(&a), woken(&b), woken(&c), woken(&d)], [0, 1, 1, 1]);
+    assert!(!set.notify_all());
+}
+
+#[test]
+fn cancel_passes_notification_on() {
+    let set = WakerSet::new();
+    let (a, wa) = counter();
+    let (b, wb) = counter();
+
+    let ka = set.insert(&Context::from_waker(&wa));
+    let kb = set.insert(&Context::from_waker(&wb));
+
+    assert!(set.notify_any());
+    assert_eq!(woken(&a), 1);
+
+    // `a` was notified but gave up, so `b` is notified instead.
+    assert!(set.cancel(ka));
+    assert_eq!(woken(&b), 1);
+    assert!(!set.cancel(kb));
+}
+
+#[test]
+fn atomic_waker_wakes_registered() {
+    let slot = AtomicWaker::new();
+    let (a, wa) = counter();
+    let (b, wb) = counter();
+
+    slot.wake();
+    slot.register(&wa);
+    slot.register(&wb);
+    slot.wake();
+    assert_eq!((woken(&a), woken(&b)), (0, 1));
+
+    // The waker was unregistered.
+    slot.wake();
+    assert_eq!(woken(&b), 1);
+
+    slot.register(&wa);
+    assert!(slot.take().is_some());
+    assert!(slot.take().is_none());
+}
+
+#[test]
+fn atomic_waker_across_threads() {
+    let slot = Arc::new(AtomicWaker::new());
+    let flag = Arc::new(AtomicUsize::new(0));
+    let (c, w) = counter();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let slot = slot.clone();
+            let flag = flag.clone();
+            thread::spawn(move || {
+                for _ in 0..1000 {
+                    flag.fetch_add(1, Ordering::SeqCst);
+                    slot.wake();
+                }
+            })
+        })
+        .collect();
+
+    let mut seen = 0;
+    while seen < 4000 {
+        let before = woken(&c);
+        slot.register(&w);
+        let now = flag.load(Ordering::SeqCst);
+        if now == seen {
+            // Nothing happened since the last check, so the next increment must wake the waker.
+            let start = Instant::now();
+            while woken(&c) == before {
+                assert!(start.elapsed() < Duration::from_secs(10), "lost wakeup");
+                thread::yield_now();
+            }
+        }
+        seen = now;
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+#![cfg(target_arch = "wasm32")]
+
+use std::time::Duration;
+
+use async_std::sync::channel;
+use async_std::task;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+async fn spawn() {
+    let handle = task::spawn(async { 1 + 2 });
+    assert_eq!(handle.await, 3);
+}
+
+#[wasm_bindgen_test]
+async fn sleep() {
+    let (s, r) = channel(2);
+
+    task::spawn({
+        let s = s.clone();
+        async move {
+            task::sleep(Duration::from_millis(20)).await;
+            s.send("slow").await;
+        }
+    });
+    task::spawn(async move {
+        task::sleep(Duration::from_millis(5)).await;
+        s.send("fast").await;
+    });
+
+    assert_eq!(r.recv().await, Some("fast"));
+    assert_eq!(r.recv().await, Some("slow"));
+}
+
+#[wasm_bindgen_test]
+async fn channel_between_tasks() {
+    let (s, r) = channel(1);
+
+    let sender = task::spawn(async move {
+        for i in 0..10 {
+            s.send(i).await;
+        }
+    });
+
+    let mut received = Vec::new();
+    while let Some(i) = r.recv().await {
+        received.push(i);
+    }
+    sender.await;
+    assert_eq!(received, (0..10).collect::<Vec<_>>());
+}
+
+#[wasm_bindgen_test]
+async fn timeout() {
+    let slow = task::sleep(Duration::from_millis(50));
+    let res = async_std::future::timeout(Duration::from_millis(5), slow).await;
+    assert!(res.is_err());
+
+    let fast = async { 5 };
+    let res = async_std::future::timeout(Duration::from_millis(5), fast).await;
+    assert_eq!(res.ok(), Some(5));
+}
+
+#[wasm_bindgen_test]
+async fn recv_timeout() {
+    let (s, r) = channel::<i32>(1);
+    assert!(r.recv_timeout(Duration::from_millis(5)).await.is_err());
+
+    s.send(1).await;
+    assert_eq!(r.recv_timeout(Duration::from_millis(5)).await.ok(), Some(1));
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::prelude::*;
+use async_std::sync::watch;
+use async_std::task;
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let (s, mut r) = watch::channel(0);
+        assert!(!r.has_changed());
+
+        s.send(1).unwrap();
+        assert!(r.has_changed());
+        r.changed().await.unwrap();
+        assert_eq!(*r.borrow(), 1);
+
+        drop(s);
+        assert!(r.changed().await.is_err());
+        assert_eq!(*r.borrow(), 1);
+    });
+}
+
+#[test]
+fn receivers_see_latest_value() {
+    task::block_on(async {
+        let (s, r) = watch::channel(0);
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let mut r = r.clone();
+                task::spawn(async move {
+                    let mut last = 0;
+                    while let Some(v) = r.next().await {
+                        assert!(v >= last);
+                        last = v;
+                    }
+                    last
+                })
+            })
+            .collect();
+        drop(r);
+
+        for i in 1..=100 {
+            s.send(i).unwrap();
+            if i % 10 == 0 {
+                task::sleep(Duration::from_millis(1)).await;
+            }
+        }
+        drop(s);
+
+        for h in handles {
+            assert_eq!(h.await, 100);
+        }
+    });
+}